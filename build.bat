@@ -6,7 +6,7 @@ cargo build --target wasm32-unknown-unknown --release
 
 rem Generate JavaScript bindings with wasm-bindgen
 echo Generating JavaScript bindings...
-wasm-bindgen --target web --out-dir ./pkg/ --no-typescript ./target/wasm32-unknown-unknown/release/wasm_multiplayer.wasm
+wasm-bindgen --target web --out-dir ./pkg/ ./target/wasm32-unknown-unknown/release/wasm_multiplayer.wasm
 
 echo Done!
 echo -------------------------------------
@@ -5,6 +5,10 @@ const fs = require('fs'); // ファイル読み込み用
 const express = require('express');
 const app = express();
 
+// メッセージの型はRust側（src/protocol.rs）で定義し、ビルド時に生成される型定義を参照する
+/** @typedef {import('./pkg/wasm_multiplayer').ClientMessage} ClientMessage */
+/** @typedef {import('./pkg/wasm_multiplayer').ServerMessage} ServerMessage */
+
 // サーバーの設定
 // Replitでは環境変数PORTが設定されているため使用
 // ローカル環境では8080をデフォルトポートとして使用
@@ -30,21 +34,156 @@ try {
 const clients = new Map();
 let nextPlayerId = 1;
 
-// ゲーム状態
-let gameState = {
-  boardWidth: 16,
-  boardHeight: 16,
-  mineCount: 40,
-  cells: [],
-  revealed: [],
-  flagged: [],
-  gameStarted: false,
-  gameOver: false,
-  win: false
+// ページを再読み込みしたクライアントは接続URLの`resume`で前のIDを引き継げる
+const PLAYER_ID_PATTERN = /^player_\d+$/;
+
+// 全員が切断してからルームを片付けるまでの猶予（再読み込みで同じゲームに戻れるようにする）
+const RESUME_GRACE_MS = 10000;
+
+// ロビー終了後に行うカウントダウンの長さ（ミリ秒）
+const COUNTDOWN_MS = 3000;
+
+// 再戦投票（全員が投票するか、最初の投票から一定時間が経てばリセットする）
+const REMATCH_TIMEOUT_MS = 15000;
+
+// ルームを選ばずに接続したクライアントが参加するルーム（ルーム一覧には表示しない）
+const DEFAULT_ROOM = 'main';
+
+// `create_room`で作るルームコードの文字と長さ
+const ROOM_CODE_CHARS = 'ABCDEFGHJKLMNPQRSTUVWXYZ23456789';
+const ROOM_CODE_LENGTH = 6;
+
+// ルームコードごとのルーム（それぞれが独立したゲームを持つ）
+const rooms = new Map();
+
+// ルームを作成
+function createRoom(code) {
+  const room = {
+    code: code,
+    // ゲーム状態
+    gameState: {
+      boardWidth: 16,
+      boardHeight: 16,
+      mineCount: 40,
+      cells: [],
+      revealed: [],
+      flagged: [],
+      gameStarted: false,
+      gameOver: false,
+      win: false,
+      startTime: null,
+      countdownEndsAt: null,
+      // ボードの版数（ボードを変えるメッセージごとに増え、新しいボードは0から始まる）
+      revision: 0
+    },
+    // ロビーの状態（全員が準備完了するか、ホストが開始するまでゲームを始めない）
+    lobby: {
+      active: true,
+      hostId: null
+    },
+    rematch: {
+      votes: new Set(),
+      deadline: null,
+      timer: null
+    },
+    // 空になったルームを片付けるタイマー
+    emptyTimer: null
+  };
+  rooms.set(code, room);
+  initializeGame(room);
+  return room;
+}
+
+// ルームを取得（無ければ作成）
+function getOrCreateRoom(code) {
+  return rooms.get(code) || createRoom(code);
+}
+
+// 使われていないルームコードを作る
+function generateRoomCode() {
+  let code;
+  do {
+    code = '';
+    for (let i = 0; i < ROOM_CODE_LENGTH; i++) {
+      code += ROOM_CODE_CHARS[Math.floor(Math.random() * ROOM_CODE_CHARS.length)];
+    }
+  } while (rooms.has(code));
+  return code;
+}
+
+// ルームに参加しているクライアント（[ws, プレイヤー情報]の組）
+function roomClients(room) {
+  return [...clients.entries()].filter(([, data]) => data.room === room.code);
+}
+
+// クライアントが参加しているルーム
+function roomOf(ws) {
+  return rooms.get(clients.get(ws).room);
+}
+
+// `list_rooms`に返すルーム一覧（プレイヤーのいるルームのみ）
+function roomList() {
+  const list = [];
+  for (const room of rooms.values()) {
+    const players = roomClients(room).length;
+    if (room.code === DEFAULT_ROOM || players === 0) continue;
+    list.push({
+      code: room.code,
+      players: players,
+      boardWidth: room.gameState.boardWidth,
+      boardHeight: room.gameState.boardHeight,
+      mineCount: room.gameState.mineCount,
+      inLobby: room.lobby.active
+    });
+  }
+  return list;
+}
+
+// リーダーボード（難易度ごとのベストタイム、上位のみ保持）
+const LEADERBOARD_SIZE = 10;
+const leaderboard = {
+  easy: [],
+  medium: [],
+  hard: [],
+  custom: []
 };
 
+// 現在のボード設定に対応する難易度のキーを取得
+function difficultyKey(room) {
+  const { boardWidth, boardHeight, mineCount } = room.gameState;
+  if (boardWidth === 9 && boardHeight === 9 && mineCount === 10) return 'easy';
+  if (boardWidth === 16 && boardHeight === 16 && mineCount === 40) return 'medium';
+  if (boardWidth === 30 && boardHeight === 16 && mineCount === 99) return 'hard';
+  return 'custom';
+}
+
+// プレイヤー名の最大文字数
+const MAX_PLAYER_NAME_LENGTH = 16;
+
+// 選択できるアバター（クライアントの候補と同じ）
+const AVATARS = ['🐱', '🐶', '🦊', '🐼', '🐸', '🐧', '🦉', '🐙'];
+
+// ルームコードとして受け付ける形式（英数字・ハイフン・アンダースコアの1〜32文字）
+const ROOM_CODE_PATTERN = /^[A-Za-z0-9_-]{1,32}$/;
+
+// クリアタイムをリーダーボードに記録
+function recordLeaderboard(room, name) {
+  const { gameState } = room;
+  if (gameState.startTime === null) return;
+
+  const entries = leaderboard[difficultyKey(room)];
+  entries.push({
+    name: name,
+    timeMs: Date.now() - gameState.startTime
+  });
+  entries.sort((a, b) => a.timeMs - b.timeMs);
+  entries.length = Math.min(entries.length, LEADERBOARD_SIZE);
+}
+
 // ゲームの初期化
-function initializeGame() {
+function initializeGame(room) {
+  const { gameState, rematch } = room;
+
   // セルの状態を初期化
   gameState.cells = Array(gameState.boardWidth * gameState.boardHeight).fill(0);
   gameState.revealed = Array(gameState.boardWidth * gameState.boardHeight).fill(false);
@@ -52,15 +191,109 @@ function initializeGame() {
   gameState.gameStarted = false;
   gameState.gameOver = false;
   gameState.win = false;
+  gameState.startTime = null;
+  gameState.countdownEndsAt = null;
+  gameState.revision = 0;
+
+  // 再戦投票を取り消す
+  if (rematch.timer !== null) {
+    clearTimeout(rematch.timer);
+  }
+  rematch.votes.clear();
+  rematch.deadline = null;
+  rematch.timer = null;
+}
+
+// ゲームをリセットしてルームの全員に通知
+function resetGame(room) {
+  const { gameState } = room;
+  initializeGame(room);
+  broadcast(room, JSON.stringify({
+    type: 'game_reset',
+    boardWidth: gameState.boardWidth,
+    boardHeight: gameState.boardHeight,
+    mineCount: gameState.mineCount
+  }));
+}
+
+// 再戦投票の状況をルームの全員に送信
+function broadcastRematchState(room) {
+  const { rematch } = room;
+  broadcast(room, JSON.stringify({
+    type: 'rematch_state',
+    votes: Array.from(rematch.votes),
+    total: roomClients(room).length,
+    deadline: rematch.deadline,
+    serverTime: Date.now()
+  }));
+}
+
+// ルームの全員が投票していればリセット
+function resetIfAllVoted(room) {
+  const { rematch } = room;
+  if (rematch.votes.size === 0) return;
+
+  for (const [, data] of roomClients(room)) {
+    if (!rematch.votes.has(data.id)) return;
+  }
+  resetGame(room);
+}
+
+// カウントダウン中かどうか（この間はボードを操作できない）
+function isCountingDown(room) {
+  const { gameState } = room;
+  return gameState.countdownEndsAt !== null && Date.now() < gameState.countdownEndsAt;
+}
+
+// ロビーの状態をルームの全員に送信
+function broadcastLobbyState(room) {
+  const { lobby } = room;
+  const players = [];
+  for (const [, data] of roomClients(room)) {
+    players.push({ id: data.id, name: data.name, ready: data.ready });
+  }
+
+  broadcast(room, JSON.stringify({
+    type: 'lobby_state',
+    active: lobby.active,
+    hostId: lobby.hostId,
+    players: players
+  }));
+}
+
+// ロビーを終了し、カウントダウンの後にゲームを開始
+function startGameFromLobby(room) {
+  const { gameState, lobby } = room;
+  if (!lobby.active) return;
+
+  lobby.active = false;
+  gameState.countdownEndsAt = Date.now() + COUNTDOWN_MS;
+  broadcastLobbyState(room);
+  broadcast(room, JSON.stringify({
+    type: 'countdown',
+    endsAt: gameState.countdownEndsAt,
+    serverTime: Date.now()
+  }));
+}
+
+// ルームの全員が準備完了ならゲームを開始
+function startGameIfAllReady(room) {
+  const members = roomClients(room);
+  if (members.length === 0) return;
+
+  for (const [, data] of members) {
+    if (!data.ready) return;
+  }
+  startGameFromLobby(room);
 }
 
 // 地雷を配置
-function placeMines(firstClickIndex) {
+function placeMines(gameState, firstClickIndex) {
   const { boardWidth, boardHeight, mineCount } = gameState;
   const totalCells = boardWidth * boardHeight;
 
   // 最初にクリックしたセルとその周囲に地雷を配置しないようにする
-  const excludedCells = getNeighbors(firstClickIndex);
+  const excludedCells = getNeighbors(gameState, firstClickIndex);
   excludedCells.push(firstClickIndex);
 
   // 地雷をランダムに配置
@@ -74,7 +307,7 @@ function placeMines(firstClickIndex) {
       minesPlaced++;
 
       // 周囲のセルのカウントを増やす
-      const neighbors = getNeighbors(randomIndex);
+      const neighbors = getNeighbors(gameState, randomIndex);
       for (const neighbor of neighbors) {
         if (gameState.cells[neighbor] !== -1) {
           gameState.cells[neighbor]++;
@@ -85,7 +318,7 @@ function placeMines(firstClickIndex) {
 }
 
 // 指定されたインデックスの周囲のセルを取得
-function getNeighbors(index) {
+function getNeighbors(gameState, index) {
   const { boardWidth, boardHeight } = gameState;
   const x = index % boardWidth;
   const y = Math.floor(index / boardWidth);
@@ -109,7 +342,7 @@ function getNeighbors(index) {
 }
 
 // セルを開く
-function revealCell(index) {
+function revealCell(gameState, index) {
   const { boardWidth, boardHeight, cells, revealed, flagged, gameOver } = gameState;
 
   // 既に開かれている、フラグが立てられている、またはゲームオーバーの場合は何もしない
@@ -131,25 +364,25 @@ function revealCell(index) {
 
   // 数字が0の場合は周囲のセルも開く（再帰的に）
   if (cells[index] === 0) {
-    const neighbors = getNeighbors(index);
+    const neighbors = getNeighbors(gameState, index);
     for (const neighbor of neighbors) {
       // 既に開かれている場合はスキップ
       if (revealed[neighbor]) continue;
 
       // 再帰的に開く
-      const additionalRevealed = revealCell(neighbor);
+      const additionalRevealed = revealCell(gameState, neighbor);
       revealedCells.push(...additionalRevealed);
     }
   }
 
   // 勝利条件をチェック
-  checkWinCondition();
+  checkWinCondition(gameState);
 
   return revealedCells;
 }
 
 // 勝利条件をチェック
-function checkWinCondition() {
+function checkWinCondition(gameState) {
   const { boardWidth, boardHeight, cells, revealed, gameOver } = gameState;
 
   if (gameOver) return;
@@ -167,16 +400,22 @@ function checkWinCondition() {
 }
 
 // フラグを切り替え
-function toggleFlag(index) {
+function toggleFlag(gameState, index) {
   const { revealed, flagged, gameOver } = gameState;
 
   // 既に開かれている、またはゲームオーバーの場合は何もしない
   if (revealed[index] || gameOver) {
-    return;
+    return false;
   }
 
   // フラグを切り替え
   flagged[index] = !flagged[index];
+  return true;
+}
+
+// ボードのセルを指す整数のインデックスか
+function isCellIndex(gameState, index) {
+  return Number.isInteger(index) && index >= 0 && index < gameState.cells.length;
 }
 
 // ルートアクセス時にサーバー情報を表示
@@ -185,6 +424,7 @@ app.get('/', (req, res) => {
         <h1>Minesweeper WebSocket Server</h1>
         <p>This server is running on port ${PORT}</p>
         <p>Connected clients: ${clients.size}</p>
+        <p>Rooms: ${rooms.size}</p>
         <p>Server status: Active</p>
         <p>WebSocket URL: ws://${req.headers.host}</p>
     `);
@@ -215,46 +455,40 @@ server.listen(PORT, () => {
   console.log(`WebSocketサーバーを起動しました (ポート: ${PORT})`);
 });
 
-// ゲームを初期化
-initializeGame();
+// ルームを選ばないクライアントのためのルームを作成
+createRoom(DEFAULT_ROOM);
 
-// 定期的にピングを送信して接続を維持
+// 定期的にピングを送信して接続を維持し、応答までの時間を遅延として測る
 setInterval(() => {
-  for (const client of clients.keys()) {
+  for (const [client, data] of clients.entries()) {
     if (client.readyState === WebSocket.OPEN) {
+      data.pingSentAt = Date.now();
       client.ping();
     }
   }
-}, 30000);
+}, 5000);
 
-// 接続イベントハンドラーを関数として抽出
-function handleConnection(ws) {
-  // 新しいクライアントにIDを付与
-  const playerId = `player_${nextPlayerId++}`;
-  console.log(`新しいプレイヤーが接続しました: ${playerId}`);
-
-  // クライアントをマップに保存
-  clients.set(ws, {
-    id: playerId,
-    x: 0,
-    y: 0,
-    color: generateRandomColor()
-  });
-
-  // 接続しているプレイヤー情報を新規クライアントに送信
+// 指定したクライアント以外の同じルームのプレイヤー情報
+function otherPlayers(ws) {
   const playerList = [];
-  for (const [client, data] of clients.entries()) {
+  for (const [client, data] of roomClients(roomOf(ws))) {
     if (client !== ws) {
       playerList.push({
         id: data.id,
+        name: data.name,
         x: data.x,
         y: data.y,
-        color: data.color
+        color: data.color,
+        avatar: data.avatar
       });
     }
   }
+  return playerList;
+}
 
-  // 既に開かれているセルの値を収集
+// クライアントに送るゲーム状態（開かれているセルの値のみ含める）
+function gameStateSnapshot(room) {
+  const { gameState } = room;
   const cellValues = {};
   for (let i = 0; i < gameState.cells.length; i++) {
     if (gameState.revealed[i]) {
@@ -262,39 +496,221 @@ function handleConnection(ws) {
     }
   }
 
-  // 初期化メッセージを送信
-  ws.send(JSON.stringify({
-    type: 'init',
-    playerId: playerId,
-    players: playerList,
-    gameState: {
-      boardWidth: gameState.boardWidth,
-      boardHeight: gameState.boardHeight,
-      mineCount: gameState.mineCount,
-      revealed: gameState.revealed,
-      flagged: gameState.flagged,
-      gameStarted: gameState.gameStarted,
-      gameOver: gameState.gameOver,
-      win: gameState.win,
-      cellValues: cellValues
+  return {
+    boardWidth: gameState.boardWidth,
+    boardHeight: gameState.boardHeight,
+    mineCount: gameState.mineCount,
+    revealed: gameState.revealed,
+    flagged: gameState.flagged,
+    gameStarted: gameState.gameStarted,
+    gameOver: gameState.gameOver,
+    win: gameState.win,
+    cellValues: cellValues,
+    countdownEndsAt: gameState.countdownEndsAt,
+    serverTime: Date.now(),
+    revision: gameState.revision
+  };
+}
+
+// ボードの更新のバイナリ形式（src/binary_protocol.rsと同じ形式）
+const BINARY_FORMAT_VERSION = 2;
+
+// 整数をLEB128の可変長で書き込む
+function pushVarint(bytes, value) {
+  while (value >= 0x80) {
+    bytes.push((value & 0x7f) | 0x80);
+    value = Math.floor(value / 128);
+  }
+  bytes.push(value);
+}
+
+// cells_revealedをバイナリ形式に変換（インデックスは1つ前のセルからの差をジグザグ符号化して書く）
+function encodeCellsRevealed(playerId, revision, cells) {
+  const bytes = [BINARY_FORMAT_VERSION, 1];
+  const id = Buffer.from(playerId, 'utf8');
+  pushVarint(bytes, id.length);
+  bytes.push(...id);
+  pushVarint(bytes, revision);
+  pushVarint(bytes, cells.length);
+  let previous = 0;
+  for (const [index, value] of cells) {
+    const delta = index - previous;
+    pushVarint(bytes, delta >= 0 ? delta * 2 : -delta * 2 - 1);
+    bytes.push(value & 0xff);
+    previous = index;
+  }
+  return Buffer.from(bytes);
+}
+
+// game_overをバイナリ形式に変換（全てのセルの値はcellsと同じため省略する）
+function encodeGameOver(win, cells) {
+  const bytes = [BINARY_FORMAT_VERSION, 2, win ? 1 : 0];
+  pushVarint(bytes, cells.length);
+  for (const value of cells) {
+    bytes.push(value & 0xff);
+  }
+  bytes.push(1);
+  return Buffer.from(bytes);
+}
+
+// ボードの更新をルームの全員に送信（バイナリ形式を選んだクライアントにはバイナリで送る）
+function broadcastBoardUpdate(room, message, binary) {
+  const text = JSON.stringify(message);
+  for (const [client, info] of roomClients(room)) {
+    if (client.readyState === WebSocket.OPEN) {
+      client.send(info.binary ? binary : text);
     }
-  }));
+  }
+}
+
+// クライアントをルームに参加させる
+// welcomeで作ったメッセージ（ルームの状態）を本人に送ってから、ルームの他のプレイヤーに参加を通知する
+function enterRoom(ws, room, welcome) {
+  // 空になったルームの片付けを取り消す
+  if (room.emptyTimer !== null) {
+    clearTimeout(room.emptyTimer);
+    room.emptyTimer = null;
+  }
 
-  console.log(`プレイヤー ${playerId} に初期化データを送信しました。開かれたセル数: ${Object.keys(cellValues).length}`);
+  const playerData = clients.get(ws);
+  playerData.room = room.code;
+  playerData.ready = false;
+  ws.send(JSON.stringify(welcome()));
 
-  // 他のプレイヤーに新規参加を通知
-  const joinMessage = JSON.stringify({
+  // ルームの他のプレイヤーに新規参加を通知
+  broadcastExcept(ws, JSON.stringify({
     type: 'player_joined',
+    id: playerData.id,
+    color: playerData.color
+  }));
+
+  // 最初に参加したプレイヤーがホスト
+  if (room.lobby.hostId === null) {
+    room.lobby.hostId = playerData.id;
+  }
+  broadcastLobbyState(room);
+}
+
+// クライアントをルームから外し、残ったプレイヤーに通知する
+function leaveRoom(ws) {
+  const playerData = clients.get(ws);
+  const room = roomOf(ws);
+  const { lobby, rematch } = room;
+
+  broadcastExcept(ws, JSON.stringify({
+    type: 'player_left',
+    id: playerData.id
+  }));
+  playerData.room = null;
+  const remaining = roomClients(room);
+
+  // ホストが抜けたら次のプレイヤーに引き継ぐ
+  if (lobby.hostId === playerData.id) {
+    lobby.hostId = remaining.length > 0 ? remaining[0][1].id : null;
+  }
+
+  // プレイヤーがいなくなったら、再読み込みの猶予の後に片付ける
+  // （既定のルームはリセットしてロビーに戻し、それ以外のルームは削除する）
+  if (remaining.length === 0) {
+    room.emptyTimer = setTimeout(() => {
+      room.emptyTimer = null;
+      if (room.code === DEFAULT_ROOM) {
+        initializeGame(room);
+        lobby.active = true;
+      } else {
+        rooms.delete(room.code);
+      }
+    }, RESUME_GRACE_MS);
+  } else if (lobby.active) {
+    broadcastLobbyState(room);
+    startGameIfAllReady(room);
+  } else if (rematch.votes.size > 0) {
+    // 抜けたプレイヤーの投票を除き、残り全員が投票済みならリセット
+    rematch.votes.delete(playerData.id);
+    broadcastRematchState(room);
+    resetIfAllVoted(room);
+  }
+}
+
+// 別のルームに移る（無ければ作成する）
+function switchRoom(ws, code) {
+  const roomJoined = JSON.stringify({
+    type: 'room_joined',
+    room: code
+  });
+  if (clients.get(ws).room === code) {
+    ws.send(roomJoined);
+    return;
+  }
+
+  leaveRoom(ws);
+  const room = getOrCreateRoom(code);
+  ws.send(roomJoined);
+  enterRoom(ws, room, () => ({
+    type: 'state_sync',
+    players: otherPlayers(ws),
+    gameState: gameStateSnapshot(room)
+  }));
+  console.log(`プレイヤー ${clients.get(ws).id} がルーム ${code} に移りました`);
+}
+
+// IDが接続中のプレイヤーに使われているか
+function isPlayerIdInUse(id) {
+  return [...clients.values()].some((client) => client.id === id);
+}
+
+// 新しいプレイヤーIDを割り当てる（引き継ぎで使われているIDは飛ばす）
+function allocatePlayerId() {
+  let id = `player_${nextPlayerId++}`;
+  while (isPlayerIdInUse(id)) {
+    id = `player_${nextPlayerId++}`;
+  }
+  return id;
+}
+
+// 接続イベントハンドラーを関数として抽出
+function handleConnection(ws, req) {
+  // 新しいクライアントにIDを付与（接続中のプレイヤーと重ならなければ前のIDを引き継ぐ）
+  const params = new URL(req.url, 'http://localhost').searchParams;
+  const resumeId = params.get('resume');
+  const resumable = resumeId !== null && PLAYER_ID_PATTERN.test(resumeId) && !isPlayerIdInUse(resumeId);
+  if (resumable) {
+    // 引き継いだIDを後から新しいプレイヤーに割り当てないよう、次のIDをその先に進める
+    nextPlayerId = Math.max(nextPlayerId, Number(resumeId.slice('player_'.length)) + 1);
+  }
+  const playerId = resumable ? resumeId : allocatePlayerId();
+  console.log(`新しいプレイヤーが接続しました: ${playerId}${resumable ? '（再接続）' : ''}`);
+
+  // クライアントをマップに保存（ルームは参加時に設定する）
+  clients.set(ws, {
     id: playerId,
-    color: clients.get(ws).color
+    name: '',
+    ready: false,
+    room: null,
+    binary: params.get('binary') === '1',
+    x: 0,
+    y: 0,
+    color: generateRandomColor(),
+    avatar: ''
   });
 
-  broadcastExcept(ws, joinMessage);
+  // 既定のルームに参加して初期化メッセージを送信
+  const defaultRoom = rooms.get(DEFAULT_ROOM);
+  enterRoom(ws, defaultRoom, () => ({
+    type: 'init',
+    playerId: playerId,
+    players: otherPlayers(ws),
+    gameState: gameStateSnapshot(defaultRoom)
+  }));
+
+  console.log(`プレイヤー ${playerId} に初期化データを送信しました`);
 
   // メッセージ受信イベント
   ws.on('message', (message) => {
     try {
       const data = JSON.parse(message);
+      const room = roomOf(ws);
+      const { gameState, lobby, rematch } = room;
 
       // メッセージのタイプによって処理を分ける
       switch (data.type) {
@@ -305,114 +721,264 @@ function handleConnection(ws) {
             playerData.x = data.x;
             playerData.y = data.y;
 
-            // 他のクライアントに移動を通知
-            for (const client of clients.keys()) {
-              if (client !== ws && client.readyState === WebSocket.OPEN) {
-                client.send(JSON.stringify({
-                  type: 'player_moved',
-                  id: playerData.id,
-                  x: data.x,
-                  y: data.y
-                }));
-              }
-            }
+            // ルームの他のクライアントに移動を通知
+            broadcastExcept(ws, JSON.stringify({
+              type: 'player_moved',
+              id: playerData.id,
+              x: data.x,
+              y: data.y
+            }));
           }
           break;
 
         case 'reveal_cell':
-          // セルを開く
-          if (data.index !== undefined) {
+          // セルを開く（ロビー中・カウントダウン中・ゲームオーバー後は受け付けない）
+          if (isCellIndex(gameState, data.index) && !lobby.active && !isCountingDown(room) && !gameState.gameOver) {
             const index = data.index;
+            const wasOver = gameState.gameOver;
 
             // ゲームが開始されていない場合は、最初のクリックで開始
             if (!gameState.gameStarted) {
               gameState.gameStarted = true;
-              placeMines(index);
+              gameState.startTime = Date.now();
+              placeMines(gameState, index);
             }
 
-            // セルを開く
-            const revealedCells = revealCell(index);
-
-            // 開かれたセルの値をマップ
-            const cellValues = {};
-            for (const cellIndex of revealedCells) {
-              cellValues[cellIndex] = gameState.cells[cellIndex];
+            // セルを開く（既に開かれている・フラグが立っている場合はボードが変わらないので通知しない）
+            const revealedCells = revealCell(gameState, index);
+            if (revealedCells.length === 0) {
+              break;
             }
 
-            // すべてのクライアントに通知
-            const updateMessage = JSON.stringify({
+            // 開かれたセルを(インデックス, 値)の組の差分にして、ルームのすべてのクライアントに通知
+            const cells = revealedCells.map((cellIndex) => [cellIndex, gameState.cells[cellIndex]]);
+            gameState.revision += 1;
+            const updateMessage = {
               type: 'cells_revealed',
-              cells: revealedCells,
-              values: cellValues
-            });
-
-            for (const client of clients.keys()) {
-              if (client.readyState === WebSocket.OPEN) {
-                client.send(updateMessage);
+              playerId: playerId,
+              revision: gameState.revision,
+              cells: cells
+            };
+            broadcastBoardUpdate(room, updateMessage, encodeCellsRevealed(playerId, gameState.revision, cells));
+
+            // このセルでゲームオーバーになった場合のみ通知
+            if (!wasOver && gameState.gameOver) {
+              // 勝利した場合は最後のセルを開いたプレイヤーでタイムを記録
+              if (gameState.win) {
+                recordLeaderboard(room, clients.get(ws).name || clients.get(ws).id);
               }
-            }
 
-            // ゲームオーバーの場合は通知
-            if (gameState.gameOver) {
               // ゲームオーバー時は全てのセル情報を送信
               const allCellValues = {};
               for (let i = 0; i < gameState.cells.length; i++) {
                 allCellValues[i] = gameState.cells[i];
               }
 
-              const gameOverMessage = JSON.stringify({
+              const gameOverMessage = {
                 type: 'game_over',
                 win: gameState.win,
                 cells: gameState.cells,
                 allCellValues: allCellValues
-              });
-
-              for (const client of clients.keys()) {
-                if (client.readyState === WebSocket.OPEN) {
-                  client.send(gameOverMessage);
-                }
-              }
+              };
+              broadcastBoardUpdate(room, gameOverMessage, encodeGameOver(gameState.win, gameState.cells));
             }
           }
           break;
 
         case 'toggle_flag':
-          // フラグを切り替え
-          if (data.index !== undefined) {
+          // フラグを切り替え（ロビー中・カウントダウン中は受け付けない）
+          if (isCellIndex(gameState, data.index) && !lobby.active && !isCountingDown(room)) {
             const index = data.index;
-            toggleFlag(index);
+            if (!toggleFlag(gameState, index)) {
+              break;
+            }
 
-            // すべてのクライアントに通知
-            const flagMessage = JSON.stringify({
+            // ルームのすべてのクライアントに通知
+            gameState.revision += 1;
+            broadcast(room, JSON.stringify({
               type: 'flag_toggled',
+              playerId: playerId,
+              revision: gameState.revision,
               index: index,
               flagged: gameState.flagged[index]
-            });
+            }));
+          }
+          break;
 
-            for (const client of clients.keys()) {
-              if (client.readyState === WebSocket.OPEN) {
-                client.send(flagMessage);
-              }
+        case 'set_board_config':
+          // ボード設定を変更（ロビー中のみ、カウントダウン中やゲーム開始後は受け付けない）
+          if (gameState.gameStarted || !lobby.active || isCountingDown(room)) {
+            console.log('ロビーの外のためボード設定の変更を無視しました');
+            break;
+          }
+
+          {
+            const width = Number(data.boardWidth);
+            const height = Number(data.boardHeight);
+            const mines = Number(data.mineCount);
+            if (!Number.isInteger(width) || !Number.isInteger(height) || !Number.isInteger(mines)) {
+              break;
             }
+
+            // 初手で開ける領域を確保できる範囲に制限
+            gameState.boardWidth = Math.min(Math.max(width, 5), 50);
+            gameState.boardHeight = Math.min(Math.max(height, 5), 50);
+            gameState.mineCount = Math.min(Math.max(mines, 1), gameState.boardWidth * gameState.boardHeight - 9);
+            initializeGame(room);
+
+            console.log(`ボード設定を変更: ${gameState.boardWidth}x${gameState.boardHeight} 地雷${gameState.mineCount}個`);
+
+            // ルームのすべてのクライアントに通知
+            broadcast(room, JSON.stringify({
+              type: 'game_reset',
+              boardWidth: gameState.boardWidth,
+              boardHeight: gameState.boardHeight,
+              mineCount: gameState.mineCount
+            }));
           }
           break;
 
+        case 'join':
+          // プレイヤー名を登録して全員に通知
+          if (typeof data.name === 'string') {
+            const playerData = clients.get(ws);
+            playerData.name = data.name.trim().slice(0, MAX_PLAYER_NAME_LENGTH);
+
+            const renameMessage = JSON.stringify({
+              type: 'player_renamed',
+              id: playerData.id,
+              name: playerData.name
+            });
+            broadcastExcept(ws, renameMessage);
+            broadcastLobbyState(room);
+          }
+
+          // 選んだカーソルの色を登録して全員に通知（`#RRGGBB`形式のみ受け付ける）
+          if (typeof data.color === 'string' && /^#[0-9a-fA-F]{6}$/.test(data.color)) {
+            const playerData = clients.get(ws);
+            playerData.color = data.color;
+
+            const recolorMessage = JSON.stringify({
+              type: 'player_recolored',
+              id: playerData.id,
+              color: playerData.color
+            });
+            broadcastExcept(ws, recolorMessage);
+          }
+
+          // 選んだアバターを登録して全員に通知（候補に無いものは無視する）
+          if (typeof data.avatar === 'string' && AVATARS.includes(data.avatar)) {
+            const playerData = clients.get(ws);
+            playerData.avatar = data.avatar;
+
+            broadcastExcept(ws, JSON.stringify({
+              type: 'player_avatar',
+              id: playerData.id,
+              avatar: playerData.avatar
+            }));
+          }
+          break;
+
+        case 'set_ready':
+          // ロビーでの準備完了の切り替え
+          if (lobby.active && typeof data.ready === 'boolean') {
+            clients.get(ws).ready = data.ready;
+            broadcastLobbyState(room);
+            startGameIfAllReady(room);
+          }
+          break;
+
+        case 'start_game':
+          // ホストは全員の準備を待たずに開始できる
+          if (lobby.active && clients.get(ws).id === lobby.hostId) {
+            startGameFromLobby(room);
+          }
+          break;
+
+        case 'emote':
+          // エモートを他のプレイヤーに中継
+          if (typeof data.emote === 'string') {
+            const emoteMessage = JSON.stringify({
+              type: 'emote',
+              id: clients.get(ws).id,
+              emote: data.emote
+            });
+            broadcastExcept(ws, emoteMessage);
+          }
+          break;
+
+        case 'join_room':
+          // 招待リンクやルーム一覧で選ばれたルームに移る（無ければ作成する）
+          if (typeof data.room === 'string' && ROOM_CODE_PATTERN.test(data.room)) {
+            switchRoom(ws, data.room);
+          }
+          break;
+
+        case 'create_room':
+          // 新しいコードのルームを作成して移る
+          switchRoom(ws, generateRoomCode());
+          break;
+
+        case 'list_rooms':
+          // 参加できるルームの一覧を要求したクライアントにのみ返す
+          ws.send(JSON.stringify({
+            type: 'room_list',
+            rooms: roomList()
+          }));
+          break;
+
+        case 'sync_request':
+          // バックグラウンドから戻ったクライアントに現在の状態を送り直す
+          ws.send(JSON.stringify({
+            type: 'state_sync',
+            players: otherPlayers(ws),
+            gameState: gameStateSnapshot(room)
+          }));
+          break;
+
+        case 'heartbeat':
+          // 遅延の計測用に送信時刻をそのまま送り返す（送信者にのみ返す）
+          if (typeof data.sentAt === 'number' && Number.isFinite(data.sentAt)) {
+            ws.send(JSON.stringify({
+              type: 'heartbeat_ack',
+              sentAt: data.sentAt
+            }));
+          }
+          break;
+
+        case 'leaderboard_request':
+          // リーダーボードを要求したクライアントにのみ返す
+          ws.send(JSON.stringify({
+            type: 'leaderboard',
+            entries: leaderboard
+          }));
+          break;
+
         case 'reset_game':
-          // ゲームをリセット
-          initializeGame();
-
-          // すべてのクライアントに通知
-          const resetMessage = JSON.stringify({
-            type: 'game_reset',
-            boardWidth: gameState.boardWidth,
-            boardHeight: gameState.boardHeight,
-            mineCount: gameState.mineCount
-          });
-
-          for (const client of clients.keys()) {
-            if (client.readyState === WebSocket.OPEN) {
-              client.send(resetMessage);
+          // ゲームをリセットしてすべてのクライアントに通知
+          resetGame(room);
+          break;
+
+        case 'leave':
+          // ページを離れる前の退出はすぐに反映し、接続を閉じる
+          leaveRoom(ws);
+          clients.delete(ws);
+          ws.close(1000, 'left');
+          break;
+
+        case 'rematch_vote':
+          // 終了したゲームでのみ再戦投票を受け付ける
+          if (gameState.gameOver) {
+            rematch.votes.add(clients.get(ws).id);
+
+            // 最初の投票から時間切れまでのタイマーを開始
+            if (rematch.timer === null) {
+              rematch.deadline = Date.now() + REMATCH_TIMEOUT_MS;
+              rematch.timer = setTimeout(() => resetGame(room), REMATCH_TIMEOUT_MS);
             }
+
+            broadcastRematchState(room);
+            resetIfAllVoted(room);
           }
           break;
       }
@@ -422,25 +988,27 @@ function handleConnection(ws) {
   });
 
   // 切断イベント
+  ws.on('pong', () => {
+    // 測った遅延をルームの全員に通知（プロフィールカードに表示する）
+    const playerData = clients.get(ws);
+    if (playerData && playerData.pingSentAt && playerData.room !== null) {
+      playerData.ping = Date.now() - playerData.pingSentAt;
+      broadcast(roomOf(ws), JSON.stringify({
+        type: 'player_ping',
+        id: playerData.id,
+        ping: playerData.ping
+      }));
+    }
+  });
+
   ws.on('close', () => {
     const clientInfo = clients.get(ws);
     if (clientInfo) {
       console.log(`プレイヤーが切断しました: ${clientInfo.id}`);
 
-      // 切断したプレイヤーを全員に通知
-      const leaveMessage = JSON.stringify({
-        type: 'player_left',
-        id: clientInfo.id
-      });
-      broadcastExcept(ws, leaveMessage);
-
-      // クライアントマップから削除
+      // ルームから外して残ったプレイヤーに通知し、クライアントマップから削除
+      leaveRoom(ws);
       clients.delete(ws);
-
-      // プレイヤーがいなくなったらゲームをリセット
-      if (clients.size === 0) {
-        initializeGame();
-      }
     }
   });
 }
@@ -448,18 +1016,18 @@ function handleConnection(ws) {
 // 通常の接続イベント処理を関数に置き換え
 wss.on('connection', handleConnection);
 
-// 全クライアントにメッセージを送信
-function broadcast(message) {
-  for (const client of clients.keys()) {
+// ルームの全クライアントにメッセージを送信
+function broadcast(room, message) {
+  for (const [client] of roomClients(room)) {
     if (client.readyState === WebSocket.OPEN) {
       client.send(message);
     }
   }
 }
 
-// 特定のクライアントを除いて全員にメッセージ送信
+// 特定のクライアントを除いて同じルームの全員にメッセージ送信
 function broadcastExcept(excludeWs, message) {
-  for (const client of clients.keys()) {
+  for (const [client] of roomClients(roomOf(excludeWs))) {
     if (client !== excludeWs && client.readyState === WebSocket.OPEN) {
       client.send(message);
     }
@@ -471,5 +1039,18 @@ function generateRandomColor() {
   const h = Math.floor(Math.random() * 360);
   const s = 70 + Math.floor(Math.random() * 30); // 70-100%
   const l = 50 + Math.floor(Math.random() * 10); // 50-60%
-  return `hsl(${h}, ${s}%, ${l}%)`;
+  return hslToHex(h, s, l);
+}
+
+// HSLの色をクライアントが読める`#RRGGBB`形式に変換
+function hslToHex(h, s, l) {
+  const saturation = s / 100;
+  const lightness = l / 100;
+  const a = saturation * Math.min(lightness, 1 - lightness);
+  const channel = (n) => {
+    const k = (n + h / 30) % 12;
+    const value = lightness - a * Math.max(-1, Math.min(k - 3, 9 - k, 1));
+    return Math.round(value * 255).toString(16).padStart(2, '0');
+  };
+  return `#${channel(0)}${channel(8)}${channel(4)}`.toUpperCase();
 }
@@ -0,0 +1,329 @@
+/**
+ * ボードの更新を小さく送るためのバイナリ形式
+ * 
+ * 大きなボードでは`cells_revealed`と`game_over`のセルの値のJSONが大きくなるため、
+ * 接続時に`binary=1`を指定したクライアントにはサーバーがこの形式のバイナリフレームで送ります。
+ * それ以外のメッセージは従来どおりJSONのテキストフレームで届き、受信側はフレームの種類で判別します。
+ * 
+ * 整数はLEB128の可変長（7ビットずつ、下位から）で表し、セルの値は1バイト（地雷は-1）で表します。
+ * `cells_revealed`のインデックスは連鎖で隣り合うセルが続くため、1つ前のセル（最初は0）からの差を
+ * ジグザグ符号化（0, -1, 1, -2, …を0, 1, 2, 3, …にする）して書きます。
+ * `game_over`の全てのセルの値は`cells`と同じ内容であれば省略します。
+ * 
+ * ```text
+ * [0]    形式のバージョン（2）
+ * [1]    メッセージの種類（1: cells_revealed, 2: game_over）
+ * cells_revealed: プレイヤーIDの長さ, プレイヤーID(UTF-8), ボードの版数, セル数, (インデックスの差, i8 値) × セル数
+ * game_over:      u8 勝利したかどうか, セル数, i8 値 × セル数, u8 値の組を省略したかどうか, 値の組 ※省略しない場合のみ
+ * 値の組:         組の数, (インデックス, i8 値) × 組の数
+ * ```
+ */
+use std::collections::HashMap;
+use crate::error::{GameError, GameResult};
+use crate::protocol::ServerMessage;
+
+/// バイナリ形式のバージョン
+pub const BINARY_FORMAT_VERSION: u8 = 2;
+
+/// `cells_revealed`を表す種類の値
+const KIND_CELLS_REVEALED: u8 = 1;
+
+/// `game_over`を表す種類の値
+const KIND_GAME_OVER: u8 = 2;
+
+/**
+ * メッセージをバイナリ形式に変換する
+ * 
+ * バイナリ形式はボードの更新にのみ使うため、それ以外のメッセージや
+ * 1バイトに収まらないセルの値・大きすぎるインデックス・10進の正規の表記でないインデックスを含む場合はJSONで送ります。
+ * 
+ * @param message 変換するメッセージ
+ * @return バイナリ形式のバイト列（JSONで送るべき場合はNone）
+ */
+pub fn encode_server_message(message: &ServerMessage) -> Option<Vec<u8>> {
+    let mut bytes = vec![BINARY_FORMAT_VERSION];
+    match message {
+        ServerMessage::CellsRevealed { player_id, revision, cells } => {
+            bytes.push(KIND_CELLS_REVEALED);
+            write_varint(&mut bytes, player_id.len())?;
+            bytes.extend_from_slice(player_id.as_bytes());
+            write_varint(&mut bytes, *revision as usize)?;
+            
+            // 開いた順を保つため、並べ替えずに1つ前のセルからの差を書く
+            write_varint(&mut bytes, cells.len())?;
+            let mut previous: i64 = 0;
+            for &(index, value) in cells {
+                let index = i64::from(u32::try_from(index).ok()?);
+                let delta = index - previous;
+                write_varint(&mut bytes, usize::try_from((delta << 1) ^ (delta >> 63)).ok()?)?;
+                bytes.push(cell_value(value)? as u8);
+                previous = index;
+            }
+        },
+        ServerMessage::GameOver { win, cells, all_cell_values } => {
+            bytes.push(KIND_GAME_OVER);
+            bytes.push(u8::from(*win));
+            write_varint(&mut bytes, cells.len())?;
+            for &value in cells {
+                bytes.push(cell_value(value)? as u8);
+            }
+            
+            // 全てのセルの値が`cells`と同じであれば省略する（サーバーは常に同じ内容を送る）
+            let values = cell_value_entries(all_cell_values)?;
+            let same_as_cells = values.len() == cells.len() &&
+                values.iter().all(|(&index, &value)| cells.get(index).map(|&cell| cell as i8) == Some(value));
+            bytes.push(u8::from(same_as_cells));
+            if !same_as_cells {
+                write_cell_values(&mut bytes, values.into_iter().collect())?;
+            }
+        },
+        _ => return None,
+    }
+    Some(bytes)
+}
+
+/**
+ * バイナリフレームをメッセージに変換する
+ * 
+ * 長さが足りない・余分なバイトがある・未知の種類などの不正なフレームは拒否します。
+ * 
+ * @param bytes 受信したバイナリフレーム
+ * @return 変換したメッセージ（不正な場合はErr(GameError::Network)）
+ */
+pub fn decode_server_message(bytes: &[u8]) -> GameResult<ServerMessage> {
+    let mut reader = Reader { bytes, position: 0 };
+    let version = reader.u8()?;
+    if version != BINARY_FORMAT_VERSION {
+        return Err(invalid(&format!("unsupported version {}", version)));
+    }
+
+    let message = match reader.u8()? {
+        KIND_CELLS_REVEALED => {
+            let id_length = reader.count(1)?;
+            let player_id = std::str::from_utf8(reader.take(id_length)?)
+                .map_err(|_| invalid("player id is not UTF-8"))?
+                .to_string();
+            let revision = reader.varint()? as u32;
+            let count = reader.count(2)?;
+            let mut cells = Vec::with_capacity(count);
+            let mut previous: i64 = 0;
+            for _ in 0..count {
+                let zigzag = reader.varint()? as i64;
+                let index = previous + ((zigzag >> 1) ^ -(zigzag & 1));
+                let index = u32::try_from(index).map_err(|_| invalid("cell index out of range"))?;
+                cells.push((index as usize, i32::from(reader.i8()?)));
+                previous = i64::from(index);
+            }
+            ServerMessage::CellsRevealed { player_id, revision, cells }
+        },
+        KIND_GAME_OVER => {
+            let win = reader.flag()?;
+            let count = reader.count(1)?;
+            let cells: Vec<i32> = (0..count).map(|_| reader.i8().map(i32::from)).collect::<GameResult<_>>()?;
+            let all_cell_values = if reader.flag()? {
+                cells.iter().enumerate().map(|(index, &value)| (index.to_string(), value)).collect()
+            } else {
+                reader.cell_values()?
+            };
+            ServerMessage::GameOver { win, cells, all_cell_values }
+        },
+        kind => return Err(invalid(&format!("unknown message kind {}", kind))),
+    };
+
+    if reader.position != bytes.len() {
+        return Err(invalid("trailing bytes"));
+    }
+    Ok(message)
+}
+
+/**
+ * 1バイトに収まるセルの値を取得する
+ * 
+ * @param value セルの値
+ * @return 1バイトの値（収まらない値はNone）
+ */
+fn cell_value(value: i32) -> Option<i8> {
+    i8::try_from(value).ok()
+}
+
+/**
+ * インデックスの文字列ごとのセルの値を数値のインデックスに変換する
+ * 
+ * @param values セルのインデックスの文字列と値の組
+ * @return インデックスごとの値（10進の正規の表記でないインデックスや1バイトに収まらない値を含む場合はNone）
+ */
+fn cell_value_entries(values: &HashMap<String, i32>) -> Option<HashMap<usize, i8>> {
+    values.iter()
+        .map(|(key, &value)| {
+            // "007"などは数値に戻すと別のキーになるため、正規の表記のみ受け付ける
+            let index = key.parse::<usize>().ok().filter(|index| index.to_string() == *key)?;
+            Some((index, cell_value(value)?))
+        })
+        .collect()
+}
+
+/**
+ * 値の組を書き込む
+ * 
+ * @param bytes 書き込み先
+ * @param entries インデックスと値の組
+ * @return 書き込めた場合はSome(())（インデックスが大きすぎる場合はNone）
+ */
+fn write_cell_values(bytes: &mut Vec<u8>, mut entries: Vec<(usize, i8)>) -> Option<()> {
+    entries.sort_unstable();
+    write_varint(bytes, entries.len())?;
+    for (index, value) in entries {
+        write_varint(bytes, index)?;
+        bytes.push(value as u8);
+    }
+    Some(())
+}
+
+/**
+ * 整数を可変長で書き込む
+ * 
+ * @param bytes 書き込み先
+ * @param value 書き込む値
+ * @return 書き込めた場合はSome(())（u32に収まらない値はNone）
+ */
+fn write_varint(bytes: &mut Vec<u8>, value: usize) -> Option<()> {
+    let mut value = u32::try_from(value).ok()?;
+    while value >= 0x80 {
+        bytes.push((value as u8 & 0x7F) | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+    Some(())
+}
+
+/**
+ * 不正なバイナリフレームのエラーを作る
+ */
+fn invalid(reason: &str) -> GameError {
+    GameError::Network(format!("invalid binary server message: {}", reason))
+}
+
+/**
+ * 範囲を確認しながらバイト列を先頭から読み進める
+ */
+struct Reader<'a> {
+    bytes: &'a [u8], // 読み込むバイト列
+    position: usize, // 次に読む位置
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, length: usize) -> GameResult<&'a [u8]> {
+        let end = self.position.checked_add(length).filter(|&end| end <= self.bytes.len())
+            .ok_or_else(|| invalid("truncated"))?;
+        let slice = &self.bytes[self.position..end];
+        self.position = end;
+        Ok(slice)
+    }
+
+    fn u8(&mut self) -> GameResult<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn i8(&mut self) -> GameResult<i8> {
+        Ok(self.u8()? as i8)
+    }
+
+    fn flag(&mut self) -> GameResult<bool> {
+        match self.u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            other => Err(invalid(&format!("invalid flag {}", other))),
+        }
+    }
+
+    /// 可変長の整数を読む（u32に収まらない値は拒否する）
+    fn varint(&mut self) -> GameResult<usize> {
+        let mut value: u64 = 0;
+        for shift in (0..35).step_by(7) {
+            let byte = self.u8()?;
+            value |= u64::from(byte & 0x7F) << shift;
+            if byte & 0x80 == 0 {
+                return u32::try_from(value).map(|value| value as usize).map_err(|_| invalid("integer overflow"));
+            }
+        }
+        Err(invalid("integer overflow"))
+    }
+
+    /// 要素数を読み、残りのバイト数で読み切れない数は拒否する（巨大な確保を防ぐ）
+    fn count(&mut self, min_element_size: usize) -> GameResult<usize> {
+        let count = self.varint()?;
+        if count.saturating_mul(min_element_size) > self.bytes.len() - self.position {
+            return Err(invalid("truncated"));
+        }
+        Ok(count)
+    }
+
+    fn cell_values(&mut self) -> GameResult<HashMap<String, i32>> {
+        let count = self.count(2)?;
+        (0..count)
+            .map(|_| Ok((self.varint()?.to_string(), i32::from(self.i8()?))))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_board_updates_round_trip_and_shrink() {
+        let values: HashMap<String, i32> = (0..400).map(|index| (index.to_string(), index % 9 - 1)).collect();
+        let revealed = ServerMessage::CellsRevealed {
+            player_id: "player_12".to_string(),
+            revision: 300,
+            cells: (0..400).map(|index| (index as usize, index % 9 - 1)).collect(),
+        };
+        let game_over = ServerMessage::GameOver {
+            win: false,
+            cells: (0..400).map(|index| index % 9 - 1).collect(),
+            all_cell_values: values,
+        };
+
+        for message in [revealed, game_over] {
+            let bytes = encode_server_message(&message).unwrap();
+            assert_eq!(decode_server_message(&bytes).unwrap(), message);
+            assert!(bytes.len() * 3 < serde_json::to_string(&message).unwrap().len());
+        }
+    }
+
+    #[test]
+    fn test_other_messages_stay_json() {
+        assert_eq!(encode_server_message(&ServerMessage::PlayerLeft { id: "player_1".to_string() }), None);
+        let message = ServerMessage::CellsRevealed { player_id: "player_1".to_string(), revision: 1, cells: vec![(0, 1000)] };
+        assert_eq!(encode_server_message(&message), None);
+    }
+
+    #[test]
+    fn test_malformed_frames_are_rejected() {
+        let message = ServerMessage::GameOver { win: true, cells: vec![1, -1], all_cell_values: HashMap::new() };
+        let bytes = encode_server_message(&message).unwrap();
+
+        assert!(decode_server_message(&bytes[..bytes.len() - 1]).is_err());
+        assert!(decode_server_message(&[bytes.as_slice(), &[0]].concat()).is_err());
+        assert!(decode_server_message(&[2, KIND_GAME_OVER]).is_err());
+        assert!(decode_server_message(&[BINARY_FORMAT_VERSION, 9]).is_err());
+        assert!(decode_server_message(&[BINARY_FORMAT_VERSION, KIND_GAME_OVER, 1, 255, 255, 255, 255, 15]).is_err());
+        assert!(decode_server_message(&[BINARY_FORMAT_VERSION, KIND_GAME_OVER, 2, 0, 1]).is_err());
+        assert!(decode_server_message(&[]).is_err());
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn fuzz_binary_frames_never_panic(bytes in proptest::collection::vec(proptest::prelude::any::<u8>(), 0..256)) {
+            let _ = decode_server_message(&bytes);
+        }
+
+        #[test]
+        fn fuzz_board_messages_round_trip(message in crate::protocol::fuzz::board_message()) {
+            // 変換できたメッセージは同じ内容に戻る
+            if let Some(bytes) = encode_server_message(&message) {
+                proptest::prop_assert_eq!(decode_server_message(&bytes).unwrap(), message);
+            }
+        }
+    }
+}
\ No newline at end of file
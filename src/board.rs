@@ -1,10 +1,23 @@
 /**
  * マインスイーパーのボードを管理するモジュール
  */
-use wasm_bindgen::JsValue;
+use std::collections::HashMap;
+use crate::error::GameResult;
 use crate::models::CellValue;
+use crate::protocol::GameStateSnapshot;
+use crate::resources::{RngResource, MAX_BOARD_SIZE};
 use crate::utils::{coordinates_to_index, index_to_coordinates, get_adjacent_offsets};
-use crate::js_bindings::log;
+use crate::logger::log_info;
+
+/**
+ * 外部に公開するセルの値で、地雷を表す値（0〜8は周囲の地雷数）
+ */
+pub const CELL_CODE_MINE: u8 = 9;
+
+/**
+ * 外部に公開するセルの値で、まだ開かれていないセルを表す値
+ */
+pub const CELL_CODE_HIDDEN: u8 = 255;
 
 /**
  * マインスイーパーのボードを表す構造体
@@ -30,6 +43,8 @@ pub struct Board {
     pub game_over: bool,
     /// 勝利したかどうか
     pub win: bool,
+    /// 開かれた地雷以外のセルの数（開くたびに加算する）
+    safe_revealed: usize,
 }
 
 impl Board {
@@ -48,6 +63,7 @@ impl Board {
             game_started: false,
             game_over: false,
             win: false,
+            safe_revealed: 0,
         }
     }
     
@@ -61,15 +77,64 @@ impl Board {
         self.game_started = false;
         self.game_over = false;
         self.win = false;
+        self.safe_revealed = 0;
+    }
+    
+    /**
+     * セルを開いた状態にし、地雷以外のセルであれば開いた数に加算する
+     * 
+     * セルの値が分かっている場合は、値を設定してから呼び出してください。
+     * 
+     * @param index 開くセルのインデックス
+     */
+    pub fn mark_revealed(&mut self, index: usize) {
+        if self.revealed[index] {
+            return;
+        }
+        
+        self.revealed[index] = true;
+        if self.cells[index] != CellValue::Mine {
+            self.safe_revealed += 1;
+        }
+    }
+    
+    /**
+     * 地雷以外のセルの数を取得する
+     * 
+     * @return 地雷以外のセルの数
+     */
+    pub fn safe_cells(&self) -> usize {
+        (self.width * self.height).saturating_sub(self.mine_count)
+    }
+    
+    /**
+     * 開かれた地雷以外のセルの数を取得する
+     * 
+     * @return 開かれた地雷以外のセルの数
+     */
+    pub fn safe_revealed(&self) -> usize {
+        self.safe_revealed
+    }
+    
+    /**
+     * 地雷以外のセルを開いた割合を取得する
+     * 
+     * @return 0.0〜1.0の割合
+     */
+    pub fn progress(&self) -> f64 {
+        match self.safe_cells() {
+            0 => 0.0,
+            safe_cells => (self.safe_revealed as f64 / safe_cells as f64).min(1.0),
+        }
     }
     
     /**
      * 指定されたセルを開く
      * 
      * @param index 開くセルのインデックス
-     * @return 成功した場合はOk(()), エラーの場合はErr(JsValue)
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
      */
-    pub fn reveal_cell(&mut self, index: usize) -> Result<(), JsValue> {
+    pub fn reveal_cell(&mut self, index: usize) -> GameResult<()> {
         // すでに開かれている、またはフラグが立っている場合は何もしない
         if self.revealed[index] || self.flagged[index] {
             return Ok(());
@@ -81,7 +146,7 @@ impl Board {
         }
         
         // セルを開く
-        self.revealed[index] = true;
+        self.mark_revealed(index);
         
         // 地雷だった場合はゲームオーバー
         if let CellValue::Mine = self.cells[index] {
@@ -113,9 +178,9 @@ impl Board {
      * 周囲のセルを再帰的に開く（0の場合）
      * 
      * @param index 中心となるセルのインデックス
-     * @return 成功した場合はOk(()), エラーの場合はErr(JsValue)
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
      */
-    fn reveal_adjacent_cells(&mut self, index: usize) -> Result<(), JsValue> {
+    fn reveal_adjacent_cells(&mut self, index: usize) -> GameResult<()> {
         let (row, col) = index_to_coordinates(index, self.width);
         
         // 周囲8方向のオフセット
@@ -130,7 +195,7 @@ impl Board {
                 
                 // 未開放かつフラグがない場合のみ開く
                 if !self.revealed[new_index] && !self.flagged[new_index] {
-                    self.revealed[new_index] = true;
+                    self.mark_revealed(new_index);
                     
                     // 0の場合は再帰的に周囲も開く
                     if let CellValue::Empty(0) = self.cells[new_index] {
@@ -143,6 +208,199 @@ impl Board {
         Ok(())
     }
     
+    /**
+     * 周囲8マスのインデックスを取得する（ボード外は含まない）
+     * 
+     * @param index 中心となるセルのインデックス
+     * @return 周囲のセルのインデックス
+     */
+    fn adjacent_indices(&self, index: usize) -> Vec<usize> {
+        let (row, col) = index_to_coordinates(index, self.width);
+        
+        get_adjacent_offsets()
+            .iter()
+            .filter_map(|(dr, dc)| {
+                let new_row = row as isize + dr;
+                let new_col = col as isize + dc;
+                if new_row >= 0 && new_row < self.height as isize &&
+                   new_col >= 0 && new_col < self.width as isize {
+                    Some(coordinates_to_index(new_row as usize, new_col as usize, self.width))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+    
+    /**
+     * ボードの3BV（クリアに必要な最小クリック数）を計算する
+     * 
+     * 周囲に地雷のない空白の領域（開口部）はそれぞれ1クリック、
+     * どの開口部にも接していない数字のセルは1つにつき1クリックとして数えます。
+     * 全てのセルの値が分かっている必要があるため、ゲーム終了後に使います。
+     * 
+     * @return 3BVの値
+     */
+    pub fn three_bv(&self) -> u32 {
+        let total = self.cells.len();
+        let mut covered = vec![false; total];
+        let mut count = 0;
+        
+        // 開口部を1つずつ塗りつぶして数える
+        for index in 0..total {
+            if covered[index] || self.cells[index] != CellValue::Empty(0) {
+                continue;
+            }
+            
+            count += 1;
+            covered[index] = true;
+            let mut stack = vec![index];
+            while let Some(current) = stack.pop() {
+                for neighbor in self.adjacent_indices(current) {
+                    if covered[neighbor] || self.cells[neighbor] == CellValue::Mine {
+                        continue;
+                    }
+                    covered[neighbor] = true;
+                    if self.cells[neighbor] == CellValue::Empty(0) {
+                        stack.push(neighbor);
+                    }
+                }
+            }
+        }
+        
+        // 開口部に含まれない数字のセル
+        let isolated = (0..total)
+            .filter(|&index| !covered[index] && matches!(self.cells[index], CellValue::Empty(n) if n > 0))
+            .count();
+        
+        count + isolated as u32
+    }
+    
+    /**
+     * 地雷の配置からボードを作り直す（履歴の盤面を練習する場合に使う）
+     * 
+     * 全てのセルを閉じた状態にし、地雷以外のセルには周囲の地雷の数を設定します。
+     * 
+     * @param mines 地雷のあるセルのインデックス
+     */
+    pub fn load_mines(&mut self, mines: &[usize]) {
+        self.initialize();
+        self.mine_count = mines.len();
+        
+        for &mine in mines {
+            self.cells[mine] = CellValue::Mine;
+        }
+        for index in 0..self.cells.len() {
+            if self.cells[index] != CellValue::Mine {
+                let count = self.adjacent_indices(index)
+                    .into_iter()
+                    .filter(|&neighbor| self.cells[neighbor] == CellValue::Mine)
+                    .count();
+                self.cells[index] = CellValue::Empty(count as u8);
+            }
+        }
+    }
+    
+    /**
+     * 最初に開くセルを避けて地雷をランダムに配置する（オフラインのゲームで使う）
+     * 
+     * 開くセルとその周囲には地雷を置かず、最初のクリックで必ず開口部が開くようにします。
+     * ボードが小さくて周囲を避けきれない場合は、開くセルだけを避けます。
+     * 
+     * @param safe_index 最初に開くセルのインデックス
+     * @param mine_count 配置する地雷の数
+     * @param rng 配置に使う乱数
+     * @return 配置した地雷のセルのインデックス
+     */
+    pub fn place_mines_avoiding(&mut self, safe_index: usize, mine_count: usize, rng: &mut RngResource) -> Vec<usize> {
+        let total = self.width * self.height;
+        let mut safe_zone = self.adjacent_indices(safe_index);
+        safe_zone.push(safe_index);
+        
+        let mut candidates: Vec<usize> = (0..total).filter(|index| !safe_zone.contains(index)).collect();
+        if candidates.len() < mine_count {
+            candidates = (0..total).filter(|&index| index != safe_index).collect();
+        }
+        
+        let mines: Vec<usize> = rng.sample_indices(candidates.len(), mine_count.min(candidates.len()))
+            .into_iter()
+            .map(|index| candidates[index])
+            .collect();
+        self.load_mines(&mines);
+        mines
+    }
+    
+    /**
+     * 結果を共有するための文字列を作成する
+     * 
+     * `Minesweeper 16x16/40 ⏱39s 🚩38/40 😀`の形式で、絵文字のグリッドを付けることもできます。
+     * グリッドは旗を🚩、開いた地雷を💥、開いたセルを🟩、閉じたセルを⬛で表します。
+     * 
+     * @param elapsed_ms 経過時間（ミリ秒）
+     * @param include_grid 絵文字のグリッドを付けるかどうか
+     * @return 共有用の文字列
+     */
+    pub fn share_text(&self, elapsed_ms: f64, include_grid: bool) -> String {
+        let flags = self.flagged.iter().filter(|&&flagged| flagged).count();
+        let mut text = format!(
+            "Minesweeper {}x{}/{} ⏱{}s 🚩{}/{} {}",
+            self.width,
+            self.height,
+            self.mine_count,
+            (elapsed_ms / 1000.0).floor(),
+            flags,
+            self.mine_count,
+            if self.win { "😀" } else { "😵" }
+        );
+        
+        if include_grid {
+            for row in 0..self.height {
+                text.push('\n');
+                for index in row * self.width..(row + 1) * self.width {
+                    text.push_str(if self.flagged[index] {
+                        "🚩"
+                    } else if !self.revealed[index] {
+                        "⬛"
+                    } else if self.cells[index] == CellValue::Mine {
+                        "💥"
+                    } else {
+                        "🟩"
+                    });
+                }
+            }
+        }
+        
+        text
+    }
+    
+    /**
+     * 外部のツール向けにセルの値を1バイトずつの配列にする
+     * 
+     * 開かれたセルは周囲の地雷数（0〜8）か`CELL_CODE_MINE`、
+     * 開かれていないセルは値が分かっていても`CELL_CODE_HIDDEN`にします（練習中の盤面を読み取れないように）。
+     * ゲーム終了後は全てのセルの値を公開します。
+     * 
+     * @return セルのインデックス順の値
+     */
+    pub fn cell_codes(&self) -> Vec<u8> {
+        self.cells.iter().zip(&self.revealed).map(|(&cell, &revealed)| {
+            match cell {
+                _ if !revealed && !self.game_over => CELL_CODE_HIDDEN,
+                CellValue::Mine => CELL_CODE_MINE,
+                CellValue::Empty(count) => count,
+            }
+        }).collect()
+    }
+    
+    /**
+     * 地雷のあるセルのインデックスを取得する
+     * 
+     * @return 地雷のあるセルのインデックス（値が分かっているセルのみ）
+     */
+    pub fn mine_indices(&self) -> Vec<usize> {
+        (0..self.cells.len()).filter(|&index| self.cells[index] == CellValue::Mine).collect()
+    }
+    
     /**
      * フラグを切り替える
      * 
@@ -169,15 +427,8 @@ impl Board {
      * 地雷以外の全てのセルが開かれていれば勝利
      */
     pub fn check_win(&mut self) {
-        let total_cells = self.width * self.height;
-        let mines = self.mine_count;
-        let non_mine_cells = total_cells - mines;
-        
-        // 開かれたセルの数をカウント
-        let revealed_count = self.revealed.iter().filter(|&&r| r).count();
-        
         // 地雷以外の全てのセルが開かれていれば勝利
-        if revealed_count == non_mine_cells {
+        if self.safe_revealed == self.safe_cells() {
             self.game_over = true;
             self.win = true;
             
@@ -188,7 +439,7 @@ impl Board {
                 }
             }
             
-            log("勝利条件を満たしました！");
+            log_info!("勝利条件を満たしました！");
         }
     }
     
@@ -224,78 +475,325 @@ impl Board {
     }
     
     /**
-     * サーバーから受信したデータでボードを更新する
+     * サーバーから受信したボードのサイズを設定する
+     * 
+     * 範囲外のサイズは無視し、地雷の数はセル数までに抑えます。
+     * セルの配列は作り直さないため、続けて`initialize`などを呼び出してください。
      * 
-     * @param game_data サーバーから受信したゲーム状態データ
+     * @param width ボードの幅
+     * @param height ボードの高さ
+     * @param mine_count 地雷の数
      */
-    pub fn update_from_server(&mut self, game_data: &serde_json::Map<String, serde_json::Value>) {
-        // ボードサイズの更新
-        if let Some(width) = game_data.get("boardWidth").and_then(|v| v.as_i64()) {
-            self.width = width as usize;
+    pub fn resize_from_server(&mut self, width: usize, height: usize, mine_count: usize) {
+        if let Some(width) = server_board_size(width) {
+            self.width = width;
         }
         
-        if let Some(height) = game_data.get("boardHeight").and_then(|v| v.as_i64()) {
-            self.height = height as usize;
+        if let Some(height) = server_board_size(height) {
+            self.height = height;
         }
         
-        if let Some(mines) = game_data.get("mineCount").and_then(|v| v.as_i64()) {
-            self.mine_count = mines as usize;
-        }
-        
-        // セルサイズの更新は親のGameStateで行う（キャンバスサイズが必要なため）
+        self.mine_count = mine_count.min(self.width * self.height);
+    }
+    
+    /**
+     * サーバーから受信したゲーム状態でボードを更新する
+     * 
+     * 不正なサイズは無視し、配列の長さはボードのセル数に揃えます。
+     * 
+     * @param snapshot サーバーから受信したゲーム状態
+     */
+    pub fn update_from_server(&mut self, snapshot: &GameStateSnapshot) {
+        // ボードサイズの更新（セルサイズの更新はキャンバスサイズが必要なため親のGameStateで行う）
+        self.resize_from_server(snapshot.board_width, snapshot.board_height, snapshot.mine_count);
         
         // ボードを初期化
-        self.cells = vec![CellValue::Empty(0); self.width * self.height];
+        let total = self.width * self.height;
+        self.cells = vec![CellValue::Empty(0); total];
+        self.revealed = server_flags(&snapshot.revealed, total);
         
-        // revealed配列を更新
-        if let Some(revealed) = game_data.get("revealed").and_then(|v| v.as_array()) {
-            self.revealed = revealed.iter()
-                .map(|v| v.as_bool().unwrap_or(false))
-                .collect();
-        } else {
-            self.revealed = vec![false; self.width * self.height];
-        }
+        // 開いた数は受信した状態から数え直す（値が分からないセルは地雷以外として数える）
+        self.safe_revealed = self.revealed.iter().filter(|&&revealed| revealed).count();
         
-        // flagged配列を更新
-        if let Some(flagged) = game_data.get("flagged").and_then(|v| v.as_array()) {
-            self.flagged = flagged.iter()
-                .map(|v| v.as_bool().unwrap_or(false))
-                .collect();
-        } else {
-            self.flagged = vec![false; self.width * self.height];
-        }
+        self.flagged = server_flags(&snapshot.flagged, total);
         
         // ゲーム状態を更新
-        if let Some(started) = game_data.get("gameStarted").and_then(|v| v.as_bool()) {
-            self.game_started = started;
-        }
-        
-        if let Some(over) = game_data.get("gameOver").and_then(|v| v.as_bool()) {
-            self.game_over = over;
-        }
-        
-        if let Some(win) = game_data.get("win").and_then(|v| v.as_bool()) {
-            self.win = win;
-        }
+        self.game_started = snapshot.game_started;
+        self.game_over = snapshot.game_over;
+        self.win = snapshot.win;
         
         // 既に開かれたセルの値を設定
-        if let Some(cell_values) = game_data.get("cellValues").and_then(|v| v.as_object()) {
-            log(&format!("セル値を受信: {} 個", cell_values.len()));
+        self.apply_cell_values(&snapshot.cell_values);
+    }
+    
+    /**
+     * サーバーから受信したセルの値を設定する
+     * 
+     * 範囲外のインデックスや不正な値は無視します。
+     * 
+     * @param index セルのインデックス
+     * @param value サーバーのセルの値（地雷は-1）
+     * @return 設定した値（無視した場合はNone）
+     */
+    pub fn set_server_cell_value(&mut self, index: usize, value: i32) -> Option<CellValue> {
+        let cell = cell_value_from_server(value)?;
+        *self.cells.get_mut(index)? = cell;
+        Some(cell)
+    }
+    
+    /**
+     * インデックスをキーにしたセルの値をまとめて設定する
+     * 
+     * @param cell_values セルのインデックスの文字列と値の組
+     */
+    pub fn apply_cell_values(&mut self, cell_values: &HashMap<String, i32>) {
+        for (index_str, &value) in cell_values {
+            if let Ok(index) = index_str.parse::<usize>() {
+                self.set_server_cell_value(index, value);
+            }
+        }
+    }
+    
+    /**
+     * 他のプレイヤーなどが開いたセルを反映する（`cells_revealed`メッセージ）
+     * 
+     * 値を設定してから開き、範囲外のインデックスは無視します。
+     * 
+     * @param cells 開かれたセルの(インデックス, 値)の組（地雷は-1）
+     * @return 開かれた地雷の数
+     */
+    pub fn apply_revealed_cells(&mut self, cells: &[(usize, i32)]) -> u32 {
+        let mut mines = 0;
+        for &(index, value) in cells {
+            if index >= self.cells.len() {
+                continue;
+            }
             
-            for (index_str, value) in cell_values {
-                if let Ok(index) = index_str.parse::<usize>() {
-                    if index < self.cells.len() {
-                        if let Some(v) = value.as_i64() {
-                            if v == -1 {
-                                self.cells[index] = CellValue::Mine;
-                            } else {
-                                self.cells[index] = CellValue::Empty(v as u8);
-                            }
-                            log(&format!("セル[{}]の値を{}に設定", index, v));
-                        }
-                    }
+            if self.set_server_cell_value(index, value) == Some(CellValue::Mine) {
+                mines += 1;
+            }
+            
+            // 値を設定してから開く（地雷以外なら進捗に加算）
+            self.mark_revealed(index);
+        }
+        mines
+    }
+}
+
+/**
+ * サーバーのセルの値をセルの値に変換する
+ * 
+ * @param value サーバーのセルの値（地雷は-1、それ以外は周囲の地雷数）
+ * @return セルの値（範囲外の値はNone）
+ */
+fn cell_value_from_server(value: i32) -> Option<CellValue> {
+    match value {
+        -1 => Some(CellValue::Mine),
+        0..=8 => Some(CellValue::Empty(value as u8)),
+        _ => None,
+    }
+}
+
+/**
+ * サーバーのボードの幅・高さを確認する
+ * 
+ * @param size サーバーの値
+ * @return 1〜MAX_BOARD_SIZEの範囲の値（それ以外はNone）
+ */
+fn server_board_size(size: usize) -> Option<usize> {
+    Some(size).filter(|size| (1..=MAX_BOARD_SIZE).contains(size))
+}
+
+/**
+ * サーバーの真偽値の配列をセル数の長さに揃える（足りない分はfalse）
+ * 
+ * @param values サーバーの値
+ * @param total ボードのセル数
+ * @return セルごとの真偽値
+ */
+fn server_flags(values: &[bool], total: usize) -> Vec<bool> {
+    let mut flags: Vec<bool> = values.iter().take(total).copied().collect();
+    flags.resize(total, false);
+    flags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::ServerMessage;
+
+    /// 地雷の位置からセルの値を計算したボードを作成
+    fn board_with_mines(width: usize, height: usize, mines: &[usize]) -> Board {
+        let mut board = Board::new(width, height, mines.len(), 10.0);
+        board.load_mines(mines);
+        board
+    }
+
+    #[test]
+    fn test_three_bv() {
+        // 地雷のないボードは1クリックで全て開く
+        assert_eq!(board_with_mines(3, 3, &[]).three_bv(), 1);
+
+        // 中央が地雷なら周囲の8マスは全て数字で、それぞれ1クリック必要
+        assert_eq!(board_with_mines(3, 3, &[4]).three_bv(), 8);
+
+        // 左端の列が地雷なら、右側の開口部1つで残りが全て開く
+        assert_eq!(board_with_mines(4, 3, &[0, 4, 8]).three_bv(), 1);
+    }
+
+    #[test]
+    fn test_load_mines_round_trip() {
+        let mut board = board_with_mines(3, 3, &[0, 8]);
+        assert_eq!(board.cells[4], CellValue::Empty(2));
+        assert_eq!(board.mine_indices(), vec![0, 8]);
+
+        // 読み込み直すと開いたセルやフラグは消える
+        board.revealed[4] = true;
+        board.flagged[1] = true;
+        board.load_mines(&[2]);
+        assert_eq!(board.mine_count, 1);
+        assert_eq!(board.cells[0], CellValue::Empty(0));
+        assert!(!board.revealed[4] && !board.flagged[1]);
+    }
+
+    #[test]
+    fn test_place_mines_avoiding_keeps_first_click_open() {
+        let mut rng = RngResource::new(7);
+        let mut board = Board::new(5, 5, 10, 10.0);
+        let mines = board.place_mines_avoiding(12, 10, &mut rng);
+        assert_eq!(mines.len(), 10);
+        assert_eq!(board.mine_count, 10);
+        assert_eq!(board.cells[12], CellValue::Empty(0));
+        assert!(board.adjacent_indices(12).iter().all(|&index| board.cells[index] != CellValue::Mine));
+
+        // 周囲を避けきれないボードでは開くセルだけを避ける
+        let mut board = Board::new(3, 3, 8, 10.0);
+        let mines = board.place_mines_avoiding(4, 8, &mut rng);
+        assert_eq!(mines.len(), 8);
+        assert_eq!(board.cells[4], CellValue::Empty(8));
+    }
+
+    #[test]
+    fn test_progress_counts_safe_reveals() {
+        let mut board = board_with_mines(3, 3, &[0, 8]);
+        assert_eq!(board.safe_cells(), 7);
+        assert_eq!(board.progress(), 0.0);
+
+        // 同じセルを二度開いても、地雷を開いても加算されない
+        board.mark_revealed(1);
+        board.mark_revealed(1);
+        board.mark_revealed(0);
+        assert_eq!(board.safe_revealed(), 1);
+
+        for index in 2..8 {
+            board.mark_revealed(index);
+        }
+        assert_eq!(board.progress(), 1.0);
+        assert_eq!(board.safe_revealed(), board.safe_cells());
+
+        board.initialize();
+        assert_eq!(board.safe_revealed(), 0);
+    }
+
+    #[test]
+    fn test_cell_codes_hide_unrevealed_cells() {
+        let mut board = board_with_mines(3, 1, &[0]);
+        board.revealed[1] = true;
+        assert_eq!(board.cell_codes(), vec![CELL_CODE_HIDDEN, 1, CELL_CODE_HIDDEN]);
+
+        board.game_over = true;
+        assert_eq!(board.cell_codes(), vec![CELL_CODE_MINE, 1, 0]);
+    }
+
+    #[test]
+    fn test_share_text() {
+        let mut board = board_with_mines(3, 2, &[0]);
+        board.flagged[0] = true;
+        board.revealed[1] = true;
+        board.revealed[5] = true;
+
+        assert_eq!(board.share_text(39_800.0, false), "Minesweeper 3x2/1 ⏱39s 🚩1/1 😵");
+
+        board.win = true;
+        assert_eq!(board.share_text(1_000.0, true), "Minesweeper 3x2/1 ⏱1s 🚩1/1 😀\n🚩🟩⬛\n⬛⬛🟩");
+    }
+
+    /// ボードの配列の長さと値がサイズに合っていることを確認
+    fn assert_consistent(board: &Board) {
+        let total = board.width * board.height;
+        assert!((1..=MAX_BOARD_SIZE).contains(&board.width) && (1..=MAX_BOARD_SIZE).contains(&board.height));
+        assert_eq!(board.cells.len(), total);
+        assert_eq!(board.revealed.len(), total);
+        assert_eq!(board.flagged.len(), total);
+        assert!(board.mine_count <= total);
+        assert!(board.safe_revealed() <= total);
+        assert!(board.cell_codes().iter().all(|&code| code <= CELL_CODE_MINE || code == CELL_CODE_HIDDEN));
+    }
+
+    #[test]
+    fn test_update_from_server_ignores_invalid_sizes() {
+        let mut board = Board::new(9, 9, 10, 10.0);
+        let snapshot = GameStateSnapshot {
+            board_width: 0,
+            board_height: usize::MAX,
+            mine_count: 1000,
+            revealed: vec![true, false, true],
+            flagged: vec![false; 100],
+            game_started: true,
+            game_over: false,
+            win: false,
+            cell_values: [("0", 9), ("1", -2), ("2", 3), ("81", 1), ("-1", -1)]
+                .into_iter()
+                .map(|(index, value)| (index.to_string(), value))
+                .collect(),
+            countdown_ends_at: None,
+            server_time: 0.0,
+            revision: 0,
+        };
+        board.update_from_server(&snapshot);
+        assert_consistent(&board);
+        assert_eq!((board.width, board.height, board.mine_count), (9, 9, 81));
+        assert_eq!(board.safe_revealed(), 2);
+        assert_eq!(&board.cells[..3], &[CellValue::Empty(0), CellValue::Empty(0), CellValue::Empty(3)]);
+    }
+
+    #[test]
+    fn test_apply_revealed_cells_skips_out_of_range_cells() {
+        let mut board = Board::new(2, 2, 1, 10.0);
+        assert_eq!(board.apply_revealed_cells(&[(0, 1), (3, -1), (4, -1), (3, -1)]), 2);
+        assert_eq!(board.revealed, vec![true, false, false, true]);
+        assert_eq!(board.safe_revealed(), 1);
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn fuzz_server_game_state_keeps_board_consistent(snapshot in crate::protocol::fuzz::game_state_snapshot()) {
+            // 型は正しいがサイズや配列の長さ・セルの値が合わないゲーム状態を試す
+            let mut board = Board::new(9, 9, 10, 10.0);
+            board.update_from_server(&snapshot);
+            assert_consistent(&board);
+        }
+
+        #[test]
+        fn fuzz_server_reveals_keep_board_consistent(
+            width in 1usize..12,
+            height in 1usize..12,
+            messages in proptest::collection::vec(crate::protocol::fuzz::board_message(), 1..8)
+        ) {
+            // 受信処理と同じ順でセルの値を反映する
+            let mut board = Board::new(width, height, 0, 10.0);
+            for message in &messages {
+                match message {
+                    ServerMessage::CellsRevealed { cells, .. } => {
+                        let mines = board.apply_revealed_cells(cells);
+                        proptest::prop_assert!(mines as usize <= cells.len());
+                    },
+                    ServerMessage::GameOver { all_cell_values, .. } => board.apply_cell_values(all_cell_values),
+                    ServerMessage::StateSync { game_state, .. } => board.update_from_server(game_state),
+                    _ => {},
                 }
+                assert_consistent(&board);
             }
         }
     }
-} 
\ No newline at end of file
+}
\ No newline at end of file
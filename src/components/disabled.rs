@@ -0,0 +1,13 @@
+/**
+ * 無効化マーカーコンポーネント
+ * 
+ * このコンポーネントを持つエンティティは、破棄せずに一時的にクエリの対象外となる
+ * （UI画面やボードの一部を一時的に隠す用途）
+ */
+use super::component_trait::Component;
+
+/// エンティティが無効化されていることを示すマーカー
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Disabled;
+
+impl Component for Disabled {}
\ No newline at end of file
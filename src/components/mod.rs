@@ -10,6 +10,7 @@ mod cell;
 mod player;
 mod position;
 mod ui;
+mod disabled;
 mod component_trait;
 mod component_factory;
 mod component_vec;
@@ -17,8 +18,9 @@ mod component_vec;
 // コンポーネントを再エクスポート
 pub use cell::{CellContent, CellState};
 pub use player::PlayerComponent;
-pub use position::Position;
-pub use ui::{UIElement, Button};
+pub use position::{Position, GlobalPosition};
+pub use ui::{UIElement, Button, Anchor, UIAnchor};
+pub use disabled::Disabled;
 
 // コンポーネントシステムを再エクスポート
 pub use component_trait::{Component, SerializableComponent, ComponentDependencyHandler};
@@ -23,7 +23,7 @@ impl PlayerComponent {
         Self {
             id,
             color,
-            last_action_time: js_sys::Date::now(),
+            last_action_time: crate::utils::now_ms(),
             is_local,
         }
     }
@@ -40,6 +40,6 @@ impl PlayerComponent {
     
     /// 操作時間を更新
     pub fn update_action_time(&mut self) {
-        self.last_action_time = js_sys::Date::now();
+        self.last_action_time = crate::utils::now_ms();
     }
 } 
\ No newline at end of file
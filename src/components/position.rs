@@ -31,4 +31,30 @@ impl Position {
         let dy = self.y - other.y;
         (dx * dx + dy * dy).sqrt()
     }
-} 
\ No newline at end of file
+}
+
+/// 親子関係を考慮したワールド座標
+/// 
+/// `EntityManager::propagate_positions`が、親のワールド座標にローカルの`Position`を加算して設定します
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GlobalPosition {
+    /// X座標
+    pub x: f64,
+    /// Y座標
+    pub y: f64,
+}
+
+impl GlobalPosition {
+    /// 新しいワールド座標を作成
+    pub fn new(x: f64, y: f64) -> Self {
+        Self { x, y }
+    }
+    
+    /// ローカル座標を加算した子のワールド座標を計算
+    pub fn offset_by(&self, local: &Position) -> Self {
+        Self {
+            x: self.x + local.x,
+            y: self.y + local.y,
+        }
+    }
+}
\ No newline at end of file
@@ -4,6 +4,7 @@
  * ゲームのUI要素を表すデータ構造
  */
 use crate::components::Position;
+use crate::resources::Color;
 
 /// UI要素の種類
 #[derive(Debug, Clone)]
@@ -15,14 +16,78 @@ pub enum UIElement {
         content: String,
         font: String,
         size: f64,
-        color: String,
+        color: Color,
     },
     /// アイコン
     Icon {
         name: String,
         size: f64,
-        color: String,
+        color: Color,
     },
+    /// 進捗バー（左端が位置）
+    ProgressBar {
+        width: f64,
+        height: f64,
+        progress: f64,
+        color: Color,
+    },
+}
+
+/// キャンバス上の基準位置
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Anchor {
+    /// 左上
+    TopLeft,
+    /// 上中央
+    TopCenter,
+    /// 右上
+    TopRight,
+    /// 左下
+    BottomLeft,
+    /// 下中央
+    BottomCenter,
+    /// 右下
+    BottomRight,
+}
+
+impl Anchor {
+    /// キャンバスの大きさから基準位置の座標を計算
+    pub fn resolve(&self, canvas_width: f64, canvas_height: f64) -> (f64, f64) {
+        let x = match self {
+            Anchor::TopLeft | Anchor::BottomLeft => 0.0,
+            Anchor::TopCenter | Anchor::BottomCenter => canvas_width / 2.0,
+            Anchor::TopRight | Anchor::BottomRight => canvas_width,
+        };
+        let y = match self {
+            Anchor::TopLeft | Anchor::TopCenter | Anchor::TopRight => 0.0,
+            Anchor::BottomLeft | Anchor::BottomCenter | Anchor::BottomRight => canvas_height,
+        };
+        (x, y)
+    }
+}
+
+/// キャンバスの基準位置からの相対位置でUI要素を配置するコンポーネント
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UIAnchor {
+    /// 基準位置
+    pub anchor: Anchor,
+    /// 基準位置からのX方向のずれ
+    pub offset_x: f64,
+    /// 基準位置からのY方向のずれ
+    pub offset_y: f64,
+}
+
+impl UIAnchor {
+    /// 新しい配置を作成
+    pub const fn new(anchor: Anchor, offset_x: f64, offset_y: f64) -> Self {
+        Self { anchor, offset_x, offset_y }
+    }
+
+    /// キャンバスの大きさから配置先の座標を計算
+    pub fn resolve(&self, canvas_width: f64, canvas_height: f64) -> Position {
+        let (x, y) = self.anchor.resolve(canvas_width, canvas_height);
+        Position::new(x + self.offset_x, y + self.offset_y)
+    }
 }
 
 /// ボタンコンポーネント
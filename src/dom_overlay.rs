@@ -0,0 +1,326 @@
+/**
+ * キャンバスに重ねて表示するHTMLのメニュー
+ *
+ * 表示方法の設定が「HTML」の場合に、一時停止メニューと設定画面を
+ * キャンバスの上に配置したHTML要素で表示します（ボードはキャンバスのまま）。
+ * 文字がくっきり表示され、名前の入力や音量の調整にブラウザ標準の入力欄を使えます。
+ * 要素の操作は`OverlayAction`として共有のキューに積み、フレームの更新処理でゲームに反映します。
+ */
+use std::cell::RefCell;
+use std::rc::Rc;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{Document, Element, HtmlCanvasElement, HtmlElement, HtmlInputElement};
+use crate::event_listener::EventListener;
+use crate::resources::SettingsItem;
+
+/**
+ * HTMLのメニューでの操作
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub enum OverlayAction {
+    PauseMenu(usize),                // 一時停止メニューのボタン（`PAUSE_MENU_ITEMS`のインデックス）
+    Setting(SettingsItem),           // 設定項目のボタン
+    SliderValue(SettingsItem, f32),  // 音量スライダーの値（0.0〜1.0）
+    PlayerName(String),              // 入力欄で確定したプレイヤー名
+    Back,                            // 設定画面の戻るボタン
+}
+
+/**
+ * 操作をフレームの更新処理に渡すためのキュー
+ */
+pub type OverlayQueue = Rc<RefCell<Vec<OverlayAction>>>;
+
+/**
+ * 一時停止メニューのボタンの表示内容
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct OverlayButtonView {
+    pub label: String, // ボタンの文言
+    pub enabled: bool, // 押せるかどうか
+}
+
+/**
+ * 設定画面の1行の表示内容
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct OverlaySettingView {
+    pub item: SettingsItem,      // 設定項目
+    pub label: String,           // 項目名
+    pub value: String,           // 現在値の表示
+    pub slider: Option<f32>,     // スライダー項目の値
+}
+
+/**
+ * HTMLのメニューに表示する内容
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub enum OverlayView {
+    Hidden,                                                                   // 何も表示しない
+    PauseMenu { heading: String, buttons: Vec<OverlayButtonView> },           // 一時停止メニュー
+    Settings { heading: String, rows: Vec<OverlaySettingView>, back: String }, // 設定画面
+}
+
+/// パネルの見た目
+const PANEL_STYLE: &str = "display: flex; flex-direction: column; align-items: stretch; gap: 8px; min-width: 280px; max-height: 90%; overflow-y: auto; padding: 20px; background: rgba(0, 0, 0, 0.8); border-radius: 8px; color: #FFFFFF; font-family: Arial, sans-serif;";
+
+/// ボタンの見た目
+const BUTTON_STYLE: &str = "padding: 10px 16px; border: none; border-radius: 4px; background: #4CAF50; color: #FFFFFF; font-size: 16px; cursor: pointer;";
+
+/// 押せないボタンの見た目
+const DISABLED_BUTTON_STYLE: &str = "padding: 10px 16px; border: none; border-radius: 4px; background: #9E9E9E; color: #FFFFFF; font-size: 16px;";
+
+/// 設定画面の行の見た目
+const ROW_STYLE: &str = "display: flex; justify-content: space-between; align-items: center; gap: 16px; padding: 6px 12px; background: #546E7A; border-radius: 4px; font-size: 16px;";
+
+/**
+ * キャンバスに重ねて表示するHTMLのメニュー
+ *
+ * 破棄すると要素を取り除きます。
+ */
+pub struct DomOverlay {
+    document: Document,               // 要素を作るドキュメント
+    canvas: HtmlCanvasElement,        // 重ねる対象のキャンバス
+    root: HtmlElement,                // メニューを入れる要素
+    queue: OverlayQueue,              // 操作を積むキュー
+    view: OverlayView,                // 表示中の内容
+    bounds: (i32, i32, i32, i32),     // 表示中の位置とサイズ（左端X, 上端Y, 幅, 高さ）
+    listeners: Vec<EventListener>,    // 表示中の要素のイベントリスナー
+}
+
+impl DomOverlay {
+    /**
+     * キャンバスの親要素にメニューを入れる要素を追加する
+     *
+     * @param canvas 重ねる対象のキャンバス
+     * @param queue 操作を積むキュー
+     * @return 作成したメニュー、親要素が無い場合などはErr(JsValue)
+     */
+    pub fn new(canvas: &HtmlCanvasElement, queue: &OverlayQueue) -> Result<Self, JsValue> {
+        let document = web_sys::window()
+            .and_then(|window| window.document())
+            .ok_or_else(|| JsValue::from_str("document is not available"))?;
+        let parent = canvas.parent_element()
+            .ok_or_else(|| JsValue::from_str("canvas has no parent element"))?
+            .dyn_into::<HtmlElement>()?;
+
+        // キャンバスの位置に合わせて重ねられるよう、親要素を配置の基準にする
+        if parent.style().get_property_value("position")?.is_empty() {
+            parent.style().set_property("position", "relative")?;
+        }
+
+        let root = document.create_element("div")?.dyn_into::<HtmlElement>()?;
+        root.set_class_name("game-overlay");
+        root.style().set_property("display", "none")?;
+        parent.append_child(&root)?;
+
+        Ok(Self {
+            document,
+            canvas: canvas.clone(),
+            root,
+            queue: queue.clone(),
+            view: OverlayView::Hidden,
+            bounds: (0, 0, 0, 0),
+            listeners: Vec::new(),
+        })
+    }
+
+    /**
+     * 表示する内容を反映する
+     *
+     * 内容が変わった場合のみ要素を作り直します。
+     *
+     * @param view 表示する内容
+     * @return 成功した場合はOk(()), エラーの場合はErr(JsValue)
+     */
+    pub fn render(&mut self, view: OverlayView) -> Result<(), JsValue> {
+        self.update_bounds()?;
+        if view == self.view {
+            return Ok(());
+        }
+
+        self.listeners.clear();
+        self.root.set_inner_html("");
+        match &view {
+            OverlayView::Hidden => {
+                self.root.style().set_property("display", "none")?;
+            },
+            OverlayView::PauseMenu { heading, buttons } => {
+                let panel = self.create_panel(heading)?;
+                for (index, button) in buttons.iter().enumerate() {
+                    let element = self.create_button(&button.label, button.enabled, OverlayAction::PauseMenu(index))?;
+                    panel.append_child(&element)?;
+                }
+                self.show(&panel)?;
+            },
+            OverlayView::Settings { heading, rows, back } => {
+                let panel = self.create_panel(heading)?;
+                for row in rows {
+                    let element = self.create_setting_row(row)?;
+                    panel.append_child(&element)?;
+                }
+                let element = self.create_button(back, true, OverlayAction::Back)?;
+                panel.append_child(&element)?;
+                self.show(&panel)?;
+            },
+        }
+
+        self.view = view;
+        Ok(())
+    }
+
+    /**
+     * キャンバスの表示位置とサイズに合わせて要素を配置する
+     */
+    fn update_bounds(&mut self) -> Result<(), JsValue> {
+        let bounds = (
+            self.canvas.offset_left(),
+            self.canvas.offset_top(),
+            self.canvas.offset_width(),
+            self.canvas.offset_height(),
+        );
+        if bounds == self.bounds {
+            return Ok(());
+        }
+
+        let (left, top, width, height) = bounds;
+        self.root.set_attribute("style", &format!(
+            "position: absolute; left: {}px; top: {}px; width: {}px; height: {}px; justify-content: center; align-items: center; display: {};",
+            left, top, width, height,
+            if self.view == OverlayView::Hidden { "none" } else { "flex" }
+        ))?;
+        self.bounds = bounds;
+        Ok(())
+    }
+
+    /**
+     * パネルを表示する
+     *
+     * @param panel 表示するパネル
+     */
+    fn show(&self, panel: &Element) -> Result<(), JsValue> {
+        self.root.append_child(panel)?;
+        self.root.style().set_property("display", "flex")
+    }
+
+    /**
+     * 見出し付きのパネルを作る
+     *
+     * @param heading 見出し
+     * @return 作成したパネル
+     */
+    fn create_panel(&self, heading: &str) -> Result<Element, JsValue> {
+        let panel = self.document.create_element("div")?;
+        panel.set_attribute("style", PANEL_STYLE)?;
+        panel.set_attribute("role", "dialog")?;
+        panel.set_attribute("aria-label", heading)?;
+
+        let title = self.document.create_element("h2")?;
+        title.set_attribute("style", "margin: 0 0 8px; text-align: center;")?;
+        title.set_text_content(Some(heading));
+        panel.append_child(&title)?;
+        Ok(panel)
+    }
+
+    /**
+     * 押すと操作をキューに積むボタンを作る
+     *
+     * @param label ボタンの文言
+     * @param enabled 押せるかどうか
+     * @param action 押したときの操作
+     * @return 作成したボタン
+     */
+    fn create_button(&mut self, label: &str, enabled: bool, action: OverlayAction) -> Result<Element, JsValue> {
+        let button = self.document.create_element("button")?;
+        button.set_text_content(Some(label));
+        if !enabled {
+            button.set_attribute("style", DISABLED_BUTTON_STYLE)?;
+            button.set_attribute("disabled", "")?;
+            return Ok(button);
+        }
+
+        button.set_attribute("style", BUTTON_STYLE)?;
+        let queue = self.queue.clone();
+        self.listeners.push(EventListener::new(&button, "click", move |_: web_sys::Event| {
+            queue.borrow_mut().push(action.clone());
+        })?);
+        Ok(button)
+    }
+
+    /**
+     * 設定画面の1行を作る
+     *
+     * 音量はスライダー、プレイヤー名は入力欄、それ以外は値を切り替えるボタンにします。
+     *
+     * @param row 行の表示内容
+     * @return 作成した行
+     */
+    fn create_setting_row(&mut self, row: &OverlaySettingView) -> Result<Element, JsValue> {
+        let element = self.document.create_element("div")?;
+        element.set_attribute("style", ROW_STYLE)?;
+        let label = self.document.create_element("span")?;
+        label.set_text_content(Some(&row.label));
+        element.append_child(&label)?;
+
+        let item = row.item;
+        let queue = self.queue.clone();
+        if let Some(value) = row.slider {
+            let input = self.create_input("range", &row.label)?;
+            input.set_min("0");
+            input.set_max("100");
+            input.set_step("5");
+            input.set_value(&((value * 100.0).round() as i32).to_string());
+            self.listeners.push(EventListener::new(&input, "change", move |event: web_sys::Event| {
+                if let Some(value) = input_value(&event).and_then(|value| value.parse::<f32>().ok()) {
+                    queue.borrow_mut().push(OverlayAction::SliderValue(item, value / 100.0));
+                }
+            })?);
+            element.append_child(&input)?;
+        } else if item == SettingsItem::Name {
+            let input = self.create_input("text", &row.label)?;
+            input.set_value(&row.value);
+            self.listeners.push(EventListener::new(&input, "change", move |event: web_sys::Event| {
+                if let Some(name) = input_value(&event) {
+                    queue.borrow_mut().push(OverlayAction::PlayerName(name));
+                }
+            })?);
+            element.append_child(&input)?;
+        } else {
+            let button = self.create_button(&row.value, true, OverlayAction::Setting(item))?;
+            button.set_attribute("aria-label", &format!("{}: {}", row.label, row.value))?;
+            element.append_child(&button)?;
+        }
+
+        Ok(element)
+    }
+
+    /**
+     * 入力欄を作る
+     *
+     * @param input_type 入力欄の種類（"range"など）
+     * @param label 読み上げに使う項目名
+     * @return 作成した入力欄
+     */
+    fn create_input(&self, input_type: &str, label: &str) -> Result<HtmlInputElement, JsValue> {
+        let input = self.document.create_element("input")?.dyn_into::<HtmlInputElement>()?;
+        input.set_type(input_type);
+        input.set_attribute("aria-label", label)?;
+        Ok(input)
+    }
+}
+
+impl Drop for DomOverlay {
+    fn drop(&mut self) {
+        self.root.remove();
+    }
+}
+
+/**
+ * イベントが発生した入力欄の値を取得する
+ *
+ * @param event 入力欄のイベント
+ * @return 入力欄の値（入力欄以外のイベントの場合はNone）
+ */
+fn input_value(event: &web_sys::Event) -> Option<String> {
+    event.target()?.dyn_into::<HtmlInputElement>().ok().map(|input| input.value())
+}
\ No newline at end of file
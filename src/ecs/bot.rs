@@ -0,0 +1,139 @@
+/**
+ * ボット
+ *
+ * 練習の相手やテストのための模擬プレイヤーが、時刻に合わせて手を決めるシステムを
+ * Worldへ登録するプラグイン
+ * 決まった手は`BotResource`に積まれ、GameStateがプレイヤーの操作と同じ経路で反映する
+ */
+use crate::entities::EntityManager;
+use crate::resources::{BotResource, ResourceManager};
+use crate::system::system_registry::SystemPhase;
+use super::plugin::Plugin;
+use super::World;
+
+/// ボットのシステムの名前
+pub const BOT_SYSTEM_NAME: &str = "BotSystem";
+
+/// 手を指す時刻になったボットの手を決める
+pub fn bot_system(_entities: &mut EntityManager, resources: &mut ResourceManager) {
+    if let Some(bots) = resources.get_mut::<BotResource>() {
+        bots.update();
+    }
+}
+
+/// ボットのリソースとシステムを登録するプラグイン
+#[derive(Debug, Default, Clone, Copy)]
+pub struct BotPlugin;
+
+impl Plugin for BotPlugin {
+    fn build(&self, world: &mut World) {
+        if !world.has_resource::<BotResource>() {
+            world.insert_resource(BotResource::new(0));
+        }
+        world.systems_mut().add_fn_system(BOT_SYSTEM_NAME, SystemPhase::Update, bot_system);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::{BotBoardView, BotMove, BotStrategy};
+
+    /// 地雷の位置から周囲の地雷数を計算する
+    fn count_mines(width: usize, height: usize, mines: &[usize], index: usize) -> u8 {
+        let (x, y) = ((index % width) as isize, (index / width) as isize);
+        mines.iter().filter(|&&mine| {
+            let (mx, my) = ((mine % width) as isize, (mine / width) as isize);
+            mine != index && (mx - x).abs() <= 1 && (my - y).abs() <= 1
+        }).count() as u8
+    }
+
+    /// セルを開き、周囲に地雷が無ければ隣も開く（地雷を開いた場合はtrue）
+    fn reveal(board: &mut BotBoardView, mines: &[usize], index: usize) -> bool {
+        if mines.contains(&index) {
+            return true;
+        }
+        let mut stack = vec![index];
+        while let Some(cell) = stack.pop() {
+            if board.numbers[cell].is_some() {
+                continue;
+            }
+            let count = count_mines(board.width, board.height, mines, cell);
+            board.numbers[cell] = Some(count);
+            if count == 0 {
+                let (x, y) = (cell % board.width, cell / board.width);
+                for ny in y.saturating_sub(1)..=(y + 1).min(board.height - 1) {
+                    for nx in x.saturating_sub(1)..=(x + 1).min(board.width - 1) {
+                        stack.push(ny * board.width + nx);
+                    }
+                }
+            }
+        }
+        false
+    }
+
+    #[test]
+    fn test_solver_bots_play_through_the_world() {
+        let (width, height) = (8, 8);
+        let mines = [3, 17, 30, 42, 51, 60];
+        let mut world = World::new();
+        world.add_plugin(BotPlugin);
+        assert!(world.systems().has_system(BOT_SYSTEM_NAME));
+
+        {
+            let bots = world.get_resource_mut::<BotResource>().unwrap();
+            bots.board = BotBoardView {
+                width,
+                height,
+                numbers: vec![None; width * height],
+                flagged: vec![false; width * height],
+                cell_size: 10.0,
+                ..Default::default()
+            };
+            bots.add_bot(BotStrategy::Solver);
+            bots.add_bot(BotStrategy::Solver);
+            bots.add_bot(BotStrategy::Random);
+        }
+
+        // 最初の1手は地雷の無い角から開いておく
+        reveal(&mut world.get_resource_mut::<BotResource>().unwrap().board, &mines, 0);
+
+        let mut now = 0.0;
+        for _ in 0..2000 {
+            now += 100.0;
+            world.get_resource_mut::<BotResource>().unwrap().now = now;
+            world.run_stages();
+
+            let bots = world.get_resource_mut::<BotResource>().unwrap();
+            for action in bots.take_actions() {
+                match action.bot_move {
+                    BotMove::Reveal(index) => {
+                        assert!(bots.board.numbers[index].is_none() && !bots.board.flagged[index]);
+                        if reveal(&mut bots.board, &mines, index) {
+                            bots.board.game_over = true;
+                        }
+                    },
+                    BotMove::Flag(index) => {
+                        // ソルバーが確定させたフラグは必ず地雷
+                        assert!(mines.contains(&index));
+                        bots.board.flagged[index] = true;
+                    },
+                }
+            }
+            let opened = bots.board.numbers.iter().filter(|number| number.is_some()).count();
+            if opened == width * height - mines.len() {
+                bots.board.game_over = true;
+            }
+            if bots.board.game_over {
+                break;
+            }
+        }
+
+        // 勝っても負けてもゲームは終わり、終わった後は手を指さない
+        let bots = world.get_resource_mut::<BotResource>().unwrap();
+        assert!(bots.board.game_over);
+        bots.now = now + 10_000.0;
+        world.run_stages();
+        assert!(world.get_resource_mut::<BotResource>().unwrap().take_actions().is_empty());
+    }
+}
\ No newline at end of file
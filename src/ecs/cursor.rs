@@ -0,0 +1,65 @@
+/**
+ * カーソルの補間
+ *
+ * 他のプレイヤーのカーソルは一定間隔でしか位置が届かないため、
+ * 前の位置から届いた位置まで滑らかに動かすシステムをWorldへ登録するプラグイン
+ * GameStateが`PlayerStateResource`に目標位置と現在時刻を書き込み、補間後の位置で描画する
+ */
+use crate::entities::EntityManager;
+use crate::resources::{PlayerStateResource, ResourceManager};
+use crate::system::system_registry::SystemPhase;
+use super::plugin::Plugin;
+use super::World;
+
+/// カーソルの補間システムの名前
+pub const CURSOR_SYSTEM_NAME: &str = "CursorInterpolationSystem";
+
+/// 全プレイヤーのカーソルを現在時刻に合わせた位置に動かす
+pub fn cursor_interpolation_system(_entities: &mut EntityManager, resources: &mut ResourceManager) {
+    if let Some(players) = resources.get_mut::<PlayerStateResource>() {
+        players.interpolate_players();
+    }
+}
+
+/// プレイヤーの状態のリソースとカーソルの補間システムを登録するプラグイン
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CursorPlugin;
+
+impl Plugin for CursorPlugin {
+    fn build(&self, world: &mut World) {
+        if !world.has_resource::<PlayerStateResource>() {
+            world.insert_resource(PlayerStateResource::new());
+        }
+        world.systems_mut().add_fn_system(CURSOR_SYSTEM_NAME, SystemPhase::Update, cursor_interpolation_system);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::CURSOR_INTERPOLATION_MS;
+
+    #[test]
+    fn test_cursor_moves_smoothly_between_updates() {
+        let mut world = World::new();
+        world.add_plugin(CursorPlugin);
+        assert!(world.systems().has_system(CURSOR_SYSTEM_NAME));
+
+        {
+            let players = world.get_resource_mut::<PlayerStateResource>().unwrap();
+            players.add_player("p1".to_string(), 0.0, 0.0, "#ff0000".to_string());
+            players.set_player_target("p1", 80.0, 0.0, 0.0);
+        }
+
+        // フレームごとに目標位置へ近づき、追い越さない
+        let mut last_x = 0.0;
+        for frame in 1..=10 {
+            world.get_resource_mut::<PlayerStateResource>().unwrap().now = frame as f64 * CURSOR_INTERPOLATION_MS / 5.0;
+            world.run_stages();
+            let x = world.get_resource::<PlayerStateResource>().unwrap().all_players()["p1"].x;
+            assert!(x >= last_x && x <= 80.0);
+            last_x = x;
+        }
+        assert_eq!(last_x, 80.0);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,290 @@
+/**
+ * HUD
+ *
+ * ゲーム画面のHUD（接続状態・タイマー・残り地雷数・進捗バー・遅延）をUIエンティティとして生成し、
+ * 表示内容と配置を更新するシステムをWorldへ登録するプラグイン
+ * ウィジェットは`HUD_WIDGETS`に1行追加するだけで増やせる
+ */
+use crate::components::{Anchor, Position, UIAnchor, UIElement};
+use crate::entities::{EntityId, EntityManager, Hierarchy};
+use crate::resources::{Color, HudResource, HudWidget, ResourceManager};
+use crate::system::system_registry::SystemPhase;
+use crate::system::IntoSystem;
+use super::plugin::{Plugin, UiPlugin};
+use super::World;
+
+/// HUDのUIエンティティに付けるタグ
+pub const HUD_TAG: &str = "hud";
+
+/// 表示内容の更新システムの名前
+pub const HUD_CONTENT_SYSTEM_NAME: &str = "HudContentSystem";
+
+/// 配置システムの名前
+pub const HUD_LAYOUT_SYSTEM_NAME: &str = "HudLayoutSystem";
+
+/// HUDの文字の大きさ
+const HUD_FONT_SIZE: f64 = 16.0;
+
+/// 接続状態のインジケーターの大きさ（半径）
+const INDICATOR_SIZE: f64 = 10.0;
+
+/// 進捗バーの色
+const PROGRESS_BAR_COLOR: Color = Color(0x4CAF50);
+
+/// ウィジェットの見た目
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HudStyle {
+    /// 文言のみ
+    Text,
+    /// 文言の左にインジケーター（丸）を表示
+    IndicatorText,
+    /// 進捗バー
+    Bar { width: f64, height: f64 },
+}
+
+/// HUDのウィジェットの定義
+#[derive(Debug, Clone, Copy)]
+pub struct HudWidgetSpec {
+    /// ウィジェットの種類
+    pub widget: HudWidget,
+    /// キャンバス上の配置
+    pub anchor: UIAnchor,
+    /// 見た目
+    pub style: HudStyle,
+}
+
+/// ゲーム画面に表示するウィジェット
+pub const HUD_WIDGETS: [HudWidgetSpec; 5] = [
+    HudWidgetSpec {
+        widget: HudWidget::Connection,
+        anchor: UIAnchor::new(Anchor::TopLeft, 50.0, 30.0),
+        style: HudStyle::IndicatorText,
+    },
+    HudWidgetSpec {
+        widget: HudWidget::Progress,
+        anchor: UIAnchor::new(Anchor::TopCenter, -150.0, 4.0),
+        style: HudStyle::Bar { width: 300.0, height: 6.0 },
+    },
+    HudWidgetSpec {
+        widget: HudWidget::Timer,
+        anchor: UIAnchor::new(Anchor::BottomLeft, 20.0, -60.0),
+        style: HudStyle::Text,
+    },
+    HudWidgetSpec {
+        widget: HudWidget::MineCounter,
+        anchor: UIAnchor::new(Anchor::BottomLeft, 20.0, -30.0),
+        style: HudStyle::Text,
+    },
+    HudWidgetSpec {
+        widget: HudWidget::Latency,
+        anchor: UIAnchor::new(Anchor::BottomRight, -110.0, -30.0),
+        style: HudStyle::IndicatorText,
+    },
+];
+
+/// ウィジェットの定義からHUDのUIエンティティを生成
+/// 
+/// 各ウィジェットは文言（または進捗バー）のエンティティを親にし、インジケーターは子として左側に置く。
+/// 生成した親エンティティのIDを返す
+pub fn spawn_hud(entities: &mut EntityManager, widgets: &[HudWidgetSpec]) -> Vec<EntityId> {
+    widgets.iter().map(|spec| {
+        let element = match spec.style {
+            HudStyle::Bar { width, height } => UIElement::ProgressBar {
+                width,
+                height,
+                progress: 0.0,
+                color: Color::WHITE,
+            },
+            HudStyle::Text | HudStyle::IndicatorText => UIElement::Text {
+                content: String::new(),
+                font: "Arial".to_string(),
+                size: HUD_FONT_SIZE,
+                color: Color::WHITE,
+            },
+        };
+        let label = entities.create_builder()
+            .with_component(Position::new(0.0, 0.0))
+            .with_component(Hierarchy::new())
+            .with_component(spec.anchor)
+            .with_component(spec.widget)
+            .with_component(element)
+            .with_tag(HUD_TAG)
+            .build();
+        let label = entities.register_entity(label);
+        
+        if spec.style == HudStyle::IndicatorText {
+            let indicator = entities.create_builder()
+                .with_component(Position::new(-20.0, 0.0))
+                .with_component(spec.widget)
+                .with_component(UIElement::Icon {
+                    name: "indicator".to_string(),
+                    size: INDICATOR_SIZE,
+                    color: Color::WHITE,
+                })
+                .with_tag(HUD_TAG)
+                .build();
+            let indicator = entities.register_entity(indicator);
+            let _ = entities.set_parent(indicator, label);
+        }
+        
+        label
+    }).collect()
+}
+
+/// HUDリソースの値をUIエンティティの文言と色に反映する
+/// 
+/// 前回の実行からHUDリソースが変わっていなければ何もしない
+pub fn hud_content_system(entities: &mut EntityManager, resources: &mut ResourceManager) {
+    if !resources.is_changed::<HudResource>() {
+        return;
+    }
+    let Some(hud) = resources.get::<HudResource>() else {
+        return;
+    };
+    
+    for id in entities.query_with_component_and_tag::<HudWidget>(HUD_TAG) {
+        let Some(entity) = entities.get_entity_mut(id) else {
+            continue;
+        };
+        let Some(widget) = entity.get_component::<HudWidget>().copied() else {
+            continue;
+        };
+        
+        match entity.get_component_mut::<UIElement>() {
+            Some(UIElement::Text { content, color, .. }) => {
+                *content = hud.text(widget);
+                *color = hud.theme.text_color();
+            },
+            Some(UIElement::Icon { color, .. }) => {
+                *color = hud.widget_indicator_color(widget);
+            },
+            Some(UIElement::ProgressBar { progress, color, .. }) => {
+                *progress = hud.progress;
+                *color = PROGRESS_BAR_COLOR;
+            },
+            _ => {}
+        }
+    }
+}
+
+/// キャンバスの大きさに合わせて`UIAnchor`を持つUIエンティティを配置する
+/// 
+/// 子のエンティティは`UiPlugin`の位置伝播で親に追従する。
+/// 前回の実行からHUDリソースが変わっていなければ配置し直さない
+pub fn hud_layout_system(entities: &mut EntityManager, resources: &mut ResourceManager) {
+    if !resources.is_changed::<HudResource>() {
+        return;
+    }
+    let Some(hud) = resources.get::<HudResource>() else {
+        return;
+    };
+    
+    for id in entities.get_entities_with_component::<UIAnchor>() {
+        if let Some(entity) = entities.get_entity_mut(id) {
+            if let Some(anchor) = entity.get_component::<UIAnchor>().copied() {
+                entity.add_component(anchor.resolve(hud.canvas_width, hud.canvas_height));
+            }
+        }
+    }
+}
+
+/// HUDのリソース・UIエンティティ・システムを登録するプラグイン
+#[derive(Debug, Default, Clone, Copy)]
+pub struct HudPlugin;
+
+impl Plugin for HudPlugin {
+    fn build(&self, world: &mut World) {
+        // 配置したあとに子の位置を伝播させる
+        world.add_plugin(UiPlugin);
+        
+        if !world.has_resource::<HudResource>() {
+            world.insert_resource(HudResource::new());
+        }
+        spawn_hud(world.entities_mut(), &HUD_WIDGETS);
+        
+        world.systems_mut().add_fn_system(HUD_CONTENT_SYSTEM_NAME, SystemPhase::Update, hud_content_system);
+        world.add_system(
+            hud_layout_system
+                .into_system(HUD_LAYOUT_SYSTEM_NAME, SystemPhase::PostUpdate)
+                .with_priority(-1)
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::GlobalPosition;
+
+    fn widget_position(world: &World, widget: HudWidget, icon: bool) -> Option<GlobalPosition> {
+        let entities = world.entities();
+        entities.query_with_component_and_tag::<HudWidget>(HUD_TAG).into_iter()
+            .filter_map(|id| entities.get_entity(id))
+            .filter(|entity| entity.get_component::<HudWidget>() == Some(&widget))
+            .find(|entity| matches!(entity.get_component::<UIElement>(), Some(UIElement::Icon { .. })) == icon)
+            .and_then(|entity| entity.get_component::<GlobalPosition>().copied())
+    }
+
+    #[test]
+    fn test_hud_widgets_follow_canvas_size() {
+        let mut world = World::new();
+        world.add_plugin(HudPlugin);
+        assert!(world.systems().has_system(HUD_LAYOUT_SYSTEM_NAME));
+        assert!(world.systems().has_system(HUD_CONTENT_SYSTEM_NAME));
+
+        let hud = world.get_resource_mut::<HudResource>().unwrap();
+        hud.canvas_width = 800.0;
+        hud.canvas_height = 600.0;
+        hud.remaining_mines = 40;
+        world.run_stages();
+
+        assert_eq!(widget_position(&world, HudWidget::Timer, false), Some(GlobalPosition::new(20.0, 540.0)));
+        assert_eq!(widget_position(&world, HudWidget::Connection, true), Some(GlobalPosition::new(30.0, 30.0)));
+        assert_eq!(widget_position(&world, HudWidget::Latency, false), Some(GlobalPosition::new(690.0, 570.0)));
+
+        // キャンバスが変わると下端基準のウィジェットが追従する
+        world.get_resource_mut::<HudResource>().unwrap().canvas_height = 400.0;
+        world.run_stages();
+        assert_eq!(widget_position(&world, HudWidget::MineCounter, false), Some(GlobalPosition::new(20.0, 370.0)));
+
+        let counter = world.entities().query_with_component_and_tag::<HudWidget>(HUD_TAG).into_iter()
+            .filter_map(|id| world.entities().get_entity(id))
+            .find(|entity| entity.get_component::<HudWidget>() == Some(&HudWidget::MineCounter))
+            .and_then(|entity| entity.get_component::<UIElement>().cloned());
+        assert!(matches!(counter, Some(UIElement::Text { content, .. }) if content == "残り地雷 40"));
+
+        // 進捗バーは上端の中央に置かれ、割合が反映される
+        world.get_resource_mut::<HudResource>().unwrap().progress = 0.25;
+        world.run_stages();
+        assert_eq!(widget_position(&world, HudWidget::Progress, false), Some(GlobalPosition::new(250.0, 4.0)));
+        let bar = world.entities().query_with_component_and_tag::<HudWidget>(HUD_TAG).into_iter()
+            .filter_map(|id| world.entities().get_entity(id))
+            .find(|entity| entity.get_component::<HudWidget>() == Some(&HudWidget::Progress))
+            .and_then(|entity| entity.get_component::<UIElement>().cloned());
+        assert!(matches!(bar, Some(UIElement::ProgressBar { progress, .. }) if progress == 0.25));
+    }
+
+    #[test]
+    fn test_hud_layout_skipped_while_resource_unchanged() {
+        let mut world = World::new();
+        world.add_plugin(HudPlugin);
+        world.run_stages();
+        let timer = world.entities().query_with_component_and_tag::<HudWidget>(HUD_TAG).into_iter()
+            .find(|&id| world.entities().get_entity(id)
+                .is_some_and(|entity| entity.get_component::<HudWidget>() == Some(&HudWidget::Timer)))
+            .unwrap();
+
+        // HUDリソースが変わらないフレームでは配置し直さないので、ずらした位置がそのまま残る
+        let moved = Position::new(-1.0, -1.0);
+        world.entities_mut().get_entity_mut(timer).unwrap().add_component(moved);
+        world.run_stages();
+        world.run_stages();
+        let position = world.entities().get_entity(timer).unwrap().get_component::<Position>().copied().unwrap();
+        assert_eq!((position.x, position.y), (moved.x, moved.y));
+
+        // 変わった次のフレームで配置し直される
+        world.get_resource_mut::<HudResource>().unwrap().canvas_height = 400.0;
+        world.run_stages();
+        assert_eq!(widget_position(&world, HudWidget::Timer, false), Some(GlobalPosition::new(20.0, 340.0)));
+    }
+}
\ No newline at end of file
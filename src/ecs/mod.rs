@@ -3,7 +3,20 @@
  * 
  * ゲームエンジンのコアとなるECSアーキテクチャを実装するモジュール
  */
-
 pub mod world;
+pub mod plugin;
+pub mod hud;
+pub mod result_sequence;
+pub mod bot;
+pub mod cursor;
+#[cfg(test)]
+pub mod test_world;
 
-pub use world::World; 
\ No newline at end of file
+pub use world::World;
+pub use plugin::{Plugin, CorePlugin, BoardPlugin, UiPlugin};
+pub use hud::{HudPlugin, HUD_TAG, HUD_WIDGETS};
+pub use result_sequence::{ResultSequencePlugin, RESULT_SEQUENCE_SYSTEM_NAME};
+pub use bot::{BotPlugin, BOT_SYSTEM_NAME};
+pub use cursor::{CursorPlugin, CURSOR_SYSTEM_NAME};
+#[cfg(test)]
+pub use test_world::TestWorld; 
\ No newline at end of file
@@ -0,0 +1,232 @@
+/**
+ * プラグイン
+ *
+ * 関連するリソースとシステムをひとまとめにしてWorldへ登録する仕組み
+ * ヘッドレスのシミュレーションとHUDのWorldはプラグインの登録の並びとして組み立てる
+ */
+use crate::entities::{cell_operations, EntityManager};
+use crate::resources::{
+    BoardConfigResource, BoardStateResource, CoreGameResource, GameConfigResource,
+    NetworkState, PlayerStateResource, ResourceManager, TimeResource,
+};
+use crate::system::system_registry::SystemPhase;
+use super::World;
+use crate::logger::log_error;
+
+/// リソースとシステムをまとめてWorldに登録するプラグイン
+pub trait Plugin {
+    /// プラグイン名（重複登録の判定に使用）
+    fn name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
+
+    /// Worldにリソースとシステムを登録
+    fn build(&self, world: &mut World);
+}
+
+/// リソースが未登録の場合のみ追加
+fn insert_if_missing<T: 'static>(world: &mut World, create: impl FnOnce(&World) -> T) {
+    if !world.has_resource::<T>() {
+        let resource = create(world);
+        world.insert_resource(resource);
+    }
+}
+
+/// ゲーム進行・時間・プレイヤー・設定のコアリソースを登録するプラグイン
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CorePlugin;
+
+impl Plugin for CorePlugin {
+    fn build(&self, world: &mut World) {
+        insert_if_missing(world, |_| CoreGameResource::new());
+        insert_if_missing(world, |_| TimeResource::new());
+        insert_if_missing(world, |_| PlayerStateResource::new());
+        insert_if_missing(world, |_| GameConfigResource::new());
+    }
+}
+
+/// ボードのリソースと勝利判定システムを登録するプラグイン
+#[derive(Debug, Default, Clone, Copy)]
+pub struct BoardPlugin;
+
+/// 勝利判定システムの名前
+pub const BOARD_WIN_CHECK_SYSTEM_NAME: &str = "BoardWinCheckSystem";
+
+/// フラグ切り替えシステムの名前
+pub const FLAG_TOGGLE_SYSTEM_NAME: &str = "FlagToggleSystem";
+
+impl BoardPlugin {
+    /// 全ての安全なセルが開かれたらゲームを勝利で終了する
+    fn check_win(_entities: &mut EntityManager, resources: &mut ResourceManager) {
+        let cleared = resources
+            .get::<BoardStateResource>()
+            .is_some_and(|board| board.mines_placed() && board.all_safe_cells_revealed());
+        if !cleared {
+            return;
+        }
+
+        if let Some(core_game) = resources.get_mut::<CoreGameResource>() {
+            if core_game.is_playing() {
+                core_game.end_game(true);
+            }
+        }
+    }
+}
+
+impl BoardPlugin {
+    /// 求められたフラグの切り替えを反映する
+    /// 接続している場合はサーバーに送り、全員に届く`flag_toggled`で反映する
+    fn toggle_flags(entities: &mut EntityManager, resources: &mut ResourceManager) {
+        let Some(requests) = resources.get_mut::<BoardStateResource>().map(BoardStateResource::take_flag_requests) else {
+            return;
+        };
+
+        let updates = match resources.get_mut::<NetworkState>() {
+            Some(network) => {
+                for index in requests {
+                    if let Err(e) = network.send_toggle_flag(index) {
+                        log_error!("Flag toggle send error: {:?}", e);
+                    }
+                }
+                network.take_flag_updates()
+            },
+            // 一人で遊ぶ場合はその場で切り替える
+            None => {
+                let Some(board) = resources.get::<BoardStateResource>() else {
+                    return;
+                };
+                requests.into_iter()
+                    .filter_map(|index| {
+                        let (x, y) = board.coords_of(index)?;
+                        let state = cell_operations::get_cell_state(entities, board.cell_entity(x, y)?)?;
+                        Some((index, !state.is_flagged))
+                    })
+                    .collect()
+            },
+        };
+
+        let Some(board) = resources.get_mut::<BoardStateResource>() else {
+            return;
+        };
+        for (index, flagged) in updates {
+            let Some(id) = board.coords_of(index).and_then(|(x, y)| board.cell_entity(x, y)) else {
+                continue;
+            };
+            if cell_operations::set_flag(entities, id, flagged) {
+                board.record_flag(flagged);
+            }
+        }
+    }
+}
+
+impl Plugin for BoardPlugin {
+    fn build(&self, world: &mut World) {
+        insert_if_missing(world, |_| GameConfigResource::new());
+
+        // ボード設定はゲーム設定のボード寸法から作成
+        insert_if_missing(world, |world| {
+            world.get_resource::<GameConfigResource>()
+                .map(BoardConfigResource::from_game_config)
+                .unwrap_or_default()
+        });
+        insert_if_missing(world, |world| {
+            world.get_resource::<BoardConfigResource>()
+                .map(BoardStateResource::new)
+                .unwrap_or_default()
+        });
+
+        world.systems_mut().add_fn_system(
+            FLAG_TOGGLE_SYSTEM_NAME,
+            SystemPhase::Update,
+            Self::toggle_flags,
+        );
+        world.systems_mut().add_fn_system(
+            BOARD_WIN_CHECK_SYSTEM_NAME,
+            SystemPhase::PostUpdate,
+            Self::check_win,
+        );
+    }
+}
+
+/// UI要素の配置を親子関係に沿って更新するプラグイン
+#[derive(Debug, Default, Clone, Copy)]
+pub struct UiPlugin;
+
+/// 位置伝播システムの名前
+pub const UI_LAYOUT_SYSTEM_NAME: &str = "UiLayoutSystem";
+
+impl Plugin for UiPlugin {
+    fn build(&self, world: &mut World) {
+        world.systems_mut().add_fn_system(
+            UI_LAYOUT_SYSTEM_NAME,
+            SystemPhase::PostUpdate,
+            |entities: &mut EntityManager, _resources: &mut ResourceManager| {
+                entities.propagate_positions();
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::CellContent;
+    use crate::entities::create_cell_entity;
+
+    #[test]
+    fn test_plugins_register_resources_and_systems() {
+        let mut world = World::new();
+        world
+            .add_plugin(CorePlugin)
+            .add_plugin(BoardPlugin)
+            .add_plugin(UiPlugin);
+
+        assert!(world.has_resource::<CoreGameResource>());
+        assert!(world.has_resource::<BoardStateResource>());
+        assert!(world.systems().has_system(BOARD_WIN_CHECK_SYSTEM_NAME));
+        assert!(world.systems().has_system(FLAG_TOGGLE_SYSTEM_NAME));
+        assert!(world.systems().has_system(UI_LAYOUT_SYSTEM_NAME));
+
+        // 同じプラグインは二重に登録されない
+        world.add_plugin(BoardPlugin);
+        assert_eq!(world.systems().len(), 3);
+        assert!(world.has_plugin(BoardPlugin.name()));
+    }
+
+    #[test]
+    fn test_board_plugin_ends_game_when_cleared() {
+        let mut world = World::new();
+        world.add_plugin(CorePlugin).add_plugin(BoardPlugin);
+        world.get_resource_mut::<CoreGameResource>().unwrap().start_game();
+
+        let board = world.get_resource_mut::<BoardStateResource>().unwrap();
+        board.mark_mines_placed();
+        while !board.all_safe_cells_revealed() {
+            board.record_safe_reveal();
+        }
+
+        world.run_stages();
+        assert!(world.get_resource::<CoreGameResource>().unwrap().is_win());
+    }
+    #[test]
+    fn test_flag_toggle_system_toggles_locally_without_network() {
+        let mut world = World::new();
+        world.add_plugin(CorePlugin).add_plugin(BoardPlugin);
+        let builder = world.entities_mut().create_builder();
+        let cell = create_cell_entity(builder, 0, 1, CellContent::Empty(0));
+        let id = world.entities_mut().register_entity(cell);
+        let board = world.get_resource_mut::<BoardStateResource>().unwrap();
+        board.set_cell_entity(1, 0, id);
+        board.request_flag_toggle(1);
+
+        world.run_stages();
+        assert!(cell_operations::get_cell_state(world.entities(), id).unwrap().is_flagged);
+        assert_eq!(world.get_resource::<BoardStateResource>().unwrap().flag_count(), 1);
+
+        // もう一度求めると外れる
+        world.get_resource_mut::<BoardStateResource>().unwrap().request_flag_toggle(1);
+        world.run_stages();
+        assert!(!cell_operations::get_cell_state(world.entities(), id).unwrap().is_flagged);
+        assert_eq!(world.get_resource::<BoardStateResource>().unwrap().flag_count(), 0);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,55 @@
+/**
+ * 結果演出
+ *
+ * ゲーム終了時の演出（ボードの走査→結果パネルのスライド）の進行を
+ * `ResultSequenceResource`の時刻から更新するシステムをWorldへ登録するプラグイン
+ */
+use crate::entities::EntityManager;
+use crate::resources::{ResourceManager, ResultSequenceResource};
+use crate::system::system_registry::SystemPhase;
+use super::plugin::Plugin;
+use super::World;
+
+/// 結果演出システムの名前
+pub const RESULT_SEQUENCE_SYSTEM_NAME: &str = "ResultSequenceSystem";
+
+/// 結果演出の段階と進み具合を現在時刻に合わせて更新する
+pub fn result_sequence_system(_entities: &mut EntityManager, resources: &mut ResourceManager) {
+    if let Some(sequence) = resources.get_mut::<ResultSequenceResource>() {
+        sequence.update();
+    }
+}
+
+/// 結果演出のリソースとシステムを登録するプラグイン
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ResultSequencePlugin;
+
+impl Plugin for ResultSequencePlugin {
+    fn build(&self, world: &mut World) {
+        if !world.has_resource::<ResultSequenceResource>() {
+            world.insert_resource(ResultSequenceResource::new());
+        }
+        world.systems_mut().add_fn_system(RESULT_SEQUENCE_SYSTEM_NAME, SystemPhase::Update, result_sequence_system);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::{ResultPhase, RESULT_SWEEP_MS};
+
+    #[test]
+    fn test_system_advances_sequence() {
+        let mut world = World::new();
+        world.add_plugin(ResultSequencePlugin);
+        assert!(world.systems().has_system(RESULT_SEQUENCE_SYSTEM_NAME));
+
+        world.get_resource_mut::<ResultSequenceResource>().unwrap().start(0.0);
+        world.get_resource_mut::<ResultSequenceResource>().unwrap().now = RESULT_SWEEP_MS + 1.0;
+        world.run_stages();
+
+        let sequence = world.get_resource::<ResultSequenceResource>().unwrap();
+        assert_eq!(sequence.phase(), ResultPhase::Slide);
+        assert_eq!(sequence.sweep_progress(), 1.0);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,245 @@
+/**
+ * ヘッドレステスト用World
+ *
+ * ブラウザなしで`cargo test`からシステムを実行するためのユーティリティ
+ * 描画・通信はモックリソースに記録される
+ */
+use crate::entities::EntityManager;
+use crate::resources::ResourceManager;
+use crate::system::{IntoSystem, System};
+use crate::system::system_registry::{SystemId, SystemPhase};
+use super::{Plugin, World};
+
+/// 描画命令を記録するモックリソース
+#[derive(Debug, Default)]
+pub struct MockRenderResource {
+    /// 記録された描画命令
+    pub draw_calls: Vec<String>,
+}
+
+impl MockRenderResource {
+    /// 描画命令を記録
+    pub fn draw(&mut self, command: &str) {
+        self.draw_calls.push(command.to_string());
+    }
+}
+
+/// 送信メッセージを記録するモックリソース
+#[derive(Debug)]
+pub struct MockNetworkResource {
+    /// 接続状態
+    pub is_connected: bool,
+    /// 送信されたメッセージ
+    pub sent_messages: Vec<String>,
+    /// システムに受信させるメッセージ
+    pub incoming_messages: Vec<String>,
+}
+
+impl Default for MockNetworkResource {
+    fn default() -> Self {
+        Self {
+            is_connected: true,
+            sent_messages: Vec::new(),
+            incoming_messages: Vec::new(),
+        }
+    }
+}
+
+impl MockNetworkResource {
+    /// メッセージを送信（記録のみ）
+    pub fn send(&mut self, message: &str) {
+        self.sent_messages.push(message.to_string());
+    }
+
+    /// 受信メッセージを取り出す
+    pub fn take_incoming(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.incoming_messages)
+    }
+}
+
+/// システムをネイティブ環境で実行するためのテスト用World
+pub struct TestWorld {
+    /// テスト対象のWorld
+    world: World,
+    /// Startupフェーズを実行済みかどうか
+    started: bool,
+}
+
+impl Default for TestWorld {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TestWorld {
+    /// デフォルトリソースとモックリソースを持つTestWorldを作成
+    pub fn new() -> Self {
+        let mut world = World::new();
+        world.setup_default_resources();
+        world.insert_resource(MockRenderResource::default());
+        world.insert_resource(MockNetworkResource::default());
+
+        Self {
+            world,
+            started: false,
+        }
+    }
+
+    /// Worldを取得（不変）
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+
+    /// Worldを取得（可変）
+    pub fn world_mut(&mut self) -> &mut World {
+        &mut self.world
+    }
+
+    /// プラグインを登録（既に登録済みのリソースはそのまま使われる）
+    pub fn add_plugin<P: Plugin>(&mut self, plugin: P) -> &mut Self {
+        self.world.add_plugin(plugin);
+        self
+    }
+
+    /// システムを追加
+    pub fn add_system<S: System + 'static>(&mut self, system: S) -> SystemId {
+        self.world.add_system(system)
+    }
+
+    /// 関数やクロージャをシステムとして追加
+    pub fn add_fn_system<F: IntoSystem>(&mut self, name: &str, phase: SystemPhase, func: F) -> SystemId {
+        self.world.systems_mut().add_fn_system(name, phase, func)
+    }
+
+    /// 指定フェーズのシステムを実行
+    pub fn run_phase(&mut self, phase: SystemPhase) {
+        self.world.run_stage(phase);
+    }
+
+    /// 1フレーム分のシステムを実行（初回はStartupフェーズも実行）
+    pub fn run_frame(&mut self) {
+        if !self.started {
+            self.run_phase(SystemPhase::Startup);
+            self.started = true;
+        }
+
+        self.world.run_stages();
+    }
+
+    /// 指定したフレーム数だけ実行
+    pub fn run_frames(&mut self, count: usize) {
+        for _ in 0..count {
+            self.run_frame();
+        }
+    }
+
+    /// 記録された描画命令を取得
+    pub fn draw_calls(&self) -> &[String] {
+        self.world
+            .get_resource::<MockRenderResource>()
+            .map_or(&[], |render| render.draw_calls.as_slice())
+    }
+
+    /// 送信されたメッセージを取得
+    pub fn sent_messages(&self) -> &[String] {
+        self.world
+            .get_resource::<MockNetworkResource>()
+            .map_or(&[], |network| network.sent_messages.as_slice())
+    }
+
+    /// 受信メッセージを追加
+    pub fn push_incoming_message(&mut self, message: &str) {
+        if let Some(network) = self.world.get_resource_mut::<MockNetworkResource>() {
+            network.incoming_messages.push(message.to_string());
+        }
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{CellContent, CellState};
+    use crate::ecs::BoardPlugin;
+    use crate::entities::{cell_operations, create_cell_entity};
+    use crate::resources::{BoardStateResource, CoreGameResource, GamePhase, PhaseChanged};
+
+    #[test]
+    fn test_systems_run_headless() {
+        let mut test_world = TestWorld::new();
+
+        // Startupでセルを生成
+        test_world.add_fn_system("SpawnBoard", SystemPhase::Startup, |entities: &mut EntityManager, _resources: &mut ResourceManager| {
+            for _ in 0..4 {
+                let cell = entities.create_builder().with_component(CellState::new()).build();
+                entities.register_entity(cell);
+            }
+        });
+
+        // 受信メッセージを処理して全セル開示で勝利判定
+        test_world.add_fn_system("WinCheck", SystemPhase::Update, |entities: &mut EntityManager, resources: &mut ResourceManager| {
+            let incoming = resources.get_mut::<MockNetworkResource>().unwrap().take_incoming();
+            if incoming.iter().any(|message| message == "reveal_all") {
+                for id in entities.get_entities_with_component::<CellState>() {
+                    if let Some(entity) = entities.get_entity_mut(id) {
+                        entity.add_component(CellState::revealed());
+                    }
+                }
+                resources.get_mut::<CoreGameResource>().unwrap().end_game(true);
+                resources.get_mut::<MockNetworkResource>().unwrap().send("game_over");
+            }
+        });
+
+        // 描画はモックに記録
+        test_world.add_fn_system("Render", SystemPhase::Render, |entities: &mut EntityManager, resources: &mut ResourceManager| {
+            let count = entities.get_entities_with_component::<CellState>().len();
+            resources.get_mut::<MockRenderResource>().unwrap().draw(&format!("cells:{}", count));
+        });
+
+        test_world.run_frame();
+        assert_eq!(test_world.world().entities().entity_count(), 4);
+        assert!(test_world.sent_messages().is_empty());
+
+        test_world.push_incoming_message("reveal_all");
+        test_world.run_frame();
+
+        let core_game = test_world.world().get_resource::<CoreGameResource>().unwrap();
+        assert!(core_game.is_win());
+        assert_eq!(test_world.sent_messages(), &["game_over".to_string()]);
+        assert_eq!(test_world.draw_calls(), &["cells:4".to_string(), "cells:4".to_string()]);
+    }
+
+    #[test]
+    fn test_board_plugin_systems_run_headless() {
+        let mut test_world = TestWorld::new();
+        test_world.add_plugin(BoardPlugin);
+
+        // Startupで左上のセルを生成してボードに登録
+        test_world.add_fn_system("SpawnCell", SystemPhase::Startup, |entities: &mut EntityManager, resources: &mut ResourceManager| {
+            let cell = create_cell_entity(entities.create_builder(), 0, 0, CellContent::Empty(1));
+            let id = entities.register_entity(cell);
+            resources.get_mut::<BoardStateResource>().unwrap().set_cell_entity(0, 0, id);
+        });
+
+        // フラグの要求はBoardPluginのシステムが処理する
+        test_world.world_mut().get_resource_mut::<BoardStateResource>().unwrap().request_flag_toggle(0);
+        test_world.run_frame();
+        let id = test_world.world().get_resource::<BoardStateResource>().unwrap().cell_entity(0, 0).unwrap();
+        assert!(cell_operations::get_cell_state(test_world.world().entities(), id).unwrap().is_flagged);
+        assert_eq!(test_world.world().get_resource::<BoardStateResource>().unwrap().flag_count(), 1);
+        assert!(!test_world.world().get_resource::<CoreGameResource>().unwrap().is_game_over());
+
+        // 安全なセルを全て開くと勝利判定のシステムがゲームを終える
+        test_world.world_mut().get_resource_mut::<CoreGameResource>().unwrap().start_game();
+        let board = test_world.world_mut().get_resource_mut::<BoardStateResource>().unwrap();
+        board.mark_mines_placed();
+        while !board.all_safe_cells_revealed() {
+            board.record_safe_reveal();
+        }
+        test_world.run_frame();
+
+        let core_game = test_world.world_mut().get_resource_mut::<CoreGameResource>().unwrap();
+        assert!(core_game.is_win());
+        assert_eq!(core_game.drain_phase_events().last(), Some(&PhaseChanged { from: GamePhase::Playing, to: GamePhase::GameOver { win: true } }));
+    }
+}
\ No newline at end of file
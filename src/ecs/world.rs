@@ -4,8 +4,11 @@
  * ECSアーキテクチャの中心となるWorldクラス
  * エンティティとリソースの管理を一元化
  */
-use crate::entities::EntityManager;
+use crate::entities::{EntityId, EntityManager};
 use crate::resources::ResourceManager;
+use crate::system::{Stage, System, SystemRegistry};
+use crate::system::system_registry::SystemId;
+use super::plugin::Plugin;
 
 /// World構造体 - ECSの中心的なコンテナ
 #[derive(Debug)]
@@ -14,6 +17,10 @@ pub struct World {
     entity_manager: EntityManager,
     /// リソースマネージャー
     resource_manager: ResourceManager,
+    /// システムレジストリ
+    systems: SystemRegistry,
+    /// 登録済みプラグイン名
+    plugins: Vec<String>,
 }
 
 impl Default for World {
@@ -21,6 +28,8 @@ impl Default for World {
         Self {
             entity_manager: EntityManager::new(),
             resource_manager: ResourceManager::new(),
+            systems: SystemRegistry::new(),
+            plugins: Vec::new(),
         }
     }
 }
@@ -41,6 +50,119 @@ impl World {
         &mut self.entity_manager
     }
     
+    /// エンティティと全ての子孫を削除
+    /// 
+    /// ボードやUI画面の破棄に使用する。削除したエンティティの数を返す
+    pub fn despawn_recursive(&mut self, entity: EntityId) -> usize {
+        self.entity_manager.despawn_recursive(entity)
+    }
+    
+    /// システムを追加（システム自身のフェーズのステージに登録）
+    pub fn add_system<S: System + 'static>(&mut self, system: S) -> SystemId {
+        self.systems.add_system(Box::new(system))
+    }
+    
+    /// 指定したステージにシステムを追加
+    pub fn add_system_to_stage<S: System + 'static>(&mut self, stage: Stage, system: S) -> SystemId {
+        self.systems.add_system_to_stage(stage, Box::new(system))
+    }
+    
+    /// システムレジストリを取得（不変）
+    pub fn systems(&self) -> &SystemRegistry {
+        &self.systems
+    }
+    
+    /// システムレジストリを取得（可変）
+    pub fn systems_mut(&mut self) -> &mut SystemRegistry {
+        &mut self.systems
+    }
+    
+    /// プラグインを登録（同じ名前のプラグインは一度だけ登録される）
+    pub fn add_plugin<P: Plugin>(&mut self, plugin: P) -> &mut Self {
+        if !self.has_plugin(plugin.name()) {
+            self.plugins.push(plugin.name().to_string());
+            plugin.build(self);
+        }
+        self
+    }
+    
+    /// 指定した名前のプラグインが登録済みかどうか
+    pub fn has_plugin(&self, name: &str) -> bool {
+        self.plugins.iter().any(|plugin| plugin == name)
+    }
+    
+    /// 決定論的モードを有効化
+    /// 
+    /// システムを固定順序で実行し、シード付き乱数と固定フレーム時間を設定する。
+    /// 同じ入力列からは常に同じ状態になるため、リプレイや非同期のデバッグに使う
+    pub fn enable_deterministic_mode(&mut self, seed: u64, fixed_delta: f64) {
+        use crate::resources::{RngResource, TimeResource};
+        
+        self.systems.set_deterministic(true);
+        self.insert_resource(RngResource::new(seed));
+        
+        if !self.has_resource::<TimeResource>() {
+            self.insert_resource(TimeResource::new());
+        }
+        if let Some(time) = self.get_resource_mut::<TimeResource>() {
+            time.set_fixed_delta(Some(fixed_delta));
+        }
+    }
+    
+    /// 決定論的モードかどうか
+    pub fn is_deterministic(&self) -> bool {
+        self.systems.is_deterministic()
+    }
+    
+    /// Startupステージのシステムを実行
+    pub fn run_startup(&mut self) {
+        self.run_stage(Stage::Startup);
+    }
+    
+    /// 指定したステージのシステムを実行
+    pub fn run_stage(&mut self, stage: Stage) {
+        let systems = &mut self.systems;
+        Self::with_entities_in_resources(
+            &mut self.entity_manager,
+            &mut self.resource_manager,
+            |resources| systems.run_phase(stage, resources),
+        );
+    }
+    
+    /// 毎フレームのステージを順番に実行し、削除予約されたエンティティを片付ける
+    /// 
+    /// リソースの変更はシステムごとに前回の実行時から判定するため、フレームの終わりに消すことはしない
+    /// （後のステージやフレーム間（JS APIなど）での変更も、各システムの次の実行で変更済みに見える）
+    pub fn run_stages(&mut self) {
+        let systems = &mut self.systems;
+        Self::with_entities_in_resources(
+            &mut self.entity_manager,
+            &mut self.resource_manager,
+            |resources| systems.run_all_phases(resources),
+        );
+        self.entity_manager.flush_removals();
+    }
+    
+    /// エンティティマネージャーを一時的にリソースとして登録して処理を実行
+    /// 
+    /// 関数システムはリソースからエンティティマネージャーを取り出すため
+    fn with_entities_in_resources<F>(
+        entities: &mut EntityManager,
+        resources: &mut ResourceManager,
+        f: F,
+    )
+    where
+        F: FnOnce(&mut ResourceManager),
+    {
+        resources.insert(std::mem::take(entities));
+        
+        f(resources);
+        
+        if let Some(returned) = resources.remove::<EntityManager>() {
+            *entities = returned;
+        }
+    }
+    
     /// リソースを取得（不変）
     pub fn get_resource<T: 'static>(&self) -> Option<&T> {
         self.resource_manager.get::<T>()
@@ -56,6 +178,11 @@ impl World {
         self.resource_manager.insert(resource);
     }
     
+    /// リソースが最後の`ResourceManager::clear_changes`より後に変更されたかどうか（システムの外から確認する）
+    pub fn is_resource_changed<T: 'static>(&self) -> bool {
+        self.resource_manager.is_changed::<T>()
+    }
+    
     /// リソースが存在するかチェック
     pub fn has_resource<T: 'static>(&self) -> bool {
         self.resource_manager.contains::<T>()
@@ -74,6 +201,7 @@ impl World {
     /// 複数のリソースを一度に取得（一部可変）
     pub fn get_resources_mut<A: 'static, B: 'static>(&mut self) -> Option<(&A, &mut B)> {
         self.resource_manager.get_multi_mut::<A, B>()
+            .map(|(a, b)| (&*a, b))
     }
     
     /// リソースマネージャーを取得（不変）
@@ -89,7 +217,7 @@ impl World {
     /// リソースバッチ処理（読み取り専用）
     pub fn with_resources<F, R>(&self, f: F) -> R
     where
-        F: FnOnce(&crate::resources::ResourceBatch) -> R,
+        F: FnOnce(crate::resources::ResourceBatch) -> R,
     {
         self.resource_manager.batch(f)
     }
@@ -97,18 +225,33 @@ impl World {
     /// リソースバッチ処理（読み書き）
     pub fn with_resources_mut<F, R>(&mut self, f: F) -> R
     where
-        F: FnOnce(&mut crate::resources::ResourceBatchMut) -> R,
+        F: FnOnce(crate::resources::ResourceBatchMut) -> R,
     {
         self.resource_manager.batch_mut(f)
     }
     
+    /// 診断情報リソースを現在の状態で更新（フレームごとに呼び出す）
+    pub fn update_diagnostics(&mut self) {
+        use crate::resources::DiagnosticsResource;
+        
+        if let Some(mut diagnostics) = self.resource_manager.remove::<DiagnosticsResource>() {
+            // 取り出した診断リソース自身も数に含める
+            let resource_count = self.resource_manager.len() + 1;
+            diagnostics.update(&self.entity_manager, resource_count);
+            self.resource_manager.insert(diagnostics);
+        }
+    }
+    
     /// 初期リソースを追加
     pub fn setup_default_resources(&mut self) {
         use crate::resources::{
             CoreGameResource, 
             TimeResource, 
             PlayerStateResource, 
-            GameConfigResource
+            GameConfigResource,
+            DiagnosticsResource,
+            BoardConfigResource,
+            BoardStateResource
         };
         
         // コアゲームリソース
@@ -130,5 +273,175 @@ impl World {
         if !self.has_resource::<GameConfigResource>() {
             self.insert_resource(GameConfigResource::new());
         }
+        
+        // ボード設定リソース（ゲーム設定のボード寸法から作成）
+        if !self.has_resource::<BoardConfigResource>() {
+            let board_config = self.get_resource::<GameConfigResource>()
+                .map(BoardConfigResource::from_game_config)
+                .unwrap_or_default();
+            self.insert_resource(board_config);
+        }
+        
+        // ボード状態リソース
+        if !self.has_resource::<BoardStateResource>() {
+            let board_state = self.get_resource::<BoardConfigResource>()
+                .map(BoardStateResource::new)
+                .unwrap_or_default();
+            self.insert_resource(board_state);
+        }
+        
+        // 診断情報リソース
+        if !self.has_resource::<DiagnosticsResource>() {
+            self.insert_resource(DiagnosticsResource::new());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::Position;
+    use crate::entities::Hierarchy;
+    
+    #[test]
+    fn test_despawn_recursive() {
+        let mut world = World::new();
+        let entities = world.entities_mut();
+        
+        // 画面 -> パネル -> ボタン の階層を作成
+        let screen = entities.create_entity();
+        let panel = entities.create_entity();
+        let button = {
+            let builder = entities.create_builder();
+            let entity = builder
+                .with_component(Position::new(0.0, 0.0))
+                .with_tag("button")
+                .build();
+            entities.register_entity(entity)
+        };
+        let other = entities.create_entity();
+        
+        entities.set_parent(panel, screen).unwrap();
+        entities.set_parent(button, panel).unwrap();
+        entities.set_parent(other, screen).unwrap();
+        
+        // パネル以下を削除
+        assert_eq!(world.despawn_recursive(panel), 2);
+        
+        let entities = world.entities();
+        assert!(entities.get_entity(panel).is_none());
+        assert!(entities.get_entity(button).is_none());
+        assert!(entities.get_entities_with_tag("button").is_empty());
+        assert!(entities.get_entities_with_component::<Position>().is_empty());
+        
+        // 親の子リストから切り離されている
+        let hierarchy = entities.get_entity(screen).unwrap().get_component::<Hierarchy>().unwrap();
+        assert_eq!(hierarchy.children, vec![other]);
+        
+        // 残りを全て削除
+        assert_eq!(world.despawn_recursive(screen), 2);
+        assert_eq!(world.entities().entity_count(), 0);
+    }
+    
+    #[test]
+    fn test_deterministic_mode_reproduces_state() {
+        use crate::resources::{RngResource, TimeResource};
+        use crate::system::SystemPhase;
+        
+        fn run_world(seed: u64) -> Vec<(f64, f64)> {
+            let mut world = World::new();
+            world.enable_deterministic_mode(seed, 1.0 / 60.0);
+            
+            world.systems_mut().add_fn_system("Spawn", SystemPhase::Update, |entities: &mut EntityManager, resources: &mut ResourceManager| {
+                let x = resources.get_mut::<RngResource>().unwrap().gen_range(30) as f64;
+                let y = resources.get::<TimeResource>().unwrap().total_time;
+                let entity = entities.create_builder().with_component(Position::new(x, y)).build();
+                entities.register_entity(entity);
+            });
+            
+            for _ in 0..10 {
+                world.get_resource_mut::<TimeResource>().unwrap().begin_frame();
+                world.run_stages();
+            }
+            
+            let mut ids = world.entities().get_entities_with_component::<Position>();
+            ids.sort_by_key(|id| id.0);
+            ids.into_iter()
+                .map(|id| {
+                    let position = world.entities().get_entity(id).unwrap().get_component::<Position>().unwrap();
+                    (position.x, position.y)
+                })
+                .collect()
+        }
+        
+        assert_eq!(run_world(1234), run_world(1234));
+        assert_ne!(run_world(1234), run_world(5678));
+    }
+    
+    #[test]
+    fn test_resource_change_notification() {
+        use crate::resources::GameConfigResource;
+        use crate::system::SystemPhase;
+        
+        // セルレイアウトの再計算回数
+        struct LayoutRecalculations(usize);
+        
+        let mut world = World::new();
+        world.insert_resource(GameConfigResource::new());
+        world.insert_resource(LayoutRecalculations(0));
+        
+        world.systems_mut().add_fn_system("Layout", SystemPhase::Render, |_entities: &mut EntityManager, resources: &mut ResourceManager| {
+            if resources.is_changed::<GameConfigResource>() {
+                resources.get_mut::<LayoutRecalculations>().unwrap().0 += 1;
+            }
+        });
+        
+        // 初回は追加されたばかりなので再計算される
+        world.run_stages();
+        world.run_stages();
+        assert_eq!(world.get_resource::<LayoutRecalculations>().unwrap().0, 1);
+        
+        // 設定を書き換えた次のフレームだけ再計算される
+        world.resources_mut().write::<GameConfigResource>().unwrap().set_custom_board(20, 20, 50);
+        world.run_stages();
+        world.run_stages();
+        assert_eq!(world.get_resource::<LayoutRecalculations>().unwrap().0, 2);
+    }
+    
+    #[test]
+    fn test_late_stage_changes_are_seen_next_frame() {
+        use crate::system::SystemPhase;
+        
+        // PostUpdateで書き込むリソースと、Updateで変更を見たフレーム
+        struct Score(u32);
+        struct SeenFrames(Vec<u32>);
+        struct Frame(u32);
+        
+        let mut world = World::new();
+        world.insert_resource(Score(0));
+        world.insert_resource(SeenFrames(Vec::new()));
+        world.insert_resource(Frame(0));
+        
+        world.systems_mut().add_fn_system("Watch", SystemPhase::Update, |_entities: &mut EntityManager, resources: &mut ResourceManager| {
+            let frame = resources.get::<Frame>().unwrap().0;
+            if resources.is_changed::<Score>() {
+                resources.get_mut::<SeenFrames>().unwrap().0.push(frame);
+            }
+        });
+        world.systems_mut().add_fn_system("Score", SystemPhase::PostUpdate, |_entities: &mut EntityManager, resources: &mut ResourceManager| {
+            // 2フレーム目だけ`get_mut`で書き込む
+            let frame = resources.get::<Frame>().unwrap().0;
+            if frame == 1 {
+                resources.get_mut::<Score>().unwrap().0 += 1;
+            }
+        });
+        
+        for frame in 0..4 {
+            world.get_resource_mut::<Frame>().unwrap().0 = frame;
+            world.run_stages();
+        }
+        
+        // 追加された最初のフレームと、後のステージで書き込まれた次のフレームだけ変更が見える
+        assert_eq!(world.get_resource::<SeenFrames>().unwrap().0, vec![0, 2]);
     }
-} 
\ No newline at end of file
+}
\ No newline at end of file
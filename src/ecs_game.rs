@@ -2,15 +2,15 @@ use crate::resources::{
     CoreGameResource, GamePhase, TimeResource, 
     PlayerStateResource, GameConfigResource, ResourceManager
 };
-use crate::system::{SystemRegistry, system_registry::SystemPhase};
+use crate::ecs::{World, Plugin, CorePlugin, BoardPlugin, UiPlugin};
+use crate::system::{Stage, System, system_registry::SystemPhase};
+use crate::logger::log_info;
 
 /// ECSベースのゲームエンジン
-/// リソースとシステムを管理し、ゲームループを実行する
+/// Worldを保持し、ステージ順にシステムを実行してゲームループを進める
 pub struct EcsGame {
-    /// リソースマネージャー
-    resources: ResourceManager,
-    /// システムレジストリ
-    systems: SystemRegistry,
+    /// エンティティ・リソース・システムを保持するWorld
+    world: World,
     /// 初期化済みかどうか
     initialized: bool,
 }
@@ -19,8 +19,7 @@ impl EcsGame {
     /// 新しいEcsGameインスタンスを作成
     pub fn new() -> Self {
         Self {
-            resources: ResourceManager::new(),
-            systems: SystemRegistry::new(),
+            world: World::new(),
             initialized: false,
         }
     }
@@ -31,40 +30,48 @@ impl EcsGame {
             return;
         }
 
-        // コアリソースの初期化
-        self.setup_core_resources();
+        // プラグインの登録
+        self.world
+            .add_plugin(CorePlugin)
+            .add_plugin(BoardPlugin)
+            .add_plugin(UiPlugin);
         
         // 初期化フェーズのシステムを実行
-        self.systems.run_startup(&mut self.resources);
+        self.world.run_startup();
         
         self.initialized = true;
     }
 
-    /// コアリソースの設定
-    fn setup_core_resources(&mut self) {
-        // CoreGameResourceを追加
-        let core_game = CoreGameResource::new();
-        self.resources.insert(core_game);
-        
-        // TimeResourceを追加
-        let time = TimeResource::new();
-        self.resources.insert(time);
-        
-        // PlayerStateResourceを追加
-        let player_state = PlayerStateResource::new();
-        self.resources.insert(player_state);
-        
-        // GameConfigResourceを追加
-        let game_config = GameConfigResource::new();
-        self.resources.insert(game_config);
+    /// プラグインを追加
+    /// initializeより前に追加したプラグインのリソースはデフォルトより優先される
+    pub fn add_plugin<P: Plugin>(&mut self, plugin: P) {
+        self.world.add_plugin(plugin);
     }
 
     /// システムを追加
     pub fn add_system<S>(&mut self, system: S) -> usize
     where
-        S: 'static + crate::system::System,
+        S: 'static + System,
+    {
+        self.world.add_system(system)
+    }
+
+    /// 指定したステージにシステムを追加
+    pub fn add_system_to_stage<S>(&mut self, stage: Stage, system: S) -> usize
+    where
+        S: 'static + System,
     {
-        self.systems.add_system(Box::new(system))
+        self.world.add_system_to_stage(stage, system)
+    }
+
+    /// Worldへの参照を取得
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+
+    /// Worldへの可変参照を取得
+    pub fn world_mut(&mut self) -> &mut World {
+        &mut self.world
     }
 
     /// ゲームループの1フレームを実行
@@ -74,70 +81,72 @@ impl EcsGame {
         }
 
         // TimeResourceを更新
-        if let Some(time) = self.resources.get_mut::<TimeResource>() {
+        if let Some(time) = self.world.get_resource_mut::<TimeResource>() {
             time.begin_frame();
         }
 
-        // 各フェーズのシステムを実行
-        self.systems.run_all_phases(&mut self.resources);
+        // 各ステージのシステムを順番に実行
+        self.world.run_stages();
 
-        // CoreGameResourceのチェック - ゲームが終了したかどうか
-        if let Some(core_game) = self.resources.get::<CoreGameResource>() {
-            let phase = core_game.phase();
-            if let GamePhase::GameOver { .. } = phase {
-                // ゲームオーバー処理
-                println!("Game Over! Score: {}", core_game.score());
+        // フレーム中に発生したフェーズ遷移イベントを処理
+        if let Some((config, core_game)) = self.world.get_resources_mut::<GameConfigResource, CoreGameResource>() {
+            for event in core_game.drain_phase_events() {
+                if let GamePhase::GameOver { .. } = event.to {
+                    // ゲームオーバー処理（スコアを確定）
+                    let score = core_game.record_final_score(config);
+                    log_info!("Game Over! Score: {}", score);
+                }
             }
         }
     }
 
     /// リソースへの参照を取得
     pub fn get_resource<T: 'static>(&self) -> Option<&T> {
-        self.resources.get::<T>()
+        self.world.get_resource::<T>()
     }
 
     /// リソースへの可変参照を取得
     pub fn get_resource_mut<T: 'static>(&mut self) -> Option<&mut T> {
-        self.resources.get_mut::<T>()
+        self.world.get_resource_mut::<T>()
     }
 
     /// リソースを追加または更新
     pub fn insert_resource<T: 'static>(&mut self, resource: T) {
-        self.resources.insert(resource);
+        self.world.insert_resource(resource);
     }
 
     /// ゲームをスタート
     pub fn start_game(&mut self) {
-        if let Some(core_game) = self.resources.get_mut::<CoreGameResource>() {
+        if let Some(core_game) = self.world.get_resource_mut::<CoreGameResource>() {
             core_game.start_game();
         }
     }
 
     /// ゲームを一時停止
     pub fn pause_game(&mut self) {
-        if let Some(core_game) = self.resources.get_mut::<CoreGameResource>() {
+        if let Some(core_game) = self.world.get_resource_mut::<CoreGameResource>() {
             core_game.pause_game();
         }
     }
 
     /// ゲームを再開
     pub fn resume_game(&mut self) {
-        if let Some(core_game) = self.resources.get_mut::<CoreGameResource>() {
+        if let Some(core_game) = self.world.get_resource_mut::<CoreGameResource>() {
             core_game.resume_game();
         }
     }
 
     /// ゲームを終了
     pub fn end_game(&mut self, win: bool) {
-        if let Some(core_game) = self.resources.get_mut::<CoreGameResource>() {
+        if let Some(core_game) = self.world.get_resource_mut::<CoreGameResource>() {
             core_game.end_game(win);
         }
     }
 
     /// ゲームのフェーズを取得
     pub fn game_phase(&self) -> GamePhase {
-        self.resources
-            .get::<CoreGameResource>()
+        self.world
+            .get_resource::<CoreGameResource>()
             .map_or(GamePhase::Ready, |core| core.phase())
     }
 }
@@ -151,6 +160,7 @@ impl Default for EcsGame {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::resources::Difficulty;
 
     // シンプルなテストシステム
     struct TestSystem {
@@ -252,7 +262,7 @@ mod tests {
         game.update();
         
         // システムが実行されたことを確認
-        let systems = &game.systems;
+        let systems = game.world().systems();
         let system = systems.get_system(system_id).unwrap();
         let test_system = system.downcast_ref::<TestSystem>().unwrap();
         
@@ -261,10 +271,23 @@ mod tests {
         // もう一度更新
         game.update();
         
-        let systems = &game.systems;
+        let systems = game.world().systems();
         let system = systems.get_system(system_id).unwrap();
         let test_system = system.downcast_ref::<TestSystem>().unwrap();
         
         assert_eq!(test_system.run_count, 2);
     }
+
+    #[test]
+    fn test_ecs_game_records_score_on_game_over() {
+        let mut game = EcsGame::new();
+        game.initialize();
+        game.start_game();
+        game.end_game(true);
+        game.update();
+
+        let core_game = game.get_resource::<CoreGameResource>().unwrap();
+        assert!(core_game.score() > 0);
+        assert_eq!(core_game.best_score(&Difficulty::Easy), Some(core_game.score()));
+    }
 } 
\ No newline at end of file
@@ -122,6 +122,21 @@ pub mod cell_operations {
         false
     }
     
+    /// フラグを指定した状態にする（変わった場合はtrue）
+    pub fn set_flag(manager: &mut EntityManager, id: EntityId, flagged: bool) -> bool {
+        if let Some(state) = manager.get_entity_mut(id).and_then(|entity| entity.get_component_mut::<CellState>()) {
+            // 開かれたセルや同じ状態のセルは変えない
+            if state.is_revealed || state.is_flagged == flagged {
+                return false;
+            }
+            
+            state.is_flagged = flagged;
+            return true;
+        }
+        
+        false
+    }
+    
     /// セルの内容を取得
     pub fn get_cell_content(manager: &EntityManager, id: EntityId) -> Option<CellContent> {
         manager.get_entity(id)
@@ -17,6 +17,41 @@ impl fmt::Display for EntityId {
     }
 }
 
+/// タグのビットマスク
+/// エンティティマネージャーがタグ文字列をビット位置に割り当てて管理する
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct TagMask(pub u64);
+
+impl TagMask {
+    /// 空のマスク
+    pub const EMPTY: TagMask = TagMask(0);
+    
+    /// 指定したビット位置だけが立ったマスクを作成
+    pub fn from_bit(bit: u8) -> Self {
+        TagMask(1 << bit)
+    }
+    
+    /// 他のマスクのビットを全て含んでいるか
+    pub fn contains(&self, other: TagMask) -> bool {
+        self.0 & other.0 == other.0
+    }
+    
+    /// 2つのマスクの和集合
+    pub fn union(&self, other: TagMask) -> Self {
+        TagMask(self.0 | other.0)
+    }
+    
+    /// 他のマスクのビットを取り除いたマスク
+    pub fn without(&self, other: TagMask) -> Self {
+        TagMask(self.0 & !other.0)
+    }
+    
+    /// ビットが1つも立っていないか
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+}
+
 /// 基本的なエンティティ
 /// 一意のIDとコンポーネントの集合です
 #[derive(Debug)]
@@ -27,6 +62,8 @@ pub struct Entity {
     components: HashMap<TypeId, Box<dyn Any>>,
     /// タグ（任意のラベル）
     tags: Vec<String>,
+    /// タグのビットマスク（エンティティマネージャーが設定）
+    tag_mask: TagMask,
 }
 
 impl Entity {
@@ -36,6 +73,7 @@ impl Entity {
             id,
             components: HashMap::new(),
             tags: Vec::new(),
+            tag_mask: TagMask::EMPTY,
         }
     }
     
@@ -97,6 +135,18 @@ impl Entity {
         &self.tags
     }
     
+    /// タグのビットマスクを取得
+    /// 
+    /// エンティティマネージャー経由で登録・追加されたタグのみ反映される
+    pub fn tag_mask(&self) -> TagMask {
+        self.tag_mask
+    }
+    
+    /// タグのビットマスクを設定
+    pub(crate) fn set_tag_mask(&mut self, mask: TagMask) {
+        self.tag_mask = mask;
+    }
+    
     /// コンポーネントを追加（ボックス化済み）
     pub fn add_component_boxed(&mut self, type_id: TypeId, component: Box<dyn Any + Send + Sync>) -> &mut Self {
         self.components.insert(type_id, component);
@@ -5,9 +5,9 @@
  */
 use std::collections::{HashMap, HashSet};
 use std::any::TypeId;
-use crate::entities::entity::{Entity, EntityId};
+use crate::entities::entity::{Entity, EntityId, TagMask};
 use crate::entities::entity_id_generator::EntityIdGenerator;
-use crate::components::{Component, ComponentDependencyHandler, ComponentFactory};
+use crate::components::{Component, ComponentDependencyHandler, ComponentFactory, Disabled, Position, GlobalPosition};
 
 /// エンティティビルダー
 /// エンティティを簡単に構築するためのビルダーパターン実装
@@ -73,6 +73,64 @@ impl Hierarchy {
     }
 }
 
+/// 型消去されたコンポーネントの`on_remove`フックを呼び出す関数
+type RemovalHook = fn(&mut Entity, EntityId);
+
+/// 指定した型のコンポーネントの`on_remove`を呼び出す
+fn call_on_remove<T: Component>(entity: &mut Entity, entity_id: EntityId) {
+    if let Some(component) = entity.get_component_mut::<T>() {
+        component.on_remove(entity_id);
+    }
+}
+
+/// クエリキャッシュを破棄する
+/// 
+/// `Disabled`の付け外しは全てのクエリ結果に影響するため、キャッシュを全て破棄する
+fn invalidate_query_cache(cache: &mut HashMap<TypeId, Vec<EntityId>>, type_id: TypeId) {
+    if type_id == TypeId::of::<Disabled>() {
+        cache.clear();
+    } else {
+        cache.remove(&type_id);
+    }
+}
+
+/// エンティティが有効（`Disabled`を持たない）かどうか
+fn is_enabled(entity: &Entity) -> bool {
+    !entity.has_component::<Disabled>()
+}
+
+/// 子孫エンティティを深さ優先（行きがけ順）で辿るイテレーター
+pub struct Descendants<'a> {
+    /// 参照元のエンティティマネージャー
+    manager: &'a EntityManager,
+    /// 未訪問のエンティティID（末尾から取り出す）
+    stack: Vec<EntityId>,
+    /// 無効化されたエンティティも辿るかどうか
+    include_disabled: bool,
+}
+
+impl<'a> Iterator for Descendants<'a> {
+    type Item = EntityId;
+    
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut id = self.stack.pop()?;
+        
+        // 無効化されたエンティティはその子孫ごと飛ばす
+        if !self.include_disabled {
+            while !self.manager.is_enabled(id) {
+                id = self.stack.pop()?;
+            }
+        }
+        
+        // 子を逆順に積んで、登録順に訪問されるようにする
+        if let Some(hierarchy) = self.manager.get_hierarchy(id) {
+            self.stack.extend(hierarchy.children.iter().rev().copied());
+        }
+        
+        Some(id)
+    }
+}
+
 /// エンティティマネージャー
 /// ゲーム内の全エンティティを管理する
 #[derive(Debug)]
@@ -85,10 +143,16 @@ pub struct EntityManager {
     pending_removal: HashSet<EntityId>,
     /// タグごとのエンティティID
     tags_to_entities: HashMap<String, HashSet<EntityId>>,
+    /// タグ文字列に割り当てたビット位置
+    tag_bits: HashMap<String, u8>,
     /// コンポーネントタイプごとのエンティティID
     component_indices: HashMap<TypeId, HashSet<EntityId>>,
+    /// コンポーネントタイプごとのクエリ結果キャッシュ（構造変更時に破棄）
+    query_cache: HashMap<TypeId, Vec<EntityId>>,
     /// コンポーネントファクトリー
     component_factory: Option<ComponentFactory>,
+    /// コンポーネント型ごとの削除フック（`add_component`で登録される）
+    removal_hooks: HashMap<TypeId, RemovalHook>,
 }
 
 impl Default for EntityManager {
@@ -98,8 +162,11 @@ impl Default for EntityManager {
             id_generator: EntityIdGenerator::default(),
             pending_removal: HashSet::new(),
             tags_to_entities: HashMap::new(),
+            tag_bits: HashMap::new(),
             component_indices: HashMap::new(),
+            query_cache: HashMap::new(),
             component_factory: Some(ComponentFactory::new()),
+            removal_hooks: HashMap::new(),
         }
     }
 }
@@ -137,16 +204,19 @@ impl EntityManager {
     }
     
     /// ビルダーで作成したエンティティを登録
-    pub fn register_entity(&mut self, entity: Entity) -> EntityId {
+    pub fn register_entity(&mut self, mut entity: Entity) -> EntityId {
         let id = entity.id;
         
         // タグ情報を更新
+        let mut mask = TagMask::EMPTY;
         for tag in entity.get_tags() {
             self.tags_to_entities
                 .entry(tag.clone())
                 .or_insert_with(HashSet::new)
                 .insert(id);
+            mask = mask.union(self.intern_tag(tag));
         }
+        entity.set_tag_mask(mask);
         
         // コンポーネントタイプのインデックスを更新（将来の高速クエリ用）
         self.update_component_indices(&entity);
@@ -169,7 +239,10 @@ impl EntityManager {
     
     /// 即時エンティティを削除（待機なし）
     pub fn remove_entity_immediate(&mut self, id: EntityId) -> Option<Entity> {
-        let entity = self.entities.remove(&id)?;
+        let mut entity = self.entities.remove(&id)?;
+        
+        // 削除フックを呼び出す
+        self.run_removal_hooks(&mut entity);
         
         // タグマップから削除
         for tag in entity.get_tags() {
@@ -187,6 +260,11 @@ impl EntityManager {
             indices.remove(&id);
         }
         
+        // 持っていたコンポーネント型のクエリキャッシュを破棄
+        for type_id in entity.get_component_types() {
+            invalidate_query_cache(&mut self.query_cache, type_id);
+        }
+        
         // IDをリサイクル
         self.id_generator.recycle(id);
         
@@ -213,8 +291,39 @@ impl EntityManager {
         self.entities.keys().copied()
     }
     
-    /// 特定のコンポーネントを持つエンティティを全て取得
+    /// エンティティが有効（存在し、`Disabled`を持たない）かどうか
+    pub fn is_enabled(&self, id: EntityId) -> bool {
+        self.entities.get(&id).is_some_and(is_enabled)
+    }
+    
+    /// エンティティの有効・無効を切り替える（`Disabled`マーカーの付け外し）
+    pub fn set_enabled(&mut self, id: EntityId, enabled: bool) -> Result<(), &'static str> {
+        if !self.entities.contains_key(&id) {
+            return Err("エンティティが存在しません");
+        }
+        
+        if enabled {
+            self.remove_component::<Disabled>(id);
+            Ok(())
+        } else if self.is_enabled(id) {
+            self.add_component(id, Disabled)
+        } else {
+            Ok(())
+        }
+    }
+    
+    /// 特定のコンポーネントを持つ有効なエンティティを全て取得
+    /// 
+    /// 無効化されたエンティティは含まない（`get_entities_with_component_including_disabled`を参照）
     pub fn get_entities_with_component<T: 'static>(&self) -> Vec<EntityId> {
+        self.get_entities_with_component_including_disabled::<T>()
+            .into_iter()
+            .filter(|id| self.is_enabled(*id))
+            .collect()
+    }
+    
+    /// 特定のコンポーネントを持つエンティティを無効化されたものも含めて全て取得
+    pub fn get_entities_with_component_including_disabled<T: 'static>(&self) -> Vec<EntityId> {
         let type_id = TypeId::of::<T>();
         
         // インデックスが構築済みの場合はそれを使用
@@ -229,6 +338,28 @@ impl EntityManager {
             .collect()
     }
     
+    /// 特定のコンポーネントを持つエンティティを全て取得（キャッシュ付き、ID順）
+    /// 
+    /// 結果はその型のコンポーネントを持つエンティティが追加・削除されるまで再利用される。
+    /// キャッシュを正しく保つため、構造の変更は`register_entity`や`add_component`など
+    /// マネージャー経由で行うこと
+    pub fn cached_entities_with_component<T: 'static>(&mut self) -> &[EntityId] {
+        let type_id = TypeId::of::<T>();
+        
+        if !self.query_cache.contains_key(&type_id) {
+            let mut ids = self.get_entities_with_component::<T>();
+            ids.sort_by_key(|id| id.0);
+            self.query_cache.insert(type_id, ids);
+        }
+        
+        &self.query_cache[&type_id]
+    }
+    
+    /// クエリキャッシュが存在するかどうか
+    pub fn is_query_cached<T: 'static>(&self) -> bool {
+        self.query_cache.contains_key(&TypeId::of::<T>())
+    }
+    
     /// コンポーネントタイプのインデックスを構築
     pub fn build_component_index<T: 'static>(&mut self) -> HashSet<EntityId> {
         let type_id = TypeId::of::<T>();
@@ -244,19 +375,129 @@ impl EntityManager {
             .collect();
             
         self.component_indices.insert(type_id, ids.clone());
+        invalidate_query_cache(&mut self.query_cache, type_id);
         ids
     }
     
-    /// 特定のタグを持つエンティティを全て取得
+    /// 特定のタグを持つ有効なエンティティを全て取得
     pub fn get_entities_with_tag(&self, tag: &str) -> Vec<EntityId> {
+        self.get_entities_with_tag_including_disabled(tag)
+            .into_iter()
+            .filter(|id| self.is_enabled(*id))
+            .collect()
+    }
+    
+    /// 特定のタグを持つエンティティを無効化されたものも含めて全て取得
+    pub fn get_entities_with_tag_including_disabled(&self, tag: &str) -> Vec<EntityId> {
         self.tags_to_entities
             .get(tag)
             .map(|set| set.iter().copied().collect())
             .unwrap_or_default()
     }
     
-    /// 複数条件によるクエリ：指定したコンポーネントとタグを持つエンティティを取得
+    /// タグ文字列にビット位置を割り当てる
+    /// 
+    /// 割り当て可能なビット（64個）を使い切った場合は空のマスクを返し、
+    /// そのタグはビットマスクによるクエリの対象外となる
+    pub fn intern_tag(&mut self, tag: &str) -> TagMask {
+        if let Some(&bit) = self.tag_bits.get(tag) {
+            return TagMask::from_bit(bit);
+        }
+        
+        let bit = self.tag_bits.len();
+        if bit >= 64 {
+            return TagMask::EMPTY;
+        }
+        
+        self.tag_bits.insert(tag.to_string(), bit as u8);
+        TagMask::from_bit(bit as u8)
+    }
+    
+    /// 複数のタグからビットマスクを作成
+    /// 
+    /// ビットが割り当てられていないタグが含まれる場合はNone
+    pub fn tag_mask(&self, tags: &[&str]) -> Option<TagMask> {
+        tags.iter().try_fold(TagMask::EMPTY, |mask, tag| {
+            self.tag_bits
+                .get(*tag)
+                .map(|&bit| mask.union(TagMask::from_bit(bit)))
+        })
+    }
+    
+    /// エンティティにタグを追加（インデックスとビットマスクも更新）
+    pub fn add_tag(&mut self, id: EntityId, tag: &str) -> bool {
+        if !self.entities.contains_key(&id) {
+            return false;
+        }
+        
+        let bit = self.intern_tag(tag);
+        if let Some(entity) = self.entities.get_mut(&id) {
+            entity.add_tag(tag);
+            entity.set_tag_mask(entity.tag_mask().union(bit));
+        }
+        
+        self.tags_to_entities
+            .entry(tag.to_string())
+            .or_default()
+            .insert(id);
+        true
+    }
+    
+    /// エンティティからタグを削除（インデックスとビットマスクも更新）
+    pub fn remove_tag(&mut self, id: EntityId, tag: &str) {
+        let bit = self.tag_mask(&[tag]).unwrap_or(TagMask::EMPTY);
+        if let Some(entity) = self.entities.get_mut(&id) {
+            entity.remove_tag(tag);
+            entity.set_tag_mask(entity.tag_mask().without(bit));
+        }
+        
+        if let Some(tag_set) = self.tags_to_entities.get_mut(tag) {
+            tag_set.remove(&id);
+            
+            if tag_set.is_empty() {
+                self.tags_to_entities.remove(tag);
+            }
+        }
+    }
+    
+    /// 指定したビットマスクのタグを全て持つ有効なエンティティを取得
+    pub fn get_entities_with_tag_mask(&self, mask: TagMask) -> Vec<EntityId> {
+        self.entities.iter()
+            .filter(|(_, entity)| entity.tag_mask().contains(mask) && is_enabled(entity))
+            .map(|(id, _)| *id)
+            .collect()
+    }
+    
+    /// 複数条件によるクエリ：指定したコンポーネントとタグを持つ有効なエンティティを取得
     pub fn query_with_component_and_tag<T: 'static>(&self, tag: &str) -> Vec<EntityId> {
+        match self.tag_mask(&[tag]) {
+            Some(mask) => self.query_with_component_and_tag_mask::<T>(mask),
+            None => self.query_with_component_and_tag_set::<T>(tag),
+        }
+    }
+    
+    /// 複数条件によるクエリ：指定したコンポーネントとビットマスクのタグを全て持つ有効なエンティティを取得
+    pub fn query_with_component_and_tag_mask<T: 'static>(&self, mask: TagMask) -> Vec<EntityId> {
+        let matches = |entity: &Entity| {
+            entity.tag_mask().contains(mask) && entity.has_component::<T>() && is_enabled(entity)
+        };
+        
+        // インデックスが構築済みの場合は候補を絞り込む
+        if let Some(indices) = self.component_indices.get(&TypeId::of::<T>()) {
+            return indices.iter()
+                .filter(|id| self.entities.get(id).is_some_and(matches))
+                .copied()
+                .collect();
+        }
+        
+        self.entities.iter()
+            .filter(|(_, entity)| matches(entity))
+            .map(|(id, _)| *id)
+            .collect()
+    }
+    
+    /// ビットを割り当てられなかったタグ向けのクエリ（タグのHashSetを使用）
+    fn query_with_component_and_tag_set<T: 'static>(&self, tag: &str) -> Vec<EntityId> {
         // タグによるフィルタ
         let tag_entities = match self.tags_to_entities.get(tag) {
             Some(entities) => entities,
@@ -264,14 +505,12 @@ impl EntityManager {
         };
         
         // コンポーネントによるフィルタ
-        self.entities.iter()
-            .filter_map(|(id, entity)| {
-                if tag_entities.contains(id) && entity.has_component::<T>() {
-                    Some(*id)
-                } else {
-                    None
-                }
+        tag_entities.iter()
+            .filter(|id| {
+                self.entities.get(id)
+                    .is_some_and(|entity| entity.has_component::<T>() && is_enabled(entity))
             })
+            .copied()
             .collect()
     }
     
@@ -328,6 +567,93 @@ impl EntityManager {
         Ok(())
     }
     
+    /// 階層コンポーネントを取得
+    fn get_hierarchy(&self, id: EntityId) -> Option<&Hierarchy> {
+        self.get_entity(id)
+            .and_then(|entity| entity.get_component::<Hierarchy>())
+    }
+    
+    /// 親エンティティのIDを取得
+    pub fn get_parent(&self, id: EntityId) -> Option<EntityId> {
+        self.get_hierarchy(id).and_then(|hierarchy| hierarchy.parent)
+    }
+    
+    /// 直下の有効な子エンティティを順に取得
+    pub fn iter_children(&self, id: EntityId) -> impl Iterator<Item = EntityId> + '_ {
+        self.iter_children_including_disabled(id)
+            .filter(|child| self.is_enabled(*child))
+    }
+    
+    /// 直下の子エンティティを無効化されたものも含めて順に取得
+    pub fn iter_children_including_disabled(&self, id: EntityId) -> impl Iterator<Item = EntityId> + '_ {
+        self.get_hierarchy(id)
+            .map(|hierarchy| hierarchy.children.as_slice())
+            .unwrap_or_default()
+            .iter()
+            .copied()
+    }
+    
+    /// 全ての有効な子孫エンティティを深さ優先で取得（自身は含まない）
+    /// 
+    /// 無効化されたエンティティの子孫も辿らない
+    pub fn iter_descendants(&self, id: EntityId) -> Descendants<'_> {
+        self.descendants(id, false)
+    }
+    
+    /// 全ての子孫エンティティを無効化されたものも含めて深さ優先で取得
+    pub fn iter_descendants_including_disabled(&self, id: EntityId) -> Descendants<'_> {
+        self.descendants(id, true)
+    }
+    
+    /// 子孫イテレーターを作成
+    fn descendants(&self, id: EntityId, include_disabled: bool) -> Descendants<'_> {
+        let stack = self.get_hierarchy(id)
+            .map(|hierarchy| hierarchy.children.iter().rev().copied().collect())
+            .unwrap_or_default();
+        
+        Descendants {
+            manager: self,
+            stack,
+            include_disabled,
+        }
+    }
+    
+    /// 親子関係に沿って位置を伝播し、`GlobalPosition`を更新する
+    /// 
+    /// 親を持たない階層ルートから辿り、`Position`を親のワールド座標からの
+    /// 相対位置として扱う。`Position`を持たないエンティティは親の座標をそのまま子に引き継ぐ
+    pub fn propagate_positions(&mut self) {
+        let roots: Vec<EntityId> = self.entities.iter()
+            .filter(|(_, entity)| {
+                entity.get_component::<Hierarchy>()
+                    .is_some_and(|hierarchy| hierarchy.parent.is_none())
+            })
+            .map(|(id, _)| *id)
+            .collect();
+        
+        let mut stack: Vec<(EntityId, GlobalPosition)> = roots.into_iter()
+            .map(|id| (id, GlobalPosition::new(0.0, 0.0)))
+            .collect();
+        
+        while let Some((id, parent_global)) = stack.pop() {
+            let Some(entity) = self.entities.get_mut(&id) else {
+                continue;
+            };
+            
+            let global = match entity.get_component::<Position>() {
+                Some(local) => {
+                    let global = parent_global.offset_by(local);
+                    entity.add_component(global);
+                    self.update_component_index::<GlobalPosition>(id);
+                    global
+                },
+                None => parent_global,
+            };
+            
+            stack.extend(self.iter_children_including_disabled(id).map(|child| (child, global)));
+        }
+    }
+    
     /// 再帰的にエンティティを削除（親が削除されたら子も削除）
     pub fn remove_entity_recursive(&mut self, id: EntityId) {
         let children = {
@@ -351,6 +677,37 @@ impl EntityManager {
         self.remove_entity(id);
     }
     
+    /// エンティティと全ての子孫を即時に削除する
+    /// 
+    /// 親の子リストからも切り離し、タグとコンポーネントのインデックスを掃除する。
+    /// 削除したエンティティの数を返す
+    pub fn despawn_recursive(&mut self, id: EntityId) -> usize {
+        if !self.entities.contains_key(&id) {
+            return 0;
+        }
+        
+        // 親の子リストから切り離す
+        if let Some(parent_id) = self.get_parent(id) {
+            if let Some(parent_hierarchy) = self.get_entity_mut(parent_id)
+                .and_then(|parent| parent.get_component_mut::<Hierarchy>()) {
+                parent_hierarchy.remove_child(id);
+            }
+        }
+        
+        let mut to_remove = vec![id];
+        to_remove.extend(self.iter_descendants_including_disabled(id));
+        
+        let mut removed = 0;
+        for entity_id in to_remove {
+            self.pending_removal.remove(&entity_id);
+            if self.remove_entity_immediate(entity_id).is_some() {
+                removed += 1;
+            }
+        }
+        
+        removed
+    }
+    
     /// 削除待ちのエンティティを本当に削除する
     pub fn flush_removals(&mut self) {
         // 削除待ちリストのコピーを作成（削除中に変更を避けるため）
@@ -368,12 +725,34 @@ impl EntityManager {
         self.entities.len()
     }
     
+    /// 削除待ちのエンティティ数を取得
+    pub fn pending_removal_count(&self) -> usize {
+        self.pending_removal.len()
+    }
+    
+    /// コンポーネントタイプごとのエンティティ数を取得（全エンティティを走査）
+    pub fn component_type_counts(&self) -> HashMap<TypeId, usize> {
+        let mut counts = HashMap::new();
+        for entity in self.entities.values() {
+            for type_id in entity.get_component_types() {
+                *counts.entry(type_id).or_insert(0) += 1;
+            }
+        }
+        counts
+    }
+    
     /// 全エンティティをクリア
     pub fn clear(&mut self) {
-        self.entities.clear();
+        // 全エンティティの削除フックを呼び出す
+        let mut entities = std::mem::take(&mut self.entities);
+        for entity in entities.values_mut() {
+            self.run_removal_hooks(entity);
+        }
+        
         self.pending_removal.clear();
         self.tags_to_entities.clear();
         self.component_indices.clear();
+        self.query_cache.clear();
         // IDジェネレーターはリセットしない（一意性を保つため）
     }
     
@@ -385,7 +764,9 @@ impl EntityManager {
                 .entry(type_id)
                 .or_insert_with(HashSet::new);
                 
-            entities.insert(entity.id);
+            if entities.insert(entity.id) {
+                invalidate_query_cache(&mut self.query_cache, type_id);
+            }
         }
     }
     
@@ -445,6 +826,7 @@ impl EntityManager {
                         // エンティティを取得して依存コンポーネントを追加
                         if let Some(entity) = self.entities.get_mut(&entity_id) {
                             entity.add_component_boxed(*type_id, component);
+                            invalidate_query_cache(&mut self.query_cache, *type_id);
                         } else {
                             return Err("エンティティが見つかりません");
                         }
@@ -464,6 +846,11 @@ impl EntityManager {
         // インデックスを更新
         self.update_component_index::<T>(entity_id);
         
+        // 削除時にon_removeを呼び出せるようにフックを登録
+        self.removal_hooks
+            .entry(TypeId::of::<T>())
+            .or_insert(call_on_remove::<T>);
+        
         // コンポーネント追加イベントを呼び出し
         if let Some(entity) = self.entities.get_mut(&entity_id) {
             if let Some(comp) = entity.get_component_mut::<T>() {
@@ -474,6 +861,35 @@ impl EntityManager {
         Ok(())
     }
     
+    /// エンティティからコンポーネントを削除
+    /// 
+    /// 削除前に`Component::on_remove`を呼び出し、インデックスからも取り除く
+    pub fn remove_component<T: Component>(&mut self, entity_id: EntityId) -> Option<T> {
+        let entity = self.entities.get_mut(&entity_id)?;
+        
+        // コンポーネント削除イベントを呼び出し
+        entity.get_component_mut::<T>()?.on_remove(entity_id);
+        let component = entity.remove_component::<T>();
+        
+        if let Some(indices) = self.component_indices.get_mut(&TypeId::of::<T>()) {
+            indices.remove(&entity_id);
+        }
+        invalidate_query_cache(&mut self.query_cache, TypeId::of::<T>());
+        
+        component
+    }
+    
+    /// エンティティが持つコンポーネントの削除フックを全て呼び出す
+    fn run_removal_hooks(&self, entity: &mut Entity) {
+        let entity_id = entity.id;
+        
+        for type_id in entity.get_component_types() {
+            if let Some(hook) = self.removal_hooks.get(&type_id) {
+                hook(entity, entity_id);
+            }
+        }
+    }
+    
     /// 型IDによるコンポーネント所持確認
     fn entity_has_component_by_type_id(&self, entity_id: EntityId, type_id: TypeId) -> bool {
         if let Some(entity) = self.get_entity(entity_id) {
@@ -495,7 +911,9 @@ impl EntityManager {
             .entry(type_id)
             .or_insert_with(HashSet::new);
             
-        // エンティティIDを追加
-        entities.insert(entity_id);
+        // エンティティIDを追加（新規の場合はクエリキャッシュを破棄）
+        if entities.insert(entity_id) {
+            invalidate_query_cache(&mut self.query_cache, type_id);
+        }
     }
 } 
\ No newline at end of file
@@ -18,9 +18,9 @@ mod ui_entity;
 mod tests;
 
 // エンティティ関連の型を再エクスポート
-pub use entity::{Entity, EntityId};
-pub use entity_manager::{EntityManager, EntityBuilder, Hierarchy};
+pub use entity::{Entity, EntityId, TagMask};
+pub use entity_manager::{EntityManager, EntityBuilder, Hierarchy, Descendants};
 pub use entity_id_generator::EntityIdGenerator;
-pub use cell_entity::{CellEntity, create_cell_entity};
+pub use cell_entity::{CellEntity, create_cell_entity, cell_operations};
 pub use player_entity::{PlayerEntity, create_player_entity};
 pub use ui_entity::{UIEntityType, create_ui_entity}; 
\ No newline at end of file
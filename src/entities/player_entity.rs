@@ -113,7 +113,7 @@ pub mod player_operations {
     
     /// 非アクティブなプレイヤーを検出（一定時間操作がないプレイヤー）
     pub fn find_inactive_players(manager: &EntityManager, timeout_ms: f64) -> Vec<EntityId> {
-        let current_time = js_sys::Date::now();
+        let current_time = crate::utils::now_ms();
         
         manager.get_entities_with_tag(PLAYER_TAG)
             .into_iter()
@@ -204,4 +204,269 @@ mod entity_manager_tests {
         assert!(manager.get_entity(child2).is_none());
         assert!(manager.get_entity(grandchild).is_none());
     }
-} 
\ No newline at end of file
+    
+    #[test]
+    fn test_hierarchy_traversal() {
+        let mut manager = EntityManager::new();
+        
+        // 階層構造を作成
+        let parent = manager.create_entity();
+        let child1 = manager.create_entity();
+        let child2 = manager.create_entity();
+        let grandchild = manager.create_entity();
+        
+        manager.set_parent(child1, parent).unwrap();
+        manager.set_parent(child2, parent).unwrap();
+        manager.set_parent(grandchild, child1).unwrap();
+        
+        // 直下の子だけが登録順に返される
+        let children: Vec<EntityId> = manager.iter_children(parent).collect();
+        assert_eq!(children, vec![child1, child2]);
+        
+        // 子孫は深さ優先（行きがけ順）で返される
+        let descendants: Vec<EntityId> = manager.iter_descendants(parent).collect();
+        assert_eq!(descendants, vec![child1, grandchild, child2]);
+        
+        // 葉エンティティには子孫がない
+        assert_eq!(manager.iter_descendants(grandchild).count(), 0);
+        assert_eq!(manager.get_parent(grandchild), Some(child1));
+        assert_eq!(manager.get_parent(parent), None);
+    }
+    
+    #[test]
+    fn test_position_propagation() {
+        use crate::components::{Position, GlobalPosition};
+        
+        let mut manager = EntityManager::new();
+        
+        // パネル -> ボタン -> ラベル の階層
+        let panel = manager.create_entity();
+        let button = manager.create_entity();
+        let label = manager.create_entity();
+        
+        manager.get_entity_mut(panel).unwrap().add_component(Position::new(100.0, 50.0));
+        manager.get_entity_mut(button).unwrap().add_component(Position::new(10.0, 20.0));
+        manager.get_entity_mut(label).unwrap().add_component(Position::new(1.0, 2.0));
+        
+        manager.set_parent(button, panel).unwrap();
+        manager.set_parent(label, button).unwrap();
+        
+        manager.propagate_positions();
+        
+        let global = |id| *manager.get_entity(id).unwrap().get_component::<GlobalPosition>().unwrap();
+        assert_eq!(global(panel), GlobalPosition::new(100.0, 50.0));
+        assert_eq!(global(button), GlobalPosition::new(110.0, 70.0));
+        assert_eq!(global(label), GlobalPosition::new(111.0, 72.0));
+    }
+    
+    #[test]
+    fn test_component_lifecycle_hooks() {
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use crate::components::Component;
+        
+        // フックの呼び出し回数を記録するコンポーネント
+        #[derive(Debug, Clone)]
+        struct TrackedComponent {
+            added: Arc<AtomicUsize>,
+            removed: Arc<AtomicUsize>,
+        }
+        
+        impl Component for TrackedComponent {
+            fn on_added(&mut self, _entity_id: EntityId) {
+                self.added.fetch_add(1, Ordering::SeqCst);
+            }
+            
+            fn on_remove(&mut self, _entity_id: EntityId) {
+                self.removed.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+        
+        let added = Arc::new(AtomicUsize::new(0));
+        let removed = Arc::new(AtomicUsize::new(0));
+        let tracked = TrackedComponent {
+            added: added.clone(),
+            removed: removed.clone(),
+        };
+        
+        let mut manager = EntityManager::new();
+        let id1 = manager.create_entity();
+        let id2 = manager.create_entity();
+        let id3 = manager.create_entity();
+        
+        for id in [id1, id2, id3] {
+            manager.add_component(id, tracked.clone()).unwrap();
+        }
+        assert_eq!(added.load(Ordering::SeqCst), 3);
+        
+        // 明示的なコンポーネント削除
+        assert!(manager.remove_component::<TrackedComponent>(id1).is_some());
+        assert!(!manager.get_entities_with_component::<TrackedComponent>().contains(&id1));
+        assert_eq!(removed.load(Ordering::SeqCst), 1);
+        
+        // エンティティ削除時にもフックが呼ばれる
+        manager.remove_entity(id2);
+        manager.flush_removals();
+        assert_eq!(removed.load(Ordering::SeqCst), 2);
+        
+        // 全クリア時にもフックが呼ばれる
+        manager.clear();
+        assert_eq!(removed.load(Ordering::SeqCst), 3);
+    }
+    
+    #[test]
+    fn test_tag_mask_queries() {
+        let mut manager = EntityManager::new();
+        
+        #[derive(Debug)]
+        struct TestComponent;
+        
+        let id1 = manager.create_entity();
+        let id2 = manager.create_entity();
+        let id3 = manager.create_entity();
+        
+        let mut entity1 = Entity::new(id1);
+        entity1.add_component(TestComponent);
+        entity1.add_tag("cell");
+        entity1.add_tag("mine");
+        manager.register_entity(entity1);
+        
+        let mut entity2 = Entity::new(id2);
+        entity2.add_component(TestComponent);
+        entity2.add_tag("cell");
+        manager.register_entity(entity2);
+        
+        let mut entity3 = Entity::new(id3);
+        entity3.add_tag("mine");
+        manager.register_entity(entity3);
+        
+        // 複数タグのマスクによるクエリ
+        let mask = manager.tag_mask(&["cell", "mine"]).unwrap();
+        assert_eq!(manager.get_entities_with_tag_mask(mask), vec![id1]);
+        
+        // 未登録のタグを含む場合はマスクを作れない
+        assert!(manager.tag_mask(&["cell", "unknown"]).is_none());
+        
+        // コンポーネントとの複合クエリ
+        let mut result = manager.query_with_component_and_tag::<TestComponent>("cell");
+        result.sort_by_key(|id| id.0);
+        assert_eq!(result, vec![id1, id2]);
+        
+        // マネージャー経由のタグ追加・削除でマスクも更新される
+        assert!(manager.add_tag(id2, "mine"));
+        let mut result = manager.query_with_component_and_tag::<TestComponent>("mine");
+        result.sort_by_key(|id| id.0);
+        assert_eq!(result, vec![id1, id2]);
+        
+        manager.remove_tag(id1, "mine");
+        assert_eq!(manager.query_with_component_and_tag::<TestComponent>("mine"), vec![id2]);
+        assert!(!manager.get_entity(id1).unwrap().has_tag("mine"));
+    }
+    
+    #[test]
+    fn test_tag_bits_exhausted_falls_back_to_tag_index() {
+        let mut manager = EntityManager::new();
+        
+        #[derive(Debug)]
+        struct TestComponent;
+        
+        // 64個のビットを使い切る
+        for i in 0..64 {
+            manager.intern_tag(&format!("tag{}", i));
+        }
+        
+        let id = manager.create_entity();
+        let mut entity = Entity::new(id);
+        entity.add_component(TestComponent);
+        entity.add_tag("overflow");
+        manager.register_entity(entity);
+        
+        assert!(manager.tag_mask(&["overflow"]).is_none());
+        assert_eq!(manager.query_with_component_and_tag::<TestComponent>("overflow"), vec![id]);
+    }
+    
+    #[test]
+    fn test_cached_component_queries() {
+        use crate::components::{CellState, PlayerComponent};
+        
+        let mut manager = EntityManager::new();
+        
+        let cell1_entity = manager.create_builder().with_component(CellState::new()).build();
+        let cell1 = manager.register_entity(cell1_entity);
+        let cell2_entity = manager.create_builder().with_component(CellState::new()).build();
+        let cell2 = manager.register_entity(cell2_entity);
+        
+        assert_eq!(manager.cached_entities_with_component::<CellState>(), &[cell1, cell2]);
+        assert!(manager.is_query_cached::<CellState>());
+        
+        // 無関係な型のエンティティ追加ではキャッシュは破棄されない
+        let player = manager.create_entity();
+        manager.get_entity_mut(player).unwrap().add_component(PlayerComponent {
+            id: "p1".to_string(),
+            color: "#FF0000".to_string(),
+            last_action_time: 0.0,
+            is_local: true,
+        });
+        manager.build_component_index::<PlayerComponent>();
+        assert!(manager.is_query_cached::<CellState>());
+        
+        // 同じ型のエンティティ追加で破棄される
+        let cell3_entity = manager.create_builder().with_component(CellState::revealed()).build();
+        let cell3 = manager.register_entity(cell3_entity);
+        assert!(!manager.is_query_cached::<CellState>());
+        assert_eq!(manager.cached_entities_with_component::<CellState>(), &[cell1, cell2, cell3]);
+        
+        // 削除でも破棄される
+        manager.remove_entity(cell2);
+        manager.flush_removals();
+        assert!(!manager.is_query_cached::<CellState>());
+        assert_eq!(manager.cached_entities_with_component::<CellState>(), &[cell1, cell3]);
+    }
+    
+    #[test]
+    fn test_disabled_entities_skipped_by_queries() {
+        use crate::components::{CellState, Disabled};
+        
+        let mut manager = EntityManager::new();
+        
+        // UI画面（パネルとボタン）とセルを作成
+        let screen = manager.create_entity();
+        let panel = manager.create_entity();
+        let button_entity = manager.create_builder().with_tag("button").build();
+        let button = manager.register_entity(button_entity);
+        manager.set_parent(panel, screen).unwrap();
+        manager.set_parent(button, panel).unwrap();
+        
+        let cell1_entity = manager.create_builder().with_component(CellState::new()).build();
+        let cell1 = manager.register_entity(cell1_entity);
+        let cell2_entity = manager.create_builder().with_component(CellState::new()).build();
+        let cell2 = manager.register_entity(cell2_entity);
+        assert_eq!(manager.cached_entities_with_component::<CellState>().len(), 2);
+        
+        // セルを無効化するとクエリとキャッシュから外れる
+        manager.set_enabled(cell2, false).unwrap();
+        assert!(!manager.is_enabled(cell2));
+        assert_eq!(manager.get_entities_with_component::<CellState>(), vec![cell1]);
+        assert_eq!(manager.cached_entities_with_component::<CellState>(), &[cell1]);
+        assert_eq!(manager.get_entities_with_component_including_disabled::<CellState>().len(), 2);
+        
+        // パネルを無効化すると子孫ごと辿られない
+        manager.set_enabled(panel, false).unwrap();
+        assert_eq!(manager.iter_children(screen).count(), 0);
+        assert_eq!(manager.iter_descendants(screen).count(), 0);
+        let all: Vec<EntityId> = manager.iter_descendants_including_disabled(screen).collect();
+        assert_eq!(all, vec![panel, button]);
+        assert_eq!(manager.get_entities_with_tag("button"), vec![button]);
+        
+        // 再度有効化すると元に戻る
+        manager.set_enabled(cell2, true).unwrap();
+        manager.set_enabled(panel, true).unwrap();
+        assert_eq!(manager.cached_entities_with_component::<CellState>().len(), 2);
+        assert_eq!(manager.iter_descendants(screen).count(), 2);
+        assert!(manager.get_entities_with_component_including_disabled::<Disabled>().is_empty());
+        
+        // 無効化されたエンティティも子孫ごと削除される
+        manager.set_enabled(panel, false).unwrap();
+        assert_eq!(manager.despawn_recursive(screen), 3);
+    }
+}
\ No newline at end of file
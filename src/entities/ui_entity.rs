@@ -6,6 +6,7 @@
 use crate::components::{Position, UIElement, Button};
 use crate::entities::entity::{Entity, EntityId};
 use crate::entities::entity_manager::EntityBuilder;
+use crate::resources::Color;
 
 /// UIエンティティのタグ
 pub const UI_TAG: &str = "ui";
@@ -48,14 +49,14 @@ pub fn create_button(builder: EntityBuilder, id: &str, label: &str, x: f64, y: f
 }
 
 /// テキストUIエンティティを作成
-pub fn create_text(builder: EntityBuilder, content: &str, font: &str, size: f64, color: &str, x: f64, y: f64) -> Entity {
+pub fn create_text(builder: EntityBuilder, content: &str, font: &str, size: f64, color: Color, x: f64, y: f64) -> Entity {
     builder
         .with_component(Position::new(x, y))
         .with_component(UIElement::Text {
             content: content.to_string(),
             font: font.to_string(),
             size,
-            color: color.to_string(),
+            color,
         })
         .with_tag(UI_TAG)
         .with_tag(TEXT_TAG)
@@ -63,19 +64,34 @@ pub fn create_text(builder: EntityBuilder, content: &str, font: &str, size: f64,
 }
 
 /// アイコンUIエンティティを作成
-pub fn create_icon(builder: EntityBuilder, name: &str, size: f64, color: &str, x: f64, y: f64) -> Entity {
+pub fn create_icon(builder: EntityBuilder, name: &str, size: f64, color: Color, x: f64, y: f64) -> Entity {
     builder
         .with_component(Position::new(x, y))
         .with_component(UIElement::Icon {
             name: name.to_string(),
             size,
-            color: color.to_string(),
+            color,
         })
         .with_tag(UI_TAG)
         .with_tag("icon")
         .build()
 }
 
+/// 進捗バーUIエンティティを作成
+pub fn create_progress_bar(builder: EntityBuilder, width: f64, height: f64, color: Color, x: f64, y: f64) -> Entity {
+    builder
+        .with_component(Position::new(x, y))
+        .with_component(UIElement::ProgressBar {
+            width,
+            height,
+            progress: 0.0,
+            color,
+        })
+        .with_tag(UI_TAG)
+        .with_tag("progress_bar")
+        .build()
+}
+
 /// 汎用UIエンティティを作成（UIElement種類に応じて自動判断）
 pub fn create_ui_entity(builder: EntityBuilder, element: UIElement, x: f64, y: f64) -> Entity {
     match &element {
@@ -85,10 +101,13 @@ pub fn create_ui_entity(builder: EntityBuilder, element: UIElement, x: f64, y: f
             create_button(builder, id, label, x, y, button.width, button.height)
         },
         UIElement::Text { content, font, size, color } => {
-            create_text(builder, content, font, *size, color, x, y)
+            create_text(builder, content, font, *size, *color, x, y)
         },
         UIElement::Icon { name, size, color } => {
-            create_icon(builder, name, *size, color, x, y)
+            create_icon(builder, name, *size, *color, x, y)
+        },
+        UIElement::ProgressBar { width, height, color, .. } => {
+            create_progress_bar(builder, *width, *height, *color, x, y)
         },
     }
 }
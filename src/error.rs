@@ -0,0 +1,160 @@
+/**
+ * クレート全体で使うエラー型
+ * 
+ * 内部の処理は`GameError`を返し、種類ごとにパターンマッチで扱えるようにします。
+ * JavaScriptの値への変換は、`#[wasm_bindgen]`で公開する関数の境界でのみ行います。
+ */
+use std::fmt;
+use wasm_bindgen::{JsCast, JsValue};
+
+/**
+ * ゲームのエラー
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub enum GameError {
+    Network(String), // サーバーとの通信（未接続・送信の失敗など）
+    Board(String),   // ボードの操作（ローカルのゲーム以外でのボットの追加など）
+    Render(String),  // キャンバスやHTMLのメニューの描画
+    Config(String),  // 起動オプションや設定の値（不明な難易度・テーマなど）
+    Js(String),      // ブラウザのAPIの呼び出し（windowが無い・例外が発生したなど）
+}
+
+/**
+ * ゲームの処理の結果
+ */
+pub type GameResult<T> = Result<T, GameError>;
+
+impl GameError {
+    /**
+     * 描画中に発生したJavaScriptのエラーを描画のエラーにする
+     * 
+     * @param value 発生したエラー
+     * @return 描画のエラー
+     */
+    pub fn render(value: JsValue) -> Self {
+        GameError::Render(js_error_message(&value))
+    }
+
+    /**
+     * 通信中に発生したJavaScriptのエラーを通信のエラーにする
+     * 
+     * @param value 発生したエラー
+     * @return 通信のエラー
+     */
+    pub fn network(value: JsValue) -> Self {
+        GameError::Network(js_error_message(&value))
+    }
+
+    /**
+     * エラーの内容（種類を含まない）
+     */
+    pub fn message(&self) -> &str {
+        match self {
+            GameError::Network(message)
+            | GameError::Board(message)
+            | GameError::Render(message)
+            | GameError::Config(message)
+            | GameError::Js(message) => message,
+        }
+    }
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self {
+            GameError::Network(_) => "network",
+            GameError::Board(_) => "board",
+            GameError::Render(_) => "render",
+            GameError::Config(_) => "config",
+            GameError::Js(_) => "js",
+        };
+        write!(f, "{} error: {}", kind, self.message())
+    }
+}
+
+impl std::error::Error for GameError {}
+
+impl From<JsValue> for GameError {
+    fn from(value: JsValue) -> Self {
+        GameError::Js(js_error_message(&value))
+    }
+}
+
+impl From<GameError> for JsValue {
+    fn from(error: GameError) -> Self {
+        js_sys::Error::new(&error.to_string()).into()
+    }
+}
+
+/**
+ * エラー画面に表示し、不具合の報告に添える内容
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct ErrorReport {
+    pub summary: String,          // エラーの種類と内容（例: "render error: ..."）
+    pub diagnostics: Vec<String>, // 発生時の画面・接続状態と直近のログ
+}
+
+impl ErrorReport {
+    /**
+     * 新しい報告を作成する
+     * 
+     * @param summary エラーの種類と内容
+     * @param diagnostics 発生時の状態と直近のログ（1項目1行）
+     * @return 報告
+     */
+    pub fn new(summary: String, diagnostics: Vec<String>) -> Self {
+        Self { summary, diagnostics }
+    }
+
+    /**
+     * 不具合の報告に貼り付ける文字列にする
+     * 
+     * @return 1行目がエラー、2行目以降が状態とログの文字列
+     */
+    pub fn text(&self) -> String {
+        std::iter::once(self.summary.as_str())
+            .chain(self.diagnostics.iter().map(String::as_str))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/**
+ * JavaScriptのエラーの値から文言を取り出す
+ * 
+ * @param value 文字列またはErrorオブジェクト（それ以外はデバッグ表示）
+ * @return エラーの文言
+ */
+fn js_error_message(value: &JsValue) -> String {
+    if let Some(message) = value.as_string() {
+        return message;
+    }
+    match value.dyn_ref::<js_sys::Error>() {
+        Some(error) => error.message().into(),
+        None => format!("{:?}", value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_includes_kind() {
+        let error = GameError::Config("unknown theme: neon".to_string());
+        assert_eq!(error.to_string(), "config error: unknown theme: neon");
+        assert_eq!(error.message(), "unknown theme: neon");
+        assert!(matches!(GameError::Network("closed".to_string()), GameError::Network(_)));
+    }
+
+    #[test]
+    fn test_report_text_starts_with_summary() {
+        let report = ErrorReport::new(
+            "render error: canvas lost".to_string(),
+            vec!["screen: Game".to_string(), "[ERROR network] closed".to_string()],
+        );
+        assert_eq!(report.text(), "render error: canvas lost\nscreen: Game\n[ERROR network] closed");
+        assert_eq!(ErrorReport::new("js error: x".to_string(), Vec::new()).text(), "js error: x");
+    }
+}
\ No newline at end of file
@@ -0,0 +1,55 @@
+/**
+ * 解除できるイベントリスナー
+ *
+ * 登録したクロージャを保持し、破棄されたときにリスナーを解除します。
+ * `Closure::forget`を使うとクロージャが解放されずに残り続けるため、
+ * ゲームを停止して作り直せるようにこの型でリスナーを管理します。
+ */
+use std::any::Any;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::convert::FromWasmAbi;
+use wasm_bindgen::JsCast;
+use web_sys::EventTarget;
+
+/**
+ * 登録中のイベントリスナー（破棄すると解除される）
+ */
+pub struct EventListener {
+    target: EventTarget,          // 登録先
+    event_type: &'static str,     // イベントの種類
+    function: js_sys::Function,   // 登録した関数（解除に使う）
+    _closure: Box<dyn Any>,       // 関数の実体（解除するまで保持する）
+}
+
+impl EventListener {
+    /**
+     * イベントリスナーを登録する
+     * 
+     * @param target 登録先の要素やウィンドウ
+     * @param event_type イベントの種類（"mousedown"など）
+     * @param handler イベント発生時に呼び出す処理
+     * @return 登録したリスナー、登録に失敗した場合はErr(JsValue)
+     */
+    pub fn new<E, F>(target: &EventTarget, event_type: &'static str, handler: F) -> Result<Self, JsValue>
+    where
+        E: FromWasmAbi + 'static,
+        F: FnMut(E) + 'static,
+    {
+        let closure = Closure::wrap(Box::new(handler) as Box<dyn FnMut(E)>);
+        let function = closure.as_ref().unchecked_ref::<js_sys::Function>().clone();
+        target.add_event_listener_with_callback(event_type, &function)?;
+        
+        Ok(Self {
+            target: target.clone(),
+            event_type,
+            function,
+            _closure: Box::new(closure),
+        })
+    }
+}
+
+impl Drop for EventListener {
+    fn drop(&mut self) {
+        let _ = self.target.remove_event_listener_with_callback(self.event_type, &self.function);
+    }
+}
\ No newline at end of file
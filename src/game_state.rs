@@ -1,14 +1,52 @@
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
-use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement};
-use std::collections::HashMap;
-
-use crate::js_bindings::{log, update_connection_status, update_player_count, update_game_status};
-use crate::models::{CellValue, Screen, Player};
-use crate::utils::get_cell_index_from_coordinates;
-use crate::rendering::GameRenderer;
-use crate::network::{NetworkManager, MessageCallback};
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement, RtcPeerConnection};
+use std::collections::{HashMap, HashSet};
+
+use crate::js_bindings::{announce, copy_to_clipboard, share_or_copy, ClipboardQueue, CopyResult, request_notification_permission, show_browser_notification, update_connection_status, update_player_count, update_game_status, load_local_storage, save_local_storage, load_session_storage, save_session_storage, browser_language};
+use crate::models::{CellValue, Screen, Player, GameSnapshot, GameOptions};
+use crate::error::{ErrorReport, GameError, GameResult};
+use crate::logger::{self, log_error, log_warn, log_info, log_debug, log_group};
+use crate::offline_storage::{self, OfflineStorageEvent, OfflineStorageQueue};
+use crate::utils::{get_cell_index_from_coordinates, now_ms, wasm_memory_bytes};
+use crate::rendering::{GameRenderer, GameSummaryView, SummaryPlayerView, HistoryRowView, ProfileCardView, ThemeRowView, PAUSE_MENU_ITEMS, SUMMARY_BUTTONS, ERROR_SCREEN_BUTTONS};
+use crate::send_queue::MOVE_INTERVAL_MS;
+use crate::network::{NetworkManager, ConnectionCallbacks, NetworkEventQueue, PeerTransport, RevisionCheck, parse_server_url};
+use crate::peer_host::{PeerHost, PeerAction, HOST_PLAYER_ID};
+use crate::protocol::{ServerMessage, ClientMessage, PlayerInfo, GameStateSnapshot};
+use crate::room::{normalize_room_code, invite_link};
+use crate::dom_overlay::{DomOverlay, OverlayAction, OverlayQueue, OverlayView, OverlayButtonView, OverlaySettingView};
 use crate::board::Board;
+use crate::system::SystemRegistry;
+use crate::ecs::{World, HudPlugin, ResultSequencePlugin, BotPlugin, CursorPlugin};
+use crate::resources::{AudioResource, SoundEffect, MusicTrack, CoreGameResource, GamePhase, PhaseChanged, GameConfigResource, Difficulty, CustomBoardResource, CustomBoardField, LeaderboardResource, SettingsResource, SettingsItem, UiMode, AccessibilityResource, AccessibilityItem, ACCESSIBILITY_STORAGE_KEY, Language, Theme, ThemeUnlock, is_theme_unlocked, newly_unlocked_themes, SETTINGS_STORAGE_KEY, MAX_PLAYER_NAME_LENGTH, sanitize_player_name, resolve_cursor_color, StatsResource, STATS_STORAGE_KEY, HistoryResource, GameRecord, HISTORY_STORAGE_KEY, OfflineSave, OfflineMove, OFFLINE_SAVE_KEY, StreakResource, STREAK_STORAGE_KEY, DAILY_BOARD_WIDTH, DAILY_BOARD_HEIGHT, day_number, daily_challenge_mines, Emote, EmoteResource, CameraResource, TelemetryResource, RngResource, Color, BotResource, BotStrategy, BotMove, CURSOR_COLORS, HudResource, ResultSequenceResource, DiagnosticsResource, SessionResource, SESSION_SAVE_INTERVAL_MS, session_storage_key, ToastResource, ToastCategory, CountdownResource, PlayerStateResource, is_known_avatar, next_avatar, LobbyResource, RoomStateResource, RoomSelectButton, NetworkStatsResource, GameSummaryResource, RematchResource, ReconnectResource, MAX_RECONNECT_ATTEMPTS};
+
+/// 描画処理のシステム名（デバッグコンソールからの切り替え用）
+pub const RENDER_SYSTEM_NAME: &str = "RenderSystem";
+/// ネットワーク送信処理のシステム名（デバッグコンソールからの切り替え用）
+pub const NETWORK_SYSTEM_NAME: &str = "NetworkSystem";
+/// タッチを長押しと判定してフラグ操作にするまでの時間（ミリ秒）
+pub const LONG_PRESS_MS: f64 = 500.0;
+/// 共有する結果に絵文字のグリッドを付けるボードの最大の幅・高さ
+pub const SHARE_GRID_MAX_SIZE: usize = 10;
+/// ゲーム内のログパネルに表示する行数
+pub const LOG_PANEL_LINES: usize = 12;
+/// エラーの報告に添える直近のログの行数
+pub const ERROR_REPORT_LOG_LINES: usize = 20;
+/// 埋め込み用の最小表示で下端に表示する状態の帯の高さ
+pub const COMPACT_STATUS_HEIGHT: f64 = 28.0;
+/// サーバーから受け取った色が読み取れない場合のカーソルの色
+const FALLBACK_PLAYER_COLOR: Color = Color(0xFF0000);
+
+/**
+ * サーバーや設定のCSSの色をプレイヤーのカーソルの色に変換する
+ * 
+ * @param css `#RRGGBB`形式か`hsl()`形式の色
+ * @return 変換した色（読み取れない場合は赤）
+ */
+fn player_color(css: &str) -> Color {
+    Color::parse(css).unwrap_or(FALLBACK_PLAYER_COLOR)
+}
 
 /**
  * ゲーム全体の状態を管理する構造体
@@ -17,6 +55,7 @@ pub struct GameState {
     // プレイヤー関連
     pub local_player_id: Option<String>,  // ローカルプレイヤーのID
     pub players: HashMap<String, Player>, // 全プレイヤーの情報
+    pub idle_players: HashSet<String>,    // 離席中と通知済みのプレイヤー
 
     // 通信関連
     pub network: NetworkManager,          // ネットワーク管理
@@ -30,28 +69,92 @@ pub struct GameState {
     pub mouse_x: f64,                     // マウスX座標
     pub mouse_y: f64,                     // マウスY座標
     pub is_mouse_down: bool,              // マウスボタン押下状態
-    pub last_position_update: f64,        // 最後に位置情報を送信した時間
+    pub touch_start: Option<(f64, f64, f64)>, // タッチ開始時の座標と時刻
+    pub last_position_update: f64,        // ピアツーピアの参加者にホストの位置を最後に送った時間
     
     // 画面状態
     pub current_screen: Screen,           // 現在の画面
+    pub settings_return_screen: Screen,   // 設定画面から戻る先の画面
+    pub help_return_screen: Screen,       // ヘルプ画面から戻る先の画面
+    pub practice_mines: Option<Vec<usize>>, // 履歴の盤面を練習中の場合の地雷の配置
+    pub practice_moves: Vec<OfflineMove>, // オフラインのゲームでの操作の記録（保存用）
+    pub offline_play: bool, // タイトル画面でオフラインを選んだかどうか（難易度を選ぶとサーバーに接続せずに始める）
+    pub offline_mine_count: Option<usize>, // 地雷をランダムに配置するオフラインのゲームの地雷の数
+    pub mines_pending: bool, // 最初にセルを開くまで地雷の配置を待っているかどうか
+    pub offline_storage: OfflineStorageQueue, // IndexedDBへの保存・読み込みの結果
+    pub clipboard: ClipboardQueue, // クリップボードへのコピーの結果
+    pub page_hidden: bool, // タブが非表示かどうか
+    pub offline: bool, // ブラウザがオフラインかどうか（navigator.onLine）
+    pub compact: bool, // 埋め込み用の最小表示かどうか
+    pub log_panel: bool, // ゲーム内のログパネルを表示するかどうか
+    pub debug_overlay: bool, // 診断情報（メモリ使用量など）を表示するかどうか
+    pub session_key: String, // sessionStorageに再読み込み用のセッションを保存するキー
+    pub last_session_save: f64, // 最後にセッションを保存した時間
+    pub resume_match: bool, // 再読み込み前に対戦中だった場合、起動後に接続し直す
+    pub error_report: Option<ErrorReport>, // 表示中のエラー画面の内容（ゲームの処理は止める）
+    pub rng: RngResource, // ローカルで作る盤面の乱数（シードを指定すると同じ順に同じ盤面になる）
+    pub windowed_canvas_size: Option<(u32, u32)>, // 全画面表示にする前のキャンバスのサイズ
+    pub dom_overlay: Option<DomOverlay>, // HTMLで表示するメニュー（表示方法がHTMLの場合のみ）
+    pub overlay_actions: OverlayQueue, // HTMLのメニューでの操作
+    pub telemetry: TelemetryResource, // ホストページに渡す性能・プレイの集計
+    pub telemetry_callback: Option<js_sys::Function>, // 集計結果を受け取るホストページの関数
+    pub connection_callbacks: ConnectionCallbacks, // 接続の状態の変化を受け取るホストページの関数
+    connection_reported: bool,            // ホストページに最後に知らせた接続の状態
+    pub daily_challenge_day: Option<i64>, // デイリーチャレンジ中の場合の日番号
+    pub name_input_focused: bool,         // タイトル画面の名前入力欄に入力中かどうか
     
     // ボード関連
     pub board: Board,                     // ゲームボード
+    pub game_config: GameConfigResource,  // 選択された難易度とボード設定
+    pub custom_board: CustomBoardResource, // カスタムボード画面の入力値
+    pub core_game: CoreGameResource,      // ゲーム進行とスコア
+    pub leaderboard: LeaderboardResource, // ランキングとパネルの表示状態
+    pub emotes: EmoteResource,            // エモートの吹き出しと選択メニュー
+    pub toasts: ToastResource,            // 他のプレイヤーの操作の通知
+    pub camera: CameraResource,           // ゲーム画面の視点（プレイヤーの追従）
+    pub hud: World,                       // ゲーム画面のHUDのUIエンティティ
+    pub countdown: CountdownResource,     // 開始前のカウントダウン
+    pub lobby: LobbyResource,             // ロビーのプレイヤーと準備完了の状態
+    pub room_state: RoomStateResource,    // ルーム選択画面のルーム一覧
+    pub network_stats: NetworkStatsResource, // ハートビートで測った通信の遅延
+    pub summary: GameSummaryResource,     // ゲーム結果パネル用の集計
+    pub rematch: RematchResource,         // 再戦投票の状況
+    pub reconnect: ReconnectResource,     // 切断時の再接続の状態
+    pub peer_host: Option<PeerHost>,      // ピアツーピアでホストしている場合の参加者と送信状態
+    pending_peer: Option<RtcPeerConnection>, // アンサーを待っている参加者との接続
+    board_config_pending: bool,           // ルームに入った後、ホストならボード設定を送る（最初のロビーの状態で判断する）
+    
+    // 設定
+    pub settings: SettingsResource,       // プレイヤー設定（localStorageに保存）
+    pub accessibility: AccessibilityResource, // アクセシビリティ設定（設定とは別に保存）
+    pub stats: StatsResource,             // 累計統計（localStorageに保存）
+    pub history: HistoryResource,         // 最近のゲームの履歴（localStorageに保存）
+    pub streak: StreakResource,           // デイリーチャレンジの連続記録（localStorageに保存）
+    
+    // サウンド
+    pub audio: AudioResource,             // 効果音の再生
+    
+    // システム管理
+    pub systems: SystemRegistry,          // システムの登録と有効・無効の管理
 }
 
 impl GameState {
     /**
      * GameStateの新しいインスタンスを作成する
      * 
+     * 起動オプションで指定された項目は保存された設定より優先します。
+     * 
      * @param canvas キャンバス要素
+     * @param options ホストページから渡された起動オプション
      * @return GameStateインスタンス
      */
-    pub fn new(canvas: HtmlCanvasElement) -> Result<Self, JsValue> {
+    pub fn new(canvas: HtmlCanvasElement, options: &GameOptions) -> GameResult<Self> {
         // キャンバスから2Dコンテキストを取得
         let context = canvas
             .get_context("2d")?
-            .unwrap()
-            .dyn_into::<CanvasRenderingContext2d>()?;
+            .ok_or_else(|| GameError::Render("2d context is not available".to_string()))?
+            .dyn_into::<CanvasRenderingContext2d>()
+            .map_err(|_| GameError::Render("2d context is not a CanvasRenderingContext2d".to_string()))?;
         
         // デフォルトのボードサイズ
         let board_width = 16;
@@ -61,18 +164,95 @@ impl GameState {
         // セルのサイズを計算
         let cell_size = ((canvas.width() as f64).min(canvas.height() as f64) - 40.0) / board_width as f64;
 
+        // ページを再読み込みする前のセッションがあれば引き継ぐ
+        let session_key = session_storage_key(&canvas.id());
+        let session = load_session_storage(&session_key)
+            .and_then(|json| SessionResource::from_json(&json))
+            .unwrap_or_default();
+        
+        // 保存された設定を読み込む（セッションの設定を優先し、初回起動時はブラウザの言語設定から表示言語を決める）
+        let settings = session.settings.clone()
+            .or_else(|| load_local_storage(SETTINGS_STORAGE_KEY).map(|json| SettingsResource::from_json(&json)))
+            .unwrap_or_else(|| {
+                let mut settings = SettingsResource::new();
+                if let Some(locale) = browser_language() {
+                    settings.language = Language::from_locale(&locale);
+                }
+                settings
+            });
+        let stats = load_local_storage(STATS_STORAGE_KEY)
+            .map(|json| StatsResource::from_json(&json))
+            .unwrap_or_default();
+        
+        // 起動オプションの指定を反映する
+        let mut settings = settings;
+        if let Some(locale) = &options.locale {
+            settings.language = Language::from_locale(locale);
+        }
+        if let Some(theme) = options.theme.as_deref().and_then(Theme::from_key) {
+            settings.theme = theme;
+        }
+        if let Some(name) = &options.player_name {
+            settings.player_name = sanitize_player_name(name);
+        }
+        if let Some(ui_mode) = options.ui_mode.as_deref().and_then(UiMode::from_key) {
+            settings.ui_mode = ui_mode;
+        }
+        let mut game_config = GameConfigResource::new();
+        if let Some(difficulty) = options.difficulty.as_deref().or(session.difficulty.as_deref()).and_then(Difficulty::from_key) {
+            game_config.set_difficulty(difficulty);
+        }
+        let seed = options.seed.unwrap_or_else(|| game_config.get_random_seed());
+        
+        // 統計が消えるなどして解放条件を満たさなくなったテーマは使えない
+        if !is_theme_unlocked(settings.theme, &stats) {
+            settings.theme = Theme::Dark;
+        }
+        // アクセシビリティ設定は別に保存する（未保存なら以前の設定から引き継ぐ）
+        let accessibility = load_local_storage(ACCESSIBILITY_STORAGE_KEY)
+            .map(|json| AccessibilityResource::from_json(&json))
+            .or_else(|| load_local_storage(SETTINGS_STORAGE_KEY).map(|json| AccessibilityResource::from_legacy_settings(&json)))
+            .unwrap_or_default();
+        let history = load_local_storage(HISTORY_STORAGE_KEY)
+            .map(|json| HistoryResource::from_json(&json))
+            .unwrap_or_default();
+        let streak = load_local_storage(STREAK_STORAGE_KEY)
+            .map(|json| StreakResource::from_json(&json))
+            .unwrap_or_default();
+        
         // レンダラーの作成
-        let renderer = GameRenderer::new(context.clone());
+        let mut renderer = GameRenderer::new(context.clone());
+        renderer.set_theme(settings.theme);
+        renderer.set_language(settings.language);
+        renderer.set_accessibility(&accessibility);
+        
+        // オーディオの作成（AudioContextは最初のクリック時に作成する）
+        let mut audio = AudioResource::new();
+        audio.apply_settings(&settings);
 
         // ネットワークマネージャーの作成
-        let network = NetworkManager::new();
+        let mut network = NetworkManager::new();
+        network.server_url = options.server_url.as_deref().map(parse_server_url).transpose()?;
+        network.binary_protocol = options.binary_protocol;
+        network.room = options.room.as_deref().or(session.room.as_deref()).and_then(normalize_room_code);
+        network.resume_player_id = session.player_id.clone();
         
         // ボードの作成
         let board = Board::new(board_width, board_height, mine_count, cell_size);
+        
+        // HUDのUIエンティティの作成
+        let mut hud = World::new();
+        hud.add_plugin(HudPlugin);
+        hud.insert_resource(DiagnosticsResource::new());
+        hud.add_plugin(ResultSequencePlugin);
+        hud.insert_resource(BotResource::new(seed));
+        hud.add_plugin(BotPlugin);
+        hud.add_plugin(CursorPlugin);
 
         Ok(Self {
             local_player_id: None,
             players: HashMap::new(),
+            idle_players: HashSet::new(),
             network,
             canvas,
             context,
@@ -80,166 +260,316 @@ impl GameState {
             mouse_x: 0.0,
             mouse_y: 0.0,
             is_mouse_down: false,
+            touch_start: None,
             last_position_update: 0.0,
             current_screen: Screen::Title,  // 初期画面はタイトル画面
+            settings_return_screen: Screen::Title,
+            help_return_screen: Screen::Title,
+            practice_mines: None,
+            practice_moves: Vec::new(),
+            offline_play: false,
+            offline_mine_count: None,
+            mines_pending: false,
+            offline_storage: OfflineStorageQueue::default(),
+            clipboard: ClipboardQueue::default(),
+            page_hidden: false,
+            offline: false,
+            compact: options.compact,
+            log_panel: options.log_panel,
+            debug_overlay: options.debug_overlay,
+            session_key,
+            last_session_save: 0.0,
+            resume_match: session.in_match && !options.compact,
+            error_report: None,
+            rng: RngResource::new(seed),
+            windowed_canvas_size: None,
+            dom_overlay: None,
+            overlay_actions: OverlayQueue::default(),
+            telemetry: TelemetryResource::new(),
+            telemetry_callback: None,
+            connection_callbacks: ConnectionCallbacks::default(),
+            connection_reported: false,
+            daily_challenge_day: None,
+            name_input_focused: false,
             board,
+            game_config,
+            custom_board: CustomBoardResource::new(),
+            core_game: CoreGameResource::new(),
+            leaderboard: LeaderboardResource::new(),
+            emotes: EmoteResource::new(),
+            toasts: ToastResource::new(),
+            camera: CameraResource::new(),
+            hud,
+            countdown: CountdownResource::new(),
+            lobby: LobbyResource::new(),
+            room_state: RoomStateResource::new(),
+            network_stats: NetworkStatsResource::new(),
+            summary: GameSummaryResource::new(),
+            rematch: RematchResource::new(),
+            reconnect: ReconnectResource::new(),
+            peer_host: None,
+            pending_peer: None,
+            board_config_pending: false,
+            settings,
+            accessibility,
+            stats,
+            history,
+            streak,
+            audio,
+            systems: SystemRegistry::new(),
         })
     }
 
     /**
      * WebSocketサーバーに接続する
      * 
-     * @return 成功した場合はOk(()), エラーの場合はErr(JsValue)
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
      */
-    pub fn connect_websocket(&mut self) -> Result<(), JsValue> {
-        // WebSocketメッセージを処理するコールバック関数を作成
-        let this = self as *mut GameState;
-        let message_callback: MessageCallback = Box::new(move |json: &serde_json::Value| {
-            let game_state = unsafe { &mut *this };
-            
-            // メッセージタイプによって処理を分ける
-            if let Some(msg_type) = json["type"].as_str() {
-                match msg_type {
-                    "init" => {
-                        // 初期化メッセージ
-                        log("Init message received!");
-                        if let Some(player_id) = json["playerId"].as_str() {
-                            // 自分をプレイヤーとして追加
-                            log(&format!("Adding local player: {}", player_id));
-                            game_state.add_player(player_id.to_string(), json["players"].clone());
-                        }
-                        
-                        // ゲーム状態を更新
-                        if let Some(game_data) = json["gameState"].as_object() {
-                            game_state.update_game_state(game_data);
-                        }
-                    },
-                    "player_joined" => {
-                        // 新しいプレイヤーが参加
-                        if let Some(id) = json["id"].as_str() {
-                            if !game_state.players.contains_key(id) {
-                                log(&format!("Player joined: {}", id));
-                                let color = json["color"].as_str().unwrap_or("#FF0000").to_string();
-                                game_state.add_remote_player(id, 0.0, 0.0, color);
-                            }
-                        }
-                    },
-                    "player_left" => {
-                        // プレイヤーが退出
-                        if let Some(id) = json["id"].as_str() {
-                            log(&format!("Player left: {}", id));
-                            game_state.remove_player(id);
-                        }
-                    },
-                    "player_moved" => {
-                        // プレイヤーの移動
-                        if let (Some(id), Some(x), Some(y)) = (
-                            json["id"].as_str(),
-                            json["x"].as_f64(),
-                            json["y"].as_f64()
-                        ) {
-                            game_state.update_player_position(id, x, y);
-                        }
-                    },
-                    "cells_revealed" => {
-                        // セルが開かれた
-                        if let Some(cells) = json["cells"].as_array() {
-                            if let Some(values) = json["values"].as_object() {
-                                // 各セルを開く
-                                for cell in cells {
-                                    if let Some(index) = cell.as_i64() {
-                                        let index = index as usize;
-                                        game_state.board.revealed[index] = true;
-                                        
-                                        // セルの値を設定
-                                        if let Some(value) = values.get(&index.to_string()) {
-                                            if let Some(v) = value.as_i64() {
-                                                if v == -1 {
-                                                    game_state.board.cells[index] = CellValue::Mine;
-                                                } else {
-                                                    game_state.board.cells[index] = CellValue::Empty(v as u8);
-                                                }
-                                            }
-                                        }
-                                    }
-                                }
-                                
-                                // ゲームオーバーかどうか
-                                if let Some(game_over) = json["gameOver"].as_bool() {
-                                    game_state.board.game_over = game_over;
-                                }
-                                
-                                // 勝利かどうか
-                                if let Some(win) = json["win"].as_bool() {
-                                    game_state.board.win = win;
-                                }
-                                
-                                // ゲーム状態を更新
-                                game_state.update_game_status();
-                            }
-                        }
-                    },
-                    "game_over" => {
-                        // ゲームオーバー
-                        game_state.board.game_over = true;
-                        
-                        // 勝利かどうか
-                        if let Some(win) = json["win"].as_bool() {
-                            game_state.board.win = win;
-                        }
-                        
-                        // 全てのセル情報を受け取って表示
-                        if let Some(all_cell_values) = json["allCellValues"].as_object() {
-                            log(&format!("ゲームオーバー：全てのセル情報を受信 ({} 個)", all_cell_values.len()));
-                            
-                            // 全てのセルの値を設定
-                            for (index_str, value) in all_cell_values {
-                                if let Ok(index) = index_str.parse::<usize>() {
-                                    if index < game_state.board.cells.len() {
-                                        if let Some(v) = value.as_i64() {
-                                            if v == -1 {
-                                                game_state.board.cells[index] = CellValue::Mine;
-                                            } else {
-                                                game_state.board.cells[index] = CellValue::Empty(v as u8);
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                            
-                            // 地雷セルは表示、他は元のまま
-                            for i in 0..game_state.board.cells.len() {
-                                if let CellValue::Mine = game_state.board.cells[i] {
-                                    game_state.board.revealed[i] = true;
-                                }
-                            }
-                        }
-                        
-                        // ゲーム状態を更新
-                        game_state.update_game_status();
-                    },
-                    "flag_toggled" => {
-                        // フラグが切り替えられた
-                        if let Some(index) = json["index"].as_i64() {
-                            let index = index as usize;
-                            if index < game_state.board.flagged.len() {
-                                if let Some(flagged) = json["flagged"].as_bool() {
-                                    game_state.board.flagged[index] = flagged;
-                                }
-                            }
-                        }
-                    },
-                    _ => {
-                        log(&format!("Unknown message type: {}", msg_type));
+    pub fn connect_websocket(&mut self) -> GameResult<()> {
+        self.telemetry.record_connection();
+        
+        // 届いたメッセージは受信キューに溜まり、`step`で処理する
+        self.network.connect()
+    }
+
+    /**
+     * サーバーから届いたメッセージを処理する
+     * 
+     * 不正なメッセージは受信時に`ServerMessage`への変換で拒否されるため、
+     * ここでは種類ごとの処理だけを行います。
+     * 
+     * @param message サーバーからのメッセージ
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
+     */
+    pub fn handle_server_message(&mut self, message: &ServerMessage) -> GameResult<()> {
+        match message {
+            ServerMessage::Init { player_id, players, game_state } => {
+                // 初期化メッセージ（新しい接続のため、前の接続で測った遅延は捨てる）
+                let _group = log_group!(Debug, "Init message received!");
+                self.network_stats.clear();
+                
+                // 自分をプレイヤーとして追加
+                log_debug!("Adding local player: {}", player_id);
+                self.add_player(player_id.clone(), players);
+                
+                // 名前とカーソルの色を他のプレイヤーに通知
+                self.send_join()?;
+                
+                // 招待リンクなどでルームが指定されていれば参加する
+                if let Some(room) = &self.network.room {
+                    self.network.send_join_room(room)?;
+                }
+                
+                // ゲーム状態を更新（ボード設定はホストでなければルームのものに従う）
+                self.update_game_state(game_state);
+                self.board_config_pending = true;
+                
+                // 切断からの再接続であれば、参加やルームの移動を反映した状態を要求し直す
+                if self.reconnect.finish() {
+                    log_info!("再接続しました。状態を同期します");
+                    self.show_notice("toast.reconnected");
+                    self.network.send_sync_request()?;
+                }
+            },
+            ServerMessage::StateSync { players, game_state } => {
+                // 要求した現在の状態（タブが表示に戻ったとき）
+                self.update_game_state(game_state);
+                self.sync_remote_players(players);
+            },
+            ServerMessage::RoomJoined { room } => {
+                // サーバーが受け付けたルームを現在のルームにする（ルームの状態は続く`state_sync`で届く）
+                if let Some(room) = normalize_room_code(room) {
+                    log_info!("Joined room: {}", room);
+                    self.network.room = Some(room);
+                }
+                self.room_state.clear();
+                if self.current_screen == Screen::RoomSelect {
+                    self.current_screen = Screen::Lobby;
+                }
+                self.board_config_pending = true;
+            },
+            ServerMessage::RoomList { rooms } => {
+                // ルーム一覧の応答
+                self.room_state.update(rooms);
+            },
+            ServerMessage::HeartbeatAck { sent_at } => {
+                // ハートビートの応答（往復時間を遅延として記録）
+                self.network_stats.record_ack(*sent_at, now_ms());
+            },
+            ServerMessage::Leaderboard { entries } => {
+                // リーダーボードの応答
+                self.leaderboard.update(entries);
+            },
+            ServerMessage::GameReset { board_width, board_height, mine_count } => {
+                // ボードが作り直された（リセットやボード設定の変更）
+                // リセットメッセージには進行状態が含まれないため、初期状態に戻す
+                self.board.resize_from_server(*board_width, *board_height, *mine_count);
+                self.board.initialize();
+                self.network.reset_board_revision(0);
+                self.countdown.clear();
+                self.rematch.clear();
+                self.fit_board_to_canvas();
+                self.update_game_status();
+            },
+            ServerMessage::PlayerJoined { id, color } => {
+                // 新しいプレイヤーが参加
+                if !self.players.contains_key(id) {
+                    log_info!("Player joined: {}", id);
+                    self.add_remote_player(id, 0.0, 0.0, player_color(color));
+                    self.show_toast(ToastCategory::Presence, "toast.join", id);
+                }
+            },
+            ServerMessage::PlayerRecolored { id, color } => {
+                // プレイヤーがカーソルの色を設定
+                if let Some(player) = self.players.get_mut(id) {
+                    player.color = player_color(color);
+                }
+            },
+            ServerMessage::PlayerAvatar { id, avatar } => {
+                // プレイヤーがアバターを設定（候補に無いアバターは表示しない）
+                if let Some(player) = self.players.get_mut(id) {
+                    player.avatar = if is_known_avatar(avatar) { avatar.clone() } else { String::new() };
+                }
+            },
+            ServerMessage::PlayerPing { id, ping } => {
+                // サーバーが測ったプレイヤーの通信の遅延
+                if let Some(player) = self.players.get_mut(id) {
+                    player.ping = Some(*ping);
+                }
+            },
+            ServerMessage::PlayerRenamed { id, name } => {
+                // プレイヤーが名前を設定
+                self.rename_player(id, name);
+            },
+            ServerMessage::RematchState { votes, total, deadline, server_time } => {
+                // 再戦投票の状況が更新された
+                self.rematch.update(votes, *total, *deadline, *server_time, now_ms());
+            },
+            ServerMessage::LobbyState { active, host_id, players } => {
+                // ロビーの状態が更新された
+                self.lobby.update(*active, host_id.as_deref(), players);
+                
+                // 入ったルームのホストであれば、選択した難易度のボード設定を送信（ロビー中のみ反映される）
+                if std::mem::take(&mut self.board_config_pending) && self.lobby.is_active() && self.is_lobby_host() {
+                    if let Err(e) = self.send_board_config() {
+                        log_error!("Board config send error: {:?}", e);
                     }
                 }
-            }
-            
-            Ok(())
-        });
+                
+                // ロビーが終了したらゲーム画面に移る
+                if self.current_screen == Screen::Lobby && !self.lobby.is_active() {
+                    self.current_screen = Screen::Game;
+                }
+            },
+            ServerMessage::Countdown { ends_at, server_time } => {
+                // ロビー終了によるカウントダウン開始
+                if let Some(ends_at) = ends_at {
+                    self.countdown.start(*ends_at, *server_time, now_ms());
+                    self.notify_in_background("notification.game_starting");
+                }
+            },
+            ServerMessage::Emote { id, emote } => {
+                // 他のプレイヤーのエモート（未知のエモートは無視）
+                if let Some(emote) = Emote::from_key(emote) {
+                    self.emotes.show(id, emote, now_ms());
+                    self.mark_player_active(id);
+                }
+            },
+            ServerMessage::PlayerLeft { id } => {
+                // プレイヤーが退出
+                log_info!("Player left: {}", id);
+                self.show_toast(ToastCategory::Presence, "toast.leave", id);
+                self.remove_player(id);
+            },
+            ServerMessage::PlayerMoved { id, x, y } => {
+                // プレイヤーの移動
+                self.update_player_position(id, *x, *y);
+            },
+            ServerMessage::CellsRevealed { player_id, revision, cells } => {
+                // セルが開かれた（反映済みの版数は捨て、取りこぼしていれば全体の状態を要求した上で反映する）
+                if self.network.check_board_revision(*revision) == RevisionCheck::Stale {
+                    return Ok(());
+                }
+                self.mark_player_active(player_id);
+                
+                // 各セルを開く（範囲外のインデックスや不正な値は無視される）
+                let mines = self.board.apply_revealed_cells(cells);
+                
+                // ゲーム結果用に操作回数・地雷・プレイヤーごとのセル数を集計
+                self.summary.record_reveal(Some(player_id), cells.len(), mines);
+                
+                if self.is_local_action(player_id) {
+                    // 自分が開いたセルを統計に加算
+                    let difficulty = self.game_config.difficulty.clone();
+                    let before = self.stats.clone();
+                    self.stats.record_cells_revealed(&difficulty, cells.len() as u64);
+                    self.announce_unlocks(&before);
+                    self.save_stats()?;
+                } else if mines > 0 {
+                    // 他のプレイヤーが地雷を踏んだら通知
+                    self.show_toast(ToastCategory::Mine, "toast.mine", player_id);
+                }
+                
+                // 効果音（連鎖して複数開いた場合は和音）
+                let effect = if cells.len() > 1 { SoundEffect::Chord } else { SoundEffect::Reveal };
+                self.play_sound(effect);
+                
+                // ゲーム状態を更新
+                self.update_game_status();
+            },
+            ServerMessage::GameOver { win, all_cell_values, .. } => {
+                // ゲームオーバー
+                self.board.game_over = true;
+                self.board.win = *win;
+                
+                // 効果音
+                let effect = if *win { SoundEffect::Win } else { SoundEffect::Explosion };
+                self.play_sound(effect);
+                self.notify_in_background(if *win { "notification.win" } else { "notification.lose" });
+                
+                // 全てのセル情報を受け取って表示
+                let _group = log_group!(Debug, "ゲームオーバー：全てのセル情報を受信 ({} 個)", all_cell_values.len());
+                self.board.apply_cell_values(all_cell_values);
+                
+                // 地雷セルは表示、他は元のまま
+                for i in 0..self.board.cells.len() {
+                    if let CellValue::Mine = self.board.cells[i] {
+                        self.board.revealed[i] = true;
+                    }
+                }
+                log_debug!("地雷を表示: {} 個", self.board.mine_indices().len());
+                
+                // ゲーム状態を更新
+                self.update_game_status();
+            },
+            ServerMessage::FlagToggled { player_id, revision, index, flagged } => {
+                // フラグが切り替えられた（版数の扱いはセルを開いた場合と同じ）
+                if self.network.check_board_revision(*revision) == RevisionCheck::Stale {
+                    return Ok(());
+                }
+                self.mark_player_active(player_id);
+                if *index >= self.board.flagged.len() {
+                    return Ok(());
+                }
+                
+                self.board.flagged[*index] = *flagged;
+                self.play_sound(SoundEffect::Flag);
+                
+                // 自分が立てたフラグは統計に加算し、他のプレイヤーのフラグは通知
+                if *flagged {
+                    self.summary.record_flag(player_id);
+                    if self.is_local_action(player_id) {
+                        let difficulty = self.game_config.difficulty.clone();
+                        self.stats.record_flag_placed(&difficulty);
+                        self.save_stats()?;
+                    } else {
+                        self.show_toast(ToastCategory::Flag, "toast.flag", player_id);
+                    }
+                }
+            },
+        }
         
-        // WebSocketを接続
-        self.network.connect(message_callback)
+        Ok(())
     }
 
     /**
@@ -248,58 +578,69 @@ impl GameState {
      * @param id プレイヤーID
      * @param other_players 他のプレイヤー情報
      */
-    pub fn add_player(&mut self, id: String, other_players: serde_json::Value) {
+    pub fn add_player(&mut self, id: String, other_players: &[PlayerInfo]) {
         // 自分をローカルプレイヤーとして設定
         self.local_player_id = Some(id.clone());
         self.network.set_local_player_id(id.clone());
         
-        // 自分をプレイヤーとして追加
+        // 自分をプレイヤーとして追加（設定に名前があればそれを使う）
+        let name = if self.settings.player_name.is_empty() {
+            self.default_player_name(&id)
+        } else {
+            self.settings.player_name.clone()
+        };
         let player = Player {
             id: id.clone(),
-            name: format!("プレイヤー_{}", id),
+            name,
             x: self.mouse_x,
             y: self.mouse_y,
-            color: "#00FF00".to_string(), // 自分は緑色
+            color: player_color(&self.settings.cursor_color),
+            avatar: self.settings.avatar.clone(),
             score: 0,
             is_local: true,
             is_host: true,
             is_alive: true,
             cells_revealed: 0,
+            last_update: now_ms(),
+            ping: None,
         };
         self.players.insert(id, player);
         
         // 他のプレイヤーも追加
-        if let Some(players) = other_players.as_object() {
-            for (player_id, player_data) in players {
-                if let Some(player_obj) = player_data.as_object() {
-                    if let (Some(x), Some(y), Some(color)) = (
-                        player_obj.get("x").and_then(|v| v.as_f64()),
-                        player_obj.get("y").and_then(|v| v.as_f64()),
-                        player_obj.get("color").and_then(|v| v.as_str())
-                    ) {
-                        let player = Player {
-                            id: player_id.clone(),
-                            name: format!("プレイヤー_{}", player_id),
-                            x,
-                            y,
-                            color: color.to_string(),
-                            score: 0,
-                            is_local: false,
-                            is_host: false,
-                            is_alive: true,
-                            cells_revealed: 0,
-                        };
-                        self.players.insert(player_id.clone(), player);
-                    }
-                }
-            }
+        for info in other_players {
+            let name = if info.name.is_empty() {
+                self.default_player_name(&info.id)
+            } else {
+                info.name.clone()
+            };
+            let player = Player {
+                id: info.id.clone(),
+                name,
+                x: info.x,
+                y: info.y,
+                color: player_color(&info.color),
+                avatar: if is_known_avatar(&info.avatar) { info.avatar.clone() } else { String::new() },
+                score: 0,
+                is_local: false,
+                is_host: false,
+                is_alive: true,
+                cells_revealed: 0,
+                last_update: now_ms(),
+                ping: None,
+            };
+            self.players.insert(info.id.clone(), player);
         }
         
         // プレイヤー数の表示を更新
         update_player_count(self.players.len());
         
-        // ゲーム画面に切り替え
-        self.current_screen = Screen::Game;
+        // ルーム選択中であれば一覧を取り直し、それ以外はロビー画面に切り替え
+        // （ゲームが始まっていればlobby_stateでゲーム画面に移る）
+        if self.current_screen == Screen::RoomSelect && self.network.room.is_none() {
+            self.room_state.clear();
+        } else {
+            self.current_screen = Screen::Lobby;
+        }
     }
 
     /**
@@ -310,18 +651,21 @@ impl GameState {
      * @param y Y座標
      * @param color カーソルの色
      */
-    pub fn add_remote_player(&mut self, id: &str, x: f64, y: f64, color: String) {
+    pub fn add_remote_player(&mut self, id: &str, x: f64, y: f64, color: Color) {
         let player = Player {
             id: id.to_string(),
-            name: format!("プレイヤー_{}", id),
+            name: self.default_player_name(id),
             x,
             y,
             color,
+            avatar: String::new(),
             score: 0,
             is_local: false,
             is_host: false,
             is_alive: true,
             cells_revealed: 0,
+            last_update: now_ms(),
+            ping: None,
         };
         self.players.insert(id.to_string(), player);
         
@@ -330,257 +674,3314 @@ impl GameState {
     }
 
     /**
-     * プレイヤーを削除する
+     * サーバーから受け取ったプレイヤー一覧に位置と色を合わせる
      * 
-     * @param id 削除するプレイヤーのID
+     * 一覧に無いプレイヤーは削除し、知らないプレイヤーは追加します。
+     * 
+     * @param players サーバーから受け取ったローカルプレイヤー以外のプレイヤー一覧
      */
-    pub fn remove_player(&mut self, id: &str) {
-        self.players.remove(id);
+    pub fn sync_remote_players(&mut self, players: &[PlayerInfo]) {
+        let ids: HashSet<&str> = players.iter().map(|player| player.id.as_str()).collect();
+        let stale: Vec<String> = self.players.keys()
+            .filter(|id| Some(id.as_str()) != self.local_player_id.as_deref() && !ids.contains(id.as_str()))
+            .cloned()
+            .collect();
+        for id in stale {
+            self.remove_player(&id);
+        }
         
-        // プレイヤー数の表示を更新
-        update_player_count(self.players.len());
+        for data in players {
+            let color = player_color(&data.color);
+            match self.players.get_mut(&data.id) {
+                Some(player) => {
+                    player.x = data.x;
+                    player.y = data.y;
+                    player.color = color;
+                    player.avatar = if is_known_avatar(&data.avatar) { data.avatar.clone() } else { String::new() };
+                },
+                None => self.add_remote_player(&data.id, data.x, data.y, color),
+            }
+            self.rename_player(&data.id, &data.name);
+        }
     }
 
     /**
-     * プレイヤーの位置を更新する
+     * タブの表示・非表示が切り替わったときの処理
      * 
-     * @param id 更新するプレイヤーのID
-     * @param x 新しいX座標
-     * @param y 新しいY座標
+     * 非表示の間はアニメーションループが止まるため位置情報も送信されません。
+     * 表示に戻ったときは経過時間を計算し直し、サーバーに現在の状態を要求します。
+     * 
+     * @param hidden タブが非表示になった場合はtrue
      */
-    pub fn update_player_position(&mut self, id: &str, x: f64, y: f64) {
-        if let Some(player) = self.players.get_mut(id) {
-            player.x = x;
-            player.y = y;
+    pub fn set_page_hidden(&mut self, hidden: bool) {
+        self.page_hidden = hidden;
+        if hidden {
+            log_info!("タブが非表示になりました");
+            return;
+        }
+        
+        log_info!("タブが表示に戻りました。状態を同期します");
+        self.telemetry.skip_frame();
+        self.core_game.update_elapsed_time();
+        if self.network.is_connected && self.practice_mines.is_none() {
+            if let Err(e) = self.network.send_sync_request() {
+                log_error!("Sync request error: {:?}", e);
+            }
         }
     }
 
     /**
-     * ゲーム状態を更新する
+     * ページを離れるときに他のプレイヤーに退出を知らせて切断する
      * 
-     * サーバーから受信したデータを元にゲーム状態を更新します。
+     * 閉じたタブのプレイヤーが人数に残らないよう、サーバーの切断の検知を待たずに退出します。
+     * 再読み込みでも同じIDで参加し直せるよう、先にセッションを保存します。
+     */
+    pub fn leave_on_page_hide(&mut self) {
+        self.save_session();
+        self.reconnect.cancel();
+        self.stop_peer_host();
+        self.network.leave();
+    }
+
+    /**
+     * 戻る・進むのキャッシュからページが復元されたときの処理
+     * 
+     * ページを離れるときに切断しているため、対戦中であれば接続し直して状態を同期します。
+     */
+    pub fn resume_after_page_show(&mut self) {
+        if !self.in_multiplayer() || self.offline || self.network.is_peer() || self.network.is_connected {
+            return;
+        }
+        log_info!("ページが復元されました。接続し直します");
+        if let Err(e) = self.resync() {
+            log_error!("Reconnect error: {:?}", e);
+        }
+    }
+
+    /**
+     * ブラウザのオンライン・オフラインが切り替わったときの処理
      * 
-     * @param game_data サーバーから受信したゲーム状態データ
+     * オフラインの間は画面上部にバナーを表示します。
+     * オンラインに戻ったとき、対戦中で切断されていれば接続し直し（初期化メッセージで状態が揃う）、
+     * 接続が残っていれば現在の状態を要求します。
+     * 
+     * @param online オンラインになった場合はtrue
      */
-    pub fn update_game_state(&mut self, game_data: &serde_json::Map<String, serde_json::Value>) {
-        // ボードの更新を委譲
-        self.board.update_from_server(game_data);
+    pub fn set_online(&mut self, online: bool) {
+        if !online {
+            log_info!("オフラインになりました");
+            self.offline = true;
+            return;
+        }
+        if !self.offline {
+            return;
+        }
         
-        // セルサイズの更新（キャンバスサイズが必要なため、ここで行う）
-        self.board.cell_size = ((self.canvas.width() as f64).min(self.canvas.height() as f64) - 40.0) / self.board.width as f64;
+        log_info!("オンラインに戻りました。状態を同期します");
+        self.offline = false;
+        self.show_notice("toast.back_online");
         
-        // ゲーム状態の表示を更新
-        self.update_game_status();
+        if !self.in_multiplayer() {
+            return;
+        }
+        if let Err(e) = self.resync() {
+            log_error!("Resync error: {:?}", e);
+        }
     }
 
     /**
-     * ゲーム状態の表示を更新する
+     * 再読み込み前に対戦中だったゲームに接続し直す
      * 
-     * 現在のゲーム状態に基づいてUIに表示するステータスを更新します。
+     * 起動時に一度だけ呼び出します。サーバーには前のプレイヤーIDの引き継ぎを要求し、
+     * 初期化メッセージを受け取るとロビーまたはゲーム画面に戻ります。
      */
-    pub fn update_game_status(&self) {
-        let status = if self.board.game_over {
-            if self.board.win {
-                "勝利！"
-            } else {
-                "ゲームオーバー！"
-            }
-        } else if self.board.game_started {
-            "ゲーム中..."
-        } else {
-            "ゲーム開始待ち..."
-        };
-        
-        update_game_status(status);
+    pub fn resume_session(&mut self) {
+        if !std::mem::take(&mut self.resume_match) {
+            return;
+        }
+        log_info!("再読み込み前のゲームに接続し直します（ルーム: {:?}）", self.network.room);
+        self.current_screen = Screen::Lobby;
+        if let Err(e) = self.connect_websocket() {
+            log_error!("Resume error: {:?}", e);
+            self.quit_to_title();
+        }
     }
 
     /**
-     * マウス座標からセルのインデックスを取得する
+     * 参加中のルーム・プレイヤーID・設定をsessionStorageに保存する
      * 
-     * 画面上のマウス座標から対応するセルのインデックスを計算します。
-     * 座標がボード外の場合はNoneを返します。
+     * ページを再読み込みしたときに`resume_session`で同じゲームに戻るために使います。
+     */
+    pub fn save_session(&mut self) {
+        self.last_session_save = now_ms();
+        let session = SessionResource {
+            room: self.network.room.clone(),
+            player_id: self.local_player_id.clone(),
+            in_match: self.in_multiplayer() && !self.network.is_peer(),
+            difficulty: Some(self.game_config.difficulty.key().to_string()),
+            settings: Some(self.settings.clone()),
+        };
+        if let Err(e) = save_session_storage(&self.session_key, &session.to_json()) {
+            log_warn!("Session save error: {:?}", e);
+        }
+    }
+
+    /**
+     * サーバーと対戦中（ロビーまたはゲーム画面）かどうか
      * 
-     * @param x マウスのX座標
-     * @param y マウスのY座標
-     * @return セルのインデックス（Option<usize>）
+     * @return 練習・デイリーチャレンジ以外でロビーかゲーム画面にいる場合はtrue
      */
-    pub fn get_cell_index(&self, x: f64, y: f64) -> Option<usize> {
-        let canvas_width = self.canvas.width() as f64;
-        let canvas_height = self.canvas.height() as f64;
-        
-        self.board.get_cell_index(x, y, canvas_width, canvas_height)
+    fn in_multiplayer(&self) -> bool {
+        self.practice_mines.is_none() && matches!(self.current_screen, Screen::RoomSelect | Screen::Lobby | Screen::Game)
     }
 
     /**
-     * ゲームの状態を更新する
+     * サーバーから状態を受け取り直す
      * 
-     * プレイヤーの位置などを更新し、画面を再描画します。
+     * 接続が残っていれば現在の状態を要求し、切断されていれば接続し直します（初期化メッセージで状態が揃う）。
      * 
-     * @return 成功した場合はOk(()), エラーの場合はErr(JsValue)
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
      */
-    pub fn update(&mut self) -> Result<(), JsValue> {
-        // ローカルプレイヤーの移動
-        if let Some(player_id) = &self.local_player_id {
-            if let Some(player) = self.players.get_mut(player_id) {
-                // プレイヤーの位置を更新
-                player.x = self.mouse_x;
-                player.y = self.mouse_y;
-                
-                // 位置情報を送信
-                self.send_position_update()?;
-            }
+    fn resync(&mut self) -> GameResult<()> {
+        if self.network.is_connected {
+            return self.network.send_sync_request();
         }
-        
-        // 描画
-        self.draw()?;
-        
-        Ok(())
+        let player_id = self.local_player_id.take();
+        self.reconnect_now(player_id)
     }
 
     /**
-     * ゲームを描画する
+     * 接続を作り直す
      * 
-     * 現在の画面状態に応じて、タイトル画面かゲーム画面を描画します。
+     * 切断中に古くなったプレイヤーは初期化メッセージで受け取り直します。
      * 
-     * @return 成功した場合はOk(()), エラーの場合はErr(JsValue)
+     * @param player_id サーバーに引き継ぎを要求するプレイヤーID
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
      */
-    pub fn draw(&mut self) -> Result<(), JsValue> {
-        let canvas_width = self.canvas.width() as f64;
-        let canvas_height = self.canvas.height() as f64;
-        
-        match self.current_screen {
-            Screen::Title => {
-                // タイトル画面を描画
-                self.renderer.draw_title_screen(canvas_width, canvas_height, self.network.is_connected)?;
-            },
-            Screen::Game => {
-                // ボードを描画
-                self.renderer.draw_board(
-                    &self.board.cells,
-                    &self.board.revealed,
-                    &self.board.flagged,
-                    self.board.width,
-                    self.board.height,
-                    self.board.cell_size,
-                    canvas_width,
-                    canvas_height
-                )?;
-                
-                // プレイヤーを描画
-                self.renderer.draw_players(&self.players, &self.local_player_id)?;
-                
-                // UIを描画
-                self.renderer.draw_ui(canvas_width)?;
-                
-                // 接続状態を描画
-                self.renderer.draw_connection_status(self.network.is_connected)?;
-                
-                // ゲームオーバー時の処理
-                if self.board.game_over {
-                    if self.board.win {
-                        self.renderer.draw_win_screen(canvas_width, canvas_height)?;
-                    } else {
-                        self.renderer.draw_game_over_screen(canvas_width, canvas_height)?;
+    fn reconnect_now(&mut self, player_id: Option<String>) -> GameResult<()> {
+        self.network.disconnect();
+        self.network.resume_player_id = player_id;
+        self.local_player_id = None;
+        self.players.clear();
+        self.connect_websocket()
+    }
+
+    /**
+     * 予期しない切断を検知し、待ち時間を延ばしながら接続し直す
+     * 
+     * 毎フレーム（タブが非表示の間はバックグラウンドの更新で）呼び出します。
+     * オフラインの間は`set_online`で接続し直すため試行しません。
+     * 回数の上限に達したらエラー画面を表示し、「状態を読み込み直す」で再び試せるようにします。
+     */
+    fn poll_reconnect(&mut self) {
+        let now = now_ms();
+        let connection_lost = self.network.take_connection_lost();
+        self.report_connection(connection_lost);
+        if connection_lost {
+            if !self.in_multiplayer() || self.offline {
+                self.reconnect.cancel();
+                return;
+            }
+            // ピアツーピアの接続はシグナリングをやり直さないと繋がらないため再接続しない
+            if self.network.is_peer() {
+                self.reconnect.cancel();
+                self.show_notice("toast.host_left");
+                return;
+            }
+            match self.reconnect.schedule(self.local_player_id.clone(), now) {
+                Some(delay) => {
+                    log_warn!("接続が切れました。{}ms後に再接続します（{}回目）", delay, self.reconnect.attempts());
+                    self.connection_callbacks.reconnecting(self.reconnect.attempts());
+                    if self.reconnect.attempts() == 1 {
+                        self.show_notice("toast.reconnecting");
                     }
-                }
+                },
+                None => {
+                    let error = GameError::Network(format!("could not reconnect after {} attempts", MAX_RECONNECT_ATTEMPTS));
+                    self.give_up_reconnect(&error);
+                },
             }
         }
         
-        Ok(())
+        if !self.reconnect.take_due(now) {
+            return;
+        }
+        if !self.in_multiplayer() {
+            self.reconnect.cancel();
+            return;
+        }
+        let player_id = self.reconnect.player_id().map(str::to_string);
+        if let Err(e) = self.reconnect_now(player_id) {
+            // WebSocketを作れなかった場合も切断と同じく次の再接続を予約する
+            log_error!("Reconnect error: {:?}", e);
+            if self.reconnect.schedule(None, now).is_none() {
+                self.give_up_reconnect(&e);
+            }
+        }
     }
 
     /**
-     * マウスクリック処理を行う
+     * 再接続を諦めてエラー画面を表示する
      * 
-     * 画面状態に応じて適切なクリック処理を実行します：
-     * - タイトル画面：スタートボタンの処理
+     * 「状態を読み込み直す」で同じプレイヤーIDのまま接続し直せるよう、IDを戻しておきます。
+     * 
+     * @param error 表示するエラー
+     */
+    fn give_up_reconnect(&mut self, error: &GameError) {
+        self.local_player_id = self.reconnect.player_id().map(str::to_string);
+        self.reconnect.cancel();
+        self.connection_callbacks.disconnected("reconnect_failed");
+        self.show_error(error);
+    }
+
+    /**
+     * 前のフレームから接続の状態が変わっていればホストページの関数に知らせる
+     * 
+     * @param connection_lost 予期しない切断があった場合はtrue
+     */
+    fn report_connection(&mut self, connection_lost: bool) {
+        let connected = self.network.is_connected;
+        if connected == self.connection_reported {
+            return;
+        }
+        self.connection_reported = connected;
+        
+        if connected {
+            self.connection_callbacks.connected();
+        } else if connection_lost {
+            self.connection_callbacks.disconnected("connection_lost");
+        } else {
+            self.connection_callbacks.disconnected("closed");
+        }
+    }
+
+    /**
+     * タブが非表示の間だけブラウザ通知を出す
+     * 
+     * 設定でブラウザ通知が有効になっている場合のみ通知します。
+     * 
+     * @param key 通知の本文の翻訳キー
+     */
+    pub fn notify_in_background(&self, key: &'static str) {
+        if !self.page_hidden || !self.settings.browser_notifications {
+            return;
+        }
+        let localization = &self.renderer.localization;
+        show_browser_notification(localization.text("notification.title"), localization.text(key));
+    }
+
+    /**
+     * プレイヤーの表示名を変更する
+     * 
+     * 空の名前の場合はIDから作った名前に戻します。
+     * 
+     * @param id プレイヤーID
+     * @param name 新しい名前
+     */
+    pub fn rename_player(&mut self, id: &str, name: &str) {
+        let name = if name.is_empty() { self.default_player_name(id) } else { name.to_string() };
+        if let Some(player) = self.players.get_mut(id) {
+            player.name = name;
+        }
+    }
+
+    /**
+     * 他のプレイヤーの操作を通知する
+     * 
+     * 設定で種類ごとに非表示にできます。
+     * 
+     * @param category 通知の種類
+     * @param key 通知の文言キー（`{}`はプレイヤーの表示名に置き換える）
+     * @param player_id 操作したプレイヤーのID
+     */
+    pub fn show_toast(&mut self, category: ToastCategory, key: &'static str, player_id: &str) {
+        if !self.settings.toast_enabled(category) {
+            return;
+        }
+        
+        let name = self.players.get(player_id)
+            .map_or_else(|| self.default_player_name(player_id), |player| player.name.clone());
+        let text = self.renderer.localization.format(key, &[&name]);
+        self.announce(&text);
+        self.toasts.push(category, text, now_ms());
+    }
+
+    /**
+     * 統計の更新で新しく解放されたテーマを通知する
+     * 
+     * @param before 更新前の統計
+     */
+    pub fn announce_unlocks(&mut self, before: &StatsResource) {
+        for theme in newly_unlocked_themes(before, &self.stats) {
+            let text = self.renderer.localization.format("toast.unlock", &[&theme.label(self.settings.language)]);
+            self.announce(&text);
+            self.toasts.push(ToastCategory::Unlock, text, now_ms());
+        }
+    }
+
+    /**
+     * デイリーチャレンジのクリアを連続記録に加え、新しく獲得した実績を通知する
+     * 
+     * @param day クリアした盤面の日番号
+     */
+    pub fn record_daily_completion(&mut self, day: i64) {
+        let before = self.streak.clone();
+        self.streak.record_completion(day);
+        if self.streak == before {
+            return;
+        }
+        
+        for days in self.streak.newly_earned(&before) {
+            let text = self.renderer.localization.format("toast.streak_achievement", &[&days]);
+            self.announce(&text);
+            self.toasts.push(ToastCategory::Unlock, text, now_ms());
+        }
+        if let Err(e) = self.save_streak() {
+            log_error!("Streak save error: {:?}", e);
+        }
+    }
+
+    /**
+     * 名前が未設定のプレイヤーの表示名をIDから作成する
+     * 
+     * @param id プレイヤーID
+     * @return 表示言語に合わせた表示名
+     */
+    fn default_player_name(&self, id: &str) -> String {
+        self.renderer.localization.format("player.default_name", &[&id])
+    }
+
+    /**
+     * プレイヤーを削除する
+     * 
+     * @param id 削除するプレイヤーのID
+     */
+    pub fn remove_player(&mut self, id: &str) {
+        self.players.remove(id);
+        self.idle_players.remove(id);
+        
+        // プレイヤー数の表示を更新
+        update_player_count(self.players.len());
+    }
+
+    /**
+     * プレイヤーの位置を更新する
+     * 
+     * @param id 更新するプレイヤーのID
+     * @param x 新しいX座標
+     * @param y 新しいY座標
+     */
+    pub fn update_player_position(&mut self, id: &str, x: f64, y: f64) {
+        // 位置は止まっていても定期的に届くため、動いた場合だけ操作とみなす
+        if let Some(player) = self.players.get_mut(id) {
+            if player.x == x && player.y == y {
+                return;
+            }
+            player.x = x;
+            player.y = y;
+        }
+        self.mark_player_active(id);
+    }
+
+    /**
+     * プレイヤーが操作したことを記録する
+     * 
+     * @param id 操作したプレイヤーのID
+     */
+    pub fn mark_player_active(&mut self, id: &str) {
+        if let Some(player) = self.players.get_mut(id) {
+            player.last_update = now_ms();
+        }
+        self.idle_players.remove(id);
+    }
+
+    /**
+     * 離席中になったプレイヤーを通知する
+     * 
+     * ゲーム画面で他のプレイヤーが離席中になった時に一度だけ通知します。
+     */
+    pub fn update_idle_players(&mut self) {
+        let now = now_ms();
+        let newly_idle: Vec<String> = self.players.values()
+            .filter(|player| !player.is_local && player.is_idle(now) && !self.idle_players.contains(&player.id))
+            .map(|player| player.id.clone())
+            .collect();
+        
+        for id in newly_idle {
+            if self.current_screen == Screen::Game {
+                self.show_toast(ToastCategory::Presence, "toast.idle", &id);
+            }
+            self.idle_players.insert(id);
+        }
+    }
+
+    /**
+     * ゲーム状態を更新する
+     * 
+     * サーバーから受信したデータを元にゲーム状態を更新します。
+     * 
+     * @param snapshot サーバーから受信したゲーム状態
+     */
+    pub fn update_game_state(&mut self, snapshot: &GameStateSnapshot) {
+        // ボードの更新を委譲し、以降の差分はこの版数から数える
+        self.board.update_from_server(snapshot);
+        self.network.reset_board_revision(snapshot.revision);
+        
+        // 進行中のカウントダウンに合わせる（含まれない場合はカウントダウンなし）
+        match snapshot.countdown_ends_at {
+            Some(ends_at) => self.countdown.start(ends_at, snapshot.server_time, now_ms()),
+            None => self.countdown.clear(),
+        }
+        
+        // セルサイズの更新（キャンバスサイズが必要なため、ここで行う）
+        self.fit_board_to_canvas();
+        
+        // ゲーム状態の表示を更新
+        self.update_game_status();
+    }
+
+    /**
+     * キャンバスの大きさに合わせてセルサイズを計算し直す
+     */
+    fn fit_board_to_canvas(&mut self) {
+        let canvas_width = self.canvas.width() as f64;
+        let canvas_height = self.canvas.height() as f64;
+        self.board.cell_size = if self.compact {
+            // 中央のボードが下端の状態の帯に重ならないよう上下に帯の高さを空ける
+            let available = canvas_width.min(canvas_height - COMPACT_STATUS_HEIGHT * 2.0) - 16.0;
+            available / self.board.width.max(self.board.height) as f64
+        } else {
+            (canvas_width.min(canvas_height) - 40.0) / self.board.width as f64
+        };
+        self.game_config.update_cell_size(canvas_width, canvas_height);
+    }
+
+    /**
+     * キャンバスの表示サイズが変わったときに描画サイズを合わせる
+     * 
+     * CSSのレイアウトで変わった表示サイズをキャンバスの幅・高さに反映し、
+     * セルサイズを計算し直します。
+     * 
+     * @param width 新しい幅（ピクセル）
+     * @param height 新しい高さ（ピクセル）
+     * @return サイズが変わった場合はtrue
+     */
+    pub fn resize_canvas(&mut self, width: u32, height: u32) -> bool {
+        if width == 0 || height == 0 || (width == self.canvas.width() && height == self.canvas.height()) {
+            return false;
+        }
+        
+        self.canvas.set_width(width);
+        self.canvas.set_height(height);
+        self.fit_board_to_canvas();
+        log_debug!("キャンバスのサイズを変更: {}x{}", width, height);
+        true
+    }
+
+    /**
+     * キャンバスを囲む要素の全画面表示を切り替える
+     * 
+     * 全画面表示の間はキャンバスが画面全体に広がり、ResizeObserverがレイアウトを計算し直します。
+     * 
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
+     */
+    pub fn toggle_fullscreen(&mut self) -> GameResult<()> {
+        let document = web_sys::window()
+            .and_then(|window| window.document())
+            .ok_or_else(|| GameError::Js("document is not available".to_string()))?;
+        
+        if document.fullscreen_element().is_some() {
+            document.exit_fullscreen();
+            return Ok(());
+        }
+        
+        // 全画面表示を終えたときに元のサイズに戻せるよう覚えておく
+        self.windowed_canvas_size = Some((self.canvas.width(), self.canvas.height()));
+        match self.canvas.parent_element() {
+            Some(container) => container.request_fullscreen()?,
+            None => self.canvas.request_fullscreen()?,
+        }
+        Ok(())
+    }
+
+    /**
+     * 全画面表示が開始・終了したときの処理
+     * 
+     * 終了した場合は全画面表示にする前のキャンバスのサイズに戻します。
+     * 
+     * @param fullscreen 全画面表示になった場合はtrue
+     */
+    pub fn handle_fullscreen_change(&mut self, fullscreen: bool) {
+        if fullscreen {
+            log_debug!("全画面表示になりました");
+            return;
+        }
+        
+        if let Some((width, height)) = self.windowed_canvas_size.take() {
+            self.resize_canvas(width, height);
+        }
+    }
+
+    /**
+     * ゲーム状態の表示を更新する
+     * 
+     * 現在のゲーム状態に基づいてUIに表示するステータスを更新します。
+     */
+    pub fn update_game_status(&self) {
+        update_game_status(self.renderer.localization.text(self.status_key()));
+    }
+
+    /**
+     * 現在のゲームの状態を表す文言の翻訳キー
+     * 
+     * @return 勝利・ゲームオーバー・ゲーム中・開始待ちのいずれかのキー
+     */
+    fn status_key(&self) -> &'static str {
+        if self.board.game_over {
+            if self.board.win {
+                "status.win"
+            } else {
+                "status.game_over"
+            }
+        } else if self.board.game_started {
+            "status.playing"
+        } else {
+            "status.waiting"
+        }
+    }
+
+    /**
+     * マウス座標からセルのインデックスを取得する
+     * 
+     * 画面上のマウス座標から対応するセルのインデックスを計算します。
+     * 座標がボード外の場合はNoneを返します。
+     * 
+     * @param x マウスのX座標
+     * @param y マウスのY座標
+     * @return セルのインデックス（Option<usize>）
+     */
+    pub fn get_cell_index(&self, x: f64, y: f64) -> Option<usize> {
+        let canvas_width = self.canvas.width() as f64;
+        let canvas_height = self.canvas.height() as f64;
+        
+        // カメラの視点を考慮してボード上の座標に変換
+        let (x, y) = self.camera.screen_to_world(x, y, canvas_width, canvas_height);
+        self.board.get_cell_index(x, y, canvas_width, canvas_height)
+    }
+
+    /**
+     * ゲーム画面のプレイヤー一覧に並べるプレイヤーを取得する（ID順）
+     * 
+     * @return プレイヤーの一覧
+     */
+    pub fn player_list(&self) -> Vec<&Player> {
+        let mut players: Vec<&Player> = self.players.values().collect();
+        players.sort_by(|a, b| a.id.cmp(&b.id));
+        players
+    }
+
+    /**
+     * 外部のツール向けにボードの状態を型付き配列にする
+     * 
+     * JSに渡した後もWASMのメモリの変化に影響されないよう、配列はコピーして作ります。
+     * 
+     * @return width, height, mineCount, cells, revealed, flaggedを持つオブジェクト
+     */
+    pub fn board_arrays(&self) -> GameResult<JsValue> {
+        let to_bytes = |values: &[bool]| values.iter().map(|&value| u8::from(value)).collect::<Vec<u8>>();
+        
+        let object = js_sys::Object::new();
+        js_sys::Reflect::set(&object, &"width".into(), &JsValue::from(self.board.width as u32))?;
+        js_sys::Reflect::set(&object, &"height".into(), &JsValue::from(self.board.height as u32))?;
+        js_sys::Reflect::set(&object, &"mineCount".into(), &JsValue::from(self.board.mine_count as u32))?;
+        js_sys::Reflect::set(&object, &"cells".into(), &js_sys::Uint8Array::from(self.board.cell_codes().as_slice()))?;
+        js_sys::Reflect::set(&object, &"revealed".into(), &js_sys::Uint8Array::from(to_bytes(&self.board.revealed).as_slice()))?;
+        js_sys::Reflect::set(&object, &"flagged".into(), &js_sys::Uint8Array::from(to_bytes(&self.board.flagged).as_slice()))?;
+        Ok(object.into())
+    }
+
+    /**
+     * テレメトリーを受け取る関数を登録する
+     * 
+     * @param callback 集計結果を受け取る関数（Noneの場合は登録を解除する）
+     */
+    pub fn set_telemetry_callback(&mut self, callback: Option<js_sys::Function>) {
+        self.telemetry_callback = callback;
+    }
+
+    /**
+     * 送る間隔が過ぎていれば集計結果をホストページの関数に渡す
+     */
+    fn emit_telemetry(&mut self) {
+        let Some(callback) = &self.telemetry_callback else {
+            return;
+        };
+        let Some(report) = self.telemetry.take_report(now_ms()) else {
+            return;
+        };
+        
+        let result = serde_json::to_string(&report)
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+            .and_then(|json| js_sys::JSON::parse(&json))
+            .and_then(|value| callback.call1(&JsValue::NULL, &value));
+        if let Err(e) = result {
+            log_error!("Telemetry callback error: {:?}", e);
+        }
+    }
+
+    /**
+     * ホストページ向けのゲーム状態のスナップショットを作成する
+     * 
+     * @return 現在のフェーズ・ボード・経過時間・プレイヤーの情報
+     */
+    pub fn snapshot(&self) -> GameSnapshot {
+        GameSnapshot {
+            phase: self.core_game.phase().key(),
+            in_game: self.current_screen == Screen::Game,
+            width: self.board.width,
+            height: self.board.height,
+            mine_count: self.board.mine_count,
+            flags: self.board.flagged.iter().filter(|&&flagged| flagged).count(),
+            safe_revealed: self.board.safe_revealed(),
+            safe_cells: self.board.safe_cells(),
+            elapsed_ms: self.core_game.elapsed_time(),
+            players: self.player_list().into_iter().cloned().collect(),
+        }
+    }
+
+    /**
+     * マウスが乗っているプレイヤーのプロフィールカードの内容を作成する
+     * 
+     * プレイヤー一覧の行か、他のプレイヤーのカーソルにマウスが乗っている場合に表示します。
+     * 
+     * @return プロフィールカードの内容（Option<ProfileCardView>）
+     */
+    pub fn hovered_profile_card(&self) -> Option<ProfileCardView> {
+        let canvas_width = self.canvas.width() as f64;
+        let canvas_height = self.canvas.height() as f64;
+        
+        let player = match self.player_list_index(self.mouse_x, self.mouse_y) {
+            Some(index) => self.player_list().get(index).copied(),
+            None => {
+                // カーソルはカメラの視点で描画しているため、ボード上の座標で判定する
+                let (x, y) = self.camera.screen_to_world(self.mouse_x, self.mouse_y, canvas_width, canvas_height);
+                self.player_list().into_iter()
+                    .filter(|player| !player.is_local)
+                    .find(|player| (player.x - x).hypot(player.y - y) <= 12.0)
+            },
+        }?;
+        
+        let contribution = self.summary.player(&player.id);
+        Some(ProfileCardView {
+            name: player.name.clone(),
+            color: player.color,
+            score: contribution.score(),
+            cells: contribution.cells,
+            flags: contribution.flags,
+            ping: player.ping,
+        })
+    }
+
+    /**
+     * 座標にあるプレイヤー一覧の行のインデックスを取得する
+     * 
+     * @param x X座標
+     * @param y Y座標
+     * @return 行のインデックス（Option<usize>、並びは`player_list`の順）
+     */
+    pub fn player_list_index(&self, x: f64, y: f64) -> Option<usize> {
+        let canvas_width = self.canvas.width() as f64;
+        
+        (0..self.players.len()).find(|&index| {
+            let (left, top, width, height) = GameRenderer::player_list_row_bounds(canvas_width, index);
+            x >= left && x <= left + width && y >= top && y <= top + height
+        })
+    }
+
+    /**
+     * プレイヤー一覧のクリックでカーソルの追従を切り替える
+     * 
+     * 追いかけているプレイヤーをもう一度クリックすると通常の視点に戻ります。
+     * 
+     * @param index クリックされた行のインデックス
+     */
+    pub fn toggle_follow(&mut self, index: usize) {
+        let Some(id) = self.player_list().get(index).map(|player| player.id.clone()) else {
+            return;
+        };
+        
+        if self.camera.following() == Some(id.as_str()) {
+            self.camera.stop_following();
+        } else {
+            self.camera.follow(&id);
+        }
+    }
+
+    /**
+     * ゲームの状態を更新する
+     * 
+     * プレイヤーの位置などを更新し、画面を再描画します。
+     * 
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
+     */
+    pub fn update(&mut self) -> GameResult<()> {
+        // エラー画面の表示中はゲームの処理を止め、エラー画面だけを描画する
+        if self.error_report.is_some() {
+            self.toasts.prune(now_ms());
+            self.handle_copy_results();
+            return self.draw_error_screen().map_err(GameError::render);
+        }
+        
+        // テレメトリーを登録している場合はフレーム時間を集計して一定間隔で渡す
+        if self.telemetry_callback.is_some() {
+            self.telemetry.record_frame(now_ms());
+            self.emit_telemetry();
+        }
+        
+        self.step()?;
+        
+        // フレームの間に積んだメッセージをまとめて送信
+        if let Err(e) = self.network.flush_outbox() {
+            log_error!("Send error: {:?}", e);
+        }
+        
+        // 描画
+        if self.systems.is_system_active(RENDER_SYSTEM_NAME) {
+            self.draw().map_err(GameError::render)?;
+        }
+        
+        Ok(())
+    }
+
+    /**
+     * 描画せずにゲームの状態だけを進める
+     * 
+     * タブが非表示などでアニメーションフレームが来ない間、低頻度のタイマーから呼び出し、
+     * 受信した状態の反映・ボット・セッションの保存などを止めないようにします。
+     * 
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
+     */
+    pub fn update_in_background(&mut self) -> GameResult<()> {
+        if self.error_report.is_some() {
+            return Ok(());
+        }
+        self.step()
+    }
+
+    /**
+     * 1フレーム分のゲームの処理（描画以外）
+     * 
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
+     */
+    fn step(&mut self) -> GameResult<()> {
+        // 前のフレーム以降にサーバーから届いたメッセージを届いた順に処理
+        for message in self.network.poll_messages() {
+            if let Err(e) = self.handle_server_message(&message) {
+                log_error!("Error processing message: {:?}", e);
+            }
+        }
+        
+        // サーバーから受信したボードの状態をゲーム進行に反映
+        self.sync_core_game();
+        
+        // 画面に合わせてBGMを切り替え
+        self.update_music();
+        
+        // 表示時間を過ぎたエモートと通知を削除
+        self.emotes.prune(now_ms());
+        self.toasts.prune(now_ms());
+        
+        // 操作が無くなったプレイヤーを離席中にする
+        self.update_idle_players();
+        
+        // IndexedDBへの保存・読み込みの結果を反映
+        self.handle_offline_storage_events();
+        
+        // 予期しない切断から接続し直す
+        self.poll_reconnect();
+        
+        // ピアツーピアでホストしている場合は参加者の操作を反映し、ボードの変化を送る
+        self.update_peer_host();
+        
+        // 接続中は一定間隔でハートビートを送り、遅延を測る
+        if self.network.is_connected && self.network_stats.heartbeat_due(now_ms()) {
+            if let Err(e) = self.network.send_heartbeat(now_ms()) {
+                log_error!("Heartbeat send error: {:?}", e);
+            }
+        }
+        
+        // ルーム選択画面では一覧を一定間隔で要求し直す
+        if self.current_screen == Screen::RoomSelect && self.network.is_connected && self.room_state.request_due(now_ms()) {
+            if let Err(e) = self.network.send_list_rooms() {
+                log_error!("Room list request error: {:?}", e);
+            }
+        }
+        
+        // 再読み込みに備えてセッションを一定間隔で保存
+        if now_ms() - self.last_session_save >= SESSION_SAVE_INTERVAL_MS {
+            self.save_session();
+        }
+        
+        // クリップボードへのコピーの結果を通知
+        self.handle_copy_results();
+        
+        // HTMLのメニューでの操作を反映し、表示を現在の画面に合わせる
+        self.handle_overlay_actions()?;
+        self.sync_dom_overlay();
+        
+        // 追いかけているプレイヤーのカーソルにカメラを動かす
+        let canvas_width = self.canvas.width() as f64;
+        let canvas_height = self.canvas.height() as f64;
+        let target = self.camera.following()
+            .and_then(|id| self.players.get(id))
+            .map(|player| (player.x, player.y));
+        self.camera.update(target, canvas_width, canvas_height, self.accessibility.reduced_motion);
+        
+        // ローカルプレイヤーの移動（カメラの視点を考慮してボード上の座標にする）
+        let (cursor_x, cursor_y) = self.camera.screen_to_world(self.mouse_x, self.mouse_y, canvas_width, canvas_height);
+        if let Some(player_id) = &self.local_player_id {
+            if let Some(player) = self.players.get_mut(player_id) {
+                // プレイヤーの位置を更新
+                player.x = cursor_x;
+                player.y = cursor_y;
+                
+                // 位置情報を送信
+                if self.systems.is_system_active(NETWORK_SYSTEM_NAME) {
+                    self.send_position_update()?;
+                }
+            }
+        }
+        
+        // ボットに見えている盤面と他のプレイヤーのカーソルの目標位置を渡し、HUDの表示内容と配置と一緒にシステムで更新
+        self.sync_bots();
+        self.sync_cursors();
+        self.update_hud();
+        self.update_diagnostics();
+        
+        // ボットが指した手をプレイヤーの操作と同じ経路で反映
+        self.apply_bot_actions()
+    }
+
+    /**
+     * クリップボードへのコピーの結果を通知する
+     */
+    fn handle_copy_results(&mut self) {
+        let copy_results: Vec<CopyResult> = self.clipboard.borrow_mut().drain(..).collect();
+        for result in copy_results {
+            match result {
+                CopyResult::Copied(key) => self.show_notice(key),
+                CopyResult::Shared => self.show_notice("toast.shared"),
+                CopyResult::Failed => self.show_notice("toast.copy_failed"),
+            }
+        }
+    }
+
+    /**
+     * エラー画面を表示する
+     * 
+     * ゲームの処理は復帰するまで止め、発生時の画面・ボード・接続の状態と直近のログを報告に添えます。
+     * 
+     * @param error 発生したエラー
+     */
+    pub fn show_error(&mut self, error: &GameError) {
+        self.show_error_report(error.to_string());
+    }
+
+    /**
+     * パニックで再読み込みしたときにエラー画面を表示する
+     * 
+     * @param message パニックの内容
+     */
+    pub fn show_panic(&mut self, message: &str) {
+        self.show_error_report(format!("panic: {}", message));
+    }
+
+    /**
+     * 発生時の状態を添えてエラー画面に切り替える
+     * 
+     * @param summary エラーの種類と内容
+     */
+    fn show_error_report(&mut self, summary: String) {
+        log_error!("エラー画面を表示します: {}", summary);
+        let mut diagnostics = vec![
+            format!("screen: {:?}", self.current_screen),
+            format!("board: {}x{}, mines {}, game over {}", self.board.width, self.board.height, self.board.mine_count, self.board.game_over),
+            format!("connected: {}, offline: {}, players: {}", self.network.is_connected, self.offline, self.players.len()),
+            format!("practice: {}, daily: {:?}", self.practice_mines.is_some(), self.daily_challenge_day),
+        ];
+        diagnostics.extend(logger::recent_records(ERROR_REPORT_LOG_LINES).iter().map(|record| record.to_string()));
+        
+        self.error_report = Some(ErrorReport::new(summary, diagnostics));
+        self.leaderboard.close();
+        // HTMLのメニューがエラー画面を覆わないようにする（復帰後に作り直す）
+        self.dom_overlay = None;
+        let title = self.renderer.localization.text("error.title").to_string();
+        self.announce(&title);
+    }
+
+    /**
+     * エラー画面から復帰する
+     * 
+     * 対戦中はサーバーから状態を受け取り直し、それ以外（または再接続に失敗した場合）はタイトル画面に戻ります。
+     */
+    pub fn recover_from_error(&mut self) {
+        if self.error_report.take().is_none() {
+            return;
+        }
+        log_info!("エラー画面から復帰します");
+        
+        if !self.in_multiplayer() {
+            self.quit_to_title();
+            return;
+        }
+        if let Err(e) = self.resync() {
+            log_error!("Resync error: {:?}", e);
+            self.quit_to_title();
+        }
+    }
+
+    /**
+     * エラーの報告をクリップボードにコピーする
+     */
+    pub fn copy_error_report(&mut self) {
+        if let Some(report) = &self.error_report {
+            copy_to_clipboard(&self.clipboard, report.text(), "toast.report_copied");
+        }
+    }
+
+    /**
+     * 座標にあるエラー画面のボタンのインデックスを取得する
+     * 
+     * @param x X座標
+     * @param y Y座標
+     * @return ボタンのインデックス（Option<usize>、並びは`ERROR_SCREEN_BUTTONS`の順）
+     */
+    pub fn error_button_index(&self, x: f64, y: f64) -> Option<usize> {
+        let canvas_width = self.canvas.width() as f64;
+        let canvas_height = self.canvas.height() as f64;
+        
+        (0..ERROR_SCREEN_BUTTONS.len()).find(|&index| {
+            let (left, top, width, height) = GameRenderer::error_screen_button_bounds(canvas_width, canvas_height, index);
+            x >= left && x <= left + width && y >= top && y <= top + height
+        })
+    }
+
+    /**
+     * エラー画面を描画する
+     * 
+     * コピーの結果の通知とログパネルはエラー画面の上に表示します。
+     * 
+     * @return 成功した場合はOk(()), キャンバスの呼び出しに失敗した場合はErr(JsValue)
+     */
+    fn draw_error_screen(&mut self) -> Result<(), JsValue> {
+        let Some(report) = &self.error_report else {
+            return Ok(());
+        };
+        let canvas_width = self.canvas.width() as f64;
+        let canvas_height = self.canvas.height() as f64;
+        let highlighted = self.error_button_index(self.mouse_x, self.mouse_y);
+        
+        self.renderer.draw_error_screen(canvas_width, canvas_height, report, highlighted)?;
+        self.renderer.draw_toasts(canvas_width, self.toasts.active(now_ms()), now_ms())?;
+        if self.log_panel {
+            self.renderer.draw_log_panel(canvas_width, canvas_height, &logger::recent_records(LOG_PANEL_LINES))?;
+        }
+        
+        Ok(())
+    }
+
+    /**
+     * HUDリソースに現在の値を書き込み、HUDのシステムを実行する
+     * 
+     * HUDのUIエンティティの文言と配置はシステムがキャンバスの大きさに合わせて更新します。
+     * 値が前のフレームと同じときは書き込まず、システムに変更として伝わらないようにします。
+     */
+    fn update_hud(&mut self) {
+        let flags = self.board.flagged.iter().filter(|&&flagged| flagged).count();
+        if let Some(current) = self.hud.get_resource::<HudResource>() {
+            let mut hud = current.clone();
+            hud.canvas_width = self.canvas.width() as f64;
+            hud.canvas_height = self.canvas.height() as f64;
+            hud.elapsed_ms = self.core_game.elapsed_time();
+            hud.remaining_mines = self.board.mine_count as i64 - flags as i64;
+            hud.progress = self.board.progress();
+            hud.connected = self.network.is_connected;
+            hud.latency_ms = if self.network.is_connected { self.network_stats.latency_ms(now_ms()) } else { None };
+            hud.language = self.settings.language;
+            hud.theme = self.settings.theme;
+            if &hud != current {
+                self.hud.insert_resource(hud);
+            }
+        }
+        
+        // 結果演出の開始・終了はフェーズ遷移イベントで行う（on_phase_changed）
+        if let Some(sequence) = self.hud.get_resource_mut::<ResultSequenceResource>() {
+            sequence.now = now_ms();
+            sequence.reduced_motion = self.accessibility.reduced_motion;
+        }
+        self.hud.run_stages();
+    }
+
+    /**
+     * WASMのメモリ使用量と主なコレクションの大きさを診断情報に記録する
+     * 
+     * `Closure::forget`や溜まり続けるキューによるリークを見つけるため、毎フレーム計測し、
+     * しきい値を超えたときに警告をログに出します。
+     */
+    fn update_diagnostics(&mut self) {
+        let collection_sizes = vec![
+            ("players", self.players.capacity()),
+            ("idle players", self.idle_players.capacity()),
+            ("toasts", self.toasts.capacity()),
+            ("practice moves", self.practice_moves.capacity()),
+            ("clipboard queue", self.clipboard.borrow().capacity()),
+            ("overlay queue", self.overlay_actions.borrow().capacity()),
+            ("storage queue", self.offline_storage.borrow().capacity()),
+            ("send buffer", self.network.send_buffer_capacity()),
+            ("forgotten closures", self.network.forgotten_closures()),
+        ];
+        if let Some(diagnostics) = self.hud.get_resource_mut::<DiagnosticsResource>() {
+            diagnostics.record_memory(wasm_memory_bytes(), collection_sizes);
+        }
+        self.hud.update_diagnostics();
+    }
+
+    /**
+     * ボードの状態をCoreGameResourceに反映する
+     * 
+     * ゲームの開始・終了を検出してゲーム進行のフェーズを進め、ボードがリセットされた場合はゲーム進行も初期化します。
+     * フェーズが変わった後の処理は、記録された遷移イベントごとに`on_phase_changed`で行います。
+     */
+    pub fn sync_core_game(&mut self) {
+        if !self.board.game_started && !self.board.game_over {
+            // リセット後の新しいボード
+            if !matches!(self.core_game.phase(), GamePhase::Ready) {
+                self.core_game.initialize(self.board.mine_count as u32);
+                self.summary.reset();
+            }
+        } else {
+            if self.board.game_started && matches!(self.core_game.phase(), GamePhase::Ready) {
+                self.core_game.start_game();
+            }
+            if self.board.game_over && !self.core_game.is_game_over() {
+                self.core_game.end_game(self.board.win);
+            }
+        }
+        
+        for event in self.core_game.drain_phase_events() {
+            self.on_phase_changed(event);
+        }
+    }
+
+    /**
+     * ゲーム進行のフェーズ遷移に反応する
+     * 
+     * 開始と終了を読み上げ、終了時には結果演出を始めて、ゲーム設定の計算式でスコアを確定して統計と履歴に記録します。
+     * 履歴の盤面を練習している場合は統計・履歴には記録しません。
+     * 準備中に戻った場合は結果演出を終えます。
+     * 
+     * @param event フェーズ遷移イベント
+     */
+    fn on_phase_changed(&mut self, event: PhaseChanged) {
+        match event {
+            PhaseChanged { from: GamePhase::Ready, to: GamePhase::Playing } => {
+                self.announce(self.renderer.localization.text("status.playing"));
+            },
+            PhaseChanged { to: GamePhase::Ready, .. } => {
+                if let Some(sequence) = self.hud.get_resource_mut::<ResultSequenceResource>() {
+                    sequence.reset();
+                }
+            },
+            PhaseChanged { to: GamePhase::GameOver { win }, .. } => self.finish_game(win),
+            _ => {},
+        }
+    }
+
+    /**
+     * 終了したゲームの結果演出を始め、スコアを確定して記録する
+     * 
+     * @param win 勝利した場合はtrue
+     */
+    fn finish_game(&mut self, win: bool) {
+        self.announce(self.renderer.localization.text(if win { "status.win" } else { "status.game_over" }));
+        if let Some(sequence) = self.hud.get_resource_mut::<ResultSequenceResource>() {
+            sequence.reduced_motion = self.accessibility.reduced_motion;
+            sequence.start(now_ms());
+        }
+        let score = self.core_game.record_final_score(&self.game_config);
+        log_info!("スコア: {} ({})", score, self.game_config.difficulty.key());
+        
+        if self.practice_mines.is_some() {
+            if win {
+                if let Some(day) = self.daily_challenge_day {
+                    self.record_daily_completion(day);
+                }
+            }
+            return;
+        }
+        
+        // 累計統計に結果を記録
+        let before = self.stats.clone();
+        self.stats.record_game(&self.game_config.difficulty, win, self.core_game.elapsed_time());
+        self.telemetry.record_game(self.core_game.elapsed_time(), win);
+        if win {
+            self.stats.record_score(score);
+        }
+        self.announce_unlocks(&before);
+        if let Err(e) = self.save_stats() {
+            log_error!("Stats save error: {:?}", e);
+        }
+        
+        // 履歴に記録
+        let mut players: Vec<String> = self.player_list().iter().map(|player| player.name.clone()).collect();
+        players.sort();
+        self.history.record(GameRecord {
+            difficulty: self.game_config.difficulty.key().to_string(),
+            width: self.board.width,
+            height: self.board.height,
+            mine_count: self.board.mine_count,
+            win,
+            time_ms: self.core_game.elapsed_time(),
+            finished_at: js_sys::Date::now(),
+            players,
+            mines: self.board.mine_indices(),
+        });
+        if let Err(e) = self.save_history() {
+            log_error!("History save error: {:?}", e);
+        }
+    }
+
+    /**
+     * 現在の画面に合わせてBGMを切り替える
+     * 
+     * ゲーム中に開いた設定画面ではゲームのBGMを流し続けます。
+     * 曲が変わる場合はクロスフェードします。
+     */
+    pub fn update_music(&mut self) {
+        let screen = match self.current_screen {
+            Screen::Settings | Screen::ThemeSelect | Screen::Accessibility => self.settings_return_screen,
+            Screen::Help => self.help_return_screen,
+            screen => screen,
+        };
+        let track = match screen {
+            Screen::Game => MusicTrack::Game,
+            _ => MusicTrack::Title,
+        };
+        
+        if let Err(e) = self.audio.play_music(track) {
+            log_warn!("Music playback error: {:?}", e);
+        }
+    }
+
+    /**
+     * システムの有効・無効を切り替える
+     * 
+     * 描画・ネットワーク送信の組み込み処理と、レジストリに登録されたシステムが対象です。
+     * 
+     * @param name システム名
+     * @param enabled 有効にする場合はtrue
+     * @return 該当するシステムが存在した場合はtrue
+     */
+    pub fn set_system_enabled(&mut self, name: &str, enabled: bool) -> bool {
+        let registered = self.systems.set_system_active(name, enabled);
+        let builtin = name == RENDER_SYSTEM_NAME || name == NETWORK_SYSTEM_NAME;
+        
+        if registered || builtin {
+            log_info!("🔧 システム {} を{}にしました", name, if enabled { "有効" } else { "無効" });
+            true
+        } else {
+            // 存在しない名前で無効化状態が残らないよう戻しておく
+            self.systems.set_system_active(name, true);
+            log_warn!("⚠️ システム {} は存在しません", name);
+            false
+        }
+    }
+
+    /**
+     * ゲームを描画する
+     * 
+     * 現在の画面状態に応じて、タイトル画面かゲーム画面を描画します。
+     * 
+     * @return 成功した場合はOk(()), キャンバスの呼び出しに失敗した場合はErr(JsValue)
+     */
+    pub fn draw(&mut self) -> Result<(), JsValue> {
+        let canvas_width = self.canvas.width() as f64;
+        let canvas_height = self.canvas.height() as f64;
+        
+        match self.current_screen {
+            Screen::Title => {
+                // タイトル画面を描画
+                let today = day_number(js_sys::Date::now());
+                self.renderer.draw_title_screen(canvas_width, canvas_height, self.network.is_connected, self.streak.current_streak(today), self.streak.best_streak())?;
+                self.renderer.draw_name_field(canvas_width, canvas_height, &self.settings.player_name, self.name_input_focused)?;
+                self.renderer.draw_avatar_button(canvas_width, canvas_height, &self.settings.avatar)?;
+            },
+            Screen::DifficultySelect => {
+                // 難易度選択画面を描画
+                let localization = &self.renderer.localization;
+                let mut options: Vec<(&str, String)> = Difficulty::PRESETS
+                    .iter()
+                    .filter_map(|difficulty| {
+                        difficulty.preset().map(|(width, height, mine_count)| {
+                            let detail = localization.format("difficulty.description", &[&width, &height, &mine_count]);
+                            (difficulty.label(localization.language()), detail)
+                        })
+                    })
+                    .collect();
+                options.push((
+                    Difficulty::Custom.label(localization.language()),
+                    localization.text("difficulty.custom_description").to_string(),
+                ));
+                let highlighted = self.difficulty_button_index(self.mouse_x, self.mouse_y);
+                
+                self.renderer.draw_difficulty_screen(
+                    canvas_width,
+                    canvas_height,
+                    &options,
+                    highlighted,
+                    self.network.is_connected
+                )?;
+            },
+            Screen::RoomSelect => {
+                // ルーム選択画面を描画
+                let highlighted = self.room_select_button(self.mouse_x, self.mouse_y);
+                self.renderer.draw_room_select_screen(canvas_width, canvas_height, &self.room_state, highlighted)?;
+            },
+            Screen::Lobby => {
+                // ロビー画面を描画
+                let highlighted = self.lobby_button_index(self.mouse_x, self.mouse_y);
+                self.renderer.draw_lobby_screen(canvas_width, canvas_height, &self.lobby, &self.local_player_id, highlighted)?;
+            },
+            Screen::CustomBoard => {
+                // カスタムボード画面を描画
+                let highlighted = self.custom_board_button_index(self.mouse_x, self.mouse_y);
+                self.renderer.draw_custom_board_screen(canvas_width, canvas_height, &self.custom_board, highlighted)?;
+            },
+            Screen::Stats => {
+                // 統計画面を描画
+                let (left, top, width, height) = GameRenderer::stats_back_button_bounds(canvas_width, canvas_height);
+                let back_highlighted = self.mouse_x >= left && self.mouse_x <= left + width &&
+                    self.mouse_y >= top && self.mouse_y <= top + height;
+                self.renderer.draw_stats_screen(canvas_width, canvas_height, &self.stats, back_highlighted)?;
+            },
+            Screen::Help => {
+                // ヘルプ画面を描画
+                let (left, top, width, height) = GameRenderer::stats_back_button_bounds(canvas_width, canvas_height);
+                let back_highlighted = self.mouse_x >= left && self.mouse_x <= left + width &&
+                    self.mouse_y >= top && self.mouse_y <= top + height;
+                self.renderer.draw_help_screen(canvas_width, canvas_height, back_highlighted)?;
+            },
+            Screen::History => {
+                // 対戦履歴画面を描画
+                let (left, top, width, height) = GameRenderer::stats_back_button_bounds(canvas_width, canvas_height);
+                let back_highlighted = self.mouse_x >= left && self.mouse_x <= left + width &&
+                    self.mouse_y >= top && self.mouse_y <= top + height;
+                let rows = self.history_rows();
+                let highlighted = self.history_practice_index(self.mouse_x, self.mouse_y);
+                self.renderer.draw_history_screen(canvas_width, canvas_height, &rows, highlighted, back_highlighted)?;
+            },
+            Screen::Settings if self.dom_ui_enabled() => {
+                // 設定項目はHTMLで表示するため背景のみ描画
+                self.renderer.draw_background(canvas_width, canvas_height);
+            },
+            Screen::Settings => {
+                // 設定画面を描画
+                let highlighted = self.settings_row_index(self.mouse_x, self.mouse_y);
+                self.renderer.draw_settings_screen(canvas_width, canvas_height, &self.settings, highlighted)?;
+            },
+            Screen::ThemeSelect => {
+                // テーマ選択画面を描画
+                let (left, top, width, height) = GameRenderer::stats_back_button_bounds(canvas_width, canvas_height);
+                let back_highlighted = self.mouse_x >= left && self.mouse_x <= left + width &&
+                    self.mouse_y >= top && self.mouse_y <= top + height;
+                let rows = self.theme_rows();
+                let highlighted = self.theme_row_index(self.mouse_x, self.mouse_y);
+                self.renderer.draw_theme_screen(canvas_width, canvas_height, &rows, highlighted, back_highlighted)?;
+            },
+            Screen::Accessibility => {
+                // アクセシビリティ画面を描画
+                let highlighted = self.accessibility_row_index(self.mouse_x, self.mouse_y);
+                self.renderer.draw_accessibility_screen(canvas_width, canvas_height, highlighted)?;
+            },
+            Screen::Game if self.compact => {
+                // 埋め込み用の最小表示ではボードと状態の帯のみ描画
+                self.renderer.draw_board(
+                    &self.board.cells,
+                    &self.board.revealed,
+                    &self.board.flagged,
+                    self.board.width,
+                    self.board.height,
+                    self.board.cell_size,
+                    canvas_width,
+                    canvas_height
+                )?;
+                let flags = self.board.flagged.iter().filter(|&&flagged| flagged).count();
+                let mines_left = self.board.mine_count as i64 - flags as i64;
+                let status = self.renderer.localization.text(self.status_key());
+                let elapsed = self.core_game.format_elapsed_time();
+                self.renderer.draw_compact_status(canvas_width, canvas_height, COMPACT_STATUS_HEIGHT, status, mines_left, &elapsed)?;
+            },
+            Screen::Game => {
+                // ボードとカーソルはカメラの視点で描画
+                self.renderer.begin_camera(&self.camera, canvas_width, canvas_height)?;
+                
+                // ボードを描画
+                self.renderer.draw_board(
+                    &self.board.cells,
+                    &self.board.revealed,
+                    &self.board.flagged,
+                    self.board.width,
+                    self.board.height,
+                    self.board.cell_size,
+                    canvas_width,
+                    canvas_height
+                )?;
+                
+                // ゲーム終了時はボードを走査する演出
+                if let Some(sequence) = self.result_sequence().filter(|_| self.board.game_over) {
+                    self.renderer.draw_board_sweep(&self.board, canvas_width, canvas_height, sequence.sweep_progress());
+                }
+                
+                // プレイヤーを描画
+                let cursors = self.hud.get_resource::<PlayerStateResource>().expect("cursor plugin");
+                self.renderer.draw_players(&self.players, &self.local_player_id, cursors, now_ms())?;
+                self.renderer.draw_emote_bubbles(&self.players, cursors, &self.emotes, now_ms())?;
+                self.renderer.end_camera();
+                
+                // UIを描画
+                self.renderer.draw_ui(canvas_width)?;
+                let highlighted = self.player_list_index(self.mouse_x, self.mouse_y);
+                self.renderer.draw_player_list(canvas_width, &self.player_list(), self.camera.following(), highlighted)?;
+                self.renderer.draw_toasts(canvas_width, self.toasts.active(now_ms()), now_ms())?;
+                
+                // エモート選択メニュー
+                let highlighted = self.emotes.picker_option_at(self.mouse_x, self.mouse_y);
+                self.renderer.draw_emote_picker(&self.emotes, highlighted)?;
+                
+                // HUD（接続状態・タイマー・残り地雷数）を描画
+                self.renderer.draw_hud(self.hud.entities())?;
+                
+                // マウスが乗っているプレイヤーのプロフィールカード
+                if !self.core_game.is_paused() && !self.board.game_over {
+                    if let Some(card) = self.hovered_profile_card() {
+                        self.renderer.draw_profile_card(canvas_width, canvas_height, &card, self.mouse_x, self.mouse_y)?;
+                    }
+                }
+                
+                // 開始前のカウントダウン
+                if let Some(number) = self.countdown.display_number(now_ms()) {
+                    self.renderer.draw_countdown(canvas_width, canvas_height, number)?;
+                }
+                
+                // 一時停止メニュー（HTMLのメニューを使う場合はHTMLで表示する）
+                if self.core_game.is_paused() && !self.dom_ui_enabled() {
+                    let highlighted = self.pause_menu_index(self.mouse_x, self.mouse_y);
+                    self.renderer.draw_pause_menu(canvas_width, canvas_height, highlighted, self.can_save_offline_game())?;
+                }
+                
+                // ゲームオーバー時は走査の演出のあとで結果パネルを表示
+                let panel_progress = self.result_sequence().map_or(1.0, |sequence| sequence.panel_progress());
+                if self.board.game_over && panel_progress > 0.0 {
+                    self.draw_game_summary(canvas_width, canvas_height, panel_progress)?;
+                }
+            }
+        }
+        
+        // ランキングパネルを最前面に描画
+        if self.leaderboard.is_visible() {
+            let status = if self.leaderboard.is_loaded() {
+                None
+            } else if self.network.is_connected {
+                Some(self.renderer.localization.text("leaderboard.loading"))
+            } else {
+                Some(self.renderer.localization.text("leaderboard.offline"))
+            };
+            
+            self.renderer.draw_leaderboard_panel(
+                canvas_width,
+                canvas_height,
+                &self.leaderboard.lines(self.settings.language),
+                self.leaderboard.scroll_offset(),
+                status
+            )?;
+        }
+        
+        // オフラインの間はどの画面でもバナーを表示
+        if self.offline {
+            self.renderer.draw_offline_banner(canvas_width)?;
+        }
+        
+        // 診断情報とログを最前面に表示
+        if self.debug_overlay {
+            if let Some(diagnostics) = self.hud.get_resource::<DiagnosticsResource>() {
+                self.renderer.draw_debug_overlay(canvas_width, &diagnostics.overlay_lines())?;
+            }
+        }
+        if self.log_panel {
+            self.renderer.draw_log_panel(canvas_width, canvas_height, &logger::recent_records(LOG_PANEL_LINES))?;
+        }
+        
+        Ok(())
+    }
+
+    /**
+     * マウスクリック処理を行う
+     * 
+     * 画面状態に応じて適切なクリック処理を実行します：
+     * - タイトル画面：スタートボタンの処理
+     * - 難易度選択画面：難易度ボタンの処理
+     * - 設定画面：設定項目の切り替えと戻るボタンの処理
+     * - 統計画面：戻るボタンの処理
+     * - ロビー画面：準備完了の切り替えとホストによる開始
      * - ゲーム画面：セルのクリックやフラグ処理
      * 
-     * @param x クリック位置のX座標
-     * @param y クリック位置のY座標
-     * @param right_click 右クリックかどうか
-     * @return 成功した場合はOk(()), エラーの場合はErr(JsValue)
+     * ランキングパネルの表示中は、パネルの外をクリックすると閉じます。
+     * 
+     * @param x クリック位置のX座標
+     * @param y クリック位置のY座標
+     * @param right_click 右クリックかどうか
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
+     */
+    pub fn handle_mouse_click(&mut self, x: f64, y: f64, right_click: bool) -> GameResult<()> {
+        // エラー画面の表示中はエラー画面のボタンのみ操作できる
+        if self.error_report.is_some() {
+            match self.error_button_index(x, y) {
+                Some(0) => self.recover_from_error(),
+                Some(_) => self.copy_error_report(),
+                None => {},
+            }
+            return Ok(());
+        }
+        
+        // ブラウザの自動再生制限のため、オーディオはユーザー操作時に初期化する
+        if let Err(e) = self.audio.initialize() {
+            log_warn!("Audio initialization error: {:?}", e);
+        }
+        
+        // ランキングパネル表示中はパネル以外を操作させない
+        if self.leaderboard.is_visible() {
+            let (left, top, width, height) = GameRenderer::leaderboard_panel_bounds(
+                self.canvas.width() as f64,
+                self.canvas.height() as f64
+            );
+            if !(x >= left && x <= left + width && y >= top && y <= top + height) {
+                self.leaderboard.close();
+            }
+            return Ok(());
+        }
+        
+        match self.current_screen {
+            Screen::Title => {
+                let canvas_width = self.canvas.width() as f64;
+                let canvas_height = self.canvas.height() as f64;
+                
+                // 名前入力欄がクリックされたら入力を開始、それ以外なら入力を確定
+                let (left, top, width, height) = GameRenderer::name_field_bounds(canvas_width, canvas_height);
+                if x >= left && x <= left + width && y >= top && y <= top + height {
+                    self.name_input_focused = true;
+                    return Ok(());
+                }
+                if self.name_input_focused {
+                    self.commit_player_name();
+                }
+                
+                // アバターボタンがクリックされたら次のアバターに切り替え
+                let (left, top, width, height) = GameRenderer::avatar_button_bounds(canvas_width, canvas_height);
+                if x >= left && x <= left + width && y >= top && y <= top + height {
+                    self.cycle_avatar();
+                    return Ok(());
+                }
+                
+                // ヘルプボタンがクリックされたかチェック
+                let (left, top, width, height) = GameRenderer::help_button_bounds(canvas_width, canvas_height);
+                if x >= left && x <= left + width && y >= top && y <= top + height {
+                    self.open_help();
+                    return Ok(());
+                }
+                
+                // スタートボタンの位置を計算
+                let button_x = canvas_width / 2.0;
+                let button_y = canvas_height / 2.0 + 50.0;
+                let button_width = 200.0;
+                let button_height = 60.0;
+                
+                // スタートボタンがクリックされたかチェック
+                if x >= button_x - button_width / 2.0 &&
+                   x <= button_x + button_width / 2.0 &&
+                   y >= button_y - button_height / 2.0 &&
+                   y <= button_y + button_height / 2.0 {
+                    // 難易度選択画面に遷移
+                    self.offline_play = false;
+                    self.current_screen = Screen::DifficultySelect;
+                    return Ok(());
+                }
+                
+                // 設定ボタンがクリックされたかチェック
+                let (left, top, width, height) = GameRenderer::settings_button_bounds(canvas_width, canvas_height);
+                if x >= left && x <= left + width && y >= top && y <= top + height {
+                    self.open_settings();
+                    return Ok(());
+                }
+                
+                // ランキングボタンがクリックされたかチェック
+                let (left, top, width, height) = GameRenderer::title_leaderboard_button_bounds(canvas_width, canvas_height);
+                if x >= left && x <= left + width && y >= top && y <= top + height {
+                    self.open_leaderboard();
+                    return Ok(());
+                }
+                
+                // 統計ボタンがクリックされたかチェック
+                let (left, top, width, height) = GameRenderer::stats_button_bounds(canvas_width, canvas_height);
+                if x >= left && x <= left + width && y >= top && y <= top + height {
+                    self.current_screen = Screen::Stats;
+                    return Ok(());
+                }
+                
+                // 履歴ボタンがクリックされたかチェック
+                let (left, top, width, height) = GameRenderer::history_button_bounds(canvas_width, canvas_height);
+                if x >= left && x <= left + width && y >= top && y <= top + height {
+                    self.current_screen = Screen::History;
+                    return Ok(());
+                }
+                
+                // デイリーチャレンジボタンがクリックされたかチェック
+                let (left, top, width, height) = GameRenderer::daily_button_bounds(canvas_width, canvas_height);
+                if x >= left && x <= left + width && y >= top && y <= top + height {
+                    self.start_daily_challenge();
+                    return Ok(());
+                }
+                
+                // オフラインボタンがクリックされたかチェック（難易度を選ぶとローカルで始める）
+                let (left, top, width, height) = GameRenderer::offline_button_bounds(canvas_width, canvas_height);
+                if x >= left && x <= left + width && y >= top && y <= top + height {
+                    self.offline_play = true;
+                    self.current_screen = Screen::DifficultySelect;
+                }
+            },
+            Screen::RoomSelect => {
+                if self.room_state.is_joining() {
+                    return Ok(());
+                }
+                match self.room_select_button(x, y) {
+                    Some(RoomSelectButton::Room(index)) => {
+                        // 一覧のルームに参加（`room_joined`でロビー画面に移る）
+                        if let Some(room) = self.room_state.rooms().get(index) {
+                            self.network.send_join_room(&room.code)?;
+                            self.room_state.start_joining();
+                        }
+                    },
+                    Some(RoomSelectButton::Create) => {
+                        self.network.send_create_room()?;
+                        self.room_state.start_joining();
+                    },
+                    Some(RoomSelectButton::QuickPlay) => {
+                        // 接続時に参加した既定のルームでそのまま遊ぶ
+                        self.current_screen = Screen::Lobby;
+                    },
+                    Some(RoomSelectButton::Back) => self.quit_to_title(),
+                    None => {}
+                }
+            },
+            Screen::Lobby => {
+                match self.lobby_button_index(x, y) {
+                    Some(0) => {
+                        // 準備完了を切り替え
+                        let ready = self.local_player_id.as_deref().is_some_and(|id| self.lobby.is_ready(id));
+                        self.network.send_ready(!ready)?;
+                    },
+                    Some(_) => {
+                        // ホストによる開始
+                        self.network.send_start_game()?;
+                    },
+                    None => {}
+                }
+            },
+            Screen::Stats => {
+                // 戻るボタンがクリックされたかチェック
+                let (left, top, width, height) = GameRenderer::stats_back_button_bounds(
+                    self.canvas.width() as f64,
+                    self.canvas.height() as f64
+                );
+                if x >= left && x <= left + width && y >= top && y <= top + height {
+                    self.current_screen = Screen::Title;
+                }
+            },
+            Screen::Help => {
+                // 戻るボタンがクリックされたかチェック
+                let (left, top, width, height) = GameRenderer::stats_back_button_bounds(
+                    self.canvas.width() as f64,
+                    self.canvas.height() as f64
+                );
+                if x >= left && x <= left + width && y >= top && y <= top + height {
+                    self.current_screen = self.help_return_screen;
+                }
+            },
+            Screen::History => {
+                // 練習ボタンがクリックされたかチェック
+                if let Some(index) = self.history_practice_index(x, y) {
+                    self.start_practice(index);
+                    return Ok(());
+                }
+                
+                // 戻るボタンがクリックされたかチェック
+                let (left, top, width, height) = GameRenderer::stats_back_button_bounds(
+                    self.canvas.width() as f64,
+                    self.canvas.height() as f64
+                );
+                if x >= left && x <= left + width && y >= top && y <= top + height {
+                    self.current_screen = Screen::Title;
+                }
+            },
+            Screen::Settings => {
+                match self.settings_row_index(x, y) {
+                    Some(index) if index < SettingsItem::ALL.len() => {
+                        let item = SettingsItem::ALL[index];
+                        let (left, _, width, _) = GameRenderer::settings_slider_bounds(
+                            self.canvas.width() as f64,
+                            self.canvas.height() as f64,
+                            index
+                        );
+                        
+                        // スライダー上のクリックはその位置の値に設定
+                        if item.is_slider() && x >= left - 8.0 && x <= left + width + 8.0 {
+                            self.set_slider_setting(item, ((x - left) / width) as f32)?;
+                        } else {
+                            self.select_setting(item)?;
+                        }
+                    },
+                    Some(_) => {
+                        // 戻るボタン
+                        self.current_screen = self.settings_return_screen;
+                    },
+                    None => {}
+                }
+            },
+            Screen::Accessibility => {
+                match self.accessibility_row_index(x, y) {
+                    Some(index) if index < AccessibilityItem::ALL.len() => {
+                        // 値を切り替えて即座に反映
+                        self.accessibility.cycle(AccessibilityItem::ALL[index]);
+                        self.renderer.set_accessibility(&self.accessibility);
+                        self.save_accessibility()?;
+                    },
+                    Some(_) => {
+                        // 戻るボタン
+                        self.current_screen = Screen::Settings;
+                    },
+                    None => {}
+                }
+            },
+            Screen::ThemeSelect => {
+                // 解放済みのテーマがクリックされたら適用
+                if let Some(index) = self.theme_row_index(x, y) {
+                    self.apply_theme(Theme::ALL[index])?;
+                    return Ok(());
+                }
+                
+                // 戻るボタンがクリックされたかチェック
+                let (left, top, width, height) = GameRenderer::stats_back_button_bounds(
+                    self.canvas.width() as f64,
+                    self.canvas.height() as f64
+                );
+                if x >= left && x <= left + width && y >= top && y <= top + height {
+                    self.current_screen = Screen::Settings;
+                }
+            },
+            Screen::DifficultySelect => {
+                // 難易度ボタンがクリックされたかチェック
+                match self.difficulty_button_index(x, y) {
+                    Some(index) if index < Difficulty::PRESETS.len() => {
+                        self.select_difficulty(Difficulty::PRESETS[index].clone())?;
+                    },
+                    Some(_) => {
+                        // カスタムはボード設定画面で入力してから開始
+                        self.current_screen = Screen::CustomBoard;
+                    },
+                    None => {}
+                }
+            },
+            Screen::CustomBoard => {
+                return self.handle_custom_board_click(x, y);
+            },
+            Screen::Game if self.compact => {
+                // 状態の帯のクリックで新しいゲームを始める
+                if y >= self.canvas.height() as f64 - COMPACT_STATUS_HEIGHT {
+                    self.start_compact_game();
+                } else if !self.board.game_over {
+                    if let Some(index) = self.get_cell_index(x, y) {
+                        if self.settings.is_flag_click(right_click) {
+                            self.toggle_flag(index)?;
+                        } else {
+                            self.reveal_cell(index)?;
+                        }
+                    }
+                }
+            },
+            Screen::Game => {
+                // 一時停止中はメニュー以外の操作を受け付けない
+                if self.core_game.is_paused() {
+                    return self.handle_pause_menu_click(x, y);
+                }
+                
+                // エモート選択メニューが開いていれば選択して閉じる
+                if self.emotes.picker_center().is_some() {
+                    match self.emotes.picker_option_at(x, y) {
+                        Some(emote) => self.send_emote(emote)?,
+                        None => self.emotes.close_picker(),
+                    }
+                    return Ok(());
+                }
+                
+                // リセットボタンがクリックされたかチェック
+                let canvas_width = self.canvas.width() as f64;
+                let reset_x = canvas_width - 80.0;
+                let reset_y = 30.0;
+                let dx = x - reset_x;
+                let dy = y - reset_y;
+                if dx * dx / (40.0 * 40.0) + dy * dy / (20.0 * 20.0) <= 1.0 {
+                    // リセットボタンがクリックされた
+                    return self.reset_game();
+                }
+                
+                // 一時停止ボタンがクリックされたかチェック
+                let dx = x - (canvas_width - 170.0);
+                if dx * dx / (40.0 * 40.0) + dy * dy / (20.0 * 20.0) <= 1.0 {
+                    self.toggle_pause();
+                    return Ok(());
+                }
+                
+                // ゲーム結果パネルのボタンを処理（パネル表示中はボードを操作しない）
+                if self.board.game_over {
+                    // パネルが現れるまではボタンを押せない
+                    if !self.result_sequence().is_none_or(|sequence| sequence.is_panel_interactive()) {
+                        return Ok(());
+                    }
+                    return match self.summary_button_index(x, y) {
+                        Some(0) => self.vote_rematch(),
+                        Some(1) => {
+                            self.open_leaderboard();
+                            Ok(())
+                        },
+                        Some(2) => {
+                            self.share_result();
+                            Ok(())
+                        },
+                        Some(_) => {
+                            self.quit_to_title();
+                            Ok(())
+                        },
+                        None => Ok(()),
+                    };
+                }
+                
+                // プレイヤー一覧のクリックでカーソルの追従を切り替える
+                if let Some(index) = self.player_list_index(x, y) {
+                    self.toggle_follow(index);
+                    return Ok(());
+                }
+                
+                // カウントダウン中はボードを操作できない
+                if self.countdown.is_active(now_ms()) {
+                    return Ok(());
+                }
+                
+                // クリックされたセルを取得
+                if let Some(index) = self.get_cell_index(x, y) {
+                    if self.settings.is_flag_click(right_click) {
+                        // フラグを切り替え（既定は右クリック）
+                        self.toggle_flag(index)?;
+                    } else {
+                        // セルを開く（既定は左クリック）
+                        self.reveal_cell(index)?;
+                    }
+                }
+            }
+        }
+        
+        Ok(())
+    }
+
+    /**
+     * ゲーム内のログパネルの表示を切り替える
+     */
+    pub fn toggle_log_panel(&mut self) {
+        self.log_panel = !self.log_panel;
+    }
+
+    /**
+     * 診断情報の表示を切り替える
+     */
+    pub fn toggle_debug_overlay(&mut self) {
+        self.debug_overlay = !self.debug_overlay;
+    }
+
+    /**
+     * 一時停止を切り替える
+     * 
+     * ゲーム画面でプレイ中の場合のみ一時停止でき、一時停止中なら再開します。
+     */
+    pub fn toggle_pause(&mut self) {
+        if self.current_screen != Screen::Game || self.compact || self.error_report.is_some() {
+            return;
+        }
+        
+        if self.core_game.is_paused() {
+            self.core_game.resume_game();
+        } else {
+            self.core_game.pause_game();
+        }
+    }
+
+    /**
+     * 座標にある一時停止メニューのボタンのインデックスを取得する
+     * 
+     * @param x X座標
+     * @param y Y座標
+     * @return ボタンのインデックス（Option<usize>）
+     */
+    pub fn pause_menu_index(&self, x: f64, y: f64) -> Option<usize> {
+        let canvas_width = self.canvas.width() as f64;
+        let canvas_height = self.canvas.height() as f64;
+        
+        (0..PAUSE_MENU_ITEMS.len()).find(|&index| {
+            let (left, top, width, height) = GameRenderer::pause_menu_button_bounds(canvas_width, canvas_height, index);
+            x >= left && x <= left + width && y >= top && y <= top + height
+        })
+    }
+
+    /**
+     * 一時停止メニューのクリック処理を行う
+     * 
+     * @param x クリック位置のX座標
+     * @param y クリック位置のY座標
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
+     */
+    fn handle_pause_menu_click(&mut self, x: f64, y: f64) -> GameResult<()> {
+        match self.pause_menu_index(x, y) {
+            Some(index) => self.select_pause_menu_item(index),
+            None => Ok(()),
+        }
+    }
+
+    /**
+     * 一時停止メニューの項目を選んだときの処理
+     * 
+     * @param index 選んだ項目のインデックス（`PAUSE_MENU_ITEMS`の順）
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
+     */
+    fn select_pause_menu_item(&mut self, index: usize) -> GameResult<()> {
+        match index {
+            // 再開
+            0 => self.core_game.resume_game(),
+            // 設定（戻るとゲーム画面の一時停止メニューに戻る）
+            1 => self.open_settings(),
+            // ヘルプ（戻るとゲーム画面の一時停止メニューに戻る）
+            2 => self.open_help(),
+            // オフラインのゲームを保存
+            3 => self.save_offline_game(),
+            // 保存したゲームを読み込む（進行中のゲームは確認してから置き換える）
+            4 if self.confirm_leave()? => self.load_offline_game(),
+            4 => {},
+            // タイトルへ戻る（進行中のゲームは確認してから抜ける）
+            _ if self.confirm_leave()? => self.quit_to_title(),
+            _ => {}
+        }
+        
+        Ok(())
+    }
+
+    /**
+     * 設定画面を開く
+     * 
+     * 設定画面の「戻る」ボタンで現在の画面に戻ります。
+     */
+    pub fn open_settings(&mut self) {
+        self.settings_return_screen = self.current_screen;
+        self.current_screen = Screen::Settings;
+    }
+
+    /**
+     * ヘルプ画面を開く
+     * 
+     * ヘルプ画面の「戻る」ボタンで現在の画面に戻ります。
+     */
+    pub fn open_help(&mut self) {
+        self.help_return_screen = self.current_screen;
+        self.current_screen = Screen::Help;
+    }
+
+    /**
+     * タッチ開始時の処理を行う
+     * 
+     * 指を離したときにタップか長押しかを判定するため、位置と時刻を記録します。
+     * 
+     * @param x タッチ位置のX座標
+     * @param y タッチ位置のY座標
+     */
+    pub fn handle_touch_start(&mut self, x: f64, y: f64) {
+        self.mouse_x = x;
+        self.mouse_y = y;
+        self.touch_start = Some((x, y, now_ms()));
+    }
+
+    /**
+     * タッチ終了時の処理を行う
+     * 
+     * タップは左クリック、`LONG_PRESS_MS`以上の長押しは右クリックとして扱います。
+     * 
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
+     */
+    pub fn handle_touch_end(&mut self) -> GameResult<()> {
+        match self.touch_start.take() {
+            Some((x, y, started_at)) => {
+                let long_press = now_ms() - started_at >= LONG_PRESS_MS;
+                self.handle_mouse_click(x, y, long_press)
+            },
+            None => Ok(()),
+        }
+    }
+
+    /**
+     * ゲームを抜ける前に確認が必要かどうか
+     * 
+     * ゲームが進行中の場合（一時停止中に開いた設定・ヘルプ画面を含む）に確認します。
+     * タブを閉じる時の`beforeunload`の確認にも使用します。
+     * 
+     * @return 確認が必要な場合はtrue
+     */
+    pub fn should_confirm_leave(&self) -> bool {
+        let screen = match self.current_screen {
+            Screen::Settings | Screen::ThemeSelect | Screen::Accessibility => self.settings_return_screen,
+            Screen::Help => self.help_return_screen,
+            screen => screen,
+        };
+        screen == Screen::Game && self.board.game_started && !self.board.game_over
+    }
+
+    /**
+     * ゲームを抜けてよいかプレイヤーに確認する
+     * 
+     * 確認が不要な場合は確認せずにtrueを返します。
+     * 
+     * @return 抜けてよい場合はOk(true), エラーの場合はErr(GameError)
+     */
+    pub fn confirm_leave(&self) -> GameResult<bool> {
+        if !self.should_confirm_leave() {
+            return Ok(true);
+        }
+        
+        let window = web_sys::window().ok_or_else(|| GameError::Js("window is not available".to_string()))?;
+        Ok(window.confirm_with_message(self.renderer.localization.text("leave.confirm"))?)
+    }
+
+    /**
+     * ゲームを停止するときの後始末をする
+     * 
+     * サーバーから切断し、BGMを止めてAudioContextを閉じます。
+     */
+    pub fn shutdown(&mut self) {
+        self.network.disconnect();
+        self.dom_overlay = None;
+        if let Err(e) = self.audio.close() {
+            log_warn!("Audio close error: {:?}", e);
+        }
+    }
+
+    /**
+     * ゲームを抜けてタイトル画面に戻る
+     * 
+     * サーバーから切断し、プレイヤーとボードの状態を初期化します。
+     */
+    pub fn quit_to_title(&mut self) {
+        self.network.disconnect();
+        self.stop_peer_host();
+        self.network.room = None;
+        self.room_state.clear();
+        self.network_stats.clear();
+        self.reconnect.cancel();
+        self.clear_bots();
+        
+        self.local_player_id = None;
+        self.players.clear();
+        update_player_count(0);
+        
+        self.board.initialize();
+        self.core_game.initialize(self.board.mine_count as u32);
+        self.summary.reset();
+        self.rematch.clear();
+        self.toasts.clear();
+        self.camera = CameraResource::new();
+        self.practice_mines = None;
+        self.offline_play = false;
+        self.offline_mine_count = None;
+        self.mines_pending = false;
+        self.daily_challenge_day = None;
+        self.update_game_status();
+        
+        self.current_screen = Screen::Title;
+    }
+
+    /**
+     * 対戦履歴画面に表示する行を作成する
+     * 
+     * @return 新しい順の履歴の表示内容
+     */
+    fn history_rows(&self) -> Vec<HistoryRowView> {
+        let language = self.settings.language;
+        self.history.records().iter().map(|record| {
+            let date = js_sys::Date::new(&JsValue::from_f64(record.finished_at));
+            let result = if record.win { "history.win" } else { "history.loss" };
+            HistoryRowView {
+                summary: format!(
+                    "{:02}/{:02} {:02}:{:02}  {}  {}  {}",
+                    date.get_month() + 1,
+                    date.get_date(),
+                    date.get_hours(),
+                    date.get_minutes(),
+                    record.difficulty().label(language),
+                    self.renderer.localization.text(result),
+                    record.time_text()
+                ),
+                players: self.renderer.localization.format("history.players", &[&record.players.join(", ")]),
+                win: record.win,
+                can_practice: record.can_practice(),
+            }
+        }).collect()
+    }
+
+    /**
+     * 指定された座標にある対戦履歴画面の練習ボタンのインデックスを取得する
+     * 
+     * @param x X座標
+     * @param y Y座標
+     * @return 練習できる履歴の練習ボタン上であればインデックス、それ以外はNone
+     */
+    pub fn history_practice_index(&self, x: f64, y: f64) -> Option<usize> {
+        let canvas_width = self.canvas.width() as f64;
+        self.history.records().iter().enumerate()
+            .filter(|(_, record)| record.can_practice())
+            .map(|(index, _)| index)
+            .find(|&index| {
+                let (left, top, width, height) = GameRenderer::history_practice_button_bounds(canvas_width, index);
+                x >= left && x <= left + width && y >= top && y <= top + height
+            })
+    }
+
+    /**
+     * 履歴の盤面で一人用の練習を始める
+     * 
+     * サーバーには接続せず、記録された地雷の配置でボードを作り直してローカルで操作します。
+     * 練習の結果は統計・履歴には記録しません。
+     * 
+     * @param index 練習する履歴のインデックス
+     */
+    pub fn start_practice(&mut self, index: usize) {
+        let record = match self.history.records().get(index) {
+            Some(record) if record.can_practice() => record.clone(),
+            _ => return,
+        };
+        log_info!("履歴の盤面を練習: {} {}x{}", record.difficulty, record.width, record.height);
+        
+        self.start_local_board(record.width, record.height, &record.mines, record.difficulty());
+        self.daily_challenge_day = None;
+    }
+
+    /**
+     * 今日のデイリーチャレンジを始める
+     * 
+     * 日付から決まる盤面を一人用の練習と同じようにローカルで遊びます。
+     * クリアすると連続記録を更新します（統計・履歴には記録しません）。
+     */
+    pub fn start_daily_challenge(&mut self) {
+        let day = day_number(js_sys::Date::now());
+        log_info!("デイリーチャレンジ開始: {}日目", day);
+        
+        self.start_local_board(DAILY_BOARD_WIDTH, DAILY_BOARD_HEIGHT, &daily_challenge_mines(day), Difficulty::Medium);
+        self.daily_challenge_day = Some(day);
+    }
+
+    /**
+     * 埋め込み用の最小表示で新しいゲームを始める
+     * 
+     * 選択中の難易度の大きさで、オフラインのゲームと同じようにローカルで遊びます。
+     */
+    pub fn start_compact_game(&mut self) {
+        self.start_offline_game();
+    }
+
+    /**
+     * サーバーに接続せずに一人用のゲームを始める
+     * 
+     * 選択中の難易度の大きさで、最初に開いたセルとその周囲を避けて地雷をランダムに配置します。
+     * 練習と同じく結果は統計・履歴には記録しません。
+     */
+    pub fn start_offline_game(&mut self) {
+        let config = self.game_config.board_config.clone();
+        log_info!("オフラインのゲームを開始: {}x{} 地雷{}", config.width, config.height, config.mine_count);
+        
+        let difficulty = self.game_config.difficulty.clone();
+        self.start_local_board(config.width, config.height, &[], difficulty);
+        self.daily_challenge_day = None;
+        self.offline_mine_count = Some(config.mine_count);
+        self.defer_mine_placement(config.mine_count);
+        self.core_game.initialize(config.mine_count as u32);
+        self.update_game_status();
+    }
+
+    /**
+     * 地雷を置かずにボードを閉じ、最初にセルを開くまで配置を待つ
+     * 
+     * @param mine_count 配置する地雷の数
+     */
+    fn defer_mine_placement(&mut self, mine_count: usize) {
+        self.board.load_mines(&[]);
+        self.board.mine_count = mine_count;
+        self.practice_mines = Some(Vec::new());
+        self.practice_moves.clear();
+        self.mines_pending = true;
+    }
+
+    /**
+     * サーバーに接続せずに地雷の配置が決まった盤面でゲームを始める
+     * 
+     * @param width ボードの幅
+     * @param height ボードの高さ
+     * @param mines 地雷のセルのインデックス
+     * @param difficulty 記録上の難易度
+     */
+    fn start_local_board(&mut self, width: usize, height: usize, mines: &[usize], difficulty: Difficulty) {
+        self.network.disconnect();
+        self.stop_peer_host();
+        self.clear_bots();
+        self.local_player_id = None;
+        self.players.clear();
+        update_player_count(0);
+        
+        // 指定された地雷の配置でボードを作り直す
+        self.board.width = width;
+        self.board.height = height;
+        self.fit_board_to_canvas();
+        self.board.load_mines(mines);
+        self.practice_mines = Some(mines.to_vec());
+        self.practice_moves.clear();
+        self.offline_mine_count = None;
+        self.mines_pending = false;
+        
+        self.game_config.set_difficulty(difficulty);
+        self.core_game.initialize(self.board.mine_count as u32);
+        self.summary.reset();
+        self.rematch.clear();
+        self.toasts.clear();
+        self.camera = CameraResource::new();
+        self.update_game_status();
+        
+        self.current_screen = Screen::Game;
+    }
+
+    /**
+     * 再戦に投票する
+     * 
+     * 投票済みの場合は何もしません。オフラインの場合はそのままリセットを要求します。
+     * 
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
+     */
+    pub fn vote_rematch(&mut self) -> GameResult<()> {
+        if self.local_player_id.as_deref().is_some_and(|id| self.rematch.has_voted(id)) {
+            return Ok(());
+        }
+        
+        if self.network.is_connected {
+            self.network.send_rematch_vote()
+        } else {
+            self.reset_game()
+        }
+    }
+
+    /**
+     * 現在のルームへの招待リンクを作る
+     * 
+     * @return ルームに参加している場合は`#room=`付きのページのURL
+     */
+    pub fn invite_link(&self) -> Option<String> {
+        let room = self.network.room.as_ref()?;
+        let page_url = web_sys::window()?.location().href().ok()?;
+        Some(invite_link(&page_url, room))
+    }
+
+    /**
+     * ゲームの結果を共有用の文字列にしてクリップボードにコピーする
+     * 
+     * SHARE_GRID_MAX_SIZE以下のボードには絵文字のグリッドを付けます。
+     */
+    pub fn copy_result(&mut self) {
+        copy_to_clipboard(&self.clipboard, self.result_share_text(), "toast.copied");
+    }
+
+    /**
+     * ゲームの結果を共有シートで共有する
+     * 
+     * Web Share APIに対応していないブラウザではクリップボードにコピーします。
+     * ルームに参加している場合は招待リンクも一緒に共有します。
+     */
+    pub fn share_result(&mut self) {
+        share_or_copy(&self.clipboard, self.result_share_text(), self.invite_link(), "toast.copied");
+    }
+
+    /**
+     * 共有用の結果の文字列を作る
+     * 
+     * @return 結果の文字列（小さいボードは絵文字のグリッド付き）
+     */
+    fn result_share_text(&self) -> String {
+        let include_grid = self.board.width <= SHARE_GRID_MAX_SIZE && self.board.height <= SHARE_GRID_MAX_SIZE;
+        self.board.share_text(self.core_game.elapsed_time(), include_grid)
+    }
+
+    /**
+     * 現在のルームへの招待リンクをクリップボードにコピーする
+     */
+    pub fn copy_invite_link(&mut self) {
+        match self.invite_link() {
+            Some(link) => copy_to_clipboard(&self.clipboard, link, "toast.invite_copied"),
+            None => self.show_notice("toast.no_room"),
+        }
+    }
+
+    /**
+     * 盤面のシードをクリップボードにコピーする
+     * 
+     * シードを持つのは日付から地雷を配置するデイリーチャレンジのみです。
+     */
+    pub fn copy_seed(&mut self) {
+        match self.daily_challenge_day {
+            Some(day) => copy_to_clipboard(&self.clipboard, day.to_string(), "toast.seed_copied"),
+            None => self.show_notice("toast.no_seed"),
+        }
+    }
+
+    /**
+     * 設定に関係なく表示するお知らせの通知を出す
+     * 
+     * @param key 表示する文言の翻訳キー
+     */
+    fn show_notice(&mut self, key: &'static str) {
+        let text = self.renderer.localization.text(key).to_string();
+        self.announce(&text);
+        self.toasts.push(ToastCategory::Notice, text, now_ms());
+    }
+
+    /**
+     * オフラインのゲームを保存できるかどうか
+     * 
+     * @return 練習やデイリーチャレンジ、オフラインのゲームの進行中であればtrue（地雷の配置前は保存しない）
+     */
+    pub fn can_save_offline_game(&self) -> bool {
+        self.practice_mines.is_some() && !self.mines_pending && !self.board.game_over
+    }
+
+    /**
+     * 進行中のオフラインのゲームをIndexedDBに保存する
+     * 
+     * 地雷の配置と操作の記録を保存し、完了すると通知を表示します。
+     */
+    pub fn save_offline_game(&mut self) {
+        let Some(mines) = &self.practice_mines else {
+            return;
+        };
+        if !self.can_save_offline_game() {
+            return;
+        }
+        
+        self.core_game.update_elapsed_time();
+        let save = OfflineSave {
+            width: self.board.width,
+            height: self.board.height,
+            mines: mines.clone(),
+            difficulty: self.game_config.difficulty.key().to_string(),
+            daily_challenge_day: self.daily_challenge_day,
+            moves: self.practice_moves.clone(),
+            elapsed_ms: self.core_game.elapsed_time(),
+            saved_at: js_sys::Date::now(),
+        };
+        offline_storage::save(&self.offline_storage, OFFLINE_SAVE_KEY, save.to_json());
+    }
+
+    /**
+     * IndexedDBに保存したオフラインのゲームを読み込む
+     * 
+     * 読み込みが完了するとフレームの更新時に盤面を復元します。
+     */
+    pub fn load_offline_game(&mut self) {
+        offline_storage::load(&self.offline_storage, OFFLINE_SAVE_KEY);
+    }
+
+    /**
+     * IndexedDBへの保存・読み込みの結果を反映する
+     */
+    fn handle_offline_storage_events(&mut self) {
+        let events: Vec<OfflineStorageEvent> = self.offline_storage.borrow_mut().drain(..).collect();
+        for event in events {
+            match event {
+                OfflineStorageEvent::Saved => self.show_notice("toast.offline_saved"),
+                OfflineStorageEvent::Loaded(Some(json)) => match OfflineSave::from_json(&json) {
+                    Some(save) => {
+                        self.restore_offline_save(&save);
+                        self.show_notice("toast.offline_loaded");
+                    },
+                    None => self.show_notice("toast.offline_invalid"),
+                },
+                OfflineStorageEvent::Loaded(None) => self.show_notice("toast.offline_empty"),
+                OfflineStorageEvent::Failed => self.show_notice("toast.offline_failed"),
+            }
+        }
+    }
+
+    /**
+     * 保存データからオフラインのゲームを復元する
+     * 
+     * 地雷の配置からボードを作り直し、記録された操作を順に適用します。
+     * 
+     * @param save 復元する保存データ
+     */
+    pub fn restore_offline_save(&mut self, save: &OfflineSave) {
+        self.start_local_board(save.width, save.height, &save.mines, save.difficulty());
+        self.daily_challenge_day = save.daily_challenge_day;
+        
+        for &step in &save.moves {
+            match step {
+                OfflineMove::Reveal(index) => {
+                    self.board.game_started = true;
+                    if let Err(e) = self.board.reveal_cell(index) {
+                        log_error!("Replay error: {:?}", e);
+                    }
+                },
+                OfflineMove::Flag(index) => self.board.toggle_flag(index),
+            }
+        }
+        self.practice_moves = save.moves.clone();
+        
+        // 開始済みのゲームは保存時点の経過時間から再開する
+        if self.board.game_started {
+            self.core_game.start_game();
+            self.core_game.restore_elapsed_time(save.elapsed_ms);
+        }
+        self.update_game_status();
+    }
+
+    /**
+     * 結果演出の進行状況を取得する
+     * 
+     * @return 結果演出リソース（Option<&ResultSequenceResource>）
+     */
+    pub fn result_sequence(&self) -> Option<&ResultSequenceResource> {
+        self.hud.get_resource::<ResultSequenceResource>()
+    }
+
+    /**
+     * ゲーム結果パネルを描画する
+     * 
+     * タイム・スコア・3BVと効率・踏んだ地雷の数と、プレイヤーごとの開いたセル数を貢献バーで表示します。
+     * 
+     * @param panel_progress パネルがスライドして現れる進み具合（0.0〜1.0）
+     * @return 成功した場合はOk(()), キャンバスの呼び出しに失敗した場合はErr(JsValue)
+     */
+    fn draw_game_summary(&self, canvas_width: f64, canvas_height: f64, panel_progress: f64) -> Result<(), JsValue> {
+        let localization = &self.renderer.localization;
+        let score = self.core_game.score();
+        let score_text = match self.core_game.best_score(&self.game_config.difficulty) {
+            Some(best) if best == score && score > 0 => localization.format("summary.best_updated", &[&score]),
+            Some(best) => localization.format("summary.best", &[&score, &best]),
+            None => score.to_string(),
+        };
+        let three_bv = self.board.three_bv();
+        let efficiency = self.summary.efficiency(three_bv)
+            .map_or_else(|| "-".to_string(), |efficiency| format!("{}%", (efficiency * 100.0).round()));
+        
+        let stats = [
+            (localization.text("summary.difficulty"), self.game_config.difficulty.label(localization.language()).to_string()),
+            (localization.text("summary.time"), self.core_game.format_elapsed_time()),
+            (localization.text("summary.score"), score_text),
+            (localization.text("summary.three_bv"), format!("{} / {}", three_bv, self.summary.reveal_actions())),
+            (localization.text("summary.efficiency"), efficiency),
+            (localization.text("summary.mines"), self.summary.mines_triggered().to_string()),
+        ];
+        
+        // プレイヤーIDを表示名に置き換える（退出したプレイヤーはIDのまま灰色のバーで表示）
+        let players: Vec<SummaryPlayerView> = self.summary.cells_by_player()
+            .into_iter()
+            .map(|(id, cells)| match self.players.get(&id) {
+                Some(player) => SummaryPlayerView { name: player.name.clone(), color: player.color, cells },
+                None => SummaryPlayerView { name: id, color: Color(0x90A4AE), cells },
+            })
+            .collect();
+        
+        // 再戦投票の進み具合
+        let (votes, total) = self.rematch.progress();
+        let voted = self.local_player_id.as_deref().is_some_and(|id| self.rematch.has_voted(id));
+        let rematch_label = match (voted, votes) {
+            (true, _) => localization.format("rematch.voted", &[&votes, &total]),
+            (false, 0) => localization.text(SUMMARY_BUTTONS[0]).to_string(),
+            (false, _) => localization.format("rematch.votes", &[&votes, &total]),
+        };
+        let rematch_status = self.rematch.remaining_seconds(now_ms())
+            .map(|seconds| localization.format("rematch.deadline", &[&seconds]));
+        
+        let highlighted = self.summary_button_index(self.mouse_x, self.mouse_y);
+        let summary = GameSummaryView {
+            win: self.board.win,
+            stats: &stats,
+            players: &players,
+            rematch_label: &rematch_label,
+            rematch_status: rematch_status.as_deref(),
+            panel_progress,
+        };
+        self.renderer.draw_game_summary(canvas_width, canvas_height, &summary, highlighted)
+    }
+
+    /**
+     * 座標にあるゲーム結果パネルのボタンのインデックスを取得する
+     * 
+     * @param x X座標
+     * @param y Y座標
+     * @return ボタンのインデックス（Option<usize>、並びは`SUMMARY_BUTTONS`の順）
+     */
+    pub fn summary_button_index(&self, x: f64, y: f64) -> Option<usize> {
+        let canvas_width = self.canvas.width() as f64;
+        let canvas_height = self.canvas.height() as f64;
+        
+        (0..SUMMARY_BUTTONS.len()).find(|&index| {
+            let (left, top, width, height) = GameRenderer::summary_button_bounds(canvas_width, canvas_height, index);
+            x >= left && x <= left + width && y >= top && y <= top + height
+        })
+    }
+
+    /**
+     * 座標にある難易度ボタンのインデックスを取得する
+     * 
+     * @param x X座標
+     * @param y Y座標
+     * @return 難易度ボタンのインデックス（Option<usize>）
+     */
+    pub fn difficulty_button_index(&self, x: f64, y: f64) -> Option<usize> {
+        let canvas_width = self.canvas.width() as f64;
+        let canvas_height = self.canvas.height() as f64;
+        
+        // プリセットの後ろにカスタムのボタンが並ぶ
+        (0..=Difficulty::PRESETS.len()).find(|&index| {
+            let (left, top, width, height) = GameRenderer::difficulty_button_bounds(canvas_width, canvas_height, index);
+            x >= left && x <= left + width && y >= top && y <= top + height
+        })
+    }
+
+    /**
+     * 難易度を選択してゲームを開始する
+     * 
+     * 選択した難易度をゲーム設定に書き込み、サーバーに接続します。
+     * 招待リンクなどでルームが決まっていればロビー画面、それ以外はルーム選択画面に遷移します。
+     * ボード設定は接続後の初期化メッセージ・ルームへの参加の受信時に送信されます。
+     * 
+     * @param difficulty 選択された難易度
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
+     */
+    pub fn select_difficulty(&mut self, difficulty: Difficulty) -> GameResult<()> {
+        log_info!("難易度を選択: {}", difficulty.key());
+        self.game_config.set_difficulty(difficulty);
+        
+        // タイトル画面でオフラインを選んだ場合はサーバーに接続しない
+        if self.offline_play {
+            self.start_offline_game();
+            return Ok(());
+        }
+        
+        // ルームが決まっていなければルーム選択画面に遷移
+        self.room_state.clear();
+        self.current_screen = if self.network.room.is_some() { Screen::Lobby } else { Screen::RoomSelect };
+        
+        // WebSocketに接続
+        self.connect_websocket()
+    }
+
+    /**
+     * ホストページから難易度を変更する
+     * 
+     * ルームのホストとして接続中であれば新しいボード設定をサーバーに送ります（ロビー中のみ反映されます）。
+     * 
+     * @param key 難易度のキー（easy / medium / hard）
+     * @return 成功した場合はOk(()), 不明なキーの場合はErr(GameError::Config)
+     */
+    pub fn set_difficulty_by_key(&mut self, key: &str) -> GameResult<()> {
+        let difficulty = Difficulty::from_key(key)
+            .ok_or_else(|| GameError::Config(format!("unknown difficulty: {}", key)))?;
+        self.game_config.set_difficulty(difficulty);
+        
+        if self.network.is_connected && self.practice_mines.is_none() && self.is_lobby_host() {
+            self.send_board_config()?;
+        }
+        Ok(())
+    }
+
+    /**
+     * ホストページからテーマを変更する
+     * 
+     * @param name テーマ名（"dark"など）
+     * @return 成功した場合はOk(()), 不明なテーマや未解放のテーマの場合はErr(GameError::Config)
+     */
+    pub fn set_theme_by_key(&mut self, name: &str) -> GameResult<()> {
+        let theme = Theme::from_key(name)
+            .ok_or_else(|| GameError::Config(format!("unknown theme: {}", name)))?;
+        if !self.apply_theme(theme)? {
+            return Err(GameError::Config(format!("theme is locked: {}", name)));
+        }
+        Ok(())
+    }
+
+    /**
+     * 解放済みのテーマを適用して保存する
+     * 
+     * @param theme 適用するテーマ
+     * @return 適用した場合はOk(true), 未解放の場合はOk(false)
+     */
+    fn apply_theme(&mut self, theme: Theme) -> GameResult<bool> {
+        if !is_theme_unlocked(theme, &self.stats) {
+            return Ok(false);
+        }
+        self.settings.theme = theme;
+        self.renderer.set_theme(theme);
+        self.save_settings()?;
+        Ok(true)
+    }
+
+    /**
+     * ホストページからプレイヤー名を変更する
+     * 
+     * 保存して自分の表示名に反映し、接続中であれば他のプレイヤーにも通知します。
+     * 
+     * @param name 新しいプレイヤー名（前後の空白を除き、最大文字数で切り詰める）
+     */
+    pub fn set_player_name(&mut self, name: &str) {
+        self.settings.set_player_name(name);
+        self.commit_player_name();
+    }
+
+    /**
+     * ホストページから接続先のWebSocketサーバーを変更する
+     * 
+     * 対戦中であれば新しいサーバーに接続し直し（プレイヤーIDは引き継がない）、
+     * それ以外は次に対戦を始めたときから使います。
+     * 
+     * @param url 新しいサーバーのURL（Noneの場合はページのホストから決める）
+     * @return 成功した場合はOk(()), 不正なURLの場合はErr(GameError::Config)
+     */
+    pub fn set_server_url(&mut self, url: Option<&str>) -> GameResult<()> {
+        let url = url.map(parse_server_url).transpose()?;
+        if url == self.network.server_url {
+            return Ok(());
+        }
+        
+        log_info!("接続先のサーバーを変更: {:?}", url);
+        self.network.server_url = url;
+        self.reconnect.cancel();
+        if self.in_multiplayer() && !self.network.is_peer() && (self.network.is_connected || self.network.has_transport()) {
+            self.reconnect_now(None)?;
+        }
+        Ok(())
+    }
+
+    /**
+     * 座標にあるカスタムボード画面のボタンのインデックスを取得する
+     * 
+     * @param x X座標
+     * @param y Y座標
+     * @return 0は戻るボタン、1は開始ボタン（Option<usize>）
+     */
+    pub fn custom_board_button_index(&self, x: f64, y: f64) -> Option<usize> {
+        let canvas_width = self.canvas.width() as f64;
+        let canvas_height = self.canvas.height() as f64;
+        
+        (0..2).find(|&index| {
+            let (left, top, width, height) = GameRenderer::custom_board_button_bounds(canvas_width, canvas_height, index);
+            x >= left && x <= left + width && y >= top && y <= top + height
+        })
+    }
+
+    /**
+     * カスタムボード画面のクリックを処理する
+     * 
+     * 「−」「＋」ボタンで値を増減し、入力欄をクリックするとキーボードで入力できます。
+     * 開始ボタンは入力値が正しい場合のみ、ボード設定を書き込んでロビーに進みます。
+     * 
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
+     */
+    fn handle_custom_board_click(&mut self, x: f64, y: f64) -> GameResult<()> {
+        let canvas_width = self.canvas.width() as f64;
+        let canvas_height = self.canvas.height() as f64;
+        self.custom_board.focus(None);
+        
+        for (index, field) in CustomBoardField::ALL.iter().enumerate() {
+            let controls = GameRenderer::custom_board_control_bounds(canvas_width, canvas_height, index);
+            let clicked = controls.iter().position(|&(left, top, width, height)| {
+                x >= left && x <= left + width && y >= top && y <= top + height
+            });
+            match clicked {
+                Some(0) => self.custom_board.adjust(*field, -1),
+                Some(1) => self.custom_board.focus(Some(*field)),
+                Some(_) => self.custom_board.adjust(*field, 1),
+                None => continue,
+            }
+            return Ok(());
+        }
+        
+        match self.custom_board_button_index(x, y) {
+            Some(0) => {
+                self.current_screen = Screen::DifficultySelect;
+                Ok(())
+            },
+            Some(_) if self.custom_board.validate().is_ok() => {
+                let (width, height, mine_count) = self.custom_board.dimensions();
+                self.game_config.set_custom_board(width, height, mine_count);
+                self.select_difficulty(Difficulty::Custom)
+            },
+            _ => Ok(()),
+        }
+    }
+
+    /**
+     * ローカルプレイヤーがルームのホストかどうか
+     * 
+     * @return ホストの場合はtrue
+     */
+    fn is_lobby_host(&self) -> bool {
+        self.local_player_id.as_deref().is_some_and(|id| self.lobby.is_host(id))
+    }
+
+    /**
+     * 座標にあるロビー画面のボタンのインデックスを取得する
+     * 
+     * 0は準備完了ボタン、1はホストにのみ表示される開始ボタンです。
+     * 
+     * @param x X座標
+     * @param y Y座標
+     * @return ボタンのインデックス（Option<usize>）
+     */
+    pub fn lobby_button_index(&self, x: f64, y: f64) -> Option<usize> {
+        let canvas_width = self.canvas.width() as f64;
+        let canvas_height = self.canvas.height() as f64;
+        let is_host = self.is_lobby_host();
+        
+        let (left, top, width, height) = GameRenderer::lobby_ready_button_bounds(canvas_width, canvas_height);
+        if x >= left && x <= left + width && y >= top && y <= top + height {
+            return Some(0);
+        }
+        
+        let (left, top, width, height) = GameRenderer::lobby_start_button_bounds(canvas_width, canvas_height);
+        if is_host && x >= left && x <= left + width && y >= top && y <= top + height {
+            return Some(1);
+        }
+        
+        None
+    }
+
+    /**
+     * 座標にあるルーム選択画面のボタンを取得する
+     * 
+     * @param x X座標
+     * @param y Y座標
+     * @return ボタン（Option<RoomSelectButton>）
+     */
+    pub fn room_select_button(&self, x: f64, y: f64) -> Option<RoomSelectButton> {
+        let canvas_width = self.canvas.width() as f64;
+        let canvas_height = self.canvas.height() as f64;
+        let contains = |(left, top, width, height): (f64, f64, f64, f64)| {
+            x >= left && x <= left + width && y >= top && y <= top + height
+        };
+        
+        let room = (0..self.room_state.rooms().len())
+            .find(|&index| contains(GameRenderer::room_row_bounds(canvas_width, index)))
+            .map(RoomSelectButton::Room);
+        room.or_else(|| {
+            [RoomSelectButton::Create, RoomSelectButton::QuickPlay, RoomSelectButton::Back]
+                .into_iter()
+                .enumerate()
+                .find(|&(index, _)| contains(GameRenderer::room_select_button_bounds(canvas_width, canvas_height, index)))
+                .map(|(_, button)| button)
+        })
+    }
+
+    /**
+     * 選択中のボード設定をサーバーに送信する
+     * 
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
+     */
+    pub fn send_board_config(&self) -> GameResult<()> {
+        let board_config = &self.game_config.board_config;
+        self.network.send_board_config(board_config.width, board_config.height, board_config.mine_count)
+    }
+
+    /**
+     * 座標にある設定画面の行のインデックスを取得する
+     * 
+     * 設定項目の数と同じインデックスは「戻る」ボタンを表します。
+     * 
+     * @param x X座標
+     * @param y Y座標
+     * @return 行のインデックス（Option<usize>）
+     */
+    pub fn settings_row_index(&self, x: f64, y: f64) -> Option<usize> {
+        let canvas_width = self.canvas.width() as f64;
+        let canvas_height = self.canvas.height() as f64;
+        
+        (0..=SettingsItem::ALL.len()).find(|&index| {
+            let (left, top, width, height) = GameRenderer::settings_row_bounds(canvas_width, canvas_height, index);
+            x >= left && x <= left + width && y >= top && y <= top + height
+        })
+    }
+
+    /**
+     * テーマ選択画面に表示する行を作成する
+     * 
+     * @return テーマごとの表示内容
+     */
+    pub fn theme_rows(&self) -> Vec<ThemeRowView> {
+        Theme::ALL.iter().map(|&theme| ThemeRowView {
+            theme,
+            selected: theme == self.settings.theme,
+            requirement: ThemeUnlock::for_theme(theme)
+                .filter(|unlock| !unlock.is_met(&self.stats))
+                .map(|unlock| unlock.description(self.settings.language)),
+        }).collect()
+    }
+
+    /**
+     * 座標にあるテーマ選択画面の行のインデックスを取得する
+     * 
+     * @param x X座標
+     * @param y Y座標
+     * @return 行のインデックス（Option<usize>）
+     */
+    pub fn theme_row_index(&self, x: f64, y: f64) -> Option<usize> {
+        let canvas_width = self.canvas.width() as f64;
+        
+        (0..Theme::ALL.len()).find(|&index| {
+            let (left, top, width, height) = GameRenderer::theme_row_bounds(canvas_width, index);
+            x >= left && x <= left + width && y >= top && y <= top + height
+        })
+    }
+
+    /**
+     * 座標にあるアクセシビリティ画面の行のインデックスを取得する
+     * 
+     * 項目の数と同じインデックスは「戻る」ボタンを表します。
+     * 
+     * @param x X座標
+     * @param y Y座標
+     * @return 行のインデックス（Option<usize>）
+     */
+    pub fn accessibility_row_index(&self, x: f64, y: f64) -> Option<usize> {
+        let canvas_width = self.canvas.width() as f64;
+        let canvas_height = self.canvas.height() as f64;
+        
+        (0..=AccessibilityItem::ALL.len()).find(|&index| {
+            let (left, top, width, height) = GameRenderer::accessibility_row_bounds(canvas_width, canvas_height, index);
+            x >= left && x <= left + width && y >= top && y <= top + height
+        })
+    }
+
+    /**
+     * 設定画面の項目を選んだときの処理
+     * 
+     * テーマとアクセシビリティは専用の画面を開き、それ以外は値を切り替えます。
+     * 
+     * @param item 選んだ設定項目
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
+     */
+    fn select_setting(&mut self, item: SettingsItem) -> GameResult<()> {
+        match item {
+            // 解放済みのテーマから選ぶ
+            SettingsItem::Theme => self.current_screen = Screen::ThemeSelect,
+            SettingsItem::Accessibility => self.current_screen = Screen::Accessibility,
+            _ => self.change_setting(item)?,
+        }
+        Ok(())
+    }
+
+    /**
+     * スライダーの設定項目の値を変更して保存する
+     * 
+     * @param item スライダーの設定項目
+     * @param value 新しい値（0.0〜1.0）
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
+     */
+    fn set_slider_setting(&mut self, item: SettingsItem, value: f32) -> GameResult<()> {
+        self.settings.set_slider_value(item, value);
+        self.audio.apply_settings(&self.settings);
+        self.save_settings()
+    }
+
+    /**
+     * HTMLのメニューを使うかどうか
+     * 
+     * @return 表示方法がHTMLで、メニューの要素を作れている場合はtrue
+     */
+    fn dom_ui_enabled(&self) -> bool {
+        self.settings.ui_mode == UiMode::Dom && self.dom_overlay.is_some()
+    }
+
+    /**
+     * HTMLのメニューに表示する内容を作る
+     * 
+     * @return 一時停止中は一時停止メニュー、設定画面では設定項目、それ以外は非表示
+     */
+    fn overlay_view(&self) -> OverlayView {
+        let localization = &self.renderer.localization;
+        match self.current_screen {
+            Screen::Game if self.core_game.is_paused() => OverlayView::PauseMenu {
+                heading: localization.text("pause.heading").to_string(),
+                buttons: PAUSE_MENU_ITEMS.iter().map(|&key| OverlayButtonView {
+                    label: localization.text(key).to_string(),
+                    enabled: key != "pause.save" || self.can_save_offline_game(),
+                }).collect(),
+            },
+            Screen::Settings => OverlayView::Settings {
+                heading: localization.text("menu.settings").to_string(),
+                rows: SettingsItem::ALL.iter().map(|&item| OverlaySettingView {
+                    item,
+                    label: item.label(localization.language()).to_string(),
+                    value: match item {
+                        // 入力欄には未設定の表示ではなく名前そのものを入れる
+                        SettingsItem::Name => self.settings.player_name.clone(),
+                        _ => self.settings.value_text(item),
+                    },
+                    slider: self.settings.slider_value(item),
+                }).collect(),
+                back: localization.text("common.back").to_string(),
+            },
+            _ => OverlayView::Hidden,
+        }
+    }
+
+    /**
+     * 表示方法の設定に合わせてHTMLのメニューを作成・更新・削除する
+     */
+    fn sync_dom_overlay(&mut self) {
+        if self.settings.ui_mode != UiMode::Dom {
+            self.dom_overlay = None;
+            return;
+        }
+        
+        if self.dom_overlay.is_none() {
+            match DomOverlay::new(&self.canvas, &self.overlay_actions) {
+                Ok(overlay) => self.dom_overlay = Some(overlay),
+                Err(e) => {
+                    // 要素を作れない場合はキャンバスのメニューを使い続ける
+                    log_error!("DOM overlay error: {:?}", e);
+                    self.settings.ui_mode = UiMode::Canvas;
+                    return;
+                }
+            }
+        }
+        
+        let view = self.overlay_view();
+        if let Some(overlay) = self.dom_overlay.as_mut() {
+            if let Err(e) = overlay.render(view) {
+                log_error!("DOM overlay render error: {:?}", e);
+            }
+        }
+    }
+
+    /**
+     * HTMLのメニューでの操作を反映する
+     * 
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
+     */
+    fn handle_overlay_actions(&mut self) -> GameResult<()> {
+        let actions: Vec<OverlayAction> = self.overlay_actions.borrow_mut().drain(..).collect();
+        for action in actions {
+            match action {
+                OverlayAction::PauseMenu(index) if self.current_screen == Screen::Game && self.core_game.is_paused() => {
+                    self.select_pause_menu_item(index)?;
+                },
+                OverlayAction::Setting(item) if self.current_screen == Screen::Settings => self.select_setting(item)?,
+                OverlayAction::SliderValue(item, value) => self.set_slider_setting(item, value)?,
+                OverlayAction::PlayerName(name) => self.set_player_name(&name),
+                OverlayAction::Back if self.current_screen == Screen::Settings => {
+                    self.current_screen = self.settings_return_screen;
+                },
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /**
+     * 設定項目を変更して保存する
+     * 
+     * プレイヤー名は入力ダイアログで変更し、それ以外は次の選択肢に切り替えます。
+     * 
+     * @param item 変更する設定項目
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
+     */
+    pub fn change_setting(&mut self, item: SettingsItem) -> GameResult<()> {
+        if item == SettingsItem::Name {
+            let window = web_sys::window().ok_or_else(|| GameError::Js("window is not available".to_string()))?;
+            match window.prompt_with_message_and_default(self.renderer.localization.text("settings.name_prompt"), &self.settings.player_name)? {
+                Some(name) => self.settings.set_player_name(&name),
+                None => return Ok(()),
+            }
+        } else {
+            self.settings.cycle(item);
+        }
+        
+        // ブラウザ通知を有効にしたときに許可を求める（ユーザー操作の中で呼ぶ必要がある）
+        if item == SettingsItem::BrowserNotifications && self.settings.browser_notifications {
+            request_notification_permission();
+        }
+        
+        // カーソルの色は他のプレイヤーにも通知
+        if item == SettingsItem::CursorColor && self.network.is_connected {
+            self.send_join()?;
+        }
+        
+        // テーマ・表示言語・音量は即座に反映
+        self.renderer.set_theme(self.settings.theme);
+        self.renderer.set_language(self.settings.language);
+        self.update_game_status();
+        self.audio.apply_settings(&self.settings);
+        
+        self.save_settings()
+    }
+
+    /**
+     * 効果音を再生する
+     * 
+     * 再生に失敗してもゲームは続行できるため、エラーはログに出力するだけです。
+     * 
+     * @param effect 再生する効果音
+     */
+    pub fn play_sound(&self, effect: SoundEffect) {
+        if let Err(e) = self.audio.play(effect) {
+            log_warn!("Sound playback error: {:?}", e);
+        }
+    }
+
+    /**
+     * 設定をlocalStorageに保存する
+     * 
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
+     */
+    pub fn save_settings(&self) -> GameResult<()> {
+        Ok(save_local_storage(SETTINGS_STORAGE_KEY, &self.settings.to_json())?)
+    }
+
+    /**
+     * アクセシビリティ設定をlocalStorageに保存する
+     * 
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
+     */
+    pub fn save_accessibility(&self) -> GameResult<()> {
+        Ok(save_local_storage(ACCESSIBILITY_STORAGE_KEY, &self.accessibility.to_json())?)
+    }
+
+    /**
+     * スクリーンリーダーに読み上げさせる
+     * 
+     * アクセシビリティ設定で読み上げが有効な場合のみ読み上げます。
+     * 
+     * @param message 読み上げる文言
+     */
+    pub fn announce(&self, message: &str) {
+        if self.accessibility.announcements {
+            announce(message);
+        }
+    }
+
+    /**
+     * キー入力を処理する
+     * 
+     * タイトル画面の名前入力欄に入力中の場合は文字を追加・削除し、
+     * Enterで確定、Escapeで入力を終了します。
+     * ゲーム画面ではEキーでエモート選択メニューを開閉し、数字キーでエモートを送信します。
+     * 
+     * @param key 押されたキー（KeyboardEvent.key）
+     * @return キー入力を消費した場合はtrue
+     */
+    pub fn handle_key_down(&mut self, key: &str) -> bool {
+        if self.compact || self.error_report.is_some() {
+            return false;
+        }
+        if self.current_screen == Screen::Game {
+            return self.handle_emote_key(key);
+        }
+        if self.current_screen == Screen::CustomBoard {
+            return self.handle_custom_board_key(key);
+        }
+        if !self.name_input_focused || self.current_screen != Screen::Title {
+            return false;
+        }
+        
+        match key {
+            "Enter" | "Escape" => self.commit_player_name(),
+            "Backspace" => {
+                self.settings.player_name.pop();
+            },
+            // 1文字のキーのみ入力として扱う（ShiftやArrowLeftなどは無視）
+            _ if key.chars().count() == 1 && self.settings.player_name.chars().count() < MAX_PLAYER_NAME_LENGTH => {
+                self.settings.player_name.push_str(key);
+            },
+            _ => {}
+        }
+        
+        true
+    }
+
+    /**
+     * カスタムボード画面の入力欄へのキー入力を処理する
+     * 
+     * 数字は入力中の欄の末尾に追加し、Backspaceで1桁削除します。
+     * EnterまたはEscapeで入力を終えます。
+     * 
+     * @param key 押されたキー（KeyboardEvent.key）
+     * @return キー入力を消費した場合はtrue
+     */
+    fn handle_custom_board_key(&mut self, key: &str) -> bool {
+        if self.custom_board.focused().is_none() {
+            return false;
+        }
+        
+        match key {
+            "Enter" | "Escape" => self.custom_board.focus(None),
+            "Backspace" => self.custom_board.pop_digit(),
+            _ => match key.chars().next().and_then(|c| c.to_digit(10)) {
+                Some(digit) if key.len() == 1 => self.custom_board.push_digit(digit),
+                _ => {}
+            },
+        }
+        
+        true
+    }
+
+    /**
+     * ゲーム画面でのエモート操作のキー入力を処理する
+     * 
+     * @param key 押されたキー（KeyboardEvent.key）
+     * @return キー入力を消費した場合はtrue
      */
-    pub fn handle_mouse_click(&mut self, x: f64, y: f64, right_click: bool) -> Result<(), JsValue> {
-        match self.current_screen {
-            Screen::Title => {
-                // スタートボタンの位置を計算
-                let canvas_width = self.canvas.width() as f64;
-                let canvas_height = self.canvas.height() as f64;
-                let button_x = canvas_width / 2.0;
-                let button_y = canvas_height / 2.0 + 50.0;
-                let button_width = 200.0;
-                let button_height = 60.0;
-                
-                // スタートボタンがクリックされたかチェック
-                if x >= button_x - button_width / 2.0 &&
-                   x <= button_x + button_width / 2.0 &&
-                   y >= button_y - button_height / 2.0 &&
-                   y <= button_y + button_height / 2.0 {
-                    // ゲーム画面に遷移
-                    self.current_screen = Screen::Game;
-                    
-                    // WebSocketに接続
-                    self.connect_websocket()?;
+    fn handle_emote_key(&mut self, key: &str) -> bool {
+        if self.core_game.is_paused() {
+            return false;
+        }
+        
+        let picker_open = self.emotes.picker_center().is_some();
+        match key {
+            "e" | "E" if picker_open => self.emotes.close_picker(),
+            "e" | "E" => self.emotes.open_picker(self.mouse_x, self.mouse_y),
+            "Escape" if picker_open => self.emotes.close_picker(),
+            _ if picker_open => {
+                // 数字キーで選択メニューの番号のエモートを送信
+                let Some(emote) = key.parse::<usize>().ok()
+                    .and_then(|number| number.checked_sub(1))
+                    .and_then(|index| Emote::ALL.get(index).copied()) else {
+                    return false;
+                };
+                if let Err(e) = self.send_emote(emote) {
+                    log_error!("Emote send error: {:?}", e);
                 }
             },
-            Screen::Game => {
-                // リセットボタンがクリックされたかチェック
-                let canvas_width = self.canvas.width() as f64;
-                let reset_x = canvas_width - 80.0;
-                let reset_y = 30.0;
-                let dx = x - reset_x;
-                let dy = y - reset_y;
-                if dx * dx / (40.0 * 40.0) + dy * dy / (20.0 * 20.0) <= 1.0 {
-                    // リセットボタンがクリックされた
-                    return self.reset_game();
+            _ => return false,
+        }
+        
+        true
+    }
+
+    /**
+     * エモートを送信する
+     * 
+     * 自分のカーソルにもすぐに表示し、選択メニューを閉じます。
+     * 
+     * @param emote 送信するエモート
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
+     */
+    pub fn send_emote(&mut self, emote: Emote) -> GameResult<()> {
+        self.emotes.close_picker();
+        
+        if let Some(id) = &self.local_player_id {
+            self.emotes.show(id, emote, now_ms());
+        }
+        
+        if self.network.is_connected {
+            self.network.send_emote(emote.key())?;
+        }
+        
+        Ok(())
+    }
+
+    /**
+     * 入力中のプレイヤー名を確定する
+     * 
+     * 名前を整形して設定に保存し、自分のカーソルの名前を更新して、
+     * 接続中であれば参加メッセージでサーバーに送信します。
+     */
+    pub fn commit_player_name(&mut self) {
+        self.name_input_focused = false;
+        
+        let name = self.settings.player_name.clone();
+        self.settings.set_player_name(&name);
+        if let Err(e) = self.save_settings() {
+            log_error!("Settings save error: {:?}", e);
+        }
+        
+        if let Some(id) = self.local_player_id.clone() {
+            let name = self.settings.player_name.clone();
+            self.rename_player(&id, &name);
+            
+            if self.network.is_connected {
+                if let Err(e) = self.send_join() {
+                    log_error!("Join message send error: {:?}", e);
                 }
-                
-                // クリックされたセルを取得
-                if let Some(index) = self.get_cell_index(x, y) {
-                    if right_click {
-                        // 右クリック: フラグを切り替え
-                        self.toggle_flag(index)?;
-                    } else {
-                        // 左クリック: セルを開く
-                        self.reveal_cell(index)?;
-                    }
+            }
+        }
+    }
+
+    /**
+     * アバターを次の候補に切り替えて保存する
+     * 
+     * 最後の候補の次はアバターなしに戻ります。接続中ならサーバーにも登録し直します。
+     */
+    pub fn cycle_avatar(&mut self) {
+        self.settings.avatar = next_avatar(&self.settings.avatar).to_string();
+        if let Err(e) = self.save_settings() {
+            log_error!("Settings save error: {:?}", e);
+        }
+        
+        if let Some(player) = self.local_player_id.as_ref().and_then(|id| self.players.get_mut(id)) {
+            player.avatar = self.settings.avatar.clone();
+            
+            if self.network.is_connected {
+                if let Err(e) = self.send_join() {
+                    log_error!("Join message send error: {:?}", e);
                 }
             }
         }
+    }
+
+    /**
+     * プレイヤー名・カーソルの色・アバターをサーバーに登録する
+     * 
+     * カーソルの色は他のプレイヤーと似ていれば空いている色に選び直し、自分のカーソルにも反映します。
+     * 
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
+     */
+    pub fn send_join(&mut self) -> GameResult<()> {
+        let taken: Vec<String> = self.players.values()
+            .filter(|player| !player.is_local)
+            .map(|player| player.color.to_css())
+            .collect();
+        let taken: Vec<&str> = taken.iter().map(String::as_str).collect();
+        let color = resolve_cursor_color(&self.settings.cursor_color, &taken);
         
-        Ok(())
+        if let Some(player) = self.local_player_id.as_ref().and_then(|id| self.players.get_mut(id)) {
+            player.color = player_color(&color);
+        }
+        self.network.send_join(&self.settings.player_name, &color, &self.settings.avatar)
     }
 
     /**
-     * ローカルプレイヤーの位置情報を送信する
+     * 累計統計をlocalStorageに保存する
      * 
-     * 一定間隔で位置情報を送信します。
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
+     */
+    pub fn save_stats(&self) -> GameResult<()> {
+        Ok(save_local_storage(STATS_STORAGE_KEY, &self.stats.to_json())?)
+    }
+
+    /**
+     * デイリーチャレンジの連続記録をlocalStorageに保存する
+     * 
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
+     */
+    pub fn save_streak(&self) -> GameResult<()> {
+        Ok(save_local_storage(STREAK_STORAGE_KEY, &self.streak.to_json())?)
+    }
+
+    /**
+     * 対戦履歴をlocalStorageに保存する
+     * 
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
+     */
+    pub fn save_history(&self) -> GameResult<()> {
+        Ok(save_local_storage(HISTORY_STORAGE_KEY, &self.history.to_json())?)
+    }
+
+    /**
+     * メッセージがローカルプレイヤーの操作によるものかどうかを判定する
+     * 
+     * @param player_id メッセージの`playerId`
+     * @return ローカルプレイヤーのIDと一致する場合はtrue
+     */
+    pub fn is_local_action(&self, player_id: &str) -> bool {
+        self.local_player_id.as_deref() == Some(player_id)
+    }
+
+    /**
+     * ランキングパネルを開く
      * 
-     * @return 成功した場合はOk(()), エラーの場合はErr(JsValue)
+     * サーバーに接続中であれば最新のリーダーボードを要求します。
      */
-    pub fn send_position_update(&mut self) -> Result<(), JsValue> {
-        // 現在時刻を取得
-        let now = js_sys::Date::now();
+    pub fn open_leaderboard(&mut self) {
+        self.leaderboard.open();
         
-        // 前回の更新から一定時間（100ms）経過していれば送信
-        if now - self.last_position_update > 100.0 {
-            self.last_position_update = now;
-            
-            // 位置情報を送信
-            self.network.send_position_update(self.mouse_x, self.mouse_y)?;
+        if self.network.is_connected {
+            if let Err(e) = self.network.send_leaderboard_request() {
+                log_error!("Leaderboard request error: {:?}", e);
+            }
+        }
+    }
+
+    /**
+     * マウスホイール処理を行う
+     * 
+     * ランキングパネルの表示中はパネルをスクロールします。
+     * 
+     * @param delta_y ホイールの縦方向の移動量
+     * @return ホイール操作を処理した場合はtrue
+     */
+    pub fn handle_wheel(&mut self, delta_y: f64) -> bool {
+        if !self.leaderboard.is_visible() {
+            return false;
         }
         
-        Ok(())
+        let viewport_height = GameRenderer::leaderboard_viewport_height(
+            self.canvas.width() as f64,
+            self.canvas.height() as f64
+        );
+        self.leaderboard.scroll_by(delta_y, viewport_height);
+        true
+    }
+
+    /**
+     * ローカルプレイヤーの位置情報を送信する
+     * 
+     * 毎フレーム送信キューに積み、送る間隔は送信キューで調整します。
+     * 
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
+     */
+    pub fn send_position_update(&mut self) -> GameResult<()> {
+        // 位置情報を送信（カメラの視点によらないボード上の座標）
+        let (x, y) = self.camera.screen_to_world(
+            self.mouse_x,
+            self.mouse_y,
+            self.canvas.width() as f64,
+            self.canvas.height() as f64
+        );
+        self.network.send_position_update(x, y)
     }
 
     /**
      * セルを開く
      * 
      * @param index 開くセルのインデックス
-     * @return 成功した場合はOk(()), エラーの場合はErr(JsValue)
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
      */
-    pub fn reveal_cell(&mut self, index: usize) -> Result<(), JsValue> {
+    pub fn reveal_cell(&mut self, index: usize) -> GameResult<()> {
         // すでに開かれている、またはフラグが立っている場合は何もしない
         if self.board.revealed[index] || self.board.flagged[index] {
             return Ok(());
@@ -591,17 +3992,54 @@ impl GameState {
             return Ok(());
         }
         
+        // 練習中はローカルで開く
+        if self.practice_mines.is_some() {
+            return self.reveal_local_cell(index, None);
+        }
+        
         // サーバーに送信
         self.network.send_reveal_cell(index)
     }
 
+    /**
+     * 練習中のボードでセルを開く
+     * 
+     * @param index 開くセルのインデックス
+     * @param player_id 開いたプレイヤー（自分の場合はNone）
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
+     */
+    fn reveal_local_cell(&mut self, index: usize, player_id: Option<&str>) -> GameResult<()> {
+        // オフラインのゲームでは最初に開くセルを避けて地雷を配置する
+        if std::mem::take(&mut self.mines_pending) {
+            let mine_count = self.board.mine_count;
+            self.practice_mines = Some(self.board.place_mines_avoiding(index, mine_count, &mut self.rng));
+        }
+        
+        self.practice_moves.push(OfflineMove::Reveal(index));
+        let before = self.board.revealed.iter().filter(|&&revealed| revealed).count();
+        self.board.game_started = true;
+        self.board.reveal_cell(index)?;
+        
+        let cells = self.board.revealed.iter().filter(|&&revealed| revealed).count() - before;
+        let mines = u32::from(self.board.cells[index] == CellValue::Mine);
+        self.summary.record_reveal(player_id, cells, mines);
+        if let Some(player) = player_id.and_then(|id| self.players.get_mut(id)) {
+            player.cells_revealed += cells;
+        }
+        
+        let effect = if cells > 1 { SoundEffect::Chord } else { SoundEffect::Reveal };
+        self.play_sound(effect);
+        self.update_game_status();
+        Ok(())
+    }
+
     /**
      * フラグを切り替える
      * 
      * @param index フラグを切り替えるセルのインデックス
-     * @return 成功した場合はOk(()), エラーの場合はErr(JsValue)
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
      */
-    pub fn toggle_flag(&mut self, index: usize) -> Result<(), JsValue> {
+    pub fn toggle_flag(&mut self, index: usize) -> GameResult<()> {
         // すでに開かれている場合は何もしない
         if self.board.revealed[index] {
             return Ok(());
@@ -612,16 +4050,348 @@ impl GameState {
             return Ok(());
         }
         
+        // 練習中はローカルで切り替える
+        if self.practice_mines.is_some() {
+            self.toggle_local_flag(index, None);
+            return Ok(());
+        }
+        
         // サーバーに送信
         self.network.send_toggle_flag(index)
     }
 
+    /**
+     * 練習中のボードでフラグを切り替える
+     * 
+     * @param index フラグを切り替えるセルのインデックス
+     * @param player_id 切り替えたプレイヤー（自分の場合はNone）
+     */
+    fn toggle_local_flag(&mut self, index: usize, player_id: Option<&str>) {
+        self.practice_moves.push(OfflineMove::Flag(index));
+        self.board.toggle_flag(index);
+        if let Some(id) = player_id.filter(|_| self.board.flagged[index]) {
+            self.summary.record_flag(id);
+        }
+        self.play_sound(SoundEffect::Flag);
+    }
+
+    /**
+     * 練習中のボードにボットを追加する
+     * 
+     * ボットは他のプレイヤーと同じようにカーソルを表示し、開いたセルは結果パネルの貢献に数えます。
+     * 
+     * @param strategy 打ち方（"random"または"solver"）
+     * @return 追加したボットのプレイヤーID、不明な打ち方の場合はErr(GameError::Config)、練習中でない場合はErr(GameError::Board)
+     */
+    pub fn add_bot_by_key(&mut self, strategy: &str) -> GameResult<String> {
+        let strategy = BotStrategy::from_key(strategy)
+            .ok_or_else(|| GameError::Config(format!("unknown bot strategy: {}", strategy)))?;
+        if self.practice_mines.is_none() || self.current_screen != Screen::Game {
+            return Err(GameError::Board("bots can only join a local game".to_string()));
+        }
+        let bots = self.hud.get_resource_mut::<BotResource>()
+            .ok_or_else(|| GameError::Board("bots are not available".to_string()))?;
+        let number = bots.bots().len() + 1;
+        let id = bots.add_bot(strategy);
+        
+        let (_, color) = CURSOR_COLORS[number % CURSOR_COLORS.len()];
+        let player = Player {
+            id: id.clone(),
+            name: self.renderer.localization.format("bot.name", &[&number]),
+            x: 0.0,
+            y: 0.0,
+            color: player_color(color),
+            avatar: String::new(),
+            score: 0,
+            is_local: false,
+            is_host: false,
+            is_alive: true,
+            cells_revealed: 0,
+            last_update: now_ms(),
+            ping: None,
+        };
+        self.players.insert(id.clone(), player);
+        update_player_count(self.players.len());
+        log_info!("ボットを追加: {}", id);
+        Ok(id)
+    }
+
+    /**
+     * ローカルのゲームをピアツーピアで公開し、参加者を1人招待する
+     * 
+     * 参加者ごとにデータチャネルを作ります。返した接続でオファーを作り、
+     * 参加者のアンサーは`pending_peer_connection`の接続に設定します。
+     * 
+     * @return 参加者との接続、ローカルのゲーム中でない場合はErr(GameError::Board)
+     */
+    pub fn host_peer_game(&mut self) -> GameResult<RtcPeerConnection> {
+        if self.practice_mines.is_none() || self.current_screen != Screen::Game {
+            return Err(GameError::Board("peer games can only be hosted from a local game".to_string()));
+        }
+        let host = self.peer_host.get_or_insert_with(|| PeerHost::new(&self.board));
+        let events = NetworkEventQueue::<ClientMessage>::default();
+        let transport = PeerTransport::offer(&events)?;
+        let connection = transport.connection();
+        let id = host.add_guest(Box::new(transport), events);
+        log_info!("ピアツーピアの参加者を招待: {}", id);
+        
+        self.pending_peer = Some(connection.clone());
+        Ok(connection)
+    }
+
+    /**
+     * アンサーを待っている参加者との接続を取り出す
+     * 
+     * @return 最後に招待した参加者との接続（無ければNone）
+     */
+    pub fn take_pending_peer(&mut self) -> Option<RtcPeerConnection> {
+        self.pending_peer.take()
+    }
+
+    /**
+     * ホストのゲームにピアツーピアで参加する
+     * 
+     * ゲームサーバーの代わりにホストとのデータチャネルを使います。
+     * 返した接続にホストのオファーを設定し、アンサーを作ってホストに渡します。
+     * 
+     * @return ホストとの接続、作れない場合はErr(GameError)
+     */
+    pub fn join_peer_game(&mut self) -> GameResult<RtcPeerConnection> {
+        self.quit_to_title();
+        self.current_screen = Screen::Game;
+        self.network.connect_peer()
+    }
+
+    /**
+     * ピアツーピアのホストをやめ、全ての参加者との接続を閉じる
+     */
+    fn stop_peer_host(&mut self) {
+        if let Some(mut host) = self.peer_host.take() {
+            host.close();
+        }
+        self.pending_peer = None;
+        for id in self.players.keys().filter(|id| id.starts_with("peer_")).cloned().collect::<Vec<_>>() {
+            self.remove_player(&id);
+        }
+    }
+
+    /**
+     * 参加者に送るホストのプレイヤーの情報
+     */
+    fn host_player_info(&self) -> PlayerInfo {
+        let (x, y) = self.camera.screen_to_world(
+            self.mouse_x,
+            self.mouse_y,
+            self.canvas.width() as f64,
+            self.canvas.height() as f64
+        );
+        PlayerInfo {
+            id: HOST_PLAYER_ID.to_string(),
+            name: self.settings.player_name.clone(),
+            x,
+            y,
+            color: resolve_cursor_color(&self.settings.cursor_color, &[]),
+            avatar: self.settings.avatar.clone(),
+        }
+    }
+
+    /**
+     * 参加者の操作をホストのゲームに反映し、ボードの変化とホストのカーソルを送る
+     */
+    fn update_peer_host(&mut self) {
+        let Some(mut host) = self.peer_host.take() else {
+            return;
+        };
+        
+        // ホスト自身の操作による変化を先に送ってから参加者の操作を処理する
+        host.sync_board(&self.board, HOST_PLAYER_ID);
+        let info = self.host_player_info();
+        for action in host.poll(&self.board, &info) {
+            match action {
+                PeerAction::Joined(guest) => {
+                    if !self.players.contains_key(&guest.id) {
+                        self.add_remote_player(&guest.id, guest.x, guest.y, player_color(&guest.color));
+                        self.show_toast(ToastCategory::Presence, "toast.join", &guest.id);
+                    }
+                    self.rename_player(&guest.id, &guest.name);
+                    if let Some(player) = self.players.get_mut(&guest.id) {
+                        player.color = player_color(&guest.color);
+                        player.avatar = guest.avatar.clone();
+                    }
+                },
+                PeerAction::Moved { id, x, y } => self.update_player_position(&id, x, y),
+                PeerAction::Reveal { id, index } => {
+                    if index < self.board.cells.len() && !self.board.game_over && !self.board.revealed[index] && !self.board.flagged[index] {
+                        self.mark_player_active(&id);
+                        if let Err(e) = self.reveal_local_cell(index, Some(&id)) {
+                            log_error!("Peer reveal error: {:?}", e);
+                        }
+                        host.sync_board(&self.board, &id);
+                    }
+                },
+                PeerAction::Flag { id, index } => {
+                    if index < self.board.cells.len() && !self.board.game_over && !self.board.revealed[index] {
+                        self.mark_player_active(&id);
+                        self.toggle_local_flag(index, Some(&id));
+                        host.sync_board(&self.board, &id);
+                    }
+                },
+                PeerAction::Emote { id, emote } => {
+                    if let Some(emote) = Emote::from_key(&emote) {
+                        self.emotes.show(&id, emote, now_ms());
+                        self.mark_player_active(&id);
+                    }
+                },
+                PeerAction::Reset => {
+                    if let Err(e) = self.reset_game() {
+                        log_error!("Peer reset error: {:?}", e);
+                    }
+                    host.sync_board(&self.board, HOST_PLAYER_ID);
+                },
+                PeerAction::Left(id) => {
+                    self.show_toast(ToastCategory::Presence, "toast.leave", &id);
+                    self.remove_player(&id);
+                },
+            }
+        }
+        
+        // ホストのカーソルは送信キューの移動と同じ間隔で送る
+        let now = now_ms();
+        if now - self.last_position_update >= MOVE_INTERVAL_MS {
+            self.last_position_update = now;
+            host.broadcast_host_move(info.x, info.y);
+        }
+        self.peer_host = Some(host);
+    }
+
+    /**
+     * 全てのボットを取り除く
+     */
+    pub fn clear_bots(&mut self) {
+        let Some(bots) = self.hud.get_resource_mut::<BotResource>() else {
+            return;
+        };
+        if bots.is_empty() {
+            return;
+        }
+        let ids: Vec<String> = bots.bots().iter().map(|bot| bot.id.clone()).collect();
+        bots.clear();
+        for id in ids {
+            self.remove_player(&id);
+        }
+    }
+
+    /**
+     * 他のプレイヤーの最新の位置をカーソルの補間の目標位置にする
+     * 
+     * 初めて見るプレイヤーはその位置にそのまま置き、いなくなったプレイヤーは補間の対象から外します。
+     */
+    fn sync_cursors(&mut self) {
+        let Some(cursors) = self.hud.get_resource_mut::<PlayerStateResource>() else {
+            return;
+        };
+        
+        let now = now_ms();
+        cursors.now = now;
+        let remote_ids: Vec<&str> = self.players.values()
+            .filter(|player| !player.is_local)
+            .map(|player| player.id.as_str())
+            .collect();
+        cursors.retain_players(&remote_ids);
+        
+        for player in self.players.values().filter(|player| !player.is_local) {
+            if cursors.has_player(&player.id) {
+                cursors.set_player_target(&player.id, player.x, player.y, now);
+            } else {
+                cursors.add_player(player.id.clone(), player.x, player.y, player.color.to_css());
+            }
+        }
+    }
+
+    /**
+     * ボットに見えている盤面と現在時刻を渡し、ボットのカーソルの位置をプレイヤーに反映する
+     * 
+     * 一時停止中は時刻を進めないため、ボットも手を指しません。
+     */
+    fn sync_bots(&mut self) {
+        let paused = self.core_game.is_paused();
+        let board_left = (self.canvas.width() as f64 - self.board.cell_size * self.board.width as f64) / 2.0;
+        let board_top = (self.canvas.height() as f64 - self.board.cell_size * self.board.height as f64) / 2.0;
+        let Some(bots) = self.hud.get_resource_mut::<BotResource>().filter(|bots| !bots.is_empty()) else {
+            return;
+        };
+        
+        if !paused {
+            bots.now = now_ms();
+        }
+        let view = &mut bots.board;
+        view.width = self.board.width;
+        view.height = self.board.height;
+        view.numbers.clear();
+        view.numbers.extend(self.board.cells.iter().zip(&self.board.revealed).map(|(cell, &revealed)| match cell {
+            CellValue::Empty(count) if revealed => Some(*count),
+            _ => None,
+        }));
+        view.flagged.clone_from(&self.board.flagged);
+        view.game_over = self.board.game_over;
+        view.origin = (board_left, board_top);
+        view.cell_size = self.board.cell_size;
+        
+        for bot in bots.bots() {
+            if let Some(player) = self.players.get_mut(&bot.id) {
+                player.x = bot.x;
+                player.y = bot.y;
+            }
+        }
+    }
+
+    /**
+     * ボットが指した手を反映する
+     * 
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
+     */
+    fn apply_bot_actions(&mut self) -> GameResult<()> {
+        let actions = match self.hud.get_resource_mut::<BotResource>() {
+            Some(bots) if !bots.is_empty() => bots.take_actions(),
+            _ => return Ok(()),
+        };
+        
+        for action in actions {
+            let index = action.bot_move.index();
+            if self.practice_mines.is_none() || self.board.game_over || self.board.revealed[index] {
+                continue;
+            }
+            self.mark_player_active(&action.bot_id);
+            match action.bot_move {
+                BotMove::Reveal(_) if !self.board.flagged[index] => self.reveal_local_cell(index, Some(&action.bot_id))?,
+                BotMove::Flag(_) if !self.board.flagged[index] => self.toggle_local_flag(index, Some(&action.bot_id)),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
     /**
      * ゲームをリセットする
      * 
-     * @return 成功した場合はOk(()), エラーの場合はErr(JsValue)
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
      */
-    pub fn reset_game(&mut self) -> Result<(), JsValue> {
+    pub fn reset_game(&mut self) -> GameResult<()> {
+        // オフラインのゲームは地雷を配置し直す
+        if let Some(mine_count) = self.offline_mine_count {
+            self.defer_mine_placement(mine_count);
+            self.update_game_status();
+            return Ok(());
+        }
+        
+        // 練習中は同じ盤面からやり直す
+        if let Some(mines) = &self.practice_mines {
+            self.board.load_mines(mines);
+            self.practice_moves.clear();
+            self.update_game_status();
+            return Ok(());
+        }
+        
         // サーバーに送信
         self.network.send_reset_game()
     }
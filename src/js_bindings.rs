@@ -1,14 +1,15 @@
+use std::cell::RefCell;
+use std::rc::Rc;
 use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+use crate::logger::log_warn;
 
 /**
  * JavaScriptの関数を呼び出すためのユーティリティ
  */
 #[wasm_bindgen]
 extern "C" {
-    // JavaScriptのconsole.log関数を呼び出すためのバインディング
-    #[wasm_bindgen(js_namespace = console)]
-    pub fn log(s: &str);
-    
     // JavaScriptのグローバル関数
     // 接続状態をUIに表示するための関数
     #[wasm_bindgen(js_name = updateConnectionStatus)]
@@ -22,11 +23,86 @@ extern "C" {
     #[wasm_bindgen(js_name = updateGameStatus)]
     pub fn update_game_status(status: &str);
     
+    // スクリーンリーダーに読み上げさせるための関数（aria-liveの領域に書き込む）
+    #[wasm_bindgen(js_name = announce)]
+    pub fn announce(message: &str);
+    
     // WebSocketのURLを取得するための関数
     #[wasm_bindgen(js_name = getWebSocketUrl)]
     pub fn get_websocket_url() -> String;
 }
 
+/**
+ * localStorageから値を読み込む
+ * 
+ * @param key 保存キー
+ * @return 保存されている値（存在しない場合やlocalStorageが使えない場合はNone）
+ */
+pub fn load_local_storage(key: &str) -> Option<String> {
+    web_sys::window()?
+        .local_storage()
+        .ok()??
+        .get_item(key)
+        .ok()?
+}
+
+/**
+ * ブラウザの言語設定（navigator.language）を取得する
+ * 
+ * @return 言語タグ（例: "ja-JP"、取得できない場合はNone）
+ */
+pub fn browser_language() -> Option<String> {
+    web_sys::window()?.navigator().language()
+}
+
+/**
+ * localStorageに値を保存する
+ * 
+ * @param key 保存キー
+ * @param value 保存する値
+ * @return 成功した場合はOk(()), エラーの場合はErr(JsValue)
+ */
+pub fn save_local_storage(key: &str, value: &str) -> Result<(), JsValue> {
+    let storage = web_sys::window()
+        .ok_or_else(|| JsValue::from_str("window is not available"))?
+        .local_storage()?
+        .ok_or_else(|| JsValue::from_str("localStorage is not available"))?;
+    
+    storage.set_item(key, value)
+}
+
+/**
+ * sessionStorageから値を読み込む
+ * 
+ * @param key 保存キー
+ * @return 保存されている値（存在しない場合やsessionStorageが使えない場合はNone）
+ */
+pub fn load_session_storage(key: &str) -> Option<String> {
+    web_sys::window()?
+        .session_storage()
+        .ok()??
+        .get_item(key)
+        .ok()?
+}
+
+/**
+ * sessionStorageに値を保存する
+ * 
+ * タブを閉じると消えるため、ページの再読み込みをまたぐ状態の保存に使います。
+ * 
+ * @param key 保存キー
+ * @param value 保存する値
+ * @return 成功した場合はOk(()), エラーの場合はErr(JsValue)
+ */
+pub fn save_session_storage(key: &str, value: &str) -> Result<(), JsValue> {
+    let storage = web_sys::window()
+        .ok_or_else(|| JsValue::from_str("window is not available"))?
+        .session_storage()?
+        .ok_or_else(|| JsValue::from_str("sessionStorage is not available"))?;
+    
+    storage.set_item(key, value)
+}
+
 /**
  * アニメーションフレームをリクエストする
  * 
@@ -34,10 +110,198 @@ extern "C" {
  * ゲームのアニメーションループを実現するために使用されます。
  * 
  * @param f 次のフレームで実行するクロージャ
+ * @return リクエストのID（取り消しに使う）
  */
-pub fn request_animation_frame(f: &Closure<dyn FnMut()>) {
+pub fn request_animation_frame(f: &Closure<dyn FnMut()>) -> i32 {
     web_sys::window()
         .unwrap()
         .request_animation_frame(f.as_ref().unchecked_ref())
-        .unwrap();
-} 
\ No newline at end of file
+        .unwrap()
+} 
+
+/**
+ * 指定した時間の後にコールバックを実行するようにタイマーを登録する
+ * 
+ * タブが非表示などでアニメーションフレームが来ない間の代わりのループに使います。
+ * 
+ * @param f 実行するクロージャ
+ * @param timeout_ms 待つ時間（ミリ秒）
+ * @return タイマーのID（取り消しに使う）
+ */
+pub fn set_timeout(f: &Closure<dyn FnMut()>, timeout_ms: i32) -> i32 {
+    web_sys::window()
+        .unwrap()
+        .set_timeout_with_callback_and_timeout_and_arguments_0(f.as_ref().unchecked_ref(), timeout_ms)
+        .unwrap()
+}
+
+/**
+ * クリップボードへのコピーの結果
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CopyResult {
+    Copied(&'static str), // コピーできた（成功時に表示する翻訳キー）
+    Shared,               // 共有シートから共有できた
+    Failed,               // 権限が無いなどでコピーできなかった
+}
+
+/**
+ * コピーの結果をフレームの更新処理に渡すためのキュー
+ */
+pub type ClipboardQueue = Rc<RefCell<Vec<CopyResult>>>;
+
+/**
+ * 非同期のClipboard APIで文字列をコピーする
+ * 
+ * @param text コピーする文字列
+ * @return 成功した場合はOk(()), エラーの場合はErr(JsValue)
+ */
+async fn write_clipboard_text(text: &str) -> Result<(), JsValue> {
+    let clipboard = web_sys::window()
+        .ok_or_else(|| JsValue::from_str("window is not available"))?
+        .navigator()
+        .clipboard();
+    JsFuture::from(clipboard.write_text(text)).await?;
+    Ok(())
+}
+
+/**
+ * クリップボードに文字列をコピーし、結果をキューに積む
+ * 
+ * @param queue 結果を受け取るキュー
+ * @param text コピーする文字列
+ * @param success_key 成功時に表示する翻訳キー
+ */
+pub fn copy_to_clipboard(queue: &ClipboardQueue, text: String, success_key: &'static str) {
+    let queue = queue.clone();
+    spawn_local(async move {
+        let result = match write_clipboard_text(&text).await {
+            Ok(()) => CopyResult::Copied(success_key),
+            Err(e) => {
+                log_warn!("Clipboard write error: {:?}", e);
+                CopyResult::Failed
+            }
+        };
+        queue.borrow_mut().push(result);
+    });
+}
+
+/**
+ * ブラウザ通知の許可を求める
+ * 
+ * 既に許可・拒否されている場合はダイアログは表示されません。
+ */
+pub fn request_notification_permission() {
+    if web_sys::Notification::permission() != web_sys::NotificationPermission::Default {
+        return;
+    }
+    match web_sys::Notification::request_permission() {
+        Ok(promise) => spawn_local(async move {
+            if let Err(e) = JsFuture::from(promise).await {
+                log_warn!("Notification permission error: {:?}", e);
+            }
+        }),
+        Err(e) => log_warn!("Notification permission error: {:?}", e),
+    }
+}
+
+/**
+ * ブラウザ通知を表示する（許可されていない場合は何もしない）
+ * 
+ * @param title 通知のタイトル
+ * @param body 通知の本文
+ */
+pub fn show_browser_notification(title: &str, body: &str) {
+    if web_sys::Notification::permission() != web_sys::NotificationPermission::Granted {
+        return;
+    }
+    let options = web_sys::NotificationOptions::new();
+    options.set_body(body);
+    if let Err(e) = web_sys::Notification::new_with_options(title, &options) {
+        log_warn!("Notification error: {:?}", e);
+    }
+}
+
+/**
+ * Web Share API（`navigator.share`）の関数を取得する
+ * 
+ * @return 対応していないブラウザではNone
+ */
+fn share_function() -> Option<(web_sys::Navigator, js_sys::Function)> {
+    let navigator = web_sys::window()?.navigator();
+    let share = js_sys::Reflect::get(&navigator, &JsValue::from_str("share")).ok()?;
+    share.dyn_into::<js_sys::Function>().ok().map(|share| (navigator, share))
+}
+
+/**
+ * 共有シートで文字列とリンクを共有する
+ * 
+ * @param navigator navigatorオブジェクト
+ * @param share `navigator.share`関数
+ * @param text 共有する文字列
+ * @param url 共有するリンク（無い場合は文字列のみ）
+ * @return 成功した場合はOk(()), キャンセルやエラーの場合はErr(JsValue)
+ */
+async fn share_data(navigator: &web_sys::Navigator, share: &js_sys::Function, text: &str, url: Option<&str>) -> Result<(), JsValue> {
+    let data = js_sys::Object::new();
+    js_sys::Reflect::set(&data, &JsValue::from_str("text"), &JsValue::from_str(text))?;
+    if let Some(url) = url {
+        js_sys::Reflect::set(&data, &JsValue::from_str("url"), &JsValue::from_str(url))?;
+    }
+    let promise = share.call1(navigator, &data)?.dyn_into::<js_sys::Promise>()?;
+    JsFuture::from(promise).await?;
+    Ok(())
+}
+
+/**
+ * 共有がユーザーにキャンセルされたかどうか
+ * 
+ * @param error `navigator.share`が返したエラー
+ * @return AbortErrorの場合はtrue
+ */
+fn is_share_cancelled(error: &JsValue) -> bool {
+    error.dyn_ref::<web_sys::DomException>().is_some_and(|exception| exception.name() == "AbortError")
+}
+
+/**
+ * 対応しているブラウザでは共有シートで共有し、それ以外ではクリップボードにコピーする
+ * 
+ * 共有シートがキャンセルされた場合は何も通知せず、その他の失敗ではクリップボードにコピーします。
+ * 
+ * @param queue 結果を受け取るキュー
+ * @param text 共有する文字列
+ * @param url 共有するリンク（クリップボードへは文字列の後ろに付けてコピーする）
+ * @param copied_key クリップボードにコピーしたときに表示する翻訳キー
+ */
+pub fn share_or_copy(queue: &ClipboardQueue, text: String, url: Option<String>, copied_key: &'static str) {
+    let Some((navigator, share)) = share_function() else {
+        copy_to_clipboard(queue, clipboard_text(text, url), copied_key);
+        return;
+    };
+    
+    let queue = queue.clone();
+    spawn_local(async move {
+        match share_data(&navigator, &share, &text, url.as_deref()).await {
+            Ok(()) => queue.borrow_mut().push(CopyResult::Shared),
+            Err(e) if is_share_cancelled(&e) => {},
+            Err(e) => {
+                log_warn!("Share error: {:?}", e);
+                copy_to_clipboard(&queue, clipboard_text(text, url), copied_key);
+            }
+        }
+    });
+}
+
+/**
+ * クリップボードにコピーする共有の文字列（リンクがあれば最後の行に付ける）
+ * 
+ * @param text 共有する文字列
+ * @param url 共有するリンク
+ * @return コピーする文字列
+ */
+fn clipboard_text(text: String, url: Option<String>) -> String {
+    match url {
+        Some(url) => format!("{}\n{}", text, url),
+        None => text,
+    }
+}
\ No newline at end of file
@@ -14,65 +14,326 @@ use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
 use web_sys::HtmlCanvasElement;
 use std::rc::Rc;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
 
 // サブモジュールを登録
 mod js_bindings;
+mod logger; // レベル付きのログ出力
+mod error; // クレート全体のエラー型
+mod event_listener;
 mod models;
 mod game_state;
 mod utils;
 mod rendering;
 mod network;
+mod peer_host; // ピアツーピアで遊ぶときのホスト
+mod protocol;
+mod binary_protocol; // ボードの更新のバイナリ形式
+mod send_queue; // 送信するメッセージのまとめと間隔の制限
+mod room;
+mod dom_overlay;
+mod offline_storage;
 mod board;
+#[cfg(test)]
+mod mock_server; // テスト用の模擬サーバー
 mod components; // ECSコンポーネント
 pub mod resources;  // ECSリソース
 mod entities;   // ECSエンティティ
+pub mod ecs;    // ECSワールド
 pub mod system;
 pub mod ecs_game;
+pub mod simulation; // ブラウザを使わないシミュレーション
 
 // サブモジュールからの要素をインポート
-use js_bindings::{log, request_animation_frame};
+use js_bindings::{request_animation_frame, set_timeout};
+use utils::now_ms;
+use logger::{log_error, log_warn, log_info};
+use error::{GameError, GameResult};
 use game_state::GameState;
+use models::GameOptions;
+use event_listener::EventListener;
+use rendering::GameRenderer;
+use resources::{Language, LocalizationResource};
 
 pub use ecs_game::EcsGame;
 
+/**
+ * アニメーションループのクロージャ（停止するときに取り出して破棄する）
+ */
+type FrameClosure = Rc<RefCell<Option<Closure<dyn FnMut()>>>>;
+
+/**
+ * アニメーションフレームが来ない間に状態を進めるタイマーの間隔（ミリ秒）
+ */
+const BACKGROUND_TICK_MS: i32 = 1000;
+
+/**
+ * 最後のアニメーションフレームからこの時間が経つと、タイマーで状態を進める（ミリ秒）
+ */
+const FRAME_STALL_MS: f64 = 250.0;
+
+/**
+ * 実行中のゲームと、停止するときに解放するリスナー・アニメーションループ
+ */
+struct GameHandle {
+    game_state: Rc<RefCell<GameState>>,                 // ゲーム状態
+    canvas: HtmlCanvasElement,                          // 描画先のキャンバス（パニック時の案内用）
+    language: Language,                                 // 起動時の表示言語（パニック時の案内用）
+    crashed: Cell<bool>,                                // パニックで停止したかどうか
+    listeners: Vec<EventListener>,                      // 登録中のイベントリスナー
+    frame: FrameClosure,                                // アニメーションループのクロージャ
+    frame_id: Rc<Cell<Option<i32>>>,                    // 予約済みのアニメーションフレームのID
+    fallback: FrameClosure,                             // アニメーションフレームの代わりに状態を進めるタイマーのクロージャ
+    fallback_id: Rc<Cell<Option<i32>>>,                 // 予約済みのタイマーのID
+    resize_observer: web_sys::ResizeObserver,           // キャンバスの表示サイズの監視
+    _resize_closure: Closure<dyn FnMut(js_sys::Array)>, // サイズ変更時のコールバック（監視中は保持する）
+}
+
+thread_local! {
+    // 実行中のゲーム（インスタンスIDごと、デバッグコンソールからの操作と停止用）
+    static GAMES: RefCell<HashMap<u32, GameHandle>> = RefCell::new(HashMap::new());
+    // 次に割り当てるインスタンスID
+    static NEXT_GAME_ID: Cell<u32> = const { Cell::new(1) };
+    // キーボード操作を受け付けるインスタンス（最後にクリック・タッチしたゲーム）
+    static FOCUSED_GAME: Cell<Option<u32>> = const { Cell::new(None) };
+}
+
+/// パニックの内容を再読み込み後のエラー画面に渡すsessionStorageのキー
+const PANIC_MESSAGE_KEY: &str = "minesweeper_panic";
+
+/**
+ * パニック時の処理を登録する（最初の1回のみ）
+ * 
+ * コンソールに出力したうえで、処理中だったゲームのキャンバスに再読み込みの案内を描画します。
+ * パニックの後はモジュールの状態が保証されないため、同じインスタンスではゲームを起動し直しません。
+ */
+fn install_panic_hook() {
+    static INSTALL: std::sync::Once = std::sync::Once::new();
+    INSTALL.call_once(|| {
+        std::panic::set_hook(Box::new(|info| {
+            console_error_panic_hook::hook(info);
+            handle_panic(&info.to_string());
+        }));
+    });
+}
+
+/**
+ * パニックの内容を記録し、停止したゲームに案内を描画する
+ * 
+ * ゲーム状態を借用できないインスタンスを、パニックで停止したものとみなします。
+ * 停止したキャンバスのクリックでページを再読み込みします（クリックの処理はWASMを経由しません）。
+ * 
+ * @param message パニックの内容
+ */
+fn handle_panic(message: &str) {
+    let _ = js_bindings::save_session_storage(PANIC_MESSAGE_KEY, message);
+    let reload = web_sys::window()
+        .and_then(|window| {
+            let location = window.location();
+            let reload = js_sys::Reflect::get(&location, &JsValue::from_str("reload")).ok()?;
+            Some(reload.dyn_into::<js_sys::Function>().ok()?.bind0(&location))
+        });
+    let _ = GAMES.try_with(|games| {
+        let Ok(games) = games.try_borrow() else {
+            return;
+        };
+        for handle in games.values().filter(|handle| handle.game_state.try_borrow_mut().is_err()) {
+            handle.crashed.set(true);
+            let canvas = &handle.canvas;
+            let context = canvas.get_context("2d").ok().flatten().and_then(|context| context.dyn_into::<web_sys::CanvasRenderingContext2d>().ok());
+            if let Some(context) = context {
+                let localization = LocalizationResource::new(handle.language);
+                let _ = GameRenderer::draw_crash_screen(&context, &localization, canvas.width() as f64, canvas.height() as f64, message);
+            }
+            if let Some(reload) = &reload {
+                let _ = canvas.add_event_listener_with_callback("click", reload);
+            }
+        }
+    });
+}
+
+/**
+ * 再読み込み前のパニックの内容を取り出す（1度だけ返す）
+ * 
+ * @return パニックで再読み込みした場合はその内容
+ */
+fn take_panic_message() -> Option<String> {
+    let storage = web_sys::window()?.session_storage().ok()??;
+    let message = storage.get_item(PANIC_MESSAGE_KEY).ok()??;
+    let _ = storage.remove_item(PANIC_MESSAGE_KEY);
+    Some(message)
+}
+
+/**
+ * ゲームの更新の結果を処理する
+ * 
+ * 通信のエラーは再接続で回復するため、ループを止めずに警告のみ出します。
+ * それ以外のエラーはエラー画面に切り替え、画面から状態を読み込み直せるようにします。
+ * 
+ * @param game ゲーム状態
+ * @param result 更新の結果
+ */
+fn handle_update_result(game: &mut GameState, result: GameResult<()>) {
+    match result {
+        Ok(()) => {},
+        Err(GameError::Network(message)) => log_warn!("Game update network error: {}", message),
+        Err(e) => {
+            log_error!("Game update error: {}", e);
+            game.show_error(&e);
+        },
+    }
+}
+
+/**
+ * インスタンスIDのゲーム状態に対して処理を行う
+ * 
+ * @param id インスタンスID
+ * @param f ゲーム状態を受け取る処理
+ * @return 処理の結果、ゲームが存在しないか使用中の場合はErr(JsValue)
+ */
+fn with_game<T>(id: u32, f: impl FnOnce(&mut GameState) -> T) -> Result<T, JsValue> {
+    GAMES.with(|games| {
+        let games = games.borrow();
+        let handle = games.get(&id).ok_or_else(|| JsValue::from_str(&format!("game {} is not started", id)))?;
+        let mut game = handle.game_state.try_borrow_mut().map_err(|_| JsValue::from_str("game state is busy"))?;
+        Ok(f(&mut game))
+    })
+}
+
+/**
+ * 起動オプションでキャンバスIDを省略した場合に使うキャンバス
+ */
+const DEFAULT_CANVAS_ID: &str = "game-canvas";
+
 /**
  * ゲームのエントリーポイント
  * 
  * Webページから呼び出されるWASMのエントリーポイントです。
+ * 起動オプション（キャンバスID・サーバーURL・プレイヤー名・難易度・ロケール・テーマ・ルーム・メニューの表示方法・埋め込み用の最小表示・乱数のシード・ログの出力レベル）を読み取り、
  * ゲームの初期化、イベントリスナーの設定、アニメーションループの開始を行います。
+ * 呼び出すたびに別のインスタンスを作るので、1つのページに複数のボードを並べられます。
+ * 例: `const id = wasm.init_game({ canvasId: "game-canvas", serverUrl: "ws://localhost:8080", locale: "en" })`
+ * 
+ * @param options 起動オプションのオブジェクト（undefinedの場合は全て既定値）
+ * @return 成功した場合は他の関数に渡すインスタンスID, エラーの場合はErr(JsValue)
+ */
+#[wasm_bindgen]
+pub fn init_game(options: JsValue) -> Result<u32, JsValue> {
+    let mut options: GameOptions = if options.is_undefined() || options.is_null() {
+        GameOptions::default()
+    } else {
+        serde_wasm_bindgen::from_value(options)?
+    };
+    
+    // ルームの指定が無ければページのURL（`#room=ABC123`など）から読み取る
+    if options.room.is_none() {
+        let location = web_sys::window().map(|window| window.location());
+        if let Some(location) = location {
+            options.room = room::parse_room_code(
+                &location.hash().unwrap_or_default(),
+                &location.search().unwrap_or_default(),
+            );
+        }
+    }
+    
+    // ログの出力レベル（ページの全てのゲームで共有する）
+    if let Some(spec) = &options.log {
+        let config = logger::LogConfig::parse(spec)
+            .ok_or_else(|| JsValue::from_str(&format!("invalid log filter: {}", spec)))?;
+        logger::init(config);
+    }
+    
+    let canvas_id = options.canvas_id.as_deref().unwrap_or(DEFAULT_CANVAS_ID);
+    let canvas_element = web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.get_element_by_id(canvas_id))
+        .ok_or_else(|| JsValue::from_str(&format!("canvas #{} is not found", canvas_id)))?
+        .dyn_into::<HtmlCanvasElement>()?;
+    
+    run_game(canvas_element, &options)
+}
+
+/**
+ * キャンバス要素を直接渡してゲームを開始する（起動オプションは全て既定値）
  * 
  * @param canvas_element ゲームを描画するキャンバス要素
- * @return 成功した場合はOk(()), エラーの場合はErr(JsValue)
+ * @return 成功した場合はインスタンスID, エラーの場合はErr(JsValue)
  */
 #[wasm_bindgen]
-pub fn start_game(canvas_element: HtmlCanvasElement) -> Result<(), JsValue> {
-    // パニック時にログ出力するようにする
-    console_error_panic_hook::set_once();
+pub fn start_game(canvas_element: HtmlCanvasElement) -> Result<u32, JsValue> {
+    run_game(canvas_element, &GameOptions::default())
+}
+
+/**
+ * ゲームの初期化、イベントリスナーの設定、アニメーションループの開始を行う
+ * 
+ * @param canvas_element ゲームを描画するキャンバス要素
+ * @param options 起動オプション
+ * @return 成功した場合はインスタンスID, エラーの場合はErr(JsValue)
+ */
+fn run_game(canvas_element: HtmlCanvasElement, options: &GameOptions) -> Result<u32, JsValue> {
+    // パニック時にログ出力し、停止したゲームに再読み込みの案内を表示するようにする
+    install_panic_hook();
+    
+    let id = NEXT_GAME_ID.with(|next| next.replace(next.get() + 1));
+    start_instance(id, canvas_element, options)?;
+    FOCUSED_GAME.with(|focused| focused.set(Some(id)));
+    
+    // パニックで再読み込みした場合は、その内容をエラー画面に表示する
+    if let Some(message) = take_panic_message() {
+        with_game(id, |game| game.show_panic(&message))?;
+    }
     
+    Ok(id)
+}
+
+/**
+ * インスタンスIDを指定してゲームを開始する
+ * 
+ * ゲーム状態を作成し、イベントリスナーとアニメーションループを登録します。
+ * 
+ * 処理中のパニックでゲーム状態が借用されたままになった場合、リスナーは何もしません
+ * （キャンバスのクリックでページを再読み込みします）。
+ * 
+ * @param id インスタンスID
+ * @param canvas_element ゲームを描画するキャンバス要素
+ * @param options 起動オプション
+ * @return 成功した場合はOk(()), エラーの場合はErr(JsValue)
+ */
+fn start_instance(id: u32, canvas_element: HtmlCanvasElement, options: &GameOptions) -> Result<(), JsValue> {
     // ゲーム状態の初期化
-    let game_state = Rc::new(RefCell::new(GameState::new(canvas_element.clone())?));
+    let game_state = Rc::new(RefCell::new(GameState::new(canvas_element.clone(), options)?));
+    let language = game_state.borrow().settings.language;
+    if options.compact {
+        // 埋め込み用の最小表示ではタイトルとロビーを飛ばしてすぐにボードを表示する
+        game_state.borrow_mut().start_compact_game();
+    }
+    // ページを再読み込みする前に対戦中だった場合は同じゲームに戻る
+    game_state.borrow_mut().resume_session();
+    let window = web_sys::window().ok_or_else(|| JsValue::from_str("window is not available"))?;
+    let mut listeners = Vec::new();
     
     // マウスイベントのセットアップ
     let game_state_clone = game_state.clone();
-    let mouse_move_closure = Closure::wrap(Box::new(move |event: web_sys::MouseEvent| {
-        let mut game = game_state_clone.borrow_mut();
+    listeners.push(EventListener::new(&canvas_element, "mousemove", move |event: web_sys::MouseEvent| {
+        let Ok(mut game) = game_state_clone.try_borrow_mut() else {
+            return;
+        };
         let rect = game.canvas.get_bounding_client_rect();
         game.mouse_x = event.client_x() as f64 - rect.left();
         game.mouse_y = event.client_y() as f64 - rect.top();
-    }) as Box<dyn FnMut(web_sys::MouseEvent)>);
-    
-    canvas_element.add_event_listener_with_callback(
-        "mousemove",
-        mouse_move_closure.as_ref().unchecked_ref(),
-    )?;
-    mouse_move_closure.forget();
+    })?);
     
     // マウスクリックイベントのセットアップ
     let game_state_clone = game_state.clone();
-    let mouse_click_closure = Closure::wrap(Box::new(move |event: web_sys::MouseEvent| {
+    listeners.push(EventListener::new(&canvas_element, "mousedown", move |event: web_sys::MouseEvent| {
         event.prevent_default();
-        let mut game = game_state_clone.borrow_mut();
+        FOCUSED_GAME.with(|focused| focused.set(Some(id)));
+        let Ok(mut game) = game_state_clone.try_borrow_mut() else {
+            // パニックで停止している場合はキャンバスのクリックでページを再読み込みする（handle_panicで登録）
+            return;
+        };
         let rect = game.canvas.get_bounding_client_rect();
         let x = event.client_x() as f64 - rect.left();
         let y = event.client_y() as f64 - rect.top();
@@ -81,47 +342,641 @@ pub fn start_game(canvas_element: HtmlCanvasElement) -> Result<(), JsValue> {
         let right_click = event.button() == 2;
         
         if let Err(e) = game.handle_mouse_click(x, y, right_click) {
-            log(&format!("Mouse click error: {:?}", e));
+            log_error!("Mouse click error: {:?}", e);
+        }
+    })?);
+    
+    // タッチイベントのセットアップ（タップでセルを開き、長押しでフラグ）
+    let game_state_clone = game_state.clone();
+    listeners.push(EventListener::new(&canvas_element, "touchstart", move |event: web_sys::TouchEvent| {
+        event.prevent_default();
+        FOCUSED_GAME.with(|focused| focused.set(Some(id)));
+        let Ok(mut game) = game_state_clone.try_borrow_mut() else {
+            return;
+        };
+        if let Some(touch) = event.changed_touches().get(0) {
+            let rect = game.canvas.get_bounding_client_rect();
+            let x = touch.client_x() as f64 - rect.left();
+            let y = touch.client_y() as f64 - rect.top();
+            game.handle_touch_start(x, y);
+        }
+    })?);
+    
+    let game_state_clone = game_state.clone();
+    listeners.push(EventListener::new(&canvas_element, "touchend", move |event: web_sys::TouchEvent| {
+        event.prevent_default();
+        let Ok(mut game) = game_state_clone.try_borrow_mut() else {
+            return;
+        };
+        if let Err(e) = game.handle_touch_end() {
+            log_error!("Touch error: {:?}", e);
         }
-    }) as Box<dyn FnMut(web_sys::MouseEvent)>);
+    })?);
     
-    canvas_element.add_event_listener_with_callback(
-        "mousedown",
-        mouse_click_closure.as_ref().unchecked_ref(),
-    )?;
-    mouse_click_closure.forget();
+    // マウスホイールイベントのセットアップ（ランキングパネルのスクロール）
+    let game_state_clone = game_state.clone();
+    listeners.push(EventListener::new(&canvas_element, "wheel", move |event: web_sys::WheelEvent| {
+        if game_state_clone.try_borrow_mut().is_ok_and(|mut game| game.handle_wheel(event.delta_y())) {
+            event.prevent_default();
+        }
+    })?);
+    
+    // キーボードイベントのセットアップ（名前の入力、Escで一時停止）
+    // キーはウィンドウ全体で受け取るため、最後に操作したインスタンスだけが処理する
+    let game_state_clone = game_state.clone();
+    listeners.push(EventListener::new(&window, "keydown", move |event: web_sys::KeyboardEvent| {
+        if FOCUSED_GAME.with(|focused| focused.get()) != Some(id) {
+            return;
+        }
+        // HTMLのメニューの入力欄への入力はゲームの操作にしない
+        if event.target().is_some_and(|target| target.is_instance_of::<web_sys::HtmlInputElement>()) {
+            return;
+        }
+        let Ok(mut game_state) = game_state_clone.try_borrow_mut() else {
+            return;
+        };
+        if game_state.handle_key_down(&event.key()) {
+            event.prevent_default();
+        } else if event.key() == "Escape" {
+            game_state.toggle_pause();
+        } else if event.key() == "`" {
+            // `キーでログパネルを切り替える
+            game_state.toggle_log_panel();
+        } else if event.key() == "F3" {
+            // F3キーで診断情報（メモリ使用量など）を切り替える
+            event.prevent_default();
+            game_state.toggle_debug_overlay();
+        } else if event.key() == "f" || event.key() == "F" {
+            // Fキーで全画面表示を切り替える
+            if let Err(e) = game_state.toggle_fullscreen() {
+                log_warn!("Fullscreen error: {:?}", e);
+            }
+        }
+    })?);
+    
+    // 進行中のゲームがある場合はタブを閉じる前に確認する
+    let game_state_clone = game_state.clone();
+    listeners.push(EventListener::new(&window, "beforeunload", move |event: web_sys::BeforeUnloadEvent| {
+        if game_state_clone.try_borrow().is_ok_and(|game| game.should_confirm_leave()) {
+            event.prevent_default();
+            event.set_return_value("");
+        }
+    })?);
+    
+    // ページを離れるときは退出を知らせて接続を閉じる
+    // （`beforeunload`は確認で取り消される場合があるため、実際に離れるときの`pagehide`で送る）
+    let game_state_clone = game_state.clone();
+    listeners.push(EventListener::new(&window, "pagehide", move |_: web_sys::PageTransitionEvent| {
+        if let Ok(mut game) = game_state_clone.try_borrow_mut() {
+            game.leave_on_page_hide();
+        }
+    })?);
+    
+    // 戻る・進むのキャッシュから復元された場合は接続し直す
+    let game_state_clone = game_state.clone();
+    listeners.push(EventListener::new(&window, "pageshow", move |event: web_sys::PageTransitionEvent| {
+        if !event.persisted() {
+            return;
+        }
+        if let Ok(mut game) = game_state_clone.try_borrow_mut() {
+            game.resume_after_page_show();
+        }
+    })?);
     
     // コンテキストメニューを無効化
-    let context_menu_closure = Closure::wrap(Box::new(move |event: web_sys::MouseEvent| {
+    listeners.push(EventListener::new(&canvas_element, "contextmenu", move |event: web_sys::MouseEvent| {
         event.prevent_default();
-    }) as Box<dyn FnMut(web_sys::MouseEvent)>);
+    })?);
     
-    canvas_element.add_event_listener_with_callback(
-        "contextmenu",
-        context_menu_closure.as_ref().unchecked_ref(),
-    )?;
-    context_menu_closure.forget();
+    // キャンバスの表示サイズの監視（CSSのレイアウト変更に追従する）
+    let game_state_clone = game_state.clone();
+    let resize_closure = Closure::wrap(Box::new(move |entries: js_sys::Array| {
+        let Some(entry) = entries.get(0).dyn_into::<web_sys::ResizeObserverEntry>().ok() else {
+            return;
+        };
+        let rect = entry.content_rect();
+        if let Ok(mut game) = game_state_clone.try_borrow_mut() {
+            game.resize_canvas(rect.width().round() as u32, rect.height().round() as u32);
+        }
+    }) as Box<dyn FnMut(js_sys::Array)>);
+    let resize_observer = web_sys::ResizeObserver::new(resize_closure.as_ref().unchecked_ref())?;
+    resize_observer.observe(&canvas_element);
     
     // アニメーションフレームのセットアップ
-    let f = Rc::new(RefCell::new(None));
-    let g = f.clone();
+    let frame: FrameClosure = Rc::new(RefCell::new(None));
+    let frame_id = Rc::new(Cell::new(None));
+    
+    let last_frame_at = Rc::new(Cell::new(now_ms()));
     
     let game_state_clone = game_state.clone();
-    *g.borrow_mut() = Some(Closure::wrap(Box::new(move || {
-        // ゲームの更新
-        if let Err(e) = game_state_clone.borrow_mut().update() {
-            log(&format!("Game update error: {:?}", e));
+    let next_frame = frame.clone();
+    let next_frame_id = frame_id.clone();
+    let frame_last_at = last_frame_at.clone();
+    *frame.borrow_mut() = Some(Closure::wrap(Box::new(move || {
+        next_frame_id.set(None);
+        frame_last_at.set(now_ms());
+        
+        // パニックで停止したゲームはループを止める（キャンバスのクリックでページを再読み込みする）
+        let Ok(mut game) = game_state_clone.try_borrow_mut() else {
             return;
+        };
+        
+        // ゲームの更新
+        let result = game.update();
+        handle_update_result(&mut game, result);
+        drop(game);
+        
+        // 次のフレームをリクエスト（停止済みならクロージャが無いので何もしない）
+        if let Some(closure) = next_frame.borrow().as_ref() {
+            next_frame_id.set(Some(request_animation_frame(closure)));
+        }
+    }) as Box<dyn FnMut()>));
+    
+    frame_id.set(Some(request_animation_frame(frame.borrow().as_ref().unwrap())));
+    
+    // アニメーションフレームが止まっている間（非表示のタブ・省電力モードなど）は
+    // 低頻度のタイマーで描画以外の処理を進め、受信した状態が古くならないようにする
+    let fallback: FrameClosure = Rc::new(RefCell::new(None));
+    let fallback_id = Rc::new(Cell::new(None));
+    
+    let game_state_clone = game_state.clone();
+    let next_fallback = fallback.clone();
+    let next_fallback_id = fallback_id.clone();
+    *fallback.borrow_mut() = Some(Closure::wrap(Box::new(move || {
+        next_fallback_id.set(None);
+        
+        if now_ms() - last_frame_at.get() >= FRAME_STALL_MS {
+            let Ok(mut game) = game_state_clone.try_borrow_mut() else {
+                return;
+            };
+            let result = game.update_in_background();
+            handle_update_result(&mut game, result);
         }
         
-        // 次のフレームをリクエスト
-        request_animation_frame(f.borrow().as_ref().unwrap());
+        // 次のタイマーを登録（停止済みならクロージャが無いので何もしない）
+        if let Some(closure) = next_fallback.borrow().as_ref() {
+            next_fallback_id.set(Some(set_timeout(closure, BACKGROUND_TICK_MS)));
+        }
     }) as Box<dyn FnMut()>));
     
-    request_animation_frame(g.borrow().as_ref().unwrap());
+    fallback_id.set(Some(set_timeout(fallback.borrow().as_ref().unwrap(), BACKGROUND_TICK_MS)));
+    
+    // タブが非表示の間はアニメーションループを止め、表示に戻ったら再開して状態を同期する
+    let document = window.document().ok_or_else(|| JsValue::from_str("document is not available"))?;
+    let document_clone = document.clone();
+    let game_state_clone = game_state.clone();
+    let visible_frame = frame.clone();
+    let visible_frame_id = frame_id.clone();
+    listeners.push(EventListener::new(&document, "visibilitychange", move |_: web_sys::Event| {
+        let hidden = document_clone.hidden();
+        if let Ok(mut game) = game_state_clone.try_borrow_mut() {
+            game.set_page_hidden(hidden);
+        }
+        
+        if hidden {
+            if let (Some(window), Some(id)) = (web_sys::window(), visible_frame_id.take()) {
+                let _ = window.cancel_animation_frame(id);
+            }
+        } else if visible_frame_id.get().is_none() {
+            if let Some(closure) = visible_frame.borrow().as_ref() {
+                visible_frame_id.set(Some(request_animation_frame(closure)));
+            }
+        }
+    })?);
+    
+    // オフラインの間はバナーを表示し、オンラインに戻ったら接続し直して状態を同期する
+    game_state.borrow_mut().set_online(window.navigator().on_line());
+    let game_state_clone = game_state.clone();
+    listeners.push(EventListener::new(&window, "offline", move |_: web_sys::Event| {
+        if let Ok(mut game) = game_state_clone.try_borrow_mut() {
+            game.set_online(false);
+        }
+    })?);
+    let game_state_clone = game_state.clone();
+    listeners.push(EventListener::new(&window, "online", move |_: web_sys::Event| {
+        if let Ok(mut game) = game_state_clone.try_borrow_mut() {
+            game.set_online(true);
+        }
+    })?);
+    
+    // 全画面表示の開始・終了に合わせてキャンバスのサイズを計算し直す
+    let document_clone = document.clone();
+    let game_state_clone = game_state.clone();
+    listeners.push(EventListener::new(&document, "fullscreenchange", move |_: web_sys::Event| {
+        let fullscreen = document_clone.fullscreen_element().is_some();
+        if let Ok(mut game) = game_state_clone.try_borrow_mut() {
+            game.handle_fullscreen_change(fullscreen);
+        }
+    })?);
+    
+    GAMES.with(|games| games.borrow_mut().insert(id, GameHandle {
+        game_state,
+        canvas: canvas_element,
+        language,
+        crashed: Cell::new(false),
+        listeners,
+        frame,
+        frame_id,
+        fallback,
+        fallback_id,
+        resize_observer,
+        _resize_closure: resize_closure,
+    }));
+    log_info!("🎮 ゲーム {} を開始しました", id);
     
     Ok(())
 }
 
+/**
+ * ゲームを停止する
+ * 
+ * アニメーションループを止め、イベントリスナーを解除し、サーバーから切断します。
+ * SPAなどでゲームを外した後に`init_game`で再び開始できます。
+ * ゲームが開始されていない場合は何もしません。
+ * ゲームの更新中に呼ばれた場合、サーバーからの切断は更新が終わった後に行います。
+ * 
+ * @param id 停止するゲームのインスタンスID
+ */
+#[wasm_bindgen]
+pub fn stop_game(id: u32) {
+    let Some(handle) = GAMES.with(|games| games.borrow_mut().remove(&id)) else {
+        return;
+    };
+    FOCUSED_GAME.with(|focused| {
+        if focused.get() == Some(id) {
+            focused.set(None);
+        }
+    });
+    
+    // 予約済みのフレームを取り消し、ループのクロージャを破棄して循環参照を断つ
+    if let (Some(window), Some(id)) = (web_sys::window(), handle.frame_id.get()) {
+        if let Err(e) = window.cancel_animation_frame(id) {
+            log_warn!("Cancel animation frame error: {:?}", e);
+        }
+    }
+    handle.frame.borrow_mut().take();
+    if let (Some(window), Some(id)) = (web_sys::window(), handle.fallback_id.get()) {
+        window.clear_timeout_with_handle(id);
+    }
+    handle.fallback.borrow_mut().take();
+    
+    // リスナーは破棄すると解除される
+    handle.resize_observer.disconnect();
+    drop(handle.listeners);
+    
+    if handle.crashed.get() {
+        // パニックで借用されたまま残ったゲーム状態は切断できないため、そのまま手放す
+        log_warn!("ゲーム {} はパニックで停止しています", id);
+    } else if let Ok(mut game) = handle.game_state.try_borrow_mut() {
+        game.shutdown();
+    } else {
+        // ゲームの更新中（ホストのコールバックなど）に呼ばれた場合は、更新が終わってから切断する
+        let game_state = handle.game_state.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            match game_state.try_borrow_mut() {
+                Ok(mut game) => game.shutdown(),
+                Err(_) => log_warn!("ゲーム {} を切断できませんでした", id),
+            }
+        });
+    }
+    log_info!("🛑 ゲーム {} を停止しました", id);
+}
+
+/**
+ * システムの有効・無効を切り替える（デバッグ用）
+ * 
+ * ブラウザのコンソールから描画やネットワークのシステムを一時的に止めるために使用します。
+ * 例: `wasm.set_system_enabled(id, "RenderSystem", false)`
+ * 
+ * @param id ゲームのインスタンスID
+ * @param name システム名
+ * @param enabled 有効にする場合はtrue
+ * @return 該当するシステムが存在した場合はtrue
+ */
+#[wasm_bindgen]
+pub fn set_system_enabled(id: u32, name: &str, enabled: bool) -> bool {
+    match with_game(id, |game| game.set_system_enabled(name, enabled)) {
+        Ok(found) => found,
+        Err(e) => {
+            log_warn!("⚠️ 切り替えできません: {:?}", e);
+            false
+        }
+    }
+}
+
+/**
+ * ゲーム状態のスナップショットを取得する
+ * 
+ * ホストページがキャンバスの外にUIを作るために使用します。
+ * 例: `wasm.get_game_summary(id).players.length`
+ * 
+ * @param id ゲームのインスタンスID
+ * @return フェーズ・ボードの大きさ・フラグ数・プレイヤー・経過時間を持つオブジェクト
+ */
+#[wasm_bindgen]
+pub fn get_game_summary(id: u32) -> Result<JsValue, JsValue> {
+    let json = with_game(id, |game| serde_json::to_string(&game.snapshot()))?
+        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+    js_sys::JSON::parse(&json)
+}
+
+/**
+ * ボードの状態を型付き配列で取得する
+ * 
+ * ソルバーや読み上げなどの外部ツールがボードを効率よく読み取るために使用します。
+ * `cells`は0〜8が周囲の地雷数、9が地雷、255が未開封のセルです。
+ * `revealed`と`flagged`は1がtrue、0がfalseです。
+ * 例: `const { width, cells } = wasm.get_board_state(id)`
+ * 
+ * @param id ゲームのインスタンスID
+ * @return width, height, mineCount, cells, revealed, flaggedを持つオブジェクト
+ */
+#[wasm_bindgen]
+pub fn get_board_state(id: u32) -> Result<JsValue, JsValue> {
+    with_game(id, |game| game.board_arrays())?.map_err(JsValue::from)
+}
+
+/**
+ * 最近サーバーと送受信したメッセージの記録をJSONで取得する
+ * 
+ * クライアントとサーバーの状態のずれを調べるために使用します。
+ * 直近の200件を、時刻（ミリ秒）・向き（"inbound"/"outbound"）・メッセージの組で古い順に返します。
+ * 例: `console.log(wasm.dump_network_log(id))`
+ * 
+ * @param id ゲームのインスタンスID
+ * @return time, direction, messageを持つ要素の配列のJSON
+ */
+#[wasm_bindgen]
+pub fn dump_network_log(id: u32) -> Result<String, JsValue> {
+    with_game(id, |game| game.network.traffic_log_json())
+}
+
 // パニックハンドラのセットアップ
-extern crate console_error_panic_hook; 
\ No newline at end of file
+extern crate console_error_panic_hook; 
+
+/**
+ * 現在のルームへの招待リンクを取得する
+ * 
+ * ホストページの「招待リンクをコピー」などのボタンから使用します。
+ * 例: `navigator.clipboard.writeText(wasm.get_invite_link(id))`
+ * 
+ * @param id ゲームのインスタンスID
+ * @return `#room=`付きのページのURL（ルームに参加していない場合はundefined）
+ */
+#[wasm_bindgen]
+pub fn get_invite_link(id: u32) -> Result<Option<String>, JsValue> {
+    with_game(id, |game| game.invite_link())
+}
+
+/**
+ * 現在のルームへの招待リンクをクリップボードにコピーする
+ * 
+ * 結果はゲーム画面の通知で表示します。
+ * 
+ * @param id ゲームのインスタンスID
+ */
+#[wasm_bindgen]
+pub fn copy_invite_link(id: u32) -> Result<(), JsValue> {
+    with_game(id, |game| game.copy_invite_link())
+}
+
+/**
+ * 盤面のシード（デイリーチャレンジの日番号）をクリップボードにコピーする
+ * 
+ * 結果はゲーム画面の通知で表示します。
+ * 
+ * @param id ゲームのインスタンスID
+ */
+#[wasm_bindgen]
+pub fn copy_seed(id: u32) -> Result<(), JsValue> {
+    with_game(id, |game| game.copy_seed())
+}
+
+/**
+ * ゲームの結果をクリップボードにコピーする
+ * 
+ * 結果はゲーム画面の通知で表示します。
+ * 
+ * @param id ゲームのインスタンスID
+ */
+#[wasm_bindgen]
+pub fn copy_result(id: u32) -> Result<(), JsValue> {
+    with_game(id, |game| game.copy_result())
+}
+
+/**
+ * キャンバスを囲む要素の全画面表示を切り替える
+ * 
+ * ホストページの全画面ボタンから使用します（Fキーでも切り替えられます）。
+ * 
+ * @param id ゲームのインスタンスID
+ * @return 成功した場合はOk(()), エラーの場合はErr(JsValue)
+ */
+#[wasm_bindgen]
+pub fn toggle_fullscreen(id: u32) -> Result<(), JsValue> {
+    with_game(id, |game| game.toggle_fullscreen())?.map_err(JsValue::from)
+}
+
+/**
+ * 難易度を変更する
+ * 
+ * ホストページが独自の設定UIを作るために使用します。
+ * 接続中であればボード設定をサーバーに送ります（開始前のゲームにのみ反映されます）。
+ * 例: `wasm.set_difficulty(id, "hard")`
+ * 
+ * @param id ゲームのインスタンスID
+ * @param level 難易度のキー（easy / medium / hard）
+ * @return 成功した場合はOk(()), 不明なキーの場合はErr(JsValue)
+ */
+#[wasm_bindgen]
+pub fn set_difficulty(id: u32, level: &str) -> Result<(), JsValue> {
+    with_game(id, |game| game.set_difficulty_by_key(level))?.map_err(JsValue::from)
+}
+
+/**
+ * テーマを変更して保存する
+ * 
+ * 例: `wasm.set_theme(id, "light")`
+ * 
+ * @param id ゲームのインスタンスID
+ * @param name テーマ名（"dark"など）
+ * @return 成功した場合はOk(()), 不明なテーマや未解放のテーマの場合はErr(JsValue)
+ */
+#[wasm_bindgen]
+pub fn set_theme(id: u32, name: &str) -> Result<(), JsValue> {
+    with_game(id, |game| game.set_theme_by_key(name))?.map_err(JsValue::from)
+}
+
+/**
+ * 練習中のボードにボットを追加する
+ * 
+ * 一人用の練習・デイリーチャレンジ・埋め込み用の最小表示の盤面で、練習相手として一緒に遊びます。
+ * 例: `wasm.add_bot(id, "solver")`
+ * 
+ * @param id ゲームのインスタンスID
+ * @param strategy 打ち方（"random"はランダムに開き、"solver"は数字から確定する手を優先する）
+ * @return 追加したボットのプレイヤーID、不明な打ち方や練習中でない場合はErr(JsValue)
+ */
+#[wasm_bindgen]
+pub fn add_bot(id: u32, strategy: &str) -> Result<String, JsValue> {
+    with_game(id, |game| game.add_bot_by_key(strategy))?.map_err(JsValue::from)
+}
+
+/**
+ * 全てのボットを取り除く
+ * 
+ * @param id ゲームのインスタンスID
+ * @return 成功した場合はOk(()), インスタンスが無い場合はErr(JsValue)
+ */
+#[wasm_bindgen]
+pub fn clear_bots(id: u32) -> Result<(), JsValue> {
+    with_game(id, |game| game.clear_bots())
+}
+
+/**
+ * ローカルのゲームをピアツーピアで公開し、参加者を1人招待する（ホスト側）
+ * 
+ * ゲームサーバーを使わずにWebRTCのデータチャネルで遊びます。
+ * 返したオファーのテキストをチャットなどで参加者に渡し、参加者のアンサーは`accept_peer_answer`で設定します。
+ * 例: `const offer = await wasm.create_peer_offer(id)`
+ * 
+ * @param id ゲームのインスタンスID
+ * @return オファーのテキストを返すPromise、ローカルのゲーム中でない場合はErr(JsValue)
+ */
+#[wasm_bindgen]
+pub fn create_peer_offer(id: u32) -> Result<js_sys::Promise, JsValue> {
+    let connection = with_game(id, |game| game.host_peer_game())?.map_err(JsValue::from)?;
+    Ok(wasm_bindgen_futures::future_to_promise(async move {
+        network::create_signal(&connection, true).await.map(JsValue::from)
+    }))
+}
+
+/**
+ * 招待した参加者のアンサーを設定する（ホスト側）
+ * 
+ * 例: `await wasm.accept_peer_answer(id, answer)`
+ * 
+ * @param id ゲームのインスタンスID
+ * @param answer 参加者が`join_peer_game`で作ったアンサーのテキスト
+ * @return 設定が終わると解決するPromise、アンサーを待っている参加者がいない場合はErr(JsValue)
+ */
+#[wasm_bindgen]
+pub fn accept_peer_answer(id: u32, answer: String) -> Result<js_sys::Promise, JsValue> {
+    let connection = with_game(id, |game| game.take_pending_peer())?
+        .ok_or_else(|| JsValue::from_str("no peer is waiting for an answer"))?;
+    Ok(wasm_bindgen_futures::future_to_promise(async move {
+        network::accept_signal(&connection, &answer, false).await.map(|_| JsValue::UNDEFINED)
+    }))
+}
+
+/**
+ * ホストのオファーを使ってピアツーピアのゲームに参加する（参加者側）
+ * 
+ * 返したアンサーのテキストをホストに渡すと、接続が開いてゲームが始まります。
+ * 例: `const answer = await wasm.join_peer_game(id, offer)`
+ * 
+ * @param id ゲームのインスタンスID
+ * @param offer ホストが`create_peer_offer`で作ったオファーのテキスト
+ * @return アンサーのテキストを返すPromise、接続を作れない場合はErr(JsValue)
+ */
+#[wasm_bindgen]
+pub fn join_peer_game(id: u32, offer: String) -> Result<js_sys::Promise, JsValue> {
+    let connection = with_game(id, |game| game.join_peer_game())?.map_err(JsValue::from)?;
+    Ok(wasm_bindgen_futures::future_to_promise(async move {
+        network::accept_signal(&connection, &offer, true).await?;
+        network::create_signal(&connection, false).await.map(JsValue::from)
+    }))
+}
+
+/**
+ * プレイヤー名を変更して保存する
+ * 
+ * 接続中であれば他のプレイヤーにも新しい名前を通知します。
+ * 例: `wasm.set_player_name(id, "えだ")`
+ * 
+ * @param id ゲームのインスタンスID
+ * @param name 新しいプレイヤー名
+ * @return 成功した場合はOk(()), エラーの場合はErr(JsValue)
+ */
+#[wasm_bindgen]
+pub fn set_player_name(id: u32, name: &str) -> Result<(), JsValue> {
+    with_game(id, |game| game.set_player_name(name))
+}
+
+/**
+ * 接続先のWebSocketサーバーを変更する
+ * 
+ * WASMを作り直さずに、デプロイ先ごとに別のサーバーを使うために使用します。
+ * 起動時に決まっている場合は`init_game`の`serverUrl`でも指定できます。
+ * 対戦中であれば新しいサーバーに接続し直します。
+ * 例: `wasm.set_server_url(id, "wss://example.com/ws")`
+ * 
+ * @param id ゲームのインスタンスID
+ * @param url `ws://`か`wss://`で始まるURL（undefinedの場合はページのホストから決める）
+ * @return 成功した場合はOk(()), 不正なURLの場合はErr(JsValue)
+ */
+#[wasm_bindgen]
+pub fn set_server_url(id: u32, url: Option<String>) -> Result<(), JsValue> {
+    with_game(id, |game| game.set_server_url(url.as_deref()))?.map_err(JsValue::from)
+}
+
+/**
+ * テレメトリーを受け取る関数を登録する
+ * 
+ * 登録すると、フレーム時間のパーセンタイル・再接続の回数・ゲームの所要時間を
+ * 1分ごとにまとめて渡します（プレイヤー名やIDは含みません）。
+ * 例: `wasm.set_telemetry_callback(id, (report) => console.log(report.frameTimeP95))`
+ * 
+ * @param id ゲームのインスタンスID
+ * @param callback 集計結果を受け取る関数（undefinedの場合は登録を解除する）
+ * @return 成功した場合はOk(()), エラーの場合はErr(JsValue)
+ */
+#[wasm_bindgen]
+pub fn set_telemetry_callback(id: u32, callback: Option<js_sys::Function>) -> Result<(), JsValue> {
+    with_game(id, |game| game.set_telemetry_callback(callback))
+}
+
+/**
+ * サーバーとの接続が確立したときに呼ぶ関数を登録する
+ * 
+ * 再接続で繋がり直した場合も呼びます。
+ * 例: `wasm.on_connected(id, () => showOnline())`
+ * 
+ * @param id ゲームのインスタンスID
+ * @param callback 呼ぶ関数（undefinedの場合は登録を解除する）
+ * @return 成功した場合はOk(()), エラーの場合はErr(JsValue)
+ */
+#[wasm_bindgen]
+pub fn on_connected(id: u32, callback: Option<js_sys::Function>) -> Result<(), JsValue> {
+    with_game(id, |game| game.connection_callbacks.on_connected = callback)
+}
+
+/**
+ * サーバーとの接続が切れたときに呼ぶ関数を登録する
+ * 
+ * 理由は"closed"（ゲームが閉じた）、"connection_lost"（予期しない切断）、
+ * "reconnect_failed"（再接続を諦めた）のいずれかです。
+ * 例: `wasm.on_disconnected(id, (reason) => analytics.track("disconnect", { reason }))`
+ * 
+ * @param id ゲームのインスタンスID
+ * @param callback 理由を受け取る関数（undefinedの場合は登録を解除する）
+ * @return 成功した場合はOk(()), エラーの場合はErr(JsValue)
+ */
+#[wasm_bindgen]
+pub fn on_disconnected(id: u32, callback: Option<js_sys::Function>) -> Result<(), JsValue> {
+    with_game(id, |game| game.connection_callbacks.on_disconnected = callback)
+}
+
+/**
+ * 切断後の再接続を予約したときに呼ぶ関数を登録する
+ * 
+ * 例: `wasm.on_reconnecting(id, (attempt) => showRetry(attempt))`
+ * 
+ * @param id ゲームのインスタンスID
+ * @param callback 何回目の再接続か（1から数える）を受け取る関数（undefinedの場合は登録を解除する）
+ * @return 成功した場合はOk(()), エラーの場合はErr(JsValue)
+ */
+#[wasm_bindgen]
+pub fn on_reconnecting(id: u32, callback: Option<js_sys::Function>) -> Result<(), JsValue> {
+    with_game(id, |game| game.connection_callbacks.on_reconnecting = callback)
+}
\ No newline at end of file
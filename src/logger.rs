@@ -0,0 +1,341 @@
+/**
+ * レベル付きのログ出力
+ * 
+ * `log_error!`〜`log_trace!`のマクロで出力し、呼び出したモジュールごとに出力するレベルを絞り込めます。
+ * 絞り込みは起動オプションの`log`に`"info,network=debug,game_state=warn"`の形式で指定します。
+ * 出力はブラウザのコンソールのレベル別のメソッドに送り、直近の記録はゲーム内のログパネル用に保持します。
+ */
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::fmt;
+use crate::utils::now_ms;
+
+/**
+ * ログパネル用に保持する記録の数
+ */
+pub const LOG_HISTORY_CAPACITY: usize = 50;
+
+/**
+ * ログのレベル（値が小さいほど重要）
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl LogLevel {
+    /**
+     * 設定の文字列からレベルを取得する
+     * 
+     * @param key レベル名（大文字・小文字は区別しない）
+     * @return レベル（不明な名前の場合はNone）
+     */
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key.to_ascii_lowercase().as_str() {
+            "error" => Some(LogLevel::Error),
+            "warn" | "warning" => Some(LogLevel::Warn),
+            "info" => Some(LogLevel::Info),
+            "debug" => Some(LogLevel::Debug),
+            "trace" => Some(LogLevel::Trace),
+            _ => None,
+        }
+    }
+
+    /**
+     * 表示用のレベル名
+     */
+    pub fn label(self) -> &'static str {
+        match self {
+            LogLevel::Error => "ERROR",
+            LogLevel::Warn => "WARN",
+            LogLevel::Info => "INFO",
+            LogLevel::Debug => "DEBUG",
+            LogLevel::Trace => "TRACE",
+        }
+    }
+}
+
+/**
+ * 出力するレベルの設定
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogConfig {
+    pub level: LogLevel,                  // モジュールの指定が無い場合のレベル
+    pub filters: Vec<(String, LogLevel)>, // モジュールのパス（`network`や`ecs::plugin`）ごとのレベル
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        Self { level: LogLevel::Info, filters: Vec::new() }
+    }
+}
+
+impl LogConfig {
+    /**
+     * `"info,network=debug"`の形式の設定を解析する
+     * 
+     * @param spec カンマ区切りの設定（モジュールを省略した項目は全体のレベル）
+     * @return 解析した設定（不明なレベルを含む場合はNone）
+     */
+    pub fn parse(spec: &str) -> Option<Self> {
+        let mut config = Self::default();
+        for part in spec.split(',').map(str::trim).filter(|part| !part.is_empty()) {
+            match part.split_once('=') {
+                Some((module, level)) => {
+                    config.filters.push((module.trim().to_string(), LogLevel::from_key(level.trim())?));
+                },
+                None => config.level = LogLevel::from_key(part)?,
+            }
+        }
+        Some(config)
+    }
+
+    /**
+     * モジュールのログを出力するかどうか
+     * 
+     * 最も長く一致するモジュールの指定を優先します。
+     * 
+     * @param level ログのレベル
+     * @param target クレート名を除いたモジュールのパス
+     * @return 出力する場合はtrue
+     */
+    pub fn enabled(&self, level: LogLevel, target: &str) -> bool {
+        let max_level = self.filters.iter()
+            .filter(|(module, _)| {
+                target.strip_prefix(module.as_str()).is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+            })
+            .max_by_key(|(module, _)| module.len())
+            .map_or(self.level, |&(_, level)| level);
+        level <= max_level
+    }
+}
+
+/**
+ * ログの1件
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogRecord {
+    pub level: LogLevel,       // レベル
+    pub target: &'static str,  // 出力したモジュール（クレート名を除く）
+    pub message: String,       // 本文
+    pub time: f64,             // 出力した時刻（ミリ秒）
+}
+
+impl fmt::Display for LogRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{} {}] {}", self.level.label(), self.target, self.message)
+    }
+}
+
+/**
+ * ロガーの状態
+ */
+#[derive(Default)]
+struct Logger {
+    config: LogConfig,            // 出力するレベルの設定
+    history: VecDeque<LogRecord>, // ログパネル用の直近の記録
+}
+
+thread_local! {
+    static LOGGER: RefCell<Logger> = RefCell::new(Logger::default());
+}
+
+/**
+ * 出力するレベルを設定する
+ * 
+ * 1つのページの全てのゲームで共有します。
+ * 
+ * @param config 出力するレベルの設定
+ */
+pub fn init(config: LogConfig) {
+    LOGGER.with(|logger| logger.borrow_mut().config = config);
+}
+
+/**
+ * モジュールのパスからクレート名を除く
+ */
+fn short_target(module_path: &'static str) -> &'static str {
+    module_path.split_once("::").map_or(module_path, |(_, rest)| rest)
+}
+
+/**
+ * ログを出力するかどうか
+ * 
+ * @param level ログのレベル
+ * @param module_path `module_path!()`の値
+ * @return 出力する場合はtrue
+ */
+pub fn enabled(level: LogLevel, module_path: &'static str) -> bool {
+    LOGGER.with(|logger| logger.borrow().config.enabled(level, short_target(module_path)))
+}
+
+/**
+ * ログを出力する（マクロから呼び出す）
+ * 
+ * @param level ログのレベル
+ * @param module_path `module_path!()`の値
+ * @param args 本文
+ */
+pub fn write(level: LogLevel, module_path: &'static str, args: fmt::Arguments) {
+    if !enabled(level, module_path) {
+        return;
+    }
+
+    let record = LogRecord { level, target: short_target(module_path), message: args.to_string(), time: now_ms() };
+    output(&record);
+    LOGGER.with(|logger| {
+        let history = &mut logger.borrow_mut().history;
+        if history.len() == LOG_HISTORY_CAPACITY {
+            history.pop_front();
+        }
+        history.push_back(record);
+    });
+}
+
+/**
+ * ログパネル用の直近の記録を取得する
+ * 
+ * @param count 取得する最大の件数
+ * @return 古い順の記録
+ */
+pub fn recent_records(count: usize) -> Vec<LogRecord> {
+    LOGGER.with(|logger| {
+        let history = &logger.borrow().history;
+        history.iter().skip(history.len().saturating_sub(count)).cloned().collect()
+    })
+}
+
+/**
+ * コンソールのレベル別のメソッドに出力する
+ */
+fn output(record: &LogRecord) {
+    #[cfg(target_arch = "wasm32")]
+    {
+        use web_sys::console;
+        let text = wasm_bindgen::JsValue::from_str(&format!("[{}] {}", record.target, record.message));
+        match record.level {
+            LogLevel::Error => console::error_1(&text),
+            LogLevel::Warn => console::warn_1(&text),
+            LogLevel::Info => console::info_1(&text),
+            LogLevel::Debug | LogLevel::Trace => console::debug_1(&text),
+        }
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    eprintln!("{}", record);
+}
+
+/**
+ * コンソールでまとめて表示するログのグループ
+ * 
+ * 作成するとグループを開き、破棄すると閉じます。レベルが出力対象でなければ何もしません。
+ */
+pub struct LogGroup {
+    open: bool, // グループを開いたかどうか
+}
+
+impl LogGroup {
+    /**
+     * 折りたたんだグループを開く（マクロから呼び出す）
+     * 
+     * @param level グループのレベル
+     * @param module_path `module_path!()`の値
+     * @param args グループの見出し
+     * @return グループ（破棄するまでのログがグループに入る）
+     */
+    pub fn open(level: LogLevel, module_path: &'static str, args: fmt::Arguments) -> Self {
+        let open = enabled(level, module_path);
+        if open {
+            #[cfg(target_arch = "wasm32")]
+            web_sys::console::group_collapsed_1(&wasm_bindgen::JsValue::from_str(&args.to_string()));
+            #[cfg(not(target_arch = "wasm32"))]
+            eprintln!("[{} {}] {}", level.label(), short_target(module_path), args);
+        }
+        Self { open }
+    }
+}
+
+impl Drop for LogGroup {
+    fn drop(&mut self) {
+        #[cfg(target_arch = "wasm32")]
+        if self.open {
+            web_sys::console::group_end();
+        }
+    }
+}
+
+/// エラーを出力する
+macro_rules! log_error {
+    ($($arg:tt)*) => { $crate::logger::write($crate::logger::LogLevel::Error, module_path!(), format_args!($($arg)*)) };
+}
+
+/// 警告を出力する
+macro_rules! log_warn {
+    ($($arg:tt)*) => { $crate::logger::write($crate::logger::LogLevel::Warn, module_path!(), format_args!($($arg)*)) };
+}
+
+/// 情報を出力する
+macro_rules! log_info {
+    ($($arg:tt)*) => { $crate::logger::write($crate::logger::LogLevel::Info, module_path!(), format_args!($($arg)*)) };
+}
+
+/// デバッグ用の情報を出力する
+macro_rules! log_debug {
+    ($($arg:tt)*) => { $crate::logger::write($crate::logger::LogLevel::Debug, module_path!(), format_args!($($arg)*)) };
+}
+
+/// 詳細な追跡用の情報を出力する
+macro_rules! log_trace {
+    ($($arg:tt)*) => { $crate::logger::write($crate::logger::LogLevel::Trace, module_path!(), format_args!($($arg)*)) };
+}
+
+/// 折りたたんだグループを開く（戻り値を破棄するまでのログがグループに入る）
+macro_rules! log_group {
+    ($level:ident, $($arg:tt)*) => { $crate::logger::LogGroup::open($crate::logger::LogLevel::$level, module_path!(), format_args!($($arg)*)) };
+}
+
+pub(crate) use {log_error, log_warn, log_info, log_debug, log_trace, log_group};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_filter_by_module() {
+        let config = LogConfig::parse("warn, network=debug ,ecs::plugin=error").unwrap();
+        assert_eq!(config.level, LogLevel::Warn);
+        assert!(config.enabled(LogLevel::Debug, "network"));
+        assert!(!config.enabled(LogLevel::Trace, "network"));
+        assert!(config.enabled(LogLevel::Warn, "ecs::bot"));
+        assert!(!config.enabled(LogLevel::Warn, "ecs::plugin"));
+
+        // 前方一致はモジュールの区切りでのみ判定する
+        assert!(!config.enabled(LogLevel::Debug, "network_state"));
+
+        assert_eq!(LogConfig::parse("info,board=loud"), None);
+        assert_eq!(LogConfig::parse(""), Some(LogConfig::default()));
+    }
+
+    #[test]
+    fn test_history_keeps_recent_records() {
+        init(LogConfig::parse("error,logger=debug").unwrap());
+        for index in 0..LOG_HISTORY_CAPACITY + 5 {
+            log_debug!("message {}", index);
+        }
+        log_trace!("hidden");
+        {
+            let _group = log_group!(Info, "group");
+            log_warn!("inside");
+        }
+
+        let records = recent_records(2);
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1].to_string(), "[WARN logger::tests] inside");
+        assert_eq!(records[0].message, format!("message {}", LOG_HISTORY_CAPACITY + 4));
+        assert_eq!(recent_records(usize::MAX).len(), LOG_HISTORY_CAPACITY);
+        init(LogConfig::default());
+    }
+}
\ No newline at end of file
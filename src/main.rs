@@ -2,10 +2,209 @@
  * メイン関数
  * 
  * このファイルはWASMをcrate-typeに含むため必要ですが、
- * 実際のエントリーポイントはlib.rsのstart_game関数です。
- * このmain関数はnative buildの際に呼び出されますが、
- * WebAssembly環境では使われません。
+ * ブラウザでの実際のエントリーポイントはlib.rsのstart_game関数です。
+ * native buildではブラウザを使わないシミュレーションを端末で実行します。
+ * 
+ * 使い方:
+ *   cargo run -- [--width N] [--height N] [--mines N] [--seed N]
+ *                [--bots solver,random] [--games N] [--quiet] [--interactive]
+ * 
+ * 対話モードでは`r X Y`で開く、`f X Y`でフラグ、`s [N]`でボットをNステップ進める、`q`で終了です。
  */
+use std::io::{self, BufRead, Write};
+use std::time::Instant;
+use wasm_multiplayer::resources::BotStrategy;
+use wasm_multiplayer::simulation::{RevealOutcome, Simulation, SimulationConfig};
+
+/// 1ゲームで進める最大のステップ数
+const MAX_STEPS: usize = 100_000;
+
+/**
+ * コマンドライン引数
+ */
+struct Options {
+    config: SimulationConfig, // シミュレーションの設定
+    games: usize,             // 続けて実行するゲーム数
+    quiet: bool,              // ゲームごとの盤面を表示しないかどうか
+    interactive: bool,        // 標準入力から操作するかどうか
+}
+
 fn main() {
-    println!("Hello, world!");
+    let options = match parse_args(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{}", message);
+            std::process::exit(2);
+        },
+    };
+
+    if options.interactive {
+        if let Err(error) = run_interactive(&options.config) {
+            eprintln!("入力エラー: {}", error);
+            std::process::exit(1);
+        }
+    } else {
+        run_scripted(&options);
+    }
+}
+
+/**
+ * コマンドライン引数を解析する
+ * 
+ * @param args プログラム名を除いた引数
+ * @return 解析した引数、不正な引数の場合はErr(メッセージ)
+ */
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options {
+        config: SimulationConfig::default(),
+        games: 1,
+        quiet: false,
+        interactive: false,
+    };
+
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or_else(|| format!("{} には値が必要です", name));
+        match arg.as_str() {
+            "--width" => options.config.width = parse_number(&arg, &value(&arg)?)?,
+            "--height" => options.config.height = parse_number(&arg, &value(&arg)?)?,
+            "--mines" => options.config.mines = parse_number(&arg, &value(&arg)?)?,
+            "--seed" => options.config.seed = parse_number(&arg, &value(&arg)?)?,
+            "--games" => options.games = parse_number(&arg, &value(&arg)?)?,
+            "--bots" => {
+                options.config.bots = value(&arg)?
+                    .split(',')
+                    .filter(|key| !key.is_empty())
+                    .map(|key| BotStrategy::from_key(key).ok_or_else(|| format!("不明なボット: {}", key)))
+                    .collect::<Result<_, _>>()?;
+            },
+            "--quiet" => options.quiet = true,
+            "--interactive" => options.interactive = true,
+            _ => return Err(format!("不明な引数: {}", arg)),
+        }
+    }
+
+    if options.config.width == 0 || options.config.height == 0 {
+        return Err("ボードの幅と高さは1以上にしてください".to_string());
+    }
+    Ok(options)
+}
+
+/**
+ * 数値の引数を解析する
+ */
+fn parse_number<T: std::str::FromStr>(name: &str, value: &str) -> Result<T, String> {
+    value.parse().map_err(|_| format!("{} の値が不正です: {}", name, value))
+}
+
+/**
+ * ボットだけで指定数のゲームを実行し、結果を表示する
+ * 
+ * ゲームごとにシードを1ずつずらします。
+ * 
+ * @param options コマンドライン引数
+ */
+fn run_scripted(options: &Options) {
+    if options.config.bots.is_empty() {
+        eprintln!("ボットがいないため、ゲームが進みません（--bots を指定してください）");
+        return;
+    }
+
+    let started = Instant::now();
+    let mut wins = 0;
+    for game in 0..options.games {
+        let config = SimulationConfig { seed: options.config.seed.wrapping_add(game as u64), ..options.config.clone() };
+        let mut simulation = Simulation::new(&config);
+        let result = simulation.run(MAX_STEPS);
+        if result.win {
+            wins += 1;
+        }
+        if !options.quiet {
+            println!(
+                "game {} (seed {}): {} / {} steps, {} moves",
+                game + 1, config.seed,
+                if result.win { "win" } else if simulation.is_over() { "lose" } else { "unfinished" },
+                result.steps, result.moves
+            );
+            println!("{}", simulation.render(true));
+        }
+    }
+
+    let elapsed = started.elapsed();
+    println!(
+        "{} games: {} wins ({:.1}%), {:.2} ms",
+        options.games, wins,
+        if options.games == 0 { 0.0 } else { wins as f64 * 100.0 / options.games as f64 },
+        elapsed.as_secs_f64() * 1000.0
+    );
 }
+
+/**
+ * 標準入力のコマンドでゲームを操作する
+ * 
+ * @param config シミュレーションの設定
+ * @return 入出力のエラー
+ */
+fn run_interactive(config: &SimulationConfig) -> io::Result<()> {
+    let mut simulation = Simulation::new(config);
+    let stdin = io::stdin();
+    let mut stdout = io::stdout();
+    println!("{}", simulation.render(false));
+
+    loop {
+        print!("> ");
+        stdout.flush()?;
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line)? == 0 {
+            return Ok(());
+        }
+
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let position = || -> Option<usize> {
+            let x: usize = words.get(1)?.parse().ok()?;
+            let y: usize = words.get(2)?.parse().ok()?;
+            (x < simulation.width() && y < simulation.height()).then_some(y * simulation.width() + x)
+        };
+        match words.first().copied() {
+            Some("r") => match position() {
+                Some(index) => {
+                    if simulation.reveal(index) == RevealOutcome::Ignored {
+                        println!("そのセルは開けません");
+                    }
+                },
+                None => println!("使い方: r X Y"),
+            },
+            Some("f") => match position() {
+                Some(index) => {
+                    if !simulation.toggle_flag(index) {
+                        println!("そのセルにはフラグを立てられません");
+                    }
+                },
+                None => println!("使い方: f X Y"),
+            },
+            Some("s") => {
+                let steps = words.get(1).and_then(|count| count.parse().ok()).unwrap_or(1);
+                for _ in 0..steps {
+                    if simulation.is_over() {
+                        break;
+                    }
+                    simulation.step();
+                }
+            },
+            Some("q") => return Ok(()),
+            _ => {
+                println!("コマンド: r X Y（開く） / f X Y（フラグ） / s [N]（進める） / q（終了）");
+                continue;
+            },
+        }
+
+        // 勝利の判定はシステムの実行時に行う
+        if !simulation.is_over() {
+            simulation.step();
+        }
+        println!("{}", simulation.render(simulation.is_over()));
+        if simulation.is_over() {
+            println!("{}", if simulation.is_win() { "クリア！" } else { "ゲームオーバー" });
+            return Ok(());
+        }
+    }
+}
\ No newline at end of file
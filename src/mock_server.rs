@@ -0,0 +1,1222 @@
+/**
+ * テスト用の模擬サーバー
+ * 
+ * server.jsと同じ規則で、同じメッセージの型（`ClientMessage` / `ServerMessage`）を
+ * JSONの文字列でやり取りするサーバーをRustで実装します。
+ * `NetworkManager::connect_mock`で接続すると、WebSocketの代わりにこのサーバーと通信するため、
+ * 参加・同じセルの取り合い・切断と再接続などの対戦の流れを`cargo test`で確かめられます。
+ * 
+ * テストを決定的にするため、地雷の配置は作成時に指定し（全てのルームで同じ配置を使う）、
+ * 空になったルームの片付けは行いません。開始前のカウントダウンは`with_countdown`で有効にした場合のみ行い、
+ * 時刻が進まないため`finish_countdown`を呼ぶまで終わりません。
+ */
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::rc::Rc;
+use crate::error::{GameError, GameResult};
+use crate::protocol::{ClientMessage, ServerMessage, PlayerInfo, LobbyPlayerInfo, GameStateSnapshot, RoomInfo};
+use crate::binary_protocol::encode_server_message;
+use crate::network::Frame;
+use crate::room::normalize_room_code;
+use crate::resources::is_known_avatar;
+
+/// 接続したプレイヤーに割り当てるカーソルの色（接続順に使う）
+const PLAYER_COLORS: [&str; 4] = ["#E53935", "#1E88E5", "#43A047", "#FB8C00"];
+
+/// 接続直後のプレイヤーが参加するルーム（server.jsの`DEFAULT_ROOM`と同じ）
+pub const DEFAULT_ROOM: &str = "main";
+
+/**
+ * 接続中のプレイヤー
+ */
+struct MockPlayer {
+    id: String,         // プレイヤーID
+    name: String,       // プレイヤー名
+    color: String,      // カーソルの色
+    avatar: String,     // アバター（未設定なら空文字列）
+    ready: bool,        // ロビーで準備完了かどうか
+    x: f64,             // X座標
+    y: f64,             // Y座標
+    binary: bool,       // ボードの更新をバイナリ形式で受け取るかどうか
+    inbox: Vec<Frame>,  // まだ受け取っていないサーバーからのメッセージ
+}
+
+/**
+ * 模擬サーバーの状態
+ */
+struct MockState {
+    width: usize,                     // 新しいルームのボードの幅
+    height: usize,                    // 新しいルームのボードの高さ
+    mines: Vec<usize>,                // 新しいルームの地雷の位置
+    rooms: BTreeMap<String, MockGame>, // ルームコードごとのゲーム
+    next_player_id: u32,              // 次に割り当てるプレイヤーIDの番号
+    next_room_id: u32,                // 次に作成するルームコードの番号
+    countdown_ms: f64,                // 新しいルームの開始前のカウントダウンの長さ（0なら行わない）
+}
+
+/**
+ * 模擬サーバーの1つのルームのゲーム状態
+ */
+struct MockGame {
+    width: usize,             // ボードの幅
+    height: usize,            // ボードの高さ
+    cells: Vec<i32>,          // セルの値（地雷は-1）
+    revealed: Vec<bool>,      // 開かれているかどうか
+    flagged: Vec<bool>,       // フラグが立っているかどうか
+    game_started: bool,       // 最初のセルが開かれたかどうか
+    game_over: bool,          // ゲームが終了したかどうか
+    win: bool,                // 勝利したかどうか
+    lobby_active: bool,       // ロビー中かどうか（この間はボードを操作できない）
+    countdown_ms: f64,        // 開始前のカウントダウンの長さ（0なら行わない）
+    counting_down: bool,      // カウントダウン中かどうか（この間はボードを操作できない）
+    host_id: Option<String>,  // ホストのプレイヤーID
+    players: Vec<MockPlayer>, // 参加中のプレイヤー（参加順）
+    revision: u32,            // ボードの版数（ボードを変えるメッセージごとに増える）
+    sync_requests: usize,     // 受け付けた`sync_request`の数
+}
+
+/**
+ * テスト用の模擬サーバー（複製しても同じサーバーを指す）
+ */
+#[derive(Clone)]
+pub struct MockServer {
+    state: Rc<RefCell<MockState>>,
+}
+
+/**
+ * 模擬サーバーへの1つの接続
+ */
+pub struct MockConnection {
+    server: MockServer, // 接続先のサーバー
+    player_id: String,  // サーバーが割り当てたプレイヤーID
+}
+
+impl MockServer {
+    /**
+     * 地雷の配置を指定してサーバーを作成する
+     * 
+     * @param width ボードの幅
+     * @param height ボードの高さ
+     * @param mines 地雷を置くセルのインデックス
+     * @return ロビー中のサーバー
+     */
+    pub fn new(width: usize, height: usize, mines: &[usize]) -> Self {
+        let mut rooms = BTreeMap::new();
+        rooms.insert(DEFAULT_ROOM.to_string(), MockGame::new(width, height, mines));
+        Self {
+            state: Rc::new(RefCell::new(MockState {
+                width,
+                height,
+                mines: mines.to_vec(),
+                rooms,
+                next_player_id: 1,
+                next_room_id: 1,
+                countdown_ms: 0.0,
+            })),
+        }
+    }
+
+    /**
+     * ロビーを終えてから開始するまでのカウントダウンを有効にする
+     * 
+     * @param countdown_ms カウントダウンの長さ（ミリ秒）
+     * @return 全てのルームでカウントダウンを行うサーバー
+     */
+    pub fn with_countdown(self, countdown_ms: f64) -> Self {
+        {
+            let mut state = self.state.borrow_mut();
+            state.countdown_ms = countdown_ms;
+            for game in state.rooms.values_mut() {
+                game.countdown_ms = countdown_ms;
+            }
+        }
+        self
+    }
+
+    /**
+     * サーバーに接続する
+     * 
+     * 接続したプレイヤーを既定のルームに参加させて初期化メッセージを送り、他のプレイヤーに参加を通知します。
+     * 
+     * @param resume 引き継ぐプレイヤーID（接続中のプレイヤーと重なる場合は新しいIDにする）
+     * @param binary ボードの更新をバイナリ形式で受け取るかどうか
+     * @return 接続
+     */
+    pub fn connect(&self, resume: Option<&str>, binary: bool) -> MockConnection {
+        let mut state = self.state.borrow_mut();
+        let resumable = resume.filter(|id| state.room_of(id).is_none());
+        let player_id = match resumable {
+            Some(id) => {
+                // 引き継いだIDを後から新しいプレイヤーに割り当てないよう、次の番号をその先に進める
+                if let Some(number) = id.strip_prefix("player_").and_then(|number| number.parse::<u32>().ok()) {
+                    state.next_player_id = state.next_player_id.max(number + 1);
+                }
+                id.to_string()
+            },
+            None => loop {
+                state.next_player_id += 1;
+                let id = format!("player_{}", state.next_player_id - 1);
+                if state.room_of(&id).is_none() {
+                    break id;
+                }
+            },
+        };
+        let game = state.rooms.get_mut(DEFAULT_ROOM).expect("default room");
+        let color = PLAYER_COLORS[game.players.len() % PLAYER_COLORS.len()].to_string();
+        
+        let player = MockPlayer {
+            id: player_id.clone(),
+            name: String::new(),
+            color,
+            avatar: String::new(),
+            ready: false,
+            x: 0.0,
+            y: 0.0,
+            binary,
+            inbox: Vec::new(),
+        };
+        game.enter(player, |game, id| ServerMessage::Init {
+            player_id: id.to_string(),
+            players: game.player_infos(),
+            game_state: game.snapshot(),
+        });
+        
+        MockConnection { server: self.clone(), player_id }
+    }
+
+    /**
+     * 既定のルームに参加中のプレイヤーID（参加順）
+     */
+    pub fn player_ids(&self) -> Vec<String> {
+        self.room_player_ids(DEFAULT_ROOM)
+    }
+
+    /**
+     * ルームに参加中のプレイヤーID（参加順、ルームが無ければ空）
+     */
+    pub fn room_player_ids(&self, room: &str) -> Vec<String> {
+        self.state.borrow().rooms.get(room)
+            .map(|game| game.players.iter().map(|player| player.id.clone()).collect())
+            .unwrap_or_default()
+    }
+
+    /**
+     * 既定のルームのホストのプレイヤーID
+     */
+    pub fn host_id(&self) -> Option<String> {
+        self.state.borrow().rooms[DEFAULT_ROOM].host_id.clone()
+    }
+
+    /**
+     * 通信の途絶を模してプレイヤーの接続をサーバー側から切る
+     * 
+     * 他のプレイヤーには通常の切断と同じく退出を通知します。
+     * 
+     * @param player_id 切断するプレイヤーのID
+     */
+    pub fn drop_connection(&self, player_id: &str) {
+        self.disconnect(player_id);
+    }
+
+    /**
+     * 既定のルームがロビー中かどうか
+     */
+    pub fn lobby_active(&self) -> bool {
+        self.state.borrow().rooms[DEFAULT_ROOM].lobby_active
+    }
+
+    /**
+     * 既定のルームがカウントダウン中かどうか
+     */
+    pub fn counting_down(&self) -> bool {
+        self.state.borrow().rooms[DEFAULT_ROOM].counting_down
+    }
+
+    /**
+     * 既定のルームのカウントダウンを終えてボードを操作できるようにする
+     */
+    pub fn finish_countdown(&self) {
+        self.state.borrow_mut().rooms.get_mut(DEFAULT_ROOM).expect("default room").counting_down = false;
+    }
+
+    /**
+     * 既定のルームが受け付けた`sync_request`の数
+     */
+    pub fn sync_requests(&self) -> usize {
+        self.state.borrow().rooms[DEFAULT_ROOM].sync_requests
+    }
+
+    /**
+     * 既定のルームの現在のゲーム状態
+     */
+    pub fn snapshot(&self) -> GameStateSnapshot {
+        self.room_snapshot(DEFAULT_ROOM).expect("default room")
+    }
+
+    /**
+     * ルームの現在のゲーム状態（ルームが無ければNone）
+     */
+    pub fn room_snapshot(&self, room: &str) -> Option<GameStateSnapshot> {
+        self.state.borrow().rooms.get(room).map(MockGame::snapshot)
+    }
+
+    /**
+     * クライアントから届いたメッセージを処理する
+     * 
+     * @param player_id 送信したプレイヤーのID
+     * @param text メッセージのJSON
+     * @return 成功した場合はOk(()), 解釈できない場合はErr(GameError)
+     */
+    fn receive(&self, player_id: &str, text: &str) -> GameResult<()> {
+        let message: ClientMessage = serde_json::from_str(text).map_err(|e| GameError::Network(e.to_string()))?;
+        let mut state = self.state.borrow_mut();
+        let Some(room) = state.room_of(player_id) else {
+            return Err(GameError::Network(format!("{} is not connected", player_id)));
+        };
+        
+        // ルームを移る・一覧を返すメッセージはサーバー全体で扱う
+        match message {
+            ClientMessage::JoinRoom { room } => {
+                if normalize_room_code(&room).as_deref() == Some(room.as_str()) {
+                    state.switch_room(player_id, &room);
+                }
+                return Ok(());
+            },
+            ClientMessage::CreateRoom => {
+                let room = format!("ROOM{}", state.next_room_id);
+                state.next_room_id += 1;
+                state.switch_room(player_id, &room);
+                return Ok(());
+            },
+            ClientMessage::ListRooms => {
+                let rooms = state.room_list();
+                if let Some(game) = state.rooms.get_mut(&room) {
+                    game.send_to(player_id, &ServerMessage::RoomList { rooms });
+                }
+                return Ok(());
+            },
+            ClientMessage::Leave => {
+                state.rooms.get_mut(&room).expect("room of a connected player").leave(player_id);
+                return Ok(());
+            },
+            ClientMessage::SetBoardConfig { board_width, board_height, mine_count: _ } => {
+                // 新しいボードの地雷は地雷の数によらず、作成時に指定した配置のうちボードに収まるもの
+                let mines: Vec<usize> = state.mines.iter().copied().filter(|&mine| mine < board_width * board_height).collect();
+                state.rooms.get_mut(&room).expect("room of a connected player").set_board_config(board_width, board_height, &mines);
+                return Ok(());
+            },
+            _ => {},
+        }
+        
+        let game = state.rooms.get_mut(&room).expect("room of a connected player");
+        match message {
+            ClientMessage::PlayerMove { x, y } => {
+                if let Some(player) = game.player_mut(player_id) {
+                    player.x = x;
+                    player.y = y;
+                }
+                game.broadcast_except(player_id, &ServerMessage::PlayerMoved { id: player_id.to_string(), x, y });
+            },
+            ClientMessage::RevealCell { index } => game.reveal(player_id, index),
+            ClientMessage::ToggleFlag { index } => {
+                // 開いたセルやゲームオーバー後はボードが変わらないので通知しない
+                if game.lobby_active || game.counting_down || index >= game.cells.len() || game.revealed[index] || game.game_over {
+                    return Ok(());
+                }
+                game.flagged[index] = !game.flagged[index];
+                let flagged = game.flagged[index];
+                game.revision += 1;
+                game.broadcast(&ServerMessage::FlagToggled { player_id: player_id.to_string(), revision: game.revision, index, flagged });
+            },
+            ClientMessage::Join { name, color, avatar } => {
+                if let Some(player) = game.player_mut(player_id) {
+                    player.name = name.clone();
+                    player.color = color.clone();
+                }
+                game.broadcast_except(player_id, &ServerMessage::PlayerRenamed { id: player_id.to_string(), name });
+                game.broadcast_except(player_id, &ServerMessage::PlayerRecolored { id: player_id.to_string(), color });
+                
+                // 候補に無いアバターは受け付けない
+                if is_known_avatar(&avatar) {
+                    if let Some(player) = game.player_mut(player_id) {
+                        player.avatar = avatar.clone();
+                    }
+                    game.broadcast_except(player_id, &ServerMessage::PlayerAvatar { id: player_id.to_string(), avatar });
+                }
+                game.broadcast_lobby_state();
+            },
+            ClientMessage::SetReady { ready } if game.lobby_active => {
+                if let Some(player) = game.player_mut(player_id) {
+                    player.ready = ready;
+                }
+                if game.players.iter().all(|player| player.ready) {
+                    game.close_lobby();
+                }
+                game.broadcast_lobby_state();
+            },
+            ClientMessage::StartGame if game.lobby_active && game.host_id.as_deref() == Some(player_id) => {
+                game.close_lobby();
+                game.broadcast_lobby_state();
+            },
+            ClientMessage::SyncRequest => {
+                game.sync_requests += 1;
+                let sync = ServerMessage::StateSync {
+                    players: game.player_infos_except(player_id),
+                    game_state: game.snapshot(),
+                };
+                game.send_to(player_id, &sync);
+            },
+            ClientMessage::Heartbeat { sent_at } => {
+                game.send_to(player_id, &ServerMessage::HeartbeatAck { sent_at });
+            },
+            // ランキング・エモート・再戦・ボード設定は対戦の流れのテストでは扱わない
+            _ => {},
+        }
+        Ok(())
+    }
+
+    /**
+     * プレイヤーを切断する
+     * 
+     * 参加していたルームの他のプレイヤーに退出を通知し、ホストが抜けた場合は次のプレイヤーに引き継ぎます。
+     * 
+     * @param player_id 切断するプレイヤーのID
+     */
+    fn disconnect(&self, player_id: &str) {
+        let mut state = self.state.borrow_mut();
+        if let Some(room) = state.room_of(player_id) {
+            state.rooms.get_mut(&room).expect("room of a connected player").leave(player_id);
+        }
+    }
+}
+
+impl MockState {
+    /**
+     * プレイヤーが参加しているルームのコード
+     */
+    fn room_of(&self, player_id: &str) -> Option<String> {
+        self.rooms.iter()
+            .find(|(_, game)| game.player(player_id).is_some())
+            .map(|(code, _)| code.clone())
+    }
+
+    /**
+     * プレイヤーを別のルームに移す（無ければ作成する、server.jsの`switchRoom`と同じ規則）
+     * 
+     * 移った本人には`room_joined`と`state_sync`を送り、両方のルームの他のプレイヤーに出入りを通知します。
+     * 
+     * @param player_id 移るプレイヤーのID
+     * @param room 移り先のルームコード
+     */
+    fn switch_room(&mut self, player_id: &str, room: &str) {
+        let Some(current) = self.room_of(player_id) else {
+            return;
+        };
+        let joined = ServerMessage::RoomJoined { room: room.to_string() };
+        if current == room {
+            self.rooms.get_mut(room).expect("current room").send_to(player_id, &joined);
+            return;
+        }
+        
+        let Some(mut player) = self.rooms.get_mut(&current).and_then(|game| game.leave(player_id)) else {
+            return;
+        };
+        player.push(&joined);
+        let (width, height, mines, countdown_ms) = (self.width, self.height, &self.mines, self.countdown_ms);
+        let game = self.rooms.entry(room.to_string()).or_insert_with(|| MockGame {
+            countdown_ms,
+            ..MockGame::new(width, height, mines)
+        });
+        game.enter(player, |game, id| ServerMessage::StateSync {
+            players: game.player_infos_except(id),
+            game_state: game.snapshot(),
+        });
+    }
+
+    /**
+     * `list_rooms`に返すルーム一覧（既定のルームと空のルームは含めない）
+     */
+    fn room_list(&self) -> Vec<RoomInfo> {
+        self.rooms.iter()
+            .filter(|(code, game)| code.as_str() != DEFAULT_ROOM && !game.players.is_empty())
+            .map(|(code, game)| RoomInfo {
+                code: code.clone(),
+                players: game.players.len(),
+                board_width: game.width,
+                board_height: game.height,
+                mine_count: game.cells.iter().filter(|&&cell| cell == -1).count(),
+                in_lobby: game.lobby_active,
+            })
+            .collect()
+    }
+
+    fn player(&self, id: &str) -> Option<&MockPlayer> {
+        self.rooms.values().find_map(|game| game.player(id))
+    }
+
+    fn player_mut(&mut self, id: &str) -> Option<&mut MockPlayer> {
+        self.rooms.values_mut().find_map(|game| game.player_mut(id))
+    }
+}
+
+impl MockGame {
+    /**
+     * 地雷の配置を指定してロビー中のゲームを作成する
+     */
+    fn new(width: usize, height: usize, mines: &[usize]) -> Self {
+        let mut cells = vec![0; width * height];
+        for &mine in mines {
+            cells[mine] = -1;
+        }
+        for index in 0..cells.len() {
+            if cells[index] != -1 {
+                cells[index] = neighbors(width, height, index).filter(|&neighbor| cells[neighbor] == -1).count() as i32;
+            }
+        }
+        
+        Self {
+            width,
+            height,
+            revealed: vec![false; cells.len()],
+            flagged: vec![false; cells.len()],
+            cells,
+            game_started: false,
+            game_over: false,
+            win: false,
+            lobby_active: true,
+            countdown_ms: 0.0,
+            counting_down: false,
+            host_id: None,
+            players: Vec::new(),
+            revision: 0,
+            sync_requests: 0,
+        }
+    }
+
+    /**
+     * ロビーを終え、有効な場合はカウントダウンを始めて全員に通知する
+     */
+    fn close_lobby(&mut self) {
+        self.lobby_active = false;
+        if self.countdown_ms > 0.0 {
+            self.counting_down = true;
+            self.broadcast(&ServerMessage::Countdown { ends_at: Some(self.countdown_ms), server_time: 0.0 });
+        }
+    }
+
+    /**
+     * ボードを作り直して全員に通知する（server.jsの`set_board_config`と同じ規則）
+     * 
+     * ロビー中のみ受け付け、カウントダウン中やゲーム開始後は何もしません。
+     * 
+     * @param width ボードの幅
+     * @param height ボードの高さ
+     * @param mines 地雷を置くセルのインデックス
+     */
+    fn set_board_config(&mut self, width: usize, height: usize, mines: &[usize]) {
+        if self.game_started || !self.lobby_active || self.counting_down {
+            return;
+        }
+        let board = MockGame::new(width, height, mines);
+        self.width = board.width;
+        self.height = board.height;
+        self.cells = board.cells;
+        self.revealed = board.revealed;
+        self.flagged = board.flagged;
+        self.revision = 0;
+        self.broadcast(&ServerMessage::GameReset {
+            board_width: width,
+            board_height: height,
+            mine_count: mines.len(),
+        });
+    }
+
+    /**
+     * プレイヤーをルームに参加させる
+     * 
+     * `welcome`で作ったメッセージ（参加前のルームの状態）を本人に送ってから、他のプレイヤーに参加を通知します。
+     * 
+     * @param player 参加するプレイヤー
+     * @param welcome ルームとプレイヤーIDから本人に送るメッセージを作る関数
+     */
+    fn enter(&mut self, mut player: MockPlayer, welcome: impl FnOnce(&MockGame, &str) -> ServerMessage) {
+        let id = player.id.clone();
+        let color = player.color.clone();
+        player.ready = false;
+        player.push(&welcome(self, &id));
+        self.players.push(player);
+        self.broadcast_except(&id, &ServerMessage::PlayerJoined { id: id.clone(), color });
+        
+        if self.host_id.is_none() {
+            self.host_id = Some(id);
+        }
+        self.broadcast_lobby_state();
+    }
+
+    /**
+     * プレイヤーをルームから外す
+     * 
+     * 他のプレイヤーに退出を通知し、ホストが抜けた場合は次のプレイヤーに引き継ぎます。
+     * 
+     * @param player_id 外すプレイヤーのID
+     * @return 外したプレイヤー（参加していなければNone）
+     */
+    fn leave(&mut self, player_id: &str) -> Option<MockPlayer> {
+        let position = self.players.iter().position(|player| player.id == player_id)?;
+        let player = self.players.remove(position);
+        self.broadcast(&ServerMessage::PlayerLeft { id: player_id.to_string() });
+        if self.host_id.as_deref() == Some(player_id) {
+            self.host_id = self.players.first().map(|player| player.id.clone());
+        }
+        if self.lobby_active {
+            self.broadcast_lobby_state();
+        }
+        Some(player)
+    }
+
+    fn player(&self, id: &str) -> Option<&MockPlayer> {
+        self.players.iter().find(|player| player.id == id)
+    }
+
+    fn player_mut(&mut self, id: &str) -> Option<&mut MockPlayer> {
+        self.players.iter_mut().find(|player| player.id == id)
+    }
+
+    /**
+     * 接続中の全てのプレイヤーの情報
+     */
+    fn player_infos(&self) -> Vec<PlayerInfo> {
+        self.players.iter().map(|player| PlayerInfo {
+            id: player.id.clone(),
+            name: player.name.clone(),
+            x: player.x,
+            y: player.y,
+            color: player.color.clone(),
+            avatar: player.avatar.clone(),
+        }).collect()
+    }
+
+    /**
+     * 指定したプレイヤー以外の情報
+     */
+    fn player_infos_except(&self, id: &str) -> Vec<PlayerInfo> {
+        self.player_infos().into_iter().filter(|player| player.id != id).collect()
+    }
+
+    /**
+     * 開かれているセルの値のみを含むゲーム状態
+     */
+    fn snapshot(&self) -> GameStateSnapshot {
+        GameStateSnapshot {
+            board_width: self.width,
+            board_height: self.height,
+            mine_count: self.cells.iter().filter(|&&cell| cell == -1).count(),
+            revealed: self.revealed.clone(),
+            flagged: self.flagged.clone(),
+            game_started: self.game_started,
+            game_over: self.game_over,
+            win: self.win,
+            cell_values: self.revealed_values((0..self.cells.len()).filter(|&index| self.revealed[index])),
+            countdown_ends_at: self.counting_down.then_some(self.countdown_ms),
+            server_time: 0.0,
+            revision: self.revision,
+        }
+    }
+
+    /**
+     * セルのインデックスの文字列と値の組を作る
+     */
+    fn revealed_values(&self, indices: impl Iterator<Item = usize>) -> HashMap<String, i32> {
+        indices.map(|index| (index.to_string(), self.cells[index])).collect()
+    }
+
+    /**
+     * セルを開いて全員に通知する（server.jsの`reveal_cell`と同じ規則）
+     * 
+     * 開かれているセルやフラグのセルは開かず、空のリストを通知します。
+     */
+    fn reveal(&mut self, player_id: &str, index: usize) {
+        if self.lobby_active || self.counting_down || index >= self.cells.len() || self.game_over {
+            return;
+        }
+        self.game_started = true;
+        
+        // 開いたセルが無い場合はボードが変わらないので通知しない
+        let cells: Vec<(usize, i32)> = self.reveal_from(index).into_iter().map(|cell| (cell, self.cells[cell])).collect();
+        if cells.is_empty() {
+            return;
+        }
+        self.revision += 1;
+        self.broadcast(&ServerMessage::CellsRevealed { player_id: player_id.to_string(), revision: self.revision, cells });
+        
+        if self.game_over {
+            let all_cell_values = self.revealed_values(0..self.cells.len());
+            self.broadcast(&ServerMessage::GameOver { win: self.win, cells: self.cells.clone(), all_cell_values });
+        }
+    }
+
+    /**
+     * 0のセルから周囲に広げながら開く
+     * 
+     * @return 開いたセルのインデックス
+     */
+    fn reveal_from(&mut self, index: usize) -> Vec<usize> {
+        if self.revealed[index] || self.flagged[index] || self.game_over {
+            return Vec::new();
+        }
+        
+        let mut opened = Vec::new();
+        let mut stack = vec![index];
+        while let Some(current) = stack.pop() {
+            if self.revealed[current] || self.flagged[current] {
+                continue;
+            }
+            self.revealed[current] = true;
+            opened.push(current);
+            
+            if self.cells[current] == -1 {
+                self.game_over = true;
+                return opened;
+            }
+            if self.cells[current] == 0 {
+                stack.extend(neighbors(self.width, self.height, current).filter(|&neighbor| !self.revealed[neighbor]));
+            }
+        }
+        
+        // 地雷以外を全て開いたら勝利
+        if (0..self.cells.len()).all(|cell| self.cells[cell] == -1 || self.revealed[cell]) {
+            self.win = true;
+            self.game_over = true;
+        }
+        opened
+    }
+
+    fn send_to(&mut self, id: &str, message: &ServerMessage) {
+        if let Some(player) = self.player_mut(id) {
+            player.push(message);
+        }
+    }
+
+    fn broadcast(&mut self, message: &ServerMessage) {
+        for player in &mut self.players {
+            player.push(message);
+        }
+    }
+
+    fn broadcast_except(&mut self, id: &str, message: &ServerMessage) {
+        for player in self.players.iter_mut().filter(|player| player.id != id) {
+            player.push(message);
+        }
+    }
+
+    fn broadcast_lobby_state(&mut self) {
+        let players = self.players.iter().map(|player| LobbyPlayerInfo {
+            id: player.id.clone(),
+            name: player.name.clone(),
+            ready: player.ready,
+        }).collect();
+        let message = ServerMessage::LobbyState { active: self.lobby_active, host_id: self.host_id.clone(), players };
+        self.broadcast(&message);
+    }
+}
+
+impl MockPlayer {
+    /**
+     * メッセージを届ける（バイナリ形式を要求したプレイヤーにはボードの更新をバイナリで送る）
+     */
+    fn push(&mut self, message: &ServerMessage) {
+        let binary = if self.binary { encode_server_message(message) } else { None };
+        let frame = match binary {
+            Some(bytes) => Frame::Binary(bytes),
+            None => Frame::Text(serde_json::to_string(message).unwrap_or_default()),
+        };
+        self.inbox.push(frame);
+    }
+}
+
+impl MockConnection {
+    /**
+     * サーバーが割り当てたプレイヤーID
+     */
+    pub fn player_id(&self) -> &str {
+        &self.player_id
+    }
+
+    /**
+     * メッセージのJSONをサーバーに送る
+     * 
+     * @param text メッセージのJSON
+     * @return 成功した場合はOk(()), 切断済みか解釈できない場合はErr(GameError)
+     */
+    pub fn send(&self, text: &str) -> GameResult<()> {
+        self.server.receive(&self.player_id, text)
+    }
+
+    /**
+     * 届いているメッセージを受け取る
+     * 
+     * @return 届いた順のフレーム
+     */
+    pub fn receive(&self) -> Vec<Frame> {
+        let mut state = self.server.state.borrow_mut();
+        let Some(player) = state.player_mut(&self.player_id) else {
+            return Vec::new();
+        };
+        player.inbox.drain(..).collect()
+    }
+
+    /**
+     * 接続が続いているかどうか（サーバー側で切られた場合はfalse）
+     */
+    pub fn is_open(&self) -> bool {
+        self.server.state.borrow().player(&self.player_id).is_some()
+    }
+
+    /**
+     * 切断する
+     */
+    pub fn close(self) {
+        self.server.disconnect(&self.player_id);
+    }
+}
+
+/**
+ * 周囲8マスのインデックス
+ */
+fn neighbors(width: usize, height: usize, index: usize) -> impl Iterator<Item = usize> {
+    let (x, y) = ((index % width) as i64, (index / width) as i64);
+    (-1..=1).flat_map(move |dy| (-1..=1).map(move |dx| (x + dx, y + dy)))
+        .filter(move |&(nx, ny)| (nx, ny) != (x, y) && nx >= 0 && ny >= 0 && nx < width as i64 && ny < height as i64)
+        .map(move |(nx, ny)| ny as usize * width + nx as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::Board;
+    use crate::models::CellValue;
+    use crate::network::{NetworkManager, RevisionCheck};
+    use crate::resources::ReconnectResource;
+
+    /**
+     * 模擬サーバーに接続するクライアント
+     * 
+     * ブラウザの`GameState`の代わりに、受け取ったメッセージを同じ`Board`のメソッドで反映します。
+     */
+    struct TestClient {
+        network: NetworkManager,
+        board: Board,
+        players: Vec<String>,
+        lobby_active: bool,
+        host_id: Option<String>,
+        room: Option<String>,
+        rooms: Vec<RoomInfo>,
+        avatars: HashMap<String, String>,
+    }
+
+    impl TestClient {
+        fn connect(server: &MockServer) -> Self {
+            Self::resume(server, None)
+        }
+
+        fn resume(server: &MockServer, player_id: Option<&str>) -> Self {
+            Self::open(server, player_id, false)
+        }
+
+        fn connect_binary(server: &MockServer) -> Self {
+            Self::open(server, None, true)
+        }
+
+        fn open(server: &MockServer, player_id: Option<&str>, binary: bool) -> Self {
+            let mut network = NetworkManager::new();
+            network.resume_player_id = player_id.map(str::to_string);
+            network.binary_protocol = binary;
+            network.connect_mock(server);
+            let mut client = Self { network, board: Board::new(1, 1, 0, 30.0), players: Vec::new(), lobby_active: true, host_id: None, room: None, rooms: Vec::new(), avatars: HashMap::new() };
+            client.pump();
+            client
+        }
+
+        fn id(&self) -> String {
+            self.network.local_player_id.clone().unwrap_or_default()
+        }
+
+        fn pump(&mut self) {
+            for message in self.network.receive_mock() {
+                match message {
+                    ServerMessage::Init { player_id, players, game_state } => {
+                        self.network.set_local_player_id(player_id);
+                        self.network.reset_board_revision(game_state.revision);
+                        self.board.update_from_server(&game_state);
+                        self.avatars = players.iter().map(|player| (player.id.clone(), player.avatar.clone())).collect();
+                        self.players = players.into_iter().map(|player| player.id).collect();
+                    },
+                    ServerMessage::StateSync { players, game_state } => {
+                        self.network.reset_board_revision(game_state.revision);
+                        self.board.update_from_server(&game_state);
+                        self.players = players.into_iter().map(|player| player.id).collect();
+                    },
+                    ServerMessage::RoomJoined { room } => self.room = Some(room),
+                    ServerMessage::RoomList { rooms } => self.rooms = rooms,
+                    ServerMessage::PlayerJoined { id, .. } => self.players.push(id),
+                    ServerMessage::PlayerAvatar { id, avatar } => { self.avatars.insert(id, avatar); },
+                    ServerMessage::PlayerLeft { id } => self.players.retain(|player_id| *player_id != id),
+                    ServerMessage::LobbyState { active, host_id, .. } => {
+                        self.lobby_active = active;
+                        self.host_id = host_id;
+                    },
+                    ServerMessage::CellsRevealed { revision, cells, .. } if self.network.check_board_revision(revision) != RevisionCheck::Stale => {
+                        self.board.apply_revealed_cells(&cells);
+                    },
+                    ServerMessage::FlagToggled { revision, index, flagged, .. } if self.network.check_board_revision(revision) != RevisionCheck::Stale => {
+                        self.board.flagged[index] = flagged;
+                    },
+                    ServerMessage::GameOver { win, all_cell_values, .. } => {
+                        self.board.game_over = true;
+                        self.board.win = win;
+                        self.board.apply_cell_values(&all_cell_values);
+                    },
+                    _ => {},
+                }
+            }
+        }
+    }
+
+    /// 3x3のボードの右下の角だけが地雷（左上を開くと8マス中7マスが開く）
+    fn corner_mine_server() -> MockServer {
+        MockServer::new(3, 3, &[8])
+    }
+
+    #[test]
+    fn test_join_and_start_from_lobby() {
+        let server = corner_mine_server();
+        let mut alice = TestClient::connect(&server);
+        let mut bob = TestClient::connect(&server);
+        alice.network.send_join("Alice", "#112233", "").unwrap();
+        alice.pump();
+        bob.pump();
+        
+        assert_eq!(server.player_ids(), vec![alice.id(), bob.id()]);
+        assert_eq!(bob.players, vec![alice.id()]);
+        assert_eq!(alice.players, vec![bob.id()]);
+        assert_eq!(alice.host_id, Some(alice.id()));
+        assert_eq!((bob.board.width, bob.board.height, bob.board.mine_count), (3, 3, 1));
+        
+        // ロビー中はボードを操作できず、全員が準備完了すると始まる
+        alice.network.send_reveal_cell(0).unwrap();
+        alice.network.send_ready(true).unwrap();
+        alice.pump();
+        assert!(alice.lobby_active);
+        assert!(!alice.board.revealed[0]);
+        bob.network.send_ready(true).unwrap();
+        alice.pump();
+        bob.pump();
+        assert!(!alice.lobby_active && !bob.lobby_active);
+        assert!(!server.lobby_active());
+    }
+
+    #[test]
+    fn test_avatar_is_relayed_to_other_players() {
+        let server = corner_mine_server();
+        let mut alice = TestClient::connect(&server);
+        let mut bob = TestClient::connect(&server);
+        alice.network.send_join("Alice", "#112233", "🦊").unwrap();
+        alice.pump();
+        bob.pump();
+        assert_eq!(bob.avatars.get(&alice.id()).map(String::as_str), Some("🦊"));
+        
+        // 後から参加したプレイヤーにも初期化メッセージで伝わる
+        let carol = TestClient::connect(&server);
+        assert_eq!(carol.avatars.get(&alice.id()).map(String::as_str), Some("🦊"));
+        
+        // 候補に無いアバターは無視される
+        bob.network.send_join("Bob", "#445566", "<b>").unwrap();
+        alice.pump();
+        assert!(!alice.avatars.contains_key(&bob.id()));
+    }
+
+    #[test]
+    fn test_reveal_race_applies_first_reveal_only() {
+        let server = corner_mine_server();
+        let mut alice = TestClient::connect(&server);
+        let mut bob = TestClient::connect(&server);
+        alice.network.send_start_game().unwrap();
+        
+        // 2人がほぼ同時に同じセルを開いても、後の要求は何も開かない
+        alice.network.send_reveal_cell(4).unwrap();
+        bob.network.send_reveal_cell(4).unwrap();
+        alice.pump();
+        bob.pump();
+        for client in [&alice, &bob] {
+            assert!(client.board.revealed[4]);
+            assert_eq!(client.board.cells[4], CellValue::Empty(1));
+            assert_eq!(client.board.safe_revealed(), 1);
+        }
+        
+        // ボードが変わらない操作ではリビジョンを進めない
+        bob.network.send_toggle_flag(4).unwrap();
+        bob.pump();
+        assert!(!bob.board.flagged[4]);
+        assert_eq!(server.snapshot().revision, 1);
+        
+        // 0のセルから広がって残りを開き、両方のクライアントで勝利になる
+        bob.network.send_toggle_flag(8).unwrap();
+        bob.network.send_reveal_cell(0).unwrap();
+        alice.pump();
+        bob.pump();
+        for client in [&alice, &bob] {
+            assert!(client.board.flagged[8]);
+            assert!(client.board.game_over && client.board.win);
+            assert_eq!(client.board.cells[8], CellValue::Mine);
+            assert_eq!(client.board.safe_revealed(), 8);
+        }
+        assert_eq!(server.snapshot().cell_values.len(), 8);
+    }
+
+    #[test]
+    fn test_late_joiner_does_not_cancel_countdown() {
+        let server = corner_mine_server().with_countdown(3000.0);
+        let mut alice = TestClient::connect(&server);
+        let mut bob = TestClient::connect(&server);
+        alice.network.send_start_game().unwrap();
+        alice.pump();
+        bob.pump();
+        assert!(server.counting_down());
+        
+        // 後から接続したプレイヤーがボード設定を送っても、カウントダウンもボードも変わらない
+        let carol = TestClient::connect(&server);
+        carol.network.send_board_config(9, 9, 10).unwrap();
+        assert!(server.counting_down());
+        assert_eq!(server.snapshot().board_width, 3);
+        assert_eq!(server.snapshot().countdown_ends_at, Some(3000.0));
+        
+        // カウントダウン中はセルを開けず、終わると開ける
+        alice.network.send_reveal_cell(4).unwrap();
+        assert!(!server.snapshot().revealed[4]);
+        server.finish_countdown();
+        alice.network.send_reveal_cell(4).unwrap();
+        alice.pump();
+        assert!(alice.board.revealed[4]);
+    }
+
+    #[test]
+    fn test_binary_and_text_clients_see_the_same_board() {
+        let server = corner_mine_server();
+        let mut alice = TestClient::connect_binary(&server);
+        let mut bob = TestClient::connect(&server);
+        alice.network.send_start_game().unwrap();
+        
+        // ボードの更新だけがバイナリで届き、それ以外のメッセージはテキストのまま処理される
+        bob.network.send_reveal_cell(4).unwrap();
+        alice.network.send_reveal_cell(0).unwrap();
+        alice.pump();
+        bob.pump();
+        assert!(!alice.lobby_active);
+        for client in [&alice, &bob] {
+            assert!(client.board.game_over && client.board.win);
+            assert_eq!(client.board.cells[8], CellValue::Mine);
+            assert_eq!(client.board.safe_revealed(), 8);
+        }
+        assert_eq!(alice.board.cells, bob.board.cells);
+        assert_eq!(alice.board.revealed, bob.board.revealed);
+    }
+
+    #[test]
+    fn test_disconnect_and_rejoin_with_same_identity() {
+        let server = corner_mine_server();
+        let mut alice = TestClient::connect(&server);
+        let mut bob = TestClient::connect(&server);
+        alice.network.send_start_game().unwrap();
+        alice.network.send_reveal_cell(4).unwrap();
+        let alice_id = alice.id();
+        
+        // 切断すると他のプレイヤーに通知され、ホストが引き継がれる
+        alice.network.disconnect();
+        bob.pump();
+        assert!(!alice.network.is_connected);
+        assert!(bob.players.is_empty());
+        assert_eq!(server.host_id(), Some(bob.id()));
+        assert!(alice.network.send_reveal_cell(0).is_err());
+        
+        // 同じIDで接続し直すと、切断中の状態も初期化メッセージで受け取る
+        bob.network.send_toggle_flag(0).unwrap();
+        let alice = TestClient::resume(&server, Some(&alice_id));
+        bob.pump();
+        assert_eq!(alice.id(), alice_id);
+        assert_eq!(bob.players, vec![alice_id.clone()]);
+        assert!(alice.board.revealed[4] && alice.board.flagged[0]);
+        assert_eq!(alice.board.cells[4], CellValue::Empty(1));
+        
+        // 接続中のIDは引き継げない
+        let carol = TestClient::resume(&server, Some(&alice_id));
+        assert_ne!(carol.id(), alice_id);
+    }
+
+    #[test]
+    fn test_disconnect_forgets_board_revision() {
+        let server = corner_mine_server();
+        let mut alice = TestClient::connect(&server);
+        alice.network.send_start_game().unwrap();
+        alice.network.send_reveal_cell(4).unwrap();
+        alice.pump();
+        assert_eq!(alice.network.check_board_revision(1), RevisionCheck::Stale);
+        
+        // 切断した後は前の接続の版数で差分を捨てない
+        alice.network.disconnect();
+        assert_eq!(alice.network.check_board_revision(1), RevisionCheck::Missed);
+    }
+
+    #[test]
+    fn test_new_ids_skip_resumed_ids() {
+        let server = corner_mine_server();
+        let alice = TestClient::resume(&server, Some("player_2"));
+        let bob = TestClient::connect(&server);
+        let carol = TestClient::connect(&server);
+        assert_eq!(alice.id(), "player_2");
+        assert_ne!(bob.id(), alice.id());
+        assert_ne!(carol.id(), alice.id());
+        assert_ne!(carol.id(), bob.id());
+    }
+
+    #[test]
+    fn test_leave_is_sent_before_closing() {
+        let server = corner_mine_server();
+        let mut alice = TestClient::connect(&server);
+        let mut bob = TestClient::connect(&server);
+        let alice_id = alice.id();
+        assert_eq!(bob.players, vec![alice_id]);
+        
+        // 退出を知らせてから切断し、他のプレイヤーからすぐに外れる
+        alice.network.leave();
+        assert!(!alice.network.is_connected);
+        assert!(alice.network.traffic_log_json().contains(r#""type":"leave""#));
+        bob.pump();
+        assert!(bob.players.is_empty());
+        assert_eq!(server.host_id(), Some(bob.id()));
+    }
+
+    #[test]
+    fn test_dropped_connection_reconnects_and_resyncs() {
+        let server = corner_mine_server();
+        let mut alice = TestClient::connect(&server);
+        let mut bob = TestClient::connect(&server);
+        alice.network.send_start_game().unwrap();
+        alice.pump();
+        let alice_id = alice.id();
+        
+        // 通信が途絶えると切断として検知され、待ち時間を延ばしながら再接続を予約する
+        server.drop_connection(&alice_id);
+        alice.pump();
+        assert!(!alice.network.is_connected);
+        assert!(alice.network.take_connection_lost());
+        assert!(!alice.network.take_connection_lost());
+        let mut reconnect = ReconnectResource::new();
+        assert_eq!(reconnect.schedule(Some(alice_id.clone()), 0.0), Some(500.0));
+        
+        // 切断中に進んだ操作はまだ反映されていない
+        bob.network.send_toggle_flag(8).unwrap();
+        bob.network.send_reveal_cell(4).unwrap();
+        assert!(!alice.board.revealed[4] && !alice.board.flagged[8]);
+        
+        // 予約した時刻になったら同じIDで接続し直し、状態を要求し直して置き換える
+        assert!(reconnect.take_due(500.0));
+        alice.network.resume_player_id = reconnect.player_id().map(str::to_string);
+        alice.network.connect_mock(&server);
+        alice.pump();
+        assert!(reconnect.finish());
+        alice.network.send_sync_request().unwrap();
+        alice.pump();
+        bob.pump();
+        assert_eq!(alice.id(), alice_id);
+        assert_eq!(bob.players, vec![alice_id]);
+        assert!(alice.board.revealed[4] && alice.board.flagged[8]);
+        assert_eq!(alice.board.cells[4], CellValue::Empty(1));
+    }
+
+    #[test]
+    fn test_missed_board_revision_requests_snapshot_once() {
+        let server = MockServer::new(3, 3, &[8]);
+        let mut alice = TestClient::connect(&server);
+        let mut bob = TestClient::connect(&server);
+        alice.network.send_start_game().unwrap();
+        alice.pump();
+        bob.pump();
+        
+        // 順に届いた差分はそのまま反映され、全体の状態は要求しない
+        alice.network.send_toggle_flag(8).unwrap();
+        alice.pump();
+        bob.pump();
+        assert!(bob.board.flagged[8]);
+        assert_eq!(server.sync_requests(), 0);
+        
+        // 差分を2つ取りこぼすと、次の差分で1回だけ全体の状態を要求する
+        alice.network.send_toggle_flag(8).unwrap();
+        alice.network.send_toggle_flag(7).unwrap();
+        bob.network.receive_mock();
+        alice.network.send_toggle_flag(6).unwrap();
+        alice.network.send_toggle_flag(5).unwrap();
+        bob.pump();
+        assert_eq!(server.sync_requests(), 1);
+        
+        // 届いた全体の状態で取りこぼした更新も揃い、以降の差分は順に反映される
+        bob.pump();
+        assert_eq!(bob.board.flagged, server.snapshot().flagged);
+        assert!(!bob.board.flagged[8] && bob.board.flagged[7]);
+        assert_eq!(bob.network.check_board_revision(server.snapshot().revision), RevisionCheck::Stale);
+        alice.network.send_reveal_cell(0).unwrap();
+        bob.pump();
+        assert_eq!(bob.board.revealed, server.snapshot().revealed);
+        assert!(bob.board.safe_revealed() > 0);
+        assert_eq!(server.sync_requests(), 1);
+    }
+
+    #[test]
+    fn test_rooms_run_independent_games() {
+        let server = corner_mine_server();
+        let mut alice = TestClient::connect(&server);
+        let mut bob = TestClient::connect(&server);
+        let mut carol = TestClient::connect(&server);
+        
+        // ルームを作成すると既定のルームから移り、一覧に表示される
+        alice.network.send_create_room().unwrap();
+        alice.pump();
+        let room = alice.room.clone().unwrap();
+        assert!(alice.players.is_empty());
+        assert_eq!(alice.host_id, Some(alice.id()));
+        bob.network.send_list_rooms().unwrap();
+        bob.pump();
+        assert_eq!(bob.players, vec![carol.id()]);
+        assert_eq!(bob.rooms.len(), 1);
+        assert_eq!((bob.rooms[0].code.as_str(), bob.rooms[0].players), (room.as_str(), 1));
+        
+        // 一覧から参加すると、同じルームのプレイヤーだけが見える
+        bob.network.send_join_room(&room).unwrap();
+        alice.pump();
+        bob.pump();
+        carol.pump();
+        assert_eq!(bob.room, Some(room.clone()));
+        assert_eq!(bob.players, vec![alice.id()]);
+        assert_eq!(alice.players, vec![bob.id()]);
+        assert!(carol.players.is_empty());
+        assert_eq!(server.room_player_ids(&room), vec![alice.id(), bob.id()]);
+        
+        // ルームのゲームを進めても、既定のルームのボードは変わらない
+        alice.network.send_start_game().unwrap();
+        alice.network.send_reveal_cell(0).unwrap();
+        alice.pump();
+        bob.pump();
+        carol.pump();
+        assert!(alice.board.win && bob.board.win);
+        assert_eq!(carol.board.safe_revealed(), 0);
+        assert!(server.lobby_active());
+        assert!(server.snapshot().cell_values.is_empty());
+        assert_eq!(server.room_snapshot(&room).unwrap().cell_values.len(), 8);
+        
+        // 既定のルームとプレイヤーのいないルームは一覧に出ない
+        alice.network.disconnect();
+        bob.network.disconnect();
+        carol.network.send_list_rooms().unwrap();
+        carol.pump();
+        assert!(carol.rooms.is_empty());
+    }
+}
\ No newline at end of file
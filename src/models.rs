@@ -1,9 +1,10 @@
 use serde::{Serialize, Deserialize};
+use crate::resources::Color;
 
 /**
  * セルの状態を表す列挙型
  */
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CellValue {
     Mine,           // 地雷
     Empty(u8),      // 空白（周囲の地雷数）
@@ -12,10 +13,20 @@ pub enum CellValue {
 /**
  * 画面状態を表す列挙型
  */
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Screen {
-    Title,  // タイトル画面
-    Game,   // ゲーム画面
+    Title,            // タイトル画面
+    DifficultySelect, // 難易度選択画面
+    CustomBoard,      // カスタムボード設定画面
+    Settings,         // 設定画面
+    ThemeSelect,      // テーマ選択画面
+    Accessibility,    // アクセシビリティ画面
+    Stats,            // 統計画面
+    Help,             // ヘルプ画面
+    History,          // 対戦履歴画面
+    RoomSelect,       // ルーム選択画面
+    Lobby,            // ロビー画面
+    Game,             // ゲーム画面
 }
 
 /**
@@ -27,10 +38,73 @@ pub struct Player {
     pub name: String,    // プレイヤー名
     pub x: f64,          // X座標
     pub y: f64,          // Y座標
-    pub color: String,   // カーソルの色
+    pub color: Color,    // カーソルの色
+    pub avatar: String,  // アバター（未設定なら空文字列）
     pub score: u32,      // プレイヤースコア
     pub is_local: bool,  // ローカルプレイヤーかどうか
     pub is_host: bool,   // ホストプレイヤーかどうか
     pub is_alive: bool,  // 生存中かどうか
     pub cells_revealed: usize, // 開いたセル数
-} 
\ No newline at end of file
+    pub last_update: f64, // 最後に操作した時刻（ミリ秒）
+    pub ping: Option<u32>, // 通信の遅延（ミリ秒、未計測ならNone）
+}
+
+/**
+ * カーソルも操作も無い状態がこの時間（ミリ秒）続いたプレイヤーを離席中とみなす
+ */
+pub const IDLE_TIMEOUT_MS: f64 = 30_000.0;
+
+impl Player {
+    /**
+     * 離席中かどうか
+     * 
+     * @param now 現在時刻（ミリ秒）
+     * @return 最後の操作からIDLE_TIMEOUT_MS以上経っていればtrue
+     */
+    pub fn is_idle(&self, now: f64) -> bool {
+        now - self.last_update >= IDLE_TIMEOUT_MS
+    }
+}
+
+/**
+ * ホストページ向けに公開するゲーム状態のスナップショット
+ * 
+ * キャンバスの外でUI（サイドバーや配信用オーバーレイなど）を作るために使います。
+ */
+#[derive(Clone, Serialize, Debug)]
+pub struct GameSnapshot {
+    pub phase: &'static str,   // ゲームフェーズ（ready / playing / paused / won / lost）
+    pub in_game: bool,         // ゲーム画面を表示中かどうか
+    pub width: usize,          // ボードの幅
+    pub height: usize,         // ボードの高さ
+    pub mine_count: usize,     // 地雷の数
+    pub flags: usize,          // 立てられたフラグの数
+    pub safe_revealed: usize,  // 開かれた安全なセルの数
+    pub safe_cells: usize,     // 安全なセルの総数
+    pub elapsed_ms: f64,       // 経過時間（ミリ秒）
+    pub players: Vec<Player>,  // プレイヤー一覧（ID順）
+}
+
+/**
+ * ホストページから`init_game`に渡す起動オプション
+ * 
+ * 全て省略でき、省略した項目は保存された設定や既定値を使います。
+ */
+#[derive(Clone, Deserialize, Debug, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct GameOptions {
+    pub canvas_id: Option<String>,   // 描画するキャンバスのID（既定は"game-canvas"）
+    pub server_url: Option<String>,  // WebSocketサーバーのURL（既定はページのホストから決める）
+    pub player_name: Option<String>, // プレイヤー名
+    pub difficulty: Option<String>,  // 難易度のキー（easy / medium / hard）
+    pub locale: Option<String>,      // 表示言語のロケール（"ja-JP"など）
+    pub theme: Option<String>,       // テーマ名（"dark"など、未解放のテーマは無視する）
+    pub room: Option<String>,        // 接続後に参加するルームのコード（既定はページのURLから読み取る）
+    pub ui_mode: Option<String>,     // メニューの表示方法（"canvas"または"dom"、既定は保存された設定）
+    pub compact: bool,               // 埋め込み用の最小表示（ボードと状態の帯のみ表示し、一人用で遊ぶ）
+    pub seed: Option<u64>,           // ローカルで作る盤面の乱数のシード（既定は時刻から決める）
+    pub log: Option<String>,         // 出力するログのレベル（"info,network=debug"など、既定はinfo）
+    pub log_panel: bool,             // ゲーム内のログパネルを表示するかどうか（`キーで切り替え）
+    pub debug_overlay: bool,         // 診断情報（メモリ使用量など）を表示するかどうか（F3キーで切り替え）
+    pub binary_protocol: bool,       // ボードの更新をバイナリ形式で受け取るかどうか（大きなボードで通信量を減らす）
+}
\ No newline at end of file
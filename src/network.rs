@@ -2,30 +2,159 @@
  * WebSocket通信を管理するモジュール
  * 
  * サーバーとの通信機能を提供します。
+ * 通信路は`Transport`で抽象化し、ゲームサーバーを使わずにLANや少人数で遊ぶための
+ * WebRTCのデータチャネル（手動のシグナリング）にも切り替えられます。
  */
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
-use web_sys::{WebSocket, MessageEvent};
-use std::collections::HashMap;
-use serde_json::{json, Value};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{WebSocket, MessageEvent, RtcPeerConnection, RtcDataChannel, RtcDataChannelEvent, RtcDataChannelState, RtcDataChannelType, RtcIceGatheringState, RtcSdpType, RtcSessionDescriptionInit};
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::rc::Rc;
 
-use crate::js_bindings::{log, update_connection_status, update_player_count, get_websocket_url};
+use crate::js_bindings::{update_connection_status, update_player_count, get_websocket_url};
 use crate::models::Player;
+use crate::protocol::{ClientMessage, ServerMessage};
+use crate::binary_protocol::decode_server_message;
+use crate::send_queue::SendQueue;
+use crate::resources::{TrafficLogResource, TrafficDirection};
+use crate::utils::now_ms;
+use crate::error::{GameError, GameResult};
+use crate::logger::{log_error, log_warn, log_info, log_trace};
 
 /**
  * WebSocket通信を管理する構造体
  */
 pub struct NetworkManager {
-    /// WebSocketインスタンス
-    pub websocket: Option<WebSocket>,
+    /// 接続に使っている通信路（WebSocketかWebRTCのデータチャネル）
+    transport: Option<Box<dyn Transport>>,
     /// 接続状態
     pub is_connected: bool,
     /// ローカルプレイヤーID
     pub local_player_id: Option<String>,
+    /// 接続先のURL（Noneの場合はページのホストから決める）
+    pub server_url: Option<String>,
+    /// 参加しているルームのコード（接続前は参加する予定のルーム）
+    pub room: Option<String>,
+    /// 次の接続でサーバーに引き継ぎを要求するプレイヤーID（ページの再読み込み前のID）
+    pub resume_player_id: Option<String>,
+    /// ボードの更新をバイナリ形式で受け取るかどうか（次の接続から反映される）
+    pub binary_protocol: bool,
+    /// 送信するメッセージのJSONを書き込むバッファ（位置情報など頻繁な送信で使い回す）
+    send_buffer: RefCell<Vec<u8>>,
+    /// フレームの最後に`flush_outbox`でまとめて送るメッセージ
+    outbox: RefCell<SendQueue>,
+    /// 最近送受信したメッセージの記録（状態のずれを調べるため、切断しても残す）
+    traffic_log: RefCell<TrafficLogResource>,
+    /// WebSocketのコールバックが積み、フレームごとに`poll_messages`で取り出す受信キュー
+    events: NetworkEventQueue,
+    /// 接続のたびに`forget`したWebSocketのコールバックの数（解放されずに残る）
+    forgotten_closures: usize,
+    /// `disconnect`を呼ばずに接続が切れたかどうか（再接続の判断に使う）
+    connection_lost: bool,
+    /// 最後に反映したボードの版数（全体の状態を受け取るまではNone）
+    board_revision: Option<u32>,
+    /// 版数の飛びを見つけて全体の状態を要求し、まだ届いていないかどうか
+    awaiting_snapshot: bool,
+    /// テストでWebSocketの代わりに使う模擬サーバーへの接続
+    #[cfg(test)]
+    mock: Option<crate::mock_server::MockConnection>,
 }
 
-/// CallbackType: GameStateのメソッドをコールバックとして使用するための型
-pub type MessageCallback = Box<dyn Fn(&serde_json::Value) -> Result<(), JsValue>>;
+/**
+ * 通信路のコールバックで起きた出来事
+ * 
+ * コールバックは状態を直接書き換えず、キューに積んでフレームの処理に任せます。
+ * 受け取るメッセージの型は、クライアントでは`ServerMessage`、ピアツーピアのホストでは`ClientMessage`です。
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub enum NetworkEvent<M = ServerMessage> {
+    Opened,      // 接続が確立した
+    Message(M),  // 変換できたメッセージが届いた
+    Error,       // 通信路でエラーが起きた（この後に切断が続く）
+    Closed,      // 接続が切れた
+}
+
+/// 通信路のコールバックと所有者で共有する出来事のキュー
+pub type NetworkEventQueue<M = ServerMessage> = Rc<RefCell<VecDeque<NetworkEvent<M>>>>;
+
+/**
+ * 接続の状態の変化を受け取るホストページの関数
+ * 
+ * `update_connection_status`より詳しい変化を、ホストページ独自の接続表示や分析に渡します。
+ */
+#[derive(Debug, Clone, Default)]
+pub struct ConnectionCallbacks {
+    pub on_connected: Option<js_sys::Function>,    // 接続が確立したとき
+    pub on_disconnected: Option<js_sys::Function>, // 接続が切れたとき（理由を渡す）
+    pub on_reconnecting: Option<js_sys::Function>, // 再接続を予約したとき（何回目かを渡す）
+}
+
+impl ConnectionCallbacks {
+    /**
+     * 接続が確立したことを知らせる
+     */
+    pub fn connected(&self) {
+        Self::call(&self.on_connected, &JsValue::UNDEFINED);
+    }
+
+    /**
+     * 接続が切れたことを知らせる
+     * 
+     * @param reason 切れた理由（"closed"、"connection_lost"、"reconnect_failed"）
+     */
+    pub fn disconnected(&self, reason: &str) {
+        Self::call(&self.on_disconnected, &JsValue::from_str(reason));
+    }
+
+    /**
+     * 再接続を予約したことを知らせる
+     * 
+     * @param attempt 何回目の再接続か（1から数える）
+     */
+    pub fn reconnecting(&self, attempt: u32) {
+        Self::call(&self.on_reconnecting, &JsValue::from(attempt));
+    }
+
+    fn call(callback: &Option<js_sys::Function>, argument: &JsValue) {
+        if let Some(callback) = callback {
+            if let Err(e) = callback.call1(&JsValue::NULL, argument) {
+                log_error!("Connection callback error: {:?}", e);
+            }
+        }
+    }
+}
+
+/// WebRTCのデータチャネルの名前
+const PEER_CHANNEL_LABEL: &str = "minesweeper";
+
+/// シグナリングの情報を作るときにICEの候補を集め終わるのを待つ間隔（ミリ秒）
+const PEER_GATHER_POLL_MS: i32 = 100;
+
+/// ICEの候補を集め終わるのを待つ最大の回数（超えたらそれまでの候補で進める）
+const PEER_GATHER_ATTEMPTS: u32 = 50;
+
+/**
+ * サーバーから届いた1つのフレーム
+ * 
+ * ボードの更新はバイナリ形式を有効にした場合のみバイナリフレームで届きます。
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub enum Frame {
+    Text(String),    // JSONのテキストフレーム
+    Binary(Vec<u8>), // `binary_protocol`形式のバイナリフレーム
+}
+
+/**
+ * ボードの差分の版数を確認した結果
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RevisionCheck {
+    InOrder, // 次の版数（そのまま反映する）
+    Stale,   // 反映済みの版数（全体の状態に含まれているため捨てる）
+    Missed,  // 途中の版数を取りこぼした（反映した上で全体の状態を要求し直す）
+}
 
 impl NetworkManager {
     /**
@@ -33,9 +162,23 @@ impl NetworkManager {
      */
     pub fn new() -> Self {
         Self {
-            websocket: None,
+            transport: None,
             is_connected: false,
             local_player_id: None,
+            server_url: None,
+            room: None,
+            resume_player_id: None,
+            binary_protocol: false,
+            send_buffer: RefCell::new(Vec::with_capacity(128)),
+            outbox: RefCell::new(SendQueue::new()),
+            traffic_log: RefCell::new(TrafficLogResource::new()),
+            events: NetworkEventQueue::default(),
+            forgotten_closures: 0,
+            connection_lost: false,
+            board_revision: None,
+            awaiting_snapshot: false,
+            #[cfg(test)]
+            mock: None,
         }
     }
     
@@ -43,94 +186,269 @@ impl NetworkManager {
      * WebSocketサーバーに接続する
      * 
      * サーバーとの通信を確立し、各種イベントハンドラを設定します。
-     * - onopen: 接続成功時の処理
-     * - onmessage: メッセージ受信時の処理
-     * - onerror: エラー発生時の処理
-     * - onclose: 接続終了時の処理
+     * ハンドラは出来事を受信キューに積むだけで、反映は`poll_messages`で行います。
+     * - onopen: 接続成功を積む
+     * - onmessage: 変換できたメッセージを積む
+     * - onerror: エラーを記録する
+     * - onclose: 切断を積む
      * 
-     * @return 成功した場合はOk(()), エラーの場合はErr(JsValue)
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
      */
-    pub fn connect(&mut self, message_callback: MessageCallback) -> Result<(), JsValue> {
+    pub fn connect(&mut self) -> GameResult<()> {
         // WebSocketの作成
-        let server_url = get_websocket_url();
-        log(&format!("Connecting to WebSocket server at: {}", server_url));
+        let mut server_url = self.server_url.clone().unwrap_or_else(get_websocket_url);
+        if let Some(player_id) = self.resume_player_id.take() {
+            server_url = with_query_param(&server_url, "resume", &player_id);
+        }
+        if self.binary_protocol {
+            server_url = with_query_param(&server_url, "binary", "1");
+        }
+        log_info!("Connecting to WebSocket server at: {}", server_url);
         
-        let ws = WebSocket::new(&server_url)?;
-        let this = self as *mut NetworkManager;
-
-        // onopen: 接続成功時のコールバック
-        let onopen_callback = Closure::wrap(Box::new(move || {
-            log("WebSocket connected!");
-            update_connection_status(true);
-            unsafe {
-                (*this).is_connected = true;  // 接続状態を更新
+        self.transport = Some(Box::new(WebSocketTransport::open(&server_url, &self.events)?));
+        self.connection_lost = false;
+        self.forgotten_closures += WebSocketTransport::FORGOTTEN_CLOSURES;
+        
+        Ok(())
+    }
+    
+    /**
+     * ホストへのWebRTCのデータチャネルで接続する
+     * 
+     * ホストから受け取ったオファーへのアンサーを作る接続を用意し、`poll_messages`で受け取れるようにします。
+     * シグナリング（オファーの設定とアンサーの作成）は戻り値の接続に対して呼び出し側で行います。
+     * 
+     * @return シグナリングに使う接続、作成できない場合はErr(GameError)
+     */
+    pub fn connect_peer(&mut self) -> GameResult<RtcPeerConnection> {
+        log_info!("Joining a peer-to-peer game");
+        let transport = PeerTransport::answer(&self.events)?;
+        let connection = transport.connection();
+        self.transport = Some(Box::new(transport));
+        self.connection_lost = false;
+        self.forgotten_closures += PeerTransport::FORGOTTEN_CLOSURES;
+        Ok(connection)
+    }
+    
+    /**
+     * ホストとピアツーピアで接続しているかどうか
+     * 
+     * @return データチャネルで接続している（または接続中の）場合はtrue
+     */
+    pub fn is_peer(&self) -> bool {
+        self.transport.as_ref().is_some_and(|transport| transport.is_peer_to_peer())
+    }
+    
+    /**
+     * 通信路を作成済みかどうか（接続が開く前も含む）
+     */
+    pub fn has_transport(&self) -> bool {
+        self.transport.is_some()
+    }
+    
+    /**
+     * WebSocketの代わりに模擬サーバーに接続する（テスト用）
+     * 
+     * 引き継ぎを要求するプレイヤーIDがあれば`connect`と同じようにサーバーに渡します。
+     * 
+     * @param server 接続先の模擬サーバー
+     */
+    #[cfg(test)]
+    pub fn connect_mock(&mut self, server: &crate::mock_server::MockServer) {
+        let resume = self.resume_player_id.take();
+        self.mock = Some(server.connect(resume.as_deref(), self.binary_protocol));
+        self.is_connected = true;
+        self.connection_lost = false;
+    }
+    
+    /**
+     * 模擬サーバーから届いたメッセージを受け取る（テスト用）
+     * 
+     * WebSocketのコールバックと同じく受信キューに積んでから`poll_messages`で取り出すため、
+     * 不正なメッセージは捨て、サーバー側で接続が切られていれば切断を記録します。
+     * 
+     * @return 届いた順のメッセージ（接続していない場合は空）
+     */
+    #[cfg(test)]
+    pub fn receive_mock(&mut self) -> Vec<ServerMessage> {
+        let Some(connection) = &self.mock else {
+            return Vec::new();
+        };
+        let frames = connection.receive();
+        let open = connection.is_open();
+        {
+            let mut events = self.events.borrow_mut();
+            events.extend(frames.iter().filter_map(parse_server_frame).map(NetworkEvent::Message));
+            if !open {
+                events.push_back(NetworkEvent::Closed);
             }
-        }) as Box<dyn FnMut()>);
-        ws.set_onopen(Some(onopen_callback.as_ref().unchecked_ref()));
-        onopen_callback.forget();
-
-        // onmessage: メッセージ受信時のコールバック
-        let callback = message_callback;
-        let onmessage_callback = Closure::wrap(Box::new(move |e: MessageEvent| {
-            if let Ok(txt) = e.data().dyn_into::<js_sys::JsString>() {
-                let message = String::from(txt);
-                log(&format!("Message received: {}", message));
-                
-                // JSONをパース
-                if let Ok(json) = serde_json::from_str::<serde_json::Value>(&message) {
-                    // メッセージを処理するコールバックを呼び出す
-                    if let Err(e) = callback(&json) {
-                        log(&format!("Error processing message: {:?}", e));
-                    }
-                }
+        }
+        if !open {
+            self.mock = None;
+        }
+        self.poll_messages()
+    }
+    
+    /**
+     * 受信キューに溜まった出来事を反映し、届いたメッセージを取り出す
+     * 
+     * 接続状態の変化はここで反映するため、毎フレームの最初に呼び出します。
+     * 
+     * @return 前回の呼び出し以降に届いた順のメッセージ
+     */
+    pub fn poll_messages(&mut self) -> Vec<ServerMessage> {
+        let events: Vec<NetworkEvent> = self.events.borrow_mut().drain(..).collect();
+        let mut messages = Vec::new();
+        for event in events {
+            match event {
+                NetworkEvent::Opened => self.is_connected = true,
+                NetworkEvent::Message(message) => {
+                    self.traffic_log.borrow_mut().record(TrafficDirection::Inbound, &message, now_ms());
+                    messages.push(message);
+                },
+                NetworkEvent::Error => {},
+                NetworkEvent::Closed => {
+                    self.is_connected = false;
+                    self.connection_lost = true;
+                },
             }
-        }) as Box<dyn FnMut(MessageEvent)>);
-        ws.set_onmessage(Some(onmessage_callback.as_ref().unchecked_ref()));
-        onmessage_callback.forget();
-
-        // onerror: エラー発生時のコールバック
-        let onerror_callback = Closure::wrap(Box::new(move |e: web_sys::Event| {
-            log(&format!("WebSocket error: {:?}", e));
-        }) as Box<dyn FnMut(web_sys::Event)>);
-        ws.set_onerror(Some(onerror_callback.as_ref().unchecked_ref()));
-        onerror_callback.forget();
-
-        // onclose: 接続終了時のコールバック
-        let network_manager = this;
-        let onclose_callback = Closure::wrap(Box::new(move |e: web_sys::CloseEvent| {
-            log(&format!("WebSocket closed: code={}, reason={}", e.code(), e.reason()));
-            update_connection_status(false);
-            unsafe {
-                (*network_manager).is_connected = false;  // 接続状態を更新
+        }
+        messages
+    }
+    
+    /**
+     * 予期しない切断があったかどうかを取得し、記録を消す
+     * 
+     * @return 前回の呼び出し以降に`disconnect`以外で接続が切れた場合はtrue
+     */
+    pub fn take_connection_lost(&mut self) -> bool {
+        std::mem::take(&mut self.connection_lost)
+    }
+    
+    /**
+     * これまでに`forget`したWebSocketのコールバックの数（メモリの診断用）
+     */
+    pub fn forgotten_closures(&self) -> usize {
+        self.forgotten_closures
+    }
+    
+    /**
+     * 最近送受信したメッセージの記録をJSONで取得する（状態のずれの調査用）
+     * 
+     * @return 時刻・向き・メッセージを持つ要素の配列（古い順）
+     */
+    pub fn traffic_log_json(&self) -> String {
+        self.traffic_log.borrow().to_json()
+    }
+    
+    /**
+     * 送信用のバッファの容量（メモリの診断用）
+     */
+    pub fn send_buffer_capacity(&self) -> usize {
+        self.send_buffer.borrow().capacity()
+    }
+    
+    /**
+     * WebSocketサーバーから切断する
+     * 
+     * 切断後に届いたメッセージは処理しません。
+     */
+    pub fn disconnect(&mut self) {
+        self.connection_lost = false;
+        self.events.borrow_mut().clear();
+        self.outbox.borrow_mut().clear();
+        self.is_connected = false;
+        self.local_player_id = None;
+        self.board_revision = None;
+        self.awaiting_snapshot = false;
+        
+        #[cfg(test)]
+        if let Some(connection) = self.mock.take() {
+            connection.close();
+            return;
+        }
+        
+        if let Some(mut transport) = self.transport.take() {
+            // 切断後やゲームの停止後にコールバックが呼ばれないよう、ハンドラを外してから閉じる
+            transport.close();
+        }
+        update_connection_status(false);
+    }
+    
+    /**
+     * サーバーに退出を知らせてから切断する
+     * 
+     * ページを離れるときに呼び出します。送信キューに残っているメッセージも先に送ります。
+     */
+    pub fn leave(&mut self) {
+        if self.is_connected {
+            self.outbox.borrow_mut().push(ClientMessage::Leave);
+            if let Err(e) = self.flush_outbox() {
+                log_warn!("Leave send error: {:?}", e);
             }
-        }) as Box<dyn FnMut(web_sys::CloseEvent)>);
-        ws.set_onclose(Some(onclose_callback.as_ref().unchecked_ref()));
-        onclose_callback.forget();
-
-        // WebSocketをフィールドに保存
-        self.websocket = Some(ws);
+        }
+        self.disconnect();
+    }
+    
+    /**
+     * メッセージを送信キューに積む
+     * 
+     * 実際の送信はフレームの最後の`flush_outbox`で行います。
+     * 
+     * @param message 送信するメッセージ（JSONに変換して送る）
+     * @return 成功した場合はOk(()), 接続していない場合はErr(GameError)
+     */
+    pub fn send_message(&self, message: &ClientMessage) -> GameResult<()> {
+        #[cfg(test)]
+        if self.mock.is_some() {
+            // 模擬サーバーにはフレームを待たずにすぐ送る
+            self.outbox.borrow_mut().push(message.clone());
+            return self.flush_outbox();
+        }
         
+        if self.transport.is_none() {
+            return Err(GameError::Network("WebSocket is not initialized".to_string()));
+        }
+        self.outbox.borrow_mut().push(message.clone());
         Ok(())
     }
     
     /**
-     * メッセージを送信する
+     * 送信キューに積んだメッセージのうち、送れるものをまとめて送信する（フレームの最後に呼び出す）
      * 
-     * @param message 送信するJSONメッセージ
-     * @return 成功した場合はOk(()), エラーの場合はErr(JsValue)
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
      */
-    pub fn send_message(&self, message: &serde_json::Value) -> Result<(), JsValue> {
-        if let Some(ws) = &self.websocket {
-            if ws.ready_state() == web_sys::WebSocket::OPEN {
-                let json_string = serde_json::to_string(message).unwrap();
-                ws.send_with_str(&json_string)?;
-                Ok(())
-            } else {
-                Err(JsValue::from_str("WebSocket is not open"))
-            }
-        } else {
-            Err(JsValue::from_str("WebSocket is not initialized"))
+    pub fn flush_outbox(&self) -> GameResult<()> {
+        self.outbox.borrow_mut().flush(now_ms(), |message| self.write_message(message))
+    }
+    
+    /**
+     * 1つのメッセージを通信路に書き込む
+     * 
+     * @param message 送信するメッセージ（JSONに変換して送る）
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
+     */
+    fn write_message(&self, message: &ClientMessage) -> GameResult<()> {
+        self.traffic_log.borrow_mut().record(TrafficDirection::Outbound, message, now_ms());
+        
+        #[cfg(test)]
+        if let Some(connection) = &self.mock {
+            let json_string = serde_json::to_string(message).map_err(|e| GameError::Network(e.to_string()))?;
+            return connection.send(&json_string);
         }
+        
+        let Some(transport) = &self.transport else {
+            return Err(GameError::Network("WebSocket is not initialized".to_string()));
+        };
+        
+        // 毎回文字列を作らないよう、前回のバッファを空にして書き込む
+        let mut buffer = self.send_buffer.borrow_mut();
+        buffer.clear();
+        serde_json::to_writer(&mut *buffer, message)
+            .map_err(|e| GameError::Network(e.to_string()))?;
+        let json_string = std::str::from_utf8(&buffer)
+            .map_err(|e| GameError::Network(e.to_string()))?;
+        transport.send_text(json_string)
     }
     
     /**
@@ -138,64 +456,227 @@ impl NetworkManager {
      * 
      * @param x X座標
      * @param y Y座標
-     * @return 成功した場合はOk(()), エラーの場合はErr(JsValue)
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
      */
-    pub fn send_position_update(&self, x: f64, y: f64) -> Result<(), JsValue> {
+    pub fn send_position_update(&self, x: f64, y: f64) -> GameResult<()> {
         // 自分のIDがなければ送信しない
         if self.local_player_id.is_none() {
             return Ok(());
         }
         
-        let message = json!({
-            "type": "player_move",
-            "x": x,
-            "y": y
-        });
-        
-        self.send_message(&message)
+        self.send_message(&ClientMessage::PlayerMove { x, y })
     }
     
     /**
      * セルを開く要求を送信する
      * 
      * @param index 開くセルのインデックス
-     * @return 成功した場合はOk(()), エラーの場合はErr(JsValue)
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
      */
-    pub fn send_reveal_cell(&self, index: usize) -> Result<(), JsValue> {
-        let message = json!({
-            "type": "reveal_cell",
-            "index": index
-        });
-        
-        self.send_message(&message)
+    pub fn send_reveal_cell(&self, index: usize) -> GameResult<()> {
+        self.send_message(&ClientMessage::RevealCell { index })
     }
     
     /**
      * フラグをトグルする要求を送信する
      * 
      * @param index フラグを設定/解除するセルのインデックス
-     * @return 成功した場合はOk(()), エラーの場合はErr(JsValue)
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
      */
-    pub fn send_toggle_flag(&self, index: usize) -> Result<(), JsValue> {
-        let message = json!({
-            "type": "toggle_flag",
-            "index": index
-        });
-        
-        self.send_message(&message)
+    pub fn send_toggle_flag(&self, index: usize) -> GameResult<()> {
+        self.send_message(&ClientMessage::ToggleFlag { index })
     }
     
     /**
      * ゲームをリセットする要求を送信する
      * 
-     * @return 成功した場合はOk(()), エラーの場合はErr(JsValue)
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
+     */
+    pub fn send_reset_game(&self) -> GameResult<()> {
+        self.send_message(&ClientMessage::ResetGame)
+    }
+    
+    /**
+     * プレイヤー名・カーソルの色・アバターを登録する参加メッセージを送信する
+     * 
+     * サーバーは他のプレイヤーに新しい名前・色・アバターを通知します。
+     * 
+     * @param name プレイヤー名
+     * @param color カーソルの色（`#RRGGBB`形式）
+     * @param avatar アバター（空の場合はアバターなし）
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
+     */
+    pub fn send_join(&self, name: &str, color: &str, avatar: &str) -> GameResult<()> {
+        self.send_message(&ClientMessage::Join { name: name.to_string(), color: color.to_string(), avatar: avatar.to_string() })
+    }
+    
+    /**
+     * ロビーでの準備完了の状態を送信する
+     * 
+     * @param ready 準備完了ならtrue
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
+     */
+    pub fn send_ready(&self, ready: bool) -> GameResult<()> {
+        self.send_message(&ClientMessage::SetReady { ready })
+    }
+    
+    /**
+     * ホストとしてゲームの開始を要求する
+     * 
+     * サーバーは全員の準備完了を待たずにカウントダウンを開始します。
+     * 
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
+     */
+    pub fn send_start_game(&self) -> GameResult<()> {
+        self.send_message(&ClientMessage::StartGame)
+    }
+    
+    /**
+     * エモートを送信する
+     * 
+     * サーバーは他のプレイヤーにエモートを中継します。
+     * 
+     * @param emote エモートのキー
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
+     */
+    pub fn send_emote(&self, emote: &str) -> GameResult<()> {
+        self.send_message(&ClientMessage::Emote { emote: emote.to_string() })
+    }
+    
+    /**
+     * 再戦に投票する
+     * 
+     * 全員が投票するか時間切れになると、サーバーはゲームをリセットします。
+     * 
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
+     */
+    pub fn send_rematch_vote(&self) -> GameResult<()> {
+        self.send_message(&ClientMessage::RematchVote)
+    }
+    
+    /**
+     * ボード設定（サイズと地雷数）を送信する
+     * 
+     * ゲーム開始前であれば、サーバーはこの設定でボードを作り直します。
+     * 
+     * @param width ボードの幅
+     * @param height ボードの高さ
+     * @param mine_count 地雷の数
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
+     */
+    pub fn send_board_config(&self, width: usize, height: usize, mine_count: usize) -> GameResult<()> {
+        self.send_message(&ClientMessage::SetBoardConfig { board_width: width, board_height: height, mine_count })
+    }
+    
+    /**
+     * リーダーボードを要求する
+     * 
+     * サーバーは`leaderboard`メッセージで難易度ごとのベストタイムを返します。
+     * 
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
+     */
+    pub fn send_leaderboard_request(&self) -> GameResult<()> {
+        self.send_message(&ClientMessage::LeaderboardRequest)
+    }
+    
+    /**
+     * 現在のゲーム状態を要求する
+     * 
+     * サーバーは`state_sync`メッセージでボードとプレイヤーの状態を返します。
+     * タブがバックグラウンドから戻ったときのずれの解消に使います。
+     * 
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
+     */
+    pub fn send_sync_request(&self) -> GameResult<()> {
+        self.send_message(&ClientMessage::SyncRequest)
+    }
+    
+    /**
+     * ルームへの参加を要求する
+     * 
+     * サーバーは`room_joined`メッセージで参加したルームを返し、
+     * 続けて`state_sync`メッセージでルームのボードとプレイヤーの状態を送ります。
+     * 
+     * @param room ルームコード（無ければサーバーが作成する）
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
+     */
+    pub fn send_join_room(&self, room: &str) -> GameResult<()> {
+        self.send_message(&ClientMessage::JoinRoom { room: room.to_string() })
+    }
+    
+    /**
+     * 新しいルームの作成を要求する
+     * 
+     * サーバーはコードを決めてルームを作成し、`join_room`と同じく`room_joined`メッセージを返します。
+     * 
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
      */
-    pub fn send_reset_game(&self) -> Result<(), JsValue> {
-        let message = json!({
-            "type": "reset_game"
-        });
+    pub fn send_create_room(&self) -> GameResult<()> {
+        self.send_message(&ClientMessage::CreateRoom)
+    }
+    
+    /**
+     * 参加できるルームの一覧を要求する
+     * 
+     * サーバーは`room_list`メッセージで一覧を返します。
+     * 
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
+     */
+    pub fn send_list_rooms(&self) -> GameResult<()> {
+        self.send_message(&ClientMessage::ListRooms)
+    }
+    
+    /**
+     * 遅延を測るハートビートを送信する
+     * 
+     * サーバーは送信時刻をそのまま`heartbeat_ack`メッセージで返すため、受け取った時刻との差が往復時間になります。
+     * 
+     * @param sent_at 送信時刻（ミリ秒）
+     * @return 成功した場合はOk(()), エラーの場合はErr(GameError)
+     */
+    pub fn send_heartbeat(&self, sent_at: f64) -> GameResult<()> {
+        self.send_message(&ClientMessage::Heartbeat { sent_at })
+    }
+    
+    /**
+     * 全体の状態で受け取ったボードの版数を記録する
+     * 
+     * `init`・`state_sync`のゲーム状態の版数と、新しいボードの版数（0）を記録し、
+     * 取りこぼしによる全体の状態の要求を終えます。
+     * 
+     * @param revision ボードの版数
+     */
+    pub fn reset_board_revision(&mut self, revision: u32) {
+        self.board_revision = Some(revision);
+        self.awaiting_snapshot = false;
+    }
+    
+    /**
+     * ボードの差分の版数を確認し、取りこぼしていれば全体の状態を要求する
+     * 
+     * 差分は版数の順に1つずつ届くため、版数が飛んだ場合は途中の更新を取りこぼしています。
+     * その場合だけ`sync_request`を送り、`state_sync`が届くまでは要求し直しません。
+     * 
+     * @param revision 届いた差分の版数
+     * @return 確認した結果
+     */
+    pub fn check_board_revision(&mut self, revision: u32) -> RevisionCheck {
+        let previous = self.board_revision;
+        let check = match previous {
+            Some(current) if revision <= current => return RevisionCheck::Stale,
+            Some(current) if Some(revision) == current.checked_add(1) => RevisionCheck::InOrder,
+            _ => RevisionCheck::Missed,
+        };
+        self.board_revision = Some(revision);
         
-        self.send_message(&message)
+        if check == RevisionCheck::Missed && !self.awaiting_snapshot {
+            log_warn!("Board revision {} skipped (last {:?}), requesting a snapshot", revision, previous);
+            match self.send_sync_request() {
+                Ok(()) => self.awaiting_snapshot = true,
+                Err(e) => log_error!("Sync request send error: {:?}", e),
+            }
+        }
+        check
     }
     
     /**
@@ -206,4 +687,489 @@ impl NetworkManager {
     pub fn set_local_player_id(&mut self, id: String) {
         self.local_player_id = Some(id);
     }
-} 
\ No newline at end of file
+} 
+
+/**
+ * サーバー（またはピアツーピアの相手）との通信路
+ * 
+ * 受信したメッセージと接続状態の変化は、作成時に渡された受信キューに積みます。
+ */
+pub trait Transport {
+    /**
+     * テキストのメッセージを送る
+     * 
+     * @param text 送信するJSONの文字列
+     * @return 成功した場合はOk(()), 接続が開いていない場合はErr(GameError::Network)
+     */
+    fn send_text(&self, text: &str) -> GameResult<()>;
+    
+    /**
+     * ハンドラを外して接続を閉じる（以降は受信キューに何も積まない）
+     */
+    fn close(&mut self);
+    
+    /**
+     * ピアツーピアの通信路かどうか（切断してもゲームサーバーには接続し直さない）
+     */
+    fn is_peer_to_peer(&self) -> bool {
+        false
+    }
+}
+
+/**
+ * 通信路から受け取るメッセージの型
+ */
+pub trait IncomingMessage: Sized {
+    /**
+     * 受信したフレームをメッセージに変換する
+     * 
+     * @param frame 受信したフレーム
+     * @return 変換したメッセージ（不正な場合はNone）
+     */
+    fn from_frame(frame: &Frame) -> Option<Self>;
+}
+
+impl IncomingMessage for ServerMessage {
+    fn from_frame(frame: &Frame) -> Option<Self> {
+        parse_server_frame(frame)
+    }
+}
+
+impl IncomingMessage for ClientMessage {
+    fn from_frame(frame: &Frame) -> Option<Self> {
+        // クライアントからのメッセージはJSONのテキストのみ
+        let Frame::Text(text) = frame else {
+            log_warn!("Rejected binary client message");
+            return None;
+        };
+        match serde_json::from_str(text) {
+            Ok(message) => Some(message),
+            Err(e) => {
+                log_warn!("Rejected client message ({}): {}", e, text);
+                None
+            },
+        }
+    }
+}
+
+/**
+ * 受信したイベントのデータをフレームにする
+ * 
+ * @param data `MessageEvent`のデータ
+ * @return テキストかArrayBufferのフレーム（それ以外はNone）
+ */
+fn frame_of(data: &JsValue) -> Option<Frame> {
+    if let Some(txt) = data.dyn_ref::<js_sys::JsString>() {
+        Some(Frame::Text(String::from(txt)))
+    } else if let Some(buffer) = data.dyn_ref::<js_sys::ArrayBuffer>() {
+        Some(Frame::Binary(js_sys::Uint8Array::new(buffer).to_vec()))
+    } else {
+        log_warn!("Unsupported frame received: {:?}", data);
+        None
+    }
+}
+
+/**
+ * ゲームサーバーとのWebSocketの通信路
+ */
+pub struct WebSocketTransport {
+    socket: WebSocket, // WebSocketインスタンス
+}
+
+impl WebSocketTransport {
+    /// 接続のたびに`forget`するコールバックの数
+    pub const FORGOTTEN_CLOSURES: usize = 4;
+    
+    /**
+     * WebSocketサーバーに接続する
+     * 
+     * @param server_url 接続先のURL
+     * @param events 受信した出来事を積むキュー
+     * @return 作成した通信路、WebSocketを作成できない場合はErr(GameError)
+     */
+    pub fn open(server_url: &str, events: &NetworkEventQueue) -> GameResult<Self> {
+        let ws = WebSocket::new(server_url).map_err(GameError::network)?;
+        // バイナリフレームはBlobではなく同期的に読めるArrayBufferで受け取る
+        ws.set_binary_type(web_sys::BinaryType::Arraybuffer);
+
+        // onopen: 接続成功時のコールバック
+        let queue = Rc::clone(events);
+        let onopen_callback = Closure::wrap(Box::new(move || {
+            log_info!("WebSocket connected!");
+            update_connection_status(true);
+            queue.borrow_mut().push_back(NetworkEvent::Opened);
+        }) as Box<dyn FnMut()>);
+        ws.set_onopen(Some(onopen_callback.as_ref().unchecked_ref()));
+        onopen_callback.forget();
+
+        // onmessage: メッセージ受信時のコールバック（テキストかバイナリかはフレームの種類で判別する）
+        let queue = Rc::clone(events);
+        let onmessage_callback = Closure::wrap(Box::new(move |e: MessageEvent| {
+            // 不正なメッセージはここで捨て、キューには変換できたものだけを積む
+            if let Some(message) = frame_of(&e.data()).as_ref().and_then(parse_server_frame) {
+                queue.borrow_mut().push_back(NetworkEvent::Message(message));
+            }
+        }) as Box<dyn FnMut(MessageEvent)>);
+        ws.set_onmessage(Some(onmessage_callback.as_ref().unchecked_ref()));
+        onmessage_callback.forget();
+
+        // onerror: エラー発生時のコールバック
+        let onerror_callback = Closure::wrap(Box::new(move |e: web_sys::Event| {
+            log_error!("WebSocket error: {:?}", e);
+        }) as Box<dyn FnMut(web_sys::Event)>);
+        ws.set_onerror(Some(onerror_callback.as_ref().unchecked_ref()));
+        onerror_callback.forget();
+
+        // onclose: 接続終了時のコールバック
+        // `close`ではハンドラを外すため、ここに来るのは予期しない切断
+        let queue = Rc::clone(events);
+        let onclose_callback = Closure::wrap(Box::new(move |e: web_sys::CloseEvent| {
+            log_info!("WebSocket closed: code={}, reason={}", e.code(), e.reason());
+            update_connection_status(false);
+            queue.borrow_mut().push_back(NetworkEvent::Closed);
+        }) as Box<dyn FnMut(web_sys::CloseEvent)>);
+        ws.set_onclose(Some(onclose_callback.as_ref().unchecked_ref()));
+        onclose_callback.forget();
+
+        Ok(Self { socket: ws })
+    }
+}
+
+impl Transport for WebSocketTransport {
+    fn send_text(&self, text: &str) -> GameResult<()> {
+        if self.socket.ready_state() != WebSocket::OPEN {
+            return Err(GameError::Network("WebSocket is not open".to_string()));
+        }
+        self.socket.send_with_str(text).map_err(GameError::network)
+    }
+    
+    fn close(&mut self) {
+        self.socket.set_onopen(None);
+        self.socket.set_onmessage(None);
+        self.socket.set_onerror(None);
+        self.socket.set_onclose(None);
+        // 正常な切断（1000）として閉じる
+        if let Err(e) = self.socket.close_with_code(1000) {
+            log_warn!("WebSocket close error: {:?}", e);
+        }
+    }
+}
+
+/**
+ * WebRTCのデータチャネルによるピアツーピアの通信路
+ * 
+ * ホストはデータチャネルを作ってオファーを、参加する側はホストのデータチャネルを受け取ってアンサーを作ります。
+ * オファーとアンサーはゲームサーバーを介さず、プレイヤー同士でテキストとして受け渡します（手動のシグナリング）。
+ */
+pub struct PeerTransport {
+    connection: RtcPeerConnection,                // 相手との接続
+    channel: Rc<RefCell<Option<RtcDataChannel>>>, // データチャネル（参加する側は届くまでNone）
+}
+
+impl PeerTransport {
+    /// 接続のたびに`forget`するコールバックの数（データチャネルの4つと、参加する側の`ondatachannel`）
+    pub const FORGOTTEN_CLOSURES: usize = 5;
+    
+    /**
+     * ホストとして、相手に渡すオファーを作るための接続とデータチャネルを作成する
+     * 
+     * @param events 受信した出来事を積むキュー
+     * @return 作成した通信路、作成できない場合はErr(GameError)
+     */
+    pub fn offer<M: IncomingMessage + 'static>(events: &NetworkEventQueue<M>) -> GameResult<Self> {
+        let connection = RtcPeerConnection::new().map_err(GameError::network)?;
+        let channel = connection.create_data_channel(PEER_CHANNEL_LABEL);
+        attach_channel(&channel, events);
+        Ok(Self { connection, channel: Rc::new(RefCell::new(Some(channel))) })
+    }
+    
+    /**
+     * 参加する側として、ホストのデータチャネルを受け取る接続を作成する
+     * 
+     * @param events 受信した出来事を積むキュー
+     * @return 作成した通信路、作成できない場合はErr(GameError)
+     */
+    pub fn answer<M: IncomingMessage + 'static>(events: &NetworkEventQueue<M>) -> GameResult<Self> {
+        let connection = RtcPeerConnection::new().map_err(GameError::network)?;
+        let channel = Rc::new(RefCell::new(None));
+        
+        let slot = Rc::clone(&channel);
+        let queue = Rc::clone(events);
+        let ondatachannel_callback = Closure::wrap(Box::new(move |e: RtcDataChannelEvent| {
+            let channel = e.channel();
+            attach_channel(&channel, &queue);
+            *slot.borrow_mut() = Some(channel);
+        }) as Box<dyn FnMut(RtcDataChannelEvent)>);
+        connection.set_ondatachannel(Some(ondatachannel_callback.as_ref().unchecked_ref()));
+        ondatachannel_callback.forget();
+        
+        Ok(Self { connection, channel })
+    }
+    
+    /**
+     * シグナリングに使う接続を取得する
+     */
+    pub fn connection(&self) -> RtcPeerConnection {
+        self.connection.clone()
+    }
+}
+
+impl Transport for PeerTransport {
+    fn send_text(&self, text: &str) -> GameResult<()> {
+        match &*self.channel.borrow() {
+            Some(channel) if channel.ready_state() == RtcDataChannelState::Open => {
+                channel.send_with_str(text).map_err(GameError::network)
+            },
+            _ => Err(GameError::Network("data channel is not open".to_string())),
+        }
+    }
+    
+    fn close(&mut self) {
+        if let Some(channel) = self.channel.borrow_mut().take() {
+            channel.set_onopen(None);
+            channel.set_onmessage(None);
+            channel.set_onerror(None);
+            channel.set_onclose(None);
+            channel.close();
+        }
+        self.connection.set_ondatachannel(None);
+        self.connection.close();
+    }
+    
+    fn is_peer_to_peer(&self) -> bool {
+        true
+    }
+}
+
+/**
+ * データチャネルのイベントを受信キューに積むようにする
+ * 
+ * @param channel データチャネル
+ * @param events 受信した出来事を積むキュー
+ */
+fn attach_channel<M: IncomingMessage + 'static>(channel: &RtcDataChannel, events: &NetworkEventQueue<M>) {
+    channel.set_binary_type(RtcDataChannelType::Arraybuffer);
+    
+    let queue = Rc::clone(events);
+    let onopen_callback = Closure::wrap(Box::new(move || {
+        log_info!("Data channel opened");
+        queue.borrow_mut().push_back(NetworkEvent::Opened);
+    }) as Box<dyn FnMut()>);
+    channel.set_onopen(Some(onopen_callback.as_ref().unchecked_ref()));
+    onopen_callback.forget();
+    
+    let queue = Rc::clone(events);
+    let onmessage_callback = Closure::wrap(Box::new(move |e: MessageEvent| {
+        if let Some(message) = frame_of(&e.data()).as_ref().and_then(M::from_frame) {
+            queue.borrow_mut().push_back(NetworkEvent::Message(message));
+        }
+    }) as Box<dyn FnMut(MessageEvent)>);
+    channel.set_onmessage(Some(onmessage_callback.as_ref().unchecked_ref()));
+    onmessage_callback.forget();
+    
+    let queue = Rc::clone(events);
+    let onerror_callback = Closure::wrap(Box::new(move |e: web_sys::Event| {
+        log_error!("Data channel error: {:?}", e);
+        queue.borrow_mut().push_back(NetworkEvent::Error);
+    }) as Box<dyn FnMut(web_sys::Event)>);
+    channel.set_onerror(Some(onerror_callback.as_ref().unchecked_ref()));
+    onerror_callback.forget();
+    
+    let queue = Rc::clone(events);
+    let onclose_callback = Closure::wrap(Box::new(move || {
+        log_info!("Data channel closed");
+        queue.borrow_mut().push_back(NetworkEvent::Closed);
+    }) as Box<dyn FnMut()>);
+    channel.set_onclose(Some(onclose_callback.as_ref().unchecked_ref()));
+    onclose_callback.forget();
+}
+
+/**
+ * 手動のシグナリングで相手に渡す接続情報（SDP）を作る
+ * 
+ * ICEの候補を集め終わるまで待ち、候補を含めた1つのテキストにするため、
+ * 相手とは一度ずつテキストを受け渡すだけで接続できます。
+ * 
+ * @param connection 相手との接続
+ * @param offer trueならオファー、falseならアンサーを作る
+ * @return 相手に渡すSDPのテキスト
+ */
+pub async fn create_signal(connection: &RtcPeerConnection, offer: bool) -> Result<String, JsValue> {
+    let promise = if offer { connection.create_offer() } else { connection.create_answer() };
+    let description: RtcSessionDescriptionInit = JsFuture::from(promise).await?.unchecked_into();
+    JsFuture::from(connection.set_local_description(&description)).await?;
+    
+    for _ in 0..PEER_GATHER_ATTEMPTS {
+        if connection.ice_gathering_state() == RtcIceGatheringState::Complete {
+            break;
+        }
+        sleep_ms(PEER_GATHER_POLL_MS).await?;
+    }
+    connection.local_description()
+        .map(|description| description.sdp())
+        .ok_or_else(|| JsValue::from_str("local description is not set"))
+}
+
+/**
+ * 相手から受け取った接続情報（SDP）を設定する
+ * 
+ * @param connection 相手との接続
+ * @param sdp 相手から受け取ったSDPのテキスト
+ * @param offer trueならオファー、falseならアンサーとして設定する
+ * @return 成功した場合はOk(()), 不正なSDPの場合はErr(JsValue)
+ */
+pub async fn accept_signal(connection: &RtcPeerConnection, sdp: &str, offer: bool) -> Result<(), JsValue> {
+    let description = RtcSessionDescriptionInit::new(if offer { RtcSdpType::Offer } else { RtcSdpType::Answer });
+    description.set_sdp(sdp.trim());
+    JsFuture::from(connection.set_remote_description(&description)).await?;
+    Ok(())
+}
+
+/**
+ * 指定した時間だけ待つ
+ * 
+ * @param ms 待つ時間（ミリ秒）
+ */
+async fn sleep_ms(ms: i32) -> Result<(), JsValue> {
+    let window = web_sys::window().ok_or_else(|| JsValue::from_str("window is not available"))?;
+    let promise = js_sys::Promise::new(&mut |resolve, _| {
+        if let Err(e) = window.set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms) {
+            log_warn!("setTimeout error: {:?}", e);
+        }
+    });
+    JsFuture::from(promise).await.map(|_| ())
+}
+
+/**
+ * 受信したフレームをサーバーのメッセージに変換する
+ * 
+ * テキストはJSONとして、バイナリはボードの更新のバイナリ形式として変換し、
+ * 変換できないメッセージは警告を出して捨てます。
+ * 
+ * @param frame 受信したフレーム
+ * @return 変換したメッセージ（不正な場合はNone）
+ */
+pub fn parse_server_frame(frame: &Frame) -> Option<ServerMessage> {
+    let parsed = match frame {
+        Frame::Text(text) => ServerMessage::from_json(text),
+        Frame::Binary(bytes) => decode_server_message(bytes),
+    };
+    match parsed {
+        Ok(message) => {
+            // カーソルの移動は頻繁に届くためログに出さない
+            if !matches!(message, ServerMessage::PlayerMoved { .. }) {
+                log_trace!("Message received: {:?}", frame);
+            }
+            Some(message)
+        },
+        Err(e) => {
+            log_warn!("Rejected server message ({}): {:?}", e, frame);
+            None
+        },
+    }
+}
+
+/**
+ * ホストページから指定されたWebSocketサーバーのURLを確認する
+ * 
+ * 前後の空白を除き、`ws://`か`wss://`で始まりホスト名があるURLのみ受け付けます。
+ * 
+ * @param url 指定されたURL
+ * @return 空白を除いたURL（不正な場合はErr(GameError::Config)）
+ */
+pub fn parse_server_url(url: &str) -> GameResult<String> {
+    let url = url.trim();
+    let host = url.strip_prefix("wss://").or_else(|| url.strip_prefix("ws://"));
+    match host {
+        Some(host) if !host.is_empty() && !host.starts_with('/') && !host.contains(char::is_whitespace) => Ok(url.to_string()),
+        _ => Err(GameError::Config(format!("invalid server url: {}", url))),
+    }
+}
+
+/**
+ * 接続先のURLにクエリパラメータを付ける
+ * 
+ * 前のプレイヤーIDの引き継ぎ（`resume`、サーバーはIDが接続中のプレイヤーと重ならない場合のみ引き継ぐ）や
+ * バイナリ形式の要求（`binary`）に使います。
+ * 
+ * @param server_url 接続先のURL
+ * @param key パラメータ名
+ * @param value パラメータの値
+ * @return パラメータを付けたURL
+ */
+pub fn with_query_param(server_url: &str, key: &str, value: &str) -> String {
+    let separator = if server_url.contains('?') { '&' } else { '?' };
+    format!("{}{}{}={}", server_url, separator, key, value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_server_frame_rejects_unknown_types() {
+        let text = |text: &str| Frame::Text(text.to_string());
+        assert_eq!(
+            parse_server_frame(&text(r#"{"type":"player_left","id":"player_1"}"#)),
+            Some(ServerMessage::PlayerLeft { id: "player_1".to_string() })
+        );
+        assert_eq!(parse_server_frame(&text(r#"{"type":"unknown"}"#)), None);
+        assert_eq!(parse_server_frame(&text("{")), None);
+        assert_eq!(parse_server_frame(&Frame::Binary(vec![1, 9])), None);
+    }
+
+    #[test]
+    fn test_poll_messages_applies_queued_events_in_order() {
+        let mut network = NetworkManager::new();
+        let left = ServerMessage::PlayerLeft { id: "player_1".to_string() };
+        network.events.borrow_mut().extend([
+            NetworkEvent::Opened,
+            NetworkEvent::Message(left.clone()),
+            NetworkEvent::Closed,
+        ]);
+
+        // コールバックが積んだ出来事は取り出すまで反映されない
+        assert!(!network.is_connected);
+        assert_eq!(network.poll_messages(), vec![left]);
+        assert!(!network.is_connected);
+        assert!(network.take_connection_lost());
+        assert!(network.poll_messages().is_empty());
+    }
+
+    #[test]
+    fn test_traffic_log_records_both_directions() {
+        let server = crate::mock_server::MockServer::new(3, 3, &[8]);
+        let mut network = NetworkManager::new();
+        network.connect_mock(&server);
+        network.receive_mock();
+        network.send_sync_request().unwrap();
+        network.receive_mock();
+
+        let log: serde_json::Value = serde_json::from_str(&network.traffic_log_json()).unwrap();
+        let entries: Vec<(&str, &str)> = log.as_array().unwrap().iter()
+            .map(|entry| (entry["direction"].as_str().unwrap(), entry["message"]["type"].as_str().unwrap()))
+            .collect();
+        assert_eq!(entries.first(), Some(&("inbound", "init")));
+        assert!(entries.contains(&("outbound", "sync_request")));
+        assert_eq!(entries.last(), Some(&("inbound", "state_sync")));
+
+        // 切断しても記録は残す
+        network.disconnect();
+        assert_eq!(serde_json::from_str::<serde_json::Value>(&network.traffic_log_json()).unwrap(), log);
+    }
+
+    #[test]
+    fn test_parse_server_url() {
+        assert_eq!(parse_server_url(" wss://example.com/ws ").unwrap(), "wss://example.com/ws");
+        assert_eq!(parse_server_url("ws://localhost:8080").unwrap(), "ws://localhost:8080");
+        for url in ["", "http://example.com", "ws://", "wss:///path", "ws://exa mple.com", "example.com"] {
+            assert!(matches!(parse_server_url(url), Err(GameError::Config(_))), "{}", url);
+        }
+    }
+
+    #[test]
+    fn test_with_query_param() {
+        assert_eq!(with_query_param("ws://localhost:8080", "resume", "player_3"), "ws://localhost:8080?resume=player_3");
+        assert_eq!(with_query_param("wss://example.com/ws?room=abc", "resume", "player_3"), "wss://example.com/ws?room=abc&resume=player_3");
+    }
+}
\ No newline at end of file
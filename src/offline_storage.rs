@@ -0,0 +1,169 @@
+/**
+ * IndexedDBを使った非同期の保存領域
+ * 
+ * localStorageに収まらない大きさのデータ（オフラインゲームの保存データなど）を
+ * 文字列としてキーごとに保存・読み込みします。
+ * 結果はフレームの更新処理で受け取れるよう`OfflineStorageEvent`として共有のキューに積みます。
+ */
+use std::cell::RefCell;
+use std::rc::Rc;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+use web_sys::{IdbDatabase, IdbOpenDbRequest, IdbRequest, IdbTransactionMode};
+use crate::logger::log_error;
+
+/**
+ * データベース名
+ */
+const DATABASE_NAME: &str = "minesweeper";
+
+/**
+ * データベースのバージョン（オブジェクトストアを変えたら上げる）
+ */
+const DATABASE_VERSION: u32 = 1;
+
+/**
+ * 保存データを入れるオブジェクトストア名
+ */
+const STORE_NAME: &str = "saves";
+
+/**
+ * 非同期の保存・読み込みの結果
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub enum OfflineStorageEvent {
+    Saved,                  // 保存が完了した
+    Loaded(Option<String>), // 読み込みが完了した（保存データが無ければNone）
+    Failed,                 // 保存・読み込みに失敗した
+}
+
+/**
+ * 結果を受け取るキュー（非同期処理とゲーム状態で共有する）
+ */
+pub type OfflineStorageQueue = Rc<RefCell<Vec<OfflineStorageEvent>>>;
+
+/**
+ * IndexedDBのリクエストが完了するまで待つ
+ * 
+ * @param request 完了を待つリクエスト
+ * @return 成功した場合はリクエストの結果, 失敗した場合はErr(JsValue)
+ */
+async fn wait_for(request: &IdbRequest) -> Result<JsValue, JsValue> {
+    let promise = js_sys::Promise::new(&mut |resolve, reject| {
+        let success_request = request.clone();
+        let onsuccess = Closure::once_into_js(move || {
+            let result = success_request.result().unwrap_or(JsValue::UNDEFINED);
+            let _ = resolve.call1(&JsValue::NULL, &result);
+        });
+        let error_request = request.clone();
+        let onerror = Closure::once_into_js(move || {
+            let error = error_request.error().ok().flatten().map_or(JsValue::NULL, JsValue::from);
+            let _ = reject.call1(&JsValue::NULL, &error);
+        });
+        request.set_onsuccess(Some(onsuccess.unchecked_ref()));
+        request.set_onerror(Some(onerror.unchecked_ref()));
+    });
+    JsFuture::from(promise).await
+}
+
+/**
+ * データベースを開く（初回はオブジェクトストアを作成する）
+ * 
+ * @return 開いたデータベース, IndexedDBが使えない場合はErr(JsValue)
+ */
+async fn open_database() -> Result<IdbDatabase, JsValue> {
+    let factory = web_sys::window()
+        .ok_or_else(|| JsValue::from_str("window is not available"))?
+        .indexed_db()?
+        .ok_or_else(|| JsValue::from_str("IndexedDB is not available"))?;
+    let request: IdbOpenDbRequest = factory.open_with_u32(DATABASE_NAME, DATABASE_VERSION)?;
+    
+    let upgrade_request = request.clone();
+    let onupgradeneeded = Closure::once_into_js(move || {
+        if let Ok(db) = upgrade_request.result().and_then(|result| result.dyn_into::<IdbDatabase>()) {
+            if !db.object_store_names().contains(STORE_NAME) {
+                if let Err(e) = db.create_object_store(STORE_NAME) {
+                    log_error!("IndexedDB upgrade error: {:?}", e);
+                }
+            }
+        }
+    });
+    request.set_onupgradeneeded(Some(onupgradeneeded.unchecked_ref()));
+    
+    wait_for(&request).await?.dyn_into::<IdbDatabase>()
+}
+
+/**
+ * 文字列を保存する
+ * 
+ * @param key 保存キー
+ * @param value 保存する文字列
+ * @return 成功した場合はOk(()), エラーの場合はErr(JsValue)
+ */
+async fn put(key: &str, value: &str) -> Result<(), JsValue> {
+    let db = open_database().await?;
+    let store = db
+        .transaction_with_str_and_mode(STORE_NAME, IdbTransactionMode::Readwrite)?
+        .object_store(STORE_NAME)?;
+    wait_for(&store.put_with_key(&JsValue::from_str(value), &JsValue::from_str(key))?).await?;
+    db.close();
+    Ok(())
+}
+
+/**
+ * 文字列を読み込む
+ * 
+ * @param key 保存キー
+ * @return 保存されている文字列（無ければNone）, エラーの場合はErr(JsValue)
+ */
+async fn get(key: &str) -> Result<Option<String>, JsValue> {
+    let db = open_database().await?;
+    let store = db
+        .transaction_with_str_and_mode(STORE_NAME, IdbTransactionMode::Readonly)?
+        .object_store(STORE_NAME)?;
+    let value = wait_for(&store.get(&JsValue::from_str(key))?).await?;
+    db.close();
+    Ok(value.as_string())
+}
+
+/**
+ * 非同期で保存し、結果をキューに積む
+ * 
+ * @param queue 結果を受け取るキュー
+ * @param key 保存キー
+ * @param value 保存する文字列
+ */
+pub fn save(queue: &OfflineStorageQueue, key: &'static str, value: String) {
+    let queue = queue.clone();
+    spawn_local(async move {
+        let event = match put(key, &value).await {
+            Ok(()) => OfflineStorageEvent::Saved,
+            Err(e) => {
+                log_error!("IndexedDB save error: {:?}", e);
+                OfflineStorageEvent::Failed
+            }
+        };
+        queue.borrow_mut().push(event);
+    });
+}
+
+/**
+ * 非同期で読み込み、結果をキューに積む
+ * 
+ * @param queue 結果を受け取るキュー
+ * @param key 保存キー
+ */
+pub fn load(queue: &OfflineStorageQueue, key: &'static str) {
+    let queue = queue.clone();
+    spawn_local(async move {
+        let event = match get(key).await {
+            Ok(value) => OfflineStorageEvent::Loaded(value),
+            Err(e) => {
+                log_error!("IndexedDB load error: {:?}", e);
+                OfflineStorageEvent::Failed
+            }
+        };
+        queue.borrow_mut().push(event);
+    });
+}
\ No newline at end of file
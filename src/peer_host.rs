@@ -0,0 +1,489 @@
+/**
+ * ピアツーピアのホスト
+ *
+ * ゲームサーバーを使わずにLANや少人数で遊ぶとき、ホストのブラウザがサーバーの代わりを務めます。
+ * ホストのボードはオフラインのゲームと同じくローカルで動かし、参加者からの操作（`ClientMessage`）を
+ * ホストのゲームへの操作に変換して返し、ボードの変化はserver.jsと同じメッセージ（`ServerMessage`）で参加者に送ります。
+ * そのため参加者の側はゲームサーバーに接続した場合と同じ処理で動きます。
+ */
+use std::collections::HashMap;
+use crate::board::Board;
+use crate::models::CellValue;
+use crate::network::{NetworkEvent, NetworkEventQueue, Transport};
+use crate::protocol::{ClientMessage, ServerMessage, PlayerInfo, GameStateSnapshot};
+use crate::resources::{is_known_avatar, CURSOR_COLORS};
+use crate::logger::{log_info, log_warn};
+
+/// 参加者から見たホストのプレイヤーID
+pub const HOST_PLAYER_ID: &str = "host";
+
+/**
+ * 参加者
+ */
+struct PeerGuest {
+    info: PlayerInfo,                         // 他のプレイヤーに送るプレイヤーの情報
+    transport: Box<dyn Transport>,            // 参加者との通信路
+    events: NetworkEventQueue<ClientMessage>, // 参加者から届いた出来事
+    opened: bool,                             // 接続が開いて初期化メッセージを送ったかどうか
+}
+
+/**
+ * ホストのゲームに反映する参加者の操作
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub enum PeerAction {
+    Joined(PlayerInfo),                   // 参加した、または名前・色・アバターを変えた
+    Moved { id: String, x: f64, y: f64 }, // カーソルを動かした
+    Reveal { id: String, index: usize },  // セルを開いた
+    Flag { id: String, index: usize },    // フラグを切り替えた
+    Emote { id: String, emote: String },  // エモートを送った
+    Reset,                                // ゲームのリセットを求めた
+    Left(String),                         // 接続が切れた
+}
+
+/**
+ * ピアツーピアのホストの状態
+ *
+ * 参加者に最後に送ったボードの状態を覚えておき、`sync_board`でその差分を送ります。
+ */
+pub struct PeerHost {
+    guests: Vec<PeerGuest>, // 参加者（接続待ちを含む）
+    next_guest: u32,        // 次に割り当てる参加者IDの番号
+    revision: u32,          // ボードの版数（差分を送るたびに1つずつ増やす）
+    width: usize,           // 最後に送ったボードの幅
+    height: usize,          // 最後に送ったボードの高さ
+    revealed: Vec<bool>,    // 最後に送った開いたセル
+    flagged: Vec<bool>,     // 最後に送ったフラグ
+    game_over: bool,        // ゲームの終了を送ったかどうか
+}
+
+impl PeerHost {
+    /**
+     * ホストを始める
+     *
+     * @param board ホストのボード（この状態を参加者に送った状態とみなす）
+     */
+    pub fn new(board: &Board) -> Self {
+        Self {
+            guests: Vec::new(),
+            next_guest: 1,
+            revision: 0,
+            width: board.width,
+            height: board.height,
+            revealed: board.revealed.clone(),
+            flagged: board.flagged.clone(),
+            game_over: board.game_over,
+        }
+    }
+
+    /**
+     * 参加者を追加する（接続が開いたら初期化メッセージを送る）
+     *
+     * @param transport 参加者との通信路
+     * @param events 通信路が出来事を積むキュー
+     * @return 割り当てた参加者のプレイヤーID
+     */
+    pub fn add_guest(&mut self, transport: Box<dyn Transport>, events: NetworkEventQueue<ClientMessage>) -> String {
+        let number = self.next_guest;
+        self.next_guest += 1;
+        let id = format!("peer_{}", number);
+        let (_, color) = CURSOR_COLORS[number as usize % CURSOR_COLORS.len()];
+        let info = PlayerInfo {
+            id: id.clone(),
+            name: String::new(),
+            x: 0.0,
+            y: 0.0,
+            color: color.to_string(),
+            avatar: String::new(),
+        };
+        self.guests.push(PeerGuest { info, transport, events, opened: false });
+        id
+    }
+
+    /**
+     * 接続が開いている参加者の数
+     */
+    pub fn guest_count(&self) -> usize {
+        self.guests.iter().filter(|guest| guest.opened).count()
+    }
+
+    /**
+     * 参加者から届いた出来事を処理し、ホストのゲームに反映する操作を取り出す
+     *
+     * カーソルの移動や名前の変更など、ボードを変えない操作はここで他の参加者に送ります。
+     * ボードを変える操作はホストのゲームで反映してから`sync_board`で送ります。
+     *
+     * @param board ホストのボード（`sync_board`で送った後の状態）
+     * @param host 参加者に送るホストのプレイヤーの情報
+     * @return ホストのゲームに反映する操作（届いた順）
+     */
+    pub fn poll(&mut self, board: &Board, host: &PlayerInfo) -> Vec<PeerAction> {
+        let pending: Vec<(String, Vec<NetworkEvent<ClientMessage>>)> = self.guests.iter()
+            .map(|guest| (guest.info.id.clone(), guest.events.borrow_mut().drain(..).collect()))
+            .collect();
+
+        let mut actions = Vec::new();
+        for (id, events) in pending {
+            for event in events {
+                match event {
+                    NetworkEvent::Opened => actions.extend(self.open_guest(&id, board, host)),
+                    NetworkEvent::Message(message) => actions.extend(self.handle_message(&id, message, board, host)),
+                    NetworkEvent::Error => {},
+                    NetworkEvent::Closed => {
+                        self.remove_guest(&id);
+                        actions.push(PeerAction::Left(id.clone()));
+                        break;
+                    },
+                }
+            }
+        }
+        actions
+    }
+
+    /**
+     * 参加者の接続が開いたときに初期化メッセージを送り、他の参加者に参加を知らせる
+     */
+    fn open_guest(&mut self, id: &str, board: &Board, host: &PlayerInfo) -> Option<PeerAction> {
+        let players = self.players_except(id, host);
+        let game_state = self.snapshot(board);
+        let guest = self.guests.iter_mut().find(|guest| guest.info.id == id)?;
+        guest.opened = true;
+        let info = guest.info.clone();
+        log_info!("Peer guest joined: {}", id);
+
+        self.send_to(id, &ServerMessage::Init { player_id: id.to_string(), players, game_state });
+        self.broadcast_except(id, &ServerMessage::PlayerJoined { id: info.id.clone(), color: info.color.clone() });
+        Some(PeerAction::Joined(info))
+    }
+
+    /**
+     * 参加者から届いたメッセージを処理する（server.jsと同じ規則）
+     */
+    fn handle_message(&mut self, id: &str, message: ClientMessage, board: &Board, host: &PlayerInfo) -> Option<PeerAction> {
+        match message {
+            ClientMessage::PlayerMove { x, y } => {
+                let guest = self.guest_mut(id)?;
+                guest.info.x = x;
+                guest.info.y = y;
+                self.broadcast_except(id, &ServerMessage::PlayerMoved { id: id.to_string(), x, y });
+                Some(PeerAction::Moved { id: id.to_string(), x, y })
+            },
+            ClientMessage::RevealCell { index } => Some(PeerAction::Reveal { id: id.to_string(), index }),
+            ClientMessage::ToggleFlag { index } => Some(PeerAction::Flag { id: id.to_string(), index }),
+            ClientMessage::ResetGame => Some(PeerAction::Reset),
+            ClientMessage::Join { name, color, avatar } => {
+                let guest = self.guest_mut(id)?;
+                guest.info.name = name.clone();
+                guest.info.color = color.clone();
+                // 候補に無いアバターは受け付けない
+                let avatar_changed = is_known_avatar(&avatar);
+                if avatar_changed {
+                    guest.info.avatar = avatar.clone();
+                }
+                let info = guest.info.clone();
+
+                self.broadcast_except(id, &ServerMessage::PlayerRenamed { id: id.to_string(), name });
+                self.broadcast_except(id, &ServerMessage::PlayerRecolored { id: id.to_string(), color });
+                if avatar_changed {
+                    self.broadcast_except(id, &ServerMessage::PlayerAvatar { id: id.to_string(), avatar });
+                }
+                Some(PeerAction::Joined(info))
+            },
+            ClientMessage::Emote { emote } => {
+                self.broadcast_except(id, &ServerMessage::Emote { id: id.to_string(), emote: emote.clone() });
+                Some(PeerAction::Emote { id: id.to_string(), emote })
+            },
+            ClientMessage::SyncRequest => {
+                let players = self.players_except(id, host);
+                let game_state = self.snapshot(board);
+                self.send_to(id, &ServerMessage::StateSync { players, game_state });
+                None
+            },
+            ClientMessage::Heartbeat { sent_at } => {
+                self.send_to(id, &ServerMessage::HeartbeatAck { sent_at });
+                None
+            },
+            // ルーム・ロビー・ランキング・再戦・ボード設定はホストのゲームでは扱わない
+            _ => None,
+        }
+    }
+
+    /**
+     * ホストのボードの変化を参加者に送る
+     *
+     * 作り直されたボードは`game_reset`で、開いたセルとフラグは版数付きの差分で、
+     * ゲームの終了は全てのセルの値とともに送ります。
+     *
+     * @param board ホストのボード
+     * @param actor 変化を起こしたプレイヤーのID
+     */
+    pub fn sync_board(&mut self, board: &Board, actor: &str) {
+        // 大きさが変わったか、開いたセルが閉じられていればボードが作り直された
+        let rebuilt = board.width != self.width
+            || board.height != self.height
+            || self.revealed.iter().zip(&board.revealed).any(|(&before, &now)| before && !now);
+        if rebuilt {
+            self.revision = 0;
+            self.width = board.width;
+            self.height = board.height;
+            self.revealed = vec![false; board.revealed.len()];
+            self.flagged = vec![false; board.flagged.len()];
+            self.game_over = false;
+            self.broadcast(&ServerMessage::GameReset {
+                board_width: board.width,
+                board_height: board.height,
+                mine_count: board.mine_count,
+            });
+        }
+
+        let cells: Vec<(usize, i32)> = (0..board.revealed.len())
+            .filter(|&index| board.revealed[index] && !self.revealed[index])
+            .map(|index| (index, cell_value(board.cells[index])))
+            .collect();
+        if !cells.is_empty() {
+            for &(index, _) in &cells {
+                self.revealed[index] = true;
+            }
+            self.revision += 1;
+            self.broadcast(&ServerMessage::CellsRevealed { player_id: actor.to_string(), revision: self.revision, cells });
+        }
+
+        for index in 0..board.flagged.len() {
+            if board.flagged[index] != self.flagged[index] {
+                self.flagged[index] = board.flagged[index];
+                self.revision += 1;
+                self.broadcast(&ServerMessage::FlagToggled {
+                    player_id: actor.to_string(),
+                    revision: self.revision,
+                    index,
+                    flagged: board.flagged[index],
+                });
+            }
+        }
+
+        if board.game_over && !self.game_over {
+            self.game_over = true;
+            let cells: Vec<i32> = board.cells.iter().map(|&cell| cell_value(cell)).collect();
+            let all_cell_values: HashMap<String, i32> = cells.iter()
+                .enumerate()
+                .map(|(index, &value)| (index.to_string(), value))
+                .collect();
+            self.broadcast(&ServerMessage::GameOver { win: board.win, cells, all_cell_values });
+        }
+    }
+
+    /**
+     * ホストのカーソルの位置を参加者に送る
+     *
+     * @param x ボード上のX座標
+     * @param y ボード上のY座標
+     */
+    pub fn broadcast_host_move(&mut self, x: f64, y: f64) {
+        self.broadcast(&ServerMessage::PlayerMoved { id: HOST_PLAYER_ID.to_string(), x, y });
+    }
+
+    /**
+     * 全ての参加者との接続を閉じる
+     */
+    pub fn close(&mut self) {
+        for guest in &mut self.guests {
+            guest.transport.close();
+        }
+        self.guests.clear();
+    }
+
+    /**
+     * 参加者から見た現在のゲーム状態（開いたセルの値のみ含む）
+     */
+    fn snapshot(&self, board: &Board) -> GameStateSnapshot {
+        GameStateSnapshot {
+            board_width: board.width,
+            board_height: board.height,
+            mine_count: board.mine_count,
+            revealed: board.revealed.clone(),
+            flagged: board.flagged.clone(),
+            game_started: board.game_started,
+            game_over: board.game_over,
+            win: board.win,
+            cell_values: (0..board.cells.len())
+                .filter(|&index| board.revealed[index])
+                .map(|index| (index.to_string(), cell_value(board.cells[index])))
+                .collect(),
+            countdown_ends_at: None,
+            server_time: 0.0,
+            revision: self.revision,
+        }
+    }
+
+    /**
+     * 指定した参加者から見た他のプレイヤー（ホストと接続済みの参加者）
+     */
+    fn players_except(&self, id: &str, host: &PlayerInfo) -> Vec<PlayerInfo> {
+        std::iter::once(host.clone())
+            .chain(self.guests.iter().filter(|guest| guest.opened && guest.info.id != id).map(|guest| guest.info.clone()))
+            .collect()
+    }
+
+    fn guest_mut(&mut self, id: &str) -> Option<&mut PeerGuest> {
+        self.guests.iter_mut().find(|guest| guest.info.id == id)
+    }
+
+    /**
+     * 参加者を取り除き、他の参加者に退出を知らせる
+     */
+    fn remove_guest(&mut self, id: &str) {
+        let Some(position) = self.guests.iter().position(|guest| guest.info.id == id) else {
+            return;
+        };
+        let mut guest = self.guests.remove(position);
+        guest.transport.close();
+        log_info!("Peer guest left: {}", id);
+        if guest.opened {
+            self.broadcast(&ServerMessage::PlayerLeft { id: id.to_string() });
+        }
+    }
+
+    fn send_to(&self, id: &str, message: &ServerMessage) {
+        if let Some(guest) = self.guests.iter().find(|guest| guest.info.id == id) {
+            send(guest, message);
+        }
+    }
+
+    fn broadcast(&self, message: &ServerMessage) {
+        for guest in self.guests.iter().filter(|guest| guest.opened) {
+            send(guest, message);
+        }
+    }
+
+    fn broadcast_except(&self, id: &str, message: &ServerMessage) {
+        for guest in self.guests.iter().filter(|guest| guest.opened && guest.info.id != id) {
+            send(guest, message);
+        }
+    }
+}
+
+/**
+ * 参加者にメッセージを送る（送れなかった場合は警告のみ、切断は`poll`で処理する）
+ */
+fn send(guest: &PeerGuest, message: &ServerMessage) {
+    let result = serde_json::to_string(message)
+        .map_err(|e| e.to_string())
+        .and_then(|json| guest.transport.send_text(&json).map_err(|e| format!("{:?}", e)));
+    if let Err(e) = result {
+        log_warn!("Peer send error ({}): {}", guest.info.id, e);
+    }
+}
+
+/**
+ * セルの値をserver.jsと同じ数値にする（地雷は-1）
+ */
+fn cell_value(cell: CellValue) -> i32 {
+    match cell {
+        CellValue::Mine => -1,
+        CellValue::Empty(count) => count as i32,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use crate::error::GameResult;
+
+    /// 送ったメッセージを記録する通信路
+    struct RecordingTransport {
+        sent: Rc<RefCell<Vec<ServerMessage>>>,
+    }
+
+    impl Transport for RecordingTransport {
+        fn send_text(&self, text: &str) -> GameResult<()> {
+            self.sent.borrow_mut().push(ServerMessage::from_json(text)?);
+            Ok(())
+        }
+
+        fn close(&mut self) {}
+    }
+
+    fn add_guest(host: &mut PeerHost) -> (String, NetworkEventQueue<ClientMessage>, Rc<RefCell<Vec<ServerMessage>>>) {
+        let sent = Rc::new(RefCell::new(Vec::new()));
+        let events = NetworkEventQueue::<ClientMessage>::default();
+        let id = host.add_guest(Box::new(RecordingTransport { sent: Rc::clone(&sent) }), Rc::clone(&events));
+        (id, events, sent)
+    }
+
+    fn host_info() -> PlayerInfo {
+        PlayerInfo { id: HOST_PLAYER_ID.to_string(), name: "Host".to_string(), x: 0.0, y: 0.0, color: "#1565C0".to_string(), avatar: String::new() }
+    }
+
+    #[test]
+    fn test_guest_receives_init_and_board_deltas() {
+        let mut board = Board::new(3, 3, 1, 10.0);
+        board.load_mines(&[4]);
+        let mut host = PeerHost::new(&board);
+        let (id, events, sent) = add_guest(&mut host);
+
+        // 接続が開くまでは何も送らない
+        host.sync_board(&board, HOST_PLAYER_ID);
+        assert!(sent.borrow().is_empty());
+
+        events.borrow_mut().push_back(NetworkEvent::Opened);
+        events.borrow_mut().push_back(NetworkEvent::Message(ClientMessage::RevealCell { index: 0 }));
+        let actions = host.poll(&board, &host_info());
+        assert!(matches!(&actions[0], PeerAction::Joined(info) if info.id == id));
+        assert_eq!(actions[1], PeerAction::Reveal { id: id.clone(), index: 0 });
+        assert!(matches!(&sent.borrow()[0], ServerMessage::Init { player_id, players, .. } if *player_id == id && players[0].id == HOST_PLAYER_ID));
+
+        // ホストのゲームで開いた結果を版数付きの差分で送る
+        board.game_started = true;
+        board.reveal_cell(0).unwrap();
+        host.sync_board(&board, &id);
+        let revealed = board.revealed.iter().filter(|&&revealed| revealed).count();
+        match &sent.borrow()[1] {
+            ServerMessage::CellsRevealed { player_id, revision, cells } => {
+                assert_eq!(player_id, &id);
+                assert_eq!(*revision, 1);
+                assert_eq!(cells.len(), revealed);
+            },
+            other => panic!("unexpected message: {:?}", other),
+        }
+
+        // 変化がなければ何も送らない
+        host.sync_board(&board, HOST_PLAYER_ID);
+        assert_eq!(sent.borrow().len(), 2);
+
+        // フラグは1つずつ、作り直したボードは`game_reset`で送る
+        board.toggle_flag(8);
+        host.sync_board(&board, HOST_PLAYER_ID);
+        assert!(matches!(sent.borrow()[2], ServerMessage::FlagToggled { revision: 2, index: 8, flagged: true, .. }));
+        board.load_mines(&[4]);
+        host.sync_board(&board, HOST_PLAYER_ID);
+        assert!(matches!(sent.borrow()[3], ServerMessage::GameReset { board_width: 3, board_height: 3, mine_count: 1 }));
+    }
+
+    #[test]
+    fn test_guest_moves_are_relayed_and_leaving_is_announced() {
+        let board = Board::new(3, 3, 1, 10.0);
+        let mut host = PeerHost::new(&board);
+        let (alice, alice_events, _) = add_guest(&mut host);
+        let (bob, bob_events, bob_sent) = add_guest(&mut host);
+        alice_events.borrow_mut().push_back(NetworkEvent::Opened);
+        bob_events.borrow_mut().push_back(NetworkEvent::Opened);
+        host.poll(&board, &host_info());
+        assert_eq!(host.guest_count(), 2);
+
+        alice_events.borrow_mut().push_back(NetworkEvent::Message(ClientMessage::PlayerMove { x: 4.0, y: 5.0 }));
+        alice_events.borrow_mut().push_back(NetworkEvent::Closed);
+        let actions = host.poll(&board, &host_info());
+        assert_eq!(actions, vec![
+            PeerAction::Moved { id: alice.clone(), x: 4.0, y: 5.0 },
+            PeerAction::Left(alice.clone()),
+        ]);
+        assert_eq!(host.guest_count(), 1);
+
+        let sent = bob_sent.borrow();
+        assert!(sent.contains(&ServerMessage::PlayerMoved { id: alice.clone(), x: 4.0, y: 5.0 }));
+        assert_eq!(sent.last(), Some(&ServerMessage::PlayerLeft { id: alice }));
+        assert!(!sent.iter().any(|message| matches!(message, ServerMessage::PlayerJoined { id, .. } if *id == bob)));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,460 @@
+/**
+ * クライアントとサーバーの間でやり取りするメッセージの型定義
+ * 
+ * どのメッセージもJSONの`type`フィールドで種類を表します。
+ * `Tsify`で TypeScript の型定義（.d.ts）も生成されるため、
+ * ホストページやNode.jsのサーバーからも同じ型を参照できます。
+ */
+use std::collections::HashMap;
+use serde::{Serialize, Deserialize};
+use tsify::Tsify;
+use crate::error::{GameError, GameResult};
+
+/**
+ * クライアントからサーバーに送るメッセージ
+ */
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Tsify)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    /// カーソルの位置
+    PlayerMove { x: f64, y: f64 },
+    /// セルを開く
+    RevealCell { index: usize },
+    /// フラグを切り替える
+    ToggleFlag { index: usize },
+    /// ゲームをリセットする
+    ResetGame,
+    /// 名前・カーソルの色・アバターを登録する（アバターの無い古いクライアントも受け付ける）
+    Join {
+        name: String,
+        color: String,
+        #[serde(default)]
+        avatar: String,
+    },
+    /// ロビーでの準備完了を切り替える
+    SetReady { ready: bool },
+    /// ホストがゲームを開始する
+    StartGame,
+    /// エモートを送る
+    Emote { emote: String },
+    /// 再戦に投票する
+    RematchVote,
+    /// 開始前のボード設定を変更する
+    #[serde(rename_all = "camelCase")]
+    SetBoardConfig { board_width: usize, board_height: usize, mine_count: usize },
+    /// ランキングを要求する
+    LeaderboardRequest,
+    /// 現在のゲーム状態を要求する
+    SyncRequest,
+    /// ルームに参加する（無ければサーバーが作成する）
+    JoinRoom { room: String },
+    /// 新しいコードのルームを作成して参加する
+    CreateRoom,
+    /// 参加できるルームの一覧を要求する
+    ListRooms,
+    /// 遅延の計測（サーバーは`sent_at`をそのまま`heartbeat_ack`で返す）
+    #[serde(rename_all = "camelCase")]
+    Heartbeat { sent_at: f64 },
+    /// 退出する（ページを離れる前に送り、サーバーはすぐに他のプレイヤーに知らせる）
+    Leave,
+}
+
+/**
+ * サーバーからクライアントに送られるメッセージ
+ */
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Tsify)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerMessage {
+    /// 接続直後の初期化
+    #[serde(rename_all = "camelCase")]
+    Init { player_id: String, players: Vec<PlayerInfo>, game_state: GameStateSnapshot },
+    /// `sync_request`への応答
+    #[serde(rename_all = "camelCase")]
+    StateSync { players: Vec<PlayerInfo>, game_state: GameStateSnapshot },
+    /// ボードが作り直された
+    #[serde(rename_all = "camelCase")]
+    GameReset { board_width: usize, board_height: usize, mine_count: usize },
+    /// 再戦投票の状況
+    #[serde(rename_all = "camelCase")]
+    RematchState { votes: Vec<String>, total: usize, deadline: Option<f64>, server_time: f64 },
+    /// ロビーの状態
+    #[serde(rename_all = "camelCase")]
+    LobbyState { active: bool, host_id: Option<String>, players: Vec<LobbyPlayerInfo> },
+    /// 開始前のカウントダウン
+    #[serde(rename_all = "camelCase")]
+    Countdown { ends_at: Option<f64>, server_time: f64 },
+    /// プレイヤーが参加した
+    PlayerJoined { id: String, color: String },
+    /// プレイヤーが移動した
+    PlayerMoved { id: String, x: f64, y: f64 },
+    /// プレイヤーが名前を設定した
+    PlayerRenamed { id: String, name: String },
+    /// プレイヤーがカーソルの色を設定した
+    PlayerRecolored { id: String, color: String },
+    /// プレイヤーがアバターを設定した（空の場合はアバターなし）
+    PlayerAvatar { id: String, avatar: String },
+    /// サーバーが測ったプレイヤーの通信の遅延（ミリ秒）
+    PlayerPing { id: String, ping: u32 },
+    /// プレイヤーが退出した
+    PlayerLeft { id: String },
+    /// エモート
+    Emote { id: String, emote: String },
+    /// セルが開かれた（cellsは開いた順の(インデックス, 値)の組、地雷は-1）
+    /// 
+    /// `revision`はボードの版数で、ボードを変えるメッセージごとに1つずつ増えます。
+    #[serde(rename_all = "camelCase")]
+    CellsRevealed { player_id: String, revision: u32, cells: Vec<(usize, i32)> },
+    /// ゲームが終了した（全てのセルの値を含む）
+    #[serde(rename_all = "camelCase")]
+    GameOver { win: bool, cells: Vec<i32>, all_cell_values: HashMap<String, i32> },
+    /// フラグが切り替えられた（`revision`はボードの版数）
+    #[serde(rename_all = "camelCase")]
+    FlagToggled { player_id: String, revision: u32, index: usize, flagged: bool },
+    /// 難易度のキーごとのランキング
+    Leaderboard { entries: HashMap<String, Vec<LeaderboardEntryInfo>> },
+    /// ルームへの参加が完了した（続けて`state_sync`でルームの状態が送られる）
+    RoomJoined { room: String },
+    /// `list_rooms`への応答
+    RoomList { rooms: Vec<RoomInfo> },
+    /// `heartbeat`への応答（`sent_at`はクライアントが送った時刻）
+    #[serde(rename_all = "camelCase")]
+    HeartbeatAck { sent_at: f64 },
+}
+
+impl ServerMessage {
+    /**
+     * サーバーから届いたテキストをメッセージに変換する
+     * 
+     * 未知の種類やフィールドの型が合わないメッセージはここでまとめて拒否するため、
+     * 受信側は変換できたメッセージの種類ごとの処理だけを書けば済みます。
+     * 
+     * @param text 受信したJSONの文字列
+     * @return 変換したメッセージ（不正な場合はErr(GameError::Network)）
+     */
+    pub fn from_json(text: &str) -> GameResult<Self> {
+        serde_json::from_str(text).map_err(|e| GameError::Network(format!("invalid server message: {}", e)))
+    }
+}
+
+/**
+ * 他のプレイヤーの情報（初期化・同期時に送られる）
+ */
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Tsify)]
+pub struct PlayerInfo {
+    pub id: String,     // プレイヤーID
+    pub name: String,   // プレイヤー名（未設定なら空文字列）
+    pub x: f64,         // X座標
+    pub y: f64,         // Y座標
+    pub color: String,  // カーソルの色
+    #[serde(default)]
+    pub avatar: String, // アバター（未設定なら空文字列）
+}
+
+/**
+ * ロビーのプレイヤーの情報
+ */
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Tsify)]
+pub struct LobbyPlayerInfo {
+    pub id: String,   // プレイヤーID
+    pub name: String, // プレイヤー名
+    pub ready: bool,  // 準備完了かどうか
+}
+
+/**
+ * ランキングの1件
+ */
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Tsify)]
+#[serde(rename_all = "camelCase")]
+pub struct LeaderboardEntryInfo {
+    pub name: String,  // プレイヤー名
+    pub time_ms: f64,  // クリアタイム（ミリ秒）
+}
+
+/**
+ * ルーム一覧の1件
+ */
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Tsify)]
+#[serde(rename_all = "camelCase")]
+pub struct RoomInfo {
+    pub code: String,        // ルームコード
+    pub players: usize,      // 参加しているプレイヤー数
+    pub board_width: usize,  // ボードの幅
+    pub board_height: usize, // ボードの高さ
+    pub mine_count: usize,   // 地雷の数
+    pub in_lobby: bool,      // ロビー中（ゲーム開始前）かどうか
+}
+
+/**
+ * ゲーム状態（開かれているセルの値のみ含む）
+ */
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Tsify)]
+#[serde(rename_all = "camelCase")]
+pub struct GameStateSnapshot {
+    pub board_width: usize,                // ボードの幅
+    pub board_height: usize,               // ボードの高さ
+    pub mine_count: usize,                 // 地雷の数
+    pub revealed: Vec<bool>,               // 開かれているかどうか
+    pub flagged: Vec<bool>,                // フラグが立っているかどうか
+    pub game_started: bool,                // ゲームが開始されているかどうか
+    pub game_over: bool,                   // ゲームが終了しているかどうか
+    pub win: bool,                         // 勝利したかどうか
+    pub cell_values: HashMap<String, i32>, // 開かれているセルの値（地雷は-1）
+    pub countdown_ends_at: Option<f64>,    // カウントダウンの終了時刻（サーバー時刻）
+    pub server_time: f64,                  // 送信時のサーバー時刻
+    pub revision: u32,                     // ボードの版数（新しいボードは0から始まる）
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_client_messages_use_server_field_names() {
+        let message = ClientMessage::SetBoardConfig { board_width: 9, board_height: 9, mine_count: 10 };
+        assert_eq!(
+            serde_json::to_value(&message).unwrap(),
+            json!({ "type": "set_board_config", "boardWidth": 9, "boardHeight": 9, "mineCount": 10 })
+        );
+        assert_eq!(serde_json::to_value(ClientMessage::RematchVote).unwrap(), json!({ "type": "rematch_vote" }));
+        assert_eq!(
+            serde_json::to_value(ClientMessage::JoinRoom { room: "ABC123".to_string() }).unwrap(),
+            json!({ "type": "join_room", "room": "ABC123" })
+        );
+        assert_eq!(serde_json::to_value(ClientMessage::CreateRoom).unwrap(), json!({ "type": "create_room" }));
+        assert_eq!(serde_json::to_value(ClientMessage::ListRooms).unwrap(), json!({ "type": "list_rooms" }));
+        assert_eq!(
+            serde_json::to_value(ClientMessage::Heartbeat { sent_at: 1500.0 }).unwrap(),
+            json!({ "type": "heartbeat", "sentAt": 1500.0 })
+        );
+        assert_eq!(serde_json::to_value(ClientMessage::Leave).unwrap(), json!({ "type": "leave" }));
+    }
+
+    #[test]
+    fn test_server_messages_parse_from_server_json() {
+        let message: ServerMessage = serde_json::from_value(json!({
+            "type": "init",
+            "playerId": "player_1",
+            "players": [{ "id": "player_2", "name": "", "x": 0, "y": 0, "color": "#FF0000" }],
+            "gameState": {
+                "boardWidth": 2, "boardHeight": 1, "mineCount": 1,
+                "revealed": [true, false], "flagged": [false, false],
+                "gameStarted": true, "gameOver": false, "win": false,
+                "cellValues": { "0": 1 }, "countdownEndsAt": null, "serverTime": 1000, "revision": 3
+            }
+        })).unwrap();
+        let ServerMessage::Init { player_id, players, game_state } = message else {
+            panic!("init以外に解釈された");
+        };
+        assert_eq!(player_id, "player_1");
+        assert_eq!(players[0].color, "#FF0000");
+        assert_eq!(game_state.cell_values["0"], 1);
+        assert_eq!(game_state.revision, 3);
+
+        let message: ServerMessage = serde_json::from_value(json!({
+            "type": "flag_toggled", "playerId": "player_2", "revision": 4, "index": 1, "flagged": true
+        })).unwrap();
+        assert_eq!(message, ServerMessage::FlagToggled { player_id: "player_2".to_string(), revision: 4, index: 1, flagged: true });
+
+        let message: ServerMessage = serde_json::from_value(json!({
+            "type": "cells_revealed", "playerId": "player_2", "revision": 5, "cells": [[2, 0], [3, -1]]
+        })).unwrap();
+        assert_eq!(message, ServerMessage::CellsRevealed { player_id: "player_2".to_string(), revision: 5, cells: vec![(2, 0), (3, -1)] });
+
+        let message: ServerMessage = serde_json::from_value(json!({
+            "type": "room_list",
+            "rooms": [{ "code": "ABC123", "players": 2, "boardWidth": 9, "boardHeight": 9, "mineCount": 10, "inLobby": true }]
+        })).unwrap();
+        let ServerMessage::RoomList { rooms } = message else {
+            panic!("room_list以外に解釈された");
+        };
+        assert_eq!(rooms[0].code, "ABC123");
+        assert!(rooms[0].in_lobby);
+    }
+
+    #[test]
+    fn test_malformed_server_messages_are_rejected() {
+        assert!(ServerMessage::from_json(r#"{"type":"player_moved","id":"player_1","x":1.5,"y":2}"#).is_ok());
+
+        // 未知の種類・必須のフィールドの欠け・型の違い・JSON以外はまとめて拒否される
+        for text in [
+            r#"{"type":"unknown","id":"player_1"}"#,
+            r#"{"id":"player_1"}"#,
+            r#"{"type":"player_left"}"#,
+            r#"{"type":"flag_toggled","playerId":"player_1","index":-1,"flagged":true}"#,
+            r#"{"type":"lobby_state","active":true,"hostId":null,"players":[{"name":"broken"}]}"#,
+            r#"{"type":"leaderboard","entries":{"easy":[{"name":"broken"}]}}"#,
+            "not json",
+        ] {
+            let error = ServerMessage::from_json(text).unwrap_err();
+            assert!(matches!(error, GameError::Network(_)), "{}: {:?}", text, error);
+        }
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn fuzz_server_message_bytes_never_panic(bytes in proptest::collection::vec(proptest::prelude::any::<u8>(), 0..256)) {
+            // 壊れたデータはエラーになるだけで、パニックしない
+            if let Ok(json) = serde_json::from_slice::<serde_json::Value>(&bytes) {
+                let _ = serde_json::from_value::<ServerMessage>(json);
+            }
+            let _ = serde_json::from_slice::<ServerMessage>(&bytes);
+        }
+
+        #[test]
+        fn fuzz_server_messages_round_trip_or_fail(message in fuzz::server_message()) {
+            // 解釈できたメッセージは同じ内容に書き戻せる
+            if let Ok(parsed) = serde_json::from_value::<ServerMessage>(message) {
+                let json = serde_json::to_value(&parsed).unwrap();
+                proptest::prop_assert_eq!(serde_json::from_value::<ServerMessage>(json).unwrap(), parsed);
+            }
+        }
+
+        #[test]
+        fn fuzz_resources_accept_parsed_messages(message in fuzz::server_message(), local_now in -1.0e12f64..1.0e12) {
+            // 変換できたメッセージはゲーム状態以外の受信処理にもそのまま渡せる
+            match serde_json::from_value::<ServerMessage>(message) {
+                Ok(ServerMessage::LobbyState { active, host_id, players }) => {
+                    let mut lobby = crate::resources::LobbyResource::new();
+                    lobby.update(active, host_id.as_deref(), &players);
+                    proptest::prop_assert_eq!(lobby.players().len(), players.len());
+                },
+                Ok(ServerMessage::RematchState { votes, total, deadline, server_time }) => {
+                    let mut rematch = crate::resources::RematchResource::new();
+                    rematch.update(&votes, total, deadline, server_time, local_now);
+                    proptest::prop_assert_eq!(rematch.progress(), (votes.len(), total));
+                    let _ = rematch.remaining_seconds(local_now);
+                },
+                Ok(ServerMessage::Leaderboard { entries }) => {
+                    let mut leaderboard = crate::resources::LeaderboardResource::new();
+                    leaderboard.update(&entries);
+                    proptest::prop_assert!(leaderboard.is_loaded());
+                },
+                _ => {},
+            }
+        }
+    }
+}
+
+/**
+ * サーバーから届く可能性のある不正なデータを生成するファズテスト用の戦略
+ */
+#[cfg(test)]
+pub(crate) mod fuzz {
+    use std::collections::HashMap;
+    use proptest::prelude::*;
+    use serde_json::{json, Value};
+    use super::{GameStateSnapshot, ServerMessage};
+
+    /// サーバーのメッセージに現れるフィールド名（不正な値を入れて使う）
+    pub(crate) const FIELD_NAMES: &[&str] = &[
+        "type", "playerId", "players", "gameState", "boardWidth", "boardHeight", "mineCount",
+        "revealed", "flagged", "gameStarted", "gameOver", "win", "cellValues", "countdownEndsAt",
+        "serverTime", "cells", "values", "allCellValues", "index", "id", "x", "y", "color", "name",
+        "votes", "total", "deadline", "active", "hostId", "ready", "endsAt", "entries", "room",
+        "rooms", "code", "inLobby", "avatar", "revision", "sentAt",
+    ];
+
+    /// サーバーのメッセージの種類（未知の種類も含む）
+    pub(crate) const MESSAGE_TYPES: &[&str] = &[
+        "init", "state_sync", "game_reset", "rematch_state", "lobby_state", "countdown",
+        "player_joined", "player_moved", "player_renamed", "player_recolored", "player_avatar", "player_ping",
+        "player_left", "emote", "cells_revealed", "game_over", "flag_toggled", "leaderboard",
+        "room_joined", "room_list", "heartbeat_ack", "unknown",
+    ];
+
+    /// 境界付近の数値を多めに含む任意のJSONの数値
+    fn number() -> impl Strategy<Value = Value> {
+        prop_oneof![
+            (-3i64..12).prop_map(Value::from),
+            any::<i64>().prop_map(Value::from),
+            any::<u64>().prop_map(Value::from),
+            any::<f64>().prop_map(|value| json!(value)),
+            Just(json!(u64::MAX)),
+            Just(json!(i64::MIN)),
+        ]
+    }
+
+    /// 任意のJSONの値（キーはメッセージのフィールド名かセルのインデックスになりやすい）
+    pub(crate) fn json_value() -> impl Strategy<Value = Value> {
+        let leaf = prop_oneof![
+            Just(Value::Null),
+            any::<bool>().prop_map(Value::Bool),
+            number(),
+            ".{0,8}".prop_map(Value::String),
+        ];
+        leaf.prop_recursive(4, 64, 8, |inner| {
+            let key = prop_oneof![
+                proptest::sample::select(FIELD_NAMES).prop_map(str::to_string),
+                (0usize..40).prop_map(|index| index.to_string()),
+                ".{0,4}",
+            ];
+            prop_oneof![
+                proptest::collection::vec(inner.clone(), 0..16).prop_map(Value::Array),
+                proptest::collection::btree_map(key, inner, 0..12)
+                    .prop_map(|map| Value::Object(map.into_iter().collect())),
+            ]
+        })
+    }
+
+    /// 範囲外のインデックスを多めに含むセルのインデックスの文字列と値の組
+    fn cell_values() -> impl Strategy<Value = HashMap<String, i32>> {
+        let key = prop_oneof![
+            (0usize..200).prop_map(|index| index.to_string()),
+            ".{0,4}",
+        ];
+        proptest::collection::hash_map(key, prop_oneof![-3i32..12, any::<i32>()], 0..16)
+    }
+
+    /// 型は正しいが、サイズや配列の長さ・セルの値がボードに合わないゲーム状態
+    pub(crate) fn game_state_snapshot() -> impl Strategy<Value = GameStateSnapshot> {
+        (
+            (prop_oneof![0usize..40, any::<usize>()], prop_oneof![0usize..40, any::<usize>()], any::<usize>()),
+            proptest::collection::vec(any::<bool>(), 0..200),
+            proptest::collection::vec(any::<bool>(), 0..200),
+            any::<(bool, bool, bool)>(),
+            cell_values(),
+            any::<u32>(),
+        ).prop_map(|((board_width, board_height, mine_count), revealed, flagged, (game_started, game_over, win), cell_values, revision)| {
+            GameStateSnapshot {
+                board_width,
+                board_height,
+                mine_count,
+                revealed,
+                flagged,
+                game_started,
+                game_over,
+                win,
+                cell_values,
+                countdown_ends_at: None,
+                server_time: 0.0,
+                revision,
+            }
+        })
+    }
+
+    /// ボードを更新するサーバーのメッセージ（値はボードに合わないことがある）
+    pub(crate) fn board_message() -> impl Strategy<Value = ServerMessage> {
+        prop_oneof![
+            (any::<u32>(), proptest::collection::vec((prop_oneof![0usize..200, any::<usize>()], prop_oneof![-3i32..12, any::<i32>()]), 0..16))
+                .prop_map(|(revision, cells)| ServerMessage::CellsRevealed { player_id: "player_1".to_string(), revision, cells }),
+            (any::<bool>(), cell_values())
+                .prop_map(|(win, all_cell_values)| ServerMessage::GameOver { win, cells: Vec::new(), all_cell_values }),
+            game_state_snapshot()
+                .prop_map(|game_state| ServerMessage::StateSync { players: Vec::new(), game_state }),
+        ]
+    }
+
+    /// 種類だけ正しく、フィールドの値が不正なサーバーのメッセージ
+    pub(crate) fn server_message() -> impl Strategy<Value = Value> {
+        (
+            proptest::sample::select(MESSAGE_TYPES),
+            proptest::collection::btree_map(proptest::sample::select(FIELD_NAMES), json_value(), 0..10),
+        ).prop_map(|(message_type, fields)| {
+            let mut message: serde_json::Map<String, Value> =
+                fields.into_iter().map(|(key, value)| (key.to_string(), value)).collect();
+            message.insert("type".to_string(), Value::from(message_type));
+            Value::Object(message)
+        })
+    }
+}
\ No newline at end of file
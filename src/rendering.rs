@@ -3,23 +3,222 @@
  */
 use wasm_bindgen::JsValue;
 use web_sys::CanvasRenderingContext2d;
+use std::cell::{Ref, RefCell};
 use std::collections::HashMap;
 
 use crate::models::{CellValue, Player, Screen};
-use crate::components::Position;
+use crate::components::{GlobalPosition, Position, UIElement};
+use crate::entities::EntityManager;
+use crate::ecs::HUD_TAG;
 use crate::board::Board;
-use crate::js_bindings::log;
+use crate::logger::{LogLevel, LogRecord};
+use crate::error::ErrorReport;
+use crate::resources::{AccessibilityResource, AccessibilityItem, CameraResource, Color, CustomBoardResource, CustomBoardField, Emote, EmoteResource, LobbyResource, PlayerStateResource, RoomStateResource, RoomSelectButton, EMOTE_OPTION_RADIUS, SettingsItem, SettingsResource, StatsResource, Theme, Language, LocalizationResource, Toast, LEADERBOARD_LINE_HEIGHT, STATS_DIFFICULTIES, HELP_SECTIONS};
+
+/// 一時停止メニューの項目の文言キー（再開 / 設定 / ヘルプ / タイトルへ戻る）
+pub const PAUSE_MENU_ITEMS: [&str; 6] = ["pause.resume", "menu.settings", "menu.help", "pause.save", "pause.load", "pause.quit"];
+
+/// ゲーム結果パネルのボタンの文言キー（左から順に並ぶ）
+pub const SUMMARY_BUTTONS: [&str; 4] = ["summary.rematch", "menu.leaderboard", "summary.share", "summary.title"];
+
+/// エラー画面のボタンの文言キー（状態を読み込み直す / 報告をコピー）
+pub const ERROR_SCREEN_BUTTONS: [&str; 2] = ["error.reload", "error.copy_report"];
+
+/// エラー画面に表示する診断情報の最大行数
+const ERROR_SCREEN_DIAGNOSTIC_LINES: usize = 12;
+
+/**
+ * ゲーム結果パネルに表示する内容
+ */
+pub struct GameSummaryView<'a> {
+    /// 勝利したかどうか
+    pub win: bool,
+    /// 表示する項目（項目名, 値）
+    pub stats: &'a [(&'a str, String)],
+    /// プレイヤーごとの貢献（開いたセル数の多い順）
+    pub players: &'a [SummaryPlayerView],
+    /// 再戦ボタンの表示（投票の進み具合を含む）
+    pub rematch_label: &'a str,
+    /// 再戦投票の締め切りなどの補足（投票がなければNone）
+    pub rematch_status: Option<&'a str>,
+    /// パネルがスライドして現れる進み具合（0.0〜1.0、1.0で定位置）
+    pub panel_progress: f64,
+}
+
+/**
+ * ゲーム結果パネルのプレイヤーごとの貢献
+ */
+pub struct SummaryPlayerView {
+    /// 表示名
+    pub name: String,
+    /// カーソルの色（貢献バーの色）
+    pub color: Color,
+    /// 開いたセル数
+    pub cells: usize,
+}
+
+/**
+ * 対戦履歴画面の1行に表示する内容
+ */
+pub struct HistoryRowView {
+    /// 日時・難易度・結果・タイム
+    pub summary: String,
+    /// 参加したプレイヤー
+    pub players: String,
+    /// 勝利したかどうか
+    pub win: bool,
+    /// 同じ盤面を練習できるかどうか
+    pub can_practice: bool,
+}
+
+/**
+ * プレイヤーのプロフィールカードに表示する内容
+ */
+pub struct ProfileCardView {
+    /// プレイヤー名
+    pub name: String,
+    /// カーソルの色
+    pub color: Color,
+    /// このゲームでの得点
+    pub score: u32,
+    /// このゲームで開いたセル数
+    pub cells: usize,
+    /// このゲームで立てた旗の数
+    pub flags: u32,
+    /// 通信の遅延（ミリ秒、未計測ならNone）
+    pub ping: Option<u32>,
+}
+
+/**
+ * テーマ選択画面の1行に表示する内容
+ */
+pub struct ThemeRowView {
+    /// テーマ
+    pub theme: Theme,
+    /// 選択中かどうか
+    pub selected: bool,
+    /// 未解放の場合は解放条件の説明
+    pub requirement: Option<String>,
+}
+
+/// セルに表示する周囲の地雷数の文字（毎フレーム文字列を作らないよう用意しておく）
+const CELL_NUMBER_LABELS: [&str; 9] = ["0", "1", "2", "3", "4", "5", "6", "7", "8"];
+
+/**
+ * カーソルを描画する位置を取得する
+ * 
+ * 補間の対象になっている他のプレイヤーは補間後の位置、自分は最新の位置を返します。
+ */
+fn cursor_position(cursors: &PlayerStateResource, id: &str, player: &Player) -> (f64, f64) {
+    cursors.all_players().get(id).map_or((player.x, player.y), |cursor| (cursor.x, cursor.y))
+}
+
+/**
+ * ボードの描画に使う色とフォント
+ * 
+ * セルごとにJavaScriptの文字列を作らないよう、テーマかアクセシビリティ設定が変わるまで使い回します。
+ */
+struct BoardStyle {
+    background: JsValue,   // 背景色
+    revealed: JsValue,     // 開いたセルの色
+    hidden: JsValue,       // 閉じたセルの色
+    grid: JsValue,         // 枠線の色
+    mine: JsValue,         // 地雷とフラグの色
+    numbers: Vec<JsValue>, // 周囲の地雷数（1〜8）の色
+    number_font: String,   // 周囲の地雷数のフォント
+}
 
 /**
  * ゲームの描画を担当する構造体
  */
 pub struct GameRenderer {
     pub context: CanvasRenderingContext2d,
+    pub theme: Theme,
+    pub localization: LocalizationResource,
+    pub accessibility: AccessibilityResource,
+    board_style: RefCell<Option<BoardStyle>>, // ボードの描画に使う色（最初の描画時に作成）
 }
 
 impl GameRenderer {
     pub fn new(context: CanvasRenderingContext2d) -> Self {
-        Self { context }
+        Self {
+            context,
+            theme: Theme::Dark,
+            localization: LocalizationResource::default(),
+            accessibility: AccessibilityResource::new(),
+            board_style: RefCell::new(None),
+        }
+    }
+    
+    /**
+     * 描画に使うアクセシビリティ設定を設定する
+     */
+    pub fn set_accessibility(&mut self, accessibility: &AccessibilityResource) {
+        self.accessibility = accessibility.clone();
+        self.board_style.replace(None);
+    }
+    
+    /**
+     * ボードの描画に使う色とフォントを取得する（無ければ現在の設定から作成する）
+     */
+    fn board_style(&self) -> Ref<'_, BoardStyle> {
+        if self.board_style.borrow().is_none() {
+            let high_contrast = self.accessibility.high_contrast;
+            let palette = self.accessibility.palette;
+            self.board_style.replace(Some(BoardStyle {
+                background: self.background_color().js_value(),
+                revealed: JsValue::from_str(if high_contrast { "#FFFFFF" } else { "#CCCCCC" }),
+                hidden: JsValue::from_str(if high_contrast { "#4A4A4A" } else { "#999999" }),
+                grid: JsValue::from_str(if high_contrast { "#000000" } else { "#666666" }),
+                mine: JsValue::from_str("#FF0000"),
+                numbers: (1..=8).map(|count| JsValue::from_str(palette.number_color(count))).collect(),
+                number_font: self.scaled_font(16.0, true),
+            }));
+        }
+        Ref::map(self.board_style.borrow(), |style| style.as_ref().expect("board style is initialized"))
+    }
+    
+    /**
+     * 背景色を取得する（ハイコントラスト時は黒）
+     */
+    pub fn background_color(&self) -> Color {
+        if self.accessibility.high_contrast { Color::BLACK } else { self.theme.background_color() }
+    }
+    
+    /**
+     * 背景上の文字色を取得する（ハイコントラスト時は白）
+     */
+    pub fn text_color(&self) -> Color {
+        if self.accessibility.high_contrast { Color::WHITE } else { self.theme.text_color() }
+    }
+    
+    /**
+     * 文字の大きさの設定を反映したフォントを取得する
+     * 
+     * ボードの数字やカーソルの名前、通知などゲーム中の文字に使用します。
+     * 
+     * @param size 標準の文字の大きさ（px）
+     * @param bold 太字にするかどうか
+     * @return CanvasRenderingContext2dのフォント指定
+     */
+    pub fn scaled_font(&self, size: f64, bold: bool) -> String {
+        let size = (size * self.accessibility.text_scale.factor()).round();
+        if bold { format!("bold {}px Arial", size) } else { format!("{}px Arial", size) }
+    }
+    
+    /**
+     * 描画に使うテーマを設定する
+     */
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+        self.board_style.replace(None);
+    }
+    
+    /**
+     * 画面の文言の表示言語を設定する
+     */
+    pub fn set_language(&mut self, language: Language) {
+        self.localization.set_language(language);
     }
     
     /**
@@ -30,6 +229,14 @@ impl GameRenderer {
         Ok(())
     }
     
+    /**
+     * テーマの背景色で塗りつぶす（HTMLのメニューを重ねる画面で使う）
+     */
+    pub fn draw_background(&self, canvas_width: f64, canvas_height: f64) {
+        self.context.set_fill_style(&self.background_color().js_value());
+        self.context.fill_rect(0.0, 0.0, canvas_width, canvas_height);
+    }
+    
     /**
      * ボードを描画する
      */
@@ -45,15 +252,22 @@ impl GameRenderer {
         canvas_height: f64
     ) -> Result<(), JsValue> {
         let ctx = &self.context;
+        let style = self.board_style();
         
         // ボードの左上の座標
         let board_left = (canvas_width - cell_size * board_width as f64) / 2.0;
         let board_top = (canvas_height - cell_size * board_height as f64) / 2.0;
         
         // 背景を描画
-        ctx.set_fill_style(&JsValue::from_str("#333333"));
+        ctx.set_fill_style(&style.background);
         ctx.fill_rect(0.0, 0.0, canvas_width, canvas_height);
         
+        // 数字の書式と枠線の色は全てのセルで共通
+        ctx.set_font(&style.number_font);
+        ctx.set_text_align("center");
+        ctx.set_text_baseline("middle");
+        ctx.set_stroke_style(&style.grid);
+        
         // ボードを描画
         for y in 0..board_height {
             for x in 0..board_width {
@@ -61,13 +275,13 @@ impl GameRenderer {
                 let cell_x = board_left + x as f64 * cell_size;
                 let cell_y = board_top + y as f64 * cell_size;
                 
-                // セルの背景
+                // セルの背景（ハイコントラスト時は開いたセルを白、閉じたセルを濃い灰色にする）
                 if revealed[index] {
                     // 開かれたセル
-                    ctx.set_fill_style(&JsValue::from_str("#CCCCCC"));
+                    ctx.set_fill_style(&style.revealed);
                 } else {
                     // 閉じたセル
-                    ctx.set_fill_style(&JsValue::from_str("#999999"));
+                    ctx.set_fill_style(&style.hidden);
                 }
                 
                 // セルを描画
@@ -79,7 +293,6 @@ impl GameRenderer {
                 );
                 
                 // 枠線
-                ctx.set_stroke_style(&JsValue::from_str("#666666"));
                 ctx.set_line_width(1.0);
                 ctx.stroke_rect(
                     cell_x, 
@@ -93,7 +306,7 @@ impl GameRenderer {
                     match cells[index] {
                         CellValue::Mine => {
                             // 地雷
-                            ctx.set_fill_style(&JsValue::from_str("#FF0000"));
+                            ctx.set_fill_style(&style.mine);
                             ctx.begin_path();
                             ctx.arc(
                                 cell_x + cell_size / 2.0,
@@ -106,25 +319,12 @@ impl GameRenderer {
                         },
                         CellValue::Empty(count) => {
                             if count > 0 {
-                                // 周囲の地雷数
-                                let color = match count {
-                                    1 => "#0000FF", // 青
-                                    2 => "#008000", // 緑
-                                    3 => "#FF0000", // 赤
-                                    4 => "#000080", // 紺
-                                    5 => "#800000", // 茶
-                                    6 => "#008080", // シアン
-                                    7 => "#000000", // 黒
-                                    8 => "#808080", // グレー
-                                    _ => "#000000", // 黒
-                                };
+                                // 周囲の地雷数（色覚に配慮した配色を選べる）
+                                let color = &style.numbers[(count as usize - 1).min(7)];
                                 
-                                ctx.set_fill_style(&JsValue::from_str(color));
-                                ctx.set_font("bold 16px Arial");
-                                ctx.set_text_align("center");
-                                ctx.set_text_baseline("middle");
+                                ctx.set_fill_style(color);
                                 ctx.fill_text(
-                                    &count.to_string(),
+                                    CELL_NUMBER_LABELS[(count as usize).min(8)],
                                     cell_x + cell_size / 2.0,
                                     cell_y + cell_size / 2.0,
                                 )?;
@@ -133,7 +333,7 @@ impl GameRenderer {
                     }
                 } else if flagged[index] {
                     // フラグ
-                    ctx.set_fill_style(&JsValue::from_str("#FF0000"));
+                    ctx.set_fill_style(&style.mine);
                     
                     // 旗竿
                     ctx.begin_path();
@@ -158,37 +358,346 @@ impl GameRenderer {
     
     /**
      * プレイヤーのカーソルを描画する
+     * 
+     * 離席中の他のプレイヤーはカーソルを薄くして「zzz」を表示します。
+     * 他のプレイヤーのカーソルは補間後の位置に描画します。
+     * 
+     * @param cursors カーソルの補間の状態
+     * @param now 現在時刻（ミリ秒）
      */
     pub fn draw_players(
         &self, 
         players: &HashMap<String, Player>,
-        local_player_id: &Option<String>
+        local_player_id: &Option<String>,
+        cursors: &PlayerStateResource,
+        now: f64
     ) -> Result<(), JsValue> {
         let ctx = &self.context;
         
         // 全プレイヤーを描画
         for (id, player) in players {
+            let idle = !player.is_local && player.is_idle(now);
+            ctx.set_global_alpha(if idle { 0.4 } else { 1.0 });
+            let (x, y) = cursor_position(cursors, id, player);
+            
             // カーソルを描画
-            ctx.set_fill_style(&JsValue::from_str(&player.color));
+            ctx.set_fill_style(&player.color.js_value());
             ctx.begin_path();
             ctx.arc(
-                player.x,
-                player.y,
+                x,
+                y,
                 8.0,
                 0.0,
                 std::f64::consts::PI * 2.0,
             )?;
             ctx.fill();
             
-            // プレイヤーIDを表示
-            ctx.set_font("12px Arial");
+            // プレイヤー名を表示（未設定の場合はID）
+            let label = if player.name.is_empty() { id } else { &player.name };
+            ctx.set_font(&self.scaled_font(12.0, false));
             ctx.set_text_align("center");
             ctx.set_text_baseline("top");
             ctx.fill_text(
-                &id,
-                player.x,
-                player.y + 10.0,
+                label,
+                x,
+                y + 10.0,
             )?;
+            
+            // アバターはカーソルの左上に表示
+            if !player.avatar.is_empty() {
+                ctx.set_font(&self.scaled_font(16.0, false));
+                ctx.set_text_baseline("middle");
+                ctx.fill_text(&player.avatar, x - 14.0, y - 14.0)?;
+            }
+            
+            if idle {
+                ctx.set_font(&self.scaled_font(12.0, true));
+                ctx.set_text_baseline("bottom");
+                ctx.fill_text("zzz", x + 10.0, y - 6.0)?;
+            }
+        }
+        ctx.set_global_alpha(1.0);
+        
+        Ok(())
+    }
+    
+    /**
+     * プレイヤーのプロフィールカードを描画する
+     * 
+     * マウスの右下に表示し、キャンバスからはみ出す場合は内側に寄せます。
+     * 
+     * @param card 表示する内容
+     * @param mouse_x マウスのX座標
+     * @param mouse_y マウスのY座標
+     */
+    pub fn draw_profile_card(
+        &self,
+        canvas_width: f64,
+        canvas_height: f64,
+        card: &ProfileCardView,
+        mouse_x: f64,
+        mouse_y: f64
+    ) -> Result<(), JsValue> {
+        let ctx = &self.context;
+        let width = 170.0;
+        let height = 112.0;
+        let left = (mouse_x + 14.0).min(canvas_width - width - 4.0).max(4.0);
+        let top = (mouse_y + 14.0).min(canvas_height - height - 4.0).max(4.0);
+        
+        ctx.set_fill_style(&JsValue::from_str("rgba(33, 33, 33, 0.92)"));
+        ctx.fill_rect(left, top, width, height);
+        ctx.set_stroke_style(&card.color.js_value());
+        ctx.set_line_width(2.0);
+        ctx.stroke_rect(left, top, width, height);
+        
+        // 名前（カーソルの色の丸を添える）
+        ctx.set_fill_style(&card.color.js_value());
+        ctx.begin_path();
+        ctx.arc(left + 16.0, top + 18.0, 6.0, 0.0, std::f64::consts::PI * 2.0)?;
+        ctx.fill();
+        
+        ctx.set_fill_style(&JsValue::from_str("#FFFFFF"));
+        ctx.set_font("bold 14px Arial");
+        ctx.set_text_align("left");
+        ctx.set_text_baseline("middle");
+        ctx.fill_text(&card.name, left + 28.0, top + 18.0)?;
+        
+        // 項目と値
+        let ping = match card.ping {
+            Some(ping) => self.localization.format("profile.ping_ms", &[&ping]),
+            None => "-".to_string(),
+        };
+        let rows = [
+            (self.localization.text("profile.score"), card.score.to_string()),
+            (self.localization.text("profile.cells"), card.cells.to_string()),
+            (self.localization.text("profile.flags"), card.flags.to_string()),
+            (self.localization.text("profile.ping"), ping),
+        ];
+        ctx.set_font("12px Arial");
+        for (index, (label, value)) in rows.iter().enumerate() {
+            let y = top + 42.0 + index as f64 * 18.0;
+            ctx.set_text_align("left");
+            ctx.fill_text(label, left + 12.0, y)?;
+            ctx.set_text_align("right");
+            ctx.fill_text(value, left + width - 12.0, y)?;
+        }
+        
+        Ok(())
+    }
+    
+    /**
+     * カメラの視点で描画を始める
+     * 
+     * 背景を塗りつぶしてから、以降の描画にカメラの移動と拡大を適用します。
+     * `end_camera`で元に戻してください。
+     * 
+     * @param camera カメラの状態
+     */
+    pub fn begin_camera(&self, camera: &CameraResource, canvas_width: f64, canvas_height: f64) -> Result<(), JsValue> {
+        let ctx = &self.context;
+        
+        ctx.set_fill_style(&self.background_color().js_value());
+        ctx.fill_rect(0.0, 0.0, canvas_width, canvas_height);
+        
+        let (center_x, center_y) = camera.center(canvas_width, canvas_height);
+        ctx.save();
+        ctx.translate(canvas_width / 2.0, canvas_height / 2.0)?;
+        ctx.scale(camera.zoom(), camera.zoom())?;
+        ctx.translate(-center_x, -center_y)?;
+        
+        Ok(())
+    }
+    
+    /**
+     * カメラの視点での描画を終える
+     */
+    pub fn end_camera(&self) {
+        self.context.restore();
+    }
+    
+    /**
+     * ゲーム画面のプレイヤー一覧の行の位置とサイズを取得する（右端に縦に並べる）
+     * 
+     * @return (左端X, 上端Y, 幅, 高さ)
+     */
+    pub fn player_list_row_bounds(canvas_width: f64, index: usize) -> (f64, f64, f64, f64) {
+        let row_width = 110.0;
+        let row_height = 24.0;
+        
+        (canvas_width - row_width - 6.0, 64.0 + index as f64 * (row_height + 4.0), row_width, row_height)
+    }
+    
+    /**
+     * ゲーム画面のプレイヤー一覧を描画する
+     * 
+     * 行をクリックするとそのプレイヤーのカーソルを追いかけます。
+     * 
+     * @param players 一覧に並べるプレイヤー（表示順）
+     * @param following 追いかけているプレイヤーのID
+     * @param highlighted マウスが乗っている行のインデックス
+     */
+    pub fn draw_player_list(
+        &self,
+        canvas_width: f64,
+        players: &[&Player],
+        following: Option<&str>,
+        highlighted: Option<usize>
+    ) -> Result<(), JsValue> {
+        let ctx = &self.context;
+        
+        ctx.set_font(&self.scaled_font(12.0, false));
+        ctx.set_text_align("left");
+        ctx.set_text_baseline("middle");
+        
+        for (index, player) in players.iter().enumerate() {
+            let (left, top, width, height) = Self::player_list_row_bounds(canvas_width, index);
+            
+            let color = if following == Some(player.id.as_str()) {
+                "rgba(255, 152, 0, 0.85)"
+            } else if highlighted == Some(index) {
+                "rgba(96, 125, 139, 0.85)"
+            } else {
+                "rgba(33, 33, 33, 0.7)"
+            };
+            ctx.set_fill_style(&JsValue::from_str(color));
+            ctx.fill_rect(left, top, width, height);
+            
+            // カーソルの色
+            ctx.set_fill_style(&player.color.js_value());
+            ctx.begin_path();
+            ctx.arc(left + 12.0, top + height / 2.0, 5.0, 0.0, std::f64::consts::PI * 2.0)?;
+            ctx.fill();
+            
+            // 名前（入りきらない分は省略）
+            let mut name: String = player.name.chars().take(12).collect();
+            if player.name.chars().count() > 12 {
+                name.push('…');
+            }
+            ctx.set_fill_style(&JsValue::from_str("#FFFFFF"));
+            ctx.fill_text(&name, left + 22.0, top + height / 2.0)?;
+        }
+        
+        Ok(())
+    }
+    
+    /**
+     * プレイヤーのカーソルの上にエモートの吹き出しを描画する
+     * 
+     * 表示時間の終わりに近づくと吹き出しが上に浮かびながら薄くなります。
+     * 
+     * @param cursors カーソルの補間の状態
+     * @param emotes エモートの表示状態
+     * @param now 現在時刻（ミリ秒）
+     */
+    pub fn draw_emote_bubbles(
+        &self,
+        players: &HashMap<String, Player>,
+        cursors: &PlayerStateResource,
+        emotes: &EmoteResource,
+        now: f64
+    ) -> Result<(), JsValue> {
+        let ctx = &self.context;
+        
+        for (id, player) in players {
+            let Some((emote, progress)) = emotes.active(id, now) else {
+                continue;
+            };
+            
+            let (x, y) = cursor_position(cursors, id, player);
+            let y = y - 34.0 - progress * 10.0;
+            ctx.set_global_alpha(((1.0 - progress) * 2.0).min(1.0));
+            
+            // 吹き出し
+            ctx.set_fill_style(&JsValue::from_str("#FFFFFF"));
+            ctx.begin_path();
+            ctx.arc(x, y, 18.0, 0.0, std::f64::consts::PI * 2.0)?;
+            ctx.move_to(x - 6.0, y + 15.0);
+            ctx.line_to(x, y + 24.0);
+            ctx.line_to(x + 6.0, y + 15.0);
+            ctx.fill();
+            
+            ctx.set_font("20px Arial");
+            ctx.set_text_align("center");
+            ctx.set_text_baseline("middle");
+            ctx.fill_text(emote.symbol(), x, y + 1.0)?;
+        }
+        
+        ctx.set_global_alpha(1.0);
+        Ok(())
+    }
+    
+    /**
+     * 他のプレイヤーの操作の通知（トースト）を画面上部に描画する
+     * 
+     * 新しいものほど下に並べ、表示時間の最後の2割でフェードアウトします。
+     * 
+     * @param toasts 表示中のトースト（古い順）
+     * @param now 現在時刻（ミリ秒）
+     */
+    pub fn draw_toasts<'a>(
+        &self,
+        canvas_width: f64,
+        toasts: impl Iterator<Item = &'a Toast>,
+        now: f64
+    ) -> Result<(), JsValue> {
+        let ctx = &self.context;
+        let toast_height = 30.0 * self.accessibility.text_scale.factor();
+        
+        ctx.set_font(&self.scaled_font(16.0, false));
+        ctx.set_text_align("center");
+        ctx.set_text_baseline("middle");
+        
+        for (index, toast) in toasts.enumerate() {
+            let width = ctx.measure_text(&toast.text)?.width() + 32.0;
+            let left = canvas_width / 2.0 - width / 2.0;
+            let top = 60.0 + index as f64 * (toast_height + 6.0);
+            ctx.set_global_alpha(((1.0 - toast.progress(now)) * 5.0).min(1.0));
+            
+            ctx.set_fill_style(&JsValue::from_str("rgba(33, 33, 33, 0.85)"));
+            ctx.fill_rect(left, top, width, toast_height);
+            
+            ctx.set_fill_style(&JsValue::from_str("#FFFFFF"));
+            ctx.fill_text(&toast.text, canvas_width / 2.0, top + toast_height / 2.0)?;
+        }
+        
+        ctx.set_global_alpha(1.0);
+        Ok(())
+    }
+    
+    /**
+     * 円形のエモート選択メニューを描画する
+     * 
+     * @param emotes エモートの表示状態（選択メニューが閉じていれば何もしない）
+     * @param highlighted マウスが乗っているエモート
+     */
+    pub fn draw_emote_picker(&self, emotes: &EmoteResource, highlighted: Option<Emote>) -> Result<(), JsValue> {
+        let ctx = &self.context;
+        let Some((center_x, center_y)) = emotes.picker_center() else {
+            return Ok(());
+        };
+        
+        // 中心の目印
+        ctx.set_fill_style(&JsValue::from_str("rgba(0, 0, 0, 0.4)"));
+        ctx.begin_path();
+        ctx.arc(center_x, center_y, 6.0, 0.0, std::f64::consts::PI * 2.0)?;
+        ctx.fill();
+        
+        for (index, (emote, x, y)) in emotes.picker_options().into_iter().enumerate() {
+            let color = if highlighted == Some(emote) { "#FFE082" } else { "#FFFFFF" };
+            ctx.set_fill_style(&JsValue::from_str(color));
+            ctx.begin_path();
+            ctx.arc(x, y, EMOTE_OPTION_RADIUS, 0.0, std::f64::consts::PI * 2.0)?;
+            ctx.fill();
+            
+            ctx.set_font("24px Arial");
+            ctx.set_text_align("center");
+            ctx.set_text_baseline("middle");
+            ctx.fill_text(emote.symbol(), x, y + 1.0)?;
+            
+            // 数字キーでも選べるよう番号を表示
+            ctx.set_fill_style(&JsValue::from_str("#546E7A"));
+            ctx.set_font("bold 11px Arial");
+            ctx.fill_text(&(index + 1).to_string(), x + 16.0, y + 16.0)?;
         }
         
         Ok(())
@@ -219,11 +728,112 @@ impl GameRenderer {
         ctx.set_text_align("center");
         ctx.set_text_baseline("middle");
         ctx.fill_text(
-            "リセット",
+            self.localization.text("game.reset"),
             canvas_width - 80.0,
             30.0,
         )?;
         
+        // 一時停止ボタン
+        ctx.set_fill_style(&JsValue::from_str("#607D8B"));
+        ctx.begin_path();
+        ctx.ellipse(
+            canvas_width - 170.0,
+            30.0,
+            40.0,
+            20.0,
+            0.0,
+            0.0,
+            std::f64::consts::PI * 2.0,
+        )?;
+        ctx.fill();
+        
+        ctx.set_fill_style(&JsValue::from_str("#FFFFFF"));
+        ctx.fill_text(
+            self.localization.text("game.pause"),
+            canvas_width - 170.0,
+            30.0,
+        )?;
+        
+        Ok(())
+    }
+    
+    /**
+     * 一時停止メニューのボタンの位置とサイズを取得する
+     * 
+     * @return (左端X, 上端Y, 幅, 高さ)
+     */
+    pub fn pause_menu_button_bounds(canvas_width: f64, canvas_height: f64, index: usize) -> (f64, f64, f64, f64) {
+        let button_width = 240.0;
+        let button_height = 44.0;
+        let spacing = 10.0;
+        
+        let left = canvas_width / 2.0 - button_width / 2.0;
+        let top = canvas_height / 2.0 - 130.0 + index as f64 * (button_height + spacing);
+        
+        (left, top, button_width, button_height)
+    }
+    
+    /**
+     * 開始前のカウントダウンを画面中央に描画する
+     * 
+     * @param number 表示する数字（3, 2, 1）
+     */
+    pub fn draw_countdown(&self, canvas_width: f64, canvas_height: f64, number: u32) -> Result<(), JsValue> {
+        let ctx = &self.context;
+        
+        // 半透明の背景
+        ctx.set_fill_style(&JsValue::from_str("rgba(0, 0, 0, 0.4)"));
+        ctx.fill_rect(0.0, 0.0, canvas_width, canvas_height);
+        
+        ctx.set_fill_style(&JsValue::from_str("#FFFFFF"));
+        ctx.set_font("bold 120px Arial");
+        ctx.set_text_align("center");
+        ctx.set_text_baseline("middle");
+        ctx.fill_text(&number.to_string(), canvas_width / 2.0, canvas_height / 2.0)?;
+        
+        ctx.set_font("bold 24px Arial");
+        ctx.fill_text(self.localization.text("countdown.starting"), canvas_width / 2.0, canvas_height / 2.0 + 90.0)?;
+        
+        Ok(())
+    }
+    
+    /**
+     * 一時停止メニューを描画する
+     * 
+     * @param highlighted マウスが乗っているボタンのインデックス
+     * @param save_enabled 保存ボタンを押せるかどうか（オフラインのゲーム中のみ）
+     */
+    pub fn draw_pause_menu(&self, canvas_width: f64, canvas_height: f64, highlighted: Option<usize>, save_enabled: bool) -> Result<(), JsValue> {
+        let ctx = &self.context;
+        
+        // 半透明の背景
+        ctx.set_fill_style(&JsValue::from_str("rgba(0, 0, 0, 0.7)"));
+        ctx.fill_rect(0.0, 0.0, canvas_width, canvas_height);
+        
+        // 見出し
+        ctx.set_fill_style(&JsValue::from_str("#FFFFFF"));
+        ctx.set_font("bold 40px Arial");
+        ctx.set_text_align("center");
+        ctx.set_text_baseline("middle");
+        ctx.fill_text(self.localization.text("pause.heading"), canvas_width / 2.0, canvas_height / 2.0 - 180.0)?;
+        
+        // メニューボタン
+        for (index, label) in PAUSE_MENU_ITEMS.iter().enumerate() {
+            let (left, top, width, height) = Self::pause_menu_button_bounds(canvas_width, canvas_height, index);
+            
+            let color = match index {
+                _ if *label == "pause.save" && !save_enabled => "#9E9E9E",
+                _ if highlighted == Some(index) => "#66BB6A",
+                _ => "#4CAF50",
+            };
+            ctx.set_fill_style(&JsValue::from_str(color));
+            ctx.fill_rect(left, top, width, height);
+            
+            ctx.set_fill_style(&JsValue::from_str("#FFFFFF"));
+            ctx.set_font("bold 22px Arial");
+            ctx.fill_text(self.localization.text(label), left + width / 2.0, top + height / 2.0)?;
+        }
+        
         Ok(())
     }
     
@@ -235,9 +845,9 @@ impl GameRenderer {
         
         // 接続状態の色を設定
         let (color, text) = if is_connected {
-            ("#4CAF50", "接続中")
+            ("#4CAF50", self.localization.text("connection.connected"))
         } else {
-            ("#FF0000", "未接続")
+            ("#FF0000", self.localization.text("connection.disconnected"))
         };
         
         // 接続状態の背景
@@ -247,7 +857,7 @@ impl GameRenderer {
         ctx.fill();
         
         // 接続状態のテキスト
-        ctx.set_fill_style(&JsValue::from_str("#FFFFFF"));
+        ctx.set_fill_style(&self.text_color().js_value());
         ctx.set_font("16px Arial");
         ctx.set_text_align("left");
         ctx.set_text_baseline("middle");
@@ -257,22 +867,296 @@ impl GameRenderer {
     }
     
     /**
-     * タイトル画面を描画する
+     * 埋め込み用の最小表示で画面下端に状態の帯を描画する
+     * 
+     * @param canvas_width キャンバスの幅
+     * @param canvas_height キャンバスの高さ
+     * @param height 帯の高さ
+     * @param status ゲームの状態の文言
+     * @param mines_left 残りの地雷数（フラグの数を引いた値）
+     * @param elapsed 経過時間の表示
+     * @return 成功した場合はOk(()), エラーの場合はErr(JsValue)
      */
-    pub fn draw_title_screen(&self, canvas_width: f64, canvas_height: f64, is_connected: bool) -> Result<(), JsValue> {
+    pub fn draw_compact_status(
+        &self,
+        canvas_width: f64,
+        canvas_height: f64,
+        height: f64,
+        status: &str,
+        mines_left: i64,
+        elapsed: &str
+    ) -> Result<(), JsValue> {
         let ctx = &self.context;
+        let top = canvas_height - height;
+        let center_y = top + height / 2.0;
         
-        // 背景を描画
-        ctx.set_fill_style(&JsValue::from_str("#333333"));
-        ctx.fill_rect(0.0, 0.0, canvas_width, canvas_height);
+        ctx.set_fill_style(&JsValue::from_str("rgba(0, 0, 0, 0.7)"));
+        ctx.fill_rect(0.0, top, canvas_width, height);
         
-        // タイトルを描画
         ctx.set_fill_style(&JsValue::from_str("#FFFFFF"));
+        ctx.set_font("14px Arial");
+        ctx.set_text_baseline("middle");
+        ctx.set_text_align("left");
+        ctx.fill_text(&format!("💣 {}  ⏱ {}", mines_left, elapsed), 8.0, center_y)?;
+        ctx.set_text_align("center");
+        ctx.fill_text(status, canvas_width / 2.0, center_y)?;
+        ctx.set_text_align("right");
+        ctx.fill_text(self.localization.text("compact.new_game"), canvas_width - 8.0, center_y)?;
+        
+        Ok(())
+    }
+
+    /**
+     * オフラインであることを知らせるバナーを画面上部に描画する
+     * 
+     * @param canvas_width キャンバスの幅
+     */
+    pub fn draw_offline_banner(&self, canvas_width: f64) -> Result<(), JsValue> {
+        let ctx = &self.context;
+        let height = 32.0;
+        
+        ctx.set_fill_style(&JsValue::from_str("rgba(230, 81, 0, 0.9)"));
+        ctx.fill_rect(0.0, 0.0, canvas_width, height);
+        
+        ctx.set_fill_style(&JsValue::from_str("#FFFFFF"));
+        ctx.set_font("bold 16px Arial");
+        ctx.set_text_align("center");
+        ctx.set_text_baseline("middle");
+        ctx.fill_text(self.localization.text("banner.offline"), canvas_width / 2.0, height / 2.0)?;
+        
+        Ok(())
+    }
+    
+    /**
+     * 直近のログをキャンバスの下部に描画する
+     * 
+     * @param canvas_width キャンバスの幅
+     * @param canvas_height キャンバスの高さ
+     * @param records 古い順のログ
+     */
+    pub fn draw_log_panel(&self, canvas_width: f64, canvas_height: f64, records: &[LogRecord]) -> Result<(), JsValue> {
+        let ctx = &self.context;
+        let line_height = 14.0;
+        let height = line_height * records.len().max(1) as f64 + 8.0;
+        let top = canvas_height - height;
+        
+        ctx.set_fill_style(&JsValue::from_str("rgba(0, 0, 0, 0.75)"));
+        ctx.fill_rect(0.0, top, canvas_width, height);
+        
+        ctx.set_font("12px monospace");
+        ctx.set_text_align("left");
+        ctx.set_text_baseline("top");
+        for (index, record) in records.iter().enumerate() {
+            ctx.set_fill_style(&JsValue::from_str(match record.level {
+                LogLevel::Error => "#FF8A80",
+                LogLevel::Warn => "#FFD180",
+                LogLevel::Info => "#FFFFFF",
+                LogLevel::Debug | LogLevel::Trace => "#B0BEC5",
+            }));
+            ctx.fill_text(&record.to_string(), 6.0, top + 4.0 + index as f64 * line_height)?;
+        }
+        
+        Ok(())
+    }
+    
+    /**
+     * 診断情報をキャンバスの右上に描画する
+     * 
+     * @param canvas_width キャンバスの幅
+     * @param lines 表示する行（`DiagnosticsResource::overlay_lines`）
+     */
+    pub fn draw_debug_overlay(&self, canvas_width: f64, lines: &[String]) -> Result<(), JsValue> {
+        let ctx = &self.context;
+        let line_height = 14.0;
+        let width = 260.0;
+        let left = canvas_width - width;
+        
+        ctx.set_fill_style(&JsValue::from_str("rgba(0, 0, 0, 0.75)"));
+        ctx.fill_rect(left, 0.0, width, line_height * lines.len() as f64 + 8.0);
+        
+        ctx.set_fill_style(&JsValue::from_str("#B2FF59"));
+        ctx.set_font("12px monospace");
+        ctx.set_text_align("left");
+        ctx.set_text_baseline("top");
+        for (index, line) in lines.iter().enumerate() {
+            ctx.fill_text(line, left + 6.0, 4.0 + index as f64 * line_height)?;
+        }
+        
+        Ok(())
+    }
+    
+    /**
+     * エラー画面を描画する
+     * 
+     * ゲームの処理でエラーが発生したときに、画面全体を覆って内容と診断情報を表示します。
+     * 
+     * @param canvas_width キャンバスの幅
+     * @param canvas_height キャンバスの高さ
+     * @param report 表示するエラーの報告
+     * @param highlighted カーソルが乗っているボタンのインデックス
+     */
+    pub fn draw_error_screen(&self, canvas_width: f64, canvas_height: f64, report: &ErrorReport, highlighted: Option<usize>) -> Result<(), JsValue> {
+        Self::draw_error_message(&self.context, canvas_width, canvas_height, self.localization.text("error.title"), &report.summary)?;
+        let ctx = &self.context;
+        
+        // 診断情報（多い場合は末尾の直近のログを優先する）
+        ctx.set_font("12px monospace");
+        ctx.set_text_align("left");
+        ctx.set_text_baseline("top");
+        ctx.set_fill_style(&JsValue::from_str("#B0BEC5"));
+        let skip = report.diagnostics.len().saturating_sub(ERROR_SCREEN_DIAGNOSTIC_LINES);
+        let left = (canvas_width / 2.0 - 300.0).max(8.0);
+        for (index, line) in report.diagnostics.iter().skip(skip).enumerate() {
+            ctx.fill_text(line, left, canvas_height / 2.0 - 60.0 + index as f64 * 14.0)?;
+        }
+        
+        // 操作ボタン
+        ctx.set_text_align("center");
+        ctx.set_text_baseline("middle");
+        for (index, label) in ERROR_SCREEN_BUTTONS.iter().enumerate() {
+            let (left, top, width, height) = Self::error_screen_button_bounds(canvas_width, canvas_height, index);
+            ctx.set_fill_style(&JsValue::from_str(if highlighted == Some(index) { "#66BB6A" } else { "#4CAF50" }));
+            ctx.fill_rect(left, top, width, height);
+            
+            ctx.set_fill_style(&JsValue::from_str("#FFFFFF"));
+            ctx.set_font("bold 18px Arial");
+            ctx.fill_text(self.localization.text(label), left + width / 2.0, top + height / 2.0)?;
+        }
+        
+        Ok(())
+    }
+    
+    /**
+     * エラー画面のボタンの位置とサイズを取得する
+     * 
+     * ボタンは画面下部に横一列で並びます（並びは`ERROR_SCREEN_BUTTONS`の順）。
+     * 
+     * @return (左端X, 上端Y, 幅, 高さ)
+     */
+    pub fn error_screen_button_bounds(canvas_width: f64, canvas_height: f64, index: usize) -> (f64, f64, f64, f64) {
+        let button_width = 200.0;
+        let button_height = 44.0;
+        let spacing = 16.0;
+        let count = ERROR_SCREEN_BUTTONS.len() as f64;
+        
+        let total_width = button_width * count + spacing * (count - 1.0);
+        let left = canvas_width / 2.0 - total_width / 2.0 + index as f64 * (button_width + spacing);
+        let top = canvas_height / 2.0 + 130.0;
+        
+        (left, top, button_width, button_height)
+    }
+    
+    /**
+     * パニックで停止したゲームのキャンバスに案内を描画する
+     * 
+     * ゲーム状態を使えないため、描画コンテキストと翻訳だけで描画します。
+     * 
+     * @param context キャンバスの描画コンテキスト
+     * @param localization 表示言語の翻訳
+     * @param canvas_width キャンバスの幅
+     * @param canvas_height キャンバスの高さ
+     * @param message パニックの内容
+     */
+    pub fn draw_crash_screen(context: &CanvasRenderingContext2d, localization: &LocalizationResource, canvas_width: f64, canvas_height: f64, message: &str) -> Result<(), JsValue> {
+        Self::draw_error_message(context, canvas_width, canvas_height, localization.text("error.crashed"), message)?;
+        
+        context.set_fill_style(&JsValue::from_str("#FFFFFF"));
+        context.set_font("18px Arial");
+        context.fill_text(localization.text("error.restart_hint"), canvas_width / 2.0, canvas_height / 2.0 + 40.0)?;
+        
+        Ok(())
+    }
+    
+    /**
+     * エラー画面とパニック時の画面に共通する背景・見出し・内容を描画する
+     * 
+     * @param context キャンバスの描画コンテキスト
+     * @param heading 見出し
+     * @param message エラーの内容
+     */
+    fn draw_error_message(context: &CanvasRenderingContext2d, canvas_width: f64, canvas_height: f64, heading: &str, message: &str) -> Result<(), JsValue> {
+        context.set_fill_style(&JsValue::from_str("rgba(33, 33, 33, 0.95)"));
+        context.fill_rect(0.0, 0.0, canvas_width, canvas_height);
+        
+        context.set_text_align("center");
+        context.set_text_baseline("middle");
+        context.set_fill_style(&JsValue::from_str("#FF8A80"));
+        context.set_font("bold 32px Arial");
+        context.fill_text(heading, canvas_width / 2.0, canvas_height / 2.0 - 140.0)?;
+        
+        context.set_fill_style(&JsValue::from_str("#FFFFFF"));
+        context.set_font("16px Arial");
+        context.fill_text(message, canvas_width / 2.0, canvas_height / 2.0 - 95.0)?;
+        
+        Ok(())
+    }
+    
+    /**
+     * HUDのUIエンティティを描画する
+     * 
+     * 配置は`hud_layout_system`で計算された`GlobalPosition`を使います。
+     * 
+     * @param entities HUDのUIエンティティを持つエンティティマネージャー
+     */
+    pub fn draw_hud(&self, entities: &EntityManager) -> Result<(), JsValue> {
+        let ctx = &self.context;
+        ctx.set_text_align("left");
+        ctx.set_text_baseline("middle");
+        
+        for id in entities.query_with_component_and_tag::<UIElement>(HUD_TAG) {
+            let Some(entity) = entities.get_entity(id) else {
+                continue;
+            };
+            let (Some(element), Some(position)) = (
+                entity.get_component::<UIElement>(),
+                entity.get_component::<GlobalPosition>(),
+            ) else {
+                continue;
+            };
+            
+            match element {
+                UIElement::Text { content, font, size, color } => {
+                    ctx.set_fill_style(&color.js_value());
+                    let size = size * self.accessibility.text_scale.factor();
+                    ctx.set_font(&format!("{}px {}", size.round(), font));
+                    ctx.fill_text(content, position.x, position.y)?;
+                },
+                UIElement::Icon { size, color, .. } => {
+                    ctx.set_fill_style(&color.js_value());
+                    ctx.begin_path();
+                    ctx.arc(position.x, position.y, *size, 0.0, std::f64::consts::PI * 2.0)?;
+                    ctx.fill();
+                },
+                UIElement::ProgressBar { width, height, progress, color } => {
+                    ctx.set_fill_style(&JsValue::from_str("rgba(128, 128, 128, 0.4)"));
+                    ctx.fill_rect(position.x, position.y, *width, *height);
+                    ctx.set_fill_style(&color.js_value());
+                    ctx.fill_rect(position.x, position.y, width * progress.clamp(0.0, 1.0), *height);
+                },
+                UIElement::Button(_) => {}
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /**
+     * タイトル画面を描画する
+     */
+    pub fn draw_title_screen(&self, canvas_width: f64, canvas_height: f64, is_connected: bool, current_streak: u32, best_streak: u32) -> Result<(), JsValue> {
+        let ctx = &self.context;
+        
+        // 背景を描画
+        ctx.set_fill_style(&self.background_color().js_value());
+        ctx.fill_rect(0.0, 0.0, canvas_width, canvas_height);
+        
+        // タイトルを描画
+        ctx.set_fill_style(&self.text_color().js_value());
         ctx.set_font("bold 48px Arial");
         ctx.set_text_align("center");
         ctx.set_text_baseline("middle");
         ctx.fill_text(
-            "マルチプレイヤー\nマインスイーパー",
+            self.localization.text("title.heading"),
             canvas_width / 2.0,
             canvas_height / 2.0 - 50.0,
         )?;
@@ -296,11 +1180,77 @@ impl GameRenderer {
         ctx.set_fill_style(&JsValue::from_str("#FFFFFF"));
         ctx.set_font("bold 24px Arial");
         ctx.fill_text(
-            "スタート",
+            self.localization.text("title.start"),
             button_x,
             button_y,
         )?;
         
+        // 設定ボタンを描画
+        let (left, top, width, height) = Self::settings_button_bounds(canvas_width, canvas_height);
+        ctx.set_fill_style(&JsValue::from_str("#607D8B"));
+        ctx.fill_rect(left, top, width, height);
+        
+        ctx.set_fill_style(&JsValue::from_str("#FFFFFF"));
+        ctx.set_font("bold 20px Arial");
+        ctx.fill_text(self.localization.text("menu.settings"), left + width / 2.0, top + height / 2.0)?;
+        
+        // ランキングボタンを描画
+        let (left, top, width, height) = Self::title_leaderboard_button_bounds(canvas_width, canvas_height);
+        self.draw_leaderboard_button(left, top, width, height)?;
+        
+        // 統計ボタンを描画
+        let (left, top, width, height) = Self::stats_button_bounds(canvas_width, canvas_height);
+        ctx.set_fill_style(&JsValue::from_str("#7E57C2"));
+        ctx.fill_rect(left, top, width, height);
+        
+        ctx.set_fill_style(&JsValue::from_str("#FFFFFF"));
+        ctx.set_font("bold 20px Arial");
+        ctx.fill_text(self.localization.text("menu.stats"), left + width / 2.0, top + height / 2.0)?;
+        
+        // ヘルプボタンを描画
+        let (left, top, width, height) = Self::help_button_bounds(canvas_width, canvas_height);
+        ctx.set_fill_style(&JsValue::from_str("#607D8B"));
+        ctx.fill_rect(left, top, width, height);
+        
+        ctx.set_fill_style(&JsValue::from_str("#FFFFFF"));
+        ctx.set_font("bold 18px Arial");
+        ctx.fill_text(self.localization.text("menu.help"), left + width / 2.0, top + height / 2.0)?;
+        
+        // 履歴ボタンを描画
+        let (left, top, width, height) = Self::history_button_bounds(canvas_width, canvas_height);
+        ctx.set_fill_style(&JsValue::from_str("#607D8B"));
+        ctx.fill_rect(left, top, width, height);
+        
+        ctx.set_fill_style(&JsValue::from_str("#FFFFFF"));
+        ctx.fill_text(self.localization.text("menu.history"), left + width / 2.0, top + height / 2.0)?;
+        
+        // デイリーチャレンジボタンと連続記録を描画
+        let (left, top, width, height) = Self::daily_button_bounds(canvas_width, canvas_height);
+        ctx.set_fill_style(&JsValue::from_str("#FF7043"));
+        ctx.fill_rect(left, top, width, height);
+        
+        ctx.set_fill_style(&JsValue::from_str("#FFFFFF"));
+        ctx.fill_text(self.localization.text("menu.daily"), left + width / 2.0, top + height / 2.0)?;
+        
+        ctx.set_fill_style(&self.text_color().js_value());
+        ctx.set_font("14px Arial");
+        ctx.set_text_align("right");
+        ctx.fill_text(
+            &self.localization.format("title.streak", &[&current_streak, &best_streak]),
+            left + width,
+            top + height + 16.0,
+        )?;
+        ctx.set_text_align("center");
+        
+        // オフラインボタンを描画
+        let (left, top, width, height) = Self::offline_button_bounds(canvas_width, canvas_height);
+        ctx.set_fill_style(&JsValue::from_str("#78909C"));
+        ctx.fill_rect(left, top, width, height);
+        
+        ctx.set_fill_style(&JsValue::from_str("#FFFFFF"));
+        ctx.set_font("bold 18px Arial");
+        ctx.fill_text(self.localization.text("menu.offline"), left + width / 2.0, top + height / 2.0)?;
+        
         // 接続状態を描画
         self.draw_connection_status(is_connected)?;
         
@@ -308,49 +1258,1466 @@ impl GameRenderer {
     }
     
     /**
-     * ゲームオーバー画面を描画する
+     * タイトル画面の名前入力欄の位置とサイズを取得する
+     * 
+     * @return (左端X, 上端Y, 幅, 高さ)
+     */
+    pub fn name_field_bounds(canvas_width: f64, canvas_height: f64) -> (f64, f64, f64, f64) {
+        let field_width = 300.0;
+        let field_height = 34.0;
+        
+        (canvas_width / 2.0 - field_width / 2.0, canvas_height / 2.0 - 20.0, field_width, field_height)
+    }
+    
+    /**
+     * タイトル画面の名前入力欄を描画する
+     * 
+     * 名前が空で入力中でない場合はプレースホルダーを表示します。
+     * 
+     * @param name 入力中の名前
+     * @param focused 入力中かどうか
+     */
+    pub fn draw_name_field(&self, canvas_width: f64, canvas_height: f64, name: &str, focused: bool) -> Result<(), JsValue> {
+        let ctx = &self.context;
+        let (left, top, width, height) = Self::name_field_bounds(canvas_width, canvas_height);
+        
+        // 入力欄の背景と枠
+        ctx.set_fill_style(&JsValue::from_str("#FFFFFF"));
+        ctx.fill_rect(left, top, width, height);
+        ctx.set_stroke_style(&JsValue::from_str(if focused { "#4CAF50" } else { "#9E9E9E" }));
+        ctx.set_line_width(2.0);
+        ctx.stroke_rect(left, top, width, height);
+        
+        ctx.set_font("18px Arial");
+        ctx.set_text_align("left");
+        ctx.set_text_baseline("middle");
+        
+        if name.is_empty() && !focused {
+            ctx.set_fill_style(&JsValue::from_str("#9E9E9E"));
+            ctx.fill_text(self.localization.text("title.name_placeholder"), left + 10.0, top + height / 2.0)?;
+        } else {
+            ctx.set_fill_style(&JsValue::from_str("#222222"));
+            ctx.fill_text(name, left + 10.0, top + height / 2.0)?;
+            
+            // 入力中はキャレットを表示
+            if focused {
+                let caret_x = left + 10.0 + ctx.measure_text(name)?.width() + 2.0;
+                ctx.fill_rect(caret_x, top + 7.0, 2.0, height - 14.0);
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /**
+     * タイトル画面のアバターボタンの位置とサイズを取得する
+     * 
+     * 名前入力欄の右隣に並べます。
+     * 
+     * @return (左端X, 上端Y, 幅, 高さ)
+     */
+    pub fn avatar_button_bounds(canvas_width: f64, canvas_height: f64) -> (f64, f64, f64, f64) {
+        let (left, top, width, height) = Self::name_field_bounds(canvas_width, canvas_height);
+        
+        (left + width + 8.0, top, height, height)
+    }
+    
+    /**
+     * タイトル画面のアバターボタンを描画する
+     * 
+     * アバターが未設定の場合は「+」を表示します。
+     * 
+     * @param avatar 現在のアバター
      */
-    pub fn draw_game_over_screen(&self, canvas_width: f64, canvas_height: f64) -> Result<(), JsValue> {
+    pub fn draw_avatar_button(&self, canvas_width: f64, canvas_height: f64, avatar: &str) -> Result<(), JsValue> {
         let ctx = &self.context;
+        let (left, top, width, height) = Self::avatar_button_bounds(canvas_width, canvas_height);
         
-        // 半透明の背景
-        ctx.set_fill_style(&JsValue::from_str("rgba(0, 0, 0, 0.7)"));
-        ctx.fill_rect(0.0, 0.0, canvas_width, canvas_height);
+        ctx.set_fill_style(&JsValue::from_str("#FFFFFF"));
+        ctx.fill_rect(left, top, width, height);
+        ctx.set_stroke_style(&JsValue::from_str("#9E9E9E"));
+        ctx.set_line_width(2.0);
+        ctx.stroke_rect(left, top, width, height);
         
-        // ゲームオーバーテキスト
-        ctx.set_fill_style(&JsValue::from_str("#FF0000"));
-        ctx.set_font("bold 48px Arial");
         ctx.set_text_align("center");
         ctx.set_text_baseline("middle");
-        ctx.fill_text(
-            "ゲームオーバー",
-            canvas_width / 2.0,
-            canvas_height / 2.0,
-        )?;
+        if avatar.is_empty() {
+            ctx.set_font("bold 20px Arial");
+            ctx.set_fill_style(&JsValue::from_str("#9E9E9E"));
+            ctx.fill_text("+", left + width / 2.0, top + height / 2.0)?;
+        } else {
+            ctx.set_font("20px Arial");
+            ctx.fill_text(avatar, left + width / 2.0, top + height / 2.0)?;
+        }
         
         Ok(())
     }
     
     /**
-     * 勝利画面を描画する
+     * タイトル画面の設定ボタンの位置とサイズを取得する
+     * 
+     * @return (左端X, 上端Y, 幅, 高さ)
+     */
+    pub fn settings_button_bounds(canvas_width: f64, canvas_height: f64) -> (f64, f64, f64, f64) {
+        let button_width = 200.0;
+        let button_height = 44.0;
+        
+        (canvas_width / 2.0 - button_width / 2.0, canvas_height / 2.0 + 100.0, button_width, button_height)
+    }
+    
+    /**
+     * タイトル画面のランキングボタンの位置とサイズを取得する
+     * 
+     * @return (左端X, 上端Y, 幅, 高さ)
+     */
+    pub fn title_leaderboard_button_bounds(canvas_width: f64, canvas_height: f64) -> (f64, f64, f64, f64) {
+        let (left, top, width, height) = Self::settings_button_bounds(canvas_width, canvas_height);
+        (left, top + height + 12.0, width, height)
+    }
+    
+    /**
+     * タイトル画面の統計ボタンの位置とサイズを取得する
+     * 
+     * @return (左端X, 上端Y, 幅, 高さ)
+     */
+    pub fn stats_button_bounds(canvas_width: f64, canvas_height: f64) -> (f64, f64, f64, f64) {
+        let (left, top, width, height) = Self::title_leaderboard_button_bounds(canvas_width, canvas_height);
+        (left, top + height + 12.0, width, height)
+    }
+    
+    /**
+     * タイトル画面のヘルプボタンの位置とサイズを取得する（右上に表示）
+     * 
+     * @return (左端X, 上端Y, 幅, 高さ)
+     */
+    pub fn help_button_bounds(canvas_width: f64, _canvas_height: f64) -> (f64, f64, f64, f64) {
+        let button_width = 110.0;
+        let button_height = 40.0;
+        
+        (canvas_width - button_width - 20.0, 20.0, button_width, button_height)
+    }
+    
+    /**
+     * タイトル画面の履歴ボタンの位置とサイズを取得する（ヘルプボタンの下に表示）
+     * 
+     * @return (左端X, 上端Y, 幅, 高さ)
+     */
+    pub fn history_button_bounds(canvas_width: f64, canvas_height: f64) -> (f64, f64, f64, f64) {
+        let (left, top, width, height) = Self::help_button_bounds(canvas_width, canvas_height);
+        (left, top + height + 10.0, width, height)
+    }
+    
+    /**
+     * タイトル画面のデイリーチャレンジボタンの位置とサイズを取得する（履歴ボタンの下に表示）
+     * 
+     * @return (左端X, 上端Y, 幅, 高さ)
+     */
+    pub fn daily_button_bounds(canvas_width: f64, canvas_height: f64) -> (f64, f64, f64, f64) {
+        let (left, top, width, height) = Self::history_button_bounds(canvas_width, canvas_height);
+        (left, top + height + 10.0, width, height)
+    }
+    
+    /**
+     * タイトル画面のオフラインボタンの位置とサイズを取得する（デイリーチャレンジの連続記録の下に表示）
+     * 
+     * @return (左端X, 上端Y, 幅, 高さ)
+     */
+    pub fn offline_button_bounds(canvas_width: f64, canvas_height: f64) -> (f64, f64, f64, f64) {
+        let (left, top, width, height) = Self::daily_button_bounds(canvas_width, canvas_height);
+        (left, top + height + 30.0, width, height)
+    }
+    
+    /**
+     * 対戦履歴画面の行の位置とサイズを取得する
+     * 
+     * @return (左端X, 上端Y, 幅, 高さ)
+     */
+    pub fn history_row_bounds(canvas_width: f64, index: usize) -> (f64, f64, f64, f64) {
+        let row_width = 680.0;
+        let row_height = 44.0;
+        
+        (canvas_width / 2.0 - row_width / 2.0, 100.0 + index as f64 * (row_height + 6.0), row_width, row_height)
+    }
+    
+    /**
+     * 対戦履歴画面の行にある練習ボタンの位置とサイズを取得する
+     * 
+     * @return (左端X, 上端Y, 幅, 高さ)
+     */
+    pub fn history_practice_button_bounds(canvas_width: f64, index: usize) -> (f64, f64, f64, f64) {
+        let (left, top, width, height) = Self::history_row_bounds(canvas_width, index);
+        (left + width - 96.0, top + 7.0, 86.0, height - 14.0)
+    }
+    
+    /**
+     * テーマ選択画面の行の位置とサイズを取得する
+     * 
+     * @return (左端X, 上端Y, 幅, 高さ)
+     */
+    pub fn theme_row_bounds(canvas_width: f64, index: usize) -> (f64, f64, f64, f64) {
+        let row_width = 480.0;
+        let row_height = 52.0;
+        
+        (canvas_width / 2.0 - row_width / 2.0, 100.0 + index as f64 * (row_height + 8.0), row_width, row_height)
+    }
+    
+    /**
+     * ロビー画面の準備完了ボタンの位置とサイズを取得する
+     * 
+     * @return (左端X, 上端Y, 幅, 高さ)
+     */
+    pub fn lobby_ready_button_bounds(canvas_width: f64, canvas_height: f64) -> (f64, f64, f64, f64) {
+        let button_width = 240.0;
+        let button_height = 48.0;
+        
+        (canvas_width / 2.0 - button_width / 2.0, canvas_height - 150.0, button_width, button_height)
+    }
+    
+    /**
+     * ロビー画面のホスト用開始ボタンの位置とサイズを取得する
+     * 
+     * @return (左端X, 上端Y, 幅, 高さ)
+     */
+    pub fn lobby_start_button_bounds(canvas_width: f64, canvas_height: f64) -> (f64, f64, f64, f64) {
+        let (left, top, width, height) = Self::lobby_ready_button_bounds(canvas_width, canvas_height);
+        (left, top + height + 12.0, width, height)
+    }
+    
+    /**
+     * ロビー画面を描画する
+     * 
+     * 接続中のプレイヤーと準備完了の状態を一覧表示し、準備完了の切り替えボタンを表示します。
+     * ホストには全員の準備を待たずに開始するボタンも表示します。
+     * 
+     * @param lobby ロビーの状態
+     * @param local_player_id 自分のプレイヤーID
+     * @param highlighted マウスが乗っているボタン（0: 準備完了、1: 開始）
      */
-    pub fn draw_win_screen(&self, canvas_width: f64, canvas_height: f64) -> Result<(), JsValue> {
+    pub fn draw_lobby_screen(
+        &self,
+        canvas_width: f64,
+        canvas_height: f64,
+        lobby: &LobbyResource,
+        local_player_id: &Option<String>,
+        highlighted: Option<usize>
+    ) -> Result<(), JsValue> {
         let ctx = &self.context;
         
-        // 半透明の背景
-        ctx.set_fill_style(&JsValue::from_str("rgba(0, 0, 0, 0.7)"));
+        // 背景を描画
+        ctx.set_fill_style(&self.background_color().js_value());
         ctx.fill_rect(0.0, 0.0, canvas_width, canvas_height);
         
-        // 勝利テキスト
-        ctx.set_fill_style(&JsValue::from_str("#00FF00"));
-        ctx.set_font("bold 48px Arial");
+        // 見出しを描画
+        ctx.set_fill_style(&self.text_color().js_value());
+        ctx.set_font("bold 36px Arial");
         ctx.set_text_align("center");
         ctx.set_text_baseline("middle");
-        ctx.fill_text(
-            "勝利！",
-            canvas_width / 2.0,
-            canvas_height / 2.0,
-        )?;
+        ctx.fill_text(self.localization.text("lobby.heading"), canvas_width / 2.0, 60.0)?;
+        
+        ctx.set_font("18px Arial");
+        let status = self.localization.format("lobby.ready_count", &[&lobby.ready_count(), &lobby.players().len()]);
+        ctx.fill_text(&status, canvas_width / 2.0, 105.0)?;
+        
+        // プレイヤー一覧を描画
+        let row_width = 400.0;
+        let row_height = 36.0;
+        let left = canvas_width / 2.0 - row_width / 2.0;
+        for (index, player) in lobby.players().iter().enumerate() {
+            let top = 130.0 + index as f64 * (row_height + 6.0);
+            
+            let is_local = local_player_id.as_deref() == Some(player.id.as_str());
+            ctx.set_fill_style(&JsValue::from_str(if is_local { "#607D8B" } else { "#546E7A" }));
+            ctx.fill_rect(left, top, row_width, row_height);
+            
+            ctx.set_fill_style(&JsValue::from_str("#FFFFFF"));
+            ctx.set_font("18px Arial");
+            ctx.set_text_align("left");
+            let mut name = player.display_name(self.localization.language());
+            if lobby.is_host(&player.id) {
+                name.push_str(self.localization.text("lobby.host_suffix"));
+            }
+            ctx.fill_text(&name, left + 14.0, top + row_height / 2.0)?;
+            
+            ctx.set_text_align("right");
+            ctx.set_fill_style(&JsValue::from_str(if player.ready { "#A5D6A7" } else { "#FFCC80" }));
+            let state = self.localization.text(if player.ready { "lobby.ready" } else { "lobby.waiting" });
+            ctx.fill_text(state, left + row_width - 14.0, top + row_height / 2.0)?;
+        }
+        
+        // 準備完了ボタンを描画
+        let local_ready = local_player_id.as_deref().is_some_and(|id| lobby.is_ready(id));
+        let (left, top, width, height) = Self::lobby_ready_button_bounds(canvas_width, canvas_height);
+        let color = match (local_ready, highlighted == Some(0)) {
+            (true, true) => "#90A4AE",
+            (true, false) => "#78909C",
+            (false, true) => "#66BB6A",
+            (false, false) => "#4CAF50",
+        };
+        ctx.set_fill_style(&JsValue::from_str(color));
+        ctx.fill_rect(left, top, width, height);
+        
+        ctx.set_fill_style(&JsValue::from_str("#FFFFFF"));
+        ctx.set_font("bold 20px Arial");
+        ctx.set_text_align("center");
+        let label = self.localization.text(if local_ready { "lobby.unready" } else { "lobby.ready" });
+        ctx.fill_text(label, left + width / 2.0, top + height / 2.0)?;
+        
+        // ホストには開始ボタンを描画
+        if local_player_id.as_deref().is_some_and(|id| lobby.is_host(id)) {
+            let (left, top, width, height) = Self::lobby_start_button_bounds(canvas_width, canvas_height);
+            let color = if highlighted == Some(1) { "#FFB74D" } else { "#FF9800" };
+            ctx.set_fill_style(&JsValue::from_str(color));
+            ctx.fill_rect(left, top, width, height);
+            
+            ctx.set_fill_style(&JsValue::from_str("#FFFFFF"));
+            ctx.fill_text(self.localization.text("lobby.start_now"), left + width / 2.0, top + height / 2.0)?;
+        }
+        
+        Ok(())
+    }
+    
+    /**
+     * ルーム選択画面の行の位置とサイズを取得する
+     * 
+     * @return (左端X, 上端Y, 幅, 高さ)
+     */
+    pub fn room_row_bounds(canvas_width: f64, index: usize) -> (f64, f64, f64, f64) {
+        let row_width = 480.0;
+        let row_height = 48.0;
+        
+        (canvas_width / 2.0 - row_width / 2.0, 110.0 + index as f64 * (row_height + 8.0), row_width, row_height)
+    }
+    
+    /**
+     * ルーム選択画面の下部のボタン（0: 作成、1: クイックプレイ、2: 戻る）の位置とサイズを取得する
+     * 
+     * @return (左端X, 上端Y, 幅, 高さ)
+     */
+    pub fn room_select_button_bounds(canvas_width: f64, canvas_height: f64, index: usize) -> (f64, f64, f64, f64) {
+        let button_width = 170.0;
+        let button_height = 48.0;
+        let left = canvas_width / 2.0 - button_width * 1.5 - 15.0 + index as f64 * (button_width + 15.0);
+        
+        (left, canvas_height - 100.0, button_width, button_height)
+    }
+    
+    /**
+     * ルーム選択画面を描画する
+     * 
+     * サーバーから受け取ったルームを一覧表示し、ルームの作成・既定のルームでの開始・戻るボタンを表示します。
+     * 参加・作成の応答を待っている間はボタンを暗くします。
+     * 
+     * @param room_state ルーム一覧と要求の状態
+     * @param highlighted マウスが乗っているボタン
+     */
+    pub fn draw_room_select_screen(
+        &self,
+        canvas_width: f64,
+        canvas_height: f64,
+        room_state: &RoomStateResource,
+        highlighted: Option<RoomSelectButton>
+    ) -> Result<(), JsValue> {
+        let ctx = &self.context;
+        let joining = room_state.is_joining();
+        
+        // 背景を描画
+        ctx.set_fill_style(&self.background_color().js_value());
+        ctx.fill_rect(0.0, 0.0, canvas_width, canvas_height);
+        
+        // 見出しを描画
+        ctx.set_fill_style(&self.text_color().js_value());
+        ctx.set_font("bold 36px Arial");
+        ctx.set_text_align("center");
+        ctx.set_text_baseline("middle");
+        ctx.fill_text(self.localization.text("room.heading"), canvas_width / 2.0, 60.0)?;
+        
+        if room_state.rooms().is_empty() {
+            ctx.set_font("18px Arial");
+            let key = if room_state.is_loaded() { "room.empty" } else { "room.loading" };
+            ctx.fill_text(self.localization.text(key), canvas_width / 2.0, canvas_height / 2.0 - 40.0)?;
+        }
+        
+        // ルーム一覧を描画
+        for (index, room) in room_state.rooms().iter().enumerate() {
+            let (left, top, width, height) = Self::room_row_bounds(canvas_width, index);
+            let color = if !joining && highlighted == Some(RoomSelectButton::Room(index)) { "#78909C" } else { "#546E7A" };
+            ctx.set_fill_style(&JsValue::from_str(color));
+            ctx.fill_rect(left, top, width, height);
+            
+            ctx.set_fill_style(&JsValue::from_str("#FFFFFF"));
+            ctx.set_text_align("left");
+            ctx.set_font("bold 18px Arial");
+            ctx.fill_text(&room.code, left + 14.0, top + height / 2.0)?;
+            ctx.set_font("15px Arial");
+            let detail = self.localization.format("room.detail", &[&room.players, &room.board_width, &room.board_height, &room.mine_count]);
+            ctx.fill_text(&detail, left + 120.0, top + height / 2.0)?;
+            
+            ctx.set_text_align("right");
+            ctx.set_fill_style(&JsValue::from_str(if room.in_lobby { "#A5D6A7" } else { "#FFCC80" }));
+            let state = self.localization.text(if room.in_lobby { "room.in_lobby" } else { "room.playing" });
+            ctx.fill_text(state, left + width - 14.0, top + height / 2.0)?;
+        }
+        
+        // 下部のボタンを描画
+        let buttons = [
+            (RoomSelectButton::Create, "room.create"),
+            (RoomSelectButton::QuickPlay, "room.quick_play"),
+            (RoomSelectButton::Back, "common.back"),
+        ];
+        for (index, (button, key)) in buttons.into_iter().enumerate() {
+            let (left, top, width, height) = Self::room_select_button_bounds(canvas_width, canvas_height, index);
+            let color = match (joining, highlighted == Some(button)) {
+                (true, _) => "#9E9E9E",
+                (false, true) => "#66BB6A",
+                (false, false) => "#4CAF50",
+            };
+            ctx.set_fill_style(&JsValue::from_str(color));
+            ctx.fill_rect(left, top, width, height);
+            
+            ctx.set_fill_style(&JsValue::from_str("#FFFFFF"));
+            ctx.set_font("bold 18px Arial");
+            ctx.set_text_align("center");
+            ctx.fill_text(self.localization.text(key), left + width / 2.0, top + height / 2.0)?;
+        }
+        
+        Ok(())
+    }
+    
+    /**
+     * 統計画面の戻るボタンの位置とサイズを取得する
+     * 
+     * @return (左端X, 上端Y, 幅, 高さ)
+     */
+    pub fn stats_back_button_bounds(canvas_width: f64, canvas_height: f64) -> (f64, f64, f64, f64) {
+        let button_width = 200.0;
+        let button_height = 44.0;
+        
+        (canvas_width / 2.0 - button_width / 2.0, canvas_height - 90.0, button_width, button_height)
+    }
+    
+    /**
+     * 統計画面を描画する
+     * 
+     * 難易度ごとの累計記録を表形式で表示します。
+     * 
+     * @param stats 累計統計
+     * @param back_highlighted 戻るボタンにマウスが乗っているかどうか
+     */
+    pub fn draw_stats_screen(
+        &self,
+        canvas_width: f64,
+        canvas_height: f64,
+        stats: &StatsResource,
+        back_highlighted: bool
+    ) -> Result<(), JsValue> {
+        let ctx = &self.context;
+        
+        // 背景を描画
+        ctx.set_fill_style(&self.background_color().js_value());
+        ctx.fill_rect(0.0, 0.0, canvas_width, canvas_height);
+        
+        // 見出しを描画
+        ctx.set_fill_style(&self.text_color().js_value());
+        ctx.set_font("bold 36px Arial");
+        ctx.set_text_align("center");
+        ctx.set_text_baseline("middle");
+        ctx.fill_text(self.localization.text("menu.stats"), canvas_width / 2.0, 70.0)?;
+        
+        // 列ごとの幅と見出し
+        let columns: [(&str, f64); 8] = [
+            ("stats.difficulty", 100.0),
+            ("stats.played", 80.0),
+            ("stats.wins", 70.0),
+            ("stats.losses", 70.0),
+            ("stats.win_rate", 70.0),
+            ("stats.best", 110.0),
+            ("stats.cells", 110.0),
+            ("stats.flags", 80.0),
+        ];
+        let table_width: f64 = columns.iter().map(|(_, width)| width).sum();
+        let table_left = (canvas_width - table_width) / 2.0;
+        let row_height = 48.0;
+        let header_top = 130.0;
+        
+        // 行を描画（見出し行＋難易度ごとの行）
+        let mut rows: Vec<[String; 8]> = vec![columns.map(|(title, _)| self.localization.text(title).to_string())];
+        for difficulty in STATS_DIFFICULTIES.iter() {
+            let record = stats.stats(difficulty);
+            rows.push([
+                difficulty.label(self.localization.language()).to_string(),
+                record.games_played.to_string(),
+                record.wins.to_string(),
+                record.losses.to_string(),
+                record.win_rate_text(),
+                record.best_time_text(),
+                record.cells_revealed.to_string(),
+                record.flags_placed.to_string(),
+            ]);
+        }
+        
+        for (row_index, row) in rows.iter().enumerate() {
+            let top = header_top + row_index as f64 * row_height;
+            let color = if row_index == 0 { "#37474F" } else if row_index % 2 == 0 { "#607D8B" } else { "#546E7A" };
+            ctx.set_fill_style(&JsValue::from_str(color));
+            ctx.fill_rect(table_left, top, table_width, row_height - 4.0);
+            
+            ctx.set_fill_style(&JsValue::from_str("#FFFFFF"));
+            ctx.set_font(if row_index == 0 { "bold 16px Arial" } else { "16px Arial" });
+            let mut x = table_left;
+            for (text, (_, width)) in row.iter().zip(columns.iter()) {
+                ctx.fill_text(text, x + width / 2.0, top + (row_height - 4.0) / 2.0)?;
+                x += width;
+            }
+        }
+        
+        // 戻るボタンを描画
+        let (left, top, width, height) = Self::stats_back_button_bounds(canvas_width, canvas_height);
+        let color = if back_highlighted { "#66BB6A" } else { "#4CAF50" };
+        ctx.set_fill_style(&JsValue::from_str(color));
+        ctx.fill_rect(left, top, width, height);
+        
+        ctx.set_fill_style(&JsValue::from_str("#FFFFFF"));
+        ctx.set_font("bold 20px Arial");
+        ctx.fill_text(self.localization.text("common.back"), left + width / 2.0, top + height / 2.0)?;
+        
+        Ok(())
+    }
+    
+    /**
+     * 対戦履歴画面を描画する
+     * 
+     * 終了したゲームを新しい順に並べ、地雷の配置が分かるゲームには練習ボタンを表示します。
+     * 戻るボタンは統計画面と同じ位置に表示します。
+     * 
+     * @param rows 表示する履歴
+     * @param highlighted マウスが乗っている練習ボタンの行のインデックス
+     * @param back_highlighted 戻るボタンにマウスが乗っているかどうか
+     */
+    pub fn draw_history_screen(
+        &self,
+        canvas_width: f64,
+        canvas_height: f64,
+        rows: &[HistoryRowView],
+        highlighted: Option<usize>,
+        back_highlighted: bool
+    ) -> Result<(), JsValue> {
+        let ctx = &self.context;
+        
+        // 背景を描画
+        ctx.set_fill_style(&self.background_color().js_value());
+        ctx.fill_rect(0.0, 0.0, canvas_width, canvas_height);
+        
+        // 見出しを描画
+        ctx.set_fill_style(&self.text_color().js_value());
+        ctx.set_font("bold 36px Arial");
+        ctx.set_text_align("center");
+        ctx.set_text_baseline("middle");
+        ctx.fill_text(self.localization.text("menu.history"), canvas_width / 2.0, 50.0)?;
+        
+        if rows.is_empty() {
+            ctx.set_font("18px Arial");
+            ctx.fill_text(self.localization.text("history.empty"), canvas_width / 2.0, canvas_height / 2.0)?;
+        }
+        
+        for (index, row) in rows.iter().enumerate() {
+            let (left, top, width, height) = Self::history_row_bounds(canvas_width, index);
+            ctx.set_fill_style(&JsValue::from_str(if row.win { "#2E7D32" } else { "#546E7A" }));
+            ctx.fill_rect(left, top, width, height);
+            
+            ctx.set_fill_style(&JsValue::from_str("#FFFFFF"));
+            ctx.set_text_align("left");
+            ctx.set_font("bold 16px Arial");
+            ctx.fill_text(&row.summary, left + 12.0, top + 14.0)?;
+            ctx.set_font("13px Arial");
+            ctx.fill_text(&row.players, left + 12.0, top + 32.0)?;
+            
+            // 練習ボタン
+            if row.can_practice {
+                let (button_left, button_top, button_width, button_height) = Self::history_practice_button_bounds(canvas_width, index);
+                let color = if highlighted == Some(index) { "#FFB74D" } else { "#FF9800" };
+                ctx.set_fill_style(&JsValue::from_str(color));
+                ctx.fill_rect(button_left, button_top, button_width, button_height);
+                
+                ctx.set_fill_style(&JsValue::from_str("#FFFFFF"));
+                ctx.set_font("bold 15px Arial");
+                ctx.set_text_align("center");
+                ctx.fill_text(self.localization.text("history.practice"), button_left + button_width / 2.0, button_top + button_height / 2.0)?;
+            }
+        }
+        
+        // 戻るボタンを描画
+        let (left, top, width, height) = Self::stats_back_button_bounds(canvas_width, canvas_height);
+        let color = if back_highlighted { "#66BB6A" } else { "#4CAF50" };
+        ctx.set_fill_style(&JsValue::from_str(color));
+        ctx.fill_rect(left, top, width, height);
+        
+        ctx.set_fill_style(&JsValue::from_str("#FFFFFF"));
+        ctx.set_font("bold 20px Arial");
+        ctx.set_text_align("center");
+        ctx.fill_text(self.localization.text("common.back"), left + width / 2.0, top + height / 2.0)?;
+        
+        Ok(())
+    }
+    
+    /**
+     * テーマ選択画面を描画する
+     * 
+     * 各テーマの色見本を並べ、未解放のテーマは暗くして解放条件を表示します。
+     * 戻るボタンは統計画面と同じ位置に表示します。
+     * 
+     * @param rows 表示するテーマ
+     * @param highlighted マウスが乗っている行のインデックス
+     * @param back_highlighted 戻るボタンにマウスが乗っているかどうか
+     */
+    pub fn draw_theme_screen(
+        &self,
+        canvas_width: f64,
+        canvas_height: f64,
+        rows: &[ThemeRowView],
+        highlighted: Option<usize>,
+        back_highlighted: bool
+    ) -> Result<(), JsValue> {
+        let ctx = &self.context;
+        
+        // 背景を描画
+        ctx.set_fill_style(&self.background_color().js_value());
+        ctx.fill_rect(0.0, 0.0, canvas_width, canvas_height);
+        
+        // 見出しを描画
+        ctx.set_fill_style(&self.text_color().js_value());
+        ctx.set_font("bold 36px Arial");
+        ctx.set_text_align("center");
+        ctx.set_text_baseline("middle");
+        ctx.fill_text(self.localization.text("settings.theme"), canvas_width / 2.0, 50.0)?;
+        
+        for (index, row) in rows.iter().enumerate() {
+            let (left, top, width, height) = Self::theme_row_bounds(canvas_width, index);
+            let locked = row.requirement.is_some();
+            
+            // 行の背景は未解放なら暗く、選択中・マウスオーバーなら明るくする
+            ctx.set_global_alpha(if locked { 0.5 } else { 1.0 });
+            let color = if row.selected {
+                "#4CAF50"
+            } else if highlighted == Some(index) && !locked {
+                "#78909C"
+            } else {
+                "#546E7A"
+            };
+            ctx.set_fill_style(&JsValue::from_str(color));
+            ctx.fill_rect(left, top, width, height);
+            
+            // 色見本（背景色の上に文字色）
+            ctx.set_fill_style(&row.theme.background_color().js_value());
+            ctx.fill_rect(left + 8.0, top + 8.0, height - 16.0, height - 16.0);
+            ctx.set_fill_style(&row.theme.text_color().js_value());
+            ctx.set_font("bold 18px Arial");
+            ctx.set_text_align("center");
+            ctx.fill_text("A", left + height / 2.0, top + height / 2.0)?;
+            
+            ctx.set_fill_style(&JsValue::from_str("#FFFFFF"));
+            ctx.set_text_align("left");
+            match &row.requirement {
+                Some(requirement) => {
+                    ctx.set_font("bold 16px Arial");
+                    ctx.fill_text(row.theme.label(self.localization.language()), left + height + 8.0, top + 16.0)?;
+                    ctx.set_font("13px Arial");
+                    ctx.fill_text(requirement, left + height + 8.0, top + 36.0)?;
+                },
+                None => {
+                    ctx.set_font("bold 18px Arial");
+                    ctx.fill_text(row.theme.label(self.localization.language()), left + height + 8.0, top + height / 2.0)?;
+                },
+            }
+            ctx.set_global_alpha(1.0);
+        }
+        
+        // 戻るボタンを描画
+        let (left, top, width, height) = Self::stats_back_button_bounds(canvas_width, canvas_height);
+        let color = if back_highlighted { "#66BB6A" } else { "#4CAF50" };
+        ctx.set_fill_style(&JsValue::from_str(color));
+        ctx.fill_rect(left, top, width, height);
+        
+        ctx.set_fill_style(&JsValue::from_str("#FFFFFF"));
+        ctx.set_font("bold 20px Arial");
+        ctx.set_text_align("center");
+        ctx.fill_text(self.localization.text("common.back"), left + width / 2.0, top + height / 2.0)?;
+        
+        Ok(())
+    }
+    
+    /**
+     * ヘルプ画面を描画する
+     * 
+     * 操作方法とルールの説明を`HELP_SECTIONS`の順に、翻訳テーブルから表示言語の文言で表示します。
+     * 戻るボタンは統計画面と同じ位置に表示します。
+     * 
+     * @param back_highlighted 戻るボタンにマウスが乗っているかどうか
+     */
+    pub fn draw_help_screen(
+        &self,
+        canvas_width: f64,
+        canvas_height: f64,
+        back_highlighted: bool
+    ) -> Result<(), JsValue> {
+        let ctx = &self.context;
+        
+        // 背景を描画
+        ctx.set_fill_style(&self.background_color().js_value());
+        ctx.fill_rect(0.0, 0.0, canvas_width, canvas_height);
+        
+        // 見出しを描画
+        ctx.set_fill_style(&self.text_color().js_value());
+        ctx.set_font("bold 36px Arial");
+        ctx.set_text_align("center");
+        ctx.set_text_baseline("middle");
+        ctx.fill_text(self.localization.text("menu.help"), canvas_width / 2.0, 50.0)?;
+        
+        // 項目ごとに見出しと説明を描画
+        let left = 60.0;
+        let mut y = 100.0;
+        ctx.set_text_align("left");
+        for (heading, lines) in HELP_SECTIONS.iter() {
+            ctx.set_font("bold 20px Arial");
+            ctx.fill_text(self.localization.text(heading), left, y)?;
+            y += 28.0;
+            
+            ctx.set_font("16px Arial");
+            for line in lines.iter() {
+                ctx.fill_text(self.localization.text(line), left + 20.0, y)?;
+                y += 22.0;
+            }
+            y += 12.0;
+        }
+        
+        // 戻るボタンを描画
+        let (left, top, width, height) = Self::stats_back_button_bounds(canvas_width, canvas_height);
+        let color = if back_highlighted { "#66BB6A" } else { "#4CAF50" };
+        ctx.set_fill_style(&JsValue::from_str(color));
+        ctx.fill_rect(left, top, width, height);
+        
+        ctx.set_fill_style(&JsValue::from_str("#FFFFFF"));
+        ctx.set_font("bold 20px Arial");
+        ctx.set_text_align("center");
+        ctx.fill_text(self.localization.text("common.back"), left + width / 2.0, top + height / 2.0)?;
+        
+        Ok(())
+    }
+    
+    /**
+     * ランキングボタンを描画する
+     */
+    pub fn draw_leaderboard_button(&self, left: f64, top: f64, width: f64, height: f64) -> Result<(), JsValue> {
+        let ctx = &self.context;
+        
+        ctx.set_fill_style(&JsValue::from_str("#FF9800"));
+        ctx.fill_rect(left, top, width, height);
+        
+        ctx.set_fill_style(&JsValue::from_str("#FFFFFF"));
+        ctx.set_font("bold 20px Arial");
+        ctx.set_text_align("center");
+        ctx.set_text_baseline("middle");
+        ctx.fill_text(self.localization.text("menu.leaderboard"), left + width / 2.0, top + height / 2.0)?;
+        
+        Ok(())
+    }
+    
+    /**
+     * ランキングパネルの位置とサイズを取得する
+     * 
+     * @return (左端X, 上端Y, 幅, 高さ)
+     */
+    pub fn leaderboard_panel_bounds(canvas_width: f64, canvas_height: f64) -> (f64, f64, f64, f64) {
+        let panel_width = 420.0_f64.min(canvas_width - 40.0);
+        let panel_height = 460.0_f64.min(canvas_height - 80.0);
+        
+        ((canvas_width - panel_width) / 2.0, (canvas_height - panel_height) / 2.0, panel_width, panel_height)
+    }
+    
+    /**
+     * ランキングパネルの一覧部分の高さを取得する（見出しを除いた部分）
+     */
+    pub fn leaderboard_viewport_height(canvas_width: f64, canvas_height: f64) -> f64 {
+        let (_, _, _, panel_height) = Self::leaderboard_panel_bounds(canvas_width, canvas_height);
+        panel_height - 80.0
+    }
+    
+    /**
+     * ランキングパネルを描画する
+     * 
+     * 一覧部分はクリップしてスクロール量だけずらして描画します。
+     * 
+     * @param lines 表示する行
+     * @param scroll_offset スクロール量
+     * @param status 一覧の代わりに表示するメッセージ（読み込み中など）
+     */
+    pub fn draw_leaderboard_panel(
+        &self,
+        canvas_width: f64,
+        canvas_height: f64,
+        lines: &[String],
+        scroll_offset: f64,
+        status: Option<&str>
+    ) -> Result<(), JsValue> {
+        let ctx = &self.context;
+        let (left, top, width, height) = Self::leaderboard_panel_bounds(canvas_width, canvas_height);
+        
+        // パネルの背景
+        ctx.set_fill_style(&JsValue::from_str("rgba(20, 20, 20, 0.95)"));
+        ctx.fill_rect(left, top, width, height);
+        ctx.set_stroke_style(&JsValue::from_str("#FF9800"));
+        ctx.set_line_width(2.0);
+        ctx.stroke_rect(left, top, width, height);
+        
+        // 見出し
+        ctx.set_fill_style(&JsValue::from_str("#FFFFFF"));
+        ctx.set_font("bold 26px Arial");
+        ctx.set_text_align("center");
+        ctx.set_text_baseline("middle");
+        ctx.fill_text(self.localization.text("leaderboard.heading"), left + width / 2.0, top + 32.0)?;
+        
+        let list_top = top + 64.0;
+        let viewport_height = Self::leaderboard_viewport_height(canvas_width, canvas_height);
+        
+        ctx.set_font("18px Arial");
+        ctx.set_text_align("left");
+        
+        if let Some(message) = status {
+            ctx.fill_text(message, left + 20.0, list_top + LEADERBOARD_LINE_HEIGHT / 2.0)?;
+        } else {
+            // 一覧部分だけに描画を制限
+            ctx.save();
+            ctx.begin_path();
+            ctx.rect(left, list_top, width, viewport_height);
+            ctx.clip();
+            
+            for (index, line) in lines.iter().enumerate() {
+                let y = list_top + index as f64 * LEADERBOARD_LINE_HEIGHT - scroll_offset;
+                if y + LEADERBOARD_LINE_HEIGHT < list_top || y > list_top + viewport_height {
+                    continue;
+                }
+                ctx.fill_text(line, left + 20.0, y + LEADERBOARD_LINE_HEIGHT / 2.0)?;
+            }
+            
+            ctx.restore();
+        }
+        
+        // 操作説明
+        ctx.set_fill_style(&JsValue::from_str("#AAAAAA"));
+        ctx.set_font("14px Arial");
+        ctx.set_text_align("center");
+        ctx.fill_text(self.localization.text("leaderboard.hint"), left + width / 2.0, top + height - 8.0)?;
+        
+        Ok(())
+    }
+    
+    /**
+     * 設定画面の行の位置とサイズを取得する
+     * 
+     * 設定項目の行の後ろに「戻る」ボタンの行が続きます。
+     * 
+     * @return (左端X, 上端Y, 幅, 高さ)
+     */
+    pub fn settings_row_bounds(canvas_width: f64, canvas_height: f64, index: usize) -> (f64, f64, f64, f64) {
+        let row_width = 420.0;
+        let row_height = 34.0;
+        let spacing = 5.0;
+        let rows = SettingsItem::ALL.len() + 1;
+        
+        let total_height = rows as f64 * row_height + (rows - 1) as f64 * spacing;
+        let left = canvas_width / 2.0 - row_width / 2.0;
+        let top = (canvas_height - total_height) / 2.0 + 30.0 + index as f64 * (row_height + spacing);
+        
+        (left, top, row_width, row_height)
+    }
+    
+    /**
+     * 設定画面の行にある音量スライダーの位置とサイズを取得する
+     * 
+     * 値の表示の左側に配置し、クリック判定では行の高さ全体を使います。
+     * 
+     * @return (左端X, 上端Y, 幅, 高さ)
+     */
+    pub fn settings_slider_bounds(canvas_width: f64, canvas_height: f64, index: usize) -> (f64, f64, f64, f64) {
+        let (left, top, width, height) = Self::settings_row_bounds(canvas_width, canvas_height, index);
+        let slider_width = 140.0;
+        let slider_height = 8.0;
+        
+        (left + width - 80.0 - slider_width, top + (height - slider_height) / 2.0, slider_width, slider_height)
+    }
+    
+    /**
+     * 設定画面を描画する
+     * 
+     * 各行をクリックすると値が切り替わり、最後の行は「戻る」ボタンです。
+     * 音量の行はスライダーをクリックした位置の値になります。
+     */
+    pub fn draw_settings_screen(
+        &self,
+        canvas_width: f64,
+        canvas_height: f64,
+        settings: &SettingsResource,
+        highlighted: Option<usize>
+    ) -> Result<(), JsValue> {
+        let ctx = &self.context;
+        
+        // 背景を描画
+        ctx.set_fill_style(&self.background_color().js_value());
+        ctx.fill_rect(0.0, 0.0, canvas_width, canvas_height);
+        
+        // 見出しを描画
+        let (_, first_top, _, _) = Self::settings_row_bounds(canvas_width, canvas_height, 0);
+        ctx.set_fill_style(&self.text_color().js_value());
+        ctx.set_font("bold 36px Arial");
+        ctx.set_text_align("center");
+        ctx.set_text_baseline("middle");
+        ctx.fill_text(self.localization.text("menu.settings"), canvas_width / 2.0, first_top - 40.0)?;
+        
+        // 設定項目を描画
+        for (index, item) in SettingsItem::ALL.iter().enumerate() {
+            let (left, top, width, height) = Self::settings_row_bounds(canvas_width, canvas_height, index);
+            
+            let color = if highlighted == Some(index) { "#78909C" } else { "#546E7A" };
+            ctx.set_fill_style(&JsValue::from_str(color));
+            ctx.fill_rect(left, top, width, height);
+            
+            ctx.set_fill_style(&JsValue::from_str("#FFFFFF"));
+            ctx.set_font("18px Arial");
+            ctx.set_text_align("left");
+            ctx.fill_text(item.label(self.localization.language()), left + 16.0, top + height / 2.0)?;
+            
+            ctx.set_text_align("right");
+            ctx.fill_text(&settings.value_text(*item), left + width - 16.0, top + height / 2.0)?;
+            
+            // カーソルの色は色見本も表示
+            if *item == SettingsItem::CursorColor {
+                let value_width = ctx.measure_text(&settings.value_text(*item))?.width();
+                ctx.set_fill_style(&JsValue::from_str(&settings.cursor_color));
+                ctx.begin_path();
+                ctx.arc(left + width - 28.0 - value_width, top + height / 2.0, 8.0, 0.0, std::f64::consts::PI * 2.0)?;
+                ctx.fill();
+            }
+            
+            // 音量はスライダーで表示
+            if let Some(value) = settings.slider_value(*item) {
+                let (bar_x, bar_y, bar_width, bar_height) = Self::settings_slider_bounds(canvas_width, canvas_height, index);
+                ctx.set_fill_style(&JsValue::from_str("#37474F"));
+                ctx.fill_rect(bar_x, bar_y, bar_width, bar_height);
+                ctx.set_fill_style(&JsValue::from_str("#4FC3F7"));
+                ctx.fill_rect(bar_x, bar_y, bar_width * value as f64, bar_height);
+                
+                // つまみ
+                ctx.set_fill_style(&JsValue::from_str("#FFFFFF"));
+                ctx.begin_path();
+                ctx.arc(bar_x + bar_width * value as f64, bar_y + bar_height / 2.0, 8.0, 0.0, std::f64::consts::PI * 2.0)?;
+                ctx.fill();
+            }
+        }
+        
+        // 戻るボタンを描画
+        let back_index = SettingsItem::ALL.len();
+        let (left, top, width, height) = Self::settings_row_bounds(canvas_width, canvas_height, back_index);
+        let color = if highlighted == Some(back_index) { "#66BB6A" } else { "#4CAF50" };
+        ctx.set_fill_style(&JsValue::from_str(color));
+        ctx.fill_rect(left, top, width, height);
+        
+        ctx.set_fill_style(&JsValue::from_str("#FFFFFF"));
+        ctx.set_font("bold 20px Arial");
+        ctx.set_text_align("center");
+        ctx.fill_text(self.localization.text("common.back"), left + width / 2.0, top + height / 2.0)?;
+        
+        Ok(())
+    }
+    
+    /**
+     * アクセシビリティ画面の行の位置とサイズを取得する
+     * 
+     * 項目の数と同じインデックスは「戻る」ボタンの位置です。
+     * 
+     * @return (左端X, 上端Y, 幅, 高さ)
+     */
+    pub fn accessibility_row_bounds(canvas_width: f64, canvas_height: f64, index: usize) -> (f64, f64, f64, f64) {
+        let row_width = 460.0;
+        let row_height = 44.0;
+        let spacing = 8.0;
+        let rows = AccessibilityItem::ALL.len() + 1;
+        
+        let total_height = rows as f64 * row_height + (rows - 1) as f64 * spacing;
+        let left = canvas_width / 2.0 - row_width / 2.0;
+        let top = (canvas_height - total_height) / 2.0 + 30.0 + index as f64 * (row_height + spacing);
+        
+        (left, top, row_width, row_height)
+    }
+    
+    /**
+     * アクセシビリティ画面を描画する
+     * 
+     * 各行をクリックすると値が切り替わり、最後の行は設定画面に戻るボタンです。
+     * 
+     * @param highlighted マウスが乗っている行のインデックス
+     */
+    pub fn draw_accessibility_screen(
+        &self,
+        canvas_width: f64,
+        canvas_height: f64,
+        highlighted: Option<usize>
+    ) -> Result<(), JsValue> {
+        let ctx = &self.context;
+        let language = self.localization.language();
+        
+        // 背景を描画
+        ctx.set_fill_style(&self.background_color().js_value());
+        ctx.fill_rect(0.0, 0.0, canvas_width, canvas_height);
+        
+        // 見出しを描画
+        let (_, first_top, _, _) = Self::accessibility_row_bounds(canvas_width, canvas_height, 0);
+        ctx.set_fill_style(&self.text_color().js_value());
+        ctx.set_font("bold 36px Arial");
+        ctx.set_text_align("center");
+        ctx.set_text_baseline("middle");
+        ctx.fill_text(self.localization.text("settings.accessibility"), canvas_width / 2.0, first_top - 40.0)?;
+        
+        // 設定項目を描画
+        for (index, item) in AccessibilityItem::ALL.iter().enumerate() {
+            let (left, top, width, height) = Self::accessibility_row_bounds(canvas_width, canvas_height, index);
+            
+            let color = if highlighted == Some(index) { "#78909C" } else { "#546E7A" };
+            ctx.set_fill_style(&JsValue::from_str(color));
+            ctx.fill_rect(left, top, width, height);
+            
+            ctx.set_fill_style(&JsValue::from_str("#FFFFFF"));
+            ctx.set_font("18px Arial");
+            ctx.set_text_align("left");
+            ctx.fill_text(item.label(language), left + 16.0, top + height / 2.0)?;
+            
+            ctx.set_text_align("right");
+            ctx.fill_text(&self.accessibility.value_text(*item, language), left + width - 16.0, top + height / 2.0)?;
+        }
+        
+        // 戻るボタンを描画
+        let back_index = AccessibilityItem::ALL.len();
+        let (left, top, width, height) = Self::accessibility_row_bounds(canvas_width, canvas_height, back_index);
+        let color = if highlighted == Some(back_index) { "#66BB6A" } else { "#4CAF50" };
+        ctx.set_fill_style(&JsValue::from_str(color));
+        ctx.fill_rect(left, top, width, height);
+        
+        ctx.set_fill_style(&JsValue::from_str("#FFFFFF"));
+        ctx.set_font("bold 20px Arial");
+        ctx.set_text_align("center");
+        ctx.fill_text(self.localization.text("common.back"), left + width / 2.0, top + height / 2.0)?;
+        
+        Ok(())
+    }
+    
+    /**
+     * 難易度選択ボタンの位置とサイズを取得する
+     * 
+     * 描画とクリック判定で同じレイアウトを使うための関数です。
+     * 
+     * @return (左端X, 上端Y, 幅, 高さ)
+     */
+    pub fn difficulty_button_bounds(canvas_width: f64, canvas_height: f64, index: usize) -> (f64, f64, f64, f64) {
+        let button_width = 320.0;
+        let button_height = 70.0;
+        let spacing = 20.0;
+        
+        let left = canvas_width / 2.0 - button_width / 2.0;
+        let top = canvas_height / 2.0 - 80.0 + index as f64 * (button_height + spacing);
+        
+        (left, top, button_width, button_height)
+    }
+    
+    /**
+     * 難易度選択画面を描画する
+     * 
+     * @param options 各難易度の(表示名, ボードサイズと地雷数の説明)
+     * @param highlighted マウスが乗っているボタンのインデックス
+     */
+    pub fn draw_difficulty_screen(
+        &self,
+        canvas_width: f64,
+        canvas_height: f64,
+        options: &[(&str, String)],
+        highlighted: Option<usize>,
+        is_connected: bool
+    ) -> Result<(), JsValue> {
+        let ctx = &self.context;
+        
+        // 背景を描画
+        ctx.set_fill_style(&self.background_color().js_value());
+        ctx.fill_rect(0.0, 0.0, canvas_width, canvas_height);
+        
+        // 見出しを描画
+        ctx.set_fill_style(&self.text_color().js_value());
+        ctx.set_font("bold 36px Arial");
+        ctx.set_text_align("center");
+        ctx.set_text_baseline("middle");
+        ctx.fill_text(
+            self.localization.text("difficulty.heading"),
+            canvas_width / 2.0,
+            canvas_height / 2.0 - 150.0,
+        )?;
+        
+        // 難易度ボタンを描画
+        for (index, (label, detail)) in options.iter().enumerate() {
+            let (left, top, width, height) = Self::difficulty_button_bounds(canvas_width, canvas_height, index);
+            
+            // ボタンの背景（マウスが乗っている場合は明るくする）
+            let color = if highlighted == Some(index) { "#66BB6A" } else { "#4CAF50" };
+            ctx.set_fill_style(&JsValue::from_str(color));
+            ctx.fill_rect(left, top, width, height);
+            
+            // 難易度名
+            ctx.set_fill_style(&JsValue::from_str("#FFFFFF"));
+            ctx.set_font("bold 24px Arial");
+            ctx.fill_text(label, left + width / 2.0, top + height / 2.0 - 12.0)?;
+            
+            // ボードサイズと地雷数
+            ctx.set_font("16px Arial");
+            ctx.fill_text(detail, left + width / 2.0, top + height / 2.0 + 16.0)?;
+        }
+        
+        // 接続状態を描画
+        self.draw_connection_status(is_connected)?;
+        
+        Ok(())
+    }
+    
+    /**
+     * カスタムボード画面の入力行の位置とサイズを取得する
+     * 
+     * @return (左端X, 上端Y, 幅, 高さ)
+     */
+    pub fn custom_board_row_bounds(canvas_width: f64, canvas_height: f64, index: usize) -> (f64, f64, f64, f64) {
+        let row_width = 360.0;
+        let row_height = 50.0;
+        let spacing = 14.0;
+        
+        (canvas_width / 2.0 - row_width / 2.0, canvas_height / 2.0 - 150.0 + index as f64 * (row_height + spacing), row_width, row_height)
+    }
+    
+    /**
+     * カスタムボード画面の入力行にある操作部品の位置とサイズを取得する
+     * 
+     * 右端に「−」ボタン・入力欄・「＋」ボタンの順で並びます。
+     * 
+     * @return [−ボタン, 入力欄, ＋ボタン]のそれぞれの(左端X, 上端Y, 幅, 高さ)
+     */
+    pub fn custom_board_control_bounds(canvas_width: f64, canvas_height: f64, index: usize) -> [(f64, f64, f64, f64); 3] {
+        let (left, top, width, height) = Self::custom_board_row_bounds(canvas_width, canvas_height, index);
+        let size = height - 16.0;
+        let field_width = 80.0;
+        let right = left + width - 8.0;
+        
+        [
+            (right - size * 2.0 - field_width - 12.0, top + 8.0, size, size),
+            (right - size - field_width - 6.0, top + 8.0, field_width, size),
+            (right - size, top + 8.0, size, size),
+        ]
+    }
+    
+    /**
+     * カスタムボード画面のボタンの位置とサイズを取得する
+     * 
+     * 0は戻るボタン、1は開始ボタンです。
+     * 
+     * @return (左端X, 上端Y, 幅, 高さ)
+     */
+    pub fn custom_board_button_bounds(canvas_width: f64, canvas_height: f64, index: usize) -> (f64, f64, f64, f64) {
+        let button_width = 170.0;
+        let button_height = 50.0;
+        let left = canvas_width / 2.0 - button_width - 10.0 + index as f64 * (button_width + 20.0);
+        
+        (left, canvas_height / 2.0 + 150.0, button_width, button_height)
+    }
+    
+    /**
+     * カスタムボード画面を描画する
+     * 
+     * 入力値はその場で検証し、制約を満たさない場合はエラーを表示して開始ボタンを無効にします。
+     * 地雷密度はバーで表示し、密度が高いほど警告色になります。
+     * 
+     * @param custom 入力中のボード設定
+     * @param highlighted マウスが乗っているボタンのインデックス（0は戻る、1は開始）
+     */
+    pub fn draw_custom_board_screen(
+        &self,
+        canvas_width: f64,
+        canvas_height: f64,
+        custom: &CustomBoardResource,
+        highlighted: Option<usize>
+    ) -> Result<(), JsValue> {
+        let ctx = &self.context;
+        let language = self.localization.language();
+        
+        // 背景を描画
+        ctx.set_fill_style(&self.background_color().js_value());
+        ctx.fill_rect(0.0, 0.0, canvas_width, canvas_height);
+        
+        // 見出しを描画
+        ctx.set_fill_style(&self.text_color().js_value());
+        ctx.set_font("bold 36px Arial");
+        ctx.set_text_align("center");
+        ctx.set_text_baseline("middle");
+        ctx.fill_text(self.localization.text("custom.heading"), canvas_width / 2.0, canvas_height / 2.0 - 200.0)?;
+        
+        // 入力行を描画
+        for (index, field) in CustomBoardField::ALL.iter().enumerate() {
+            let (left, top, width, height) = Self::custom_board_row_bounds(canvas_width, canvas_height, index);
+            ctx.set_fill_style(&JsValue::from_str("#546E7A"));
+            ctx.fill_rect(left, top, width, height);
+            
+            ctx.set_fill_style(&JsValue::from_str("#FFFFFF"));
+            ctx.set_font("18px Arial");
+            ctx.set_text_align("left");
+            ctx.fill_text(field.label(language), left + 16.0, top + height / 2.0)?;
+            
+            let [minus, input, plus] = Self::custom_board_control_bounds(canvas_width, canvas_height, index);
+            ctx.set_text_align("center");
+            ctx.set_font("bold 20px Arial");
+            for ((button_left, button_top, button_width, button_height), label) in [(minus, "−"), (plus, "+")] {
+                ctx.set_fill_style(&JsValue::from_str("#78909C"));
+                ctx.fill_rect(button_left, button_top, button_width, button_height);
+                ctx.set_fill_style(&JsValue::from_str("#FFFFFF"));
+                ctx.fill_text(label, button_left + button_width / 2.0, button_top + button_height / 2.0)?;
+            }
+            
+            // 入力欄（入力中は枠を強調）
+            let (input_left, input_top, input_width, input_height) = input;
+            let focused = custom.focused() == Some(*field);
+            ctx.set_fill_style(&JsValue::from_str("#FFFFFF"));
+            ctx.fill_rect(input_left, input_top, input_width, input_height);
+            ctx.set_stroke_style(&JsValue::from_str(if focused { "#4CAF50" } else { "#9E9E9E" }));
+            ctx.set_line_width(2.0);
+            ctx.stroke_rect(input_left, input_top, input_width, input_height);
+            ctx.set_fill_style(&JsValue::from_str("#222222"));
+            ctx.set_font("18px Arial");
+            ctx.fill_text(&custom.value(*field).to_string(), input_left + input_width / 2.0, input_top + input_height / 2.0)?;
+        }
+        
+        // 地雷密度のプレビュー
+        let (left, _, width, _) = Self::custom_board_row_bounds(canvas_width, canvas_height, 0);
+        let density = custom.density();
+        let density_y = canvas_height / 2.0 + 60.0;
+        ctx.set_fill_style(&self.text_color().js_value());
+        ctx.set_font("16px Arial");
+        ctx.fill_text(
+            &self.localization.format("custom.density", &[&format!("{:.1}", density * 100.0)]),
+            canvas_width / 2.0,
+            density_y,
+        )?;
+        
+        let bar_color = if density < 0.15 {
+            "#4CAF50"
+        } else if density < 0.25 {
+            "#FF9800"
+        } else {
+            "#F44336"
+        };
+        ctx.set_fill_style(&JsValue::from_str("#9E9E9E"));
+        ctx.fill_rect(left, density_y + 16.0, width, 10.0);
+        ctx.set_fill_style(&JsValue::from_str(bar_color));
+        ctx.fill_rect(left, density_y + 16.0, width * density.min(1.0), 10.0);
+        
+        // 検証エラー
+        let validation = custom.validate();
+        if let Err(error) = validation {
+            ctx.set_fill_style(&JsValue::from_str("#F44336"));
+            ctx.set_font("bold 16px Arial");
+            ctx.fill_text(&error.message(language), canvas_width / 2.0, density_y + 55.0)?;
+        }
+        
+        // 戻るボタンと開始ボタン（入力が正しくない間は開始できない）
+        for (index, key) in ["common.back", "custom.start"].iter().enumerate() {
+            let (left, top, width, height) = Self::custom_board_button_bounds(canvas_width, canvas_height, index);
+            let enabled = index == 0 || validation.is_ok();
+            let color = match (enabled, highlighted == Some(index)) {
+                (false, _) => "#9E9E9E",
+                (true, true) => "#66BB6A",
+                (true, false) => "#4CAF50",
+            };
+            ctx.set_fill_style(&JsValue::from_str(color));
+            ctx.fill_rect(left, top, width, height);
+            
+            ctx.set_fill_style(&JsValue::from_str("#FFFFFF"));
+            ctx.set_font("bold 20px Arial");
+            ctx.fill_text(self.localization.text(key), left + width / 2.0, top + height / 2.0)?;
+        }
+        
+        Ok(())
+    }
+    
+    /**
+     * ゲーム結果パネルの位置とサイズを取得する
+     * 
+     * @return (左端X, 上端Y, 幅, 高さ)
+     */
+    pub fn summary_panel_bounds(canvas_width: f64, canvas_height: f64) -> (f64, f64, f64, f64) {
+        let panel_width = 480.0_f64.min(canvas_width - 40.0);
+        let panel_height = 470.0_f64.min(canvas_height - 60.0);
+        
+        ((canvas_width - panel_width) / 2.0, (canvas_height - panel_height) / 2.0 + 20.0, panel_width, panel_height)
+    }
+    
+    /**
+     * ゲーム結果パネルのボタンの位置とサイズを取得する
+     * 
+     * ボタンはパネルの下端に横一列で並びます（並びは`SUMMARY_BUTTONS`の順）。
+     * 
+     * @return (左端X, 上端Y, 幅, 高さ)
+     */
+    pub fn summary_button_bounds(canvas_width: f64, canvas_height: f64, index: usize) -> (f64, f64, f64, f64) {
+        let (panel_left, panel_top, panel_width, panel_height) = Self::summary_panel_bounds(canvas_width, canvas_height);
+        let spacing = 12.0;
+        let button_height = 44.0;
+        let count = SUMMARY_BUTTONS.len() as f64;
+        let button_width = (panel_width - 40.0 - spacing * (count - 1.0)) / count;
+        
+        (
+            panel_left + 20.0 + index as f64 * (button_width + spacing),
+            panel_top + panel_height - button_height - 20.0,
+            button_width,
+            button_height
+        )
+    }
+    
+    /**
+     * 結果演出でボードを上から走査する帯を描画する
+     * 
+     * 走査済みの範囲を勝敗の色で薄く塗り、先端に明るい帯を描きます。
+     * カメラの視点での描画中に呼び出してください。
+     * 
+     * @param board 終了したボード
+     * @param progress 走査の進み具合（0.0〜1.0）
+     */
+    pub fn draw_board_sweep(&self, board: &Board, canvas_width: f64, canvas_height: f64, progress: f64) {
+        let ctx = &self.context;
+        let cell_size = board.cell_size;
+        let width = cell_size * board.width as f64;
+        let height = cell_size * board.height as f64;
+        let left = (canvas_width - width) / 2.0;
+        let top = (canvas_height - height) / 2.0;
+        let swept = height * progress;
+        let (tint, band) = if board.win {
+            ("rgba(76, 175, 80, 0.25)", "rgba(200, 255, 200, 0.7)")
+        } else {
+            ("rgba(244, 67, 54, 0.25)", "rgba(255, 205, 210, 0.7)")
+        };
+        
+        ctx.set_fill_style(&JsValue::from_str(tint));
+        ctx.fill_rect(left, top, width, swept);
+        
+        if progress < 1.0 {
+            ctx.set_fill_style(&JsValue::from_str(band));
+            ctx.fill_rect(left, top + swept - cell_size / 2.0, width, cell_size / 2.0);
+        }
+    }
+    
+    /**
+     * ゲーム結果パネルを描画する
+     * 
+     * 勝敗の見出しの下に、タイム・スコアなどの項目と
+     * プレイヤーごとの開いたセル数を貢献バーで表示し、下端に再戦などのボタンを並べます。
+     * パネルは画面の下からスライドして現れ、貢献バーも同時に伸びます。
+     * 
+     * @param summary 表示する内容
+     * @param highlighted マウスが乗っているボタンのインデックス
+     */
+    pub fn draw_game_summary(
+        &self,
+        canvas_width: f64,
+        canvas_height: f64,
+        summary: &GameSummaryView,
+        highlighted: Option<usize>
+    ) -> Result<(), JsValue> {
+        let ctx = &self.context;
+        let (left, top, width, height) = Self::summary_panel_bounds(canvas_width, canvas_height);
+        
+        // 半透明の背景（パネルに合わせて濃くする）
+        let eased = 1.0 - (1.0 - summary.panel_progress).powi(3);
+        ctx.set_fill_style(&JsValue::from_str(&format!("rgba(0, 0, 0, {})", 0.6 * eased)));
+        ctx.fill_rect(0.0, 0.0, canvas_width, canvas_height);
+        
+        // パネルを画面の下からスライドさせる
+        ctx.save();
+        ctx.translate(0.0, (1.0 - eased) * (canvas_height - top))?;
+        ctx.set_fill_style(&JsValue::from_str("#263238"));
+        ctx.fill_rect(left, top, width, height);
+        
+        // 見出し
+        ctx.set_fill_style(&JsValue::from_str(if summary.win { "#00FF00" } else { "#FF5252" }));
+        ctx.set_font("bold 36px Arial");
+        ctx.set_text_align("center");
+        ctx.set_text_baseline("middle");
+        ctx.fill_text(self.localization.text(if summary.win { "summary.win" } else { "summary.game_over" }), left + width / 2.0, top + 36.0)?;
+        
+        // 項目
+        let row_height = 28.0;
+        let mut y = top + 84.0;
+        ctx.set_font("18px Arial");
+        for (label, value) in summary.stats {
+            ctx.set_fill_style(&JsValue::from_str("#B0BEC5"));
+            ctx.set_text_align("left");
+            ctx.fill_text(label, left + 30.0, y)?;
+            
+            ctx.set_fill_style(&JsValue::from_str("#FFFFFF"));
+            ctx.set_text_align("right");
+            ctx.fill_text(value, left + width - 30.0, y)?;
+            y += row_height;
+        }
+        
+        // プレイヤーごとの開いたセル数（ボタンに重ならない分だけ表示）
+        y += 10.0;
+        ctx.set_fill_style(&JsValue::from_str("#FFFFFF"));
+        ctx.set_font("bold 18px Arial");
+        ctx.set_text_align("left");
+        ctx.fill_text(self.localization.text("summary.cells_by_player"), left + 30.0, y)?;
+        
+        let (_, buttons_top, _, _) = Self::summary_button_bounds(canvas_width, canvas_height, 0);
+        let list_bottom = if summary.rematch_status.is_some() { buttons_top - 44.0 } else { buttons_top - 20.0 };
+        let max_cells = summary.players.iter().map(|player| player.cells).max().unwrap_or(0).max(1);
+        let bar_left = left + 160.0;
+        let bar_max_width = width - 160.0 - 80.0;
+        ctx.set_font("16px Arial");
+        for player in summary.players {
+            y += 26.0;
+            if y > list_bottom {
+                break;
+            }
+            ctx.set_fill_style(&JsValue::from_str("#FFFFFF"));
+            ctx.set_text_align("left");
+            ctx.fill_text(&player.name, left + 46.0, y)?;
+            
+            // 最も多く開いたプレイヤーを最大の長さとする貢献バー
+            let bar_width = bar_max_width * player.cells as f64 / max_cells as f64 * eased;
+            ctx.set_fill_style(&player.color.js_value());
+            ctx.fill_rect(bar_left, y - 8.0, bar_width, 16.0);
+            
+            ctx.set_fill_style(&JsValue::from_str("#FFFFFF"));
+            ctx.set_text_align("right");
+            ctx.fill_text(&player.cells.to_string(), left + width - 30.0, y)?;
+        }
+        
+        // 再戦投票の補足
+        ctx.set_text_align("center");
+        if let Some(status) = summary.rematch_status {
+            ctx.set_fill_style(&JsValue::from_str("#FFE082"));
+            ctx.set_font("16px Arial");
+            ctx.fill_text(status, left + width / 2.0, buttons_top - 20.0)?;
+        }
+        
+        // ボタン（再戦ボタンは投票の進み具合を表示）
+        ctx.set_font("bold 16px Arial");
+        for (index, label) in SUMMARY_BUTTONS.iter().enumerate() {
+            let label = if index == 0 { summary.rematch_label } else { self.localization.text(label) };
+            let (button_left, button_top, button_width, button_height) = Self::summary_button_bounds(canvas_width, canvas_height, index);
+            let color = if highlighted == Some(index) { "#66BB6A" } else { "#4CAF50" };
+            ctx.set_fill_style(&JsValue::from_str(color));
+            ctx.fill_rect(button_left, button_top, button_width, button_height);
+            
+            ctx.set_fill_style(&JsValue::from_str("#FFFFFF"));
+            ctx.fill_text(label, button_left + button_width / 2.0, button_top + button_height / 2.0)?;
+        }
+        ctx.restore();
         
         Ok(())
     }
@@ -0,0 +1,223 @@
+/**
+ * アクセシビリティ設定リソース
+ *
+ * ハイコントラスト・文字の拡大・アニメーションの軽減・色覚に配慮した配色・
+ * スクリーンリーダーへの読み上げをまとめて管理するリソース
+ * 通常の設定とは別のキーでlocalStorageに保存する
+ */
+use serde::{Serialize, Deserialize};
+use super::localization::text;
+use super::settings::Language;
+
+/// localStorageに保存する際のキー
+pub const ACCESSIBILITY_STORAGE_KEY: &str = "minesweeper_accessibility";
+
+/// 文字の大きさ
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TextScale {
+    /// 標準
+    Normal,
+    /// 大きい
+    Large,
+    /// 特大
+    ExtraLarge,
+}
+
+impl TextScale {
+    /// 標準の文字の大きさに対する倍率
+    pub fn factor(&self) -> f64 {
+        match self {
+            TextScale::Normal => 1.0,
+            TextScale::Large => 1.25,
+            TextScale::ExtraLarge => 1.5,
+        }
+    }
+}
+
+/// 数字の配色
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ColorPalette {
+    /// 標準の配色
+    Standard,
+    /// 赤と緑を区別しにくい色覚向け
+    RedGreen,
+    /// 青と黄を区別しにくい色覚向け
+    BlueYellow,
+}
+
+impl ColorPalette {
+    /// 周囲の地雷数（1〜8）の色を取得
+    pub fn number_color(&self, count: u8) -> &'static str {
+        let colors = match self {
+            ColorPalette::Standard => ["#0000FF", "#008000", "#FF0000", "#000080", "#800000", "#008080", "#000000", "#808080"],
+            ColorPalette::RedGreen => ["#0072B2", "#A65E00", "#D55E00", "#1B2A6B", "#7A3E00", "#00607A", "#000000", "#555555"],
+            ColorPalette::BlueYellow => ["#C2185B", "#00796B", "#D32F2F", "#4A148C", "#6D4C41", "#00838F", "#000000", "#616161"],
+        };
+        colors.get((count as usize).wrapping_sub(1)).copied().unwrap_or("#000000")
+    }
+
+    /// 表示名を取得
+    pub fn label(&self, language: Language) -> &'static str {
+        match self {
+            ColorPalette::Standard => text(language, "palette.standard"),
+            ColorPalette::RedGreen => text(language, "palette.red_green"),
+            ColorPalette::BlueYellow => text(language, "palette.blue_yellow"),
+        }
+    }
+}
+
+/// アクセシビリティ画面に並べる項目
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessibilityItem {
+    /// ハイコントラスト
+    HighContrast,
+    /// 文字の大きさ
+    TextScale,
+    /// アニメーションを減らす
+    ReducedMotion,
+    /// 数字の配色
+    Palette,
+    /// スクリーンリーダーへの読み上げ
+    Announcements,
+}
+
+impl AccessibilityItem {
+    /// アクセシビリティ画面での表示順
+    pub const ALL: [AccessibilityItem; 5] = [
+        AccessibilityItem::HighContrast,
+        AccessibilityItem::TextScale,
+        AccessibilityItem::ReducedMotion,
+        AccessibilityItem::Palette,
+        AccessibilityItem::Announcements,
+    ];
+
+    /// 項目名を取得
+    pub fn label(&self, language: Language) -> &'static str {
+        let key = match self {
+            AccessibilityItem::HighContrast => "accessibility.high_contrast",
+            AccessibilityItem::TextScale => "accessibility.text_scale",
+            AccessibilityItem::ReducedMotion => "settings.reduced_motion",
+            AccessibilityItem::Palette => "accessibility.palette",
+            AccessibilityItem::Announcements => "accessibility.announcements",
+        };
+        text(language, key)
+    }
+}
+
+/// アクセシビリティ設定リソース
+/// 保存データに無い項目はデフォルト値で補う
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AccessibilityResource {
+    /// ハイコントラスト表示にするかどうか
+    pub high_contrast: bool,
+    /// 文字の大きさ
+    pub text_scale: TextScale,
+    /// アニメーションを減らすかどうか
+    pub reduced_motion: bool,
+    /// 数字の配色
+    pub palette: ColorPalette,
+    /// ゲームの状況をスクリーンリーダーに読み上げさせるかどうか
+    pub announcements: bool,
+}
+
+impl Default for AccessibilityResource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AccessibilityResource {
+    /// デフォルト設定を作成
+    pub fn new() -> Self {
+        Self {
+            high_contrast: false,
+            text_scale: TextScale::Normal,
+            reduced_motion: false,
+            palette: ColorPalette::Standard,
+            announcements: false,
+        }
+    }
+
+    /// 保存されたJSONから設定を復元（読み込めない場合はデフォルト設定）
+    pub fn from_json(json: &str) -> Self {
+        serde_json::from_str(json).unwrap_or_default()
+    }
+
+    /// 以前の設定のJSONから引き継ぐ（以前は通常の設定に「アニメーションを減らす」があった）
+    pub fn from_legacy_settings(json: &str) -> Self {
+        let reduced_motion = serde_json::from_str::<serde_json::Value>(json)
+            .ok()
+            .and_then(|value| value["reduced_motion"].as_bool())
+            .unwrap_or(false);
+        Self { reduced_motion, ..Self::new() }
+    }
+
+    /// 保存用のJSONに変換
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    /// 項目の値を次の選択肢に切り替える
+    pub fn cycle(&mut self, item: AccessibilityItem) {
+        match item {
+            AccessibilityItem::HighContrast => self.high_contrast = !self.high_contrast,
+            AccessibilityItem::TextScale => {
+                self.text_scale = match self.text_scale {
+                    TextScale::Normal => TextScale::Large,
+                    TextScale::Large => TextScale::ExtraLarge,
+                    TextScale::ExtraLarge => TextScale::Normal,
+                };
+            },
+            AccessibilityItem::ReducedMotion => self.reduced_motion = !self.reduced_motion,
+            AccessibilityItem::Palette => {
+                self.palette = match self.palette {
+                    ColorPalette::Standard => ColorPalette::RedGreen,
+                    ColorPalette::RedGreen => ColorPalette::BlueYellow,
+                    ColorPalette::BlueYellow => ColorPalette::Standard,
+                };
+            },
+            AccessibilityItem::Announcements => self.announcements = !self.announcements,
+        }
+    }
+
+    /// 項目の現在値を表示用の文字列で取得
+    pub fn value_text(&self, item: AccessibilityItem, language: Language) -> String {
+        let on_off = |enabled: bool| text(language, if enabled { "settings.on" } else { "settings.off" });
+        match item {
+            AccessibilityItem::HighContrast => on_off(self.high_contrast).to_string(),
+            AccessibilityItem::TextScale => format!("{}%", (self.text_scale.factor() * 100.0).round()),
+            AccessibilityItem::ReducedMotion => on_off(self.reduced_motion).to_string(),
+            AccessibilityItem::Palette => self.palette.label(language).to_string(),
+            AccessibilityItem::Announcements => on_off(self.announcements).to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_accessibility_json_round_trip() {
+        let mut accessibility = AccessibilityResource::new();
+        accessibility.cycle(AccessibilityItem::HighContrast);
+        accessibility.cycle(AccessibilityItem::TextScale);
+        accessibility.cycle(AccessibilityItem::TextScale);
+        accessibility.cycle(AccessibilityItem::Palette);
+
+        let restored = AccessibilityResource::from_json(&accessibility.to_json());
+        assert_eq!(restored, accessibility);
+        assert_eq!(restored.value_text(AccessibilityItem::TextScale, Language::English), "150%");
+        assert_eq!(restored.value_text(AccessibilityItem::HighContrast, Language::English), "On");
+        assert_eq!(restored.palette.number_color(1), "#0072B2");
+        assert_eq!(ColorPalette::Standard.number_color(9), "#000000");
+    }
+
+    #[test]
+    fn test_reduced_motion_is_carried_over_from_settings() {
+        let legacy = r#"{"theme":"Dark","reduced_motion":true}"#;
+        assert!(AccessibilityResource::from_legacy_settings(legacy).reduced_motion);
+        assert!(!AccessibilityResource::from_legacy_settings("{}").reduced_motion);
+    }
+}
\ No newline at end of file
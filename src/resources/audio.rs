@@ -0,0 +1,385 @@
+/**
+ * オーディオリソース
+ *
+ * Web Audio APIで効果音とBGMを再生するリソース
+ * 音声は起動時に波形を合成してAudioBufferに読み込んでおく
+ */
+use std::collections::HashMap;
+use wasm_bindgen::prelude::*;
+use web_sys::{AudioBuffer, AudioBufferSourceNode, AudioContext, GainNode};
+use super::settings::SettingsResource;
+
+/// 効果音の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SoundEffect {
+    /// セルを開いた
+    Reveal,
+    /// フラグを切り替えた
+    Flag,
+    /// 複数のセルがまとめて開いた
+    Chord,
+    /// 地雷を開いた
+    Explosion,
+    /// ゲームに勝利した
+    Win,
+}
+
+impl SoundEffect {
+    /// 全ての効果音
+    pub const ALL: [SoundEffect; 5] = [
+        SoundEffect::Reveal,
+        SoundEffect::Flag,
+        SoundEffect::Chord,
+        SoundEffect::Explosion,
+        SoundEffect::Win,
+    ];
+}
+
+/// BGMの種類（画面ごとに切り替える）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MusicTrack {
+    /// タイトル・メニュー画面
+    Title,
+    /// ゲーム画面
+    Game,
+}
+
+impl MusicTrack {
+    /// 全てのBGM
+    pub const ALL: [MusicTrack; 2] = [MusicTrack::Title, MusicTrack::Game];
+}
+
+/// 効果音を合成する際のサンプリングレート
+pub const EFFECT_SAMPLE_RATE: f32 = 44_100.0;
+
+/// BGMを切り替える際のクロスフェード時間（秒）
+pub const MUSIC_CROSSFADE_SECONDS: f64 = 1.0;
+
+/// 減衰する正弦波を合成
+fn tone(frequency: f32, duration: f32, amplitude: f32, sample_rate: f32) -> Vec<f32> {
+    let length = (duration * sample_rate) as usize;
+
+    (0..length)
+        .map(|i| {
+            let t = i as f32 / sample_rate;
+            let envelope = (1.0 - t / duration).powi(2);
+            (t * frequency * std::f32::consts::TAU).sin() * envelope * amplitude
+        })
+        .collect()
+}
+
+/// 効果音の波形を合成（値は-1.0〜1.0）
+pub fn synthesize(effect: SoundEffect, sample_rate: f32) -> Vec<f32> {
+    match effect {
+        SoundEffect::Reveal => tone(880.0, 0.05, 0.5, sample_rate),
+        SoundEffect::Flag => {
+            let mut samples = tone(660.0, 0.04, 0.5, sample_rate);
+            samples.extend(tone(990.0, 0.06, 0.5, sample_rate));
+            samples
+        },
+        SoundEffect::Chord => {
+            // 和音（ド・ミ・ソ）を重ねる
+            let notes = [523.25, 659.25, 783.99];
+            let mut samples = vec![0.0; (0.2 * sample_rate) as usize];
+            for frequency in notes {
+                for (sample, value) in samples.iter_mut().zip(tone(frequency, 0.2, 0.3, sample_rate)) {
+                    *sample += value;
+                }
+            }
+            samples
+        },
+        SoundEffect::Explosion => {
+            // 減衰するノイズ（擬似乱数で毎回同じ波形になる）
+            let duration = 0.6;
+            let length = (duration * sample_rate) as usize;
+            let mut state: u32 = 0x1234_5678;
+
+            (0..length)
+                .map(|i| {
+                    state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                    let noise = (state >> 8) as f32 / (1u32 << 24) as f32 * 2.0 - 1.0;
+                    let t = i as f32 / sample_rate;
+                    noise * (1.0 - t / duration).powi(3) * 0.8
+                })
+                .collect()
+        },
+        SoundEffect::Win => {
+            // 上昇するジングル（ド・ミ・ソ・高いド）
+            [523.25, 659.25, 783.99, 1046.5]
+                .iter()
+                .flat_map(|&frequency| tone(frequency, 0.12, 0.5, sample_rate))
+                .collect()
+        },
+    }
+}
+
+/// BGMの波形を合成（ループ再生しても継ぎ目が出ないよう各音符を減衰させる）
+pub fn synthesize_music(track: MusicTrack, sample_rate: f32) -> Vec<f32> {
+    // (周波数, 長さ（秒）)
+    let (notes, note_length): (&[f32], f32) = match track {
+        // ゆったりしたハ長調のアルペジオ
+        MusicTrack::Title => (&[261.63, 329.63, 392.0, 523.25, 392.0, 329.63, 293.66, 349.23], 0.5),
+        // テンポの速いイ短調のフレーズ
+        MusicTrack::Game => (&[
+            220.0, 261.63, 329.63, 261.63, 220.0, 261.63, 329.63, 392.0,
+            196.0, 246.94, 293.66, 246.94, 196.0, 246.94, 293.66, 349.23,
+        ], 0.25),
+    };
+
+    notes
+        .iter()
+        .flat_map(|&frequency| tone(frequency, note_length, 0.25, sample_rate))
+        .collect()
+}
+
+/// 再生中のBGM
+#[derive(Debug)]
+struct PlayingMusic {
+    /// 曲の種類
+    track: MusicTrack,
+    /// 再生ノード
+    source: AudioBufferSourceNode,
+    /// 音量調整ノード
+    gain: GainNode,
+}
+
+/// 効果音とBGMを再生するリソース
+/// AudioContextはユーザー操作後に`initialize`で作成する（ブラウザの自動再生制限のため）
+#[derive(Debug, Default)]
+pub struct AudioResource {
+    /// Web Audioのコンテキスト
+    context: Option<AudioContext>,
+    /// 読み込み済みの効果音
+    buffers: HashMap<SoundEffect, AudioBuffer>,
+    /// 読み込み済みのBGM
+    music_buffers: HashMap<MusicTrack, AudioBuffer>,
+    /// 再生中のBGM
+    current_music: Option<PlayingMusic>,
+    /// 効果音の音量（0.0〜1.0）
+    volume: f32,
+    /// BGMの音量（0.0〜1.0）
+    music_volume: f32,
+    /// ミュート中かどうか（効果音とBGMの両方に適用）
+    muted: bool,
+}
+
+impl AudioResource {
+    /// 未初期化のオーディオリソースを作成
+    pub fn new() -> Self {
+        Self {
+            volume: 1.0,
+            music_volume: 1.0,
+            ..Default::default()
+        }
+    }
+
+    /// AudioContextを作成して効果音を読み込む（初期化済みなら何もしない）
+    pub fn initialize(&mut self) -> Result<(), JsValue> {
+        if let Some(context) = &self.context {
+            // タブの切り替えなどで停止している場合は再開
+            let _ = context.resume()?;
+            return Ok(());
+        }
+
+        let context = AudioContext::new()?;
+        for effect in SoundEffect::ALL {
+            let samples = synthesize(effect, EFFECT_SAMPLE_RATE);
+            let buffer = context.create_buffer(1, samples.len() as u32, EFFECT_SAMPLE_RATE)?;
+            buffer.copy_to_channel(&samples, 0)?;
+            self.buffers.insert(effect, buffer);
+        }
+        for track in MusicTrack::ALL {
+            let samples = synthesize_music(track, EFFECT_SAMPLE_RATE);
+            let buffer = context.create_buffer(1, samples.len() as u32, EFFECT_SAMPLE_RATE)?;
+            buffer.copy_to_channel(&samples, 0)?;
+            self.music_buffers.insert(track, buffer);
+        }
+
+        self.context = Some(context);
+        Ok(())
+    }
+
+    /// BGMを止めてAudioContextを閉じる（再び使うにはinitializeし直す）
+    pub fn close(&mut self) -> Result<(), JsValue> {
+        if let Some(music) = self.current_music.take() {
+            music.source.stop()?;
+        }
+        self.buffers.clear();
+        self.music_buffers.clear();
+        if let Some(context) = self.context.take() {
+            let _ = context.close()?;
+        }
+        Ok(())
+    }
+
+    /// 初期化済みかどうか
+    pub fn is_initialized(&self) -> bool {
+        self.context.is_some()
+    }
+
+    /// 設定の音量とミュートを反映
+    pub fn apply_settings(&mut self, settings: &SettingsResource) {
+        self.set_volume(settings.sound_volume);
+        self.set_music_volume(settings.music_volume);
+        self.set_muted(!settings.sound_enabled);
+    }
+
+    /// 音量を設定（0.0〜1.0に制限）
+    pub fn set_volume(&mut self, volume: f32) {
+        self.volume = volume.clamp(0.0, 1.0);
+    }
+
+    /// 音量を取得
+    pub fn volume(&self) -> f32 {
+        self.volume
+    }
+
+    /// BGMの音量を設定（0.0〜1.0に制限、再生中のBGMにも反映）
+    pub fn set_music_volume(&mut self, volume: f32) {
+        self.music_volume = volume.clamp(0.0, 1.0);
+        self.update_music_gain();
+    }
+
+    /// BGMの音量を取得
+    pub fn music_volume(&self) -> f32 {
+        self.music_volume
+    }
+
+    /// ミュートを設定
+    pub fn set_muted(&mut self, muted: bool) {
+        self.muted = muted;
+        self.update_music_gain();
+    }
+
+    /// ミュート中かどうか
+    pub fn is_muted(&self) -> bool {
+        self.muted
+    }
+
+    /// BGMの実際の再生音量（ミュートを考慮）
+    fn effective_music_volume(&self) -> f32 {
+        if self.muted { 0.0 } else { self.music_volume }
+    }
+
+    /// 再生中のBGMの音量を更新
+    fn update_music_gain(&self) {
+        if let Some(music) = &self.current_music {
+            music.gain.gain().set_value(self.effective_music_volume());
+        }
+    }
+
+    /// 再生中のBGMを取得
+    pub fn current_music(&self) -> Option<MusicTrack> {
+        self.current_music.as_ref().map(|music| music.track)
+    }
+
+    /// BGMを切り替える（再生中の曲と同じなら何もしない）
+    /// 
+    /// 前の曲はフェードアウトし、新しい曲はフェードインする
+    pub fn play_music(&mut self, track: MusicTrack) -> Result<(), JsValue> {
+        if self.current_music() == Some(track) {
+            return Ok(());
+        }
+        let (Some(context), Some(buffer)) = (&self.context, self.music_buffers.get(&track)) else {
+            return Ok(());
+        };
+
+        let now = context.current_time();
+        let fade_end = now + MUSIC_CROSSFADE_SECONDS;
+
+        // 前の曲をフェードアウトして停止
+        if let Some(previous) = self.current_music.take() {
+            let gain = previous.gain.gain();
+            gain.set_value_at_time(gain.value(), now)?;
+            gain.linear_ramp_to_value_at_time(0.0, fade_end)?;
+            previous.source.stop_with_when(fade_end)?;
+        }
+
+        // 新しい曲をループ再生してフェードイン
+        let source = context.create_buffer_source()?;
+        source.set_buffer(Some(buffer));
+        source.set_loop(true);
+
+        let gain = context.create_gain()?;
+        gain.gain().set_value_at_time(0.0, now)?;
+        gain.gain().linear_ramp_to_value_at_time(self.effective_music_volume(), fade_end)?;
+
+        source.connect_with_audio_node(&gain)?;
+        gain.connect_with_audio_node(&context.destination())?;
+        source.start()?;
+
+        self.current_music = Some(PlayingMusic { track, source, gain });
+        Ok(())
+    }
+
+    /// 効果音を再生（未初期化・ミュート中は何もしない）
+    pub fn play(&self, effect: SoundEffect) -> Result<(), JsValue> {
+        if self.muted || self.volume <= 0.0 {
+            return Ok(());
+        }
+        let (Some(context), Some(buffer)) = (&self.context, self.buffers.get(&effect)) else {
+            return Ok(());
+        };
+
+        let source = context.create_buffer_source()?;
+        source.set_buffer(Some(buffer));
+
+        let gain = context.create_gain()?;
+        gain.gain().set_value(self.volume);
+
+        source.connect_with_audio_node(&gain)?;
+        gain.connect_with_audio_node(&context.destination())?;
+        source.start()?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_synthesized_effects_are_in_range() {
+        for effect in SoundEffect::ALL {
+            let samples = synthesize(effect, EFFECT_SAMPLE_RATE);
+            assert!(!samples.is_empty(), "{:?} has no samples", effect);
+            assert!(samples.iter().all(|s| (-1.0..=1.0).contains(s)), "{:?} clips", effect);
+        }
+
+        // 爆発音は毎回同じ波形
+        assert_eq!(
+            synthesize(SoundEffect::Explosion, EFFECT_SAMPLE_RATE),
+            synthesize(SoundEffect::Explosion, EFFECT_SAMPLE_RATE)
+        );
+    }
+
+    #[test]
+    fn test_settings_control_volume_and_mute() {
+        let mut audio = AudioResource::new();
+        let mut settings = SettingsResource::new();
+        settings.sound_volume = 1.5;
+        settings.sound_enabled = false;
+
+        audio.apply_settings(&settings);
+        assert_eq!(audio.volume(), 1.0);
+        assert!(audio.is_muted());
+
+        // 未初期化でも再生要求はエラーにならない
+        assert!(audio.play(SoundEffect::Reveal).is_ok());
+        assert!(audio.play_music(MusicTrack::Title).is_ok());
+        assert_eq!(audio.current_music(), None);
+    }
+
+    #[test]
+    fn test_music_loops_are_whole_notes() {
+        // タイトルは0.5秒×8音、ゲームは0.25秒×16音で、どちらも4秒のループ
+        for track in MusicTrack::ALL {
+            let samples = synthesize_music(track, EFFECT_SAMPLE_RATE);
+            assert_eq!(samples.len(), (4.0 * EFFECT_SAMPLE_RATE) as usize);
+            assert!(samples.iter().all(|s| (-1.0..=1.0).contains(s)));
+            // 末尾は減衰しきっているのでループの継ぎ目で音が途切れない
+            assert!(samples.last().unwrap().abs() < 0.01);
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,40 @@
+/**
+ * アバター
+ *
+ * プレイヤーが名前と一緒に選べるアバター（絵文字）の候補と、その切り替えを扱う
+ * 空文字列はアバターなしを表し、サーバーでも同じ候補のみを受け付ける
+ */
+/// 選べるアバター
+pub const AVATARS: [&str; 8] = ["🐱", "🐶", "🦊", "🐼", "🐸", "🐧", "🦉", "🐙"];
+
+/// 候補にあるアバター（またはアバターなし）かどうか
+pub fn is_known_avatar(avatar: &str) -> bool {
+    avatar.is_empty() || AVATARS.contains(&avatar)
+}
+
+/// 次のアバターを取得（最後の候補の次はアバターなし、候補に無い場合は最初の候補）
+pub fn next_avatar(avatar: &str) -> &'static str {
+    match AVATARS.iter().position(|&candidate| candidate == avatar) {
+        Some(index) if index + 1 < AVATARS.len() => AVATARS[index + 1],
+        Some(_) => "",
+        None => AVATARS[0],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_avatar_cycles_through_none() {
+        let mut avatar = next_avatar("");
+        assert_eq!(avatar, AVATARS[0]);
+        for _ in 1..AVATARS.len() {
+            avatar = next_avatar(avatar);
+            assert!(is_known_avatar(avatar) && !avatar.is_empty());
+        }
+        assert_eq!(next_avatar(avatar), "");
+        assert!(!is_known_avatar("<img>"));
+        assert_eq!(next_avatar("<img>"), AVATARS[0]);
+    }
+}
\ No newline at end of file
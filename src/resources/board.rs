@@ -0,0 +1,263 @@
+/**
+ * ボードリソース
+ *
+ * ボードの寸法・地雷数（BoardConfigResource）と、セルエンティティの配置や
+ * 開示状況（BoardStateResource）を管理するリソース
+ */
+use crate::entities::EntityId;
+use super::game_config::GameConfigResource;
+
+/// リソースとして扱える型を表すマーカートレイト
+pub trait Resource: 'static {}
+
+impl<T: 'static> Resource for T {}
+
+/// ボードの寸法と地雷数
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoardConfigResource {
+    /// ボードの幅（セル数）
+    pub width: usize,
+    /// ボードの高さ（セル数）
+    pub height: usize,
+    /// 地雷の数
+    pub mine_count: usize,
+}
+
+impl Default for BoardConfigResource {
+    fn default() -> Self {
+        Self::from_game_config(&GameConfigResource::new())
+    }
+}
+
+impl BoardConfigResource {
+    /// 新しいボード設定を作成
+    pub fn new(width: usize, height: usize, mine_count: usize) -> Self {
+        Self {
+            width,
+            height,
+            mine_count: mine_count.min(width * height),
+        }
+    }
+
+    /// ゲーム設定からボード設定を作成
+    pub fn from_game_config(config: &GameConfigResource) -> Self {
+        let board = &config.board_config;
+        Self::new(board.width, board.height, board.mine_count)
+    }
+
+    /// 総セル数を取得
+    pub fn total_cells(&self) -> usize {
+        self.width * self.height
+    }
+
+    /// 地雷以外のセル数を取得
+    pub fn safe_cells(&self) -> usize {
+        self.total_cells() - self.mine_count
+    }
+}
+
+/// ボード上のセルエンティティと開示状況を管理するリソース
+#[derive(Debug, Clone)]
+pub struct BoardStateResource {
+    /// ボードの幅
+    width: usize,
+    /// ボードの高さ
+    height: usize,
+    /// セルエンティティのグリッド（行優先）
+    cells: Vec<Option<EntityId>>,
+    /// 残りの安全なセル数（これが0になれば勝利）
+    remaining_safe_cells: usize,
+    /// 立てられているフラグの数
+    flag_count: usize,
+    /// 地雷が配置済みかどうか
+    mines_placed: bool,
+    /// フラグの切り替えを求められたセルのインデックス（FlagToggleSystemが取り出す）
+    flag_requests: Vec<usize>,
+}
+
+impl Default for BoardStateResource {
+    fn default() -> Self {
+        Self::new(&BoardConfigResource::default())
+    }
+}
+
+impl BoardStateResource {
+    /// ボード設定から空のボード状態を作成
+    pub fn new(config: &BoardConfigResource) -> Self {
+        Self {
+            width: config.width,
+            height: config.height,
+            cells: vec![None; config.total_cells()],
+            remaining_safe_cells: config.safe_cells(),
+            flag_count: 0,
+            mines_placed: false,
+            flag_requests: Vec::new(),
+        }
+    }
+
+    /// ボードを新しい設定で作り直す
+    pub fn reset(&mut self, config: &BoardConfigResource) {
+        *self = Self::new(config);
+    }
+
+    /// ボードの幅を取得
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// ボードの高さを取得
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// 座標がボード内かどうか
+    pub fn in_bounds(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height
+    }
+
+    /// 座標をセルのインデックスに変換
+    pub fn index_of(&self, x: usize, y: usize) -> Option<usize> {
+        if self.in_bounds(x, y) {
+            Some(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    /// インデックスを座標に変換
+    pub fn coords_of(&self, index: usize) -> Option<(usize, usize)> {
+        if index < self.cells.len() {
+            Some((index % self.width, index / self.width))
+        } else {
+            None
+        }
+    }
+
+    /// 座標のセルエンティティを取得
+    pub fn cell_entity(&self, x: usize, y: usize) -> Option<EntityId> {
+        self.index_of(x, y).and_then(|index| self.cells[index])
+    }
+
+    /// 座標にセルエンティティを登録
+    pub fn set_cell_entity(&mut self, x: usize, y: usize, entity: EntityId) {
+        if let Some(index) = self.index_of(x, y) {
+            self.cells[index] = Some(entity);
+        }
+    }
+
+    /// セルエンティティの座標を検索
+    pub fn find_cell(&self, entity: EntityId) -> Option<(usize, usize)> {
+        self.cells
+            .iter()
+            .position(|cell| *cell == Some(entity))
+            .and_then(|index| self.coords_of(index))
+    }
+
+    /// 周囲8マスの座標を取得（ボード外は含まない）
+    pub fn neighbors(&self, x: usize, y: usize) -> Vec<(usize, usize)> {
+        let mut result = Vec::with_capacity(8);
+
+        for dy in -1i64..=1 {
+            for dx in -1i64..=1 {
+                if dx == 0 && dy == 0 {
+                    continue;
+                }
+
+                let nx = x as i64 + dx;
+                let ny = y as i64 + dy;
+                if nx >= 0 && ny >= 0 && self.in_bounds(nx as usize, ny as usize) {
+                    result.push((nx as usize, ny as usize));
+                }
+            }
+        }
+
+        result
+    }
+
+    /// 周囲8マスのセルエンティティを取得
+    pub fn neighbor_entities(&self, x: usize, y: usize) -> Vec<EntityId> {
+        self.neighbors(x, y)
+            .into_iter()
+            .filter_map(|(nx, ny)| self.cell_entity(nx, ny))
+            .collect()
+    }
+
+    /// 残りの安全なセル数を取得
+    pub fn remaining_safe_cells(&self) -> usize {
+        self.remaining_safe_cells
+    }
+
+    /// 安全なセルが開かれたことを記録
+    pub fn record_safe_reveal(&mut self) {
+        self.remaining_safe_cells = self.remaining_safe_cells.saturating_sub(1);
+    }
+
+    /// 全ての安全なセルが開かれたかどうか
+    pub fn all_safe_cells_revealed(&self) -> bool {
+        self.remaining_safe_cells == 0
+    }
+
+    /// 立てられているフラグの数を取得
+    pub fn flag_count(&self) -> usize {
+        self.flag_count
+    }
+
+    /// フラグの増減を記録
+    pub fn record_flag(&mut self, flagged: bool) {
+        if flagged {
+            self.flag_count += 1;
+        } else {
+            self.flag_count = self.flag_count.saturating_sub(1);
+        }
+    }
+
+    /// 地雷が配置済みかどうか
+    pub fn mines_placed(&self) -> bool {
+        self.mines_placed
+    }
+
+    /// 地雷の配置完了を記録
+    pub fn mark_mines_placed(&mut self) {
+        self.mines_placed = true;
+    }
+
+    /// セルのフラグの切り替えを求める（反映はFlagToggleSystemが行う）
+    pub fn request_flag_toggle(&mut self, index: usize) {
+        if index < self.cells.len() {
+            self.flag_requests.push(index);
+        }
+    }
+
+    /// 求められたフラグの切り替えを取り出す
+    pub fn take_flag_requests(&mut self) -> Vec<usize> {
+        std::mem::take(&mut self.flag_requests)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_board_state_grid_and_neighbors() {
+        let config = BoardConfigResource::new(4, 3, 2);
+        let mut state = BoardStateResource::new(&config);
+        assert_eq!(state.remaining_safe_cells(), 10);
+
+        state.set_cell_entity(3, 2, EntityId(7));
+        assert_eq!(state.cell_entity(3, 2), Some(EntityId(7)));
+        assert_eq!(state.find_cell(EntityId(7)), Some((3, 2)));
+        assert_eq!(state.cell_entity(4, 0), None);
+
+        // 角は3マス、辺は5マス、内側は8マス
+        assert_eq!(state.neighbors(0, 0).len(), 3);
+        assert_eq!(state.neighbors(1, 0).len(), 5);
+        assert_eq!(state.neighbors(1, 1).len(), 8);
+        assert_eq!(state.neighbor_entities(2, 1), vec![EntityId(7)]);
+
+        for _ in 0..10 {
+            state.record_safe_reveal();
+        }
+        assert!(state.all_safe_cells_revealed());
+    }
+}
\ No newline at end of file
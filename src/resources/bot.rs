@@ -0,0 +1,323 @@
+/**
+ * ボットリソース
+ *
+ * 練習の相手やテストのためにボードを操作する模擬プレイヤー（ボット）を管理するリソース
+ * GameStateが見えている盤面と現在時刻を書き込み、`bot_system`が各ボットの手を決めて
+ * `BotAction`として積む。積まれた手はGameStateがプレイヤーの操作と同じ経路で反映する
+ */
+use super::rng::RngResource;
+use crate::utils::get_adjacent_offsets;
+
+/// ボットが手を指す間隔（ミリ秒）
+pub const BOT_MOVE_INTERVAL_MS: f64 = 900.0;
+
+/// 手を指す間隔のばらつき（ミリ秒、ボット同士が同時に動かないようにする）
+const BOT_MOVE_JITTER_MS: f64 = 400.0;
+
+/// カーソルが目標のセルへ近づく割合（1フレームあたり）
+const BOT_CURSOR_EASING: f64 = 0.2;
+
+/// ボットの打ち方
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BotStrategy {
+    /// 閉じたセルをランダムに開く
+    Random,
+    /// 数字から確定する手を優先し、無ければランダムに開く
+    Solver,
+}
+
+impl BotStrategy {
+    /// 文字列のキーから取得（"random" / "solver"）
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "random" => Some(BotStrategy::Random),
+            "solver" => Some(BotStrategy::Solver),
+            _ => None,
+        }
+    }
+}
+
+/// ボットの手
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BotMove {
+    /// セルを開く
+    Reveal(usize),
+    /// フラグを立てる
+    Flag(usize),
+}
+
+impl BotMove {
+    /// 操作するセルのインデックス
+    pub fn index(&self) -> usize {
+        match self {
+            BotMove::Reveal(index) | BotMove::Flag(index) => *index,
+        }
+    }
+}
+
+/// ボットが指した手
+#[derive(Debug, Clone, PartialEq)]
+pub struct BotAction {
+    /// 指したボットのID
+    pub bot_id: String,
+    /// 指した手
+    pub bot_move: BotMove,
+}
+
+/// ボットから見える盤面（開いたセルの数字とフラグのみで、地雷の位置は含まない）
+#[derive(Debug, Clone, Default)]
+pub struct BotBoardView {
+    /// ボードの幅
+    pub width: usize,
+    /// ボードの高さ
+    pub height: usize,
+    /// 開いたセルの周囲の地雷数（閉じたセルはNone）
+    pub numbers: Vec<Option<u8>>,
+    /// フラグが立っているかどうか
+    pub flagged: Vec<bool>,
+    /// ゲームが終了しているかどうか
+    pub game_over: bool,
+    /// 盤面の左上の座標（カーソルを動かす先の計算に使う）
+    pub origin: (f64, f64),
+    /// セルの大きさ
+    pub cell_size: f64,
+}
+
+impl BotBoardView {
+    /// 閉じていてフラグも無いセルか
+    fn is_hidden(&self, index: usize) -> bool {
+        self.numbers[index].is_none() && !self.flagged[index]
+    }
+
+    /// 周囲のセルのインデックス
+    fn neighbors(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        let (x, y) = ((index % self.width) as isize, (index / self.width) as isize);
+        get_adjacent_offsets().into_iter().filter_map(move |(dy, dx)| {
+            let (nx, ny) = (x + dx, y + dy);
+            let inside = nx >= 0 && ny >= 0 && (nx as usize) < self.width && (ny as usize) < self.height;
+            inside.then(|| ny as usize * self.width + nx as usize)
+        })
+    }
+
+    /// セルの中心の座標
+    fn cell_center(&self, index: usize) -> (f64, f64) {
+        let (x, y) = ((index % self.width) as f64, (index / self.width) as f64);
+        (self.origin.0 + (x + 0.5) * self.cell_size, self.origin.1 + (y + 0.5) * self.cell_size)
+    }
+}
+
+/// 数字から確定する手を探す
+///
+/// 周囲のフラグが数字と同じなら残りの閉じたセルは安全、
+/// 閉じたセルとフラグの合計が数字と同じなら閉じたセルは全て地雷とみなす
+pub fn find_certain_move(board: &BotBoardView) -> Option<BotMove> {
+    for (index, number) in board.numbers.iter().enumerate() {
+        let Some(number) = *number else {
+            continue;
+        };
+        let flags = board.neighbors(index).filter(|&n| board.flagged[n]).count();
+        let mut hidden = board.neighbors(index).filter(|&n| board.is_hidden(n));
+        let Some(first_hidden) = hidden.next() else {
+            continue;
+        };
+        let hidden_count = 1 + hidden.count();
+        if flags == number as usize {
+            return Some(BotMove::Reveal(first_hidden));
+        }
+        if flags + hidden_count == number as usize {
+            return Some(BotMove::Flag(first_hidden));
+        }
+    }
+    None
+}
+
+/// 打ち方に従って次の手を決める（開けるセルが無い場合はNone）
+pub fn choose_move(board: &BotBoardView, strategy: BotStrategy, rng: &mut RngResource) -> Option<BotMove> {
+    if board.game_over {
+        return None;
+    }
+    if strategy == BotStrategy::Solver {
+        if let Some(certain) = find_certain_move(board) {
+            return Some(certain);
+        }
+    }
+    let hidden = (0..board.numbers.len()).filter(|&index| board.is_hidden(index)).count();
+    if hidden == 0 {
+        return None;
+    }
+    let pick = rng.gen_range(hidden);
+    (0..board.numbers.len()).filter(|&index| board.is_hidden(index)).nth(pick).map(BotMove::Reveal)
+}
+
+/// 1体のボット
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bot {
+    /// プレイヤーID
+    pub id: String,
+    /// 打ち方
+    pub strategy: BotStrategy,
+    /// カーソルの座標
+    pub x: f64,
+    /// カーソルの座標
+    pub y: f64,
+    /// カーソルを動かす先
+    target: (f64, f64),
+    /// 次に手を指す時刻
+    next_move_at: f64,
+}
+
+/// ボットの一覧と、ボットが指した手
+#[derive(Debug, Clone)]
+pub struct BotResource {
+    /// 現在時刻（ミリ秒）
+    pub now: f64,
+    /// ボットから見える盤面
+    pub board: BotBoardView,
+    /// 手を指す間隔（ミリ秒）
+    pub move_interval_ms: f64,
+    /// 参加しているボット
+    bots: Vec<Bot>,
+    /// 手とばらつきを決める乱数
+    rng: RngResource,
+    /// 指したがまだ反映されていない手
+    actions: Vec<BotAction>,
+    /// 次に追加するボットの番号
+    next_number: usize,
+}
+
+impl Default for BotResource {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl BotResource {
+    /// シードを指定してボットのいない状態を作成
+    pub fn new(seed: u64) -> Self {
+        Self {
+            now: 0.0,
+            board: BotBoardView::default(),
+            move_interval_ms: BOT_MOVE_INTERVAL_MS,
+            bots: Vec::new(),
+            rng: RngResource::new(seed),
+            actions: Vec::new(),
+            next_number: 1,
+        }
+    }
+
+    /// ボットを追加してIDを返す
+    pub fn add_bot(&mut self, strategy: BotStrategy) -> String {
+        let id = format!("bot-{}", self.next_number);
+        self.next_number += 1;
+        let next_move_at = self.now + self.move_interval_ms + self.rng.next_f64() * BOT_MOVE_JITTER_MS;
+        self.bots.push(Bot { id: id.clone(), strategy, x: 0.0, y: 0.0, target: (0.0, 0.0), next_move_at });
+        id
+    }
+
+    /// 全てのボットを取り除く（番号も1から振り直す）
+    pub fn clear(&mut self) {
+        self.bots.clear();
+        self.actions.clear();
+        self.next_number = 1;
+    }
+
+    /// 参加しているボット
+    pub fn bots(&self) -> &[Bot] {
+        &self.bots
+    }
+
+    /// ボットがいるかどうか
+    pub fn is_empty(&self) -> bool {
+        self.bots.is_empty()
+    }
+
+    /// 指した手を取り出す
+    pub fn take_actions(&mut self) -> Vec<BotAction> {
+        std::mem::take(&mut self.actions)
+    }
+
+    /// 手を指す時刻になったボットの手を決め、カーソルを目標のセルへ近づける
+    pub fn update(&mut self) {
+        let now = self.now;
+        for bot in &mut self.bots {
+            if now >= bot.next_move_at {
+                bot.next_move_at = now + self.move_interval_ms + self.rng.next_f64() * BOT_MOVE_JITTER_MS;
+                if let Some(bot_move) = choose_move(&self.board, bot.strategy, &mut self.rng) {
+                    bot.target = self.board.cell_center(bot_move.index());
+                    self.actions.push(BotAction { bot_id: bot.id.clone(), bot_move });
+                }
+            }
+            bot.x += (bot.target.0 - bot.x) * BOT_CURSOR_EASING;
+            bot.y += (bot.target.1 - bot.y) * BOT_CURSOR_EASING;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 数字とフラグを指定した盤面（Noneは閉じたセル）
+    fn view(width: usize, height: usize, numbers: &[Option<u8>], flagged: &[usize]) -> BotBoardView {
+        let mut board = BotBoardView {
+            width,
+            height,
+            numbers: numbers.to_vec(),
+            flagged: vec![false; width * height],
+            ..Default::default()
+        };
+        for &index in flagged {
+            board.flagged[index] = true;
+        }
+        board
+    }
+
+    #[test]
+    fn test_find_certain_move() {
+        // 1の隣の閉じたセルが1つだけなら地雷
+        let board = view(2, 1, &[Some(1), None], &[]);
+        assert_eq!(find_certain_move(&board), Some(BotMove::Flag(1)));
+
+        // フラグで数字が満たされていれば残りは安全
+        let board = view(3, 1, &[None, Some(1), None], &[0]);
+        assert_eq!(find_certain_move(&board), Some(BotMove::Reveal(2)));
+
+        // 確定しない場合はNone
+        let board = view(3, 1, &[None, Some(1), None], &[]);
+        assert_eq!(find_certain_move(&board), None);
+    }
+
+    #[test]
+    fn test_choose_move_only_picks_hidden_cells() {
+        let mut rng = RngResource::new(1);
+        let board = view(3, 1, &[Some(0), None, None], &[2]);
+        for _ in 0..20 {
+            assert_eq!(choose_move(&board, BotStrategy::Random, &mut rng), Some(BotMove::Reveal(1)));
+        }
+
+        let mut finished = view(2, 1, &[Some(1), None], &[]);
+        finished.game_over = true;
+        assert_eq!(choose_move(&finished, BotStrategy::Solver, &mut rng), None);
+    }
+
+    #[test]
+    fn test_bots_act_on_their_own_schedule() {
+        let mut bots = BotResource::new(5);
+        bots.board = view(3, 1, &[None, None, None], &[]);
+        let first = bots.add_bot(BotStrategy::Random);
+        bots.add_bot(BotStrategy::Solver);
+
+        bots.update();
+        assert!(bots.take_actions().is_empty());
+
+        bots.now = BOT_MOVE_INTERVAL_MS + BOT_MOVE_JITTER_MS;
+        bots.update();
+        let actions = bots.take_actions();
+        assert_eq!(actions.len(), 2);
+        assert_eq!(actions[0].bot_id, first);
+        assert!(bots.take_actions().is_empty());
+
+        bots.clear();
+        assert!(bots.is_empty());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,140 @@
+/**
+ * カメラリソース
+ *
+ * ゲーム画面のボードとカーソルを表示する視点（中心座標と拡大率）を管理するリソース
+ * プレイヤー一覧で選んだプレイヤーのカーソルを追いかけ、解除すると通常の視点に戻る
+ * 座標はキャンバス上の座標（カメラを動かしていない時の画面座標）を基準にする
+ */
+/// プレイヤーを追いかけている時の拡大率
+pub const FOLLOW_ZOOM: f64 = 2.0;
+
+/// 1フレームで目標に近づく割合（0.0〜1.0）
+const CAMERA_SMOOTHING: f64 = 0.2;
+
+/// 通常の視点に戻ったとみなす距離
+const CAMERA_EPSILON: f64 = 0.01;
+
+/// カメラリソース
+#[derive(Debug, Clone, PartialEq)]
+pub struct CameraResource {
+    /// 視点の中心（Noneの場合はキャンバスの中心）
+    center: Option<(f64, f64)>,
+    /// 拡大率
+    zoom: f64,
+    /// 追いかけているプレイヤーのID
+    following: Option<String>,
+}
+
+impl Default for CameraResource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CameraResource {
+    /// 通常の視点のカメラを作成
+    pub fn new() -> Self {
+        Self { center: None, zoom: 1.0, following: None }
+    }
+
+    /// プレイヤーのカーソルを追いかける
+    pub fn follow(&mut self, player_id: &str) {
+        self.following = Some(player_id.to_string());
+    }
+
+    /// 追いかけるのをやめる（視点は徐々に通常に戻る）
+    pub fn stop_following(&mut self) {
+        self.following = None;
+    }
+
+    /// 追いかけているプレイヤーのID
+    pub fn following(&self) -> Option<&str> {
+        self.following.as_deref()
+    }
+
+    /// 拡大率
+    pub fn zoom(&self) -> f64 {
+        self.zoom
+    }
+
+    /// 視点の中心を取得
+    pub fn center(&self, canvas_width: f64, canvas_height: f64) -> (f64, f64) {
+        self.center.unwrap_or((canvas_width / 2.0, canvas_height / 2.0))
+    }
+
+    /// 1フレーム分カメラを動かす
+    ///
+    /// `target`は追いかけているプレイヤーのカーソル位置（プレイヤーが退出していればNone）
+    /// `instant`がtrueの場合は補間せずにすぐ目標の視点にする（アニメーションを減らす設定）
+    pub fn update(&mut self, target: Option<(f64, f64)>, canvas_width: f64, canvas_height: f64, instant: bool) {
+        // 追いかけているプレイヤーがいなくなったら解除
+        if self.following.is_some() && target.is_none() {
+            self.following = None;
+        }
+
+        let default_center = (canvas_width / 2.0, canvas_height / 2.0);
+        let (target_center, target_zoom) = match (&self.following, target) {
+            (Some(_), Some(position)) => (position, FOLLOW_ZOOM),
+            _ => (default_center, 1.0),
+        };
+
+        let rate = if instant { 1.0 } else { CAMERA_SMOOTHING };
+        let (center_x, center_y) = self.center(canvas_width, canvas_height);
+        let center = (
+            center_x + (target_center.0 - center_x) * rate,
+            center_y + (target_center.1 - center_y) * rate,
+        );
+        self.zoom += (target_zoom - self.zoom) * rate;
+
+        // 通常の視点に戻りきったら中心の記録を消す
+        let settled = (center.0 - default_center.0).abs() < CAMERA_EPSILON &&
+            (center.1 - default_center.1).abs() < CAMERA_EPSILON &&
+            (self.zoom - 1.0).abs() < CAMERA_EPSILON;
+        if self.following.is_none() && settled {
+            self.center = None;
+            self.zoom = 1.0;
+        } else {
+            self.center = Some(center);
+        }
+    }
+
+    /// 画面上の座標をキャンバス上の座標に変換
+    pub fn screen_to_world(&self, x: f64, y: f64, canvas_width: f64, canvas_height: f64) -> (f64, f64) {
+        let (center_x, center_y) = self.center(canvas_width, canvas_height);
+        (
+            (x - canvas_width / 2.0) / self.zoom + center_x,
+            (y - canvas_height / 2.0) / self.zoom + center_y,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_follow_and_release() {
+        let mut camera = CameraResource::new();
+        assert_eq!(camera.screen_to_world(100.0, 50.0, 800.0, 600.0), (100.0, 50.0));
+
+        camera.follow("player_1");
+        camera.update(Some((200.0, 100.0)), 800.0, 600.0, true);
+        assert_eq!(camera.following(), Some("player_1"));
+        assert_eq!(camera.zoom(), FOLLOW_ZOOM);
+        assert_eq!(camera.center(800.0, 600.0), (200.0, 100.0));
+
+        // 画面の中心が追いかけているカーソルの位置になる
+        assert_eq!(camera.screen_to_world(400.0, 300.0, 800.0, 600.0), (200.0, 100.0));
+        assert_eq!(camera.screen_to_world(500.0, 300.0, 800.0, 600.0), (250.0, 100.0));
+
+        // 補間する場合は少しずつ近づく
+        camera.update(Some((300.0, 100.0)), 800.0, 600.0, false);
+        assert_eq!(camera.center(800.0, 600.0), (220.0, 100.0));
+
+        // プレイヤーがいなくなったら解除して通常の視点に戻る
+        camera.update(None, 800.0, 600.0, true);
+        assert_eq!(camera.following(), None);
+        assert_eq!(camera.zoom(), 1.0);
+        assert_eq!(camera, CameraResource::new());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,147 @@
+/**
+ * 描画に使う色
+ *
+ * 色を`0xRRGGBB`の整数で持ち、CSSの文字列への変換は必要になった時だけ行う。
+ * キャンバスに渡す`JsValue`は色ごとに一度だけ作って使い回すため、
+ * 毎フレームの描画で短命な文字列を作らずに済む
+ */
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use wasm_bindgen::JsValue;
+
+thread_local! {
+    /// 色ごとに作成済みの`JsValue`
+    static JS_COLORS: RefCell<HashMap<u32, JsValue>> = RefCell::new(HashMap::new());
+}
+
+/// `0xRRGGBB`形式で保持する色
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Color(pub u32);
+
+impl Color {
+    /// 白
+    pub const WHITE: Color = Color(0xFFFFFF);
+    /// 黒
+    pub const BLACK: Color = Color(0x000000);
+
+    /// `#RRGGBB`形式か`hsl(H, S%, L%)`形式の文字列から作成（形式が違う場合はNone）
+    ///
+    /// サーバーは名前を登録する前のプレイヤーに`hsl()`形式の色を割り当てる
+    pub fn parse(css: &str) -> Option<Self> {
+        let css = css.trim();
+        if let Some(args) = css.strip_prefix("hsl(").and_then(|rest| rest.strip_suffix(')')) {
+            return Self::parse_hsl(args);
+        }
+        let hex = css.strip_prefix('#')?;
+        if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        u32::from_str_radix(hex, 16).ok().map(Color)
+    }
+
+    /// `hsl()`の引数（`H, S%, L%`）から作成
+    fn parse_hsl(args: &str) -> Option<Self> {
+        let mut parts = args.split(',').map(str::trim);
+        let hue: f64 = parts.next()?.parse().ok()?;
+        let saturation: f64 = parts.next()?.strip_suffix('%')?.parse().ok()?;
+        let lightness: f64 = parts.next()?.strip_suffix('%')?.parse().ok()?;
+        if parts.next().is_some() {
+            return None;
+        }
+        Some(Self::from_hsl(hue, saturation / 100.0, lightness / 100.0))
+    }
+
+    /// 色相（度）・彩度・明度（0.0〜1.0）から作成
+    pub fn from_hsl(hue: f64, saturation: f64, lightness: f64) -> Self {
+        let (saturation, lightness) = (saturation.clamp(0.0, 1.0), lightness.clamp(0.0, 1.0));
+        let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+        let sector = hue.rem_euclid(360.0) / 60.0;
+        let second = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+        let (r, g, b) = match sector as u32 {
+            0 => (chroma, second, 0.0),
+            1 => (second, chroma, 0.0),
+            2 => (0.0, chroma, second),
+            3 => (0.0, second, chroma),
+            4 => (second, 0.0, chroma),
+            _ => (chroma, 0.0, second),
+        };
+        let offset = lightness - chroma / 2.0;
+        let channel = |value: f64| ((value + offset) * 255.0).round().clamp(0.0, 255.0) as u32;
+        Color((channel(r) << 16) | (channel(g) << 8) | channel(b))
+    }
+
+    /// RGBの各成分
+    pub fn rgb(&self) -> (u8, u8, u8) {
+        ((self.0 >> 16) as u8, (self.0 >> 8) as u8, self.0 as u8)
+    }
+
+    /// `#RRGGBB`形式の文字列
+    pub fn to_css(&self) -> String {
+        self.to_string()
+    }
+
+    /// キャンバスの塗りつぶしなどに渡す値（色ごとに一度だけ作成して使い回す）
+    pub fn js_value(&self) -> JsValue {
+        JS_COLORS.with(|colors| {
+            colors.borrow_mut()
+                .entry(self.0)
+                .or_insert_with(|| JsValue::from_str(&self.to_css()))
+                .clone()
+        })
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:06X}", self.0 & 0xFFFFFF)
+    }
+}
+
+impl Serialize for Color {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Color {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let css = String::deserialize(deserializer)?;
+        Color::parse(&css).ok_or_else(|| serde::de::Error::custom(format!("invalid color: {}", css)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_format() {
+        assert_eq!(Color::parse("#1565c0"), Some(Color(0x1565C0)));
+        assert_eq!(Color(0x1565C0).to_css(), "#1565C0");
+        assert_eq!(Color(0x00000F).to_css(), "#00000F");
+        assert_eq!(Color(0x1565C0).rgb(), (0x15, 0x65, 0xC0));
+        assert_eq!(Color::parse("blue"), None);
+        assert_eq!(Color::parse("#12345"), None);
+        assert_eq!(Color::parse("#+12345"), None);
+    }
+
+    #[test]
+    fn test_parse_hsl() {
+        assert_eq!(Color::parse("hsl(0, 100%, 50%)"), Some(Color(0xFF0000)));
+        assert_eq!(Color::parse("hsl(120, 100%, 50%)"), Some(Color(0x00FF00)));
+        assert_eq!(Color::parse("hsl(240, 100%, 25%)"), Some(Color(0x000080)));
+        assert_eq!(Color::parse("hsl(0, 0%, 100%)"), Some(Color::WHITE));
+        assert_eq!(Color::parse("hsl(90, 50)"), None);
+        assert_eq!(Color::parse("hsl(90, 50%, 50%, 1)"), None);
+    }
+
+    #[test]
+    fn test_serializes_as_css_string() {
+        let json = serde_json::to_string(&Color(0xC62828)).unwrap();
+        assert_eq!(json, "\"#C62828\"");
+        assert_eq!(serde_json::from_str::<Color>(&json).unwrap(), Color(0xC62828));
+        assert!(serde_json::from_str::<Color>("\"red\"").is_err());
+    }
+}
\ No newline at end of file
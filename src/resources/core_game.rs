@@ -3,8 +3,10 @@
  * 
  * ゲームの進行状態、フェーズ、時間など基本的なゲーム状態を管理するリソース
  */
+use std::collections::HashMap;
 use wasm_bindgen::prelude::*;
-use js_sys::Date;
+use crate::utils::now_ms;
+use super::game_config::{Difficulty, GameConfigResource};
 
 /// ゲームの状態を表す列挙型
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -22,6 +24,28 @@ pub enum GamePhase {
     },
 }
 
+impl GamePhase {
+    /// 外部に公開する際の識別子
+    pub fn key(&self) -> &'static str {
+        match self {
+            GamePhase::Ready => "ready",
+            GamePhase::Playing => "playing",
+            GamePhase::Paused => "paused",
+            GamePhase::GameOver { win: true } => "won",
+            GamePhase::GameOver { win: false } => "lost",
+        }
+    }
+}
+
+/// ゲームフェーズの遷移イベント
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PhaseChanged {
+    /// 遷移前のフェーズ
+    pub from: GamePhase,
+    /// 遷移後のフェーズ
+    pub to: GamePhase,
+}
+
 /// ゲームの核となる状態を管理するリソース
 #[derive(Debug, Clone)]
 pub struct CoreGameResource {
@@ -35,6 +59,10 @@ pub struct CoreGameResource {
     score: u32,
     /// 残りの地雷数
     remaining_mines: u32,
+    /// 未処理のフェーズ遷移イベント
+    phase_events: Vec<PhaseChanged>,
+    /// 難易度ごとのベストスコア（ゲームを初期化しても保持される）
+    best_scores: HashMap<Difficulty, u32>,
 }
 
 impl Default for CoreGameResource {
@@ -52,12 +80,14 @@ impl CoreGameResource {
             elapsed_time: 0.0,
             score: 0,
             remaining_mines: 0,
+            phase_events: Vec::new(),
+            best_scores: HashMap::new(),
         }
     }
 
     /// ゲームを初期化
     pub fn initialize(&mut self, mine_count: u32) {
-        self.phase = GamePhase::Ready;
+        self.set_phase(GamePhase::Ready);
         self.start_time = None;
         self.elapsed_time = 0.0;
         self.score = 0;
@@ -69,18 +99,39 @@ impl CoreGameResource {
         self.phase
     }
 
+    /// フェーズを変更し、変化があれば遷移イベントを記録
+    fn set_phase(&mut self, phase: GamePhase) {
+        if self.phase != phase {
+            self.phase_events.push(PhaseChanged {
+                from: self.phase,
+                to: phase,
+            });
+            self.phase = phase;
+        }
+    }
+
+    /// 未処理のフェーズ遷移イベントを取得
+    pub fn phase_events(&self) -> &[PhaseChanged] {
+        &self.phase_events
+    }
+
+    /// フェーズ遷移イベントを取り出してクリア
+    pub fn drain_phase_events(&mut self) -> Vec<PhaseChanged> {
+        std::mem::take(&mut self.phase_events)
+    }
+
     /// ゲームを開始
     pub fn start_game(&mut self) {
         if self.phase == GamePhase::Ready {
-            self.phase = GamePhase::Playing;
-            self.start_time = Some(Date::now());
+            self.set_phase(GamePhase::Playing);
+            self.start_time = Some(now_ms());
         }
     }
 
     /// ゲームを一時停止
     pub fn pause_game(&mut self) {
         if self.phase == GamePhase::Playing {
-            self.phase = GamePhase::Paused;
+            self.set_phase(GamePhase::Paused);
             // 経過時間を記録
             self.update_elapsed_time();
         }
@@ -89,16 +140,16 @@ impl CoreGameResource {
     /// ゲームを再開
     pub fn resume_game(&mut self) {
         if self.phase == GamePhase::Paused {
-            self.phase = GamePhase::Playing;
+            self.set_phase(GamePhase::Playing);
             // 開始時間を再設定（すでに経過した時間を考慮）
-            self.start_time = Some(Date::now() - self.elapsed_time);
+            self.start_time = Some(now_ms() - self.elapsed_time);
         }
     }
 
     /// ゲームを終了
     pub fn end_game(&mut self, win: bool) {
         self.update_elapsed_time();
-        self.phase = GamePhase::GameOver { win };
+        self.set_phase(GamePhase::GameOver { win });
     }
 
     /// ゲームが実行中かどうか
@@ -124,7 +175,15 @@ impl CoreGameResource {
     /// 経過時間を更新
     pub fn update_elapsed_time(&mut self) {
         if let (Some(start), true) = (self.start_time, self.is_playing()) {
-            self.elapsed_time = Date::now() - start;
+            self.elapsed_time = now_ms() - start;
+        }
+    }
+
+    /// 保存したゲームを再開するときに経過時間を戻す（プレイ中なら開始時刻も合わせる）
+    pub fn restore_elapsed_time(&mut self, elapsed_ms: f64) {
+        self.elapsed_time = elapsed_ms;
+        if self.is_playing() {
+            self.start_time = Some(now_ms() - elapsed_ms);
         }
     }
 
@@ -143,6 +202,29 @@ impl CoreGameResource {
         self.score += points;
     }
 
+    /// ゲーム設定のスコア計算式で最終スコアを算出して記録
+    /// 
+    /// ゲーム終了後に呼び出す。記録したスコアを返す
+    pub fn record_final_score(&mut self, config: &GameConfigResource) -> u32 {
+        let score = config.calculate_score(self.elapsed_time, self.is_win());
+        self.score = score;
+
+        let best = self.best_scores.entry(config.difficulty.clone()).or_insert(0);
+        *best = (*best).max(score);
+
+        score
+    }
+
+    /// 難易度のベストスコアを取得
+    pub fn best_score(&self, difficulty: &Difficulty) -> Option<u32> {
+        self.best_scores.get(difficulty).copied()
+    }
+
+    /// 難易度ごとのベストスコアを取得
+    pub fn best_scores(&self) -> &HashMap<Difficulty, u32> {
+        &self.best_scores
+    }
+
     /// 残りの地雷数を取得
     pub fn remaining_mines(&self) -> u32 {
         self.remaining_mines
@@ -227,4 +309,67 @@ mod tests {
         assert_eq!(resource.phase, GamePhase::Ready);
         assert_eq!(resource.remaining_mines, 15);
     }
+
+    #[test]
+    fn test_record_final_score_tracks_best() {
+        let mut config = GameConfigResource::new();
+        let mut resource = CoreGameResource::new();
+
+        // 敗北はスコア0
+        resource.initialize(10);
+        resource.start_game();
+        resource.end_game(false);
+        assert_eq!(resource.record_final_score(&config), 0);
+        assert_eq!(resource.best_score(&Difficulty::Easy), Some(0));
+
+        // 勝利すると計算式のスコアが記録される
+        resource.initialize(10);
+        resource.start_game();
+        resource.end_game(true);
+        let score = resource.record_final_score(&config);
+        assert!(score > 0);
+        assert_eq!(resource.score(), score);
+        assert_eq!(resource.best_score(&Difficulty::Easy), Some(score));
+
+        // ベストスコアは難易度ごとに保持され、初期化後も残る
+        config.set_difficulty(Difficulty::Hard);
+        resource.initialize(99);
+        assert_eq!(resource.best_score(&Difficulty::Easy), Some(score));
+        assert_eq!(resource.best_score(&Difficulty::Hard), None);
+    }
+
+    #[test]
+    fn test_phase_changed_events() {
+        let mut resource = CoreGameResource::new();
+        
+        // 同じフェーズへの遷移や無効な遷移ではイベントは発生しない
+        resource.initialize(10);
+        resource.pause_game();
+        assert!(resource.phase_events().is_empty());
+        
+        resource.end_game(false);
+        resource.initialize(10);
+        
+        let events = resource.drain_phase_events();
+        assert_eq!(events, vec![
+            PhaseChanged { from: GamePhase::Ready, to: GamePhase::GameOver { win: false } },
+            PhaseChanged { from: GamePhase::GameOver { win: false }, to: GamePhase::Ready },
+        ]);
+        assert!(resource.phase_events().is_empty());
+    }
+    #[test]
+    fn test_phase_keys() {
+        assert_eq!(GamePhase::Ready.key(), "ready");
+        assert_eq!(GamePhase::GameOver { win: true }.key(), "won");
+        assert_eq!(GamePhase::GameOver { win: false }.key(), "lost");
+    }
+    #[test]
+    fn test_restore_elapsed_time() {
+        let mut resource = CoreGameResource::new();
+        resource.start_game();
+        resource.restore_elapsed_time(60_000.0);
+        resource.update_elapsed_time();
+        assert!(resource.elapsed_time() >= 60_000.0);
+        assert!(resource.elapsed_time() < 61_000.0);
+    }
 } 
\ No newline at end of file
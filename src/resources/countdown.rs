@@ -0,0 +1,73 @@
+/**
+ * カウントダウンリソース
+ *
+ * ロビーが終了した際にサーバーが開始する3-2-1のカウントダウンを管理するリソース
+ * 終了時刻はサーバーの時計で届くため、受信時に求めた時計のずれで補正して全員の表示を揃える
+ */
+/// カウントダウンの状態
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CountdownResource {
+    /// カウントダウンの終了時刻（サーバーの時計、ミリ秒）
+    ends_at: Option<f64>,
+    /// サーバーの時計とローカルの時計の差（サーバー − ローカル、ミリ秒）
+    clock_offset: f64,
+}
+
+impl CountdownResource {
+    /// カウントダウンのない状態を作成
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// サーバーから受信したカウントダウンを開始
+    ///
+    /// `server_time`はメッセージ送信時のサーバーの時刻、`local_now`は受信時のローカルの時刻
+    pub fn start(&mut self, ends_at: f64, server_time: f64, local_now: f64) {
+        self.ends_at = Some(ends_at);
+        self.clock_offset = server_time - local_now;
+    }
+
+    /// カウントダウンを取り消す
+    pub fn clear(&mut self) {
+        self.ends_at = None;
+    }
+
+    /// 残り時間（ミリ秒）を取得（カウントダウンがなければNone）
+    pub fn remaining_ms(&self, local_now: f64) -> Option<f64> {
+        self.ends_at
+            .map(|ends_at| ends_at - (local_now + self.clock_offset))
+            .filter(|remaining| *remaining > 0.0)
+    }
+
+    /// カウントダウン中かどうか（この間は入力を受け付けない）
+    pub fn is_active(&self, local_now: f64) -> bool {
+        self.remaining_ms(local_now).is_some()
+    }
+
+    /// 画面に表示する数字（3, 2, 1）を取得
+    pub fn display_number(&self, local_now: f64) -> Option<u32> {
+        self.remaining_ms(local_now).map(|remaining| (remaining / 1000.0).ceil() as u32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_countdown_uses_server_clock() {
+        let mut countdown = CountdownResource::new();
+        assert!(!countdown.is_active(0.0));
+
+        // ローカルの時計がサーバーより500ms遅れている
+        countdown.start(13_000.0, 10_000.0, 9_500.0);
+        assert_eq!(countdown.display_number(9_500.0), Some(3));
+        assert_eq!(countdown.display_number(10_600.0), Some(2));
+        assert_eq!(countdown.display_number(12_400.0), Some(1));
+        assert!(!countdown.is_active(12_500.0));
+
+        countdown.start(13_000.0, 10_000.0, 10_000.0);
+        countdown.clear();
+        assert_eq!(countdown.remaining_ms(10_000.0), None);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,148 @@
+/**
+ * カーソルの色
+ *
+ * プレイヤーが選べるカーソルの色の候補と、ボード上での見やすさ（コントラスト）の判定、
+ * 他のプレイヤーと似た色になった場合の色の選び直しを扱う
+ */
+use super::localization::text;
+use super::settings::Language;
+
+/// 選べるカーソルの色（翻訳キー, 色）
+pub const CURSOR_COLORS: [(&str, &str); 8] = [
+    ("cursor_color.blue", "#1565C0"),
+    ("cursor_color.red", "#C62828"),
+    ("cursor_color.green", "#2E7D32"),
+    ("cursor_color.purple", "#6A1B9A"),
+    ("cursor_color.teal", "#00695C"),
+    ("cursor_color.pink", "#AD1457"),
+    ("cursor_color.brown", "#4E342E"),
+    ("cursor_color.navy", "#1A237E"),
+];
+
+/// カーソルの色の初期値
+pub const DEFAULT_CURSOR_COLOR: &str = CURSOR_COLORS[0].1;
+
+/// カーソルが重なるボードのセルの色（開く前・開いた後）
+const BOARD_CELL_COLORS: [&str; 2] = ["#999999", "#CCCCCC"];
+
+/// ボードのセルに対して必要なコントラスト比
+const MIN_CONTRAST_RATIO: f64 = 1.5;
+
+/// 他のプレイヤーの色と区別できるRGB空間での最小距離
+const MIN_COLOR_DISTANCE: f64 = 80.0;
+
+/// `#RRGGBB`形式の色をRGBに変換
+pub fn parse_hex_color(color: &str) -> Option<(u8, u8, u8)> {
+    let hex = color.strip_prefix('#')?;
+    if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let channel = |range: std::ops::Range<usize>| u8::from_str_radix(&hex[range], 16).ok();
+    Some((channel(0..2)?, channel(2..4)?, channel(4..6)?))
+}
+
+/// 相対輝度（WCAGの定義）
+fn relative_luminance((r, g, b): (u8, u8, u8)) -> f64 {
+    let linear = |channel: u8| {
+        let value = channel as f64 / 255.0;
+        if value <= 0.03928 {
+            value / 12.92
+        } else {
+            ((value + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * linear(r) + 0.7152 * linear(g) + 0.0722 * linear(b)
+}
+
+/// 2色のコントラスト比（1.0〜21.0）
+pub fn contrast_ratio(a: (u8, u8, u8), b: (u8, u8, u8)) -> f64 {
+    let (a, b) = (relative_luminance(a), relative_luminance(b));
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+/// ボードのどのセルの上でも見やすい色かどうか
+pub fn is_readable_cursor_color(color: &str) -> bool {
+    parse_hex_color(color).is_some_and(|rgb| {
+        BOARD_CELL_COLORS.iter()
+            .filter_map(|cell| parse_hex_color(cell))
+            .all(|cell| contrast_ratio(rgb, cell) >= MIN_CONTRAST_RATIO)
+    })
+}
+
+/// 2色が見分けにくいほど似ているかどうか
+fn is_similar(a: (u8, u8, u8), b: (u8, u8, u8)) -> bool {
+    let distance = |x: u8, y: u8| (x as f64 - y as f64).powi(2);
+    (distance(a.0, b.0) + distance(a.1, b.1) + distance(a.2, b.2)).sqrt() < MIN_COLOR_DISTANCE
+}
+
+/// 他のプレイヤーの色と似ていない色を決める
+///
+/// 希望の色が使えればそのまま使い、似た色がいれば候補から空いている色を選ぶ
+/// （空きが無い場合は希望の色のまま）
+pub fn resolve_cursor_color(preferred: &str, taken: &[&str]) -> String {
+    let taken: Vec<(u8, u8, u8)> = taken.iter().filter_map(|color| parse_hex_color(color)).collect();
+    let is_free = |color: &str| parse_hex_color(color).is_some_and(|rgb| !taken.iter().any(|&other| is_similar(rgb, other)));
+    
+    if is_free(preferred) {
+        return preferred.to_string();
+    }
+    CURSOR_COLORS.iter()
+        .map(|&(_, color)| color)
+        .find(|color| is_free(color))
+        .unwrap_or(preferred)
+        .to_string()
+}
+
+/// 候補の中の次の色を取得（候補に無い色の場合は最初の色）
+pub fn next_cursor_color(color: &str) -> &'static str {
+    let index = CURSOR_COLORS.iter()
+        .position(|&(_, candidate)| candidate.eq_ignore_ascii_case(color))
+        .map_or(0, |index| (index + 1) % CURSOR_COLORS.len());
+    CURSOR_COLORS[index].1
+}
+
+/// 色の表示名を取得（候補に無い色はそのまま表示）
+pub fn cursor_color_label(language: Language, color: &str) -> String {
+    CURSOR_COLORS.iter()
+        .find(|&&(_, candidate)| candidate.eq_ignore_ascii_case(color))
+        .map_or_else(|| color.to_string(), |&(key, _)| text(language, key).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_palette_is_readable_on_board() {
+        for (_, color) in CURSOR_COLORS {
+            assert!(is_readable_cursor_color(color), "{}", color);
+        }
+        // 明るすぎる色・不正な形式は使えない
+        assert!(!is_readable_cursor_color("#00FF00"));
+        assert!(!is_readable_cursor_color("#BBBBBB"));
+        assert!(!is_readable_cursor_color("blue"));
+        assert!(!is_readable_cursor_color("#12345"));
+    }
+
+    #[test]
+    fn test_resolve_avoids_similar_colors() {
+        // 似た色がいなければ希望の色
+        assert_eq!(resolve_cursor_color("#C62828", &["#1565C0"]), "#C62828");
+        // 似た色がいれば空いている最初の候補
+        assert_eq!(resolve_cursor_color("#1565C0", &["#1766C2"]), "#C62828");
+        assert_eq!(resolve_cursor_color("#1565C0", &["#1565C0", "#C62828"]), "#2E7D32");
+        
+        // 全ての候補が埋まっていれば希望の色のまま
+        let taken: Vec<&str> = CURSOR_COLORS.iter().map(|&(_, color)| color).collect();
+        assert_eq!(resolve_cursor_color("#1565C0", &taken), "#1565C0");
+    }
+
+    #[test]
+    fn test_next_cursor_color_cycles() {
+        assert_eq!(next_cursor_color("#1565c0"), "#C62828");
+        assert_eq!(next_cursor_color("#1A237E"), "#1565C0");
+        assert_eq!(next_cursor_color("#123456"), "#1565C0");
+        assert_eq!(cursor_color_label(Language::English, "#2E7D32"), "Green");
+        assert_eq!(cursor_color_label(Language::English, "#123456"), "#123456");
+    }
+}
\ No newline at end of file
@@ -0,0 +1,182 @@
+/**
+ * カスタムボード入力リソース
+ *
+ * 難易度選択画面の「カスタム」で入力中の幅・高さ・地雷数を管理するリソース
+ * 入力中は制約外の値も保持し、`BoardConfig::validate`で検証した結果を画面に表示する
+ */
+use super::game_config::{BoardConfig, BoardConfigError, Difficulty};
+use super::localization::text;
+use super::settings::Language;
+
+/// 入力できる値の最大値（4桁まで）
+const MAX_INPUT_VALUE: usize = 9999;
+
+/// カスタムボードの入力欄
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CustomBoardField {
+    /// ボードの幅
+    Width,
+    /// ボードの高さ
+    Height,
+    /// 地雷の数
+    Mines,
+}
+
+impl CustomBoardField {
+    /// 画面に並べる順番
+    pub const ALL: [CustomBoardField; 3] = [
+        CustomBoardField::Width,
+        CustomBoardField::Height,
+        CustomBoardField::Mines,
+    ];
+
+    /// 表示名を取得
+    pub fn label(&self, language: Language) -> &'static str {
+        let key = match self {
+            CustomBoardField::Width => "custom.width",
+            CustomBoardField::Height => "custom.height",
+            CustomBoardField::Mines => "custom.mines",
+        };
+        text(language, key)
+    }
+}
+
+/// カスタムボード入力リソース
+#[derive(Debug, Clone, PartialEq)]
+pub struct CustomBoardResource {
+    /// ボードの幅
+    width: usize,
+    /// ボードの高さ
+    height: usize,
+    /// 地雷の数
+    mine_count: usize,
+    /// キーボードで入力中の欄
+    focused: Option<CustomBoardField>,
+}
+
+impl Default for CustomBoardResource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CustomBoardResource {
+    /// 中級と同じ値で入力欄を作成
+    pub fn new() -> Self {
+        let (width, height, mine_count) = Difficulty::Medium.preset().unwrap_or((16, 16, 40));
+        Self { width, height, mine_count, focused: None }
+    }
+
+    /// 入力欄の値を取得
+    pub fn value(&self, field: CustomBoardField) -> usize {
+        match field {
+            CustomBoardField::Width => self.width,
+            CustomBoardField::Height => self.height,
+            CustomBoardField::Mines => self.mine_count,
+        }
+    }
+
+    /// 入力欄の値を設定（4桁まで）
+    pub fn set_value(&mut self, field: CustomBoardField, value: usize) {
+        let value = value.min(MAX_INPUT_VALUE);
+        match field {
+            CustomBoardField::Width => self.width = value,
+            CustomBoardField::Height => self.height = value,
+            CustomBoardField::Mines => self.mine_count = value,
+        }
+    }
+
+    /// 入力欄の値を増減
+    pub fn adjust(&mut self, field: CustomBoardField, delta: i64) {
+        let value = (self.value(field) as i64 + delta).max(0) as usize;
+        self.set_value(field, value);
+    }
+
+    /// キーボードで入力する欄を選ぶ（Noneで入力を終える）
+    pub fn focus(&mut self, field: Option<CustomBoardField>) {
+        self.focused = field;
+    }
+
+    /// キーボードで入力中の欄
+    pub fn focused(&self) -> Option<CustomBoardField> {
+        self.focused
+    }
+
+    /// 入力中の欄の末尾に数字を追加
+    pub fn push_digit(&mut self, digit: u32) {
+        if let Some(field) = self.focused {
+            let value = self.value(field) * 10 + digit as usize;
+            if value <= MAX_INPUT_VALUE {
+                self.set_value(field, value);
+            }
+        }
+    }
+
+    /// 入力中の欄の末尾の数字を削除
+    pub fn pop_digit(&mut self) {
+        if let Some(field) = self.focused {
+            self.set_value(field, self.value(field) / 10);
+        }
+    }
+
+    /// 入力値を検証
+    pub fn validate(&self) -> Result<(), BoardConfigError> {
+        BoardConfig::validate(self.width, self.height, self.mine_count)
+    }
+
+    /// 地雷の密度（0.0〜1.0、セルがない場合は0.0）
+    pub fn density(&self) -> f64 {
+        let cells = self.width * self.height;
+        if cells == 0 {
+            0.0
+        } else {
+            self.mine_count as f64 / cells as f64
+        }
+    }
+
+    /// 入力値を(幅, 高さ, 地雷数)で取得
+    pub fn dimensions(&self) -> (usize, usize, usize) {
+        (self.width, self.height, self.mine_count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_custom_board_input() {
+        let mut custom = CustomBoardResource::new();
+        assert_eq!(custom.dimensions(), (16, 16, 40));
+        assert_eq!(custom.validate(), Ok(()));
+        assert_eq!(custom.density(), 40.0 / 256.0);
+
+        // キーボード入力（入力中の欄がなければ無視）
+        custom.push_digit(1);
+        assert_eq!(custom.dimensions(), (16, 16, 40));
+        custom.focus(Some(CustomBoardField::Width));
+        custom.pop_digit();
+        custom.pop_digit();
+        assert_eq!(custom.value(CustomBoardField::Width), 0);
+        assert_eq!(custom.validate(), Err(BoardConfigError::Size));
+        custom.push_digit(3);
+        custom.push_digit(0);
+        assert_eq!(custom.value(CustomBoardField::Width), 30);
+
+        // 4桁を超える入力は無視
+        custom.focus(Some(CustomBoardField::Mines));
+        custom.set_value(CustomBoardField::Mines, 0);
+        for _ in 0..5 {
+            custom.push_digit(9);
+        }
+        assert_eq!(custom.value(CustomBoardField::Mines), 9999);
+        assert_eq!(custom.validate(), Err(BoardConfigError::Mines { max: 471 }));
+
+        // ステッパーでの増減（0未満にはならない）
+        custom.set_value(CustomBoardField::Mines, 0);
+        custom.adjust(CustomBoardField::Mines, -1);
+        assert_eq!(custom.value(CustomBoardField::Mines), 0);
+        custom.adjust(CustomBoardField::Mines, 99);
+        assert_eq!(custom.validate(), Ok(()));
+    }
+}
\ No newline at end of file
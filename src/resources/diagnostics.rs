@@ -0,0 +1,265 @@
+/**
+ * 診断情報リソース
+ *
+ * フレームごとのECSの状態（エンティティ数、コンポーネント数など）と
+ * WASMのメモリ使用量・主なコレクションの大きさを記録するリソース
+ */
+use std::any::{type_name, TypeId};
+use std::collections::HashMap;
+use crate::entities::EntityManager;
+
+/// エンティティ数の警告しきい値のデフォルト値
+const DEFAULT_MAX_ENTITIES: usize = 10_000;
+/// 削除待ちエンティティ数の警告しきい値のデフォルト値
+const DEFAULT_MAX_PENDING_REMOVALS: usize = 1_000;
+/// 最初の計測からのWASMメモリの増加量の警告しきい値のデフォルト値（バイト）
+const DEFAULT_MAX_MEMORY_GROWTH: usize = 32 * 1024 * 1024;
+/// コレクションの大きさの警告しきい値のデフォルト値
+const DEFAULT_MAX_COLLECTION_SIZE: usize = 10_000;
+
+/// フレームごとのECS診断情報を保持するリソース
+#[derive(Debug, Clone)]
+pub struct DiagnosticsResource {
+    /// 更新されたフレーム数
+    pub frame: u64,
+    /// エンティティの総数
+    pub entity_count: usize,
+    /// コンポーネント型ごとのエンティティ数
+    pub component_counts: HashMap<TypeId, usize>,
+    /// 削除待ちのエンティティ数
+    pub pending_removals: usize,
+    /// 登録されているリソースの数
+    pub resource_count: usize,
+    /// エンティティ数の警告しきい値
+    pub max_entities: usize,
+    /// 削除待ちエンティティ数の警告しきい値
+    pub max_pending_removals: usize,
+    /// WASMの線形メモリの大きさ（バイト、計測していない場合は0）
+    pub memory_bytes: usize,
+    /// 最初に計測したWASMの線形メモリの大きさ（増加量の基準）
+    pub baseline_memory_bytes: Option<usize>,
+    /// 主なコレクションの大きさ（名前, 容量や要素数）
+    pub collection_sizes: Vec<(&'static str, usize)>,
+    /// WASMメモリの増加量の警告しきい値（バイト）
+    pub max_memory_growth: usize,
+    /// コレクションの大きさの警告しきい値
+    pub max_collection_size: usize,
+    /// 表示用のコンポーネント型名
+    component_names: HashMap<TypeId, &'static str>,
+    /// 前フレームでしきい値を超えていたかどうか（ログの連続出力を防ぐ）
+    was_over_threshold: bool,
+}
+
+impl Default for DiagnosticsResource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DiagnosticsResource {
+    /// 新しい診断情報リソースを作成
+    pub fn new() -> Self {
+        Self {
+            frame: 0,
+            entity_count: 0,
+            component_counts: HashMap::new(),
+            pending_removals: 0,
+            resource_count: 0,
+            max_entities: DEFAULT_MAX_ENTITIES,
+            max_pending_removals: DEFAULT_MAX_PENDING_REMOVALS,
+            memory_bytes: 0,
+            baseline_memory_bytes: None,
+            collection_sizes: Vec::new(),
+            max_memory_growth: DEFAULT_MAX_MEMORY_GROWTH,
+            max_collection_size: DEFAULT_MAX_COLLECTION_SIZE,
+            component_names: HashMap::new(),
+            was_over_threshold: false,
+        }
+    }
+
+    /// 警告しきい値を設定
+    pub fn with_thresholds(mut self, max_entities: usize, max_pending_removals: usize) -> Self {
+        self.max_entities = max_entities;
+        self.max_pending_removals = max_pending_removals;
+        self
+    }
+
+    /// メモリの警告しきい値を設定
+    pub fn with_memory_thresholds(mut self, max_memory_growth: usize, max_collection_size: usize) -> Self {
+        self.max_memory_growth = max_memory_growth;
+        self.max_collection_size = max_collection_size;
+        self
+    }
+
+    /// WASMのメモリ使用量とコレクションの大きさを記録（警告は次の`update`で判定）
+    pub fn record_memory(&mut self, memory_bytes: usize, collection_sizes: Vec<(&'static str, usize)>) {
+        self.memory_bytes = memory_bytes;
+        self.baseline_memory_bytes.get_or_insert(memory_bytes);
+        self.collection_sizes = collection_sizes;
+    }
+
+    /// 最初の計測からのWASMメモリの増加量（バイト）
+    pub fn memory_growth(&self) -> usize {
+        self.baseline_memory_bytes.map_or(0, |baseline| self.memory_bytes.saturating_sub(baseline))
+    }
+
+    /// 表示用にコンポーネント型名を登録
+    pub fn register_component<T: 'static>(&mut self) {
+        let name = type_name::<T>().rsplit("::").next().unwrap_or("unknown");
+        self.component_names.insert(TypeId::of::<T>(), name);
+    }
+
+    /// エンティティマネージャーの状態から診断情報を更新
+    pub fn update(&mut self, entities: &EntityManager, resource_count: usize) {
+        self.frame += 1;
+        self.entity_count = entities.entity_count();
+        self.component_counts = entities.component_type_counts();
+        self.pending_removals = entities.pending_removal_count();
+        self.resource_count = resource_count;
+
+        // しきい値を超えた最初のフレームだけログを出す
+        let warnings = self.threshold_warnings();
+        let over_threshold = !warnings.is_empty();
+        if over_threshold && !self.was_over_threshold {
+            for warning in &warnings {
+                Self::log_warning(warning);
+            }
+        }
+        self.was_over_threshold = over_threshold;
+    }
+
+    /// 特定のコンポーネントを持つエンティティ数を取得
+    pub fn component_count<T: 'static>(&self) -> usize {
+        self.component_counts.get(&TypeId::of::<T>()).copied().unwrap_or(0)
+    }
+
+    /// しきい値を超えている項目の警告メッセージを取得
+    pub fn threshold_warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+
+        if self.entity_count > self.max_entities {
+            warnings.push(format!(
+                "エンティティ数がしきい値を超えています: {} > {}",
+                self.entity_count, self.max_entities
+            ));
+        }
+
+        if self.pending_removals > self.max_pending_removals {
+            warnings.push(format!(
+                "削除待ちエンティティ数がしきい値を超えています: {} > {}",
+                self.pending_removals, self.max_pending_removals
+            ));
+        }
+
+        if self.memory_growth() > self.max_memory_growth {
+            warnings.push(format!(
+                "WASMメモリの増加量がしきい値を超えています: {} KiB > {} KiB",
+                self.memory_growth() / 1024, self.max_memory_growth / 1024
+            ));
+        }
+
+        for &(name, size) in &self.collection_sizes {
+            if size > self.max_collection_size {
+                warnings.push(format!(
+                    "{}の大きさがしきい値を超えています: {} > {}",
+                    name, size, self.max_collection_size
+                ));
+            }
+        }
+
+        warnings
+    }
+
+    /// デバッグオーバーレイ用の表示行を取得
+    pub fn overlay_lines(&self) -> Vec<String> {
+        let mut lines = vec![
+            format!("Frame: {}", self.frame),
+            format!("Entities: {}", self.entity_count),
+            format!("Pending removals: {}", self.pending_removals),
+            format!("Resources: {}", self.resource_count),
+        ];
+        if self.memory_bytes > 0 {
+            lines.push(format!("WASM memory: {} KiB (+{} KiB)", self.memory_bytes / 1024, self.memory_growth() / 1024));
+        }
+        for &(name, size) in &self.collection_sizes {
+            lines.push(format!("  {}: {}", name, size));
+        }
+
+        // コンポーネントは名前順に表示（未登録の型はまとめて表示）
+        let mut named: Vec<(&str, usize)> = Vec::new();
+        let mut unnamed = 0;
+        for (type_id, &count) in &self.component_counts {
+            match self.component_names.get(type_id) {
+                Some(name) => named.push((name, count)),
+                None => unnamed += count,
+            }
+        }
+        named.sort();
+
+        for (name, count) in named {
+            lines.push(format!("  {}: {}", name, count));
+        }
+        if unnamed > 0 {
+            lines.push(format!("  (other): {}", unnamed));
+        }
+
+        lines
+    }
+
+    /// 警告をコンソールに出力
+    fn log_warning(message: &str) {
+        crate::logger::log_warn!("{}", message);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::Position;
+
+    #[test]
+    fn test_update_from_entity_manager() {
+        let mut entities = EntityManager::new();
+        for i in 0..3 {
+            let entity = entities.create_builder()
+                .with_component(Position::new(i as f64, 0.0))
+                .build();
+            entities.register_entity(entity);
+        }
+        let removed = entities.create_entity();
+        entities.remove_entity(removed);
+
+        let mut diagnostics = DiagnosticsResource::new().with_thresholds(3, 10);
+        diagnostics.register_component::<Position>();
+        diagnostics.update(&entities, 5);
+
+        assert_eq!(diagnostics.frame, 1);
+        assert_eq!(diagnostics.entity_count, 4);
+        assert_eq!(diagnostics.component_count::<Position>(), 3);
+        assert_eq!(diagnostics.pending_removals, 1);
+        assert_eq!(diagnostics.resource_count, 5);
+        assert!(diagnostics.overlay_lines().contains(&"  Position: 3".to_string()));
+
+        // エンティティ数がしきい値を超えている
+        assert_eq!(diagnostics.threshold_warnings().len(), 1);
+    }
+
+    #[test]
+    fn test_memory_growth_warnings() {
+        let mut diagnostics = DiagnosticsResource::new().with_memory_thresholds(1024, 100);
+        diagnostics.record_memory(65_536, vec![("players", 4)]);
+        assert_eq!(diagnostics.memory_growth(), 0);
+        assert!(diagnostics.threshold_warnings().is_empty());
+        assert!(diagnostics.overlay_lines().contains(&"WASM memory: 64 KiB (+0 KiB)".to_string()));
+
+        // 基準は最初の計測のまま、増加量としきい値を超えたコレクションを警告する
+        diagnostics.record_memory(131_072, vec![("players", 4), ("toasts", 101)]);
+        assert_eq!(diagnostics.baseline_memory_bytes, Some(65_536));
+        assert_eq!(diagnostics.memory_growth(), 65_536);
+        let warnings = diagnostics.threshold_warnings();
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[1].starts_with("toasts"));
+        assert!(diagnostics.overlay_lines().contains(&"  toasts: 101".to_string()));
+    }
+
+}
\ No newline at end of file
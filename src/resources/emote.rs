@@ -0,0 +1,177 @@
+/**
+ * エモートリソース
+ *
+ * プレイヤーが送るエモート（👍 💣 🚩 😱）の吹き出し表示と、
+ * カーソルの周囲に開く円形のエモート選択メニューの状態を管理するリソース
+ */
+use std::collections::HashMap;
+
+/// 吹き出しを表示する時間（ミリ秒）
+pub const EMOTE_DISPLAY_MS: f64 = 2000.0;
+
+/// 選択メニューの中心から各エモートまでの距離（ピクセル）
+pub const EMOTE_PICKER_RADIUS: f64 = 56.0;
+
+/// 選択メニューの各エモートのクリック判定の半径（ピクセル）
+pub const EMOTE_OPTION_RADIUS: f64 = 24.0;
+
+/// エモートの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Emote {
+    /// いいね
+    ThumbsUp,
+    /// 地雷
+    Bomb,
+    /// フラグ
+    Flag,
+    /// 驚き
+    Scream,
+}
+
+impl Emote {
+    /// 全てのエモート（選択メニューでは上から時計回りに並べる）
+    pub const ALL: [Emote; 4] = [Emote::ThumbsUp, Emote::Bomb, Emote::Flag, Emote::Scream];
+
+    /// 表示する絵文字を取得
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Emote::ThumbsUp => "👍",
+            Emote::Bomb => "💣",
+            Emote::Flag => "🚩",
+            Emote::Scream => "😱",
+        }
+    }
+
+    /// 通信で使うキーを取得
+    pub fn key(&self) -> &'static str {
+        match self {
+            Emote::ThumbsUp => "thumbs_up",
+            Emote::Bomb => "bomb",
+            Emote::Flag => "flag",
+            Emote::Scream => "scream",
+        }
+    }
+
+    /// 通信で使うキーからエモートを取得
+    pub fn from_key(key: &str) -> Option<Self> {
+        Emote::ALL.iter().copied().find(|emote| emote.key() == key)
+    }
+}
+
+/// エモートリソース
+#[derive(Debug, Clone, Default)]
+pub struct EmoteResource {
+    /// プレイヤーごとの表示中のエモートと表示開始時刻
+    bubbles: HashMap<String, (Emote, f64)>,
+    /// 開いている選択メニューの中心座標
+    picker: Option<(f64, f64)>,
+}
+
+impl EmoteResource {
+    /// 新しいエモートリソースを作成
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// プレイヤーのエモートを表示（表示中のものは置き換える）
+    pub fn show(&mut self, player_id: &str, emote: Emote, now: f64) {
+        self.bubbles.insert(player_id.to_string(), (emote, now));
+    }
+
+    /// プレイヤーの表示中のエモートと経過割合（0.0〜1.0）を取得
+    pub fn active(&self, player_id: &str, now: f64) -> Option<(Emote, f64)> {
+        self.bubbles.get(player_id).and_then(|&(emote, shown_at)| {
+            let elapsed = now - shown_at;
+            (elapsed < EMOTE_DISPLAY_MS).then_some((emote, (elapsed / EMOTE_DISPLAY_MS).max(0.0)))
+        })
+    }
+
+    /// 表示時間を過ぎたエモートを削除
+    pub fn prune(&mut self, now: f64) {
+        self.bubbles.retain(|_, (_, shown_at)| now - *shown_at < EMOTE_DISPLAY_MS);
+    }
+
+    /// 選択メニューを開く
+    pub fn open_picker(&mut self, x: f64, y: f64) {
+        self.picker = Some((x, y));
+    }
+
+    /// 選択メニューを閉じる
+    pub fn close_picker(&mut self) {
+        self.picker = None;
+    }
+
+    /// 開いている選択メニューの中心座標を取得
+    pub fn picker_center(&self) -> Option<(f64, f64)> {
+        self.picker
+    }
+
+    /// 選択メニューの各エモートの表示位置を取得
+    pub fn picker_options(&self) -> Vec<(Emote, f64, f64)> {
+        let Some((center_x, center_y)) = self.picker else {
+            return Vec::new();
+        };
+
+        Emote::ALL
+            .iter()
+            .enumerate()
+            .map(|(index, &emote)| {
+                // 真上から時計回りに等間隔で配置
+                let angle = index as f64 / Emote::ALL.len() as f64 * std::f64::consts::TAU - std::f64::consts::FRAC_PI_2;
+                (emote, center_x + angle.cos() * EMOTE_PICKER_RADIUS, center_y + angle.sin() * EMOTE_PICKER_RADIUS)
+            })
+            .collect()
+    }
+
+    /// 座標にある選択メニューのエモートを取得
+    pub fn picker_option_at(&self, x: f64, y: f64) -> Option<Emote> {
+        self.picker_options()
+            .into_iter()
+            .find(|&(_, option_x, option_y)| {
+                let (dx, dy) = (x - option_x, y - option_y);
+                dx * dx + dy * dy <= EMOTE_OPTION_RADIUS * EMOTE_OPTION_RADIUS
+            })
+            .map(|(emote, _, _)| emote)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_emote_keys_round_trip() {
+        for emote in Emote::ALL {
+            assert_eq!(Emote::from_key(emote.key()), Some(emote));
+        }
+        assert_eq!(Emote::from_key("unknown"), None);
+    }
+
+    #[test]
+    fn test_bubble_expires() {
+        let mut emotes = EmoteResource::new();
+        emotes.show("p1", Emote::Bomb, 1000.0);
+
+        assert_eq!(emotes.active("p1", 2000.0), Some((Emote::Bomb, 0.5)));
+        assert_eq!(emotes.active("p1", 1000.0 + EMOTE_DISPLAY_MS), None);
+        assert_eq!(emotes.active("p2", 1000.0), None);
+
+        emotes.prune(1000.0 + EMOTE_DISPLAY_MS);
+        assert!(emotes.bubbles.is_empty());
+    }
+
+    #[test]
+    fn test_picker_hit_test() {
+        let mut emotes = EmoteResource::new();
+        assert_eq!(emotes.picker_option_at(100.0, 100.0), None);
+
+        emotes.open_picker(100.0, 100.0);
+        // 先頭は真上、2番目は右に配置される
+        assert_eq!(emotes.picker_option_at(100.0, 100.0 - EMOTE_PICKER_RADIUS), Some(Emote::ThumbsUp));
+        assert_eq!(emotes.picker_option_at(100.0 + EMOTE_PICKER_RADIUS, 102.0), Some(Emote::Bomb));
+        assert_eq!(emotes.picker_option_at(100.0, 100.0), None);
+
+        emotes.close_picker();
+        assert_eq!(emotes.picker_center(), None);
+    }
+}
\ No newline at end of file
@@ -4,10 +4,21 @@
  * マインスイーパーゲームの設定を管理するリソース
  */
 use wasm_bindgen::prelude::*;
-use js_sys::Date;
+use crate::utils::now_ms;
+use super::localization::{text, format_text};
+use super::settings::Language;
+
+/// ボードの幅・高さの最小値
+pub const MIN_BOARD_SIZE: usize = 5;
+
+/// ボードの幅・高さの最大値（サーバーの上限と同じ）
+pub const MAX_BOARD_SIZE: usize = 50;
+
+/// 初手で開ける領域として地雷を置かないセルの数
+const SAFE_AREA_CELLS: usize = 9;
 
 /// ゲームの難易度
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Difficulty {
     /// 初級（9x9、10地雷）
     Easy,
@@ -19,6 +30,73 @@ pub enum Difficulty {
     Custom,
 }
 
+impl Difficulty {
+    /// 難易度選択画面に並べるプリセット
+    pub const PRESETS: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard];
+
+    /// 表示名を取得
+    pub fn label(&self, language: Language) -> &'static str {
+        let key = match self {
+            Difficulty::Easy => "difficulty.easy",
+            Difficulty::Medium => "difficulty.medium",
+            Difficulty::Hard => "difficulty.hard",
+            Difficulty::Custom => "difficulty.custom",
+        };
+        text(language, key)
+    }
+
+    /// サーバーとの通信で使うキーを取得
+    pub fn key(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Medium => "medium",
+            Difficulty::Hard => "hard",
+            Difficulty::Custom => "custom",
+        }
+    }
+
+    /// キーから難易度を取得
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "easy" => Some(Difficulty::Easy),
+            "medium" => Some(Difficulty::Medium),
+            "hard" => Some(Difficulty::Hard),
+            "custom" => Some(Difficulty::Custom),
+            _ => None,
+        }
+    }
+
+    /// プリセットのボード寸法と地雷数（幅, 高さ, 地雷数）を取得
+    /// カスタムの場合はNone
+    pub fn preset(&self) -> Option<(usize, usize, usize)> {
+        match self {
+            Difficulty::Easy => Some((9, 9, 10)),
+            Difficulty::Medium => Some((16, 16, 40)),
+            Difficulty::Hard => Some((30, 16, 99)),
+            Difficulty::Custom => None,
+        }
+    }
+}
+
+/// ボード設定の入力エラー
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoardConfigError {
+    /// 幅または高さが範囲外
+    Size,
+    /// 地雷の数が範囲外（最大数を保持）
+    Mines { max: usize },
+}
+
+impl BoardConfigError {
+    /// 表示用のメッセージを取得
+    pub fn message(&self, language: Language) -> String {
+        match self {
+            BoardConfigError::Size => format_text(language, "custom.error.size", &[&MIN_BOARD_SIZE, &MAX_BOARD_SIZE]),
+            BoardConfigError::Mines { max } => format_text(language, "custom.error.mines", &[max]),
+        }
+    }
+}
+
 /// ボードの設定
 #[derive(Debug, Clone)]
 pub struct BoardConfig {
@@ -36,11 +114,10 @@ impl BoardConfig {
     /// 新しいBoardConfigインスタンスを作成
     pub fn new(width: usize, height: usize, mine_count: usize, cell_size: f64) -> Self {
         // 最小値の制約を適用
-        let width = width.max(5);
-        let height = height.max(5);
+        let width = width.max(MIN_BOARD_SIZE);
+        let height = height.max(MIN_BOARD_SIZE);
         // 総セル数を超えないように地雷数を調整
-        let max_mines = width * height - 9; // 初手で開ける領域を確保
-        let mine_count = mine_count.min(max_mines);
+        let mine_count = mine_count.min(Self::max_mines(width, height));
         
         Self {
             width,
@@ -50,6 +127,28 @@ impl BoardConfig {
         }
     }
 
+    /// 置ける地雷の最大数を取得（初手で開ける領域を確保）
+    pub fn max_mines(width: usize, height: usize) -> usize {
+        (width * height).saturating_sub(SAFE_AREA_CELLS)
+    }
+
+    /// カスタムボードの入力値を検証
+    /// 
+    /// `new`で補正される値とサーバーの上限を超える値をエラーにする
+    pub fn validate(width: usize, height: usize, mine_count: usize) -> Result<(), BoardConfigError> {
+        let size_range = MIN_BOARD_SIZE..=MAX_BOARD_SIZE;
+        if !size_range.contains(&width) || !size_range.contains(&height) {
+            return Err(BoardConfigError::Size);
+        }
+        
+        let max = Self::max_mines(width, height);
+        if mine_count == 0 || mine_count > max {
+            return Err(BoardConfigError::Mines { max });
+        }
+        
+        Ok(())
+    }
+
     /// 総セル数を取得
     pub fn total_cells(&self) -> usize {
         self.width * self.height
@@ -105,16 +204,11 @@ impl GameConfigResource {
 
     /// 難易度を設定
     pub fn set_difficulty(&mut self, difficulty: Difficulty) {
-        let (width, height, mine_count) = match difficulty {
-            Difficulty::Easy => (9, 9, 10),
-            Difficulty::Medium => (16, 16, 40),
-            Difficulty::Hard => (30, 16, 99),
-            Difficulty::Custom => (
-                self.board_config.width,
-                self.board_config.height,
-                self.board_config.mine_count
-            ),
-        };
+        let (width, height, mine_count) = difficulty.preset().unwrap_or((
+            self.board_config.width,
+            self.board_config.height,
+            self.board_config.mine_count
+        ));
         
         self.board_config = BoardConfig::new(
             width,
@@ -157,7 +251,7 @@ impl GameConfigResource {
 
     /// ランダムシードを生成
     pub fn get_random_seed(&self) -> u64 {
-        let now = Date::now();
+        let now = now_ms();
         let seed = (now * 1000.0).floor() as u64;
         
         // ボード設定に基づいて追加のハッシュを作成
@@ -170,7 +264,7 @@ impl GameConfigResource {
 
     /// 現在の設定でスコアを計算する
     /// 
-    /// ゲームの難易度、ボードサイズ、経過時間に基づいてスコアを計算
+    /// ゲームの難易度、ボードサイズ、経過時間（ミリ秒）に基づいてスコアを計算
     pub fn calculate_score(&self, elapsed_time: f64, win: bool) -> u32 {
         if !win {
             return 0;
@@ -223,6 +317,24 @@ mod tests {
         assert_eq!(config.mine_count, 91); // 100 - 9 = 91 (最大地雷数)
     }
 
+    #[test]
+    fn test_validate_custom_board() {
+        assert_eq!(BoardConfig::validate(10, 10, 91), Ok(()));
+        assert_eq!(BoardConfig::validate(MAX_BOARD_SIZE, MIN_BOARD_SIZE, 1), Ok(()));
+
+        // サイズが範囲外
+        assert_eq!(BoardConfig::validate(4, 10, 10), Err(BoardConfigError::Size));
+        assert_eq!(BoardConfig::validate(10, MAX_BOARD_SIZE + 1, 10), Err(BoardConfigError::Size));
+
+        // 地雷が多すぎる・0個
+        assert_eq!(BoardConfig::validate(10, 10, 92), Err(BoardConfigError::Mines { max: 91 }));
+        assert_eq!(BoardConfig::validate(10, 10, 0), Err(BoardConfigError::Mines { max: 91 }));
+        assert_eq!(
+            BoardConfigError::Mines { max: 91 }.message(Language::English),
+            "Mines must be between 1 and 91"
+        );
+    }
+
     #[test]
     fn test_set_difficulty() {
         let mut config = GameConfigResource::new();
@@ -246,15 +358,36 @@ mod tests {
         assert_eq!(config.board_config.mine_count, 99);
     }
 
+    #[test]
+    fn test_difficulty_presets() {
+        let mut config = GameConfigResource::new();
+
+        for difficulty in Difficulty::PRESETS {
+            let (width, height, mine_count) = difficulty.preset().unwrap();
+            config.set_difficulty(difficulty.clone());
+            assert_eq!(config.difficulty, difficulty);
+            assert_eq!(config.board_config.width, width);
+            assert_eq!(config.board_config.height, height);
+            assert_eq!(config.board_config.mine_count, mine_count);
+        }
+
+        // カスタムは現在のボード設定を維持する
+        config.set_custom_board(12, 10, 20);
+        config.set_difficulty(Difficulty::Custom);
+        assert_eq!(Difficulty::Custom.preset(), None);
+        assert_eq!(config.board_config.width, 12);
+        assert_eq!(config.board_config.mine_count, 20);
+    }
+
     #[test]
     fn test_score_calculation() {
         let config = GameConfigResource::new();
         
         // 基本ケース
-        let score1 = config.calculate_score(60.0, true);
+        let score1 = config.calculate_score(60_000.0, true);
         
         // 時間がかかるとスコアが下がる
-        let score2 = config.calculate_score(120.0, true);
+        let score2 = config.calculate_score(120_000.0, true);
         assert!(score1 > score2);
     }
 } 
\ No newline at end of file
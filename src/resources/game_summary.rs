@@ -0,0 +1,155 @@
+/**
+ * ゲーム結果リソース
+ *
+ * ゲーム終了後のまとめ画面やプロフィールカードに表示するため、1ゲーム中の操作回数・
+ * 踏んだ地雷の数・プレイヤーごとの開いたセル数や立てた旗の数を集計するリソース
+ */
+use std::collections::HashMap;
+
+/// 安全なセルを1つ開いた時の得点
+const POINTS_PER_CELL: i64 = 10;
+/// 地雷を1つ踏んだ時の減点
+const POINTS_PER_MINE: i64 = 100;
+
+/// 1ゲーム中のプレイヤーごとの記録
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PlayerContribution {
+    /// 開いたセル数（地雷を含む）
+    pub cells: usize,
+    /// 踏んだ地雷の数
+    pub mines: u32,
+    /// 立てた旗の数
+    pub flags: u32,
+}
+
+impl PlayerContribution {
+    /// このゲームでの得点（安全なセル1つにつき10点、地雷1つにつき-100点、0点未満にはしない）
+    pub fn score(&self) -> u32 {
+        let safe_cells = self.cells as i64 - self.mines as i64;
+        (safe_cells * POINTS_PER_CELL - self.mines as i64 * POINTS_PER_MINE).max(0) as u32
+    }
+}
+
+/// 1ゲーム分の集計
+#[derive(Debug, Clone, Default)]
+pub struct GameSummaryResource {
+    /// セルを開く操作の回数（連鎖して開いた分は1回と数える）
+    reveal_actions: u32,
+    /// 開かれた地雷の数
+    mines_triggered: u32,
+    /// プレイヤーごとの記録
+    players: HashMap<String, PlayerContribution>,
+}
+
+impl GameSummaryResource {
+    /// 空の集計を作成
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 集計を初期化（新しいゲームの開始時に呼び出す）
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// セルを開く操作を記録
+    ///
+    /// `player_id`が分からない場合は操作回数と地雷の数のみ記録する
+    pub fn record_reveal(&mut self, player_id: Option<&str>, cells: usize, mines: u32) {
+        self.reveal_actions += 1;
+        self.mines_triggered += mines;
+
+        if let Some(id) = player_id {
+            let contribution = self.players.entry(id.to_string()).or_default();
+            contribution.cells += cells;
+            contribution.mines += mines;
+        }
+    }
+
+    /// 旗を立てた操作を記録
+    pub fn record_flag(&mut self, player_id: &str) {
+        self.players.entry(player_id.to_string()).or_default().flags += 1;
+    }
+
+    /// プレイヤーの記録（操作していなければ全て0）
+    pub fn player(&self, player_id: &str) -> PlayerContribution {
+        self.players.get(player_id).copied().unwrap_or_default()
+    }
+
+    /// セルを開く操作の回数
+    pub fn reveal_actions(&self) -> u32 {
+        self.reveal_actions
+    }
+
+    /// 開かれた地雷の数
+    pub fn mines_triggered(&self) -> u32 {
+        self.mines_triggered
+    }
+
+    /// 効率（3BV ÷ 操作回数、操作がなければNone）
+    pub fn efficiency(&self, three_bv: u32) -> Option<f64> {
+        if self.reveal_actions == 0 {
+            None
+        } else {
+            Some(three_bv as f64 / self.reveal_actions as f64)
+        }
+    }
+
+    /// プレイヤーごとの開いたセル数（多い順、同数はID順）
+    pub fn cells_by_player(&self) -> Vec<(String, usize)> {
+        let mut entries: Vec<(String, usize)> = self.players
+            .iter()
+            .filter(|(_, contribution)| contribution.cells > 0)
+            .map(|(id, contribution)| (id.clone(), contribution.cells))
+            .collect();
+        entries.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        entries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary_counts() {
+        let mut summary = GameSummaryResource::new();
+        assert_eq!(summary.efficiency(10), None);
+
+        summary.record_reveal(Some("p1"), 12, 0);
+        summary.record_reveal(Some("p2"), 1, 0);
+        summary.record_reveal(Some("p2"), 3, 0);
+        summary.record_reveal(None, 1, 1);
+
+        assert_eq!(summary.reveal_actions(), 4);
+        assert_eq!(summary.mines_triggered(), 1);
+        assert_eq!(summary.efficiency(10), Some(2.5));
+        assert_eq!(summary.cells_by_player(), vec![("p1".to_string(), 12), ("p2".to_string(), 4)]);
+
+        summary.reset();
+        assert_eq!(summary.reveal_actions(), 0);
+        assert!(summary.cells_by_player().is_empty());
+    }
+
+    #[test]
+    fn test_player_contribution() {
+        let mut summary = GameSummaryResource::new();
+        summary.record_reveal(Some("p1"), 12, 0);
+        summary.record_reveal(Some("p1"), 1, 1);
+        summary.record_flag("p1");
+        summary.record_flag("p2");
+
+        let p1 = summary.player("p1");
+        assert_eq!(p1, PlayerContribution { cells: 13, mines: 1, flags: 1 });
+        assert_eq!(p1.score(), 20);
+
+        // 旗を立てただけのプレイヤーは開いたセル数の一覧に出ない
+        assert_eq!(summary.player("p2").flags, 1);
+        assert_eq!(summary.cells_by_player(), vec![("p1".to_string(), 13)]);
+
+        // 得点は0点未満にならない
+        summary.record_reveal(Some("p3"), 1, 1);
+        assert_eq!(summary.player("p3").score(), 0);
+        assert_eq!(summary.player("nobody"), PlayerContribution::default());
+    }
+}
\ No newline at end of file
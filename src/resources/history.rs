@@ -0,0 +1,138 @@
+/**
+ * 対戦履歴リソース
+ *
+ * 終了したゲームを新しい順に最大`MAX_HISTORY`件記録するリソース
+ * 盤面の地雷の配置も保存し、配置が分かるゲームは履歴画面から同じ盤面を練習できる
+ * JSONに変換してlocalStorageに保存し、次回起動時に読み込む
+ */
+use serde::{Serialize, Deserialize};
+use super::game_config::Difficulty;
+use super::leaderboard::format_time;
+
+/// localStorageに保存する際のキー
+pub const HISTORY_STORAGE_KEY: &str = "minesweeper_history";
+
+/// 保存する履歴の最大件数
+pub const MAX_HISTORY: usize = 8;
+
+/// 1ゲーム分の記録
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameRecord {
+    /// 難易度のキー（`Difficulty::key`）
+    pub difficulty: String,
+    /// ボードの幅
+    pub width: usize,
+    /// ボードの高さ
+    pub height: usize,
+    /// 地雷の数
+    pub mine_count: usize,
+    /// 勝利したかどうか
+    pub win: bool,
+    /// プレイ時間（ミリ秒）
+    pub time_ms: f64,
+    /// 終了した日時（UNIX時間、ミリ秒）
+    pub finished_at: f64,
+    /// 参加したプレイヤーの表示名
+    pub players: Vec<String>,
+    /// 地雷のあるセルのインデックス（分からない場合は空）
+    pub mines: Vec<usize>,
+}
+
+impl GameRecord {
+    /// 難易度を取得（不明なキーはカスタム扱い）
+    pub fn difficulty(&self) -> Difficulty {
+        Difficulty::from_key(&self.difficulty).unwrap_or(Difficulty::Custom)
+    }
+
+    /// プレイ時間を表示用の文字列で取得
+    pub fn time_text(&self) -> String {
+        format_time(self.time_ms)
+    }
+
+    /// 同じ盤面を練習できるかどうか（地雷の配置が全て分かっている場合のみ）
+    pub fn can_practice(&self) -> bool {
+        self.mine_count > 0 &&
+            self.mines.len() == self.mine_count &&
+            self.mines.iter().all(|&index| index < self.width * self.height)
+    }
+}
+
+/// 対戦履歴リソース
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HistoryResource {
+    /// 記録（新しい順）
+    records: Vec<GameRecord>,
+}
+
+impl HistoryResource {
+    /// 空の履歴を作成
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 保存されたJSONから履歴を復元（読み込めない場合は空の履歴）
+    pub fn from_json(json: &str) -> Self {
+        serde_json::from_str(json).unwrap_or_default()
+    }
+
+    /// 保存用のJSONに変換
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    /// 終了したゲームを記録（最大件数を超えた古い記録は削除）
+    pub fn record(&mut self, record: GameRecord) {
+        self.records.insert(0, record);
+        self.records.truncate(MAX_HISTORY);
+    }
+
+    /// 記録を取得（新しい順）
+    pub fn records(&self) -> &[GameRecord] {
+        &self.records
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(time_ms: f64, mines: Vec<usize>) -> GameRecord {
+        GameRecord {
+            difficulty: "easy".to_string(),
+            width: 9,
+            height: 9,
+            mine_count: 2,
+            win: true,
+            time_ms,
+            finished_at: 0.0,
+            players: vec!["太郎".to_string()],
+            mines,
+        }
+    }
+
+    #[test]
+    fn test_history_keeps_newest_records() {
+        let mut history = HistoryResource::new();
+        for index in 0..=MAX_HISTORY {
+            history.record(record(index as f64 * 1000.0, vec![0, 80]));
+        }
+
+        assert_eq!(history.records().len(), MAX_HISTORY);
+        assert_eq!(history.records()[0].time_ms, MAX_HISTORY as f64 * 1000.0);
+        assert_eq!(history.records()[0].time_text(), "00:08.0");
+        assert_eq!(history.records()[0].difficulty(), Difficulty::Easy);
+
+        let restored = HistoryResource::from_json(&history.to_json());
+        assert_eq!(restored, history);
+        assert_eq!(HistoryResource::from_json("not json"), HistoryResource::new());
+    }
+
+    #[test]
+    fn test_practice_needs_full_mine_layout() {
+        assert!(record(0.0, vec![0, 80]).can_practice());
+        assert!(!record(0.0, vec![0]).can_practice());
+        assert!(!record(0.0, vec![0, 81]).can_practice());
+    }
+}
\ No newline at end of file
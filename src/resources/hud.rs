@@ -0,0 +1,126 @@
+/**
+ * HUDリソース
+ *
+ * ゲーム画面のHUD（タイマー・残り地雷数・接続状態・遅延）に表示する値とキャンバスの大きさを保持するリソース
+ * GameStateが毎フレーム書き込み、HUDのシステムがUIエンティティの内容と配置に反映する
+ */
+use super::leaderboard::format_time;
+use super::localization::{text, format_text};
+use super::color::Color;
+use super::settings::{Language, Theme};
+use super::network_stats::NetworkStatsResource;
+
+/// HUDのウィジェットの種類（UIエンティティのコンポーネントとしても使う）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HudWidget {
+    /// 接続状態
+    Connection,
+    /// 経過時間
+    Timer,
+    /// 残り地雷数（地雷数 - 旗の数）
+    MineCounter,
+    /// 地雷以外のセルを開いた割合
+    Progress,
+    /// 通信の遅延
+    Latency,
+}
+
+/// HUDリソース
+#[derive(Debug, Clone, PartialEq)]
+pub struct HudResource {
+    /// キャンバスの幅
+    pub canvas_width: f64,
+    /// キャンバスの高さ
+    pub canvas_height: f64,
+    /// 経過時間（ミリ秒）
+    pub elapsed_ms: f64,
+    /// 残り地雷数（旗を立てすぎると負になる）
+    pub remaining_mines: i64,
+    /// 地雷以外のセルを開いた割合（0.0〜1.0）
+    pub progress: f64,
+    /// サーバーに接続中かどうか
+    pub connected: bool,
+    /// 通信の遅延（ミリ秒、未計測ならNone）
+    pub latency_ms: Option<f64>,
+    /// 表示言語
+    pub language: Language,
+    /// 表示テーマ
+    pub theme: Theme,
+}
+
+impl Default for HudResource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HudResource {
+    /// 初期状態のHUDリソースを作成
+    pub fn new() -> Self {
+        Self {
+            canvas_width: 0.0,
+            canvas_height: 0.0,
+            elapsed_ms: 0.0,
+            remaining_mines: 0,
+            progress: 0.0,
+            connected: false,
+            latency_ms: None,
+            language: Language::Japanese,
+            theme: Theme::Dark,
+        }
+    }
+
+    /// ウィジェットに表示する文言を取得
+    pub fn text(&self, widget: HudWidget) -> String {
+        match widget {
+            HudWidget::Connection if self.connected => text(self.language, "connection.connected").to_string(),
+            HudWidget::Connection => text(self.language, "connection.disconnected").to_string(),
+            HudWidget::Timer => format_time(self.elapsed_ms),
+            HudWidget::MineCounter => format_text(self.language, "hud.mines", &[&self.remaining_mines]),
+            HudWidget::Progress => format!("{}%", (self.progress * 100.0).floor()),
+            HudWidget::Latency => match self.latency_ms {
+                Some(latency) => format_text(self.language, "hud.latency", &[&latency.round()]),
+                None => text(self.language, "hud.latency_unknown").to_string(),
+            },
+        }
+    }
+
+    /// 接続状態のインジケーターの色を取得
+    pub fn indicator_color(&self) -> Color {
+        if self.connected { Color(0x4CAF50) } else { Color(0xFF0000) }
+    }
+
+    /// ウィジェットのインジケーターの色を取得（遅延は速さに応じた色、それ以外は接続状態）
+    pub fn widget_indicator_color(&self, widget: HudWidget) -> Color {
+        match widget {
+            HudWidget::Latency => NetworkStatsResource::latency_color(self.latency_ms),
+            _ => self.indicator_color(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hud_text() {
+        let mut hud = HudResource::new();
+        hud.language = Language::English;
+        hud.elapsed_ms = 8000.0;
+        hud.remaining_mines = -2;
+
+        assert_eq!(hud.text(HudWidget::Timer), "00:08.0");
+        assert_eq!(hud.text(HudWidget::MineCounter), "Mines left -2");
+        assert_eq!(hud.text(HudWidget::Connection), "Offline");
+        assert_eq!(hud.indicator_color(), Color(0xFF0000));
+
+        hud.connected = true;
+        assert_eq!(hud.text(HudWidget::Connection), "Connected");
+
+        assert_eq!(hud.text(HudWidget::Latency), "Ping --");
+        hud.latency_ms = Some(42.4);
+        assert_eq!(hud.text(HudWidget::Latency), "Ping 42ms");
+        assert_eq!(hud.widget_indicator_color(HudWidget::Latency), Color(0x4CAF50));
+    }
+}
\ No newline at end of file
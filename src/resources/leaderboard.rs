@@ -0,0 +1,200 @@
+/**
+ * リーダーボードリソース
+ *
+ * サーバーから受信した難易度ごとのベストタイムと、
+ * スクロール可能なランキングパネルの表示状態を管理するリソース
+ */
+use std::collections::HashMap;
+use super::game_config::Difficulty;
+use super::localization::text;
+use super::settings::Language;
+use crate::protocol::LeaderboardEntryInfo;
+
+/// ランキングパネルの1行の高さ（ピクセル）
+pub const LEADERBOARD_LINE_HEIGHT: f64 = 28.0;
+
+/// パネルに表示する難易度の順番
+const DIFFICULTY_ORDER: [Difficulty; 4] = [
+    Difficulty::Easy,
+    Difficulty::Medium,
+    Difficulty::Hard,
+    Difficulty::Custom,
+];
+
+/// リーダーボードの1件分の記録
+#[derive(Debug, Clone, PartialEq)]
+pub struct LeaderboardEntry {
+    /// プレイヤー名
+    pub name: String,
+    /// クリアタイム（ミリ秒）
+    pub time_ms: f64,
+}
+
+/// リーダーボードリソース
+#[derive(Debug, Clone, Default)]
+pub struct LeaderboardResource {
+    /// 難易度ごとの記録（タイムの昇順）
+    entries: HashMap<Difficulty, Vec<LeaderboardEntry>>,
+    /// サーバーから受信済みかどうか
+    loaded: bool,
+    /// パネルを表示中かどうか
+    visible: bool,
+    /// パネルのスクロール量（ピクセル）
+    scroll_offset: f64,
+}
+
+/// タイムを「MM:SS.s」形式の文字列に変換
+pub fn format_time(time_ms: f64) -> String {
+    let tenths = (time_ms.max(0.0) / 100.0) as u64;
+    let minutes = tenths / 600;
+    let seconds = (tenths / 10) % 60;
+    format!("{:02}:{:02}.{}", minutes, seconds, tenths % 10)
+}
+
+impl LeaderboardResource {
+    /// 空のリーダーボードを作成
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// サーバーの`leaderboard`メッセージの`entries`から記録を更新（未知の難易度は無視）
+    pub fn update(&mut self, entries: &HashMap<String, Vec<LeaderboardEntryInfo>>) {
+        self.entries.clear();
+        for (key, list) in entries {
+            let Some(difficulty) = Difficulty::from_key(key) else {
+                continue;
+            };
+
+            let mut records: Vec<LeaderboardEntry> = list.iter()
+                .map(|item| LeaderboardEntry { name: item.name.clone(), time_ms: item.time_ms })
+                .collect();
+            records.sort_by(|a, b| a.time_ms.total_cmp(&b.time_ms));
+
+            self.entries.insert(difficulty, records);
+        }
+
+        self.loaded = true;
+    }
+
+    /// サーバーから受信済みかどうか
+    pub fn is_loaded(&self) -> bool {
+        self.loaded
+    }
+
+    /// 難易度の記録を取得
+    pub fn entries(&self, difficulty: &Difficulty) -> &[LeaderboardEntry] {
+        self.entries.get(difficulty).map_or(&[], |records| records.as_slice())
+    }
+
+    /// 難易度のベストタイムを取得
+    pub fn best_time(&self, difficulty: &Difficulty) -> Option<f64> {
+        self.entries(difficulty).first().map(|entry| entry.time_ms)
+    }
+
+    /// パネルに表示する行を表示言語で作成
+    pub fn lines(&self, language: Language) -> Vec<String> {
+        let mut lines = Vec::new();
+
+        for difficulty in DIFFICULTY_ORDER.iter() {
+            lines.push(format!("【{}】", difficulty.label(language)));
+
+            let records = self.entries(difficulty);
+            if records.is_empty() {
+                lines.push(format!("  {}", text(language, "leaderboard.no_records")));
+            }
+            for (rank, entry) in records.iter().enumerate() {
+                lines.push(format!("  {}. {}  {}", rank + 1, entry.name, format_time(entry.time_ms)));
+            }
+        }
+
+        lines
+    }
+
+    /// パネルに表示する行数（見出し＋記録、記録が無い難易度は「記録なし」の1行）
+    fn line_count(&self) -> usize {
+        DIFFICULTY_ORDER.iter()
+            .map(|difficulty| 1 + self.entries(difficulty).len().max(1))
+            .sum()
+    }
+
+    /// パネルを開く（スクロール位置は先頭に戻す）
+    pub fn open(&mut self) {
+        self.visible = true;
+        self.scroll_offset = 0.0;
+    }
+
+    /// パネルを閉じる
+    pub fn close(&mut self) {
+        self.visible = false;
+    }
+
+    /// パネルを表示中かどうか
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// スクロール量を取得
+    pub fn scroll_offset(&self) -> f64 {
+        self.scroll_offset
+    }
+
+    /// パネルをスクロール（内容の範囲内に制限）
+    pub fn scroll_by(&mut self, delta: f64, viewport_height: f64) {
+        let content_height = self.line_count() as f64 * LEADERBOARD_LINE_HEIGHT;
+        let max_offset = (content_height - viewport_height).max(0.0);
+        self.scroll_offset = (self.scroll_offset + delta).clamp(0.0, max_offset);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, time_ms: f64) -> LeaderboardEntryInfo {
+        LeaderboardEntryInfo { name: name.to_string(), time_ms }
+    }
+
+    #[test]
+    fn test_update_from_entries() {
+        let mut leaderboard = LeaderboardResource::new();
+        assert!(!leaderboard.is_loaded());
+
+        leaderboard.update(&HashMap::from([
+            ("easy".to_string(), vec![entry("player_2", 45000.0), entry("player_1", 12300.0)]),
+            ("hard".to_string(), Vec::new()),
+            ("unknown".to_string(), vec![entry("x", 1.0)]),
+        ]));
+
+        assert!(leaderboard.is_loaded());
+        assert_eq!(leaderboard.entries(&Difficulty::Easy).len(), 2);
+        assert_eq!(leaderboard.best_time(&Difficulty::Easy), Some(12300.0));
+        assert_eq!(leaderboard.best_time(&Difficulty::Hard), None);
+        assert_eq!(format_time(12300.0), "00:12.3");
+
+        let lines = leaderboard.lines(Language::Japanese);
+        assert_eq!(lines[0], "【初級】");
+        assert_eq!(lines[1], "  1. player_1  00:12.3");
+        assert_eq!(lines[3], "【中級】");
+        assert_eq!(lines[4], "  記録なし");
+
+        let lines = leaderboard.lines(Language::English);
+        assert_eq!(lines[0], "【Easy】");
+        assert_eq!(lines[4], "  No records");
+    }
+
+    #[test]
+    fn test_scroll_is_clamped() {
+        let mut leaderboard = LeaderboardResource::new();
+        leaderboard.open();
+
+        // 内容がパネルに収まる場合はスクロールしない
+        leaderboard.scroll_by(100.0, 1000.0);
+        assert_eq!(leaderboard.scroll_offset(), 0.0);
+
+        let content_height = leaderboard.lines(Language::Japanese).len() as f64 * LEADERBOARD_LINE_HEIGHT;
+        leaderboard.scroll_by(10_000.0, 100.0);
+        assert_eq!(leaderboard.scroll_offset(), content_height - 100.0);
+        leaderboard.scroll_by(-10_000.0, 100.0);
+        assert_eq!(leaderboard.scroll_offset(), 0.0);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,123 @@
+/**
+ * ロビーリソース
+ *
+ * ゲーム開始前に接続中のプレイヤーと準備完了の状態を管理するリソース
+ * 状態はサーバーの`lobby_state`メッセージで丸ごと置き換える
+ */
+use super::localization::format_text;
+use super::settings::Language;
+use crate::protocol::LobbyPlayerInfo;
+
+/// ロビーに表示するプレイヤー
+#[derive(Debug, Clone, PartialEq)]
+pub struct LobbyPlayer {
+    /// プレイヤーID
+    pub id: String,
+    /// プレイヤー名（未設定の場合は空）
+    pub name: String,
+    /// 準備完了かどうか
+    pub ready: bool,
+}
+
+impl LobbyPlayer {
+    /// 表示名を取得（未設定の場合はIDから表示言語で作成）
+    pub fn display_name(&self, language: Language) -> String {
+        if self.name.is_empty() {
+            format_text(language, "player.default_name", &[&self.id])
+        } else {
+            self.name.clone()
+        }
+    }
+}
+
+/// ロビーリソース
+#[derive(Debug, Clone, Default)]
+pub struct LobbyResource {
+    /// ロビー中かどうか（falseならゲームが始まっている）
+    active: bool,
+    /// ホストのプレイヤーID
+    host_id: Option<String>,
+    /// 接続中のプレイヤー（参加順）
+    players: Vec<LobbyPlayer>,
+}
+
+impl LobbyResource {
+    /// 空のロビーを作成
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// サーバーの`lobby_state`メッセージの内容で状態を置き換える
+    pub fn update(&mut self, active: bool, host_id: Option<&str>, players: &[LobbyPlayerInfo]) {
+        self.active = active;
+        self.host_id = host_id.map(|id| id.to_string());
+        self.players = players.iter()
+            .map(|player| LobbyPlayer {
+                id: player.id.clone(),
+                name: player.name.clone(),
+                ready: player.ready,
+            })
+            .collect();
+    }
+
+    /// ロビー中かどうか
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// 接続中のプレイヤーを取得
+    pub fn players(&self) -> &[LobbyPlayer] {
+        &self.players
+    }
+
+    /// プレイヤーがホストかどうか
+    pub fn is_host(&self, player_id: &str) -> bool {
+        self.host_id.as_deref() == Some(player_id)
+    }
+
+    /// プレイヤーが準備完了かどうか
+    pub fn is_ready(&self, player_id: &str) -> bool {
+        self.players.iter().any(|player| player.id == player_id && player.ready)
+    }
+
+    /// 準備完了のプレイヤー数
+    pub fn ready_count(&self) -> usize {
+        self.players.iter().filter(|player| player.ready).count()
+    }
+
+    /// 全員が準備完了かどうか
+    pub fn all_ready(&self) -> bool {
+        !self.players.is_empty() && self.ready_count() == self.players.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lobby_player(id: &str, name: &str, ready: bool) -> LobbyPlayerInfo {
+        LobbyPlayerInfo { id: id.to_string(), name: name.to_string(), ready }
+    }
+
+    #[test]
+    fn test_update_from_lobby_state() {
+        let mut lobby = LobbyResource::new();
+        assert!(!lobby.is_active());
+
+        lobby.update(true, Some("player_1"), &[
+            lobby_player("player_1", "", true),
+            lobby_player("player_2", "太郎", false),
+        ]);
+
+        assert!(lobby.is_active());
+        assert_eq!(lobby.players().len(), 2);
+        assert!(lobby.is_host("player_1"));
+        assert!(!lobby.is_host("player_2"));
+        assert!(lobby.is_ready("player_1"));
+        assert_eq!(lobby.ready_count(), 1);
+        assert!(!lobby.all_ready());
+        assert_eq!(lobby.players()[0].display_name(Language::Japanese), "プレイヤー_player_1");
+        assert_eq!(lobby.players()[0].display_name(Language::English), "Player_player_1");
+        assert_eq!(lobby.players()[1].display_name(Language::English), "太郎");
+    }
+}
\ No newline at end of file
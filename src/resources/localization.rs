@@ -0,0 +1,355 @@
+/**
+ * ローカライズリソース
+ *
+ * 画面に表示する文言を、設定の表示言語に合わせて取得するための翻訳テーブル
+ * 文言はキーで引き、テーブルに無いキーはそのまま表示する
+ * 文言中の`{}`は`format`で渡した値に先頭から順に置き換える
+ */
+use std::fmt::Display;
+
+use super::settings::Language;
+
+/// 翻訳テーブル（キー, 日本語, 英語）
+const TEXTS: &[(&str, &str, &str)] = &[
+    // 共通のメニュー・ボタン
+    ("menu.settings", "設定", "Settings"),
+    ("menu.help", "ヘルプ", "Help"),
+    ("menu.leaderboard", "ランキング", "Leaderboard"),
+    ("menu.stats", "統計", "Stats"),
+    ("menu.history", "履歴", "History"),
+    ("menu.daily", "デイリー", "Daily"),
+    ("menu.offline", "オフライン", "Offline"),
+    ("title.streak", "🔥 {}日連続（最高{}日）", "🔥 {}-day streak (best {})"),
+    ("common.back", "戻る", "Back"),
+    ("player.default_name", "プレイヤー_{}", "Player_{}"),
+    ("bot.name", "🤖 ボット{}", "🤖 Bot {}"),
+    // タイトル画面
+    ("title.heading", "マルチプレイヤー\nマインスイーパー", "Multiplayer\nMinesweeper"),
+    ("title.start", "スタート", "Start"),
+    ("title.name_placeholder", "名前を入力", "Enter your name"),
+    ("connection.connected", "接続中", "Connected"),
+    ("connection.disconnected", "未接続", "Offline"),
+    // ゲーム画面のHUD
+    ("hud.mines", "残り地雷 {}", "Mines left {}"),
+    ("hud.latency", "遅延 {}ms", "Ping {}ms"),
+    ("hud.latency_unknown", "遅延 --", "Ping --"),
+    // 難易度
+    ("difficulty.heading", "難易度を選択", "Select difficulty"),
+    ("difficulty.easy", "初級", "Easy"),
+    ("difficulty.medium", "中級", "Medium"),
+    ("difficulty.hard", "上級", "Hard"),
+    ("difficulty.custom", "カスタム", "Custom"),
+    ("difficulty.description", "{} × {}・地雷 {}個", "{} × {}, {} mines"),
+    ("difficulty.custom_description", "サイズと地雷数を指定", "Choose size and mines"),
+    // カスタムボード画面
+    ("custom.heading", "カスタムボード", "Custom board"),
+    ("custom.width", "幅", "Width"),
+    ("custom.height", "高さ", "Height"),
+    ("custom.mines", "地雷", "Mines"),
+    ("custom.density", "地雷密度: {}%", "Mine density: {}%"),
+    ("custom.start", "開始", "Start"),
+    ("custom.error.size", "幅と高さは{}〜{}にしてください", "Width and height must be between {} and {}"),
+    ("custom.error.mines", "地雷は1〜{}個にしてください", "Mines must be between 1 and {}"),
+    // ルーム選択画面
+    ("room.heading", "ルームを選ぶ", "Choose a room"),
+    ("room.loading", "ルームを探しています…", "Looking for rooms…"),
+    ("room.empty", "参加できるルームはありません", "No rooms to join"),
+    ("room.detail", "{}人 ・ {}×{} 地雷{}", "{} players · {}×{}, {} mines"),
+    ("room.in_lobby", "募集中", "Open"),
+    ("room.playing", "対戦中", "Playing"),
+    ("room.create", "ルームを作成", "Create room"),
+    ("room.quick_play", "クイックプレイ", "Quick play"),
+    // ロビー画面
+    ("lobby.heading", "ロビー", "Lobby"),
+    ("lobby.ready_count", "準備完了 {} / {}", "Ready {} / {}"),
+    ("lobby.host_suffix", "（ホスト）", " (host)"),
+    ("lobby.ready", "準備完了", "Ready"),
+    ("lobby.waiting", "待機中", "Waiting"),
+    ("lobby.unready", "準備を取り消す", "Not ready"),
+    ("lobby.start_now", "今すぐ開始", "Start now"),
+    // ゲーム画面
+    ("game.reset", "リセット", "Reset"),
+    ("game.pause", "一時停止", "Pause"),
+    ("countdown.starting", "まもなく開始", "Get ready"),
+    ("pause.heading", "一時停止中", "Paused"),
+    ("pause.resume", "再開", "Resume"),
+    ("pause.quit", "タイトルへ戻る", "Back to title"),
+    ("pause.save", "ゲームを保存", "Save game"),
+    ("pause.load", "保存したゲームを読み込む", "Load saved game"),
+    ("toast.offline_saved", "💾 ゲームを保存しました", "💾 Game saved"),
+    ("toast.offline_loaded", "📂 保存したゲームを読み込みました", "📂 Saved game loaded"),
+    ("toast.offline_empty", "保存したゲームはありません", "No saved game"),
+    ("toast.offline_invalid", "保存データが壊れているため読み込めません", "The saved game is damaged and cannot be loaded"),
+    ("toast.offline_failed", "保存領域を使用できませんでした", "Could not access browser storage"),
+    ("leave.confirm", "ゲームの途中です。抜けてタイトルへ戻りますか？", "A game is in progress. Leave and go back to the title?"),
+    ("status.win", "勝利！", "You win!"),
+    ("status.game_over", "ゲームオーバー！", "Game over!"),
+    ("status.playing", "ゲーム中...", "Playing..."),
+    ("status.waiting", "ゲーム開始待ち...", "Waiting for the game to start..."),
+    ("toast.flag", "⚑ {}が旗を立てた", "⚑ {} placed a flag"),
+    ("toast.mine", "💥 {}が地雷を踏んだ", "💥 {} hit a mine"),
+    ("toast.join", "👋 {}が参加した", "👋 {} joined"),
+    ("toast.leave", "🚪 {}が退出した", "🚪 {} left"),
+    // プロフィールカード
+    ("profile.score", "このゲームの得点", "Score this game"),
+    ("profile.cells", "開いたセル", "Cells revealed"),
+    ("profile.flags", "立てた旗", "Flags placed"),
+    ("profile.ping", "遅延", "Ping"),
+    ("profile.ping_ms", "{} ms", "{} ms"),
+    ("toast.idle", "💤 {}は離席中", "💤 {} is away"),
+    ("toast.unlock", "🎨 テーマ「{}」が解放された", "🎨 Theme unlocked: {}"),
+    ("toast.streak_achievement", "🏅 実績解除: デイリーチャレンジ{}日連続クリア", "🏅 Achievement: daily challenge cleared {} days in a row"),
+    // ゲーム結果パネル
+    ("summary.win", "勝利！", "You win!"),
+    ("summary.game_over", "ゲームオーバー", "Game over"),
+    ("summary.difficulty", "難易度", "Difficulty"),
+    ("summary.time", "タイム", "Time"),
+    ("summary.score", "スコア", "Score"),
+    ("summary.three_bv", "3BV / 操作回数", "3BV / clicks"),
+    ("summary.efficiency", "効率", "Efficiency"),
+    ("summary.mines", "踏んだ地雷", "Mines hit"),
+    ("summary.best_updated", "{}（ベスト更新！）", "{} (new best!)"),
+    ("summary.best", "{}（ベスト {}）", "{} (best {})"),
+    ("summary.cells_by_player", "開いたセル数", "Cells revealed"),
+    ("summary.rematch", "再戦", "Rematch"),
+    ("summary.title", "タイトルへ", "Title"),
+    ("summary.share", "📋 共有", "📋 Share"),
+    ("toast.copied", "📋 結果をコピーした", "📋 Result copied"),
+    ("toast.report_copied", "📋 エラーの報告をコピーした", "📋 Error report copied"),
+    ("error.title", "エラーが発生しました", "Something went wrong"),
+    ("error.crashed", "ゲームが停止しました", "The game has stopped"),
+    ("error.restart_hint", "クリックするとページを再読み込みします", "Click to reload the page"),
+    ("error.reload", "🔄 状態を読み込み直す", "🔄 Reload state"),
+    ("error.copy_report", "📋 報告をコピー", "📋 Copy report"),
+    ("banner.offline", "オフラインです。接続が戻ると自動で同期します", "You are offline. The game will resync when the connection returns"),
+    ("toast.back_online", "🌐 オンラインに戻りました", "🌐 Back online"),
+    ("toast.reconnecting", "📡 接続が切れました。再接続しています…", "📡 Connection lost. Reconnecting…"),
+    ("toast.host_left", "📡 ホストとの接続が切れました", "📡 Lost connection to the host"),
+    ("toast.reconnected", "🔗 再接続しました", "🔗 Reconnected"),
+    ("compact.new_game", "🔄 新しいゲーム", "🔄 New game"),
+    ("toast.shared", "📤 結果を共有した", "📤 Result shared"),
+    ("toast.invite_copied", "🔗 招待リンクをコピーした", "🔗 Invite link copied"),
+    ("toast.seed_copied", "🌱 シードをコピーした", "🌱 Seed copied"),
+    ("toast.copy_failed", "クリップボードにコピーできませんでした", "Could not copy to the clipboard"),
+    ("toast.no_room", "ルームに参加していません", "You are not in a room"),
+    ("toast.no_seed", "シードはデイリーチャレンジでのみ使えます", "Seeds are only available in the daily challenge"),
+    ("rematch.voted", "投票済み {}/{}", "Voted {}/{}"),
+    ("rematch.votes", "再戦 {}/{}", "Rematch {}/{}"),
+    ("rematch.deadline", "全員が投票するか、{}秒後に再戦します", "Rematch starts when everyone votes or in {}s"),
+    // 統計画面
+    ("stats.difficulty", "難易度", "Level"),
+    ("stats.played", "プレイ", "Played"),
+    ("stats.wins", "勝利", "Wins"),
+    ("stats.losses", "敗北", "Losses"),
+    ("stats.win_rate", "勝率", "Win %"),
+    ("stats.best", "ベスト", "Best"),
+    ("stats.cells", "開いたセル", "Cells"),
+    ("stats.flags", "フラグ", "Flags"),
+    // 対戦履歴画面
+    ("history.win", "勝利", "Win"),
+    ("history.loss", "敗北", "Loss"),
+    ("history.practice", "練習", "Practice"),
+    ("history.players", "プレイヤー: {}", "Players: {}"),
+    ("history.empty", "まだ終了したゲームがありません", "No finished games yet"),
+    // ランキングパネル
+    ("leaderboard.heading", "ランキング（ベストタイム）", "Leaderboard (best times)"),
+    ("leaderboard.hint", "ホイールでスクロール / パネルの外をクリックで閉じる", "Scroll with the wheel / click outside to close"),
+    ("leaderboard.no_records", "記録なし", "No records"),
+    ("leaderboard.loading", "読み込み中...", "Loading..."),
+    ("leaderboard.offline", "サーバーに接続するとランキングを表示できます", "Connect to the server to see the leaderboard"),
+    // 設定画面
+    ("settings.theme", "テーマ", "Theme"),
+    ("settings.sound", "サウンド", "Sound"),
+    ("settings.volume", "効果音の音量", "Sound volume"),
+    ("settings.music_volume", "BGMの音量", "Music volume"),
+    ("settings.language", "言語", "Language"),
+    ("settings.reduced_motion", "アニメーションを減らす", "Reduce motion"),
+    ("settings.accessibility", "アクセシビリティ", "Accessibility"),
+    ("settings.open", "開く ›", "Open ›"),
+    // アクセシビリティ
+    ("accessibility.high_contrast", "ハイコントラスト", "High contrast"),
+    ("accessibility.text_scale", "文字の大きさ", "Text size"),
+    ("accessibility.palette", "数字の配色", "Number colors"),
+    ("accessibility.announcements", "読み上げ", "Screen reader announcements"),
+    ("palette.standard", "標準", "Standard"),
+    ("palette.red_green", "赤緑の区別に配慮", "Red-green safe"),
+    ("palette.blue_yellow", "青黄の区別に配慮", "Blue-yellow safe"),
+    ("settings.right_click", "右クリック", "Right click"),
+    ("settings.name", "プレイヤー名", "Player name"),
+    ("settings.cursor_color", "カーソルの色", "Cursor color"),
+    ("settings.on", "オン", "On"),
+    ("settings.off", "オフ", "Off"),
+    ("settings.unset", "（未設定）", "(not set)"),
+    ("settings.toast_flags", "通知：旗を立てた", "Notify: flags placed"),
+    ("settings.toast_mines", "通知：地雷を踏んだ", "Notify: mines hit"),
+    ("settings.toast_players", "通知：参加・退出", "Notify: players joining/leaving"),
+    ("settings.ui_mode", "メニューの表示", "Menu display"),
+    ("ui_mode.canvas", "キャンバス", "Canvas"),
+    ("ui_mode.dom", "HTML", "HTML"),
+    ("settings.browser_notifications", "バックグラウンドでの通知", "Notify in background"),
+    ("notification.title", "マルチプレイ マインスイーパー", "Multiplayer Minesweeper"),
+    ("notification.game_starting", "まもなくゲームが始まります", "The game is about to start"),
+    ("notification.win", "ゲームクリア！", "You win!"),
+    ("notification.lose", "ゲームオーバー", "Game over"),
+    ("settings.name_prompt", "プレイヤー名を入力してください:", "Enter your player name:"),
+    ("theme.dark", "ダーク", "Dark"),
+    ("theme.light", "ライト", "Light"),
+    // カーソルの色
+    ("cursor_color.blue", "青", "Blue"),
+    ("cursor_color.red", "赤", "Red"),
+    ("cursor_color.green", "緑", "Green"),
+    ("cursor_color.purple", "紫", "Purple"),
+    ("cursor_color.teal", "青緑", "Teal"),
+    ("cursor_color.pink", "ピンク", "Pink"),
+    ("cursor_color.brown", "茶", "Brown"),
+    ("cursor_color.navy", "紺", "Navy"),
+    ("theme.forest", "森", "Forest"),
+    ("theme.ocean", "海", "Ocean"),
+    ("theme.sunset", "夕焼け", "Sunset"),
+    ("theme.gold", "金", "Gold"),
+    // テーマの解放条件
+    ("unlock.wins", "🔒 {}回勝利すると解放", "🔒 Win {} games to unlock"),
+    ("unlock.cells", "🔒 セルを{}個開くと解放", "🔒 Reveal {} cells to unlock"),
+    ("unlock.score", "🔒 累計スコア{}で解放", "🔒 Reach a total score of {} to unlock"),
+    ("unlock.win_on", "🔒 {}で勝利すると解放", "🔒 Win on {} to unlock"),
+    ("right_click.flag", "フラグ", "Flag"),
+    ("right_click.reveal", "セルを開く", "Reveal"),
+    // ヘルプ画面
+    ("help.mouse", "マウス操作", "Mouse"),
+    ("help.mouse.left", "左クリック：セルを開く", "Left click: reveal a cell"),
+    ("help.mouse.right", "右クリック：フラグを立てる・外す（設定で入れ替え可能）", "Right click: toggle a flag (can be swapped in Settings)"),
+    ("help.mouse.wheel", "ホイール：ランキングをスクロール", "Wheel: scroll the leaderboard"),
+    ("help.mouse.follow", "プレイヤー一覧をクリック：カーソルを追いかける（もう一度で解除）", "Click the player list: follow that cursor (click again to stop)"),
+    ("help.keyboard", "キーボード操作", "Keyboard"),
+    ("help.keyboard.escape", "Esc：一時停止メニューを開く・閉じる", "Esc: open or close the pause menu"),
+    ("help.keyboard.emote", "E：エモートを選ぶ（1〜4キーで送信）", "E: open the emote picker (send with keys 1-4)"),
+    ("help.keyboard.fullscreen", "F：全画面表示を切り替える", "F: toggle fullscreen"),
+    ("help.touch", "タッチ操作", "Touch"),
+    ("help.touch.tap", "タップ：セルを開く", "Tap: reveal a cell"),
+    ("help.touch.long_press", "長押し：フラグを立てる・外す", "Long press: toggle a flag"),
+    ("help.rules", "ルール", "Rules"),
+    ("help.rules.goal", "地雷以外の全てのセルを開くと勝利です", "Reveal every cell that is not a mine to win"),
+    ("help.rules.numbers", "数字は周囲8マスにある地雷の数を表します", "A number shows how many mines touch that cell"),
+    ("help.rules.mine", "地雷を開くとゲームオーバー（全員で同じボードを遊びます）", "Reveal a mine and the game is over for everyone on the board"),
+];
+
+/// ヘルプ画面の構成（見出しのキー, 各行のキー）
+pub const HELP_SECTIONS: &[(&str, &[&str])] = &[
+    ("help.mouse", &["help.mouse.left", "help.mouse.right", "help.mouse.wheel", "help.mouse.follow"]),
+    ("help.keyboard", &["help.keyboard.escape", "help.keyboard.emote", "help.keyboard.fullscreen"]),
+    ("help.touch", &["help.touch.tap", "help.touch.long_press"]),
+    ("help.rules", &["help.rules.goal", "help.rules.numbers", "help.rules.mine"]),
+];
+
+/// キーに対応する文言を表示言語で取得（テーブルに無いキーはそのまま返す）
+pub fn text(language: Language, key: &'static str) -> &'static str {
+    TEXTS
+        .iter()
+        .find(|(text_key, _, _)| *text_key == key)
+        .map_or(key, |(_, japanese, english)| match language {
+            Language::Japanese => japanese,
+            Language::English => english,
+        })
+}
+
+/// キーに対応する文言の`{}`を値で置き換えて取得（値が足りない場合は`{}`のまま残す）
+pub fn format_text(language: Language, key: &'static str, args: &[&dyn Display]) -> String {
+    let mut result = String::new();
+    let mut rest = text(language, key);
+
+    for arg in args {
+        match rest.split_once("{}") {
+            Some((before, after)) => {
+                result.push_str(before);
+                result.push_str(&arg.to_string());
+                rest = after;
+            },
+            None => break,
+        }
+    }
+    result.push_str(rest);
+
+    result
+}
+
+/// 表示言語を保持し、画面の文言を取得するリソース
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LocalizationResource {
+    /// 表示言語
+    language: Language,
+}
+
+impl Default for LocalizationResource {
+    fn default() -> Self {
+        Self::new(Language::Japanese)
+    }
+}
+
+impl LocalizationResource {
+    /// 表示言語を指定して作成
+    pub fn new(language: Language) -> Self {
+        Self { language }
+    }
+
+    /// 表示言語を取得
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    /// 表示言語を変更
+    pub fn set_language(&mut self, language: Language) {
+        self.language = language;
+    }
+
+    /// キーに対応する文言を取得
+    pub fn text(&self, key: &'static str) -> &'static str {
+        text(self.language, key)
+    }
+
+    /// キーに対応する文言の`{}`を値で置き換えて取得
+    pub fn format(&self, key: &'static str, args: &[&dyn Display]) -> String {
+        format_text(self.language, key, args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_lookup() {
+        assert_eq!(text(Language::Japanese, "menu.help"), "ヘルプ");
+        assert_eq!(text(Language::English, "menu.help"), "Help");
+        assert_eq!(text(Language::English, "missing.key"), "missing.key");
+    }
+
+    #[test]
+    fn test_help_sections_are_translated() {
+        for (heading, lines) in HELP_SECTIONS {
+            for key in std::iter::once(heading).chain(lines.iter()) {
+                assert!(TEXTS.iter().any(|(text_key, _, _)| text_key == key), "missing key: {}", key);
+            }
+        }
+    }
+
+    #[test]
+    fn test_format_fills_placeholders_in_order() {
+        let mut localization = LocalizationResource::new(Language::Japanese);
+        assert_eq!(localization.format("lobby.ready_count", &[&1, &3]), "準備完了 1 / 3");
+
+        localization.set_language(Language::English);
+        assert_eq!(localization.language(), Language::English);
+        assert_eq!(localization.format("difficulty.description", &[&9, &9, &10]), "9 × 9, 10 mines");
+        assert_eq!(localization.format("player.default_name", &[&"p1"]), "Player_p1");
+
+        // 値が足りない場合は残りの`{}`をそのまま表示する
+        assert_eq!(localization.format("rematch.voted", &[&2]), "Voted 2/{}");
+    }
+
+    #[test]
+    fn test_keys_are_unique() {
+        for (index, (key, _, _)) in TEXTS.iter().enumerate() {
+            assert!(TEXTS[index + 1..].iter().all(|(other, _, _)| other != key), "duplicate key: {}", key);
+        }
+    }
+}
\ No newline at end of file
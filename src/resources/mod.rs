@@ -7,24 +7,88 @@
 
 // サブモジュールをエクスポート
 mod board_config;
-mod game_state;
 mod render_state;
 mod network_state;
+mod reconnect;
 mod core_game;
 mod time;
 mod game_config;
+mod custom_board;
 mod player_state;
+mod diagnostics;
+mod telemetry;
+mod rng;
+mod board;
+mod settings;
+mod accessibility;
+mod color;
+mod cursor_color;
+mod avatar;
+mod leaderboard;
+mod stats;
+mod streak;
+mod session;
+mod unlocks;
+mod history;
+mod offline_save;
+mod emote;
+mod countdown;
+mod lobby;
+mod room_state;
+mod network_stats;
+mod traffic_log;
+mod game_summary;
+mod result_sequence;
+mod bot;
+mod rematch;
+mod toast;
+mod camera;
+mod hud;
+mod localization;
+mod audio;
 mod resource_manager;
 
 // 既存のリソースを再エクスポート（名前衝突を避けるためにリネーム）
 pub use board_config::BoardConfig as OldBoardConfig;
-pub use game_state::{GameState, GamePhase as OldGamePhase};
 pub use render_state::RenderState;
 pub use network_state::NetworkState;
+pub use reconnect::{ReconnectResource, RECONNECT_BASE_DELAY_MS, RECONNECT_MAX_DELAY_MS, MAX_RECONNECT_ATTEMPTS};
 
 // 新しいECSリソースを公開
-pub use core_game::{CoreGameResource, GamePhase};
+pub use core_game::{CoreGameResource, GamePhase, PhaseChanged};
 pub use time::TimeResource;
-pub use game_config::{GameConfigResource, BoardConfig, Difficulty};
-pub use player_state::{PlayerStateResource, Player as EcsPlayer};
-pub use resource_manager::{ResourceManager, ResourceBatch, ResourceBatchMut}; 
\ No newline at end of file
+pub use game_config::{GameConfigResource, BoardConfig, BoardConfigError, Difficulty, MIN_BOARD_SIZE, MAX_BOARD_SIZE};
+pub use custom_board::{CustomBoardResource, CustomBoardField};
+pub use player_state::{PlayerStateResource, Player as EcsPlayer, CURSOR_INTERPOLATION_MS};
+pub use diagnostics::DiagnosticsResource;
+pub use telemetry::{TelemetryResource, TelemetryReport, TELEMETRY_INTERVAL_MS};
+pub use rng::RngResource;
+pub use board::{BoardConfigResource, BoardStateResource, Resource};
+pub use audio::{AudioResource, SoundEffect, MusicTrack};
+pub use leaderboard::{LeaderboardResource, LeaderboardEntry, LEADERBOARD_LINE_HEIGHT};
+pub use countdown::CountdownResource;
+pub use game_summary::{GameSummaryResource, PlayerContribution};
+pub use result_sequence::{ResultSequenceResource, ResultPhase, RESULT_SWEEP_MS, RESULT_SLIDE_MS};
+pub use bot::{BotResource, BotStrategy, BotMove, BotAction, BotBoardView, Bot, BOT_MOVE_INTERVAL_MS, choose_move, find_certain_move};
+pub use rematch::RematchResource;
+pub use camera::{CameraResource, FOLLOW_ZOOM};
+pub use hud::{HudResource, HudWidget};
+pub use toast::{ToastResource, ToastCategory, Toast, TOAST_DISPLAY_MS};
+pub use localization::{LocalizationResource, HELP_SECTIONS};
+pub use lobby::{LobbyResource, LobbyPlayer};
+pub use room_state::{RoomStateResource, RoomSelectButton, ROOM_LIST_REFRESH_MS, MAX_LISTED_ROOMS};
+pub use network_stats::{NetworkStatsResource, HEARTBEAT_INTERVAL_MS, LATENCY_SAMPLE_COUNT};
+pub use traffic_log::{TrafficLogResource, TrafficEntry, TrafficDirection, TRAFFIC_LOG_CAPACITY};
+pub use emote::{Emote, EmoteResource, EMOTE_DISPLAY_MS, EMOTE_OPTION_RADIUS};
+pub use history::{HistoryResource, GameRecord, HISTORY_STORAGE_KEY, MAX_HISTORY};
+pub use offline_save::{OfflineSave, OfflineMove, OFFLINE_SAVE_KEY};
+pub use accessibility::{AccessibilityResource, AccessibilityItem, TextScale, ColorPalette, ACCESSIBILITY_STORAGE_KEY};
+pub use color::Color;
+pub use avatar::{AVATARS, is_known_avatar, next_avatar};
+pub use cursor_color::{CURSOR_COLORS, DEFAULT_CURSOR_COLOR, is_readable_cursor_color, resolve_cursor_color};
+pub use unlocks::{ThemeUnlock, is_theme_unlocked, newly_unlocked_themes};
+pub use stats::{StatsResource, DifficultyStats, STATS_DIFFICULTIES, STATS_STORAGE_KEY};
+pub use streak::{StreakResource, STREAK_STORAGE_KEY, STREAK_ACHIEVEMENTS, DAILY_BOARD_WIDTH, DAILY_BOARD_HEIGHT, day_number, daily_challenge_mines};
+pub use session::{SessionResource, SESSION_STORAGE_KEY, SESSION_SAVE_INTERVAL_MS, session_storage_key};
+pub use settings::{SettingsResource, SettingsItem, Theme, Language, RightClickAction, UiMode, SETTINGS_STORAGE_KEY, MAX_PLAYER_NAME_LENGTH, sanitize_player_name};
+pub use resource_manager::{ResourceManager, ResourceBatch, ResourceBatchMut, ResMut}; 
\ No newline at end of file
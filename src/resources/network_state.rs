@@ -7,6 +7,13 @@ use wasm_bindgen::prelude::*;
 use js_sys::{Function, Object, Reflect, JSON};
 use web_sys::{WebSocket, MessageEvent, CloseEvent};
 use std::collections::HashMap;
+use std::rc::Rc;
+use crate::utils::now_ms;
+use crate::protocol::{ClientMessage, ServerMessage};
+use crate::network::{parse_server_url, with_query_param, parse_server_frame, Frame, NetworkEvent, NetworkEventQueue};
+use super::reconnect::ReconnectResource;
+use crate::send_queue::SendQueue;
+use crate::logger::{log_error, log_info};
 
 /// メッセージの種類
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
@@ -29,10 +36,27 @@ pub enum MessageType {
     GameReset,
     /// エラー
     Error,
-    /// 不明
+    /// 上記以外
     Unknown,
 }
 
+impl MessageType {
+    /// 変換済みのサーバーのメッセージの種類を取得
+    pub fn of(message: &ServerMessage) -> Self {
+        match message {
+            ServerMessage::Init { .. } => MessageType::Init,
+            ServerMessage::PlayerJoined { .. } => MessageType::PlayerJoined,
+            ServerMessage::PlayerLeft { .. } => MessageType::PlayerLeft,
+            ServerMessage::PlayerMoved { .. } => MessageType::PlayerMoved,
+            ServerMessage::CellsRevealed { .. } => MessageType::CellsRevealed,
+            ServerMessage::GameOver { .. } => MessageType::GameOver,
+            ServerMessage::FlagToggled { .. } => MessageType::FlagToggled,
+            ServerMessage::GameReset { .. } => MessageType::GameReset,
+            _ => MessageType::Unknown,
+        }
+    }
+}
+
 /// メッセージハンドラ
 pub type MessageHandler = js_sys::Function;
 
@@ -49,14 +73,20 @@ pub struct NetworkState {
     pub last_error: Option<String>,
     /// サーバーURL
     pub server_url: String,
+    /// ボードの更新をバイナリ形式で受け取るかどうか
+    pub binary_protocol: bool,
+    /// WebSocketのコールバックが積み、`process_events`で取り出す受信キュー
+    events: NetworkEventQueue,
     /// メッセージハンドラー
     message_handlers: HashMap<MessageType, Vec<MessageHandler>>,
     /// 送信キュー（未接続時に蓄積する）
     message_queue: Vec<String>,
-    /// 再接続試行回数
-    reconnect_attempts: u32,
-    /// 最後に送信した位置更新時間
-    pub last_position_update: f64,
+    /// 切断時の再接続の状態
+    reconnect: ReconnectResource,
+    /// 受信したフラグの切り替え（セルのインデックスと切り替え後の状態）
+    flag_updates: Vec<(usize, bool)>,
+    /// フレームの最後に`flush_outbox`でまとめて送るゲームの操作
+    outbox: SendQueue,
 }
 
 impl Default for NetworkState {
@@ -67,10 +97,13 @@ impl Default for NetworkState {
             local_player_id: None,
             last_error: None,
             server_url: "wss://minesweeper-server.example.com".to_string(),
+            binary_protocol: false,
+            events: NetworkEventQueue::default(),
             message_handlers: HashMap::new(),
             message_queue: Vec::new(),
-            reconnect_attempts: 0,
-            last_position_update: 0.0,
+            reconnect: ReconnectResource::new(),
+            flag_updates: Vec::new(),
+            outbox: SendQueue::new(),
         }
     }
 }
@@ -84,6 +117,21 @@ impl NetworkState {
         }
     }
     
+    /// 接続先のサーバーを変更（接続中であれば新しいサーバーに接続し直す）
+    pub fn set_server_url(&mut self, server_url: &str) -> Result<(), JsValue> {
+        self.server_url = parse_server_url(server_url)?;
+        self.reconnect.cancel();
+        if let Some(ws) = self.websocket.take() {
+            // 古い接続の切断で再接続が予約されないよう、先にハンドラを外す
+            ws.set_onclose(None);
+            ws.close()?;
+            self.events.borrow_mut().clear();
+            self.is_connected = false;
+            self.connect()?;
+        }
+        Ok(())
+    }
+    
     /// WebSocketサーバーに接続
     pub fn connect(&mut self) -> Result<(), JsValue> {
         // すでに接続中なら何もしない
@@ -91,81 +139,56 @@ impl NetworkState {
             return Ok(());
         }
         
-        // WebSocketを作成
-        let ws = WebSocket::new(&self.server_url)?;
+        // WebSocketを作成（バイナリ形式を使う場合は接続URLで要求する）
+        let server_url = if self.binary_protocol {
+            with_query_param(&self.server_url, "binary", "1")
+        } else {
+            self.server_url.clone()
+        };
+        let ws = WebSocket::new(&server_url)?;
         
         // バイナリ型を指定
         ws.set_binary_type(web_sys::BinaryType::Arraybuffer);
         
-        // 接続時の処理
+        // 各ハンドラは出来事を受信キューに積むだけで、反映は`process_events`で行う
+        let events = Rc::clone(&self.events);
         let onopen_callback = Closure::wrap(Box::new(move || {
-            web_sys::console::log_1(&"WebSocket接続しました！".into());
+            events.borrow_mut().push_back(NetworkEvent::Opened);
         }) as Box<dyn FnMut()>);
         ws.set_onopen(Some(onopen_callback.as_ref().unchecked_ref()));
         onopen_callback.forget();
         
-        // メッセージ受信時の処理
-        let this_clone = self as *mut NetworkState;
+        // メッセージ受信時の処理（テキストもバイナリも`ServerMessage`に変換して積む）
+        let events = Rc::clone(&self.events);
         let onmessage_callback = Closure::wrap(Box::new(move |e: MessageEvent| {
-            let network_state = unsafe { &mut *this_clone };
-            
-            // テキストメッセージを処理
-            if let Ok(txt) = e.data().dyn_into::<js_sys::JsString>() {
-                let txt_str = String::from(txt);
-                match network_state.handle_message(&txt_str) {
-                    Ok(_) => {},
-                    Err(err) => {
-                        web_sys::console::error_1(&format!("メッセージ処理エラー: {:?}", err).into());
-                        network_state.last_error = Some(format!("メッセージ処理エラー: {:?}", err));
-                    }
-                }
+            let data = e.data();
+            let frame = if let Some(txt) = data.dyn_ref::<js_sys::JsString>() {
+                Frame::Text(String::from(txt))
+            } else if let Some(buffer) = data.dyn_ref::<js_sys::ArrayBuffer>() {
+                Frame::Binary(js_sys::Uint8Array::new(buffer).to_vec())
+            } else {
+                return;
+            };
+            if let Some(message) = parse_server_frame(&frame) {
+                events.borrow_mut().push_back(NetworkEvent::Message(message));
             }
         }) as Box<dyn FnMut(MessageEvent)>);
         ws.set_onmessage(Some(onmessage_callback.as_ref().unchecked_ref()));
         onmessage_callback.forget();
         
         // エラー発生時の処理
-        let this_clone = self as *mut NetworkState;
+        let events = Rc::clone(&self.events);
         let onerror_callback = Closure::wrap(Box::new(move |_e: web_sys::Event| {
-            let network_state = unsafe { &mut *this_clone };
-            web_sys::console::error_1(&"WebSocketエラーが発生しました".into());
-            network_state.is_connected = false;
-            network_state.last_error = Some("WebSocketエラーが発生しました".to_string());
+            events.borrow_mut().push_back(NetworkEvent::Error);
         }) as Box<dyn FnMut(web_sys::Event)>);
         ws.set_onerror(Some(onerror_callback.as_ref().unchecked_ref()));
         onerror_callback.forget();
         
         // 切断時の処理
-        let this_clone = self as *mut NetworkState;
+        let events = Rc::clone(&self.events);
         let onclose_callback = Closure::wrap(Box::new(move |e: CloseEvent| {
-            let network_state = unsafe { &mut *this_clone };
-            web_sys::console::log_1(&format!("WebSocket切断: コード={}, 理由={}", e.code(), e.reason()).into());
-            network_state.is_connected = false;
-            network_state.websocket = None;
-            
-            // 自動再接続
-            if network_state.reconnect_attempts < 3 {
-                network_state.reconnect_attempts += 1;
-                web_sys::console::log_1(&format!("再接続を試みます ({}/3)...", network_state.reconnect_attempts).into());
-                
-                // 1秒後に再接続
-                let this_clone = network_state as *mut NetworkState;
-                let reconnect_callback = Closure::once(Box::new(move || {
-                    let network_state = unsafe { &mut *this_clone };
-                    if let Err(err) = network_state.connect() {
-                        web_sys::console::error_1(&format!("再接続エラー: {:?}", err).into());
-                    }
-                }) as Box<dyn FnOnce()>);
-                
-                web_sys::window()
-                    .unwrap()
-                    .set_timeout_with_callback_and_timeout_and_arguments_0(
-                        reconnect_callback.as_ref().unchecked_ref(),
-                        1000,
-                    )
-                    .unwrap();
-                reconnect_callback.forget();
-            }
+            log_info!("WebSocket切断: コード={}, 理由={}", e.code(), e.reason());
+            events.borrow_mut().push_back(NetworkEvent::Closed);
         }) as Box<dyn FnMut(CloseEvent)>);
         ws.set_onclose(Some(onclose_callback.as_ref().unchecked_ref()));
         onclose_callback.forget();
@@ -173,7 +196,6 @@ impl NetworkState {
         // WebSocketを保存
         self.websocket = Some(ws);
         self.is_connected = true;
-        self.reconnect_attempts = 0;
         
         // 接続確立後、キューに溜まったメッセージを送信
         if !self.message_queue.is_empty() {
@@ -215,45 +237,78 @@ impl NetworkState {
         }
     }
     
-    /// メッセージを処理
-    fn handle_message(&mut self, message: &str) -> Result<(), JsValue> {
-        // JSONをパース
-        let json = JSON::parse(message)?;
+    /// 受信キューに溜まった出来事を届いた順に反映し、予約した再接続の時刻を過ぎていれば接続し直す（毎フレーム呼び出す）
+    pub fn process_events(&mut self) {
+        let events: Vec<NetworkEvent> = self.events.borrow_mut().drain(..).collect();
+        for event in events {
+            match event {
+                NetworkEvent::Opened => self.handle_open(),
+                NetworkEvent::Message(message) => {
+                    if let Err(err) = self.handle_message(message) {
+                        log_error!("メッセージ処理エラー: {:?}", err);
+                        self.last_error = Some(format!("メッセージ処理エラー: {:?}", err));
+                    }
+                },
+                NetworkEvent::Error => {
+                    log_error!("WebSocketエラーが発生しました");
+                    self.is_connected = false;
+                    self.last_error = Some("WebSocketエラーが発生しました".to_string());
+                },
+                NetworkEvent::Closed => self.handle_close(),
+            }
+        }
         
-        // メッセージタイプを取得
-        let msg_type = if let Some(t) = Reflect::get(&json, &"type".into())
-            .ok()
-            .and_then(|v| v.as_string()) {
-            match t.as_str() {
-                "init" => MessageType::Init,
-                "player_joined" => MessageType::PlayerJoined,
-                "player_left" => MessageType::PlayerLeft,
-                "player_moved" => MessageType::PlayerMoved,
-                "cells_revealed" => MessageType::CellsRevealed,
-                "game_over" => MessageType::GameOver,
-                "flag_toggled" => MessageType::FlagToggled,
-                "game_reset" => MessageType::GameReset,
-                "error" => MessageType::Error,
-                _ => MessageType::Unknown,
+        if self.reconnect.take_due(now_ms()) {
+            if let Err(err) = self.connect() {
+                log_error!("再接続エラー: {:?}", err);
+            }
+        }
+    }
+    
+    /// 接続時の処理（再接続であれば切断中に変わった状態を要求し直す）
+    fn handle_open(&mut self) {
+        log_info!("WebSocket接続しました！");
+        if self.reconnect.finish() {
+            let request = serde_json::to_string(&ClientMessage::SyncRequest).unwrap_or_default();
+            if let Err(err) = self.send_raw(&request) {
+                log_error!("状態の再要求エラー: {:?}", err);
             }
+        }
+    }
+    
+    /// 切断時の処理（待ち時間を倍に延ばしながら自動で再接続する）
+    fn handle_close(&mut self) {
+        self.is_connected = false;
+        self.websocket = None;
+        
+        if let Some(delay) = self.reconnect.schedule(None, now_ms()) {
+            log_info!("{}ms後に再接続を試みます ({}回目)...", delay, self.reconnect.attempts());
         } else {
-            MessageType::Unknown
-        };
+            log_error!("再接続を諦めました");
+            self.reconnect.cancel();
+            self.last_error = Some("再接続できませんでした".to_string());
+        }
+    }
+    
+    /// メッセージを処理（`ServerMessage`に変換できないメッセージは受信時に捨てている）
+    fn handle_message(&mut self, server_message: ServerMessage) -> Result<(), JsValue> {
+        let msg_type = MessageType::of(&server_message);
         
-        // 登録されたハンドラを呼び出す
+        // 登録されたハンドラを呼び出す（JSのハンドラには変換済みのメッセージをJSONにして渡す）
         if let Some(handlers) = self.message_handlers.get(&msg_type) {
+            let message = serde_json::to_string(&server_message).map_err(|e| JsValue::from_str(&e.to_string()))?;
+            let json = JSON::parse(&message)?;
             for handler in handlers {
                 handler.call1(&JsValue::NULL, &json)?;
             }
         }
         
-        // 初期化メッセージの場合はプレイヤーIDを保存
-        if matches!(msg_type, MessageType::Init) {
-            if let Some(player_id) = Reflect::get(&json, &"playerId".into())
-                .ok()
-                .and_then(|v| v.as_string()) {
-                self.local_player_id = Some(player_id);
-            }
+        match server_message {
+            // 初期化メッセージの場合はプレイヤーIDを保存
+            ServerMessage::Init { player_id, .. } => self.local_player_id = Some(player_id),
+            // 自分の切り替えもサーバーから届いたものをボードに反映する
+            ServerMessage::FlagToggled { index, flagged, .. } => self.flag_updates.push((index, flagged)),
+            _ => {},
         }
         
         Ok(())
@@ -267,46 +322,44 @@ impl NetworkState {
             .push(handler);
     }
     
-    /// プレイヤーの位置を送信
+    /// プレイヤーの位置を送信キューに積む（送る間隔は送信キューで調整する）
     pub fn send_position_update(&mut self, x: f64, y: f64) -> Result<(), JsValue> {
-        // 現在時刻を取得
-        let now = js_sys::Date::now();
-        
-        // 前回の更新から一定時間（100ms）経過していれば送信
-        if now - self.last_position_update > 100.0 {
-            self.last_position_update = now;
-            
-            // 位置情報をオブジェクトにする
-            let obj = Object::new();
-            Reflect::set(&obj, &"x".into(), &x.into())?;
-            Reflect::set(&obj, &"y".into(), &y.into())?;
-            
-            // 送信
-            self.send_message("move", &obj)
-        } else {
-            Ok(())
-        }
+        self.outbox.push(ClientMessage::PlayerMove { x, y });
+        Ok(())
     }
     
-    /// セルを開く操作を送信
+    /// セルを開く操作を送信キューに積む
     pub fn send_reveal_cell(&mut self, index: usize) -> Result<(), JsValue> {
-        let obj = Object::new();
-        Reflect::set(&obj, &"index".into(), &(index as u32).into())?;
-        
-        self.send_message("reveal", &obj)
+        self.outbox.push(ClientMessage::RevealCell { index });
+        Ok(())
     }
     
-    /// フラグを切り替える操作を送信
+    /// フラグを切り替える操作を送信キューに積む
     pub fn send_toggle_flag(&mut self, index: usize) -> Result<(), JsValue> {
-        let obj = Object::new();
-        Reflect::set(&obj, &"index".into(), &(index as u32).into())?;
-        
-        self.send_message("toggleFlag", &obj)
+        self.outbox.push(ClientMessage::ToggleFlag { index });
+        Ok(())
+    }
+    
+    /// 送信キューのうち送れる操作をまとめて送信（フレームの最後に呼び出す、未接続の間は溜めておく）
+    pub fn flush_outbox(&mut self) -> Result<(), JsValue> {
+        let Some(ws) = self.websocket.as_ref().filter(|_| self.is_connected) else {
+            return Ok(());
+        };
+        self.outbox.flush(now_ms(), |message| {
+            let json = serde_json::to_string(message).map_err(|e| JsValue::from_str(&e.to_string()))?;
+            ws.send_with_str(&json)
+        })
+    }
+    
+    /// 受信したフラグの切り替えを届いた順に取り出す
+    pub fn take_flag_updates(&mut self) -> Vec<(usize, bool)> {
+        std::mem::take(&mut self.flag_updates)
     }
     
-    /// ゲームのリセットを送信
+    /// ゲームのリセットを送信キューに積む
     pub fn send_reset_game(&mut self) -> Result<(), JsValue> {
-        self.send_message("resetGame", &JsValue::NULL)
+        self.outbox.push(ClientMessage::ResetGame);
+        Ok(())
     }
     
     /// 接続を閉じる
@@ -317,6 +370,7 @@ impl NetworkState {
         
         self.is_connected = false;
         self.websocket = None;
+        self.outbox.clear();
         
         Ok(())
     }
@@ -330,4 +384,50 @@ impl NetworkState {
     pub fn set_local_player_id(&mut self, id: String) {
         self.local_player_id = Some(id);
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_close_event_schedules_reconnect() {
+        let mut network = NetworkState::new("ws://localhost:8080");
+        network.is_connected = true;
+        network.events.borrow_mut().push_back(NetworkEvent::Closed);
+
+        // 再接続は待ち時間の後に`process_events`の中で行う
+        network.process_events();
+        assert!(!network.is_connected);
+        assert_eq!(network.reconnect.attempts(), 1);
+        assert!(network.events.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_flag_toggled_is_kept_for_the_board() {
+        let mut network = NetworkState::new("ws://localhost:8080");
+        network.events.borrow_mut().push_back(NetworkEvent::Message(ServerMessage::FlagToggled {
+            player_id: "player_2".to_string(),
+            revision: 3,
+            index: 5,
+            flagged: true,
+        }));
+
+        network.process_events();
+        assert_eq!(network.take_flag_updates(), vec![(5, true)]);
+        assert!(network.take_flag_updates().is_empty());
+    }
+
+    #[test]
+    fn test_operations_wait_in_outbox_until_connected() {
+        let mut network = NetworkState::new("ws://localhost:8080");
+        network.send_position_update(1.0, 2.0).unwrap();
+        network.send_position_update(3.0, 4.0).unwrap();
+        network.send_toggle_flag(5).unwrap();
+        assert_eq!(network.outbox.len(), 2);
+
+        // 接続していない間は送らずに溜めておく
+        network.flush_outbox().unwrap();
+        assert_eq!(network.outbox.len(), 2);
+    }
 } 
\ No newline at end of file
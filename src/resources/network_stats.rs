@@ -0,0 +1,144 @@
+/**
+ * 通信状況リソース
+ *
+ * ハートビートの往復時間から通信の遅延を測り、ゲーム画面の隅に表示するための値を管理するリソース
+ * 応答が返らないまま時間が経った場合は、その待ち時間も遅延として扱う
+ */
+use std::collections::VecDeque;
+use super::color::Color;
+
+/// ハートビートを送る間隔（ミリ秒）
+pub const HEARTBEAT_INTERVAL_MS: f64 = 2_000.0;
+
+/// 遅延の平均に使う往復時間の数
+pub const LATENCY_SAMPLE_COUNT: usize = 5;
+
+/// 快適とみなす遅延の上限（ミリ秒）
+const GOOD_LATENCY_MS: f64 = 100.0;
+
+/// 操作に支障が出始める遅延の上限（ミリ秒、これを超えると遅いとみなす）
+const FAIR_LATENCY_MS: f64 = 250.0;
+
+/// 通信状況リソース
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NetworkStatsResource {
+    /// 最近の往復時間（ミリ秒、古い順）
+    samples: VecDeque<f64>,
+    /// 最後にハートビートを送った時刻（ミリ秒）
+    last_sent: Option<f64>,
+    /// 応答を待っている最も古いハートビートの送信時刻（ミリ秒）
+    outstanding: Option<f64>,
+}
+
+impl NetworkStatsResource {
+    /// まだ測っていない状態を作成
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// ハートビートを送る時刻になっていれば送信時刻を記録してtrueを返す
+    pub fn heartbeat_due(&mut self, now: f64) -> bool {
+        let due = self.last_sent.is_none_or(|last| now - last >= HEARTBEAT_INTERVAL_MS);
+        if due {
+            self.last_sent = Some(now);
+            self.outstanding.get_or_insert(now);
+        }
+        due
+    }
+
+    /// サーバーから返ったハートビートの往復時間を記録（未来の時刻などの不正な値は無視する）
+    pub fn record_ack(&mut self, sent_at: f64, now: f64) {
+        let round_trip = now - sent_at;
+        if !round_trip.is_finite() || round_trip < 0.0 {
+            return;
+        }
+        
+        if self.samples.len() == LATENCY_SAMPLE_COUNT {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(round_trip);
+        
+        // これより前に送ったハートビートの応答はもう待たない
+        if self.outstanding.is_some_and(|oldest| sent_at >= oldest) {
+            self.outstanding = None;
+        }
+    }
+
+    /// 現在の遅延（ミリ秒、まだ測っていなければNone）
+    /// 
+    /// 最近の往復時間の平均と、応答を1間隔以上待っている場合はその待ち時間の大きい方
+    pub fn latency_ms(&self, now: f64) -> Option<f64> {
+        let average = (!self.samples.is_empty())
+            .then(|| self.samples.iter().sum::<f64>() / self.samples.len() as f64);
+        let waiting = self.outstanding
+            .map(|sent| now - sent)
+            .filter(|&waiting| waiting >= HEARTBEAT_INTERVAL_MS);
+        match (average, waiting) {
+            (Some(average), Some(waiting)) => Some(average.max(waiting)),
+            (average, waiting) => average.or(waiting),
+        }
+    }
+
+    /// 遅延の表示色（快適なら緑、遅くなるほど黄色・赤、未計測なら灰色）
+    pub fn latency_color(latency_ms: Option<f64>) -> Color {
+        match latency_ms {
+            None => Color(0x9E9E9E),
+            Some(latency) if latency <= GOOD_LATENCY_MS => Color(0x4CAF50),
+            Some(latency) if latency <= FAIR_LATENCY_MS => Color(0xFFC107),
+            Some(_) => Color(0xFF0000),
+        }
+    }
+
+    /// 接続し直したときなどに測った値を捨てる
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latency_from_heartbeats() {
+        let mut stats = NetworkStatsResource::new();
+        assert!(stats.heartbeat_due(0.0));
+        assert!(!stats.heartbeat_due(HEARTBEAT_INTERVAL_MS - 1.0));
+        assert_eq!(stats.latency_ms(10.0), None);
+        
+        // 往復時間の平均を遅延とし、古い値から捨てる
+        stats.record_ack(0.0, 80.0);
+        stats.record_ack(2_000.0, 2_040.0);
+        assert_eq!(stats.latency_ms(2_040.0), Some(60.0));
+        for sent in 0..LATENCY_SAMPLE_COUNT {
+            stats.record_ack(sent as f64, sent as f64 + 200.0);
+        }
+        assert_eq!(stats.latency_ms(10_000.0), Some(200.0));
+        assert_eq!(NetworkStatsResource::latency_color(stats.latency_ms(10_000.0)), Color(0xFFC107));
+        
+        // 未来の送信時刻は無視する
+        stats.record_ack(20_000.0, 10_000.0);
+        assert_eq!(stats.latency_ms(10_000.0), Some(200.0));
+    }
+
+    #[test]
+    fn test_unanswered_heartbeat_counts_as_lag() {
+        let mut stats = NetworkStatsResource::new();
+        assert!(stats.heartbeat_due(0.0));
+        stats.record_ack(0.0, 50.0);
+        
+        // 応答が返らない間は待ち時間が遅延として増えていく
+        assert!(stats.heartbeat_due(2_000.0));
+        assert!(stats.heartbeat_due(4_000.0));
+        assert_eq!(stats.latency_ms(3_000.0), Some(50.0));
+        assert_eq!(stats.latency_ms(6_500.0), Some(4_500.0));
+        assert_eq!(NetworkStatsResource::latency_color(stats.latency_ms(6_500.0)), Color(0xFF0000));
+        
+        // 後から送ったハートビートの応答で待ち時間は消える
+        stats.record_ack(4_000.0, 6_600.0);
+        assert_eq!(stats.latency_ms(6_600.0), Some((50.0 + 2_600.0) / 2.0));
+        
+        stats.clear();
+        assert_eq!(stats.latency_ms(7_000.0), None);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,108 @@
+/**
+ * オフラインゲームの保存データ
+ *
+ * 練習やデイリーチャレンジなどサーバーに接続しないゲームを途中で保存するためのデータ
+ * 地雷の配置と操作の記録（リプレイ）を持ち、読み込むときは操作を順に適用し直して盤面を復元する
+ * localStorageには収まらない大きさになるためIndexedDBに保存する
+ */
+use serde::{Serialize, Deserialize};
+use super::game_config::Difficulty;
+
+/// IndexedDBに保存する際のキー（保存枠は1つ）
+pub const OFFLINE_SAVE_KEY: &str = "offline_game";
+
+/// オフラインゲームでの1回の操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "index", rename_all = "snake_case")]
+pub enum OfflineMove {
+    /// セルを開いた
+    Reveal(usize),
+    /// フラグを切り替えた
+    Flag(usize),
+}
+
+/// オフラインゲームの保存データ
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OfflineSave {
+    /// ボードの幅
+    pub width: usize,
+    /// ボードの高さ
+    pub height: usize,
+    /// 地雷のセルのインデックス
+    pub mines: Vec<usize>,
+    /// 難易度のキー（`Difficulty::key`）
+    pub difficulty: String,
+    /// デイリーチャレンジの場合は日番号
+    pub daily_challenge_day: Option<i64>,
+    /// 開始からの操作の記録
+    pub moves: Vec<OfflineMove>,
+    /// 保存した時点の経過時間（ミリ秒）
+    pub elapsed_ms: f64,
+    /// 保存した時刻（UNIXエポックからのミリ秒）
+    pub saved_at: f64,
+}
+
+impl OfflineSave {
+    /// 保存されたJSONから復元（読み込めない場合はNone）
+    pub fn from_json(json: &str) -> Option<Self> {
+        serde_json::from_str(json)
+            .ok()
+            .filter(|save: &OfflineSave| save.is_valid())
+    }
+
+    /// 保存用のJSONに変換
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    /// 難易度を取得（不明なキーはカスタム扱い）
+    pub fn difficulty(&self) -> Difficulty {
+        Difficulty::from_key(&self.difficulty).unwrap_or(Difficulty::Custom)
+    }
+
+    /// 盤面の大きさと地雷・操作のインデックスが矛盾していないかどうか
+    fn is_valid(&self) -> bool {
+        let cells = self.width * self.height;
+        let in_board = |index: usize| index < cells;
+
+        cells > 0
+            && self.mines.iter().all(|&mine| in_board(mine))
+            && self.moves.iter().all(|&step| match step {
+                OfflineMove::Reveal(index) | OfflineMove::Flag(index) => in_board(index),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> OfflineSave {
+        OfflineSave {
+            width: 3,
+            height: 3,
+            mines: vec![0, 8],
+            difficulty: "custom".to_string(),
+            daily_challenge_day: Some(20_000),
+            moves: vec![OfflineMove::Reveal(4), OfflineMove::Flag(0)],
+            elapsed_ms: 12_500.0,
+            saved_at: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_offline_save_round_trip() {
+        let save = sample();
+        let json = save.to_json();
+        assert!(json.contains(r#"{"kind":"reveal","index":4}"#));
+        assert_eq!(OfflineSave::from_json(&json), Some(save));
+    }
+
+    #[test]
+    fn test_offline_save_rejects_out_of_board_indices() {
+        let mut save = sample();
+        save.moves.push(OfflineMove::Reveal(9));
+        assert_eq!(OfflineSave::from_json(&save.to_json()), None);
+        assert_eq!(OfflineSave::from_json("not json"), None);
+    }
+}
\ No newline at end of file
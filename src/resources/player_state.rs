@@ -6,10 +6,15 @@
 use std::collections::HashMap;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
-use js_sys::{Date, JSON, Object, Reflect};
+use js_sys::{JSON, Object, Reflect};
+use crate::utils::now_ms;
 use serde::{Serialize, Deserialize};
 use wasm_bindgen::JsValue;
 use crate::models::Player as GamePlayer;
+use super::settings::sanitize_player_name;
+
+/// 他プレイヤーのカーソルを目標位置まで動かす時間（ミリ秒、位置の送信間隔と同じ）
+pub const CURSOR_INTERPOLATION_MS: f64 = 100.0;
 
 /// マウスの状態
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -37,6 +42,25 @@ pub struct Player {
     pub active: bool,
     /// 最終更新時刻
     pub last_update: f64,
+    /// 補間の開始位置のX座標
+    pub from_x: f64,
+    /// 補間の開始位置のY座標
+    pub from_y: f64,
+    /// 補間の目標位置のX座標（最後に受け取った位置）
+    pub target_x: f64,
+    /// 補間の目標位置のY座標（最後に受け取った位置）
+    pub target_y: f64,
+    /// 目標位置を受け取った時刻
+    pub target_time: f64,
+}
+
+impl Player {
+    /// 開始位置から目標位置まで、受け取ってからの経過時間に合わせて線形補間した位置に動かす
+    pub fn interpolate(&mut self, now: f64) {
+        let t = ((now - self.target_time) / CURSOR_INTERPOLATION_MS).clamp(0.0, 1.0);
+        self.x = self.from_x + (self.target_x - self.from_x) * t;
+        self.y = self.from_y + (self.target_y - self.from_y) * t;
+    }
 }
 
 /// プレイヤー状態リソース
@@ -59,6 +83,10 @@ pub struct PlayerStateResource {
     pub last_key_pressed: Option<String>,
     /// アクティブなプレイヤー数
     pub active_player_count: usize,
+    /// ローカルプレイヤーの表示名（参加メッセージで送信する）
+    pub player_name: String,
+    /// 現在時刻（ミリ秒、カーソルの補間に使う）
+    pub now: f64,
 }
 
 impl Default for PlayerStateResource {
@@ -79,6 +107,22 @@ impl PlayerStateResource {
             last_position_update: 0.0,
             last_key_pressed: None,
             active_player_count: 0,
+            player_name: String::new(),
+            now: 0.0,
+        }
+    }
+
+    /// ローカルプレイヤーの表示名を設定（前後の空白を除き、最大文字数で切り詰める）
+    pub fn set_player_name(&mut self, name: &str) {
+        self.player_name = sanitize_player_name(name);
+    }
+
+    /// 表示名を取得（未設定の場合はIDから作成）
+    pub fn display_name(&self) -> String {
+        match (&self.local_player_id, self.player_name.is_empty()) {
+            (_, false) => self.player_name.clone(),
+            (Some(id), true) => format!("プレイヤー_{}", id),
+            (None, true) => String::new(),
         }
     }
 
@@ -107,13 +151,19 @@ impl PlayerStateResource {
 
     /// プレイヤーを追加
     pub fn add_player(&mut self, id: String, x: f64, y: f64, color: String) -> &Player {
+        let now = now_ms();
         let player = Player {
             id: id.clone(),
             x,
             y,
             color,
             active: true,
-            last_update: Date::now(),
+            last_update: now,
+            from_x: x,
+            from_y: y,
+            target_x: x,
+            target_y: y,
+            target_time: now,
         };
 
         self.players.insert(id.clone(), player);
@@ -142,15 +192,48 @@ impl PlayerStateResource {
         self.active_player_count = self.players.values().filter(|p| p.active).count();
     }
 
-    /// プレイヤーの位置を更新
+    /// プレイヤーの位置を更新（補間せずにその位置へ移す）
     pub fn update_player_position(&mut self, id: &str, x: f64, y: f64) {
         if let Some(player) = self.players.get_mut(id) {
             player.x = x;
             player.y = y;
-            player.last_update = Date::now();
+            player.from_x = x;
+            player.from_y = y;
+            player.target_x = x;
+            player.target_y = y;
+            player.last_update = now_ms();
+        }
+    }
+
+    /// プレイヤーの目標位置を設定（今の表示位置から補間で動かす）
+    pub fn set_player_target(&mut self, id: &str, x: f64, y: f64, now: f64) {
+        if let Some(player) = self.players.get_mut(id) {
+            if player.target_x == x && player.target_y == y {
+                return;
+            }
+            player.from_x = player.x;
+            player.from_y = player.y;
+            player.target_x = x;
+            player.target_y = y;
+            player.target_time = now;
+            player.last_update = now;
+        }
+    }
+
+    /// 全プレイヤーの位置を現在時刻に合わせて補間
+    pub fn interpolate_players(&mut self) {
+        let now = self.now;
+        for player in self.players.values_mut() {
+            player.interpolate(now);
         }
     }
 
+    /// 指定したID以外のプレイヤーを削除
+    pub fn retain_players(&mut self, ids: &[&str]) {
+        self.players.retain(|id, _| ids.contains(&id.as_str()));
+        self.update_active_count();
+    }
+
     /// ローカルプレイヤーの位置を更新
     pub fn update_local_player_position(&mut self, x: f64, y: f64) {
         if let Some(id) = self.local_player_id.clone() {
@@ -215,6 +298,7 @@ impl PlayerStateResource {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::resources::MAX_PLAYER_NAME_LENGTH;
     
     #[test]
     fn test_add_and_remove_player() {
@@ -240,6 +324,17 @@ mod tests {
         assert!(player_state.has_player("p2"));
     }
     
+    #[test]
+    fn test_player_name() {
+        let mut player_state = PlayerStateResource::new();
+        player_state.set_local_player_id("player_3".to_string());
+        assert_eq!(player_state.display_name(), "プレイヤー_player_3");
+        
+        player_state.set_player_name("  とても長いプレイヤーの名前を入力してみる  ");
+        assert_eq!(player_state.player_name.chars().count(), MAX_PLAYER_NAME_LENGTH);
+        assert_eq!(player_state.display_name(), player_state.player_name);
+    }
+    
     #[test]
     fn test_update_player_position() {
         let mut player_state = PlayerStateResource::new();
@@ -256,6 +351,37 @@ mod tests {
         assert_eq!(player.y, 25.0);
     }
     
+    #[test]
+    fn test_cursor_interpolation() {
+        let mut player_state = PlayerStateResource::new();
+        player_state.add_player("p1".to_string(), 0.0, 0.0, "#ff0000".to_string());
+        player_state.set_player_target("p1", 100.0, 40.0, 1000.0);
+        
+        // 受け取った直後は元の位置、半分の時間で中間、それ以降は目標位置で止まる
+        player_state.now = 1000.0;
+        player_state.interpolate_players();
+        assert_eq!((player_state.players["p1"].x, player_state.players["p1"].y), (0.0, 0.0));
+        player_state.now = 1000.0 + CURSOR_INTERPOLATION_MS / 2.0;
+        player_state.interpolate_players();
+        assert_eq!((player_state.players["p1"].x, player_state.players["p1"].y), (50.0, 20.0));
+        player_state.now = 1000.0 + CURSOR_INTERPOLATION_MS * 3.0;
+        player_state.interpolate_players();
+        assert_eq!((player_state.players["p1"].x, player_state.players["p1"].y), (100.0, 40.0));
+        
+        // 途中で次の位置が届いたら、その時点の表示位置から動き始める
+        player_state.set_player_target("p1", 200.0, 40.0, 2000.0);
+        player_state.now = 2000.0 + CURSOR_INTERPOLATION_MS / 2.0;
+        player_state.interpolate_players();
+        player_state.set_player_target("p1", 300.0, 40.0, player_state.now);
+        player_state.interpolate_players();
+        assert_eq!(player_state.players["p1"].x, 150.0);
+        
+        // 補間しない位置の更新はすぐに反映される
+        player_state.update_player_position("p1", 10.0, 10.0);
+        player_state.interpolate_players();
+        assert_eq!((player_state.players["p1"].x, player_state.players["p1"].y), (10.0, 10.0));
+    }
+    
     #[test]
     fn test_local_player() {
         let mut player_state = PlayerStateResource::new();
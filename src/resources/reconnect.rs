@@ -0,0 +1,136 @@
+/**
+ * 再接続リソース
+ *
+ * 対戦中にWebSocketが切断されたとき、待ち時間を倍に延ばしながら接続し直すための状態を管理するリソース
+ * 接続し直せたら現在の状態を要求し直し、切断中に古くなったボードとプレイヤーを置き換える
+ */
+/// 最初の再接続までの待ち時間（ミリ秒）
+pub const RECONNECT_BASE_DELAY_MS: f64 = 500.0;
+
+/// 再接続の待ち時間の上限（ミリ秒）
+pub const RECONNECT_MAX_DELAY_MS: f64 = 30_000.0;
+
+/// 諦めるまでに続けて試す再接続の回数
+pub const MAX_RECONNECT_ATTEMPTS: u32 = 10;
+
+/// 再接続の状態
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReconnectResource {
+    /// 続けて試した再接続の回数（接続し直せたら0に戻す）
+    attempts: u32,
+    /// 次に接続し直す時刻（ローカルの時計、ミリ秒）
+    next_attempt_at: Option<f64>,
+    /// 切断前のプレイヤーID（再接続のたびにサーバーに引き継ぎを要求する）
+    player_id: Option<String>,
+}
+
+impl ReconnectResource {
+    /// 再接続していない状態を作成
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 試行回数ごとの待ち時間（ミリ秒）を取得（倍に延ばし、上限で止める）
+    pub fn delay_ms(attempt: u32) -> f64 {
+        (RECONNECT_BASE_DELAY_MS * 2f64.powi(attempt.min(31) as i32)).min(RECONNECT_MAX_DELAY_MS)
+    }
+
+    /// 切断を検知したときに次の再接続を予約
+    ///
+    /// `player_id`は最初の切断時のものだけを覚えておく
+    /// 回数の上限に達した場合はNoneを返す（呼び出し側で`cancel`して諦める）
+    pub fn schedule(&mut self, player_id: Option<String>, local_now: f64) -> Option<f64> {
+        if self.player_id.is_none() {
+            self.player_id = player_id;
+        }
+        if self.attempts >= MAX_RECONNECT_ATTEMPTS {
+            return None;
+        }
+
+        let delay = Self::delay_ms(self.attempts);
+        self.attempts += 1;
+        self.next_attempt_at = Some(local_now + delay);
+        Some(delay)
+    }
+
+    /// 予約した再接続の時刻を過ぎていれば予約を取り消してtrueを返す
+    pub fn take_due(&mut self, local_now: f64) -> bool {
+        match self.next_attempt_at {
+            Some(at) if local_now >= at => {
+                self.next_attempt_at = None;
+                true
+            },
+            _ => false,
+        }
+    }
+
+    /// 再接続の途中かどうか（切断を検知してから接続し直せるまで）
+    pub fn is_reconnecting(&self) -> bool {
+        self.attempts > 0
+    }
+
+    /// 続けて試した再接続の回数
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    /// サーバーに引き継ぎを要求するプレイヤーID
+    pub fn player_id(&self) -> Option<&str> {
+        self.player_id.as_deref()
+    }
+
+    /// 接続できたときに状態を戻す（再接続だった場合はtrue）
+    pub fn finish(&mut self) -> bool {
+        let reconnected = self.is_reconnecting();
+        self.cancel();
+        reconnected
+    }
+
+    /// 再接続をやめる（タイトル画面に戻ったときなど）
+    pub fn cancel(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_doubles_until_limit() {
+        assert_eq!(ReconnectResource::delay_ms(0), 500.0);
+        assert_eq!(ReconnectResource::delay_ms(1), 1_000.0);
+        assert_eq!(ReconnectResource::delay_ms(3), 4_000.0);
+        assert_eq!(ReconnectResource::delay_ms(6), RECONNECT_MAX_DELAY_MS);
+        assert_eq!(ReconnectResource::delay_ms(u32::MAX), RECONNECT_MAX_DELAY_MS);
+
+        let mut reconnect = ReconnectResource::new();
+        assert!(!reconnect.is_reconnecting());
+        assert_eq!(reconnect.schedule(Some("player_1".to_string()), 0.0), Some(500.0));
+        assert!(!reconnect.take_due(499.0));
+        assert!(reconnect.take_due(500.0));
+        assert!(!reconnect.take_due(600.0));
+
+        // 2回目以降の切断ではIDが無くても最初のIDを引き継ぐ
+        assert_eq!(reconnect.schedule(None, 600.0), Some(1_000.0));
+        assert_eq!(reconnect.player_id(), Some("player_1"));
+        assert_eq!(reconnect.attempts(), 2);
+
+        assert!(reconnect.finish());
+        assert!(!reconnect.finish());
+        assert_eq!(reconnect.player_id(), None);
+    }
+
+    #[test]
+    fn test_gives_up_after_max_attempts() {
+        let mut reconnect = ReconnectResource::new();
+        for _ in 0..MAX_RECONNECT_ATTEMPTS {
+            assert!(reconnect.schedule(Some("player_1".to_string()), 0.0).is_some());
+        }
+        assert_eq!(reconnect.schedule(None, 0.0), None);
+        assert_eq!(reconnect.player_id(), Some("player_1"));
+
+        reconnect.cancel();
+        assert!(!reconnect.is_reconnecting());
+    }
+}
\ No newline at end of file
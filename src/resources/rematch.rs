@@ -0,0 +1,79 @@
+/**
+ * 再戦投票リソース
+ *
+ * ゲーム終了後の再戦投票の状況（投票したプレイヤーと締め切り）を管理するリソース
+ * 締め切りはサーバーの時計で届くため、受信時に求めた時計のずれで補正する
+ */
+/// 再戦投票の状況
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RematchResource {
+    /// 投票したプレイヤーのID
+    votes: Vec<String>,
+    /// 接続中のプレイヤー数
+    total: usize,
+    /// 時間切れでリセットされる時刻（サーバーの時計、ミリ秒）
+    deadline: Option<f64>,
+    /// サーバーの時計とローカルの時計の差（サーバー − ローカル、ミリ秒）
+    clock_offset: f64,
+}
+
+impl RematchResource {
+    /// 投票のない状態を作成
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// サーバーの`rematch_state`メッセージの内容で状況を更新
+    pub fn update(&mut self, votes: &[String], total: usize, deadline: Option<f64>, server_time: f64, local_now: f64) {
+        self.votes = votes.to_vec();
+        self.total = total;
+        self.deadline = deadline;
+        self.clock_offset = server_time - local_now;
+    }
+
+    /// 投票を取り消す（ゲームがリセットされたとき）
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// プレイヤーが投票済みかどうか
+    pub fn has_voted(&self, player_id: &str) -> bool {
+        self.votes.iter().any(|id| id == player_id)
+    }
+
+    /// 投票数と接続中のプレイヤー数
+    pub fn progress(&self) -> (usize, usize) {
+        (self.votes.len(), self.total)
+    }
+
+    /// 時間切れまでの残り秒数（投票がなければNone）
+    pub fn remaining_seconds(&self, local_now: f64) -> Option<u32> {
+        self.deadline.map(|deadline| {
+            let remaining = deadline - (local_now + self.clock_offset);
+            (remaining.max(0.0) / 1000.0).ceil() as u32
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rematch_progress() {
+        let mut rematch = RematchResource::new();
+        assert_eq!(rematch.remaining_seconds(0.0), None);
+
+        // ローカルの時計がサーバーより1秒進んでいる
+        rematch.update(&["player_1".to_string()], 3, Some(20_000.0), 5_000.0, 6_000.0);
+
+        assert!(rematch.has_voted("player_1"));
+        assert!(!rematch.has_voted("player_2"));
+        assert_eq!(rematch.progress(), (1, 3));
+        assert_eq!(rematch.remaining_seconds(6_000.0), Some(15));
+        assert_eq!(rematch.remaining_seconds(30_000.0), Some(0));
+
+        rematch.clear();
+        assert_eq!(rematch.progress(), (0, 0));
+    }
+}
\ No newline at end of file
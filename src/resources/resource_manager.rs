@@ -6,15 +6,69 @@
 use std::any::{Any, TypeId};
 use std::collections::HashMap;
 use std::fmt::Debug;
+use std::ops::{Deref, DerefMut};
 use std::cell::{RefCell, Ref, RefMut};
 use std::marker::PhantomData;
 
+/// 遅延実行されるリソース操作
+type DeferredCommand = Box<dyn FnOnce(&mut ResourceManager)>;
+
 /// リソースマネージャー
 /// 型安全にさまざまなリソースを保持・管理する
-#[derive(Default)]
 pub struct ResourceManager {
     /// リソースを型IDで管理するマップ
     resources: HashMap<TypeId, Box<dyn Any>>,
+    /// フェーズ終了後に適用するリソース操作のキュー
+    deferred: RefCell<Vec<DeferredCommand>>,
+    /// リソースの型IDごとの最後に変更されたティック
+    changed: HashMap<TypeId, u64>,
+    /// 現在のティック（システムを1つ実行するごとに進む）
+    change_tick: u64,
+    /// これより後のティックの変更を変更済みとみなす（実行中のシステムでは前回の実行時のティック）
+    last_seen_tick: u64,
+}
+
+/// 書き込み時にリソースを変更済みとしてマークする可変参照ラッパー
+/// 
+/// `ResourceManager::write`で取得する。読み取りだけなら変更扱いにならない
+pub struct ResMut<'a, T: 'static> {
+    /// リソースへの可変参照
+    resource: &'a mut T,
+    /// リソースごとの変更されたティック
+    changed: &'a mut HashMap<TypeId, u64>,
+    /// 書き込んだときに記録するティック
+    tick: u64,
+}
+
+impl<T: 'static> Deref for ResMut<'_, T> {
+    type Target = T;
+    
+    fn deref(&self) -> &T {
+        self.resource
+    }
+}
+
+impl<T: 'static> DerefMut for ResMut<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.changed.insert(TypeId::of::<T>(), self.tick);
+        self.resource
+    }
+}
+
+// Debugトレイトの手動実装（リソースはDebugを要求しないため件数のみ表示）
+impl Debug for ResourceManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResourceManager")
+            .field("resource_count", &self.resources.len())
+            .field("deferred_count", &self.deferred.borrow().len())
+            .finish()
+    }
+}
+
+impl Default for ResourceManager {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl ResourceManager {
@@ -22,13 +76,41 @@ impl ResourceManager {
     pub fn new() -> Self {
         Self {
             resources: HashMap::new(),
+            deferred: RefCell::new(Vec::new()),
+            changed: HashMap::new(),
+            change_tick: 1,
+            last_seen_tick: 0,
         }
     }
     
-    /// リソースを追加または置換
+    /// リソースを追加または置換（変更済みとしてマークされる）
     pub fn insert<T: 'static>(&mut self, resource: T) {
         let type_id = TypeId::of::<T>();
         self.resources.insert(type_id, Box::new(resource));
+        self.changed.insert(type_id, self.change_tick);
+    }
+    
+    /// リソースの追加を予約する
+    /// 
+    /// システム実行中など可変参照を取れない場面から呼び出せる。
+    /// 実際の追加は`apply_deferred`が呼ばれた時点で行われる
+    pub fn insert_deferred<T: 'static>(&self, resource: T) {
+        self.deferred
+            .borrow_mut()
+            .push(Box::new(move |manager: &mut ResourceManager| manager.insert(resource)));
+    }
+    
+    /// 予約されたリソース操作を登録順に適用する
+    pub fn apply_deferred(&mut self) {
+        let commands = std::mem::take(self.deferred.get_mut());
+        for command in commands {
+            command(self);
+        }
+    }
+    
+    /// 予約済みのリソース操作があるかどうか
+    pub fn has_deferred(&self) -> bool {
+        !self.deferred.borrow().is_empty()
     }
     
     /// リソースの参照を取得
@@ -38,11 +120,64 @@ impl ResourceManager {
             .and_then(|boxed| boxed.downcast_ref::<T>())
     }
     
-    /// リソースの可変参照を取得
+    /// リソースの可変参照を取得（書き込むかどうかに関わらず変更済みとしてマークされる）
+    /// 
+    /// 読み取りだけの場合に変更扱いにしたくなければ`get`か`write`を使う
     pub fn get_mut<T: 'static>(&mut self) -> Option<&mut T> {
         let type_id = TypeId::of::<T>();
-        self.resources.get_mut(&type_id)
-            .and_then(|boxed| boxed.downcast_mut::<T>())
+        let resource = self.resources.get_mut(&type_id)?.downcast_mut::<T>()?;
+        self.changed.insert(type_id, self.change_tick);
+        Some(resource)
+    }
+    
+    /// 書き込み時に変更を記録する可変参照を取得
+    pub fn write<T: 'static>(&mut self) -> Option<ResMut<'_, T>> {
+        let type_id = TypeId::of::<T>();
+        let resource = self.resources.get_mut(&type_id)?.downcast_mut::<T>()?;
+        Some(ResMut {
+            resource,
+            changed: &mut self.changed,
+            tick: self.change_tick,
+        })
+    }
+    
+    /// リソースを明示的に変更済みとしてマーク
+    pub fn set_changed<T: 'static>(&mut self) {
+        if self.contains::<T>() {
+            self.changed.insert(TypeId::of::<T>(), self.change_tick);
+        }
+    }
+    
+    /// リソースが変更されたかどうか
+    /// 
+    /// システムの中では、そのシステムの前回の実行より後に変更された場合にtrueになる
+    /// （後のステージや前のフレームの終わりでの変更も、次に実行されたときに見える）。
+    /// システムの外では、最後の`clear_changes`より後に変更された場合にtrueになる
+    pub fn is_changed<T: 'static>(&self) -> bool {
+        self.changed.get(&TypeId::of::<T>()).is_some_and(|&tick| tick > self.last_seen_tick)
+    }
+    
+    /// システムの外から見た変更を全て確認済みにする
+    pub fn clear_changes(&mut self) {
+        self.last_seen_tick = self.change_tick;
+        self.change_tick += 1;
+    }
+    
+    /// システムを1つ実行する
+    /// 
+    /// 実行中は`is_changed`の基準をそのシステムの前回の実行時のティックにする。
+    /// 戻り値は今回の実行のティックで、次回の実行時に`last_run_tick`として渡す
+    pub fn run_system(&mut self, last_run_tick: u64, run: impl FnOnce(&mut ResourceManager)) -> u64 {
+        let outside_seen_tick = self.last_seen_tick;
+        self.change_tick += 1;
+        let this_run_tick = self.change_tick;
+        self.last_seen_tick = last_run_tick;
+        
+        run(self);
+        
+        self.last_seen_tick = outside_seen_tick;
+        self.change_tick += 1;
+        this_run_tick
     }
     
     /// 指定した型のリソースが存在するかどうか
@@ -54,14 +189,17 @@ impl ResourceManager {
     /// リソースを削除して返す
     pub fn remove<T: 'static>(&mut self) -> Option<T> {
         let type_id = TypeId::of::<T>();
+        self.changed.remove(&type_id);
         self.resources.remove(&type_id)
             .and_then(|boxed| boxed.downcast().ok())
             .map(|boxed| *boxed)
     }
     
-    /// 全リソースをクリア
+    /// 全リソースと予約済みのリソース操作をクリア
     pub fn clear(&mut self) {
         self.resources.clear();
+        self.deferred.get_mut().clear();
+        self.changed.clear();
     }
     
     /// リソースの数を取得
@@ -126,6 +264,11 @@ impl<'a> ResourceBatch<'a> {
     pub fn read<T: 'static>(&self) -> Option<&'a T> {
         self.manager.get::<T>()
     }
+    
+    /// リソースの追加を予約
+    pub fn insert_deferred<T: 'static>(&self, resource: T) {
+        self.manager.insert_deferred(resource);
+    }
 }
 
 /// 複数のリソースへの書き込みアクセスを提供するヘルパー
@@ -153,6 +296,11 @@ impl<'a> ResourceBatchMut<'a> {
             Some(&mut *ptr)
         }
     }
+    
+    /// リソースの追加を予約
+    pub fn insert_deferred<T: 'static>(&self, resource: T) {
+        self.manager.insert_deferred(resource);
+    }
 }
 
 #[cfg(test)]
@@ -264,4 +412,63 @@ mod tests {
         assert_eq!(manager.get::<TestResource>().unwrap().value, 100);
         assert_eq!(manager.get::<OtherResource>().unwrap().name, "Updated");
     }
-} 
\ No newline at end of file
+    
+    #[test]
+    fn test_deferred_insert() {
+        let mut manager = ResourceManager::new();
+        manager.insert(TestResource { value: 42 });
+        
+        // バッチ処理中にリソースの追加を予約
+        manager.batch_mut(|mut batch| {
+            if let Some(test) = batch.write::<TestResource>() {
+                test.value = 100;
+            }
+            batch.insert_deferred(OtherResource { name: "Deferred".to_string() });
+        });
+        
+        // 適用前は存在しない
+        assert!(!manager.contains::<OtherResource>());
+        assert!(manager.has_deferred());
+        
+        manager.apply_deferred();
+        
+        assert!(!manager.has_deferred());
+        assert_eq!(manager.get::<OtherResource>().unwrap().name, "Deferred");
+        assert_eq!(manager.get::<TestResource>().unwrap().value, 100);
+        
+        // クリアすると予約も捨てられる
+        manager.insert_deferred(OtherResource { name: "Stale".to_string() });
+        manager.clear();
+        manager.apply_deferred();
+        assert!(manager.is_empty());
+    }
+    
+    #[test]
+    fn test_change_detection() {
+        let mut manager = ResourceManager::new();
+        manager.insert(TestResource { value: 1 });
+        manager.insert(OtherResource { name: "Other".to_string() });
+        assert!(manager.is_changed::<TestResource>());
+        
+        manager.clear_changes();
+        assert!(!manager.is_changed::<TestResource>());
+        
+        // 読み取りだけでは変更扱いにならない
+        {
+            let resource = manager.write::<TestResource>().unwrap();
+            assert_eq!(resource.value, 1);
+        }
+        assert!(!manager.is_changed::<TestResource>());
+        
+        // 書き込みで変更済みになる
+        manager.write::<TestResource>().unwrap().value = 2;
+        assert!(manager.is_changed::<TestResource>());
+        assert!(!manager.is_changed::<OtherResource>());
+        assert_eq!(manager.get::<TestResource>().unwrap().value, 2);
+        
+        // 存在しないリソースは変更扱いにできない
+        manager.remove::<OtherResource>();
+        manager.set_changed::<OtherResource>();
+        assert!(!manager.is_changed::<OtherResource>());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,167 @@
+/**
+ * 結果演出リソース
+ *
+ * ゲーム終了時に、ボードを走査する演出のあとで結果パネルをスライドさせて表示するための
+ * 進行状況を管理するリソース
+ * 時刻は`now`に書き込み、`result_sequence_system`が各段階の進み具合を更新する
+ */
+/// ボードを走査する演出の長さ（ミリ秒）
+pub const RESULT_SWEEP_MS: f64 = 900.0;
+
+/// 結果パネルがスライドして現れるまでの長さ（ミリ秒）
+pub const RESULT_SLIDE_MS: f64 = 450.0;
+
+/// 結果演出の段階
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResultPhase {
+    /// ゲーム中（演出なし）
+    Hidden,
+    /// ボードを走査している
+    Sweep,
+    /// 結果パネルがスライドしている
+    Slide,
+    /// 結果パネルを表示し終えた（ボタンを操作できる）
+    Shown,
+}
+
+/// 結果演出の進行状況
+#[derive(Debug, Clone)]
+pub struct ResultSequenceResource {
+    /// 現在時刻（ミリ秒）
+    pub now: f64,
+    /// アニメーションを減らす設定（有効なら演出を省略する）
+    pub reduced_motion: bool,
+    /// 演出を始めた時刻
+    started_at: Option<f64>,
+    /// 現在の段階
+    phase: ResultPhase,
+    /// ボードの走査の進み具合（0.0〜1.0）
+    sweep_progress: f64,
+    /// 結果パネルのスライドの進み具合（0.0〜1.0）
+    panel_progress: f64,
+}
+
+impl Default for ResultSequenceResource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ResultSequenceResource {
+    /// 演出していない状態を作成
+    pub fn new() -> Self {
+        Self {
+            now: 0.0,
+            reduced_motion: false,
+            started_at: None,
+            phase: ResultPhase::Hidden,
+            sweep_progress: 0.0,
+            panel_progress: 0.0,
+        }
+    }
+
+    /// 演出を始める（始めている場合は何もしない）
+    pub fn start(&mut self, now: f64) {
+        if self.started_at.is_none() {
+            self.started_at = Some(now);
+            self.now = now;
+            self.update();
+        }
+    }
+
+    /// 演出を終えてゲーム中の状態に戻す
+    pub fn reset(&mut self) {
+        self.started_at = None;
+        self.phase = ResultPhase::Hidden;
+        self.sweep_progress = 0.0;
+        self.panel_progress = 0.0;
+    }
+
+    /// `now`に合わせて段階と進み具合を更新
+    pub fn update(&mut self) {
+        let Some(started_at) = self.started_at else {
+            return;
+        };
+        if self.reduced_motion {
+            self.phase = ResultPhase::Shown;
+            self.sweep_progress = 1.0;
+            self.panel_progress = 1.0;
+            return;
+        }
+        
+        let elapsed = (self.now - started_at).max(0.0);
+        self.sweep_progress = (elapsed / RESULT_SWEEP_MS).min(1.0);
+        self.panel_progress = ((elapsed - RESULT_SWEEP_MS) / RESULT_SLIDE_MS).clamp(0.0, 1.0);
+        self.phase = if elapsed < RESULT_SWEEP_MS {
+            ResultPhase::Sweep
+        } else if self.panel_progress < 1.0 {
+            ResultPhase::Slide
+        } else {
+            ResultPhase::Shown
+        };
+    }
+
+    /// 現在の段階
+    pub fn phase(&self) -> ResultPhase {
+        self.phase
+    }
+
+    /// ボードの走査の進み具合（0.0〜1.0）
+    pub fn sweep_progress(&self) -> f64 {
+        self.sweep_progress
+    }
+
+    /// 結果パネルのスライドの進み具合（0.0〜1.0）
+    pub fn panel_progress(&self) -> f64 {
+        self.panel_progress
+    }
+
+    /// 結果パネルのボタンを操作できるかどうか
+    pub fn is_panel_interactive(&self) -> bool {
+        self.phase == ResultPhase::Shown
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sequence_phases() {
+        let mut sequence = ResultSequenceResource::new();
+        sequence.update();
+        assert_eq!(sequence.phase(), ResultPhase::Hidden);
+
+        sequence.start(1000.0);
+        assert_eq!(sequence.phase(), ResultPhase::Sweep);
+
+        // 2回目の開始は無視される
+        sequence.now = 1000.0 + RESULT_SWEEP_MS / 2.0;
+        sequence.start(sequence.now);
+        sequence.update();
+        assert_eq!(sequence.sweep_progress(), 0.5);
+        assert_eq!(sequence.panel_progress(), 0.0);
+
+        sequence.now = 1000.0 + RESULT_SWEEP_MS + RESULT_SLIDE_MS / 2.0;
+        sequence.update();
+        assert_eq!(sequence.phase(), ResultPhase::Slide);
+        assert_eq!(sequence.panel_progress(), 0.5);
+        assert!(!sequence.is_panel_interactive());
+
+        sequence.now += RESULT_SLIDE_MS;
+        sequence.update();
+        assert!(sequence.is_panel_interactive());
+
+        sequence.reset();
+        assert_eq!(sequence.phase(), ResultPhase::Hidden);
+    }
+
+    #[test]
+    fn test_reduced_motion_skips_sequence() {
+        let mut sequence = ResultSequenceResource::new();
+        sequence.reduced_motion = true;
+        sequence.start(0.0);
+        assert!(sequence.is_panel_interactive());
+        assert_eq!(sequence.panel_progress(), 1.0);
+    }
+}
\ No newline at end of file
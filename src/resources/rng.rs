@@ -0,0 +1,117 @@
+/**
+ * 乱数リソース
+ *
+ * シード指定で再現可能な乱数列を生成するリソース
+ * 決定論的モードではゲームロジックの乱数は全てこのリソースから取得する
+ */
+/// シード付き乱数リソース（SplitMix64）
+#[derive(Debug, Clone)]
+pub struct RngResource {
+    /// 初期シード
+    seed: u64,
+    /// 内部状態
+    state: u64,
+}
+
+impl RngResource {
+    /// シードを指定して乱数リソースを作成
+    pub fn new(seed: u64) -> Self {
+        Self { seed, state: seed }
+    }
+
+    /// 初期シードを取得
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// シードを設定し直して乱数列を最初からやり直す
+    pub fn reseed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.state = seed;
+    }
+
+    /// 次の64ビット乱数を生成
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// 0.0以上1.0未満の乱数を生成
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// 0以上max未満の整数乱数を生成（maxが0の場合は0）
+    pub fn gen_range(&mut self, max: usize) -> usize {
+        if max == 0 {
+            return 0;
+        }
+        (self.next_u64() % max as u64) as usize
+    }
+
+    /// スライスをシャッフル（Fisher-Yates）
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.gen_range(i + 1);
+            items.swap(i, j);
+        }
+    }
+
+    /// 0以上total未満の整数から重複無しでcount個を選び、昇順で返す（地雷の配置などに使う）
+    pub fn sample_indices(&mut self, total: usize, count: usize) -> Vec<usize> {
+        let mut indices: Vec<usize> = (0..total).collect();
+        self.shuffle(&mut indices);
+        indices.truncate(count);
+        indices.sort_unstable();
+        indices
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_seed_same_sequence() {
+        let mut a = RngResource::new(42);
+        let mut b = RngResource::new(42);
+        for _ in 0..100 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+
+        let mut items_a: Vec<usize> = (0..20).collect();
+        let mut items_b: Vec<usize> = (0..20).collect();
+        a.shuffle(&mut items_a);
+        b.shuffle(&mut items_b);
+        assert_eq!(items_a, items_b);
+
+        // 再シードで最初の乱数列に戻る
+        let first = RngResource::new(42).next_u64();
+        a.reseed(42);
+        assert_eq!(a.next_u64(), first);
+    }
+
+    #[test]
+    fn test_ranges() {
+        let mut rng = RngResource::new(7);
+        for _ in 0..1000 {
+            assert!(rng.gen_range(10) < 10);
+            let value = rng.next_f64();
+            assert!((0.0..1.0).contains(&value));
+        }
+        assert_eq!(rng.gen_range(0), 0);
+    }
+
+    #[test]
+    fn test_sample_indices() {
+        let mines = RngResource::new(3).sample_indices(100, 15);
+        assert_eq!(mines.len(), 15);
+        assert!(mines.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(mines.iter().all(|&index| index < 100));
+        assert_eq!(RngResource::new(3).sample_indices(100, 15), mines);
+        assert_eq!(RngResource::new(3).sample_indices(5, 10).len(), 5);
+    }
+}
\ No newline at end of file
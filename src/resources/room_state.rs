@@ -0,0 +1,118 @@
+/**
+ * ルーム状態リソース
+ *
+ * ルーム選択画面に表示するルームの一覧と、参加・作成の要求の状態を管理するリソース
+ * 一覧はサーバーの`room_list`メッセージで丸ごと置き換え、画面を開いている間は一定間隔で要求し直す
+ */
+use crate::protocol::RoomInfo;
+
+/// ルーム一覧を要求し直す間隔（ミリ秒）
+pub const ROOM_LIST_REFRESH_MS: f64 = 5_000.0;
+
+/// ルーム選択画面に一度に表示するルームの最大数
+pub const MAX_LISTED_ROOMS: usize = 6;
+
+/// ルーム選択画面のボタン
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoomSelectButton {
+    /// 一覧のルームに参加（一覧のインデックス）
+    Room(usize),
+    /// 新しいルームを作成
+    Create,
+    /// ルームを選ばずに既定のルームで遊ぶ
+    QuickPlay,
+    /// タイトル画面に戻る
+    Back,
+}
+
+/// ルーム状態リソース
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RoomStateResource {
+    /// 参加できるルーム（プレイヤーの多い順）
+    rooms: Vec<RoomInfo>,
+    /// 一覧を一度でも受け取ったかどうか
+    loaded: bool,
+    /// 最後に一覧を要求した時刻（ミリ秒）
+    last_request: Option<f64>,
+    /// 参加・作成を要求して`room_joined`を待っているかどうか
+    joining: bool,
+}
+
+impl RoomStateResource {
+    /// 一覧を受け取っていない状態を作成
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 一覧を要求する時刻になっていれば要求した時刻を記録してtrueを返す
+    pub fn request_due(&mut self, now: f64) -> bool {
+        let due = self.last_request.is_none_or(|last| now - last >= ROOM_LIST_REFRESH_MS);
+        if due {
+            self.last_request = Some(now);
+        }
+        due
+    }
+
+    /// サーバーの`room_list`メッセージの内容で一覧を置き換える
+    pub fn update(&mut self, rooms: &[RoomInfo]) {
+        self.rooms = rooms.to_vec();
+        self.rooms.sort_by(|a, b| b.players.cmp(&a.players).then_with(|| a.code.cmp(&b.code)));
+        self.loaded = true;
+    }
+
+    /// 表示するルーム（最大`MAX_LISTED_ROOMS`件）
+    pub fn rooms(&self) -> &[RoomInfo] {
+        &self.rooms[..self.rooms.len().min(MAX_LISTED_ROOMS)]
+    }
+
+    /// 一覧を受け取ったかどうか
+    pub fn is_loaded(&self) -> bool {
+        self.loaded
+    }
+
+    /// 参加・作成を要求したことを記録する
+    pub fn start_joining(&mut self) {
+        self.joining = true;
+    }
+
+    /// 参加・作成の応答を待っているかどうか（この間はボタンを受け付けない）
+    pub fn is_joining(&self) -> bool {
+        self.joining
+    }
+
+    /// 一覧と要求の状態を捨てる（ルーム選択画面を開き直したとき）
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn room(code: &str, players: usize) -> RoomInfo {
+        RoomInfo { code: code.to_string(), players, board_width: 9, board_height: 9, mine_count: 10, in_lobby: true }
+    }
+
+    #[test]
+    fn test_room_list_refresh_and_order() {
+        let mut room_state = RoomStateResource::new();
+        assert!(room_state.request_due(1_000.0));
+        assert!(!room_state.request_due(1_000.0 + ROOM_LIST_REFRESH_MS - 1.0));
+        assert!(room_state.request_due(1_000.0 + ROOM_LIST_REFRESH_MS));
+        assert!(!room_state.is_loaded());
+
+        let rooms: Vec<RoomInfo> = (0..MAX_LISTED_ROOMS + 2).map(|index| room(&format!("R{}", index), index % 3)).collect();
+        room_state.update(&rooms);
+        assert!(room_state.is_loaded());
+        assert_eq!(room_state.rooms().len(), MAX_LISTED_ROOMS);
+        assert_eq!(room_state.rooms()[0].players, 2);
+        assert_eq!(room_state.rooms()[0].code, "R2");
+
+        room_state.start_joining();
+        assert!(room_state.is_joining());
+        room_state.clear();
+        assert!(!room_state.is_joining());
+        assert!(room_state.rooms().is_empty());
+    }
+}
\ No newline at end of file
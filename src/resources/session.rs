@@ -0,0 +1,74 @@
+/**
+ * セッションの保存リソース
+ *
+ * ページを再読み込みしても同じゲームに同じプレイヤーとして戻れるよう、
+ * 参加中のルーム・プレイヤーID・設定を一定間隔でsessionStorageに保存し、起動時に読み込むリソース
+ * sessionStorageはタブごとに分かれるため、別のタブのプレイヤーIDを引き継ぐことはない
+ */
+use serde::{Serialize, Deserialize};
+use super::settings::SettingsResource;
+
+/// sessionStorageに保存する際のキー（後ろにキャンバスIDを付ける）
+pub const SESSION_STORAGE_KEY: &str = "minesweeper_session";
+
+/// セッションを保存する間隔（ミリ秒）
+pub const SESSION_SAVE_INTERVAL_MS: f64 = 3000.0;
+
+/// キャンバスごとの保存キーを作成（1つのページに複数のボードがある場合に分ける）
+pub fn session_storage_key(canvas_id: &str) -> String {
+    format!("{}:{}", SESSION_STORAGE_KEY, canvas_id)
+}
+
+/// 再読み込みで引き継ぐセッションの状態
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionResource {
+    /// 参加しているルームのコード
+    pub room: Option<String>,
+    /// サーバーから割り当てられたプレイヤーID（再接続時に引き継ぎを要求する）
+    pub player_id: Option<String>,
+    /// 対戦中（ロビーまたはゲーム画面）だったかどうか（trueなら起動時に接続し直す）
+    pub in_match: bool,
+    /// 選択していた難易度のキー
+    pub difficulty: Option<String>,
+    /// プレイヤー設定
+    pub settings: Option<SettingsResource>,
+}
+
+impl SessionResource {
+    /// 保存されたJSONからセッションを復元（読み込めない場合はNone）
+    pub fn from_json(json: &str) -> Option<Self> {
+        serde_json::from_str(json).ok()
+    }
+
+    /// 保存用のJSONに変換
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_round_trip() {
+        let mut settings = SettingsResource::new();
+        settings.player_name = "Alice".to_string();
+        let session = SessionResource {
+            room: Some("abc".to_string()),
+            player_id: Some("player_3".to_string()),
+            in_match: true,
+            difficulty: Some("hard".to_string()),
+            settings: Some(settings),
+        };
+        assert_eq!(SessionResource::from_json(&session.to_json()), Some(session));
+
+        // 壊れたデータは引き継がず、欠けた項目は既定値で補う
+        assert_eq!(SessionResource::from_json("not json"), None);
+        let partial = SessionResource::from_json(r#"{"room":"abc"}"#).unwrap();
+        assert_eq!(partial.room.as_deref(), Some("abc"));
+        assert!(!partial.in_match);
+        assert_eq!(session_storage_key("game-canvas"), "minesweeper_session:game-canvas");
+    }
+}
\ No newline at end of file
@@ -0,0 +1,570 @@
+/**
+ * 設定リソース
+ *
+ * テーマや操作方法などのプレイヤー設定を管理するリソース
+ * JSONに変換してlocalStorageに保存し、次回起動時に読み込む
+ */
+use serde::{Serialize, Deserialize};
+
+use super::localization::text;
+use super::toast::ToastCategory;
+use super::color::Color;
+use super::avatar::is_known_avatar;
+use super::cursor_color::{DEFAULT_CURSOR_COLOR, is_readable_cursor_color, next_cursor_color, cursor_color_label};
+
+/// localStorageに保存する際のキー
+pub const SETTINGS_STORAGE_KEY: &str = "minesweeper_settings";
+
+/// プレイヤー名の最大文字数
+pub const MAX_PLAYER_NAME_LENGTH: usize = 16;
+
+/// 画面のテーマ
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Theme {
+    /// ダークテーマ
+    Dark,
+    /// ライトテーマ
+    Light,
+    /// 森（解放が必要）
+    Forest,
+    /// 海（解放が必要）
+    Ocean,
+    /// 夕焼け（解放が必要）
+    Sunset,
+    /// 金（解放が必要）
+    Gold,
+}
+
+impl Theme {
+    /// テーマ選択画面に並べる順番
+    pub const ALL: [Theme; 6] = [Theme::Dark, Theme::Light, Theme::Forest, Theme::Ocean, Theme::Sunset, Theme::Gold];
+
+    /// 背景色を取得
+    pub fn background_color(&self) -> Color {
+        match self {
+            Theme::Dark => Color(0x333333),
+            Theme::Light => Color(0xE0E0E0),
+            Theme::Forest => Color(0x1B3A2B),
+            Theme::Ocean => Color(0x0D2B45),
+            Theme::Sunset => Color(0x4A1C2C),
+            Theme::Gold => Color(0x3E3418),
+        }
+    }
+
+    /// 背景上の文字色を取得
+    pub fn text_color(&self) -> Color {
+        match self {
+            Theme::Dark => Color(0xFFFFFF),
+            Theme::Light => Color(0x222222),
+            Theme::Forest => Color(0xE8F5E9),
+            Theme::Ocean => Color(0xE3F2FD),
+            Theme::Sunset => Color(0xFFE0B2),
+            Theme::Gold => Color(0xFFF8E1),
+        }
+    }
+
+    /// 表示名を取得
+    pub fn label(&self, language: Language) -> &'static str {
+        let key = match self {
+            Theme::Dark => "theme.dark",
+            Theme::Light => "theme.light",
+            Theme::Forest => "theme.forest",
+            Theme::Ocean => "theme.ocean",
+            Theme::Sunset => "theme.sunset",
+            Theme::Gold => "theme.gold",
+        };
+        text(language, key)
+    }
+
+    /// 起動オプションで指定する名前（`"dark"`など、大文字小文字は区別しない）からテーマを取得
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key.to_ascii_lowercase().as_str() {
+            "dark" => Some(Theme::Dark),
+            "light" => Some(Theme::Light),
+            "forest" => Some(Theme::Forest),
+            "ocean" => Some(Theme::Ocean),
+            "sunset" => Some(Theme::Sunset),
+            "gold" => Some(Theme::Gold),
+            _ => None,
+        }
+    }
+}
+
+/// 表示言語
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Language {
+    /// 日本語
+    Japanese,
+    /// 英語
+    English,
+}
+
+impl Language {
+    /// ブラウザの言語設定（`navigator.language`）から表示言語を決める（日本語以外は英語）
+    pub fn from_locale(locale: &str) -> Self {
+        if locale.to_ascii_lowercase().starts_with("ja") {
+            Language::Japanese
+        } else {
+            Language::English
+        }
+    }
+
+    /// 表示名を取得（どの言語でも各言語の表記で表示する）
+    pub fn label(&self) -> &'static str {
+        match self {
+            Language::Japanese => "日本語",
+            Language::English => "English",
+        }
+    }
+}
+
+/// 右クリック時の動作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RightClickAction {
+    /// フラグを切り替える（左クリックでセルを開く）
+    Flag,
+    /// セルを開く（左クリックでフラグを切り替える）
+    Reveal,
+}
+
+impl RightClickAction {
+    /// 表示名を取得
+    pub fn label(&self, language: Language) -> &'static str {
+        match self {
+            RightClickAction::Flag => text(language, "right_click.flag"),
+            RightClickAction::Reveal => text(language, "right_click.reveal"),
+        }
+    }
+}
+
+/// メニューと設定画面の表示方法
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UiMode {
+    /// 全てキャンバスに描画する
+    Canvas,
+    /// メニューと設定画面をキャンバスに重ねたHTML要素で表示する（ボードはキャンバスのまま）
+    Dom,
+}
+
+impl UiMode {
+    /// 表示名を取得
+    pub fn label(&self, language: Language) -> &'static str {
+        match self {
+            UiMode::Canvas => text(language, "ui_mode.canvas"),
+            UiMode::Dom => text(language, "ui_mode.dom"),
+        }
+    }
+
+    /// 起動オプションで指定する名前（`"canvas"`または`"dom"`）から表示方法を取得
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key.to_ascii_lowercase().as_str() {
+            "canvas" => Some(UiMode::Canvas),
+            "dom" => Some(UiMode::Dom),
+            _ => None,
+        }
+    }
+}
+
+/// 設定画面に並べる項目
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingsItem {
+    /// テーマ
+    Theme,
+    /// サウンドのオン・オフ
+    Sound,
+    /// 効果音の音量
+    Volume,
+    /// BGMの音量
+    MusicVolume,
+    /// 表示言語
+    Language,
+    /// アクセシビリティ（専用の画面で設定する）
+    Accessibility,
+    /// 右クリック時の動作
+    RightClick,
+    /// プレイヤー名
+    Name,
+    /// カーソルの色
+    CursorColor,
+    /// 旗を立てた通知
+    ToastFlags,
+    /// 地雷を踏んだ通知
+    ToastMines,
+    /// 参加・退出の通知
+    ToastPlayers,
+    /// タブが非表示の間のブラウザ通知
+    BrowserNotifications,
+    /// メニューの表示方法
+    UiMode,
+}
+
+impl SettingsItem {
+    /// 設定画面での表示順
+    pub const ALL: [SettingsItem; 14] = [
+        SettingsItem::Theme,
+        SettingsItem::Sound,
+        SettingsItem::Volume,
+        SettingsItem::MusicVolume,
+        SettingsItem::Language,
+        SettingsItem::Accessibility,
+        SettingsItem::RightClick,
+        SettingsItem::Name,
+        SettingsItem::CursorColor,
+        SettingsItem::ToastFlags,
+        SettingsItem::ToastMines,
+        SettingsItem::ToastPlayers,
+        SettingsItem::BrowserNotifications,
+        SettingsItem::UiMode,
+    ];
+
+    /// 項目名を取得
+    pub fn label(&self, language: Language) -> &'static str {
+        let key = match self {
+            SettingsItem::Theme => "settings.theme",
+            SettingsItem::Sound => "settings.sound",
+            SettingsItem::Volume => "settings.volume",
+            SettingsItem::MusicVolume => "settings.music_volume",
+            SettingsItem::Language => "settings.language",
+            SettingsItem::Accessibility => "settings.accessibility",
+            SettingsItem::RightClick => "settings.right_click",
+            SettingsItem::Name => "settings.name",
+            SettingsItem::CursorColor => "settings.cursor_color",
+            SettingsItem::ToastFlags => "settings.toast_flags",
+            SettingsItem::ToastMines => "settings.toast_mines",
+            SettingsItem::ToastPlayers => "settings.toast_players",
+            SettingsItem::BrowserNotifications => "settings.browser_notifications",
+            SettingsItem::UiMode => "settings.ui_mode",
+        };
+        text(language, key)
+    }
+
+    /// スライダーで値を調整する項目かどうか
+    pub fn is_slider(&self) -> bool {
+        matches!(self, SettingsItem::Volume | SettingsItem::MusicVolume)
+    }
+}
+
+/// プレイヤー名を整形（前後の空白を除き、最大文字数で切り詰める）
+pub fn sanitize_player_name(name: &str) -> String {
+    name.trim().chars().take(MAX_PLAYER_NAME_LENGTH).collect()
+}
+
+/// 音量を次の段階（0%〜100%を25%刻みで循環）に切り替える
+fn next_volume_step(volume: f32) -> f32 {
+    let step = (volume * 4.0).round() as u32;
+    ((step + 1) % 5) as f32 / 4.0
+}
+
+/// プレイヤー設定リソース
+/// 保存データに無い項目はデフォルト値で補う
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SettingsResource {
+    /// 画面のテーマ
+    pub theme: Theme,
+    /// サウンドを再生するかどうか
+    pub sound_enabled: bool,
+    /// 効果音の音量（0.0〜1.0）
+    pub sound_volume: f32,
+    /// BGMの音量（0.0〜1.0）
+    pub music_volume: f32,
+    /// 表示言語
+    pub language: Language,
+    /// 右クリック時の動作
+    pub right_click: RightClickAction,
+    /// プレイヤー名（空の場合はIDから自動生成）
+    pub player_name: String,
+    /// カーソルの色（`#RRGGBB`形式）
+    pub cursor_color: String,
+    /// アバター（空の場合はアバターなし）
+    pub avatar: String,
+    /// 旗を立てた通知を表示するかどうか
+    pub toast_flags: bool,
+    /// 地雷を踏んだ通知を表示するかどうか
+    pub toast_mines: bool,
+    /// 参加・退出の通知を表示するかどうか
+    pub toast_players: bool,
+    /// タブが非表示の間にブラウザ通知を出すかどうか（許可が必要なため初期値はオフ）
+    pub browser_notifications: bool,
+    /// メニューと設定画面の表示方法
+    pub ui_mode: UiMode,
+}
+
+impl Default for SettingsResource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SettingsResource {
+    /// デフォルト設定を作成
+    pub fn new() -> Self {
+        Self {
+            theme: Theme::Dark,
+            sound_enabled: true,
+            sound_volume: 0.75,
+            music_volume: 0.5,
+            language: Language::Japanese,
+            right_click: RightClickAction::Flag,
+            player_name: String::new(),
+            cursor_color: DEFAULT_CURSOR_COLOR.to_string(),
+            avatar: String::new(),
+            toast_flags: true,
+            toast_mines: true,
+            toast_players: true,
+            browser_notifications: false,
+            ui_mode: UiMode::Canvas,
+        }
+    }
+
+    /// 保存されたJSONから設定を復元（読み込めない場合はデフォルト設定）
+    /// ボード上で見にくいカーソルの色は初期値に戻し、候補に無いアバターはなしにする
+    pub fn from_json(json: &str) -> Self {
+        let mut settings: Self = serde_json::from_str(json).unwrap_or_default();
+        if !is_readable_cursor_color(&settings.cursor_color) {
+            settings.cursor_color = DEFAULT_CURSOR_COLOR.to_string();
+        }
+        if !is_known_avatar(&settings.avatar) {
+            settings.avatar.clear();
+        }
+        settings
+    }
+
+    /// 保存用のJSONに変換
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    /// 項目の値を次の選択肢に切り替える（プレイヤー名・アクセシビリティは対象外）
+    pub fn cycle(&mut self, item: SettingsItem) {
+        match item {
+            // 解放が必要なテーマはテーマ選択画面で選ぶため、ここでは基本の2つを切り替える
+            SettingsItem::Theme => {
+                self.theme = match self.theme {
+                    Theme::Dark => Theme::Light,
+                    _ => Theme::Dark,
+                };
+            },
+            SettingsItem::Sound => self.sound_enabled = !self.sound_enabled,
+            SettingsItem::Volume => self.sound_volume = next_volume_step(self.sound_volume),
+            SettingsItem::MusicVolume => self.music_volume = next_volume_step(self.music_volume),
+            SettingsItem::Language => {
+                self.language = match self.language {
+                    Language::Japanese => Language::English,
+                    Language::English => Language::Japanese,
+                };
+            },
+            SettingsItem::Accessibility => {},
+            SettingsItem::RightClick => {
+                self.right_click = match self.right_click {
+                    RightClickAction::Flag => RightClickAction::Reveal,
+                    RightClickAction::Reveal => RightClickAction::Flag,
+                };
+            },
+            SettingsItem::Name => {},
+            SettingsItem::CursorColor => self.cursor_color = next_cursor_color(&self.cursor_color).to_string(),
+            SettingsItem::ToastFlags => self.toast_flags = !self.toast_flags,
+            SettingsItem::ToastMines => self.toast_mines = !self.toast_mines,
+            SettingsItem::ToastPlayers => self.toast_players = !self.toast_players,
+            SettingsItem::BrowserNotifications => self.browser_notifications = !self.browser_notifications,
+            SettingsItem::UiMode => {
+                self.ui_mode = match self.ui_mode {
+                    UiMode::Canvas => UiMode::Dom,
+                    UiMode::Dom => UiMode::Canvas,
+                };
+            },
+        }
+    }
+
+    /// 項目の現在値を表示用の文字列で取得（設定中の表示言語で表示する）
+    pub fn value_text(&self, item: SettingsItem) -> String {
+        let on_off = |enabled: bool| text(self.language, if enabled { "settings.on" } else { "settings.off" });
+
+        match item {
+            SettingsItem::Theme => self.theme.label(self.language).to_string(),
+            SettingsItem::Sound => on_off(self.sound_enabled).to_string(),
+            SettingsItem::Volume => format!("{}%", (self.sound_volume * 100.0).round()),
+            SettingsItem::MusicVolume => format!("{}%", (self.music_volume * 100.0).round()),
+            SettingsItem::Language => self.language.label().to_string(),
+            SettingsItem::Accessibility => text(self.language, "settings.open").to_string(),
+            SettingsItem::RightClick => self.right_click.label(self.language).to_string(),
+            SettingsItem::Name if self.player_name.is_empty() => text(self.language, "settings.unset").to_string(),
+            SettingsItem::Name => self.player_name.clone(),
+            SettingsItem::CursorColor => cursor_color_label(self.language, &self.cursor_color),
+            SettingsItem::ToastFlags => on_off(self.toast_flags).to_string(),
+            SettingsItem::ToastMines => on_off(self.toast_mines).to_string(),
+            SettingsItem::ToastPlayers => on_off(self.toast_players).to_string(),
+            SettingsItem::BrowserNotifications => on_off(self.browser_notifications).to_string(),
+            SettingsItem::UiMode => self.ui_mode.label(self.language).to_string(),
+        }
+    }
+
+    /// 種類ごとの通知を表示するかどうか
+    pub fn toast_enabled(&self, category: ToastCategory) -> bool {
+        match category {
+            ToastCategory::Flag => self.toast_flags,
+            ToastCategory::Mine => self.toast_mines,
+            ToastCategory::Presence => self.toast_players,
+            ToastCategory::Unlock | ToastCategory::Notice => true,
+        }
+    }
+
+    /// スライダー項目の現在値（0.0〜1.0）を取得
+    pub fn slider_value(&self, item: SettingsItem) -> Option<f32> {
+        match item {
+            SettingsItem::Volume => Some(self.sound_volume),
+            SettingsItem::MusicVolume => Some(self.music_volume),
+            _ => None,
+        }
+    }
+
+    /// スライダー項目の値を設定（0.0〜1.0に制限し、5%刻みに丸める）
+    pub fn set_slider_value(&mut self, item: SettingsItem, value: f32) {
+        let value = (value.clamp(0.0, 1.0) * 20.0).round() / 20.0;
+        match item {
+            SettingsItem::Volume => self.sound_volume = value,
+            SettingsItem::MusicVolume => self.music_volume = value,
+            _ => {},
+        }
+    }
+
+    /// プレイヤー名を設定（前後の空白を除き、最大文字数で切り詰める）
+    pub fn set_player_name(&mut self, name: &str) {
+        self.player_name = sanitize_player_name(name);
+    }
+
+    /// 右クリック設定を反映して、クリックがフラグ操作かどうかを判定
+    pub fn is_flag_click(&self, right_click: bool) -> bool {
+        match self.right_click {
+            RightClickAction::Flag => right_click,
+            RightClickAction::Reveal => !right_click,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settings_json_round_trip() {
+        let mut settings = SettingsResource::new();
+        settings.cycle(SettingsItem::Theme);
+        settings.cycle(SettingsItem::RightClick);
+        settings.set_player_name("  マインスイーパー名人になりたい人です  ");
+
+        let restored = SettingsResource::from_json(&settings.to_json());
+        assert_eq!(restored, settings);
+        assert_eq!(restored.theme, Theme::Light);
+        assert_eq!(restored.player_name.chars().count(), MAX_PLAYER_NAME_LENGTH);
+        assert!(restored.is_flag_click(false));
+    }
+
+    #[test]
+    fn test_cursor_color_is_validated_on_load() {
+        let mut settings = SettingsResource::new();
+        settings.cycle(SettingsItem::CursorColor);
+        assert_eq!(settings.value_text(SettingsItem::CursorColor), "赤");
+        assert_eq!(SettingsResource::from_json(&settings.to_json()).cursor_color, "#C62828");
+        
+        // 見にくい色が保存されていたら初期値に戻す
+        settings.cursor_color = "#EEEEEE".to_string();
+        assert_eq!(SettingsResource::from_json(&settings.to_json()).cursor_color, DEFAULT_CURSOR_COLOR);
+    }
+
+    #[test]
+    fn test_avatar_is_validated_on_load() {
+        let mut settings = SettingsResource::new();
+        settings.avatar = "🐧".to_string();
+        assert_eq!(SettingsResource::from_json(&settings.to_json()).avatar, "🐧");
+        
+        // 候補に無いアバターはなしにする
+        settings.avatar = "<b>".to_string();
+        assert_eq!(SettingsResource::from_json(&settings.to_json()).avatar, "");
+    }
+
+    #[test]
+    fn test_volume_cycles_in_steps() {
+        let mut settings = SettingsResource::new();
+        let mut labels = Vec::new();
+        for _ in 0..5 {
+            settings.cycle(SettingsItem::Volume);
+            labels.push(settings.value_text(SettingsItem::Volume));
+        }
+        assert_eq!(labels, vec!["100%", "0%", "25%", "50%", "75%"]);
+    }
+
+    #[test]
+    fn test_music_volume_slider_is_independent() {
+        let mut settings = SettingsResource::new();
+        settings.set_slider_value(SettingsItem::MusicVolume, 0.33);
+        assert_eq!(settings.slider_value(SettingsItem::MusicVolume), Some(0.35));
+        assert_eq!(settings.value_text(SettingsItem::MusicVolume), "35%");
+        assert_eq!(settings.sound_volume, 0.75);
+
+        settings.set_slider_value(SettingsItem::MusicVolume, 1.5);
+        assert_eq!(settings.music_volume, 1.0);
+
+        // スライダー以外の項目は値を持たず、設定しても変わらない
+        settings.set_slider_value(SettingsItem::Theme, 0.0);
+        assert_eq!(settings.slider_value(SettingsItem::Theme), None);
+        assert_eq!(settings, SettingsResource::from_json(&settings.to_json()));
+    }
+
+    #[test]
+    fn test_language_from_locale_and_value_text() {
+        assert_eq!(Language::from_locale("ja-JP"), Language::Japanese);
+        assert_eq!(Language::from_locale("JA"), Language::Japanese);
+        assert_eq!(Language::from_locale("en-US"), Language::English);
+        assert_eq!(Language::from_locale("fr"), Language::English);
+        assert_eq!(Theme::from_key("Ocean"), Some(Theme::Ocean));
+        assert_eq!(Theme::from_key("neon"), None);
+
+        let mut settings = SettingsResource::new();
+        assert_eq!(settings.value_text(SettingsItem::Sound), "オン");
+        settings.cycle(SettingsItem::Language);
+        assert_eq!(settings.value_text(SettingsItem::Sound), "On");
+        assert_eq!(settings.value_text(SettingsItem::Name), "(not set)");
+        assert_eq!(SettingsItem::Theme.label(settings.language), "Theme");
+    }
+
+    #[test]
+    fn test_toast_categories_can_be_muted() {
+        let mut settings = SettingsResource::new();
+        assert!(settings.toast_enabled(ToastCategory::Flag));
+
+        settings.cycle(SettingsItem::ToastFlags);
+        assert!(!settings.toast_enabled(ToastCategory::Flag));
+        assert!(settings.toast_enabled(ToastCategory::Mine));
+        assert!(settings.toast_enabled(ToastCategory::Presence));
+        assert_eq!(settings.value_text(SettingsItem::ToastFlags), "オフ");
+    }
+
+    #[test]
+    fn test_settings_fall_back_to_defaults() {
+        // 一部の項目だけ保存されている場合は残りをデフォルトで補う
+        let partial = SettingsResource::from_json(r#"{"sound_enabled":false}"#);
+        assert!(!partial.sound_enabled);
+        assert_eq!(partial.sound_volume, 0.75);
+        assert_eq!(partial.theme, Theme::Dark);
+        // ブラウザ通知は許可が必要なため、以前の保存データでもオフになる
+        assert!(!partial.browser_notifications);
+        assert_eq!(partial.ui_mode, UiMode::Canvas);
+
+        // 壊れたデータはデフォルト設定になる
+        assert_eq!(SettingsResource::from_json("not json"), SettingsResource::new());
+    }
+
+    #[test]
+    fn test_ui_mode_cycles_and_parses() {
+        let mut settings = SettingsResource::new();
+        settings.cycle(SettingsItem::UiMode);
+        assert_eq!(settings.ui_mode, UiMode::Dom);
+        assert_eq!(settings.value_text(SettingsItem::UiMode), "HTML");
+        settings.cycle(SettingsItem::UiMode);
+        assert_eq!(settings.ui_mode, UiMode::Canvas);
+
+        assert_eq!(UiMode::from_key("DOM"), Some(UiMode::Dom));
+        assert_eq!(UiMode::from_key("svg"), None);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,193 @@
+/**
+ * 統計リソース
+ *
+ * 難易度ごとのプレイ回数・勝敗・ベストタイム・開いたセル数・立てたフラグ数を
+ * 累計で記録するリソース
+ * JSONに変換してlocalStorageに保存し、次回起動時に読み込む
+ */
+use std::collections::HashMap;
+use serde::{Serialize, Deserialize};
+use super::game_config::Difficulty;
+use super::leaderboard::format_time;
+
+/// localStorageに保存する際のキー
+pub const STATS_STORAGE_KEY: &str = "minesweeper_stats";
+
+/// 統計画面に表示する難易度の順番
+pub const STATS_DIFFICULTIES: [Difficulty; 4] = [
+    Difficulty::Easy,
+    Difficulty::Medium,
+    Difficulty::Hard,
+    Difficulty::Custom,
+];
+
+/// 1つの難易度の累計記録
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DifficultyStats {
+    /// プレイ回数
+    pub games_played: u32,
+    /// 勝利数
+    pub wins: u32,
+    /// 敗北数
+    pub losses: u32,
+    /// 勝利時のベストタイム（ミリ秒）
+    pub best_time_ms: Option<f64>,
+    /// 開いたセルの累計
+    pub cells_revealed: u64,
+    /// 立てたフラグの累計
+    pub flags_placed: u64,
+}
+
+impl DifficultyStats {
+    /// 勝率（0.0〜1.0、未プレイの場合はNone）
+    pub fn win_rate(&self) -> Option<f64> {
+        if self.games_played == 0 {
+            None
+        } else {
+            Some(self.wins as f64 / self.games_played as f64)
+        }
+    }
+
+    /// ベストタイムを表示用の文字列で取得
+    pub fn best_time_text(&self) -> String {
+        self.best_time_ms.map_or_else(|| "--:--.-".to_string(), format_time)
+    }
+
+    /// 勝率を表示用の文字列で取得
+    pub fn win_rate_text(&self) -> String {
+        self.win_rate().map_or_else(|| "-".to_string(), |rate| format!("{}%", (rate * 100.0).round()))
+    }
+}
+
+/// 累計統計リソース
+/// 難易度のキー（`Difficulty::key`）ごとに記録する
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StatsResource {
+    /// 難易度ごとの記録
+    by_difficulty: HashMap<String, DifficultyStats>,
+    /// 勝利したゲームのスコアの累計
+    total_score: u64,
+}
+
+impl StatsResource {
+    /// 空の統計を作成
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 保存されたJSONから統計を復元（読み込めない場合は空の統計）
+    pub fn from_json(json: &str) -> Self {
+        serde_json::from_str(json).unwrap_or_default()
+    }
+
+    /// 保存用のJSONに変換
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    /// 難易度の記録を取得（未プレイの場合は空の記録）
+    pub fn stats(&self, difficulty: &Difficulty) -> DifficultyStats {
+        self.by_difficulty.get(difficulty.key()).cloned().unwrap_or_default()
+    }
+
+    /// 難易度の記録を変更用に取得
+    fn stats_mut(&mut self, difficulty: &Difficulty) -> &mut DifficultyStats {
+        self.by_difficulty.entry(difficulty.key().to_string()).or_default()
+    }
+
+    /// 終了したゲームの結果を記録
+    pub fn record_game(&mut self, difficulty: &Difficulty, win: bool, time_ms: f64) {
+        let stats = self.stats_mut(difficulty);
+        stats.games_played += 1;
+
+        if win {
+            stats.wins += 1;
+            stats.best_time_ms = Some(stats.best_time_ms.map_or(time_ms, |best| best.min(time_ms)));
+        } else {
+            stats.losses += 1;
+        }
+    }
+
+    /// 開いたセル数を加算
+    pub fn record_cells_revealed(&mut self, difficulty: &Difficulty, count: u64) {
+        self.stats_mut(difficulty).cells_revealed += count;
+    }
+
+    /// 立てたフラグを1つ加算
+    pub fn record_flag_placed(&mut self, difficulty: &Difficulty) {
+        self.stats_mut(difficulty).flags_placed += 1;
+    }
+
+    /// 全難易度の合計プレイ回数
+    pub fn total_games_played(&self) -> u32 {
+        self.by_difficulty.values().map(|stats| stats.games_played).sum()
+    }
+
+    /// 全難易度の合計勝利数
+    pub fn total_wins(&self) -> u32 {
+        self.by_difficulty.values().map(|stats| stats.wins).sum()
+    }
+
+    /// 全難易度の合計の開いたセル数
+    pub fn total_cells_revealed(&self) -> u64 {
+        self.by_difficulty.values().map(|stats| stats.cells_revealed).sum()
+    }
+
+    /// 確定したスコアを累計に加算
+    pub fn record_score(&mut self, score: u32) {
+        self.total_score += score as u64;
+    }
+
+    /// スコアの累計
+    pub fn total_score(&self) -> u64 {
+        self.total_score
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_games_per_difficulty() {
+        let mut stats = StatsResource::new();
+        stats.record_game(&Difficulty::Easy, true, 30_000.0);
+        stats.record_game(&Difficulty::Easy, true, 25_000.0);
+        stats.record_game(&Difficulty::Easy, false, 5_000.0);
+        stats.record_cells_revealed(&Difficulty::Easy, 12);
+        stats.record_flag_placed(&Difficulty::Easy);
+
+        let easy = stats.stats(&Difficulty::Easy);
+        assert_eq!((easy.games_played, easy.wins, easy.losses), (3, 2, 1));
+        assert_eq!(easy.best_time_ms, Some(25_000.0));
+        assert_eq!(easy.best_time_text(), "00:25.0");
+        assert_eq!(easy.win_rate_text(), "67%");
+        assert_eq!((easy.cells_revealed, easy.flags_placed), (12, 1));
+
+        // 他の難易度には影響しない
+        let hard = stats.stats(&Difficulty::Hard);
+        assert_eq!(hard, DifficultyStats::default());
+        assert_eq!(hard.win_rate_text(), "-");
+        assert_eq!(stats.total_games_played(), 3);
+
+        // 全難易度の合計
+        stats.record_game(&Difficulty::Hard, true, 100_000.0);
+        stats.record_cells_revealed(&Difficulty::Hard, 30);
+        stats.record_score(1200);
+        stats.record_score(300);
+        assert_eq!(stats.total_wins(), 3);
+        assert_eq!(stats.total_cells_revealed(), 42);
+        assert_eq!(stats.total_score(), 1500);
+    }
+
+    #[test]
+    fn test_stats_json_round_trip() {
+        let mut stats = StatsResource::new();
+        stats.record_game(&Difficulty::Medium, false, 1_000.0);
+
+        assert_eq!(StatsResource::from_json(&stats.to_json()), stats);
+        assert_eq!(StatsResource::from_json("not json"), StatsResource::new());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,151 @@
+/**
+ * デイリーチャレンジの連続記録リソース
+ *
+ * 日付から決まる共通の盤面（デイリーチャレンジ）をクリアした日を記録し、
+ * 連続してクリアした日数と最高記録を管理するリソース
+ * JSONに変換してlocalStorageに保存し、次回起動時に読み込む
+ */
+use serde::{Serialize, Deserialize};
+use super::rng::RngResource;
+
+/// localStorageに保存する際のキー
+pub const STREAK_STORAGE_KEY: &str = "minesweeper_streak";
+
+/// デイリーチャレンジの盤面の幅
+pub const DAILY_BOARD_WIDTH: usize = 16;
+
+/// デイリーチャレンジの盤面の高さ
+pub const DAILY_BOARD_HEIGHT: usize = 16;
+
+/// デイリーチャレンジの地雷数
+pub const DAILY_MINE_COUNT: usize = 40;
+
+/// 実績を獲得できる連続日数
+pub const STREAK_ACHIEVEMENTS: [u32; 3] = [3, 7, 30];
+
+/// 1日の長さ（ミリ秒）
+const DAY_MS: f64 = 86_400_000.0;
+
+/// 時刻（UNIXエポックからのミリ秒）から日番号を計算（UTCで日付が変わる）
+pub fn day_number(time_ms: f64) -> i64 {
+    (time_ms / DAY_MS).floor() as i64
+}
+
+/// 日番号をシードにしてデイリーチャレンジの地雷の配置を作成
+/// 同じ日なら誰が作っても同じ配置になる
+pub fn daily_challenge_mines(day: i64) -> Vec<usize> {
+    RngResource::new(day as u64).sample_indices(DAILY_BOARD_WIDTH * DAILY_BOARD_HEIGHT, DAILY_MINE_COUNT)
+}
+
+/// デイリーチャレンジの連続記録
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StreakResource {
+    /// 最後にクリアした日の日番号
+    last_completed_day: Option<i64>,
+    /// 最後にクリアした日までの連続日数
+    current: u32,
+    /// 最高の連続日数
+    best: u32,
+}
+
+impl StreakResource {
+    /// 記録の無い状態を作成
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 保存されたJSONから記録を復元（読み込めない場合は空の記録）
+    pub fn from_json(json: &str) -> Self {
+        serde_json::from_str(json).unwrap_or_default()
+    }
+
+    /// 保存用のJSONに変換
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    /// 指定した日にクリア済みかどうか
+    pub fn is_completed(&self, day: i64) -> bool {
+        self.last_completed_day == Some(day)
+    }
+
+    /// クリアを記録（同じ日に2回目以降のクリアは数えない）
+    /// 前日にクリアしていれば連続日数を伸ばし、そうでなければ1日目からやり直す
+    pub fn record_completion(&mut self, day: i64) {
+        match self.last_completed_day {
+            Some(last) if last == day => return,
+            Some(last) if last + 1 == day => self.current += 1,
+            _ => self.current = 1,
+        }
+        self.last_completed_day = Some(day);
+        self.best = self.best.max(self.current);
+    }
+
+    /// 今日の時点での連続日数（前日もクリアしていなければ途切れているので0）
+    pub fn current_streak(&self, today: i64) -> u32 {
+        match self.last_completed_day {
+            Some(last) if last == today || last + 1 == today => self.current,
+            _ => 0,
+        }
+    }
+
+    /// 最高の連続日数
+    pub fn best_streak(&self) -> u32 {
+        self.best
+    }
+
+    /// 獲得済みの実績（連続日数）
+    pub fn achievements(&self) -> Vec<u32> {
+        STREAK_ACHIEVEMENTS.into_iter().filter(|&days| self.best >= days).collect()
+    }
+
+    /// beforeからselfまでの間に新しく獲得した実績（連続日数）
+    pub fn newly_earned(&self, before: &StreakResource) -> Vec<u32> {
+        STREAK_ACHIEVEMENTS
+            .into_iter()
+            .filter(|&days| before.best < days && self.best >= days)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_streak_counts_consecutive_days() {
+        let mut streak = StreakResource::new();
+        assert_eq!(streak.current_streak(100), 0);
+
+        streak.record_completion(100);
+        streak.record_completion(100);
+        streak.record_completion(101);
+        let before = streak.clone();
+        streak.record_completion(102);
+        assert_eq!(streak.current_streak(102), 3);
+        assert_eq!(streak.current_streak(103), 3);
+        assert_eq!(streak.newly_earned(&before), vec![3]);
+        assert!(streak.is_completed(102));
+
+        // 1日空くと途切れる
+        assert_eq!(streak.current_streak(104), 0);
+        streak.record_completion(104);
+        assert_eq!(streak.current_streak(104), 1);
+        assert_eq!(streak.best_streak(), 3);
+        assert_eq!(streak.achievements(), vec![3]);
+
+        let restored = StreakResource::from_json(&streak.to_json());
+        assert_eq!(restored, streak);
+    }
+
+    #[test]
+    fn test_daily_mines_depend_only_on_day() {
+        let mines = daily_challenge_mines(20_000);
+        assert_eq!(mines.len(), DAILY_MINE_COUNT);
+        assert_eq!(mines, daily_challenge_mines(20_000));
+        assert_ne!(mines, daily_challenge_mines(20_001));
+        assert!(mines.iter().all(|&mine| mine < DAILY_BOARD_WIDTH * DAILY_BOARD_HEIGHT));
+        assert_eq!(day_number(20_000.0 * 86_400_000.0 + 1.0), 20_000);
+    }
+}
\ No newline at end of file
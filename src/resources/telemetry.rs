@@ -0,0 +1,170 @@
+/**
+ * テレメトリーリソース
+ *
+ * フレーム時間・再接続の回数・ゲームの所要時間を集計し、
+ * ホストページが登録したコールバックに一定間隔でまとめて渡すためのリソース
+ * プレイヤー名やIDなど個人を特定できる情報は含めない
+ */
+use serde::Serialize;
+
+/// レポートを送る間隔（ミリ秒）
+pub const TELEMETRY_INTERVAL_MS: f64 = 60_000.0;
+
+/// 集計するフレーム時間の最大数（古いものから捨てる）
+const MAX_FRAME_SAMPLES: usize = 3_600;
+
+/// 一定間隔でホストページに渡す集計結果
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TelemetryReport {
+    /// 集計したフレーム数
+    pub frames: usize,
+    /// フレーム時間の中央値（ミリ秒）
+    pub frame_time_p50: f64,
+    /// フレーム時間の95パーセンタイル（ミリ秒）
+    pub frame_time_p95: f64,
+    /// フレーム時間の99パーセンタイル（ミリ秒）
+    pub frame_time_p99: f64,
+    /// この間隔での再接続の回数
+    pub reconnects: u32,
+    /// この間隔で終わったゲームの所要時間（ミリ秒）
+    pub game_durations_ms: Vec<f64>,
+    /// この間隔で勝ったゲームの数
+    pub wins: u32,
+}
+
+/// テレメトリーの集計リソース
+#[derive(Debug, Clone, Default)]
+pub struct TelemetryResource {
+    /// 前のフレームの時刻（ミリ秒）
+    last_frame_at: Option<f64>,
+    /// 前のレポートを送った時刻（ミリ秒）
+    last_report_at: Option<f64>,
+    /// 集計中のフレーム時間（ミリ秒）
+    frame_times: Vec<f64>,
+    /// これまでに接続した回数（最初の接続は再接続に数えない）
+    connections: u32,
+    /// 集計中の再接続の回数
+    reconnects: u32,
+    /// 集計中のゲームの所要時間（ミリ秒）
+    game_durations: Vec<f64>,
+    /// 集計中の勝ったゲームの数
+    wins: u32,
+}
+
+impl TelemetryResource {
+    /// 新しいテレメトリーリソースを作成
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// フレームの時刻を記録（前のフレームとの差をフレーム時間として集計する）
+    pub fn record_frame(&mut self, now: f64) {
+        if let Some(last) = self.last_frame_at {
+            if self.frame_times.len() >= MAX_FRAME_SAMPLES {
+                self.frame_times.remove(0);
+            }
+            self.frame_times.push(now - last);
+        }
+        self.last_frame_at = Some(now);
+        self.last_report_at.get_or_insert(now);
+    }
+
+    /// フレームの間隔を空ける（タブが非表示だった間をフレーム時間に含めない）
+    pub fn skip_frame(&mut self) {
+        self.last_frame_at = None;
+    }
+
+    /// サーバーへの接続を記録（2回目以降を再接続として数える）
+    pub fn record_connection(&mut self) {
+        if self.connections > 0 {
+            self.reconnects += 1;
+        }
+        self.connections += 1;
+    }
+
+    /// 終わったゲームの所要時間と勝敗を記録
+    pub fn record_game(&mut self, duration_ms: f64, win: bool) {
+        self.game_durations.push(duration_ms);
+        if win {
+            self.wins += 1;
+        }
+    }
+
+    /// 送る間隔が過ぎていれば集計結果を取り出し、集計をやり直す
+    pub fn take_report(&mut self, now: f64) -> Option<TelemetryReport> {
+        let last = self.last_report_at?;
+        if now - last < TELEMETRY_INTERVAL_MS {
+            return None;
+        }
+
+        let mut frame_times = std::mem::take(&mut self.frame_times);
+        frame_times.sort_by(|a, b| a.total_cmp(b));
+        let report = TelemetryReport {
+            frames: frame_times.len(),
+            frame_time_p50: percentile(&frame_times, 0.50),
+            frame_time_p95: percentile(&frame_times, 0.95),
+            frame_time_p99: percentile(&frame_times, 0.99),
+            reconnects: std::mem::take(&mut self.reconnects),
+            game_durations_ms: std::mem::take(&mut self.game_durations),
+            wins: std::mem::take(&mut self.wins),
+        };
+        self.last_report_at = Some(now);
+        Some(report)
+    }
+}
+
+/// 昇順に並んだ値のパーセンタイルを取得（値が無い場合は0）
+fn percentile(sorted: &[f64], ratio: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let index = ((sorted.len() - 1) as f64 * ratio).round() as usize;
+    sorted[index]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_is_sent_at_interval() {
+        let mut telemetry = TelemetryResource::new();
+        for frame in 0..=100 {
+            telemetry.record_frame(frame as f64 * 16.0);
+        }
+        assert_eq!(telemetry.take_report(1_600.0), None);
+
+        telemetry.record_connection();
+        telemetry.record_connection();
+        telemetry.record_game(42_000.0, true);
+
+        let report = telemetry.take_report(TELEMETRY_INTERVAL_MS).unwrap();
+        assert_eq!(report.frames, 100);
+        assert_eq!(report.frame_time_p50, 16.0);
+        assert_eq!(report.reconnects, 1);
+        assert_eq!(report.game_durations_ms, vec![42_000.0]);
+        assert_eq!(report.wins, 1);
+
+        // 送った後は集計をやり直す
+        assert_eq!(telemetry.take_report(TELEMETRY_INTERVAL_MS + 1.0), None);
+        let report = telemetry.take_report(TELEMETRY_INTERVAL_MS * 2.0).unwrap();
+        assert_eq!(report.frames, 0);
+        assert_eq!(report.reconnects, 0);
+    }
+
+    #[test]
+    fn test_percentiles_ignore_skipped_frames() {
+        let mut telemetry = TelemetryResource::new();
+        telemetry.record_frame(0.0);
+        telemetry.record_frame(10.0);
+        telemetry.skip_frame();
+        telemetry.record_frame(50_000.0);
+        telemetry.record_frame(50_030.0);
+
+        let report = telemetry.take_report(TELEMETRY_INTERVAL_MS).unwrap();
+        assert_eq!(report.frames, 2);
+        assert_eq!(report.frame_time_p50, 30.0);
+        assert_eq!(report.frame_time_p99, 30.0);
+    }
+}
\ No newline at end of file
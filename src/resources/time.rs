@@ -4,7 +4,7 @@
  * ゲームのフレームタイミング、FPS、ゲーム内時間を管理するリソース
  */
 use wasm_bindgen::prelude::*;
-use js_sys::Date;
+use crate::utils::now_ms;
 use std::collections::VecDeque;
 
 /// ゲーム内の時間管理を行うリソース
@@ -28,8 +28,14 @@ pub struct TimeResource {
     pub current_time: f64,
     /// 一時停止中かどうか
     pub is_paused: bool,
-    /// 時間スケール（1.0が通常速度）
+    /// 時間スケール（1.0が通常速度、0.0で停止）
     pub time_scale: f64,
+    /// スケール適用前の前回フレームからの経過時間（秒）
+    pub unscaled_delta_time: f64,
+    /// スケール適用前のゲーム開始からの合計時間（秒）
+    pub unscaled_total_time: f64,
+    /// 固定フレーム時間（秒）。設定時は実時間を使わずにこの値だけ進める
+    pub fixed_delta: Option<f64>,
 }
 
 impl TimeResource {
@@ -43,15 +49,24 @@ impl TimeResource {
             frame_times: VecDeque::with_capacity(60),
             max_samples: 60,
             fps: 0.0,
-            current_time: Date::now(),
+            current_time: now_ms(),
             is_paused: false,
             time_scale: 1.0,
+            unscaled_delta_time: 0.0,
+            unscaled_total_time: 0.0,
+            fixed_delta: None,
         }
     }
 
     /// 新しいフレームの開始処理
     pub fn begin_frame(&mut self) -> f64 {
-        let now = Date::now();
+        // 固定フレーム時間が設定されている場合は実時間に依存しない
+        if let Some(fixed_delta) = self.fixed_delta {
+            self.frame_count += 1;
+            return self.advance(fixed_delta);
+        }
+        
+        let now = now_ms();
         
         // 初回フレームの場合
         if self.last_frame_time == 0.0 {
@@ -62,16 +77,7 @@ impl TimeResource {
         }
         
         // 経過時間の計算（秒単位）
-        self.delta_time = (now - self.last_frame_time) / 1000.0;
-        
-        // 一時停止中は時間を進めない
-        if self.is_paused {
-            self.delta_time = 0.0;
-        } else {
-            // 時間スケールの適用
-            self.delta_time *= self.time_scale;
-            self.total_time += self.delta_time;
-        }
+        self.advance((now - self.last_frame_time) / 1000.0);
         
         // フレームカウントの更新
         self.frame_count += 1;
@@ -86,6 +92,27 @@ impl TimeResource {
         self.delta_time
     }
 
+    /// 実時間の経過（秒）を与えて時間を進める
+    /// 
+    /// スケールなしの時間は常に進み、ゲーム内時間は一時停止と時間スケールを反映する。
+    /// リプレイ再生やスローモーションでは時間スケールを変えるだけでよい
+    pub fn advance(&mut self, real_delta: f64) -> f64 {
+        let real_delta = real_delta.max(0.0);
+        self.unscaled_delta_time = real_delta;
+        self.unscaled_total_time += real_delta;
+        
+        // 一時停止中は時間を進めない
+        if self.is_paused {
+            self.delta_time = 0.0;
+        } else {
+            // 時間スケールの適用
+            self.delta_time = real_delta * self.time_scale;
+            self.total_time += self.delta_time;
+        }
+        
+        self.delta_time
+    }
+
     /// FPS（フレームレート）の更新
     fn update_fps(&mut self, now: f64) {
         // 現在のフレーム時間をサンプルに追加
@@ -120,6 +147,16 @@ impl TimeResource {
         self.time_scale = scale.max(0.0);
     }
 
+    /// 固定フレーム時間を設定（Noneで実時間に戻す）
+    pub fn set_fixed_delta(&mut self, fixed_delta: Option<f64>) {
+        self.fixed_delta = fixed_delta.map(|delta| delta.max(0.0));
+    }
+
+    /// ゲーム内時間が止まっているかどうか（一時停止中またはスケール0）
+    pub fn is_time_stopped(&self) -> bool {
+        self.is_paused || self.time_scale == 0.0
+    }
+
     /// 指定した間隔（秒）ごとにtrueを返す
     pub fn every_seconds(&self, interval: f64) -> bool {
         // 安全のために最小間隔を設定
@@ -177,7 +214,7 @@ mod tests {
         assert!(resource.is_paused);
         
         // 一時停止中はdelta_timeが0になることを確認
-        resource.last_frame_time = Date::now() - 100.0; // 100ms前
+        resource.last_frame_time = now_ms() - 100.0; // 100ms前
         let dt = resource.begin_frame();
         assert_eq!(dt, 0.0);
         
@@ -187,4 +224,25 @@ mod tests {
         let dt = resource.begin_frame();
         assert!(dt > 0.0);
     }
+
+    #[test]
+    fn test_time_scale() {
+        let mut resource = TimeResource::new();
+        
+        // スローモーション
+        resource.set_time_scale(0.5);
+        assert_eq!(resource.advance(0.2), 0.1);
+        assert_eq!(resource.unscaled_delta_time, 0.2);
+        
+        // スケール0ではゲーム内時間は止まるが実時間は進む
+        resource.set_time_scale(0.0);
+        assert!(resource.is_time_stopped());
+        assert_eq!(resource.advance(0.2), 0.0);
+        assert_eq!(resource.total_time, 0.1);
+        assert!((resource.unscaled_total_time - 0.4).abs() < 1e-9);
+        
+        // 負のスケールは0に丸められる
+        resource.set_time_scale(-2.0);
+        assert_eq!(resource.time_scale, 0.0);
+    }
 } 
\ No newline at end of file
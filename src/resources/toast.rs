@@ -0,0 +1,111 @@
+/**
+ * トーストリソース
+ *
+ * 他のプレイヤーの操作（旗を立てた・地雷を踏んだ・参加/退出）やテーマの解放を
+ * 画面上部に短時間表示する通知（トースト）を管理するリソース
+ */
+/// トーストを表示する時間（ミリ秒）
+pub const TOAST_DISPLAY_MS: f64 = 3000.0;
+
+/// 同時に表示するトーストの最大数（超えた分は古いものから消す）
+pub const MAX_TOASTS: usize = 4;
+
+/// トーストの種類（設定で種類ごとに非表示にできる）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToastCategory {
+    /// 旗を立てた
+    Flag,
+    /// 地雷を踏んだ
+    Mine,
+    /// プレイヤーの参加・退出
+    Presence,
+    /// テーマの解放（常に表示）
+    Unlock,
+    /// 自分の操作の結果のお知らせ（常に表示）
+    Notice,
+}
+
+/// 表示中のトースト
+#[derive(Debug, Clone, PartialEq)]
+pub struct Toast {
+    /// トーストの種類
+    pub category: ToastCategory,
+    /// 表示する文言
+    pub text: String,
+    /// 表示開始時刻（ミリ秒）
+    pub shown_at: f64,
+}
+
+impl Toast {
+    /// 表示時間に対する経過割合（0.0〜1.0）を取得
+    pub fn progress(&self, now: f64) -> f64 {
+        ((now - self.shown_at) / TOAST_DISPLAY_MS).clamp(0.0, 1.0)
+    }
+}
+
+/// トーストリソース
+#[derive(Debug, Clone, Default)]
+pub struct ToastResource {
+    /// 表示中のトースト（古い順）
+    toasts: Vec<Toast>,
+}
+
+impl ToastResource {
+    /// 空のトーストリソースを作成
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 確保しているトーストの容量（メモリの診断用）
+    pub fn capacity(&self) -> usize {
+        self.toasts.capacity()
+    }
+
+    /// トーストを追加
+    pub fn push(&mut self, category: ToastCategory, text: String, now: f64) {
+        self.toasts.push(Toast { category, text, shown_at: now });
+        if self.toasts.len() > MAX_TOASTS {
+            self.toasts.remove(0);
+        }
+    }
+
+    /// 表示中のトーストを取得（古い順）
+    pub fn active(&self, now: f64) -> impl Iterator<Item = &Toast> {
+        self.toasts.iter().filter(move |toast| now - toast.shown_at < TOAST_DISPLAY_MS)
+    }
+
+    /// 表示時間を過ぎたトーストを削除
+    pub fn prune(&mut self, now: f64) {
+        self.toasts.retain(|toast| now - toast.shown_at < TOAST_DISPLAY_MS);
+    }
+
+    /// 全てのトーストを消す
+    pub fn clear(&mut self) {
+        self.toasts.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toasts_expire_and_are_capped() {
+        let mut toasts = ToastResource::new();
+        for index in 0..=MAX_TOASTS {
+            toasts.push(ToastCategory::Flag, format!("toast {}", index), index as f64 * 1000.0);
+        }
+
+        // 最大数を超えた分は古いものから消える
+        let active: Vec<&str> = toasts.active(MAX_TOASTS as f64 * 1000.0).map(|toast| toast.text.as_str()).collect();
+        assert_eq!(active, vec!["toast 2", "toast 3", "toast 4"]);
+        assert_eq!(toasts.active(0.0).count(), MAX_TOASTS);
+
+        toasts.prune(5500.0);
+        assert_eq!(toasts.active(5500.0).count(), 2);
+        assert_eq!(toasts.active(5500.0).next().map(|toast| toast.progress(4500.0)), Some(0.5));
+
+        toasts.clear();
+        assert_eq!(toasts.active(5500.0).count(), 0);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,126 @@
+/**
+ * 通信記録リソース
+ *
+ * サーバーとやり取りした最近のメッセージを時刻付きで一定数だけ残すリソース
+ * クライアントとサーバーの状態のずれを、ブラウザの開発者ツールを使わずに調べるために使う
+ */
+use std::collections::VecDeque;
+use serde::Serialize;
+
+/// 残しておくメッセージの数（超えたら古いものから捨てる）
+pub const TRAFFIC_LOG_CAPACITY: usize = 200;
+
+/// メッセージの向き
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrafficDirection {
+    /// サーバーから届いた
+    Inbound,
+    /// サーバーに送った
+    Outbound,
+}
+
+/// 記録した1つのメッセージ
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TrafficEntry {
+    /// 受信・送信した時刻（ミリ秒）
+    pub time: f64,
+    /// メッセージの向き
+    pub direction: TrafficDirection,
+    /// メッセージの内容（プロトコルのJSONと同じ形）
+    pub message: serde_json::Value,
+}
+
+/// 通信記録リソース
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrafficLogResource {
+    /// 記録したメッセージ（古い順）
+    entries: VecDeque<TrafficEntry>,
+    /// 残しておくメッセージの数
+    capacity: usize,
+}
+
+impl Default for TrafficLogResource {
+    fn default() -> Self {
+        Self::with_capacity(TRAFFIC_LOG_CAPACITY)
+    }
+}
+
+impl TrafficLogResource {
+    /// 既定の数だけ残す記録を作成
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 残しておくメッセージの数を指定して作成
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// メッセージを記録（JSONにできないメッセージは記録しない）
+    pub fn record<M: Serialize>(&mut self, direction: TrafficDirection, message: &M, now: f64) {
+        let Ok(message) = serde_json::to_value(message) else {
+            return;
+        };
+        if self.capacity == 0 {
+            return;
+        }
+
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(TrafficEntry { time: now, direction, message });
+    }
+
+    /// 記録したメッセージを古い順に取得
+    pub fn entries(&self) -> impl Iterator<Item = &TrafficEntry> {
+        self.entries.iter()
+    }
+
+    /// 記録したメッセージの数を取得
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// 記録が空かどうか
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// 記録を消去
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// 記録をJSONの配列にする（古い順）
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(&self.entries).unwrap_or_else(|_| "[]".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::{ClientMessage, ServerMessage};
+
+    #[test]
+    fn test_keeps_only_the_latest_entries() {
+        let mut log = TrafficLogResource::with_capacity(2);
+        log.record(TrafficDirection::Outbound, &ClientMessage::RevealCell { index: 1 }, 10.0);
+        log.record(TrafficDirection::Inbound, &ServerMessage::PlayerLeft { id: "player_2".to_string() }, 20.0);
+        log.record(TrafficDirection::Outbound, &ClientMessage::ResetGame, 30.0);
+
+        assert_eq!(log.len(), 2);
+        let times: Vec<f64> = log.entries().map(|entry| entry.time).collect();
+        assert_eq!(times, vec![20.0, 30.0]);
+
+        let json: serde_json::Value = serde_json::from_str(&log.to_json()).unwrap();
+        assert_eq!(json[0]["direction"], "inbound");
+        assert_eq!(json[0]["message"]["type"], "player_left");
+        assert_eq!(json[1]["direction"], "outbound");
+        assert_eq!(json[1]["message"]["type"], "reset_game");
+    }
+}
\ No newline at end of file
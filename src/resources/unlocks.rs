@@ -0,0 +1,101 @@
+/**
+ * テーマの解放条件
+ *
+ * 一部のテーマは累計統計（勝利数・開いたセル数・累計スコア・難易度ごとの勝利）で解放される
+ * 統計の更新前後を比べて、新しく解放されたテーマを通知に使う
+ */
+use super::game_config::Difficulty;
+use super::localization::format_text;
+use super::settings::{Language, Theme};
+use super::stats::StatsResource;
+
+/// テーマの解放条件
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ThemeUnlock {
+    /// 全難易度の合計勝利数
+    TotalWins(u32),
+    /// 全難易度の合計の開いたセル数
+    CellsRevealed(u64),
+    /// 累計スコア
+    TotalScore(u64),
+    /// 指定した難易度での勝利
+    WinOn(Difficulty),
+}
+
+impl ThemeUnlock {
+    /// テーマの解放条件を取得（最初から使えるテーマはNone）
+    pub fn for_theme(theme: Theme) -> Option<Self> {
+        match theme {
+            Theme::Dark | Theme::Light => None,
+            Theme::Forest => Some(ThemeUnlock::TotalWins(5)),
+            Theme::Ocean => Some(ThemeUnlock::CellsRevealed(2000)),
+            Theme::Sunset => Some(ThemeUnlock::WinOn(Difficulty::Hard)),
+            Theme::Gold => Some(ThemeUnlock::TotalScore(10_000)),
+        }
+    }
+
+    /// 条件を満たしているかどうか
+    pub fn is_met(&self, stats: &StatsResource) -> bool {
+        match self {
+            ThemeUnlock::TotalWins(wins) => stats.total_wins() >= *wins,
+            ThemeUnlock::CellsRevealed(cells) => stats.total_cells_revealed() >= *cells,
+            ThemeUnlock::TotalScore(score) => stats.total_score() >= *score,
+            ThemeUnlock::WinOn(difficulty) => stats.stats(difficulty).wins > 0,
+        }
+    }
+
+    /// 条件の説明を取得
+    pub fn description(&self, language: Language) -> String {
+        match self {
+            ThemeUnlock::TotalWins(wins) => format_text(language, "unlock.wins", &[wins]),
+            ThemeUnlock::CellsRevealed(cells) => format_text(language, "unlock.cells", &[cells]),
+            ThemeUnlock::TotalScore(score) => format_text(language, "unlock.score", &[score]),
+            ThemeUnlock::WinOn(difficulty) => format_text(language, "unlock.win_on", &[&difficulty.label(language)]),
+        }
+    }
+}
+
+/// テーマが使えるかどうか
+pub fn is_theme_unlocked(theme: Theme, stats: &StatsResource) -> bool {
+    ThemeUnlock::for_theme(theme).is_none_or(|unlock| unlock.is_met(stats))
+}
+
+/// 統計の更新で新しく解放されたテーマを取得
+pub fn newly_unlocked_themes(before: &StatsResource, after: &StatsResource) -> Vec<Theme> {
+    Theme::ALL.into_iter()
+        .filter(|&theme| !is_theme_unlocked(theme, before) && is_theme_unlocked(theme, after))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_themes_unlock_from_stats() {
+        let mut stats = StatsResource::new();
+        assert!(is_theme_unlocked(Theme::Dark, &stats));
+        assert!(is_theme_unlocked(Theme::Light, &stats));
+        assert!(!is_theme_unlocked(Theme::Sunset, &stats));
+
+        // 上級で勝つと夕焼けが解放される
+        let before = stats.clone();
+        stats.record_game(&Difficulty::Hard, true, 90_000.0);
+        assert_eq!(newly_unlocked_themes(&before, &stats), vec![Theme::Sunset]);
+        assert_eq!(newly_unlocked_themes(&stats, &stats), Vec::<Theme>::new());
+
+        // 累計スコアと勝利数
+        let before = stats.clone();
+        stats.record_score(10_000);
+        for _ in 0..4 {
+            stats.record_game(&Difficulty::Easy, true, 10_000.0);
+        }
+        assert_eq!(newly_unlocked_themes(&before, &stats), vec![Theme::Forest, Theme::Gold]);
+        assert!(!is_theme_unlocked(Theme::Ocean, &stats));
+
+        assert_eq!(
+            ThemeUnlock::for_theme(Theme::Ocean).unwrap().description(Language::English),
+            "🔒 Reveal 2000 cells to unlock"
+        );
+    }
+}
\ No newline at end of file
@@ -0,0 +1,91 @@
+/**
+ * ルームの招待リンクを扱うモジュール
+ * 
+ * ページのURL（`#room=ABC123`または`?room=ABC123`）からルームコードを読み取り、
+ * 現在のルームに招待するためのリンクを作ります。
+ */
+/// ルームコードの最大の長さ
+pub const MAX_ROOM_CODE_LENGTH: usize = 32;
+
+/// URLでルームコードを指定するパラメータ名
+const ROOM_PARAM: &str = "room";
+
+/**
+ * ルームコードとして使える文字列か確認する
+ * 
+ * 英数字・ハイフン・アンダースコアのみの1〜32文字を受け付けます。
+ * 
+ * @param code 確認する文字列
+ * @return 使える場合は前後の空白を除いたコード
+ */
+pub fn normalize_room_code(code: &str) -> Option<String> {
+    let code = code.trim();
+    let valid = !code.is_empty()
+        && code.len() <= MAX_ROOM_CODE_LENGTH
+        && code.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    valid.then(|| code.to_string())
+}
+
+/**
+ * `key=value&...`形式の文字列からルームコードを探す
+ * 
+ * @param params 先頭の`#`や`?`を含んでもよいパラメータ文字列
+ * @return 有効なルームコードがあればSome
+ */
+fn find_room_param(params: &str) -> Option<String> {
+    params
+        .trim_start_matches(['#', '?'])
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == ROOM_PARAM)
+        .and_then(|(_, value)| normalize_room_code(value))
+}
+
+/**
+ * ページのURLのハッシュとクエリからルームコードを読み取る
+ * 
+ * 両方に指定がある場合はハッシュを優先します。
+ * 
+ * @param hash `location.hash`（例: "#room=ABC123"）
+ * @param search `location.search`（例: "?room=ABC123"）
+ * @return 有効なルームコードがあればSome
+ */
+pub fn parse_room_code(hash: &str, search: &str) -> Option<String> {
+    find_room_param(hash).or_else(|| find_room_param(search))
+}
+
+/**
+ * ルームへの招待リンクを作る
+ * 
+ * @param page_url ハッシュを除いたページのURL
+ * @param room ルームコード
+ * @return `#room=`を付けたURL
+ */
+pub fn invite_link(page_url: &str, room: &str) -> String {
+    let page_url = page_url.split('#').next().unwrap_or(page_url);
+    format!("{}#{}={}", page_url, ROOM_PARAM, room)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_room_code() {
+        assert_eq!(parse_room_code("#room=ABC123", ""), Some("ABC123".to_string()));
+        assert_eq!(parse_room_code("", "?lang=en&room=team-1"), Some("team-1".to_string()));
+        // ハッシュの指定を優先する
+        assert_eq!(parse_room_code("#room=HASH", "?room=QUERY"), Some("HASH".to_string()));
+        // 使えない文字や長すぎるコードは無視する
+        assert_eq!(parse_room_code("#room=a b", ""), None);
+        assert_eq!(parse_room_code(&format!("#room={}", "A".repeat(MAX_ROOM_CODE_LENGTH + 1)), ""), None);
+        assert_eq!(parse_room_code("#other=1", "?lang=en"), None);
+    }
+
+    #[test]
+    fn test_invite_link() {
+        assert_eq!(invite_link("https://example.com/?lang=en", "ABC123"), "https://example.com/?lang=en#room=ABC123");
+        // 既存のハッシュは置き換える
+        assert_eq!(invite_link("https://example.com/#room=OLD", "NEW"), "https://example.com/#room=NEW");
+    }
+}
\ No newline at end of file
@@ -0,0 +1,185 @@
+/**
+ * サーバーに送るメッセージの送信キュー
+ *
+ * 送信はすぐに行わず、フレームの間に積んだメッセージをフレームの最後に1回だけまとめて送ります。
+ * 続けて積まれたカーソルの移動は最後の位置だけを送り、種類ごとに決めた最短の間隔が空いていない
+ * メッセージは次のフレーム以降まで残します。
+ */
+use std::collections::{HashMap, VecDeque};
+use std::mem::{discriminant, Discriminant};
+use crate::protocol::ClientMessage;
+
+/// カーソルの移動を送る最短の間隔（ミリ秒）
+pub const MOVE_INTERVAL_MS: f64 = 100.0;
+
+/// エモートを送る最短の間隔（ミリ秒）
+pub const EMOTE_INTERVAL_MS: f64 = 500.0;
+
+/**
+ * メッセージの種類ごとの最短の送信間隔を取得する
+ *
+ * @param message 送るメッセージ
+ * @return 最短の間隔（ミリ秒）、制限しない種類の場合はNone
+ */
+fn min_interval(message: &ClientMessage) -> Option<f64> {
+    match message {
+        ClientMessage::PlayerMove { .. } => Some(MOVE_INTERVAL_MS),
+        ClientMessage::Emote { .. } => Some(EMOTE_INTERVAL_MS),
+        _ => None,
+    }
+}
+
+/**
+ * 送信待ちのメッセージと種類ごとの最後の送信時刻
+ */
+#[derive(Debug, Default)]
+pub struct SendQueue {
+    pending: VecDeque<ClientMessage>,                     // 積まれた順の送信待ちのメッセージ
+    last_sent: HashMap<Discriminant<ClientMessage>, f64>, // 間隔を制限する種類を最後に送った時刻
+}
+
+impl SendQueue {
+    /**
+     * 新しい送信キューを作成する
+     *
+     * @return 空の送信キュー
+     */
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /**
+     * メッセージを積む
+     *
+     * 最後に積まれたメッセージもカーソルの移動であれば、新しい位置で置き換えます。
+     *
+     * @param message 送るメッセージ
+     */
+    pub fn push(&mut self, message: ClientMessage) {
+        if let (Some(last @ ClientMessage::PlayerMove { .. }), ClientMessage::PlayerMove { .. }) = (self.pending.back_mut(), &message) {
+            *last = message;
+            return;
+        }
+        self.pending.push_back(message);
+    }
+
+    /**
+     * 送れるメッセージを積まれた順に送る（フレームの最後に1回呼び出す）
+     *
+     * 間隔が空いていない種類のメッセージは、同じ種類の後続と一緒にキューに残します。
+     * 送信に失敗した場合は、接続が使えないものとして残りのメッセージを捨てます。
+     *
+     * @param now 現在時刻（ミリ秒）
+     * @param send 1つのメッセージを送る関数
+     * @return 全て送れた場合はOk(()), 送信に失敗した場合は最初のエラー
+     */
+    pub fn flush<E>(&mut self, now: f64, mut send: impl FnMut(&ClientMessage) -> Result<(), E>) -> Result<(), E> {
+        let mut held = VecDeque::new();
+        while let Some(message) = self.pending.pop_front() {
+            if let Some(interval) = min_interval(&message) {
+                let kind = discriminant(&message);
+                // 同じ種類を先に残した場合は、順番を保つため後続も残す
+                let too_soon = self.last_sent.get(&kind).is_some_and(|&last| now - last < interval);
+                if too_soon || held.iter().any(|held| discriminant(held) == kind) {
+                    held.push_back(message);
+                    continue;
+                }
+                self.last_sent.insert(kind, now);
+            }
+
+            if let Err(e) = send(&message) {
+                self.pending.clear();
+                return Err(e);
+            }
+        }
+        self.pending = held;
+        Ok(())
+    }
+
+    /**
+     * 送信待ちのメッセージを捨てる（切断時に呼び出す）
+     */
+    pub fn clear(&mut self) {
+        self.pending.clear();
+    }
+
+    /**
+     * 送信待ちのメッセージの数を取得する
+     *
+     * @return 送信待ちのメッセージの数
+     */
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /**
+     * 送信待ちのメッセージが無いかどうか
+     *
+     * @return 送信待ちのメッセージが無い場合はtrue
+     */
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flush_all(queue: &mut SendQueue, now: f64) -> Vec<ClientMessage> {
+        let mut sent = Vec::new();
+        queue.flush(now, |message| {
+            sent.push(message.clone());
+            Ok::<(), ()>(())
+        }).unwrap();
+        sent
+    }
+
+    #[test]
+    fn test_consecutive_moves_are_coalesced() {
+        let mut queue = SendQueue::new();
+        queue.push(ClientMessage::PlayerMove { x: 1.0, y: 1.0 });
+        queue.push(ClientMessage::PlayerMove { x: 2.0, y: 2.0 });
+        queue.push(ClientMessage::RevealCell { index: 3 });
+        queue.push(ClientMessage::PlayerMove { x: 3.0, y: 3.0 });
+        queue.push(ClientMessage::PlayerMove { x: 4.0, y: 4.0 });
+        assert_eq!(queue.len(), 3);
+
+        // 1フレームに送る移動は1つで、残りは間隔が空くまで待つ
+        assert_eq!(flush_all(&mut queue, 1000.0), vec![
+            ClientMessage::PlayerMove { x: 2.0, y: 2.0 },
+            ClientMessage::RevealCell { index: 3 },
+        ]);
+        assert_eq!(flush_all(&mut queue, 1050.0), vec![]);
+        queue.push(ClientMessage::PlayerMove { x: 5.0, y: 5.0 });
+        assert_eq!(queue.len(), 1);
+        assert_eq!(flush_all(&mut queue, 1100.0), vec![ClientMessage::PlayerMove { x: 5.0, y: 5.0 }]);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_rate_limit_keeps_order_within_a_type() {
+        let mut queue = SendQueue::new();
+        queue.push(ClientMessage::Emote { emote: "wave".to_string() });
+        queue.push(ClientMessage::Emote { emote: "laugh".to_string() });
+        queue.push(ClientMessage::ToggleFlag { index: 1 });
+
+        // 制限の無い種類は待たずに送る
+        assert_eq!(flush_all(&mut queue, 0.0), vec![
+            ClientMessage::Emote { emote: "wave".to_string() },
+            ClientMessage::ToggleFlag { index: 1 },
+        ]);
+        assert_eq!(flush_all(&mut queue, EMOTE_INTERVAL_MS - 1.0), vec![]);
+        assert_eq!(flush_all(&mut queue, EMOTE_INTERVAL_MS), vec![ClientMessage::Emote { emote: "laugh".to_string() }]);
+    }
+
+    #[test]
+    fn test_send_error_drops_the_rest() {
+        let mut queue = SendQueue::new();
+        queue.push(ClientMessage::RevealCell { index: 0 });
+        queue.push(ClientMessage::ResetGame);
+
+        assert_eq!(queue.flush(0.0, |_| Err("closed")), Err("closed"));
+        assert!(queue.is_empty());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,423 @@
+/**
+ * ブラウザを使わないゲームのシミュレーション
+ * 
+ * Worldにゲーム進行・ボード・ボットのプラグインを登録し、地雷の位置を持つ盤面を
+ * ネイティブ環境で操作します。ネイティブの`main`から台本通りの対戦や対話的なプレイを実行し、
+ * ゲームロジックの確認や計測に使います。
+ */
+use crate::ecs::{World, CorePlugin, BoardPlugin, BotPlugin};
+use crate::resources::{
+    BoardConfigResource, BoardStateResource, BotBoardView, BotMove, BotResource, BotStrategy,
+    CoreGameResource, RngResource,
+};
+use crate::utils::get_adjacent_offsets;
+
+/**
+ * シミュレーションの設定
+ */
+#[derive(Debug, Clone)]
+pub struct SimulationConfig {
+    pub width: usize,             // ボードの幅
+    pub height: usize,            // ボードの高さ
+    pub mines: usize,             // 地雷の数
+    pub seed: u64,                // 地雷の配置とボットの乱数のシード
+    pub bots: Vec<BotStrategy>,   // 参加させるボットの打ち方
+    pub step_ms: f64,             // 1ステップで進める時間（ミリ秒）
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        Self {
+            width: 9,
+            height: 9,
+            mines: 10,
+            seed: 1,
+            bots: vec![BotStrategy::Solver],
+            step_ms: 100.0,
+        }
+    }
+}
+
+/**
+ * セルを開いた結果
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RevealOutcome {
+    Opened(usize), // 安全なセルを開いた（開いたセル数）
+    Exploded,      // 地雷を開いた
+    Ignored,       // 開けないセル（開いたセル・フラグ・ゲーム終了後）
+}
+
+/**
+ * シミュレーションの結果
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SimulationResult {
+    pub win: bool,      // 全ての安全なセルを開いたかどうか
+    pub steps: usize,   // 進めたステップ数
+    pub moves: usize,   // 反映した手の数
+}
+
+/**
+ * 地雷の位置を持つ盤面とWorldをまとめたシミュレーション
+ */
+pub struct Simulation {
+    world: World,
+    width: usize,
+    height: usize,
+    mine_count: usize,
+    mines: Vec<bool>,       // 地雷かどうか（最初に開くまでは未配置）
+    revealed: Vec<bool>,    // 開いたかどうか
+    flagged: Vec<bool>,     // フラグが立っているかどうか
+    rng: RngResource,       // 地雷の配置に使う乱数
+    step_ms: f64,           // 1ステップで進める時間
+    now: f64,               // シミュレーション上の時刻（ミリ秒）
+    steps: usize,           // 進めたステップ数
+    moves: usize,           // 反映した手の数
+}
+
+impl Simulation {
+    /**
+     * 設定に従ってWorldと盤面を作成する
+     * 
+     * @param config シミュレーションの設定
+     * @return 地雷を配置する前の状態のシミュレーション
+     */
+    pub fn new(config: &SimulationConfig) -> Self {
+        let cells = config.width * config.height;
+        let mine_count = config.mines.min(cells.saturating_sub(1));
+        
+        // ボードの寸法はプラグインが既定値で作る前に登録しておく
+        let mut world = World::new();
+        let board_config = BoardConfigResource::new(config.width, config.height, mine_count);
+        world.insert_resource(BoardStateResource::new(&board_config));
+        world.insert_resource(board_config);
+        world.insert_resource(BotResource::new(config.seed));
+        world.add_plugin(CorePlugin).add_plugin(BoardPlugin).add_plugin(BotPlugin);
+        world.enable_deterministic_mode(config.seed, config.step_ms);
+        if let Some(core_game) = world.get_resource_mut::<CoreGameResource>() {
+            core_game.initialize(mine_count as u32);
+        }
+        if let Some(bots) = world.get_resource_mut::<BotResource>() {
+            for &strategy in &config.bots {
+                bots.add_bot(strategy);
+            }
+        }
+        
+        let mut simulation = Self {
+            world,
+            width: config.width,
+            height: config.height,
+            mine_count,
+            mines: vec![false; cells],
+            revealed: vec![false; cells],
+            flagged: vec![false; cells],
+            rng: RngResource::new(config.seed),
+            step_ms: config.step_ms,
+            now: 0.0,
+            steps: 0,
+            moves: 0,
+        };
+        simulation.sync_bot_view();
+        simulation
+    }
+
+    /**
+     * ボードの幅
+     */
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /**
+     * ボードの高さ
+     */
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /**
+     * シミュレーションのWorld（リソースの確認用）
+     */
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+
+    /**
+     * ゲームが終了したかどうか
+     */
+    pub fn is_over(&self) -> bool {
+        self.world.get_resource::<CoreGameResource>().is_some_and(|core_game| core_game.is_game_over())
+    }
+
+    /**
+     * ゲームに勝利したかどうか
+     */
+    pub fn is_win(&self) -> bool {
+        self.world.get_resource::<CoreGameResource>().is_some_and(|core_game| core_game.is_win())
+    }
+
+    /**
+     * セルを開く
+     * 
+     * 最初に開くセルとその周囲には地雷を置きません。周囲に地雷が無いセルは隣も続けて開きます。
+     * 
+     * @param index 開くセルのインデックス
+     * @return 開いた結果
+     */
+    pub fn reveal(&mut self, index: usize) -> RevealOutcome {
+        if index >= self.revealed.len() || self.revealed[index] || self.flagged[index] || self.is_over() {
+            return RevealOutcome::Ignored;
+        }
+        if !self.mines_placed() {
+            self.place_mines(index);
+        }
+        self.moves += 1;
+        
+        if self.mines[index] {
+            self.revealed[index] = true;
+            if let Some(core_game) = self.world.get_resource_mut::<CoreGameResource>() {
+                core_game.end_game(false);
+            }
+            self.sync_bot_view();
+            return RevealOutcome::Exploded;
+        }
+        
+        let mut opened = 0;
+        let mut stack = vec![index];
+        while let Some(cell) = stack.pop() {
+            if self.revealed[cell] || self.flagged[cell] {
+                continue;
+            }
+            self.revealed[cell] = true;
+            opened += 1;
+            if self.count_adjacent_mines(cell) == 0 {
+                stack.extend(self.neighbors(cell));
+            }
+        }
+        if let Some(board) = self.world.get_resource_mut::<BoardStateResource>() {
+            for _ in 0..opened {
+                board.record_safe_reveal();
+            }
+        }
+        self.sync_bot_view();
+        RevealOutcome::Opened(opened)
+    }
+
+    /**
+     * フラグを切り替える
+     * 
+     * @param index 切り替えるセルのインデックス
+     * @return 切り替えた場合はtrue
+     */
+    pub fn toggle_flag(&mut self, index: usize) -> bool {
+        if index >= self.flagged.len() || self.revealed[index] || self.is_over() {
+            return false;
+        }
+        self.moves += 1;
+        self.flagged[index] = !self.flagged[index];
+        let flagged = self.flagged[index];
+        if let Some(board) = self.world.get_resource_mut::<BoardStateResource>() {
+            board.record_flag(flagged);
+        }
+        self.sync_bot_view();
+        true
+    }
+
+    /**
+     * 時刻を1ステップ進めてシステムを実行し、ボットが指した手を反映する
+     */
+    pub fn step(&mut self) {
+        self.now += self.step_ms;
+        self.steps += 1;
+        if let Some(bots) = self.world.get_resource_mut::<BotResource>() {
+            bots.now = self.now;
+        }
+        self.world.run_stages();
+        
+        let actions = self.world.get_resource_mut::<BotResource>()
+            .map(|bots| bots.take_actions())
+            .unwrap_or_default();
+        for action in actions {
+            match action.bot_move {
+                BotMove::Reveal(index) => {
+                    self.reveal(index);
+                },
+                BotMove::Flag(index) => {
+                    if !self.flagged[index] {
+                        self.toggle_flag(index);
+                    }
+                },
+            }
+        }
+        // 最後の安全なセルを開いた勝利はシステムが判定する
+        self.world.run_stages();
+    }
+
+    /**
+     * ゲームが終わるまでステップを進める
+     * 
+     * @param max_steps 進める最大のステップ数
+     * @return シミュレーションの結果
+     */
+    pub fn run(&mut self, max_steps: usize) -> SimulationResult {
+        while !self.is_over() && self.steps < max_steps {
+            self.step();
+        }
+        SimulationResult { win: self.is_win(), steps: self.steps, moves: self.moves }
+    }
+
+    /**
+     * 盤面を文字で表す（`#`は閉じたセル、`F`はフラグ、`*`は地雷、`.`は周囲に地雷の無いセル）
+     * 
+     * @param show_mines 開いていない地雷も表示するかどうか
+     * @return 行ごとに改行した盤面
+     */
+    pub fn render(&self, show_mines: bool) -> String {
+        let mut text = String::with_capacity((self.width * 2 + 4) * (self.height + 1));
+        text.push_str("   ");
+        for x in 0..self.width {
+            text.push_str(&format!("{:>2}", x % 100));
+        }
+        text.push('\n');
+        for y in 0..self.height {
+            text.push_str(&format!("{:>2} ", y % 100));
+            for x in 0..self.width {
+                let index = y * self.width + x;
+                let symbol = if self.revealed[index] || (show_mines && self.mines[index]) {
+                    match (self.mines[index], self.count_adjacent_mines(index)) {
+                        (true, _) => '*',
+                        (false, 0) => '.',
+                        (false, count) => char::from(b'0' + count),
+                    }
+                } else if self.flagged[index] {
+                    'F'
+                } else {
+                    '#'
+                };
+                text.push(' ');
+                text.push(symbol);
+            }
+            text.push('\n');
+        }
+        text
+    }
+
+    /**
+     * 地雷を配置済みかどうか
+     */
+    fn mines_placed(&self) -> bool {
+        self.world.get_resource::<BoardStateResource>().is_some_and(|board| board.mines_placed())
+    }
+
+    /**
+     * 最初に開くセルとその周囲を避けて地雷を配置する
+     * 
+     * @param first 最初に開くセルのインデックス
+     */
+    fn place_mines(&mut self, first: usize) {
+        let mut excluded: Vec<usize> = self.neighbors(first).collect();
+        excluded.push(first);
+        // 周囲を避けると置ききれない小さなボードでは最初のセルだけを避ける
+        if self.mines.len() - excluded.len() < self.mine_count {
+            excluded = vec![first];
+        }
+        let candidates: Vec<usize> = (0..self.mines.len()).filter(|index| !excluded.contains(index)).collect();
+        for pick in self.rng.sample_indices(candidates.len(), self.mine_count) {
+            self.mines[candidates[pick]] = true;
+        }
+        
+        if let Some(board) = self.world.get_resource_mut::<BoardStateResource>() {
+            board.mark_mines_placed();
+        }
+        if let Some(core_game) = self.world.get_resource_mut::<CoreGameResource>() {
+            core_game.start_game();
+        }
+    }
+
+    /**
+     * 周囲のセルのインデックス
+     */
+    fn neighbors(&self, index: usize) -> impl Iterator<Item = usize> {
+        let (width, height) = (self.width as isize, self.height as isize);
+        let (x, y) = ((index % self.width) as isize, (index / self.width) as isize);
+        get_adjacent_offsets().into_iter().filter_map(move |(dy, dx)| {
+            let (nx, ny) = (x + dx, y + dy);
+            (nx >= 0 && ny >= 0 && nx < width && ny < height).then(|| (ny * width + nx) as usize)
+        })
+    }
+
+    /**
+     * 周囲の地雷数
+     */
+    fn count_adjacent_mines(&self, index: usize) -> u8 {
+        self.neighbors(index).filter(|&neighbor| self.mines[neighbor]).count() as u8
+    }
+
+    /**
+     * ボットに見えている盤面を更新する
+     */
+    fn sync_bot_view(&mut self) {
+        let numbers: Vec<Option<u8>> = (0..self.revealed.len())
+            .map(|index| (self.revealed[index] && !self.mines[index]).then(|| self.count_adjacent_mines(index)))
+            .collect();
+        let game_over = self.is_over();
+        if let Some(bots) = self.world.get_resource_mut::<BotResource>() {
+            bots.board = BotBoardView {
+                width: self.width,
+                height: self.height,
+                numbers,
+                flagged: self.flagged.clone(),
+                game_over,
+                origin: (0.0, 0.0),
+                cell_size: 1.0,
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_reveal_is_safe() {
+        let mut simulation = Simulation::new(&SimulationConfig { bots: Vec::new(), ..Default::default() });
+        assert!(matches!(simulation.reveal(40), RevealOutcome::Opened(opened) if opened >= 1));
+        assert_eq!(simulation.reveal(40), RevealOutcome::Ignored);
+        assert!(!simulation.is_over());
+
+        let rendered = simulation.render(false);
+        assert_eq!(rendered.lines().count(), 10);
+        assert!(!rendered.contains('*'));
+        assert_eq!(simulation.render(true).matches('*').count(), 10);
+    }
+
+    #[test]
+    fn test_bots_finish_a_game_deterministically() {
+        let config = SimulationConfig {
+            seed: 7,
+            bots: vec![BotStrategy::Solver, BotStrategy::Solver],
+            ..Default::default()
+        };
+        let first = Simulation::new(&config).run(10_000);
+        assert!(first.steps < 10_000);
+        assert!(first.moves > 0);
+
+        // 同じシードなら同じ結果になる
+        assert_eq!(Simulation::new(&config).run(10_000), first);
+    }
+
+    #[test]
+    fn test_world_ends_game_when_board_is_cleared() {
+        let mut simulation = Simulation::new(&SimulationConfig { width: 3, height: 1, mines: 1, bots: Vec::new(), ..Default::default() });
+        simulation.reveal(0);
+        let safe = (0..3).find(|&index| !simulation.mines[index] && !simulation.revealed[index]);
+        if let Some(index) = safe {
+            simulation.reveal(index);
+        }
+        simulation.step();
+        assert!(simulation.is_win());
+        assert_eq!(simulation.reveal(2), RevealOutcome::Ignored);
+    }
+}
\ No newline at end of file
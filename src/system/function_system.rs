@@ -0,0 +1,85 @@
+use crate::entities::EntityManager;
+use crate::resources::ResourceManager;
+use super::system_registry::{System, SystemPhase, SystemPriority};
+
+/// 関数やクロージャをシステムとして扱うためのラッパー
+pub struct FunctionSystem<F> {
+    /// システムの名前
+    name: String,
+    /// 実行フェーズ
+    phase: SystemPhase,
+    /// 優先度
+    priority: SystemPriority,
+    /// 実行する関数
+    func: F,
+}
+
+impl<F> FunctionSystem<F>
+where
+    F: FnMut(&mut EntityManager, &mut ResourceManager) + 'static,
+{
+    /// 新しい関数システムを作成
+    pub fn new(name: &str, phase: SystemPhase, func: F) -> Self {
+        Self {
+            name: name.to_string(),
+            phase,
+            priority: 0,
+            func,
+        }
+    }
+
+    /// 優先度を設定
+    pub fn with_priority(mut self, priority: SystemPriority) -> Self {
+        self.priority = priority;
+        self
+    }
+}
+
+impl<F> System for FunctionSystem<F>
+where
+    F: FnMut(&mut EntityManager, &mut ResourceManager) + 'static,
+{
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn phase(&self) -> SystemPhase {
+        self.phase
+    }
+
+    fn priority(&self) -> SystemPriority {
+        self.priority
+    }
+
+    /// リソースからエンティティマネージャーを一時的に取り出して関数を実行
+    fn run(&mut self, resources: &mut ResourceManager) {
+        // エンティティマネージャーが登録されていない場合は何もしない
+        let Some(mut entities) = resources.remove::<EntityManager>() else {
+            return;
+        };
+
+        (self.func)(&mut entities, resources);
+
+        resources.insert(entities);
+    }
+}
+
+/// 関数やクロージャをシステムに変換するためのトレイト
+pub trait IntoSystem {
+    /// 変換後のシステムの型
+    type System: System + 'static;
+
+    /// 名前とフェーズを指定してシステムに変換
+    fn into_system(self, name: &str, phase: SystemPhase) -> Self::System;
+}
+
+impl<F> IntoSystem for F
+where
+    F: FnMut(&mut EntityManager, &mut ResourceManager) + 'static,
+{
+    type System = FunctionSystem<F>;
+
+    fn into_system(self, name: &str, phase: SystemPhase) -> Self::System {
+        FunctionSystem::new(name, phase, self)
+    }
+}
\ No newline at end of file
@@ -1,3 +1,5 @@
 pub mod system_registry;
+pub mod function_system;
 
-pub use system_registry::{System, SystemRegistry}; 
\ No newline at end of file
+pub use system_registry::{Stage, System, SystemPhase, SystemRegistry};
+pub use function_system::{FunctionSystem, IntoSystem};
\ No newline at end of file
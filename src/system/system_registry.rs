@@ -1,5 +1,7 @@
-use std::collections::HashMap;
+use std::any::Any;
+use std::collections::{HashMap, HashSet};
 use crate::resources::ResourceManager;
+use super::function_system::IntoSystem;
 
 /// システムの実行フェーズ
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -8,22 +10,58 @@ pub enum SystemPhase {
     Startup,
     /// 入力処理フェーズ
     Input,
+    /// 更新前フェーズ（入力結果の反映など）
+    PreUpdate,
     /// 更新フェーズ（ゲームロジックの更新）
     Update,
+    /// 更新後フェーズ（勝敗判定や位置の伝播など）
+    PostUpdate,
     /// 描画フェーズ
     Render,
     /// 後処理フェーズ（フレーム終了時に実行）
     Cleanup,
 }
 
+/// ステージ（フレーム内の実行段階）。`SystemPhase`の別名
+pub type Stage = SystemPhase;
+
+/// 毎フレーム実行するステージのデフォルトの順序
+pub const DEFAULT_STAGE_ORDER: [Stage; 6] = [
+    Stage::Input,
+    Stage::PreUpdate,
+    Stage::Update,
+    Stage::PostUpdate,
+    Stage::Render,
+    Stage::Cleanup,
+];
+
 /// システムの優先度を表す型（数字が小さいほど先に実行される）
 pub type SystemPriority = i32;
 
 /// システムのIDを表す型
 pub type SystemId = usize;
 
+/// `Any`への変換を提供するトレイト（'staticな型には自動で実装される）
+pub trait AsAny {
+    /// `&dyn Any`として取得
+    fn as_any(&self) -> &dyn Any;
+    
+    /// `&mut dyn Any`として取得
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T: 'static> AsAny for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
 /// システムの実装に必要なトレイト
-pub trait System {
+pub trait System: AsAny {
     /// システムの名前を返す
     fn name(&self) -> &str;
     
@@ -44,8 +82,19 @@ pub trait System {
     }
 }
 
+impl<'a> dyn System + 'a {
+    /// 具体的なシステム型への参照に変換
+    pub fn downcast_ref<T: System + 'static>(&self) -> Option<&T> {
+        self.as_any().downcast_ref::<T>()
+    }
+    
+    /// 具体的なシステム型への可変参照に変換
+    pub fn downcast_mut<T: System + 'static>(&mut self) -> Option<&mut T> {
+        self.as_any_mut().downcast_mut::<T>()
+    }
+}
+
 /// システムを管理・実行するためのレジストリ
-#[derive(Default)]
 pub struct SystemRegistry {
     /// 登録されたシステムのマップ（ID -> Box<dyn System>）
     systems: HashMap<SystemId, Box<dyn System>>,
@@ -59,6 +108,35 @@ pub struct SystemRegistry {
     execution_order: HashMap<SystemPhase, Vec<SystemId>>,
     /// 実行順序が変更されたかどうか
     dirty: bool,
+    /// システムごとの所属ステージ
+    system_stages: HashMap<SystemId, Stage>,
+    /// 毎フレーム実行するステージの順序
+    stage_order: Vec<Stage>,
+    /// 決定論的モード（同じ優先度のシステムは名前順で実行する）
+    deterministic: bool,
+    /// 無効化されたシステム名
+    disabled: HashSet<String>,
+    /// システムごとの前回の実行時のティック（リソースの変更検知の基準）
+    last_run_ticks: HashMap<SystemId, u64>,
+}
+
+impl Default for SystemRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for SystemRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut names: Vec<&str> = self.systems.values().map(|system| system.name()).collect();
+        names.sort_unstable();
+        
+        f.debug_struct("SystemRegistry")
+            .field("systems", &names)
+            .field("stage_order", &self.stage_order)
+            .field("disabled", &self.disabled)
+            .finish()
+    }
 }
 
 impl SystemRegistry {
@@ -71,15 +149,26 @@ impl SystemRegistry {
             dependencies: HashMap::new(),
             execution_order: HashMap::new(),
             dirty: false,
+            system_stages: HashMap::new(),
+            stage_order: DEFAULT_STAGE_ORDER.to_vec(),
+            deterministic: false,
+            disabled: HashSet::new(),
+            last_run_ticks: HashMap::new(),
         }
     }
     
-    /// システムを追加し、そのIDを返す
+    /// システムを追加し、そのIDを返す（システム自身のフェーズで登録）
     pub fn add_system(&mut self, system: Box<dyn System>) -> SystemId {
+        let stage = system.phase();
+        self.add_system_to_stage(stage, system)
+    }
+    
+    /// 指定したステージにシステムを追加し、そのIDを返す
+    pub fn add_system_to_stage(&mut self, stage: Stage, system: Box<dyn System>) -> SystemId {
         let id = self.next_id;
         self.next_id += 1;
         
-        let phase = system.phase();
+        let phase = stage;
         let priority = system.priority();
         let dependencies = system.dependencies();
         
@@ -94,6 +183,7 @@ impl SystemRegistry {
         
         // システムを保存
         self.systems.insert(id, system);
+        self.system_stages.insert(id, phase);
         
         // 実行順序を更新する必要があることをマーク
         self.dirty = true;
@@ -101,10 +191,18 @@ impl SystemRegistry {
         id
     }
     
+    /// 関数やクロージャをシステムとして追加し、そのIDを返す
+    /// 
+    /// 関数は`fn(&mut EntityManager, &mut ResourceManager)`の形をとり、
+    /// 実行時にはリソースとして登録されたエンティティマネージャーが渡される
+    pub fn add_fn_system<F: IntoSystem>(&mut self, name: &str, phase: SystemPhase, func: F) -> SystemId {
+        self.add_system(Box::new(func.into_system(name, phase)))
+    }
+    
     /// 指定したIDのシステムを削除
     pub fn remove_system(&mut self, id: SystemId) -> Option<Box<dyn System>> {
         if let Some(system) = self.systems.remove(&id) {
-            let phase = system.phase();
+            let phase = self.system_stages.remove(&id).unwrap_or_else(|| system.phase());
             
             // フェーズリストからも削除
             if let Some(systems) = self.phase_systems.get_mut(&phase) {
@@ -113,8 +211,9 @@ impl SystemRegistry {
                 }
             }
             
-            // 依存関係からも削除
+            // 依存関係と実行時のティックからも削除
             self.dependencies.remove(&id);
+            self.last_run_ticks.remove(&id);
             
             // 他のシステムの依存関係からも削除
             for deps in self.dependencies.values_mut() {
@@ -141,28 +240,58 @@ impl SystemRegistry {
         if let Some(order) = self.execution_order.get(&phase) {
             for &system_id in order {
                 if let Some(system) = self.systems.get_mut(&system_id) {
-                    system.run(resources);
+                    // 無効化されたシステムはスキップ
+                    if self.disabled.contains(system.name()) {
+                        continue;
+                    }
+                    let last_run_tick = self.last_run_ticks.get(&system_id).copied().unwrap_or(0);
+                    let run_tick = resources.run_system(last_run_tick, |resources| system.run(resources));
+                    self.last_run_ticks.insert(system_id, run_tick);
                 }
             }
         }
+        
+        // フェーズ中に予約されたリソース操作を適用
+        resources.apply_deferred();
     }
     
-    /// 全フェーズのシステムを順番に実行
+    /// 全ステージのシステムを`stage_order`の順に実行
     pub fn run_all_phases(&mut self, resources: &mut ResourceManager) {
-        // 各フェーズを順番に実行
         // Startupフェーズは特別扱い（最初の1回だけ）
-        let phases = [
-            SystemPhase::Input,
-            SystemPhase::Update,
-            SystemPhase::Render,
-            SystemPhase::Cleanup,
-        ];
-        
-        for &phase in &phases {
-            self.run_phase(phase, resources);
+        let stages = self.stage_order.clone();
+        
+        for stage in stages {
+            self.run_phase(stage, resources);
         }
     }
     
+    /// 決定論的モードを設定
+    /// 
+    /// 有効にすると、システムは単一スレッドで（優先度, システム名）の固定順序で実行される。
+    /// プラグインの登録順が変わっても同じ順序になる
+    pub fn set_deterministic(&mut self, deterministic: bool) {
+        self.deterministic = deterministic;
+        self.dirty = true;
+    }
+    
+    /// 決定論的モードかどうか
+    pub fn is_deterministic(&self) -> bool {
+        self.deterministic
+    }
+    
+    /// 毎フレーム実行するステージの順序を取得
+    pub fn stage_order(&self) -> &[Stage] {
+        &self.stage_order
+    }
+    
+    /// 毎フレーム実行するステージの順序を設定（Startupは含めない）
+    pub fn set_stage_order(&mut self, order: Vec<Stage>) {
+        self.stage_order = order
+            .into_iter()
+            .filter(|stage| *stage != Stage::Startup)
+            .collect();
+    }
+    
     /// Startupフェーズのみを実行（初期化用）
     pub fn run_startup(&mut self, resources: &mut ResourceManager) {
         self.run_phase(SystemPhase::Startup, resources);
@@ -176,9 +305,16 @@ impl SystemRegistry {
         for (&phase, systems) in &self.phase_systems {
             let mut sorted_systems = Vec::new();
             
-            // 優先度でソート
+            // 優先度でソート（決定論的モードでは同じ優先度を登録順ではなく名前で並べる）
             let mut phase_systems = systems.clone();
-            phase_systems.sort_by_key(|&(_, priority)| priority);
+            if self.deterministic {
+                let name = |id: &SystemId| self.systems.get(id).map_or("", |system| system.name());
+                phase_systems.sort_by(|(a, a_priority), (b, b_priority)| {
+                    a_priority.cmp(b_priority).then_with(|| name(a).cmp(name(b))).then(a.cmp(b))
+                });
+            } else {
+                phase_systems.sort_by_key(|&(_, priority)| priority);
+            }
             
             // 依存関係を考慮したトポロジカルソート
             let mut visited = HashMap::new();
@@ -232,6 +368,30 @@ impl SystemRegistry {
         sorted.push(id);
     }
     
+    /// システムの有効・無効を名前で切り替える
+    /// 
+    /// 無効化したシステムは登録されたまま実行だけがスキップされる。
+    /// 指定した名前のシステムが登録されていればtrueを返す
+    pub fn set_system_active(&mut self, name: &str, active: bool) -> bool {
+        if active {
+            self.disabled.remove(name);
+        } else {
+            self.disabled.insert(name.to_string());
+        }
+        
+        self.has_system(name)
+    }
+    
+    /// 指定した名前のシステムが有効かどうか
+    pub fn is_system_active(&self, name: &str) -> bool {
+        !self.disabled.contains(name)
+    }
+    
+    /// 指定した名前のシステムが登録されているかどうか
+    pub fn has_system(&self, name: &str) -> bool {
+        self.systems.values().any(|system| system.name() == name)
+    }
+    
     /// 登録されているシステムの数を返す
     pub fn len(&self) -> usize {
         self.systems.len()
@@ -247,6 +407,16 @@ impl SystemRegistry {
         self.systems.get(&id).map(|boxed| boxed.as_ref())
     }
     
+    /// 指定したIDのシステムを具体的な型で取得（テストやデバッグツール向け）
+    pub fn get_system_as<T: System + 'static>(&self, id: SystemId) -> Option<&T> {
+        self.get_system(id).and_then(|system| system.downcast_ref::<T>())
+    }
+    
+    /// 指定したIDのシステムを具体的な型の可変参照で取得
+    pub fn get_system_as_mut<T: System + 'static>(&mut self, id: SystemId) -> Option<&mut T> {
+        self.get_system_mut(id).and_then(|system| system.downcast_mut::<T>())
+    }
+    
     /// 指定したIDのシステムを可変参照で取得
     pub fn get_system_mut(&mut self, id: SystemId) -> Option<&mut dyn System> {
         // 安全なバージョン
@@ -334,6 +504,32 @@ mod tests {
         assert_eq!(sys2.run_count, 1);
     }
     
+    #[test]
+    fn test_get_system_as() {
+        let mut registry = SystemRegistry::new();
+        let id = registry.add_system(Box::new(TestSystem::new("System", SystemPhase::Update, 0, vec![])));
+        
+        // 具体的な型で取得・変更できる
+        registry.get_system_as_mut::<TestSystem>(id).unwrap().run_count = 5;
+        assert_eq!(registry.get_system_as::<TestSystem>(id).unwrap().run_count, 5);
+        
+        // 型が違う場合や存在しないIDはNone
+        struct OtherSystem;
+        impl System for OtherSystem {
+            fn name(&self) -> &str {
+                "OtherSystem"
+            }
+            
+            fn phase(&self) -> SystemPhase {
+                SystemPhase::Update
+            }
+            
+            fn run(&mut self, _resources: &mut ResourceManager) {}
+        }
+        assert!(registry.get_system_as::<OtherSystem>(id).is_none());
+        assert!(registry.get_system_as::<TestSystem>(id + 1).is_none());
+    }
+    
     #[test]
     fn test_system_priority() {
         let mut registry = SystemRegistry::new();
@@ -486,4 +682,163 @@ mod tests {
         
         assert!(a_index < b_index, "SystemA should run before SystemB");
     }
-} 
\ No newline at end of file
+    
+    #[test]
+    fn test_stage_order() {
+        let mut registry = SystemRegistry::new();
+        let mut resources = ResourceManager::new();
+        resources.insert(Vec::<&'static str>::new());
+        
+        struct StageSystem(&'static str);
+        
+        impl System for StageSystem {
+            fn name(&self) -> &str {
+                self.0
+            }
+            
+            fn phase(&self) -> SystemPhase {
+                SystemPhase::Update
+            }
+            
+            fn run(&mut self, resources: &mut ResourceManager) {
+                resources.get_mut::<Vec<&'static str>>().unwrap().push(self.0);
+            }
+        }
+        
+        // 登録順とは関係なくステージ順に実行される
+        registry.add_system_to_stage(Stage::PostUpdate, Box::new(StageSystem("post")));
+        registry.add_system(Box::new(StageSystem("update")));
+        let pre_id = registry.add_system_to_stage(Stage::PreUpdate, Box::new(StageSystem("pre")));
+        
+        registry.run_all_phases(&mut resources);
+        assert_eq!(resources.get::<Vec<&'static str>>().unwrap(), &vec!["pre", "update", "post"]);
+        
+        // ステージ指定で追加したシステムも削除できる
+        assert!(registry.remove_system(pre_id).is_some());
+        registry.set_stage_order(vec![Stage::PostUpdate, Stage::Update]);
+        resources.insert(Vec::<&'static str>::new());
+        registry.run_all_phases(&mut resources);
+        assert_eq!(resources.get::<Vec<&'static str>>().unwrap(), &vec!["post", "update"]);
+    }
+    
+    #[test]
+    fn test_set_system_active() {
+        let mut registry = SystemRegistry::new();
+        let mut resources = ResourceManager::new();
+        resources.insert(0usize);
+        
+        registry.add_fn_system("Counter", SystemPhase::Update, |_entities: &mut crate::entities::EntityManager, resources: &mut ResourceManager| {
+            *resources.get_mut::<usize>().unwrap() += 1;
+        });
+        resources.insert(crate::entities::EntityManager::new());
+        
+        // 無効化すると実行されない
+        assert!(registry.set_system_active("Counter", false));
+        assert!(!registry.is_system_active("Counter"));
+        registry.run_phase(SystemPhase::Update, &mut resources);
+        assert_eq!(*resources.get::<usize>().unwrap(), 0);
+        
+        // 再度有効化すると実行される
+        assert!(registry.set_system_active("Counter", true));
+        registry.run_phase(SystemPhase::Update, &mut resources);
+        assert_eq!(*resources.get::<usize>().unwrap(), 1);
+        
+        // 未登録の名前はfalse
+        assert!(!registry.set_system_active("Unknown", false));
+    }
+    
+    #[test]
+    fn test_deterministic_order_ignores_registration_order() {
+        use crate::entities::EntityManager;
+        
+        fn record(name: &'static str) -> impl FnMut(&mut EntityManager, &mut ResourceManager) {
+            move |_entities, resources| resources.get_mut::<Vec<&'static str>>().unwrap().push(name)
+        }
+        
+        let run = |names: &[&'static str], deterministic: bool| {
+            let mut registry = SystemRegistry::new();
+            registry.set_deterministic(deterministic);
+            let mut resources = ResourceManager::new();
+            resources.insert(EntityManager::new());
+            resources.insert(Vec::<&'static str>::new());
+            for &name in names {
+                registry.add_fn_system(name, SystemPhase::Update, record(name));
+            }
+            registry.run_phase(SystemPhase::Update, &mut resources);
+            resources.remove::<Vec<&'static str>>().unwrap()
+        };
+        
+        // 通常は同じ優先度を登録順に実行する
+        assert_eq!(run(&["B", "A"], false), vec!["B", "A"]);
+        
+        // 決定論的モードでは登録順に関係なく名前順になる
+        assert_eq!(run(&["B", "A"], true), vec!["A", "B"]);
+        assert_eq!(run(&["A", "B"], true), vec!["A", "B"]);
+    }
+    
+    #[test]
+    fn test_function_systems() {
+        use crate::entities::EntityManager;
+        
+        let mut registry = SystemRegistry::new();
+        let mut resources = ResourceManager::new();
+        resources.insert(EntityManager::new());
+        resources.insert(0usize);
+        
+        // 関数システム
+        fn spawn_system(entities: &mut EntityManager, _resources: &mut ResourceManager) {
+            entities.create_entity();
+        }
+        
+        registry.add_fn_system("SpawnSystem", SystemPhase::Update, spawn_system);
+        
+        // クロージャシステム（スポーン後に実行）
+        let count_system = (|entities: &mut EntityManager, resources: &mut ResourceManager| {
+            if let Some(count) = resources.get_mut::<usize>() {
+                *count = entities.entity_count();
+            }
+        })
+        .into_system("CountSystem", SystemPhase::Update)
+        .with_priority(10);
+        registry.add_system(Box::new(count_system));
+        
+        registry.run_phase(SystemPhase::Update, &mut resources);
+        registry.run_phase(SystemPhase::Update, &mut resources);
+        
+        // エンティティマネージャーはリソースに戻されている
+        assert_eq!(resources.get::<EntityManager>().unwrap().entity_count(), 2);
+        assert_eq!(*resources.get::<usize>().unwrap(), 2);
+    }
+    
+    #[test]
+    fn test_deferred_resource_insert_applied_after_phase() {
+        let mut registry = SystemRegistry::new();
+        let mut resources = ResourceManager::new();
+        
+        struct RecordingStarted;
+        
+        // 記録開始時にリソースを予約するシステム
+        struct StartRecordingSystem;
+        
+        impl System for StartRecordingSystem {
+            fn name(&self) -> &str {
+                "StartRecordingSystem"
+            }
+            
+            fn phase(&self) -> SystemPhase {
+                SystemPhase::Update
+            }
+            
+            fn run(&mut self, resources: &mut ResourceManager) {
+                resources.batch(|batch| batch.insert_deferred(RecordingStarted));
+            }
+        }
+        
+        registry.add_system(Box::new(StartRecordingSystem));
+        registry.run_phase(SystemPhase::Update, &mut resources);
+        
+        // フェーズ終了後に追加されている
+        assert!(resources.contains::<RecordingStarted>());
+        assert!(!resources.has_deferred());
+    }
+}
\ No newline at end of file
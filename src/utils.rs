@@ -4,6 +4,45 @@
 use wasm_bindgen::JsValue;
 use web_sys::HtmlCanvasElement;
 
+/**
+ * 現在時刻をミリ秒で取得する
+ * 
+ * ブラウザではDate.now()を使い、ネイティブ環境（cargo test等）ではシステム時刻を使う
+ */
+pub fn now_ms() -> f64 {
+    #[cfg(target_arch = "wasm32")]
+    {
+        js_sys::Date::now()
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs_f64() * 1000.0)
+            .unwrap_or(0.0)
+    }
+}
+
+/**
+ * WASMの線形メモリの大きさをバイトで取得する
+ * 
+ * ブラウザでは`wasm_bindgen::memory()`のバッファの大きさを使い、ネイティブ環境では0を返す
+ */
+pub fn wasm_memory_bytes() -> usize {
+    #[cfg(target_arch = "wasm32")]
+    {
+        use wasm_bindgen::JsCast;
+        wasm_bindgen::memory()
+            .dyn_into::<js_sys::WebAssembly::Memory>()
+            .map(|memory| js_sys::ArrayBuffer::from(memory.buffer()).byte_length() as usize)
+            .unwrap_or(0)
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        0
+    }
+}
+
 /**
  * インデックスから行と列の座標を計算する
  */
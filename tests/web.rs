@@ -0,0 +1,188 @@
+/*!
+ * ブラウザで実行する結合テスト
+ * 
+ * キャンバスを作って`init_game`でゲームを開始し、合成したマウス・キーボードのイベントを送って
+ * 公開している関数から状態を確認します。DOMが必要なためネイティブの単体テストでは確認できない部分を扱います。
+ * 
+ * 実行方法: `wasm-pack test --headless --firefox`（または`--chrome`）
+ */
+#![cfg(target_arch = "wasm32")]
+
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+use web_sys::{HtmlCanvasElement, KeyboardEvent, KeyboardEventInit, MouseEvent, MouseEventInit};
+use wasm_multiplayer::{get_board_state, get_game_summary, init_game, stop_game};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// テストで使うキャンバスの大きさ（表示サイズと描画サイズを揃える）
+const CANVAS_SIZE: u32 = 320;
+
+/// 接続できなくても構わないサーバーのURL
+const TEST_SERVER_URL: &str = "ws://127.0.0.1:9";
+
+/**
+ * ホストページが用意する関数を定義する（index.htmlの代わり）
+ */
+fn install_host_functions() {
+    let window = web_sys::window().unwrap();
+    for name in ["updateConnectionStatus", "updatePlayerCount", "updateGameStatus", "announce"] {
+        js_sys::Reflect::set(&window, &name.into(), &js_sys::Function::new_no_args("")).unwrap();
+    }
+    let url = js_sys::Function::new_no_args(&format!("return '{}';", TEST_SERVER_URL));
+    js_sys::Reflect::set(&window, &"getWebSocketUrl".into(), &url).unwrap();
+}
+
+/**
+ * ページにキャンバスを追加する
+ * 
+ * @param id キャンバスのID
+ * @return 追加したキャンバス
+ */
+fn create_canvas(id: &str) -> HtmlCanvasElement {
+    install_host_functions();
+    let document = web_sys::window().unwrap().document().unwrap();
+    let container = document.create_element("div").unwrap();
+    let canvas = document.create_element("canvas").unwrap().dyn_into::<HtmlCanvasElement>().unwrap();
+    canvas.set_id(id);
+    canvas.set_width(CANVAS_SIZE);
+    canvas.set_height(CANVAS_SIZE);
+    canvas.set_attribute("style", &format!("display: block; width: {0}px; height: {0}px;", CANVAS_SIZE)).unwrap();
+    container.append_child(&canvas).unwrap();
+    document.body().unwrap().append_child(&container).unwrap();
+    canvas
+}
+
+/**
+ * 起動オプションを作ってゲームを開始する
+ * 
+ * @param options 起動オプションのJSON
+ * @return インスタンスID
+ */
+fn start(options: serde_json::Value) -> u32 {
+    let options = js_sys::JSON::parse(&options.to_string()).unwrap();
+    init_game(options).unwrap()
+}
+
+/**
+ * キャンバス上の座標でマウスボタンを押す
+ * 
+ * @param canvas 対象のキャンバス
+ * @param x キャンバス内のX座標
+ * @param y キャンバス内のY座標
+ * @param button 押すボタン（0は左、2は右）
+ */
+fn mouse_down(canvas: &HtmlCanvasElement, x: f64, y: f64, button: i16) {
+    let rect = canvas.get_bounding_client_rect();
+    let init = MouseEventInit::new();
+    init.set_bubbles(true);
+    init.set_cancelable(true);
+    init.set_client_x((rect.left() + x).round() as i32);
+    init.set_client_y((rect.top() + y).round() as i32);
+    init.set_button(button);
+    let event = MouseEvent::new_with_mouse_event_init_dict("mousedown", &init).unwrap();
+    canvas.dispatch_event(&event).unwrap();
+}
+
+/**
+ * ウィンドウにキーを押したイベントを送る
+ * 
+ * @param key 押すキー
+ */
+fn key_down(key: &str) {
+    let init = KeyboardEventInit::new();
+    init.set_key(key);
+    init.set_bubbles(true);
+    init.set_cancelable(true);
+    let event = KeyboardEvent::new_with_keyboard_event_init_dict("keydown", &init).unwrap();
+    web_sys::window().unwrap().dispatch_event(&event).unwrap();
+}
+
+/**
+ * オブジェクトのプロパティを取得する
+ */
+fn property(object: &JsValue, key: &str) -> JsValue {
+    js_sys::Reflect::get(object, &key.into()).unwrap()
+}
+
+/**
+ * ボードの状態の配列の合計を取得する
+ */
+fn count_board_array(id: u32, key: &str) -> u32 {
+    let state = get_board_state(id).unwrap();
+    js_sys::Uint8Array::new(&property(&state, key)).to_vec().iter().map(|&value| u32::from(value)).sum()
+}
+
+#[wasm_bindgen_test]
+fn init_game_reports_missing_canvas() {
+    install_host_functions();
+    let options = js_sys::JSON::parse(r#"{ "canvasId": "no-such-canvas" }"#).unwrap();
+    assert!(init_game(options).is_err());
+}
+
+#[wasm_bindgen_test]
+fn compact_game_starts_on_the_board() {
+    create_canvas("compact-start");
+    let id = start(serde_json::json!({ "canvasId": "compact-start", "serverUrl": TEST_SERVER_URL, "compact": true, "seed": 1 }));
+
+    let summary = get_game_summary(id).unwrap();
+    assert_eq!(property(&summary, "in_game"), JsValue::TRUE);
+    let state = get_board_state(id).unwrap();
+    let cells = (property(&state, "width").as_f64().unwrap() * property(&state, "height").as_f64().unwrap()) as u32;
+    assert_eq!(js_sys::Uint8Array::new(&property(&state, "cells")).length(), cells);
+    assert_eq!(count_board_array(id, "revealed"), 0);
+
+    stop_game(id);
+    assert!(get_game_summary(id).is_err());
+}
+
+#[wasm_bindgen_test]
+fn mouse_clicks_flag_and_reveal_cells() {
+    let canvas = create_canvas("compact-mouse");
+    let id = start(serde_json::json!({ "canvasId": "compact-mouse", "serverUrl": TEST_SERVER_URL, "compact": true, "seed": 7 }));
+    let center = CANVAS_SIZE as f64 / 2.0;
+
+    // 右クリックでフラグを立てると、同じセルは左クリックしても開かない
+    mouse_down(&canvas, center, center, 2);
+    assert_eq!(count_board_array(id, "flagged"), 1);
+    mouse_down(&canvas, center, center, 0);
+    assert_eq!(count_board_array(id, "revealed"), 0);
+
+    // フラグを外して開く
+    mouse_down(&canvas, center, center, 2);
+    assert_eq!(count_board_array(id, "flagged"), 0);
+    mouse_down(&canvas, center, center, 0);
+    assert!(count_board_array(id, "revealed") > 0);
+
+    // ボードの外のクリックは何もしない
+    let revealed = count_board_array(id, "revealed");
+    mouse_down(&canvas, 1.0, 1.0, 0);
+    assert_eq!(count_board_array(id, "revealed"), revealed);
+
+    stop_game(id);
+}
+
+#[wasm_bindgen_test]
+fn keyboard_edits_player_name_on_title_screen() {
+    let storage = web_sys::window().unwrap().local_storage().unwrap().unwrap();
+    storage.clear().unwrap();
+    let canvas = create_canvas("title-keyboard");
+    let id = start(serde_json::json!({ "canvasId": "title-keyboard", "serverUrl": TEST_SERVER_URL }));
+    assert_eq!(property(&get_game_summary(id).unwrap(), "in_game"), JsValue::FALSE);
+
+    // 名前入力欄をクリックしてから入力し、Enterで確定すると設定に保存される
+    let center = CANVAS_SIZE as f64 / 2.0;
+    mouse_down(&canvas, center, center, 0);
+    for key in ["B", "o", "b", "Shift", "Enter"] {
+        key_down(key);
+    }
+    let settings = storage.get_item("minesweeper_settings").unwrap().unwrap_or_default();
+    assert!(settings.contains("\"Bob\""), "settings: {}", settings);
+
+    // 停止したゲームはキー入力を受け取らない
+    stop_game(id);
+    key_down("x");
+    let settings = storage.get_item("minesweeper_settings").unwrap().unwrap_or_default();
+    assert!(!settings.contains("Bobx"));
+}
\ No newline at end of file